@@ -3,23 +3,49 @@
 //! Contains methods for:
 //! - Starting the API server (start_api_server)
 //! - Updating API state snapshot (update_api_state)
-//! - Handling API commands (handle_api_commands)
-//! - Screenshot capture (take_screenshot, capture_raw_frame)
+//! - Handling API commands (handle_api_commands), incl. startup script commands
+//! - Remote encode jobs (start_api_encode)
+//! - Remote layer edits (api_add_layer, api_remove_layer, api_set_layer_attrs)
+//! - Screenshot capture (take_screenshot, capture_raw_frame, capture_raw_frame_png)
 
 use super::PlayaApp;
-use crate::server::ApiCommand;
+use crate::server::{ApiCommand, EncodeJobError};
 use playa_engine::core::player_events::*;
+use playa_engine::entities::AttrValue;
+use playa_engine::entities::comp_events::{RemoveLayerEvent, SetLayerAttrsEvent, SetMarkerEvent};
 use playa_engine::entities::frame::{PixelBuffer, TonemapMode};
+use playa_engine::entities::marker::MARKER_COLORS;
 use playa_engine::entities::node::Node;
+use playa_ui::dialogs::encode::{EncodeError, EncodeProgress, EncoderSettings, encode_comp};
+use playa_ui::widgets::ae::EffectAction;
+use playa_ui::widgets::node_editor::GraphExport;
 
 use eframe::egui;
 use image::{ImageBuffer, Rgba};
 use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::thread;
+use std::time::Instant;
+use uuid::Uuid;
 
 /// Full-window JPEG waiters bundled into [`egui::ViewportCommand::Screenshot`].
 #[derive(Clone)]
 pub(crate) struct WindowScreenshotWaiters(pub Vec<crossbeam_channel::Sender<Result<Vec<u8>, String>>>);
 
+/// What a queued screenshot waiter wants (see [`PlayaApp::pending_screenshots`]).
+#[derive(Debug, Clone, Copy)]
+pub enum ScreenshotKind {
+    /// Full native window as JPEG (includes chrome + UI)
+    Window,
+    /// Raw composed frame as JPEG, captured immediately
+    FrameJpeg,
+    /// Raw composed frame as PNG, held until `frame` (or the current frame)
+    /// has finished rendering; dropped after [`FRAME_CAPTURE_TIMEOUT`]
+    ///
+    /// [`FRAME_CAPTURE_TIMEOUT`]: crate::server::FRAME_CAPTURE_TIMEOUT
+    FramePng { frame: Option<i32>, queued: Instant },
+}
+
 impl PlayaApp {
     /// Start REST API server if enabled in settings.
     pub fn start_api_server(&mut self, ctx: &egui::Context) {
@@ -30,6 +56,8 @@ impl PlayaApp {
         // Store egui context for API thread to trigger repaints
         *self.api_state.egui_ctx.write().unwrap() = Some(ctx.clone());
 
+        self.sync_api_token();
+
         let port = self.settings.api_server_port.unwrap_or(9876);
         if self.settings.api_server_enabled {
             log::info!("Starting REST API server on port {}", port);
@@ -38,16 +66,32 @@ impl PlayaApp {
         }
     }
 
+    /// Apply the prefs bearer token to the running server (takes effect immediately).
+    fn sync_api_token(&self) {
+        let token = self.settings.api_server_token.trim();
+        let token = (!token.is_empty()).then_some(token);
+        if self.api_state.auth_token.read().unwrap().as_deref() != token {
+            *self.api_state.auth_token.write().unwrap() = token.map(str::to_string);
+        }
+    }
+
     /// Update API state snapshot for remote clients.
     pub fn update_api_state(&mut self) {
+        self.sync_api_token();
+
+        // SSE streams (`/api/events`) wake only when player/cache actually changed
+        let mut changed = false;
+
         // Update player snapshot
         {
             let mut player = self.api_state.player.write().unwrap();
+            let before = player.clone();
             player.frame = self.player.current_frame(&self.project);
             player.fps = self.player.fps_play();
             player.playing = self.player.is_playing();
             player.loop_enabled = self.player.loop_enabled();
             player.active_comp = self.player.active_comp();
+            changed |= *player != before;
         }
 
         // Update comp snapshot
@@ -63,6 +107,16 @@ impl PlayaApp {
                         duration: c.frame_count(),
                         in_frame: c._in(),
                         out_frame: c._out(),
+                        markers: c
+                            .markers()
+                            .into_iter()
+                            .map(|m| crate::server::MarkerSnapshot {
+                                frame: m.frame,
+                                label: m.label,
+                                color: Some(m.color),
+                            })
+                            .collect(),
+                        warnings: c.warnings.clone(),
                     })
             });
         }
@@ -70,24 +124,29 @@ impl PlayaApp {
         // Update cache snapshot
         {
             let mut cache = self.api_state.cache.write().unwrap();
+            let before = cache.clone();
             let (used, limit) = self.cache_manager.mem();
             cache.memory_used_mb = used as f32 / (1024.0 * 1024.0);
             cache.memory_limit_mb = limit as f32 / (1024.0 * 1024.0);
+            cache.max_preload_radius = self.settings.cache.max_preload_radius;
+            changed |= *cache != before;
+        }
+
+        if changed {
+            self.api_state.notify_changed();
         }
     }
 
     /// Handle commands from REST API.
     pub fn handle_api_commands(&mut self) {
-        // Collect all pending commands first (avoids borrow issues)
-        let commands: Vec<ApiCommand> = if let Some(ref rx) = self.api_command_rx {
-            let mut cmds = Vec::new();
+        // Collect all pending commands first (avoids borrow issues);
+        // startup script commands run ahead of remote ones
+        let mut commands: Vec<ApiCommand> = std::mem::take(&mut self.script_commands);
+        if let Some(ref rx) = self.api_command_rx {
             while let Ok(cmd) = rx.try_recv() {
-                cmds.push(cmd);
+                commands.push(cmd);
             }
-            cmds
-        } else {
-            return;
-        };
+        }
 
         // Process collected commands
         for cmd in commands {
@@ -119,6 +178,10 @@ impl PlayaApp {
                 ApiCommand::ToggleLoop => {
                     self.event_bus.emit(ToggleLoopEvent);
                 }
+                ApiCommand::SetMaxPreloadRadius(radius) => {
+                    // Applied to the project on the next frame (run loop)
+                    self.settings.cache.max_preload_radius = radius;
+                }
                 ApiCommand::LoadSequence(path) => {
                     let _ = self.load_sequences(vec![std::path::PathBuf::from(path)]);
                 }
@@ -140,12 +203,106 @@ impl PlayaApp {
                         }
                     }
                 }
+                ApiCommand::Encode {
+                    comp,
+                    fps,
+                    settings,
+                    response,
+                } => {
+                    let result = self.start_api_encode(comp, fps, *settings);
+                    if let Err((_, e)) = &result {
+                        log::warn!("Encode not started: {}", e);
+                    }
+                    let _ = response.send(result);
+                }
+                ApiCommand::AddEffect { effect, layer } => {
+                    let target = self.player.active_comp().and_then(|comp_uuid| {
+                        self.project
+                            .with_comp(comp_uuid, |c| {
+                                c.get_children()
+                                    .get(layer)
+                                    .map(|(child, _)| (comp_uuid, *child))
+                            })
+                            .flatten()
+                    });
+                    match target {
+                        Some((comp_uuid, layer_uuid)) => self.handle_effect_actions(
+                            comp_uuid,
+                            layer_uuid,
+                            vec![EffectAction::Add(effect)],
+                        ),
+                        None => log::warn!("add-effect: active comp has no layer {}", layer),
+                    }
+                }
+                ApiCommand::AddLayer {
+                    comp,
+                    source,
+                    frame,
+                    index,
+                    response,
+                } => {
+                    let _ = response.send(self.api_add_layer(comp, source, frame, index));
+                }
+                ApiCommand::RemoveLayer {
+                    comp,
+                    layer,
+                    response,
+                } => {
+                    let _ = response.send(self.api_remove_layer(comp, layer));
+                }
+                ApiCommand::SetLayerAttrs {
+                    comp,
+                    layer,
+                    attrs,
+                    response,
+                } => {
+                    let _ = response.send(self.api_set_layer_attrs(comp, layer, attrs));
+                }
+                ApiCommand::CacheMap { comp, response } => {
+                    let map = self.project.with_comp(comp, |c| {
+                        let statuses = c
+                            .cache_frame_statuses(self.project.global_cache.as_ref())
+                            .unwrap_or_default();
+                        (c._in(), statuses)
+                    });
+                    let _ = response.send(map.ok_or((404, format!("Comp {} not found", comp))));
+                }
+                ApiCommand::Graph { dot, response } => {
+                    // Built from the project; the node editor's view is left alone
+                    let graph = self
+                        .player
+                        .active_comp()
+                        .map(|uuid| GraphExport::build(&self.project, uuid));
+                    let text = graph.and_then(|g| if dot { Some(g.to_dot()) } else { g.to_json() });
+                    let _ = response.send(text.ok_or_else(|| "No active comp".to_string()));
+                }
                 ApiCommand::Screenshot {
                     viewport_only,
                     response,
                 } => {
                     self.take_screenshot(viewport_only, response);
                 }
+                ApiCommand::FramePng { frame, response } => {
+                    // Seek is applied next tick; the waiter holds until it renders
+                    let frame = frame.map(|f| {
+                        let clamped = self
+                            .player
+                            .active_comp()
+                            .and_then(|uuid| {
+                                self.project.with_comp(uuid, |c| f.clamp(c._in(), c._out()))
+                            })
+                            .unwrap_or(f);
+                        self.event_bus.emit(SetFrameEvent(clamped));
+                        clamped
+                    });
+                    self.pending_screenshots.push((
+                        ScreenshotKind::FramePng {
+                            frame,
+                            queued: Instant::now(),
+                        },
+                        response,
+                    ));
+                }
                 ApiCommand::Exit => {
                     log::info!("Exit command received via REST API");
                     self.exit_requested = true;
@@ -156,10 +313,181 @@ impl PlayaApp {
                 ApiCommand::PrevFrame => {
                     self.event_bus.emit(StepBackwardEvent);
                 }
+                ApiCommand::SetMarker {
+                    frame,
+                    label,
+                    color,
+                } => {
+                    if let Some(comp_uuid) = self.player.active_comp() {
+                        self.event_bus.emit(SetMarkerEvent {
+                            comp_uuid,
+                            frame,
+                            label,
+                            color: color.unwrap_or(MARKER_COLORS[0]),
+                        });
+                    }
+                }
             }
         }
     }
 
+    /// Index of `layer` in `comp`, or the 404 to answer with.
+    fn api_layer_index(&self, comp: Uuid, layer: Uuid) -> Result<usize, (u16, String)> {
+        self.project
+            .with_comp(comp, |c| {
+                c.get_children()
+                    .iter()
+                    .position(|(child, _)| *child == layer)
+            })
+            .ok_or((404, format!("Comp {} not found", comp)))?
+            .ok_or((404, format!("Layer {} not found in comp {}", layer, comp)))
+    }
+
+    /// Add a layer through the same path as `AddLayerEvent`, but synchronously
+    /// so the new layer UUID can be returned.
+    fn api_add_layer(
+        &mut self,
+        comp: Uuid,
+        source: Uuid,
+        frame: Option<i32>,
+        index: Option<usize>,
+    ) -> Result<Uuid, (u16, String)> {
+        let start = self
+            .project
+            .with_comp(comp, |c| c._in())
+            .ok_or((404, format!("Comp {} not found", comp)))?;
+        if self.project.with_node(source, |_| ()).is_none() {
+            return Err((404, format!("Source {} not found", source)));
+        }
+        let layer = self
+            .project
+            .add_layer(comp, source, frame.unwrap_or(start), index)
+            .map_err(|e| (400, format!("Cannot add layer: {}", e)))?;
+        self.node_editor_state.mark_dirty();
+        Ok(layer)
+    }
+
+    fn api_remove_layer(&mut self, comp: Uuid, layer: Uuid) -> Result<(), (u16, String)> {
+        let layer_idx = self.api_layer_index(comp, layer)?;
+        self.event_bus.emit(RemoveLayerEvent {
+            comp_uuid: comp,
+            layer_idx,
+        });
+        Ok(())
+    }
+
+    fn api_set_layer_attrs(
+        &mut self,
+        comp: Uuid,
+        layer: Uuid,
+        attrs: Vec<(String, AttrValue)>,
+    ) -> Result<(), (u16, String)> {
+        self.api_layer_index(comp, layer)?;
+        let attrs = attrs
+            .into_iter()
+            .filter_map(|(key, value)| serde_json::to_value(value).ok().map(|v| (key, v)))
+            .collect();
+        self.event_bus.emit(SetLayerAttrsEvent {
+            comp_uuid: comp,
+            layer_uuids: vec![layer],
+            attrs,
+        });
+        Ok(())
+    }
+
+    /// Start a remote encode on a worker thread, tracked in the API job table.
+    /// Only one runs at a time; errors carry the HTTP status to answer with.
+    fn start_api_encode(
+        &mut self,
+        comp: Option<Uuid>,
+        fps: Option<f32>,
+        mut settings: EncoderSettings,
+    ) -> Result<u64, (u16, String)> {
+        if self.api_state.encode_running() {
+            return Err((409, "An encode is already running".to_string()));
+        }
+        let comp_uuid = comp
+            .or(self.player.active_comp())
+            .ok_or((404, "No active comp".to_string()))?;
+        let comp_node = self
+            .project
+            .with_comp(comp_uuid, |c| c.clone())
+            .ok_or((404, format!("Comp {} not found", comp_uuid)))?;
+        settings.fps = fps.unwrap_or(comp_node.fps());
+
+        let cancel = Arc::new(AtomicBool::new(false));
+        let id = self.api_state.add_encode_job(
+            comp_uuid,
+            settings.output_path.clone(),
+            Arc::clone(&cancel),
+        );
+        log::info!(
+            "API encode #{}: {} -> {}",
+            id,
+            comp_node.name(),
+            settings.output_path.display()
+        );
+
+        let project = self.project.clone();
+        let state = Arc::clone(&self.api_state);
+        let spawned = thread::Builder::new()
+            .name("playa-api-encode".to_string())
+            .spawn(move || {
+                // Progress pump: mirrors EncodeProgress into the job table
+                let (tx, rx) = std::sync::mpsc::channel::<EncodeProgress>();
+                let pump_state = Arc::clone(&state);
+                let pump = thread::spawn(move || {
+                    for p in rx {
+                        pump_state.update_encode_job(id, |job| {
+                            job.stage = p.stage.name().to_string();
+                            job.current_frame = p.current_frame;
+                            job.total_frames = p.total_frames;
+                            job.progress = if p.total_frames > 0 {
+                                p.current_frame as f32 / p.total_frames as f32
+                            } else {
+                                0.0
+                            };
+                        });
+                    }
+                });
+
+                let result = encode_comp(&comp_node, &project, &settings, tx, cancel);
+                // Pump exits once `tx` is dropped; join so its last update can't
+                // overwrite the final state below
+                let _ = pump.join();
+
+                state.update_encode_job(id, |job| match result {
+                    Ok(()) => {
+                        job.stage = "Complete".to_string();
+                        job.progress = 1.0;
+                    }
+                    Err(e) => {
+                        job.stage = match e {
+                            EncodeError::Cancelled => "Cancelled",
+                            _ => "Error",
+                        }
+                        .to_string();
+                        job.error = Some(EncodeJobError {
+                            kind: e.kind().to_string(),
+                            message: e.to_string(),
+                        });
+                    }
+                });
+                log::info!("API encode #{} finished", id);
+            });
+        if let Err(e) = spawned {
+            self.api_state.update_encode_job(id, |job| {
+                job.stage = "Error".to_string();
+                job.error = Some(EncodeJobError {
+                    kind: "OutputCreateFailed".to_string(),
+                    message: format!("Failed to spawn encode thread: {}", e),
+                });
+            });
+            return Err((500, "Failed to spawn encode thread".to_string()));
+        }
+        Ok(id)
+    }
+
     /// Queue screenshot request.
     /// viewport_only=true: full native window JPEG (wgpu → egui screenshot path; includes chrome + UI)
     /// viewport_only=false: raw frame data only (no UI)
@@ -169,7 +497,12 @@ impl PlayaApp {
         viewport_only: bool,
         response: crossbeam_channel::Sender<Result<Vec<u8>, String>>,
     ) {
-        self.pending_screenshots.push((viewport_only, response));
+        let kind = if viewport_only {
+            ScreenshotKind::Window
+        } else {
+            ScreenshotKind::FrameJpeg
+        };
+        self.pending_screenshots.push((kind, response));
         log::trace!(
             "Screenshot request queued ({} waiting), viewport_only={}",
             self.pending_screenshots.len(),
@@ -177,8 +510,8 @@ impl PlayaApp {
         );
     }
 
-    /// Capture raw frame data (no GL, immediate).
-    pub fn capture_raw_frame(&self) -> Result<Vec<u8>, String> {
+    /// Raw frame as RGBA8 (HDR tonemapped with ACES).
+    fn raw_frame_rgba(&self) -> Result<ImageBuffer<Rgba<u8>, Vec<u8>>, String> {
         let frame = match &self.frame {
             Some(f) => f,
             None => return Err("No frame loaded".to_string()),
@@ -201,9 +534,14 @@ impl PlayaApp {
             }
         };
 
-        let img: ImageBuffer<Rgba<u8>, Vec<u8>> =
-            ImageBuffer::from_raw(width as u32, height as u32, rgba_data)
-                .ok_or_else(|| "Failed to create image buffer".to_string())?;
+        ImageBuffer::from_raw(width as u32, height as u32, rgba_data)
+            .ok_or_else(|| "Failed to create image buffer".to_string())
+    }
+
+    /// Capture raw frame data (no GL, immediate).
+    pub fn capture_raw_frame(&self) -> Result<Vec<u8>, String> {
+        let img = self.raw_frame_rgba()?;
+        let (width, height) = img.dimensions();
 
         // JPEG is much faster than PNG
         let mut jpeg_bytes: Vec<u8> = Vec::new();
@@ -228,6 +566,25 @@ impl PlayaApp {
         Ok(jpeg_bytes)
     }
 
+    /// Capture raw frame as lossless PNG (keeps alpha).
+    pub fn capture_raw_frame_png(&self) -> Result<Vec<u8>, String> {
+        let img = self.raw_frame_rgba()?;
+        let mut png_bytes: Vec<u8> = Vec::new();
+        img.write_to(
+            &mut std::io::Cursor::new(&mut png_bytes),
+            image::ImageFormat::Png,
+        )
+        .map_err(|e| format!("PNG encoding failed: {}", e))?;
+
+        log::info!(
+            "Raw frame PNG: {}x{}, {} bytes",
+            img.width(),
+            img.height(),
+            png_bytes.len()
+        );
+        Ok(png_bytes)
+    }
+
     /// Dispatches JPEG to waiters recorded in [`WindowScreenshotWaiters`] user payloads.
     pub fn consume_egui_screenshots(&mut self, ctx: &egui::Context) {
         let shots: Vec<_> = ctx.input(|i| {
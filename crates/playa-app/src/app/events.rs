@@ -7,17 +7,21 @@
 //! - Focus detection (determine_focused_window)
 
 use super::PlayaApp;
+use super::history;
 use crate::main_events::{self, AppEventContext};
 use playa_engine::core::event_bus::downcast_event;
+use playa_engine::core::player_events::{PlayNextCompEvent, PreloadNextCompEvent};
 use playa_engine::entities::comp_events::*;
 use playa_engine::entities::node::Node;
-use playa_ui::dialogs::prefs::prefs_events::HotkeyWindow;
+use playa_ui::dialogs::prefs::prefs_events::{
+    ExportHotkeysEvent, HotkeyWindow, ImportHotkeysEvent, ResetHotkeysEvent,
+};
 use playa_ui::widgets::ae::EffectAction;
-use playa_ui::widgets::project::project_events::ClearCacheEvent;
+use playa_ui::widgets::project::project_events::{ClearCacheEvent, RedoEvent, UndoEvent};
 use playa_ui::widgets::viewport::ViewportRefreshEvent;
 
 use eframe::egui;
-use log::{info, trace};
+use log::{error, info, trace, warn};
 use uuid::Uuid;
 
 impl PlayaApp {
@@ -26,15 +30,20 @@ impl PlayaApp {
         // Deferred actions to execute after event loop
         let mut deferred_load_project: Option<std::path::PathBuf> = None;
         let mut deferred_save_project: Option<std::path::PathBuf> = None;
+        let mut deferred_load_playlist: Option<std::path::PathBuf> = None;
+        let mut deferred_save_playlist: Option<std::path::PathBuf> = None;
         let mut deferred_load_sequences: Option<Vec<std::path::PathBuf>> = None;
         let mut deferred_new_comp: Option<(String, f32)> = None;
         let mut deferred_new_camera: Option<String> = None;
         let mut deferred_new_text: Option<(String, String)> = None;
+        let mut deferred_new_solid: Option<(String, bool)> = None;
         let mut deferred_enqueue_frames = false;
         let mut deferred_quick_save = false;
         let mut deferred_show_open = false;
+        let mut deferred_open_recent = false;
         let mut deferred_generate_ainode: Option<uuid::Uuid> = None;
         let mut deferred_iterate_generation: Option<(uuid::Uuid, uuid::Uuid)> = None;
+        let mut deferred_invalidate: Vec<uuid::Uuid> = Vec::new();
 
         // Poll all events from the bus
         let events = self.event_bus.poll();
@@ -45,9 +54,20 @@ impl PlayaApp {
                     "Comp {} frame changed: {} → {}",
                     e.comp_uuid, e.old_frame, e.new_frame
                 );
+                self.last_frame_change = Some(std::time::Instant::now());
+                self.sync_proxy_level();
                 self.enqueue_frame_loads_around_playhead(self.settings.playback.preload_radius);
                 continue;
             }
+            // Sequence playback (player asks for the next comp)
+            if let Some(e) = downcast_event::<PreloadNextCompEvent>(&event) {
+                self.preload_next_comp(e.0);
+                continue;
+            }
+            if let Some(e) = downcast_event::<PlayNextCompEvent>(&event) {
+                self.play_next_comp(e.0);
+                continue;
+            }
             if let Some(e) = downcast_event::<LayersChangedEvent>(&event) {
                 trace!(
                     "Comp {} layers changed (range: {:?})",
@@ -90,6 +110,7 @@ impl PlayaApp {
                 if let Some(ref cache) = self.project.global_cache {
                     cache.clear_all();
                 }
+                playa_engine::entities::effects::cache::clear();
                 self.event_bus.emit(ViewportRefreshEvent);
                 continue;
             }
@@ -131,6 +152,34 @@ impl PlayaApp {
                 self.rename_layout(&evt.0, &evt.1);
                 continue;
             }
+            // Hotkey import/export (Settings > Hotkeys)
+            if let Some(evt) = downcast_event::<ExportHotkeysEvent>(&event) {
+                self.export_hotkeys(&evt.0);
+                continue;
+            }
+            if let Some(evt) = downcast_event::<ImportHotkeysEvent>(&event) {
+                self.import_hotkeys(&evt.0);
+                continue;
+            }
+            if downcast_event::<ResetHotkeysEvent>(&event).is_some() {
+                self.hotkey_handler.reset_to_defaults();
+                self.settings.custom_hotkeys.clear();
+                info!("Hotkeys reset to defaults");
+                continue;
+            }
+            // Undo / redo (Ctrl+Z / Ctrl+Shift+Z)
+            if downcast_event::<UndoEvent>(&event).is_some() {
+                self.undo();
+                continue;
+            }
+            if downcast_event::<RedoEvent>(&event).is_some() {
+                self.redo();
+                continue;
+            }
+            // Snapshot the nodes an editing event is about to change; it
+            // becomes an undo step below if the handler changed them
+            let pending = history::undo_scope(&event, self.player.active_comp())
+                .map(|(label, uuids)| self.history.begin(&self.project, label, &uuids));
             // === App events - delegate to main_events module ===
             // log::trace!("[HANDLE] checking event type_id={:?}", (*event).type_id());
             if let Some(result) = main_events::handle_app_event(
@@ -148,6 +197,7 @@ impl PlayaApp {
                     show_encode_dialog: &mut self.show_encode_dialog,
                     show_attributes_editor: &mut self.show_attributes_editor,
                     encode_dialog: &mut self.encode_dialog,
+                    goto_frame: &mut self.goto_frame,
                     is_fullscreen: &mut self.is_fullscreen,
                     fullscreen_dirty: &mut self.fullscreen_dirty,
                     reset_settings_pending: &mut self.reset_settings_pending,
@@ -161,6 +211,12 @@ impl PlayaApp {
                 if let Some(path) = result.save_project {
                     deferred_save_project = Some(path);
                 }
+                if let Some(path) = result.load_playlist {
+                    deferred_load_playlist = Some(path);
+                }
+                if let Some(path) = result.save_playlist {
+                    deferred_save_playlist = Some(path);
+                }
                 if let Some(paths) = result.load_sequences {
                     deferred_load_sequences
                         .get_or_insert_with(Vec::new)
@@ -175,6 +231,9 @@ impl PlayaApp {
                 if let Some(text_data) = result.new_text {
                     deferred_new_text = Some(text_data);
                 }
+                if let Some(solid) = result.new_solid {
+                    deferred_new_solid = Some(solid);
+                }
                 deferred_enqueue_frames |= result.enqueue_frames;
                 if result.quick_save {
                     deferred_quick_save = true;
@@ -182,17 +241,22 @@ impl PlayaApp {
                 if result.show_open_dialog {
                     deferred_show_open = true;
                 }
+                deferred_open_recent |= result.open_most_recent;
                 if let Some(uuid) = result.generate_ainode {
                     deferred_generate_ainode = Some(uuid);
                 }
                 if let Some(pair) = result.iterate_generation {
                     deferred_iterate_generation = Some(pair);
                 }
+                deferred_invalidate.extend(result.invalidate_nodes);
                 // Update AE panel focus (immediate, not deferred)
                 if let Some(focus) = result.ae_focus_update {
                     self.ae_focus = focus;
                 }
             }
+            if let Some(pending) = pending {
+                self.history.commit(&self.project, pending);
+            }
         }
 
         // === DERIVED EVENTS LOOP - DO NOT REMOVE! ===
@@ -242,6 +306,12 @@ impl PlayaApp {
         if let Some(path) = deferred_save_project {
             self.save_project(path);
         }
+        if let Some(path) = deferred_load_playlist {
+            let _ = self.load_playlist(path);
+        }
+        if let Some(path) = deferred_save_playlist {
+            self.save_playlist(path);
+        }
         if let Some(paths) = deferred_load_sequences {
             let _ = self.load_sequences(paths);
         }
@@ -267,6 +337,24 @@ impl PlayaApp {
             self.project.add_node(text_node.into());
             info!("Created new text: {}", uuid);
         }
+        if let Some((name, matte)) = deferred_new_solid {
+            use playa_engine::entities::SolidNode;
+            let solid = if matte {
+                // Mattes must match the comp they mask (track matte skips
+                // mismatched sizes)
+                let dim = self
+                    .player
+                    .active_comp()
+                    .and_then(|uuid| self.project.with_comp(uuid, |c| c.dim()))
+                    .unwrap_or(playa_engine::defaults::DEFAULT_DIM);
+                SolidNode::new_matte(&name, dim)
+            } else {
+                SolidNode::new(&name)
+            };
+            let uuid = solid.uuid();
+            self.project.add_node(solid.into());
+            info!("Created new solid: {}", uuid);
+        }
         if deferred_enqueue_frames {
             self.enqueue_frame_loads_around_playhead(self.settings.playback.preload_radius);
         }
@@ -276,6 +364,14 @@ impl PlayaApp {
         if deferred_show_open {
             self.show_open_project_dialog();
         }
+        if deferred_open_recent {
+            self.open_most_recent();
+        }
+        if !deferred_invalidate.is_empty() {
+            deferred_invalidate.sort();
+            deferred_invalidate.dedup();
+            self.invalidate_and_refresh(&deferred_invalidate);
+        }
         #[cfg(feature = "jobs")]
         if let Some(uuid) = deferred_generate_ainode {
             self.generate_ainode(uuid);
@@ -551,6 +647,10 @@ impl PlayaApp {
         self.debounced_preloader.schedule(comp_uuid);
         // 4. Request viewport refresh
         self.event_bus.emit(ViewportRefreshEvent);
+        // 5. Live fps edits retime playback without touching loaded frames
+        if self.player.active_comp() == Some(comp_uuid) {
+            self.player.sync_fps(&self.project);
+        }
     }
 
     /// Determine which window/panel currently has focus for hotkey routing.
@@ -705,6 +805,43 @@ impl PlayaApp {
         }
     }
 
+    /// Write current hotkey bindings to `path` as JSON.
+    fn export_hotkeys(&mut self, path: &std::path::Path) {
+        match std::fs::write(path, self.hotkey_handler.export_bindings()) {
+            Ok(()) => info!("Exported hotkeys to {}", path.display()),
+            Err(e) => {
+                let msg = format!("Failed to export hotkeys to {}: {}", path.display(), e);
+                error!("{}", msg);
+                self.error_msg = Some(msg);
+            }
+        }
+    }
+
+    /// Replace hotkey bindings from an exported file and remember them in settings.
+    fn import_hotkeys(&mut self, path: &std::path::Path) {
+        let imported = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+            .and_then(|json| self.hotkey_handler.import_bindings(&json));
+        match imported {
+            Ok(warnings) => {
+                for warning in &warnings {
+                    warn!("Hotkey import: {}", warning);
+                }
+                // Store the normalized set (unknown actions already dropped)
+                self.settings.custom_hotkeys = self.hotkey_handler.export_bindings();
+                info!(
+                    "Imported hotkeys from {} ({} warning(s))",
+                    path.display(),
+                    warnings.len()
+                );
+            }
+            Err(e) => {
+                error!("{}", e);
+                self.error_msg = Some(e);
+            }
+        }
+    }
+
     /// Handle keyboard input and hotkeys.
     pub fn handle_keyboard_input(&mut self, ctx: &egui::Context) {
         // Don't process hotkeys when text input is active (typing in fields)
@@ -724,7 +861,8 @@ impl PlayaApp {
             use playa_engine::entities::comp_events::{
                 AlignLayersEndEvent, AlignLayersStartEvent, ClearLayerSelectionEvent,
                 CopyLayersEvent, DuplicateLayersEvent, PasteLayersEvent, ResetTrimsEvent,
-                SelectAllLayersEvent, TrimLayersEndEvent, TrimLayersStartEvent,
+                RippleTrimLayersEndEvent, SelectAllLayersEvent, SplitLayersEvent,
+                TrimLayersEndEvent, TrimLayersStartEvent,
             };
 
             // Fill comp_uuid for timeline-specific events
@@ -746,6 +884,11 @@ impl PlayaApp {
                     self.event_bus.emit(TrimLayersEndEvent(active_comp_uuid));
                     return;
                 }
+                if downcast_event::<RippleTrimLayersEndEvent>(&event).is_some() {
+                    self.event_bus
+                        .emit(RippleTrimLayersEndEvent(active_comp_uuid));
+                    return;
+                }
                 // Layer clipboard operations
                 if downcast_event::<DuplicateLayersEvent>(&event).is_some() {
                     log::trace!("Hotkey: Ctrl-D -> DuplicateLayersEvent");
@@ -754,6 +897,13 @@ impl PlayaApp {
                     });
                     return;
                 }
+                if downcast_event::<SplitLayersEvent>(&event).is_some() {
+                    log::trace!("Hotkey: Ctrl-Shift-D -> SplitLayersEvent");
+                    self.event_bus.emit(SplitLayersEvent {
+                        comp_uuid: active_comp_uuid,
+                    });
+                    return;
+                }
                 if downcast_event::<CopyLayersEvent>(&event).is_some() {
                     log::trace!("Hotkey: Ctrl-C -> CopyLayersEvent");
                     self.event_bus.emit(CopyLayersEvent {
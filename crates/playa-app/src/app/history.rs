@@ -0,0 +1,368 @@
+//! Undo / redo for timeline and attribute edits.
+//!
+//! A step holds node snapshots rather than hand-written inverse commands.
+//! Project nodes live behind `Arc` and every edit goes through copy-on-write
+//! (`Project::modify_comp` / `modify_node` use `Arc::make_mut`), so keeping the
+//! pre-edit `Arc` is the inverse of any edit to that node - move / trim / add /
+//! remove layer, attr sets - for the price of a refcount. An edit becomes a
+//! step only if it actually changed one of the snapshotted nodes.
+//!
+//! Undo swaps the snapshot back in and keeps what it replaced for redo. Rapid
+//! repeats of one edit on the same nodes (timeline drags, gizmo drags, slider
+//! scrubs) coalesce into a single step. The playhead is not part of a step.
+//! History holds [`HISTORY_DEPTH`] steps and is cleared on project load.
+
+use std::collections::VecDeque;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use log::info;
+use playa_engine::core::event_bus::{BoxedEvent, downcast_event};
+use playa_engine::entities::comp_events::*;
+use playa_engine::entities::node::Node;
+use playa_engine::entities::{NodeKind, Project};
+use uuid::Uuid;
+
+use super::PlayaApp;
+
+/// Steps kept; the oldest drops off once full
+pub const HISTORY_DEPTH: usize = 100;
+/// The same edit on the same nodes within this window extends the last step
+const COALESCE_WINDOW: Duration = Duration::from_millis(500);
+
+/// Nodes as they were before (undo stack) or after (redo stack) an edit
+struct Step {
+    label: &'static str,
+    nodes: Vec<(Uuid, Arc<NodeKind>)>,
+    /// Last time an edit extended this step
+    at: Instant,
+}
+
+impl Step {
+    fn same_nodes(&self, other: &Step) -> bool {
+        self.nodes.len() == other.nodes.len()
+            && self
+                .nodes
+                .iter()
+                .zip(&other.nodes)
+                .all(|((a, _), (b, _))| a == b)
+    }
+}
+
+/// Snapshot taken by [`History::begin`], turned into a step by
+/// [`History::commit`]
+pub struct PendingStep(Step);
+
+/// Undo / redo stacks (runtime only)
+#[derive(Default)]
+pub struct History {
+    undo: VecDeque<Step>,
+    redo: Vec<Step>,
+}
+
+impl History {
+    /// Snapshot `uuids` before an edit named `label`
+    pub fn begin(&self, project: &Project, label: &'static str, uuids: &[Uuid]) -> PendingStep {
+        let media = project.media.read().expect("media lock poisoned");
+        let nodes = uuids
+            .iter()
+            .filter_map(|uuid| media.get(uuid).map(|node| (*uuid, Arc::clone(node))))
+            .collect();
+        PendingStep(Step {
+            label,
+            nodes,
+            at: Instant::now(),
+        })
+    }
+
+    /// Record `pending` if the edit changed any of its nodes. Returns whether
+    /// a step was pushed or extended.
+    pub fn commit(&mut self, project: &Project, pending: PendingStep) -> bool {
+        let step = pending.0;
+        if !changed_since(project, &step) {
+            return false;
+        }
+        self.redo.clear();
+        if let Some(last) = self.undo.back_mut()
+            && last.label == step.label
+            && last.same_nodes(&step)
+            && step.at.duration_since(last.at) < COALESCE_WINDOW
+        {
+            // Keep the older snapshot: the whole drag undoes at once
+            last.at = step.at;
+            return true;
+        }
+        self.undo.push_back(step);
+        if self.undo.len() > HISTORY_DEPTH {
+            self.undo.pop_front();
+        }
+        true
+    }
+
+    /// Revert the last step. Returns its label and the nodes it touched.
+    pub fn undo(&mut self, project: &Project) -> Option<(&'static str, Vec<Uuid>)> {
+        let step = self.undo.pop_back()?;
+        let replaced = restore(project, step);
+        let touched = (
+            replaced.label,
+            replaced.nodes.iter().map(|(u, _)| *u).collect(),
+        );
+        self.redo.push(replaced);
+        Some(touched)
+    }
+
+    /// Re-apply the last undone step. Returns its label and the nodes it touched.
+    pub fn redo(&mut self, project: &Project) -> Option<(&'static str, Vec<Uuid>)> {
+        let step = self.redo.pop()?;
+        let replaced = restore(project, step);
+        let touched = (
+            replaced.label,
+            replaced.nodes.iter().map(|(u, _)| *u).collect(),
+        );
+        self.undo.push_back(replaced);
+        Some(touched)
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+/// Whether any snapshotted node differs from the live one. Edits replace the
+/// `Arc` even when they end up a no-op, so equal pointers settle it fast and
+/// different ones are compared by [`edit_hash`].
+fn changed_since(project: &Project, step: &Step) -> bool {
+    let media = project.media.read().expect("media lock poisoned");
+    step.nodes.iter().any(|(uuid, before)| {
+        media
+            .get(uuid)
+            .is_some_and(|now| !Arc::ptr_eq(now, before) && edit_hash(now) != edit_hash(before))
+    })
+}
+
+/// Hash of what an undoable edit can change: node attrs and, for comps, each
+/// layer's attrs and effects. Cheap enough to run on every drag event.
+fn edit_hash(node: &NodeKind) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    node.attrs().hash_all().hash(&mut hasher);
+    if let Some(comp) = node.as_comp() {
+        for layer in &comp.layers {
+            layer.attrs.hash_all().hash(&mut hasher);
+            for fx in &layer.effects {
+                fx.uuid.hash(&mut hasher);
+                fx.enabled.hash(&mut hasher);
+                fx.attrs.hash_all().hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Swap `step`'s snapshots into the project, returning what they replaced.
+/// Nodes deleted since are skipped; comps keep the live playhead.
+fn restore(project: &Project, step: Step) -> Step {
+    let mut media = project.media.write().expect("media lock poisoned");
+    let mut replaced = Vec::with_capacity(step.nodes.len());
+    for (uuid, mut snapshot) in step.nodes {
+        let Some(live) = media.get(&uuid).cloned() else {
+            continue;
+        };
+        if let (Some(live_comp), Some(comp)) = (live.as_comp(), snapshot.as_comp())
+            && live_comp.frame() != comp.frame()
+        {
+            let frame = live_comp.frame();
+            if let Some(comp) = Arc::make_mut(&mut snapshot).as_comp_mut() {
+                comp.set_frame(frame);
+            }
+        }
+        media.insert(uuid, snapshot);
+        replaced.push((uuid, live));
+    }
+    Step {
+        label: step.label,
+        nodes: replaced,
+        at: Instant::now(),
+    }
+}
+
+/// Undo label and target nodes of an event that edits the project; `None`
+/// for everything else (playback, selection, view).
+pub fn undo_scope(
+    event: &BoxedEvent,
+    active_comp: Option<Uuid>,
+) -> Option<(&'static str, Vec<Uuid>)> {
+    macro_rules! comp_scope {
+        ($($event:ty => $label:literal, |$e:ident| $uuid:expr;)*) => {
+            $(
+                if let Some($e) = downcast_event::<$event>(event) {
+                    return Some(($label, vec![$uuid]));
+                }
+            )*
+        };
+    }
+    comp_scope! {
+        AddLayerEvent => "Add layer", |e| e.comp_uuid;
+        RemoveLayerEvent => "Remove layer", |e| e.comp_uuid;
+        ReorderLayerEvent => "Reorder layer", |e| e.comp_uuid;
+        MoveAndReorderLayerEvent => "Move layer", |e| e.comp_uuid;
+        MoveLayerEvent => "Move layer", |e| e.comp_uuid;
+        SetLayerPlayStartEvent => "Trim layer", |e| e.comp_uuid;
+        SetLayerPlayEndEvent => "Trim layer", |e| e.comp_uuid;
+        SlideLayerEvent => "Slide layer", |e| e.comp_uuid;
+        ResetTrimsEvent => "Reset trims", |e| e.comp_uuid;
+        AlignLayersStartEvent => "Align layers", |e| e.0;
+        AlignLayersEndEvent => "Align layers", |e| e.0;
+        TrimLayersStartEvent => "Trim layers", |e| e.0;
+        TrimLayersEndEvent => "Trim layers", |e| e.0;
+        RippleTrimLayersEndEvent => "Ripple trim", |e| e.0;
+        DuplicateLayersEvent => "Duplicate layers", |e| e.comp_uuid;
+        SplitLayersEvent => "Split layers", |e| e.comp_uuid;
+        PasteLayersEvent => "Paste layers", |e| e.comp_uuid;
+        LayerAttributesChangedEvent => "Layer attributes", |e| e.comp_uuid;
+        LayerMaskRefChangedEvent => "Layer mask", |e| e.comp_uuid;
+        SetLayerAttrsEvent => "Layer attributes", |e| e.comp_uuid;
+        SetLayerTransformsEvent => "Transform", |e| e.comp_uuid;
+    }
+    if let Some(e) = downcast_event::<SetNodeAttrsEvent>(event) {
+        return Some(("Attributes", e.node_uuids.clone()));
+    }
+    if downcast_event::<RemoveSelectedLayerEvent>(event).is_some() {
+        return active_comp.map(|uuid| ("Remove layers", vec![uuid]));
+    }
+    if downcast_event::<RippleDeleteSelectedLayerEvent>(event).is_some() {
+        return active_comp.map(|uuid| ("Ripple delete", vec![uuid]));
+    }
+    None
+}
+
+impl PlayaApp {
+    /// Undo the last timeline / attribute edit (Ctrl+Z)
+    pub fn undo(&mut self) {
+        match self.history.undo(&self.project) {
+            Some((label, uuids)) => {
+                info!("Undo: {}", label);
+                self.after_history_step(&uuids);
+            }
+            None => info!("Nothing to undo"),
+        }
+    }
+
+    /// Redo the last undone edit (Ctrl+Shift+Z)
+    pub fn redo(&mut self) {
+        match self.history.redo(&self.project) {
+            Some((label, uuids)) => {
+                info!("Redo: {}", label);
+                self.after_history_step(&uuids);
+            }
+            None => info!("Nothing to redo"),
+        }
+    }
+
+    /// Restored nodes bypass `modify_comp`, so invalidate them here
+    fn after_history_step(&mut self, uuids: &[Uuid]) {
+        self.node_editor_state.mark_dirty();
+        self.invalidate_and_refresh(uuids);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use playa_engine::core::cache_man::CacheManager;
+    use playa_engine::entities::keys::{A_IN, A_OUT};
+    use playa_engine::entities::{CompNode, FileNode, NodeLayer as Layer};
+
+    /// Project with one comp holding one 10-frame layer at 0
+    fn test_project() -> (Project, Uuid, Uuid) {
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let file = FileNode::new("/shots/a.*.exr".to_string(), 1, 10, 24.0);
+        let mut comp = CompNode::new("Comp", 0, 100, 24.0);
+        let layer = Layer::new(file.uuid(), "a", 0, 10, (64, 64));
+        let layer_uuid = layer.uuid();
+        comp.add_layer(layer, None);
+        let comp_uuid = comp.uuid();
+        project.add_node(NodeKind::File(file));
+        project.add_node(NodeKind::Comp(comp));
+        (project, comp_uuid, layer_uuid)
+    }
+
+    fn layer_in_out(project: &Project, comp: Uuid, layer: Uuid) -> (i32, i32) {
+        project
+            .with_comp(comp, |c| {
+                let attrs = &c.get_layer(layer).unwrap().attrs;
+                (attrs.get_i32_or_zero(A_IN), attrs.get_i32_or_zero(A_OUT))
+            })
+            .unwrap()
+    }
+
+    fn move_child(history: &mut History, project: &Project, comp: Uuid, start: i32) -> bool {
+        let pending = history.begin(project, "Move layer", &[comp]);
+        project.modify_comp(comp, |c| c.move_child(0, start).unwrap());
+        history.commit(project, pending)
+    }
+
+    #[test]
+    fn test_undo_move_child_restores_in_out() {
+        let (project, comp, layer) = test_project();
+        let mut history = History::default();
+        let original = layer_in_out(&project, comp, layer);
+
+        assert!(move_child(&mut history, &project, comp, 40));
+        let moved = layer_in_out(&project, comp, layer);
+        assert_eq!(moved.0, 40);
+        assert_ne!(moved, original);
+
+        assert_eq!(history.undo(&project).map(|(l, _)| l), Some("Move layer"));
+        assert_eq!(layer_in_out(&project, comp, layer), original);
+        assert!(history.redo(&project).is_some());
+        assert_eq!(layer_in_out(&project, comp, layer), moved);
+    }
+
+    #[test]
+    fn test_drag_coalesces_and_noop_is_skipped() {
+        let (project, comp, layer) = test_project();
+        let mut history = History::default();
+        let original = layer_in_out(&project, comp, layer);
+
+        // A drag: many moves in quick succession undo as one step
+        for start in 1..=20 {
+            assert!(move_child(&mut history, &project, comp, start));
+        }
+        // Moving to where it already is changes nothing
+        assert!(!move_child(&mut history, &project, comp, 20));
+        assert!(history.undo(&project).is_some());
+        assert_eq!(layer_in_out(&project, comp, layer), original);
+        assert!(!history.can_undo());
+    }
+
+    #[test]
+    fn test_depth_cap_and_playhead_kept() {
+        let (project, comp, _) = test_project();
+        let mut history = History::default();
+        for i in 0..HISTORY_DEPTH + 5 {
+            let pending = history.begin(&project, "Move layer", &[comp]);
+            project.modify_comp(comp, |c| c.move_child(0, i as i32 + 1).unwrap());
+            // Distinct steps: age the last one past the coalesce window
+            history.commit(&project, pending);
+            if let Some(last) = history.undo.back_mut() {
+                last.at -= COALESCE_WINDOW;
+            }
+        }
+        assert_eq!(history.undo.len(), HISTORY_DEPTH);
+
+        project.modify_comp(comp, |c| c.set_frame(42));
+        history.undo(&project);
+        assert_eq!(project.with_comp(comp, |c| c.frame()), Some(42));
+    }
+}
@@ -4,11 +4,15 @@
 //! - `events` - Event handling (handle_events, handle_effect_actions, handle_keyboard_input)
 //! - `api` - REST API server and commands
 //! - `project_io` - Project/sequence loading and saving
+//! - `history` - Undo / redo of timeline and attribute edits
+//! - `recovery` - Per-instance crash-recovery files
 
 mod api;
 mod events;
+mod history;
 mod layout;
 mod project_io;
+mod recovery;
 mod run;
 mod snapshot;
 mod tabs;
@@ -26,9 +30,12 @@ use playa_engine::entities::{Frame, GpuBlendBridge, GpuBlendRequest, Project, gp
 #[cfg(feature = "jobs")]
 use playa_jobs::{JobQueue, JobQueueConfig};
 use playa_ui::dialogs::encode::EncodeDialog;
+use playa_ui::dialogs::goto_frame::GotoFrameDialog;
 use playa_ui::dialogs::prefs::prefs_events::HotkeyWindow;
 use playa_ui::dialogs::prefs::{AppSettings, HotkeyHandler};
 use playa_ui::widgets::ae::AttributesState;
+use playa_ui::widgets::cache_stats::CacheStatsState;
+use playa_ui::widgets::metadata::MetadataState;
 use playa_ui::widgets::node_editor::NodeEditorState;
 use playa_ui::widgets::status::StatusBar;
 use playa_ui::widgets::viewport::{Shaders, ViewportRenderer, ViewportState};
@@ -51,6 +58,10 @@ pub enum DockTab {
     /// etc). Feature-gated under `jobs` (default on).
     #[cfg(feature = "jobs")]
     Jobs,
+    /// Cache memory / hit rate / eviction stats with a memory sparkline.
+    CacheStats,
+    /// Header of the displayed frame's source file (EXR attrs, EXIF, PNG text).
+    Metadata,
 }
 
 /// Main application state.
@@ -97,6 +108,9 @@ pub struct PlayaApp {
     pub show_encode_dialog: bool,
     #[serde(skip)]
     pub encode_dialog: Option<EncodeDialog>,
+    /// Open go-to-frame prompt (Ctrl+G)
+    #[serde(skip)]
+    pub goto_frame: Option<GotoFrameDialog>,
     #[serde(skip)]
     pub show_attributes_editor: bool,
     #[serde(skip)]
@@ -119,6 +133,9 @@ pub struct PlayaApp {
     /// Debounced preloader - delays full cache preload after attribute changes
     #[serde(skip)]
     pub debounced_preloader: DebouncedPreloader,
+    /// Last playhead move; proxy playback stays active until it settles
+    #[serde(skip)]
+    pub last_frame_change: Option<std::time::Instant>,
     /// Global worker pool for background tasks (frame loading, encoding)
     #[serde(skip)]
     pub workers: Arc<Workers>,
@@ -152,6 +169,15 @@ pub struct PlayaApp {
     #[serde(skip)]
     pub ae_focus: Vec<Uuid>,
     pub attributes_state: AttributesState,
+    /// Cache stats panel samples + memory history
+    #[serde(skip)]
+    pub cache_stats_state: CacheStatsState,
+    /// Metadata panel: last inspected file and its header
+    #[serde(skip)]
+    pub metadata_state: MetadataState,
+    /// Undo / redo stacks for timeline and attribute edits
+    #[serde(skip)]
+    pub history: history::History,
     /// Node editor state (snarl graph for composition visualization)
     pub node_editor_state: NodeEditorState,
     /// Gizmo state for viewport transform manipulation
@@ -163,17 +189,32 @@ pub struct PlayaApp {
     /// REST API command receiver (polled each frame)
     #[serde(skip)]
     pub api_command_rx: Option<std::sync::mpsc::Receiver<crate::server::ApiCommand>>,
+    /// Commands from the `--script` file, run once through the API dispatch
+    #[serde(skip)]
+    pub script_commands: Vec<crate::server::ApiCommand>,
     /// Pending screenshot requests (broadcast via [`egui::ViewportCommand::Screenshot`] + CPU path for raw frame)
     /// Multiple clients can wait - all receive the same screenshot (broadcast)
-    /// (viewport_only, response_channel) - viewport_only=true means full window, false means raw frame
+    /// (kind, response_channel) - see [`api::ScreenshotKind`]
     #[serde(skip)]
-    pub pending_screenshots: Vec<(bool, crossbeam_channel::Sender<Result<Vec<u8>, String>>)>,
+    pub pending_screenshots: Vec<(
+        api::ScreenshotKind,
+        crossbeam_channel::Sender<Result<Vec<u8>, String>>,
+    )>,
     /// Exit requested via REST API
     #[serde(skip)]
     pub exit_requested: bool,
-    /// Last dark_mode value applied to egui visuals (avoids rebuilding Visuals every frame)
+    /// Recovery file left by a crashed session, awaiting the restore/discard prompt
+    #[serde(skip)]
+    pub pending_recovery: Option<std::path::PathBuf>,
+    /// Last auto-save tick (see [`Self::autosave_recovery`])
+    #[serde(skip)]
+    pub last_autosave: std::time::Instant,
+    /// This instance's recovery file and lock (taken at startup)
     #[serde(skip)]
-    pub last_applied_dark_mode: Option<bool>,
+    pub recovery: Option<recovery::RecoveryStore>,
+    /// Last theme applied to egui visuals (avoids rebuilding Visuals every frame)
+    #[serde(skip)]
+    pub last_applied_theme: Option<playa_ui::dialogs::prefs::UiTheme>,
     /// Last font_size value applied to egui style (avoids cloning style every frame)
     #[serde(skip)]
     pub last_applied_font_size: f32,
@@ -193,6 +234,34 @@ pub struct PlayaApp {
     /// [`PlayaApp::ensure_gpu_blend_initialized`] expects after deserialize.
     #[serde(skip)]
     pub gpu_blend_rx: Mutex<Option<Receiver<GpuBlendRequest>>>,
+    /// `--force-cpu`: never switch to the GPU compositor this session
+    #[serde(skip)]
+    pub force_cpu: bool,
+    /// `--still-frames`: hold duration for stills imported this session,
+    /// in place of the prefs value (which stays as it is)
+    #[serde(skip)]
+    pub cli_still_frames: Option<i32>,
+    /// `--playlist` turned sequence playback on for this session; holds the
+    /// prefs value that is saved in its place
+    #[serde(skip)]
+    pub cli_sequence_playback_pref: Option<bool>,
+    /// `--background-color` and the prefs backdrop it replaced; the prefs
+    /// value is saved unless the backdrop was changed since
+    #[serde(skip)]
+    pub cli_background: Option<([u8; 3], [u8; 3])>,
+    /// Why the GPU compositor is off for this session despite the prefs
+    /// (software adapter, failed self-test); see [`Self::update_compositor_backend`]
+    #[serde(skip)]
+    pub gpu_unavailable: Option<String>,
+    /// Adapter checked and logged (once per session)
+    #[serde(skip)]
+    pub gpu_probed: bool,
+    /// Fallback warning raised this session (shown once)
+    #[serde(skip)]
+    pub gpu_fallback_warned: bool,
+    /// Fallback warning not yet dismissed
+    #[serde(skip)]
+    pub gpu_fallback_notice: bool,
     /// Long-running IO job queue (Seedance video-gen, ffmpeg encodes that the
     /// user wants tracked outside the encode dialog, future media-import jobs).
     ///
@@ -289,6 +358,11 @@ pub struct PlayaApp {
     pub prefs_window: egui_prefs::PrefsWindow<AppSettings>,
 }
 
+/// Auto worker count: 75% of CPU cores, leaving the rest for the UI thread.
+pub fn default_worker_count() -> usize {
+    (num_cpus::get() * 3 / 4).max(1)
+}
+
 impl Default for PlayaApp {
     fn default() -> Self {
         // Create global cache manager (memory tracking + epoch)
@@ -299,8 +373,10 @@ impl Default for PlayaApp {
         let status_bar = StatusBar::new();
 
         // Create worker pool (75% of CPU cores for workers, 25% for UI thread)
-        let num_workers = (num_cpus::get() * 3 / 4).max(1);
-        let workers = Arc::new(Workers::new(num_workers, cache_manager.epoch_ref()));
+        let workers = Arc::new(Workers::new(
+            default_worker_count(),
+            cache_manager.epoch_ref(),
+        ));
 
         // Create global event bus and comp event emitter
         let event_bus = EventBus::new();
@@ -338,6 +414,7 @@ impl Default for PlayaApp {
             show_encode_dialog: false,
             show_attributes_editor: true,
             encode_dialog: None,
+            goto_frame: None,
             is_fullscreen: false,
             fullscreen_dirty: false,
             reset_settings_pending: false,
@@ -347,6 +424,7 @@ impl Default for PlayaApp {
             path_config: config::PathConfig::from_env_and_cli(None),
             cache_manager,
             debounced_preloader: DebouncedPreloader::default(),
+            last_frame_change: None,
             workers,
             comp_event_emitter,
             event_bus,
@@ -364,17 +442,32 @@ impl Default for PlayaApp {
             node_editor_tab_active: false,
             ae_focus: Vec::new(),
             attributes_state: AttributesState::default(),
+            cache_stats_state: CacheStatsState::default(),
+            metadata_state: MetadataState::default(),
+            history: history::History::default(),
             node_editor_state: NodeEditorState::new(),
             gizmo_state: playa_ui::widgets::viewport::gizmo::GizmoState::default(),
             api_state: Arc::new(crate::server::SharedApiState::default()),
             api_command_rx: None, // Started later when settings are loaded
+            script_commands: Vec::new(),
             pending_screenshots: Vec::new(),
             exit_requested: false,
-            last_applied_dark_mode: None,
+            pending_recovery: None,
+            last_autosave: std::time::Instant::now(),
+            recovery: None,
+            last_applied_theme: None,
             last_applied_font_size: 0.0,
             options_initialized: false,
             gpu_blend_bridge: Some(gpu_blend_bridge),
             gpu_blend_rx: Mutex::new(Some(gpu_blend_rx)),
+            force_cpu: false,
+            cli_still_frames: None,
+            cli_sequence_playback_pref: None,
+            cli_background: None,
+            gpu_unavailable: None,
+            gpu_probed: false,
+            gpu_fallback_warned: false,
+            gpu_fallback_notice: false,
             #[cfg(feature = "jobs")]
             job_queue,
             #[cfg(feature = "jobs")]
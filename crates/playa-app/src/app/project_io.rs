@@ -1,18 +1,38 @@
 //! Project I/O and sequence loading for PlayaApp.
 //!
 //! Contains methods for:
-//! - Loading sequences from files (load_sequences)
+//! - Loading sequences from files (load_sequences, load_dropped)
 //! - Saving/loading projects (save_project, load_project, quick_save)
+//! - Review playlists (load_playlist, save_playlist)
+//! - Per-project viewport defaults (apply_viewport_defaults)
+//! - Sequence playback transitions (play_next_comp, preload_next_comp)
 //! - File dialogs (show_open_project_dialog)
 //! - Frame preloading (enqueue_frame_loads_around_playhead)
+//! - Crash recovery auto-save (autosave_recovery, restore_recovery)
 
-use super::PlayaApp;
-use playa_engine::entities::FileNode;
+use super::recovery::RecoveryStore;
+use super::{DockTab, PlayaApp};
+use crate::config;
+use playa_engine::core::player::SEQUENCE_PRELOAD_LEAD;
+use playa_engine::entities::keys::A_IN;
 use playa_engine::entities::node::Node;
+use playa_engine::entities::{FileNode, Playlist, PlaylistItem, Project};
+use playa_engine::utils::{media, psd};
+use playa_ui::dialogs::prefs::{AppSettings, RecentKind, prune_recent, push_recent};
 
-use log::{error, info, trace, warn};
+use eframe::egui;
+use egui_dock::DockState;
+use log::{debug, error, info, trace, warn};
 use std::path::PathBuf;
-use std::sync::Arc;
+use std::sync::{Arc, RwLock};
+
+/// What auto-save writes: everything needed to get back to work, not just the project.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct RecoverySnapshot {
+    project: Project,
+    settings: AppSettings,
+    dock_state: DockState<DockTab>,
+}
 
 impl PlayaApp {
     /// Attach composition event emitter to all comps in the current project.
@@ -31,20 +51,50 @@ impl PlayaApp {
         }
     }
 
+    /// Hold duration for imported stills: `--still-frames` for this session,
+    /// else the prefs value
+    fn still_frames(&self) -> i32 {
+        self.cli_still_frames
+            .unwrap_or(self.settings.playback.still_frames)
+    }
+
     /// Load sequences from file paths and append to player/project.
     ///
     /// Detects sequences from provided paths, appends them to the player project,
-    /// and clears any error messages on success.
+    /// and clears any error messages on success. Single stills are held for
+    /// [`Self::still_frames`]. Sequences and stills play at the prefs
+    /// `sequence_fps`; videos keep their container rate.
     ///
     /// # Arguments
     /// * `paths` - Vector of file paths to detect sequences from
     ///
     /// # Returns
-    /// * `Ok(())` - Sequences loaded successfully
+    /// * `Ok(uuids)` - Sequences loaded successfully, new nodes in input order
     /// * `Err(String)` - Detection or loading failed with error message
-    pub fn load_sequences(&mut self, paths: Vec<PathBuf>) -> Result<(), String> {
+    pub fn load_sequences(&mut self, paths: Vec<PathBuf>) -> Result<Vec<uuid::Uuid>, String> {
+        // Layered PSDs import as comps (one layer per PSD layer)
+        let (psds, paths): (Vec<PathBuf>, Vec<PathBuf>) =
+            paths.into_iter().partition(|p| psd::is_psd(p));
+        let had_psds = !psds.is_empty();
+        let psd_comps = self.import_psds(psds);
+        if paths.is_empty() && had_psds {
+            if psd_comps.is_empty() {
+                return Err(self.error_msg.clone().unwrap_or_default());
+            }
+            self.attach_comp_event_emitter();
+            if self.player.active_comp().is_none() {
+                self.player
+                    .set_active_comp(Some(psd_comps[0]), &mut self.project);
+                self.node_editor_state.set_comp(psd_comps[0]);
+                self.node_editor_state.mark_dirty();
+                self.enqueue_frame_loads_around_playhead(self.settings.playback.preload_radius);
+            }
+            self.error_msg = None;
+            return Ok(psd_comps);
+        }
+
         match FileNode::detect_from_paths(paths) {
-            Ok(nodes) => {
+            Ok(mut nodes) => {
                 if nodes.is_empty() {
                     let error_msg = "No valid sequences detected".to_string();
                     warn!("{}", error_msg);
@@ -53,8 +103,17 @@ impl PlayaApp {
                 }
 
                 // Add all detected sequences to unified media pool
-                let nodes_count = nodes.len();
-                let mut first_uuid: Option<uuid::Uuid> = None;
+                let mut added = psd_comps;
+                let mut first_uuid: Option<uuid::Uuid> = added
+                    .first()
+                    .copied()
+                    .filter(|_| self.player.active_comp().is_none());
+                for node in &mut nodes {
+                    node.set_still_frames(self.still_frames());
+                    if node.is_sequence() || node.is_still() {
+                        node.set_fps(self.settings.playback.sequence_fps);
+                    }
+                }
                 for node in nodes {
                     let uuid = node.uuid();
                     let name = node.name().to_string();
@@ -65,8 +124,21 @@ impl PlayaApp {
                         name, uuid, frames, start, end
                     );
 
+                    // Recent list keeps a real file: the video itself or the first frame
+                    let recent_path = node.file_mask().and_then(|mask| {
+                        if node.is_sequence() {
+                            node.file_start().and_then(|f| node.resolve_frame_path(f))
+                        } else {
+                            Some(PathBuf::from(mask))
+                        }
+                    });
+                    if let Some(path) = recent_path {
+                        push_recent(&mut self.settings.recent_items, path, RecentKind::Media);
+                    }
+
                     // add_node() adds to media pool and order
                     self.project.add_node(node.into());
+                    added.push(uuid);
 
                     // Remember first sequence for activation
                     if self.player.active_comp().is_none() && first_uuid.is_none() {
@@ -91,8 +163,8 @@ impl PlayaApp {
                 }
 
                 self.error_msg = None;
-                info!("Loaded {} clip(s)", nodes_count);
-                Ok(())
+                info!("Loaded {} clip(s)", added.len());
+                Ok(added)
             }
             Err(e) => {
                 let error_msg = format!("Failed to load sequences: {}", e);
@@ -103,6 +175,66 @@ impl PlayaApp {
         }
     }
 
+    /// Import layered PSD files as comps held for [`Self::still_frames`].
+    /// Failures are logged and reported in the error line.
+    fn import_psds(&mut self, paths: Vec<PathBuf>) -> Vec<uuid::Uuid> {
+        let mut comps = Vec::with_capacity(paths.len());
+        for path in paths {
+            match self.project.import_psd(&path, self.still_frames()) {
+                Ok(uuid) => {
+                    push_recent(&mut self.settings.recent_items, path, RecentKind::Media);
+                    comps.push(uuid);
+                }
+                Err(e) => {
+                    let error_msg = format!("Failed to import {}: {}", path.display(), e);
+                    warn!("{}", error_msg);
+                    self.error_msg = Some(error_msg);
+                }
+            }
+        }
+        comps
+    }
+
+    /// Load files and folders dropped onto the window, one comp per sequence.
+    ///
+    /// Folders are scanned recursively for sequences, videos and stills;
+    /// loose dropped frames collapse into their sequence (grouped by common
+    /// prefix). The first new comp is activated and selected, and the number
+    /// of comps created is reported in the status bar.
+    pub fn load_dropped(&mut self, paths: Vec<PathBuf>) {
+        let mut files = Vec::new();
+        for path in paths {
+            if path.is_dir() {
+                files.extend(crate::main_events::scan_folder_for_media(&path));
+            } else {
+                files.push(path);
+            }
+        }
+        let files = media::group_by_prefix(&files);
+        if files.is_empty() {
+            self.status_bar.current_message = "Drop: no media found".to_string();
+            return;
+        }
+
+        match self.load_sequences(files) {
+            Ok(added) => {
+                if let Some(&first) = added.first() {
+                    self.player.set_active_comp(Some(first), &mut self.project);
+                    self.project.set_selection(vec![first]);
+                    self.selected_media_uuid = Some(first);
+                    self.node_editor_state.set_comp(first);
+                    self.node_editor_state.mark_dirty();
+                    self.enqueue_frame_loads_around_playhead(self.settings.playback.preload_radius);
+                }
+                self.status_bar.current_message = match added.len() {
+                    1 => "Drop: created 1 comp".to_string(),
+                    n => format!("Drop: created {} comps", n),
+                };
+            }
+            Err(e) => self.status_bar.current_message = format!("Drop: {}", e),
+        }
+    }
+
     /// Enqueue frame loading around playhead for active comp.
     ///
     /// Unified interface: works for both File mode and Layer mode.
@@ -113,6 +245,40 @@ impl PlayaApp {
         self.enqueue_frame_loads_around_playhead(0);
     }
 
+    /// Pick the project proxy level: the preferred proxy while playing or
+    /// scrubbing, full res once the playhead has been still for a moment.
+    ///
+    /// Swapping back to full res enqueues the current frame right away and
+    /// schedules a debounced full preload, same as an attribute change.
+    pub fn sync_proxy_level(&mut self) {
+        use playa_engine::entities::ProxyLevel;
+        const SETTLE: std::time::Duration = std::time::Duration::from_millis(250);
+
+        let moving = self.player.is_playing()
+            || self.last_frame_change.is_some_and(|t| t.elapsed() < SETTLE);
+        let wanted = if moving {
+            self.settings.playback.proxy_level
+        } else {
+            ProxyLevel::Full
+        };
+        if self.project.proxy_level() == wanted {
+            return;
+        }
+
+        trace!(
+            "Proxy level: {:?} -> {:?}",
+            self.project.proxy_level(),
+            wanted
+        );
+        self.project.set_proxy_level(wanted);
+        if wanted == ProxyLevel::Full
+            && let Some(comp_uuid) = self.player.active_comp()
+        {
+            self.enqueue_current_frame_only();
+            self.debounced_preloader.schedule(comp_uuid);
+        }
+    }
+
     /// File mode: loads frames from disk using spiral/forward strategies
     /// Layer mode: composes frames from children (on-demand for now)
     ///
@@ -128,7 +294,12 @@ impl PlayaApp {
             trace!("No active comp for frame loading");
             return;
         };
+        self.enqueue_frame_loads_for(comp_uuid, radius);
+    }
 
+    /// Preload frames around `comp_uuid`'s own playhead (active or not); see
+    /// [`Self::enqueue_frame_loads_around_playhead`].
+    pub fn enqueue_frame_loads_for(&self, comp_uuid: uuid::Uuid, radius: i32) {
         // -1 means load entire comp (use i32::MAX, will be capped by work_area)
         let effective_radius = if radius < 0 { i32::MAX } else { radius };
 
@@ -137,6 +308,8 @@ impl PlayaApp {
             "[PRELOAD] enqueue_frame_loads: comp={}, radius={}",
             comp_uuid, effective_radius
         );
+        // Inactive comps' preloads queue behind the viewport's
+        self.workers.set_active_comp(self.player.active_comp());
         let bridge = self.gpu_blend_bridge_ref_for_preload();
         self.project.with_comp(comp_uuid, |comp| {
             comp.signal_preload(&self.workers, &self.project, bridge, effective_radius);
@@ -151,6 +324,17 @@ impl PlayaApp {
         } else {
             self.project.set_last_save_path(Some(path.clone()));
             info!("Saved project to {}", path.display());
+            // Project::to_json forces a .json extension; record the file actually written
+            let written = if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                path
+            } else {
+                path.with_extension("json")
+            };
+            push_recent(
+                &mut self.settings.recent_items,
+                written,
+                RecentKind::Project,
+            );
         }
     }
 
@@ -185,54 +369,387 @@ impl PlayaApp {
     /// Load project from JSON file.
     pub fn load_project(&mut self, path: PathBuf) {
         match playa_engine::entities::Project::from_json(&path) {
-            Ok(mut project) => {
+            Ok(project) => {
                 info!("Loaded project from {}", path.display());
+                self.install_project(project);
+                push_recent(&mut self.settings.recent_items, path, RecentKind::Project);
+            }
+            Err(e) => {
+                error!("{}", e);
+                self.error_msg = Some(e);
+            }
+        }
+    }
 
-                // Attach schemas (not serialized)
-                project.attach_schemas();
-
-                // Rebuild runtime + set cache manager (unified)
-                project.rebuild_with_manager(
-                    Arc::clone(&self.cache_manager),
-                    self.settings.cache.cache_strategy,
-                    Some(self.comp_event_emitter.clone()),
-                );
-                // Set event emitter for auto-emit of AttrsChangedEvent
-                project.set_event_emitter(self.event_bus.emitter());
-
-                self.project = project;
-                // Restore active comp from project (also sync selection)
-                if let Some(active) = self.project.active() {
-                    self.player.set_active_comp(Some(active), &mut self.project);
-                    self.node_editor_state.set_comp(active);
+    /// Reopen the most recent file or project (skipping entries that vanished).
+    pub fn open_most_recent(&mut self) {
+        prune_recent(&mut self.settings.recent_items);
+        let Some(item) = self.settings.recent_items.first().cloned() else {
+            info!("No recent items to reopen");
+            return;
+        };
+        info!("Reopening recent {:?}: {}", item.kind, item.path.display());
+        match item.kind {
+            RecentKind::Media => {
+                let _ = self.load_sequences(vec![item.path]);
+            }
+            RecentKind::Project => self.load_project(item.path),
+        }
+    }
+
+    /// Load a review playlist: each item becomes a clip wrapped in its own comp,
+    /// appended in playlist order.
+    ///
+    /// Items with a range get it as their comp's work area. Missing or
+    /// unreadable items are skipped with a warning; the first comp is activated.
+    pub fn load_playlist(&mut self, path: PathBuf) -> Result<Vec<uuid::Uuid>, String> {
+        let playlist = Playlist::load(&path).inspect_err(|e| {
+            error!("{}", e);
+            self.error_msg = Some(e.clone());
+        })?;
+        info!(
+            "Loading playlist {} ({} items)",
+            path.display(),
+            playlist.items.len()
+        );
+
+        let mut comps = Vec::with_capacity(playlist.items.len());
+        for item in playlist.items {
+            let Ok(uuids) = self.load_sequences(vec![item.path.clone()]) else {
+                warn!("Playlist item skipped: {}", item.path.display());
+                continue;
+            };
+            let range = item.start.zip(item.end);
+            if let Some(comp) = uuids
+                .first()
+                .and_then(|&source| self.project.create_comp_for_source(source, range))
+            {
+                comps.push(comp);
+            }
+        }
+        self.attach_comp_event_emitter();
+
+        let Some(&first) = comps.first() else {
+            let msg = format!("Playlist {} has no loadable items", path.display());
+            warn!("{}", msg);
+            self.error_msg = Some(msg.clone());
+            return Err(msg);
+        };
+        self.player.set_active_comp(Some(first), &mut self.project);
+        self.node_editor_state.set_comp(first);
+        self.node_editor_state.mark_dirty();
+        self.enqueue_frame_loads_around_playhead(self.settings.playback.preload_radius);
+        self.error_msg = None;
+        Ok(comps)
+    }
+
+    /// Save the project's comps, in project order, as a review playlist.
+    ///
+    /// Each comp is written as the file behind its first layer (sequences as
+    /// their first frame); a trimmed work area becomes the item's range in
+    /// source frames. Comps without a file layer are left out.
+    pub fn save_playlist(&mut self, path: PathBuf) {
+        let mut playlist = Playlist::default();
+        {
+            let media = self.project.media.read().expect("media lock poisoned");
+            for uuid in self.project.order() {
+                let Some(comp) = media.get(&uuid).and_then(|n| n.as_comp()) else {
+                    continue;
+                };
+                let Some(layer) = comp.layers.first() else {
+                    continue;
+                };
+                let Some(file) = media.get(&layer.source_uuid()).and_then(|n| n.as_file()) else {
+                    continue;
+                };
+                let Some(mask) = file.file_mask() else {
+                    continue;
+                };
+                let item_path = if file.is_sequence() {
+                    file.file_start().and_then(|f| file.resolve_frame_path(f))
                 } else {
-                    // Ensure default if none
-                    let uuid = self.project.ensure_default_comp();
-                    self.player.set_active_comp(Some(uuid), &mut self.project);
-                    self.node_editor_state.set_comp(uuid);
-                }
-                self.selected_media_uuid = self.project.selection().last().cloned();
-                self.error_msg = None;
+                    Some(PathBuf::from(mask))
+                };
+                let Some(item_path) = item_path else {
+                    continue;
+                };
 
-                // Mark active comp dirty -> modify_comp emits AttrsChangedEvent
-                // -> handle_attrs_changed clears cache, schedules preload, emits ViewportRefreshEvent.
-                // No explicit ViewportRefreshEvent needed here.
-                if let Some(active) = self.player.active_comp() {
-                    self.project.modify_comp(active, |comp| {
-                        comp.attrs.mark_dirty();
-                    });
+                let mut item = PlaylistItem::new(item_path);
+                let (start, end) = comp.work_area();
+                if (start, end) != (comp._in(), comp._out()) {
+                    // Comp frame -> source frame through the layer's start
+                    let offset = file._in() - layer.attrs.get_i32(A_IN).unwrap_or(0);
+                    item = item.with_range(start + offset, end + offset);
                 }
+                playlist.items.push(item);
+            }
+        }
 
-                // Match the boot-path invariant (runner.rs:187): keep GPU blend channels in a
-                // known-good state across project swaps. No-op when both bridge and rx are still
-                // Some, but defends against future regressions and any path that leaves the pair
-                // half-initialized.
-                self.ensure_gpu_blend_initialized();
+        match playlist.save(&path) {
+            Ok(()) => info!(
+                "Saved playlist ({} items) to {}",
+                playlist.items.len(),
+                path.display()
+            ),
+            Err(e) => {
+                error!("{}", e);
+                self.error_msg = Some(e);
             }
+        }
+    }
+
+    /// Sequence playback: `from` finished its work area, so activate the next
+    /// comp in project order and keep playing from its work-area start.
+    /// Returns false (playback stays stopped) at the end of the list.
+    pub fn play_next_comp(&mut self, from: uuid::Uuid) -> bool {
+        // Ignore a stale request if the user switched comps meanwhile
+        if self.player.active_comp() != Some(from) {
+            return false;
+        }
+        let Some(next) = self.player.next_comp(from, &self.project) else {
+            info!("Sequence playback reached the last comp");
+            return false;
+        };
+
+        debug!("Sequence playback: {} -> {}", from, next);
+        self.player.set_active_comp(Some(next), &mut self.project);
+        self.player.to_start(&mut self.project);
+        self.player.set_is_playing(true);
+        self.node_editor_state.set_comp(next);
+        self.node_editor_state.mark_dirty();
+        self.enqueue_frame_loads_around_playhead(self.settings.playback.preload_radius);
+        true
+    }
+
+    /// Sequence playback: warm up the head of the comp that follows `from`.
+    pub fn preload_next_comp(&mut self, from: uuid::Uuid) {
+        let Some(next) = self.player.next_comp(from, &self.project) else {
+            return;
+        };
+        // Park its playhead on the work-area start; preload spirals from there
+        self.project
+            .modify_comp(next, |comp| comp.set_frame(comp.work_area().0));
+        self.enqueue_frame_loads_for(next, SEQUENCE_PRELOAD_LEAD);
+    }
+
+    /// Swap in a deserialized project and rebuild its runtime wiring
+    /// (schemas, cache manager, event emitters, active comp).
+    fn install_project(&mut self, mut project: Project) {
+        // Attach schemas (not serialized)
+        project.attach_schemas();
+
+        // Rebuild runtime + set cache manager (unified)
+        project.rebuild_with_manager(
+            Arc::clone(&self.cache_manager),
+            self.settings.cache.cache_strategy,
+            Some(self.comp_event_emitter.clone()),
+        );
+        // Set event emitter for auto-emit of AttrsChangedEvent
+        project.set_event_emitter(self.event_bus.emitter());
+
+        self.project = project;
+        self.history.clear();
+        // Restore active comp from project (also sync selection)
+        if let Some(active) = self.project.active() {
+            self.player.set_active_comp(Some(active), &mut self.project);
+            self.node_editor_state.set_comp(active);
+        } else {
+            // Ensure default if none
+            let uuid = self.project.ensure_default_comp();
+            self.player.set_active_comp(Some(uuid), &mut self.project);
+            self.node_editor_state.set_comp(uuid);
+        }
+        self.selected_media_uuid = self.project.selection().last().cloned();
+        self.error_msg = None;
+
+        // Mark active comp dirty -> modify_comp emits AttrsChangedEvent
+        // -> handle_attrs_changed clears cache, schedules preload, emits ViewportRefreshEvent.
+        // No explicit ViewportRefreshEvent needed here.
+        if let Some(active) = self.player.active_comp() {
+            self.project.modify_comp(active, |comp| {
+                comp.attrs.mark_dirty();
+            });
+        }
+
+        // Match the boot-path invariant (runner.rs:187): keep GPU blend channels in a
+        // known-good state across project swaps. No-op when both bridge and rx are still
+        // Some, but defends against future regressions and any path that leaves the pair
+        // half-initialized.
+        self.ensure_gpu_blend_initialized();
+
+        self.apply_viewport_defaults();
+    }
+
+    /// Apply the project's viewport defaults to the live viewport.
+    ///
+    /// Precedence: app prefs < project defaults < live user changes. Unset
+    /// fields keep the current value, and whatever the user changes afterwards
+    /// holds for the session (the project defaults are only re-applied on the
+    /// next load).
+    pub fn apply_viewport_defaults(&mut self) {
+        let defaults = self.project.viewport_defaults();
+        if defaults.is_empty() {
+            return;
+        }
+        info!("Applying project viewport defaults: {:?}", defaults);
+        if let Some(view) = defaults.view_transform {
+            if self.shader_manager.shaders.contains_key(&view) {
+                self.shader_manager.current_shader = view.clone();
+                self.settings.current_shader = view;
+                let mut renderer = self.viewport_renderer.lock().unwrap();
+                playa_ui::widgets::viewport::update_tonemap(&mut renderer, &self.shader_manager);
+            } else {
+                warn!("Project view transform '{}' is not available", view);
+            }
+        }
+        if let Some(rgb) = defaults.background {
+            self.settings.viewport.viewport_background = rgb;
+        }
+        if let Some(checker) = defaults.checker
+            && checker != self.viewport_state.checker
+        {
+            self.viewport_state.toggle_checker();
+        }
+        self.viewport_state.request_refresh();
+    }
+
+    /// Write project, settings and dock layout to this instance's recovery file.
+    ///
+    /// The UI thread only takes a copy of the state (node `Arc`s, not their
+    /// contents); serializing and writing happen on a background thread, see
+    /// [`RecoveryStore::save_in_background`].
+    pub fn autosave_recovery(&self) {
+        let Some(store) = self.recovery.as_ref() else {
+            return;
+        };
+        // Detached media map: the writer never holds the live project's lock
+        let mut project = self.project.clone();
+        project.media = Arc::new(RwLock::new(
+            self.project
+                .media
+                .read()
+                .expect("media lock poisoned")
+                .clone(),
+        ));
+        let snapshot = RecoverySnapshot {
+            project,
+            settings: self.settings.clone(),
+            dock_state: self.dock_state.clone(),
+        };
+        store.save_in_background(move || {
+            serde_json::to_string(&snapshot).map_err(|e| e.to_string())
+        });
+    }
+
+    /// Startup: take this instance's recovery lock and look for a snapshot
+    /// left by a session that didn't exit cleanly. Snapshots of other running
+    /// instances are ignored. Auto-save pauses until the user answers the prompt.
+    pub fn detect_recovery_file(&mut self) {
+        let store = match RecoveryStore::open(&config::get_data_dir(&self.path_config)) {
+            Ok(store) => store,
+            Err(e) => {
+                warn!("Crash recovery disabled: {}", e);
+                return;
+            }
+        };
+        if let Some(path) = store.find_orphan() {
+            warn!("Found crash recovery snapshot at {}", path.display());
+            self.pending_recovery = Some(path);
+        }
+        self.recovery = Some(store);
+    }
+
+    /// Restore the pending recovery snapshot (project, settings, dock layout).
+    pub fn restore_recovery(&mut self) {
+        let Some(path) = self.pending_recovery.take() else {
+            return;
+        };
+        let snapshot = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Read recovery file error: {}", e))
+            .and_then(|json| {
+                serde_json::from_str::<RecoverySnapshot>(&json)
+                    .map_err(|e| format!("Parse recovery file error: {}", e))
+            });
+        let mut snapshot = match snapshot {
+            Ok(snapshot) => snapshot,
             Err(e) => {
                 error!("{}", e);
                 self.error_msg = Some(e);
+                return;
+            }
+        };
+
+        info!("Restoring session from {}", path.display());
+        self.settings = snapshot.settings;
+        self.dock_state = snapshot.dock_state;
+        self.player.set_fps_base(self.settings.playback.fps_base);
+        self.player.set_fps_play(self.settings.playback.fps_base);
+        self.player
+            .set_loop_enabled(self.settings.playback.loop_enabled);
+        self.player
+            .set_sequence_playback(self.settings.playback.sequence_playback);
+        self.show_help = self.settings.show_help;
+        self.show_playlist = self.settings.show_playlist;
+        self.show_attributes_editor = self.settings.show_attributes_editor;
+
+        snapshot.project.rebuild_runtime(None);
+        self.install_project(snapshot.project);
+        self.node_editor_state.mark_dirty();
+        // The restored session is ours now: keep the snapshot as our own file
+        // until the next auto-save replaces it
+        if let Some(store) = self.recovery.as_ref() {
+            store.adopt(&path);
+        }
+    }
+
+    /// Drop the pending recovery snapshot without restoring it.
+    pub fn discard_recovery(&mut self) {
+        if let Some(path) = self.pending_recovery.take() {
+            info!("Discarding recovery snapshot {}", path.display());
+            if let Some(store) = self.recovery.as_ref() {
+                store.discard(&path);
             }
         }
     }
+
+    /// Delete this instance's recovery file (clean exit).
+    pub fn remove_recovery_file(&self) {
+        if let Some(store) = self.recovery.as_ref() {
+            store.remove();
+        }
+    }
+
+    /// Restore/discard prompt for a crashed session's recovery file.
+    pub fn render_recovery_prompt(&mut self, ctx: &egui::Context) {
+        let Some(path) = self.pending_recovery.as_ref() else {
+            return;
+        };
+        let saved_at = std::fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.elapsed().ok())
+            .map(|age| format!(" (auto-saved {} min ago)", age.as_secs() / 60))
+            .unwrap_or_default();
+
+        let mut restore = false;
+        let mut discard = false;
+        egui::Window::new("Recover Session")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("Playa did not shut down cleanly last time.");
+                ui.label(format!("Restore the unsaved session{}?", saved_at));
+                ui.add_space(8.0);
+                ui.horizontal(|ui| {
+                    restore = ui.button("Restore").clicked();
+                    discard = ui.button("Discard").clicked();
+                });
+            });
+        if restore {
+            self.restore_recovery();
+        } else if discard {
+            self.discard_recovery();
+        }
+    }
 }
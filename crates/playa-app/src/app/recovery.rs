@@ -0,0 +1,294 @@
+//! Per-instance crash-recovery files.
+//!
+//! Each running instance auto-saves to `playa_recovery.<pid>.json` and holds an
+//! exclusive OS lock on `playa_recovery.<pid>.lock` for its whole lifetime. At
+//! startup, a snapshot whose lock can be taken was left by a session that is
+//! gone (crashed); a locked one belongs to another live instance and is left
+//! alone, so concurrent instances never overwrite or restore each other's data.
+
+use std::fs::{self, File, TryLockError};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use log::{trace, warn};
+
+const PREFIX: &str = "playa_recovery.";
+
+/// Recovery file + lock of this instance.
+#[derive(Debug)]
+pub struct RecoveryStore {
+    dir: PathBuf,
+    pid: u32,
+    /// Locked for the process lifetime; released by the OS if we crash
+    lock: Option<File>,
+    /// Hash of the last snapshot written; unchanged state is not rewritten
+    last_hash: Arc<Mutex<Option<u64>>>,
+    /// A background write is running; further ticks are skipped until it ends
+    writing: Arc<AtomicBool>,
+}
+
+impl RecoveryStore {
+    /// Take this process's lock in `dir`.
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        Self::open_as(dir, std::process::id())
+    }
+
+    fn open_as(dir: &Path, pid: u32) -> io::Result<Self> {
+        let lock = File::create(lock_path(dir, pid))?;
+        lock.try_lock().map_err(|e| match e {
+            TryLockError::WouldBlock => io::Error::new(
+                io::ErrorKind::WouldBlock,
+                format!("recovery lock for pid {} is held", pid),
+            ),
+            TryLockError::Error(e) => e,
+        })?;
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            pid,
+            lock: Some(lock),
+            last_hash: Arc::new(Mutex::new(None)),
+            writing: Arc::new(AtomicBool::new(false)),
+        })
+    }
+
+    /// This instance's snapshot file.
+    pub fn path(&self) -> PathBuf {
+        snapshot_path(&self.dir, self.pid)
+    }
+
+    /// Newest snapshot left by a session that is no longer running.
+    ///
+    /// Meant for startup, before the first auto-save: a file under our own pid
+    /// can only be a leftover from an earlier process that had the same pid.
+    pub fn find_orphan(&self) -> Option<PathBuf> {
+        fs::read_dir(&self.dir)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| {
+                let pid = entry
+                    .file_name()
+                    .to_str()?
+                    .strip_prefix(PREFIX)?
+                    .strip_suffix(".json")?
+                    .parse::<u32>()
+                    .ok()?;
+                let orphan = pid == self.pid || lock_is_free(&self.dir, pid);
+                let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+                orphan.then(|| (modified, entry.path()))
+            })
+            .max_by_key(|(modified, _)| *modified)
+            .map(|(_, path)| path)
+    }
+
+    /// Serialize and write a snapshot on a background thread.
+    ///
+    /// `serialize` runs off the caller's thread; unchanged output is not
+    /// rewritten. Goes through a temp file + rename so a crash mid-write never
+    /// leaves a truncated snapshot. Returns `None` (skipped) while the previous
+    /// write is still running.
+    pub fn save_in_background<F>(&self, serialize: F) -> Option<JoinHandle<()>>
+    where
+        F: FnOnce() -> Result<String, String> + Send + 'static,
+    {
+        if self.writing.swap(true, Ordering::AcqRel) {
+            return None;
+        }
+        let path = self.path();
+        let last_hash = Arc::clone(&self.last_hash);
+        let writing = Arc::clone(&self.writing);
+        let spawned = thread::Builder::new()
+            .name("playa-autosave".to_string())
+            .spawn(move || {
+                write_snapshot(&path, serialize, &last_hash);
+                writing.store(false, Ordering::Release);
+            });
+        match spawned {
+            Ok(handle) => Some(handle),
+            Err(e) => {
+                warn!("Auto-save: failed to start writer thread: {}", e);
+                self.writing.store(false, Ordering::Release);
+                None
+            }
+        }
+    }
+
+    /// Take over a restored orphan snapshot as this instance's file. Forces the
+    /// next save to write even if the content is unchanged.
+    pub fn adopt(&self, snapshot: &Path) {
+        let own = self.path();
+        if snapshot != own {
+            match fs::rename(snapshot, &own) {
+                Ok(()) => remove_quiet(&snapshot.with_extension("lock")),
+                Err(e) => {
+                    warn!(
+                        "Failed to adopt recovery file {}: {}",
+                        snapshot.display(),
+                        e
+                    );
+                    self.discard(snapshot);
+                }
+            }
+        }
+        *self.last_hash.lock().unwrap_or_else(|e| e.into_inner()) = None;
+    }
+
+    /// Delete this instance's snapshot (clean exit).
+    pub fn remove(&self) {
+        remove_quiet(&self.path());
+    }
+
+    /// Delete an orphaned snapshot (restored or discarded) and its lock file.
+    pub fn discard(&self, snapshot: &Path) {
+        remove_quiet(snapshot);
+        if snapshot != self.path() {
+            remove_quiet(&snapshot.with_extension("lock"));
+        }
+    }
+}
+
+impl Drop for RecoveryStore {
+    fn drop(&mut self) {
+        // Unlock before deleting (an open file can't be removed on Windows)
+        if self.lock.take().is_some() {
+            remove_quiet(&lock_path(&self.dir, self.pid));
+        }
+    }
+}
+
+fn snapshot_path(dir: &Path, pid: u32) -> PathBuf {
+    dir.join(format!("{}{}.json", PREFIX, pid))
+}
+
+fn lock_path(dir: &Path, pid: u32) -> PathBuf {
+    dir.join(format!("{}{}.lock", PREFIX, pid))
+}
+
+/// The owner of `pid`'s lock is gone (we could take the lock ourselves).
+fn lock_is_free(dir: &Path, pid: u32) -> bool {
+    match File::open(lock_path(dir, pid)) {
+        // Dropped right away: only probing
+        Ok(file) => file.try_lock().is_ok(),
+        Err(_) => true,
+    }
+}
+
+fn write_snapshot<F>(path: &Path, serialize: F, last_hash: &Mutex<Option<u64>>)
+where
+    F: FnOnce() -> Result<String, String>,
+{
+    let json = match serialize() {
+        Ok(json) => json,
+        Err(e) => {
+            warn!("Auto-save: serialize failed: {}", e);
+            return;
+        }
+    };
+    let mut hasher = DefaultHasher::new();
+    json.hash(&mut hasher);
+    let hash = hasher.finish();
+    let mut last = last_hash.lock().unwrap_or_else(|e| e.into_inner());
+    if *last == Some(hash) {
+        return;
+    }
+
+    let tmp = path.with_extension("json.tmp");
+    match fs::write(&tmp, json).and_then(|_| fs::rename(&tmp, path)) {
+        Ok(()) => {
+            *last = Some(hash);
+            trace!("Auto-saved recovery snapshot to {}", path.display());
+        }
+        Err(e) => warn!("Auto-save to {} failed: {}", path.display(), e),
+    }
+}
+
+fn remove_quiet(path: &Path) {
+    if let Err(e) = fs::remove_file(path)
+        && e.kind() != io::ErrorKind::NotFound
+    {
+        warn!("Failed to remove recovery file {}: {}", path.display(), e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("playa_{}_{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn save(store: &RecoveryStore, json: &str) {
+        let json = json.to_string();
+        store
+            .save_in_background(move || Ok(json))
+            .expect("no write in flight")
+            .join()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_save_detect_restore_round_trip() {
+        let dir = temp_dir("recovery_round_trip");
+
+        let crashed = RecoveryStore::open_as(&dir, 1001).unwrap();
+        save(&crashed, r#"{"session":1}"#);
+        let snapshot = crashed.path();
+        // Crash: the OS drops the lock, the files stay behind
+        let mut crashed = crashed;
+        drop(crashed.lock.take());
+        drop(crashed);
+
+        let next = RecoveryStore::open_as(&dir, 1002).unwrap();
+        assert_eq!(next.find_orphan(), Some(snapshot.clone()));
+        assert_eq!(fs::read_to_string(&snapshot).unwrap(), r#"{"session":1}"#);
+
+        // Restored: the snapshot becomes ours, the dead session's files go away
+        next.adopt(&snapshot);
+        assert!(!snapshot.exists());
+        assert!(!lock_path(&dir, 1001).exists());
+        assert_eq!(fs::read_to_string(next.path()).unwrap(), r#"{"session":1}"#);
+
+        // Clean exit leaves nothing behind
+        save(&next, r#"{"session":2}"#);
+        assert_eq!(fs::read_to_string(next.path()).unwrap(), r#"{"session":2}"#);
+        next.remove();
+        drop(next);
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_live_instance_is_not_an_orphan() {
+        let dir = temp_dir("recovery_live");
+
+        let running = RecoveryStore::open_as(&dir, 2001).unwrap();
+        save(&running, r#"{"session":1}"#);
+        assert!(RecoveryStore::open_as(&dir, 2001).is_err());
+
+        let other = RecoveryStore::open_as(&dir, 2002).unwrap();
+        assert_eq!(other.find_orphan(), None);
+
+        // Discarded orphans are removed with their lock file
+        fs::write(snapshot_path(&dir, 2003), "{}").unwrap();
+        assert_eq!(other.find_orphan(), Some(snapshot_path(&dir, 2003)));
+        other.discard(&snapshot_path(&dir, 2003));
+        assert_eq!(other.find_orphan(), None);
+        save(&other, r#"{"session":2}"#);
+        // Each instance writes its own file
+        assert_eq!(
+            fs::read_to_string(running.path()).unwrap(),
+            r#"{"session":1}"#
+        );
+
+        drop((running, other));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -12,8 +12,10 @@ use eframe::egui;
 use egui_dock::DockArea;
 use log::{info, trace};
 
-use crate::app::api::WindowScreenshotWaiters;
+use crate::app::api::{ScreenshotKind, WindowScreenshotWaiters};
 use crate::app::{DockTabs, PlayaApp};
+use crate::server::FRAME_CAPTURE_TIMEOUT;
+use playa_ui::dialogs::goto_frame::GotoFrameResult;
 use playa_ui::dialogs::prefs::render_settings_window;
 
 impl eframe::App for PlayaApp {
@@ -49,7 +51,7 @@ impl eframe::App for PlayaApp {
                 .lock()
                 .unwrap_or_else(|e| e.into_inner())
                 .set_output_format(rs.target_format);
-            self.update_compositor_backend(&rs.device, &rs.queue);
+            self.update_compositor_backend(rs);
         }
 
         self.consume_egui_screenshots(ctx);
@@ -77,13 +79,9 @@ impl eframe::App for PlayaApp {
         }
 
         // Apply theme based on settings - skip if unchanged
-        if self.last_applied_dark_mode != Some(self.settings.dark_mode) {
-            if self.settings.dark_mode {
-                ctx.set_visuals(egui::Visuals::dark());
-            } else {
-                ctx.set_visuals(egui::Visuals::light());
-            }
-            self.last_applied_dark_mode = Some(self.settings.dark_mode);
+        if self.last_applied_theme != Some(self.settings.theme) {
+            ctx.set_visuals(self.settings.theme.visuals());
+            self.last_applied_theme = Some(self.settings.theme);
         }
 
         // Apply font size from settings - skip if unchanged (cloning style is expensive)
@@ -130,6 +128,12 @@ impl eframe::App for PlayaApp {
             self.applied_mem_fraction = mem_fraction;
         }
 
+        // Frame pacing pref: wall-clock cadence (drops frames) or every frame
+        if self.player.lock_to_fps() != self.settings.playback.lock_to_fps {
+            self.player
+                .set_lock_to_fps(self.settings.playback.lock_to_fps);
+        }
+
         // Unified frame change path: scrub/API emit `SetFrameEvent`; playback emits after advance.
         // Actual playhead mutation goes through `Project::modify_comp`, which emits
         // `CurrentFrameChangedEvent` for preload (see `events.rs`).
@@ -143,6 +147,12 @@ impl eframe::App for PlayaApp {
         // Handle composition events (SetFrame epoch/scrub; preload via CurrentFrameChangedEvent)
         self.handle_events();
 
+        // Proxy while playing/scrubbing, full res once the playhead settles
+        self.sync_proxy_level();
+        if self.project.proxy_level() != playa_engine::entities::ProxyLevel::Full {
+            ctx.request_repaint_after(std::time::Duration::from_millis(250));
+        }
+
         // Update REST API state and handle commands from remote clients
         self.update_api_state();
         self.handle_api_commands();
@@ -155,19 +165,18 @@ impl eframe::App for PlayaApp {
             self.enqueue_frame_loads_around_playhead(self.settings.playback.preload_radius);
         }
 
-        // Handle drag-and-drop files/folders - queue for async loading
-        ctx.input(|i| {
-            let mut dropped: Vec<std::path::PathBuf> = Vec::new();
-            for file in &i.raw.dropped_files {
-                if let Some(path) = &file.path {
-                    dropped.push(path.clone());
-                }
-            }
-            if !dropped.is_empty() {
-                info!("Files dropped: {:?}", dropped);
-                let _ = self.load_sequences(dropped);
-            }
+        // Handle drag-and-drop files/folders: one comp per detected sequence
+        let dropped: Vec<std::path::PathBuf> = ctx.input(|i| {
+            i.raw
+                .dropped_files
+                .iter()
+                .filter_map(|file| file.path.clone())
+                .collect()
         });
+        if !dropped.is_empty() {
+            info!("Files dropped: {:?}", dropped);
+            self.load_dropped(dropped);
+        }
 
         // Request repaint if:
         // 1. Playing (continuous animation)
@@ -191,6 +200,8 @@ impl eframe::App for PlayaApp {
                 &self.viewport_state,
                 self.last_render_time_ms,
                 cache_mgr.as_ref(),
+                &self.workers,
+                self.timeline_state.time_display,
                 |evt| self.event_bus.emit_boxed(evt),
             );
         }
@@ -412,9 +423,38 @@ impl eframe::App for PlayaApp {
             }
         }
 
+        // Go-to-frame prompt (Ctrl+G); the target is already clamped to the comp
+        if let Some(dialog) = self.goto_frame.as_mut() {
+            match dialog.show(ctx) {
+                GotoFrameResult::Open => {}
+                GotoFrameResult::Cancelled => self.goto_frame = None,
+                GotoFrameResult::Go(frame) => {
+                    self.goto_frame = None;
+                    self.event_bus
+                        .emit(playa_engine::core::player_events::SetFrameEvent(frame));
+                }
+            }
+        }
+
+        // Crash recovery: restore prompt, then periodic auto-save (paused while
+        // the prompt is up so the old snapshot isn't overwritten)
+        self.render_recovery_prompt(ctx);
+        self.render_gpu_fallback_notice(ctx);
+        let autosave_interval = self.settings.autosave_interval_secs;
+        if autosave_interval > 0 && self.pending_recovery.is_none() {
+            let interval = std::time::Duration::from_secs(autosave_interval as u64);
+            if self.last_autosave.elapsed() >= interval {
+                self.last_autosave = std::time::Instant::now();
+                self.autosave_recovery();
+            }
+        }
+
         // Apply settings that affect runtime infrastructure/state.
         // This must not depend on "Settings window opened".
         self.apply_cache_strategy_if_changed();
+        self.apply_workers_if_changed();
+        self.project
+            .set_max_preload_radius(self.settings.cache.max_preload_radius);
 
         // Handle queued screenshot requests after UI + egui primitives are finalized for this tick.
         self.handle_pending_screenshots(ctx);
@@ -425,11 +465,30 @@ impl eframe::App for PlayaApp {
         // Gather all settings from components
         self.settings.playback.fps_base = self.player.fps_base();
         self.settings.playback.loop_enabled = self.player.loop_enabled();
+        self.settings.playback.sequence_playback = self
+            .cli_sequence_playback_pref
+            .unwrap_or_else(|| self.player.sequence_playback());
         self.settings.current_shader = self.shader_manager.current_shader.clone();
         self.settings.show_help = self.show_help;
         self.settings.show_playlist = self.show_playlist;
         self.settings.show_attributes_editor = self.show_attributes_editor;
 
+        // Vsync is read before the window opens, so it lives in its own file
+        let display = crate::config::DisplayConfig {
+            vsync: self.settings.playback.vsync,
+        };
+        if let Err(e) = display.save(&self.path_config) {
+            log::warn!("Display config not saved: {:#}", e);
+        }
+
+        // A --background-color backdrop is saved as the prefs value it replaced
+        let session_background = self.settings.viewport.viewport_background;
+        if let Some((cli, pref)) = self.cli_background
+            && session_background == cli
+        {
+            self.settings.viewport.viewport_background = pref;
+        }
+
         // Serialize and save app settings
         if let Ok(json) = serde_json::to_string(self) {
             storage.set_string(eframe::APP_KEY, json);
@@ -438,6 +497,7 @@ impl eframe::App for PlayaApp {
                 self.settings.playback.fps_base, self.settings.playback.loop_enabled, self.settings.current_shader
             );
         }
+        self.settings.viewport.viewport_background = session_background;
     }
 
     /// Cleanup on application exit.
@@ -474,6 +534,13 @@ impl eframe::App for PlayaApp {
         let mut renderer = self.viewport_renderer.lock().unwrap_or_else(|e| e.into_inner());
         renderer.destroy();
         trace!("ViewportRenderer GPU resources cleaned up");
+        drop(renderer);
+
+        // Clean exit: the crash-recovery snapshot is no longer needed. Keep it if
+        // the restore prompt was never answered, so it's offered again next start.
+        if self.pending_recovery.is_none() {
+            self.remove_recovery_file();
+        }
     }
 }
 
@@ -495,6 +562,7 @@ impl PlayaApp {
         self.player.reset_settings();
         self.viewport_state = playa_ui::widgets::viewport::ViewportState::new();
         self.shader_manager.reset_settings();
+        self.hotkey_handler.reset_to_defaults();
 
         // Reset window size
         ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(egui::vec2(1280.0, 720.0)));
@@ -509,10 +577,37 @@ impl PlayaApp {
     }
 
     /// Update compositor backend based on settings (CPU vs wgpu offload path).
-    pub fn update_compositor_backend(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+    ///
+    /// The GPU compositor is only used when it works: `--force-cpu`, a
+    /// software adapter (RDP, VMs) or a failed self-test keep the CPU
+    /// compositor for the whole session, on the main thread and the workers
+    /// alike, and the user is told once.
+    pub fn update_compositor_backend(&mut self, rs: &eframe::egui_wgpu::RenderState) {
         use playa_engine::entities::compositor::{CompositorType, CpuCompositor};
         use playa_engine::render_gpu::WgpuCompositor;
 
+        if !self.gpu_probed {
+            self.gpu_probed = true;
+            let info = rs.adapter.get_info();
+            info!(
+                "GPU adapter: {} ({:?}, {:?}), driver: {} {}",
+                info.name, info.backend, info.device_type, info.driver, info.driver_info
+            );
+            if self.force_cpu {
+                info!("--force-cpu: compositing on the CPU");
+                self.gpu_unavailable = Some("--force-cpu".to_string());
+            } else if info.device_type == wgpu::DeviceType::Cpu {
+                self.gpu_unavailable = Some(format!("software renderer ({})", info.name));
+            }
+        }
+        let wants_gpu = matches!(
+            self.settings.compositor_backend,
+            playa_events::CompositorBackend::Gpu
+        );
+        if wants_gpu && !self.force_cpu && self.gpu_unavailable.is_some() {
+            self.warn_gpu_fallback();
+        }
+
         let current_is_cpu = matches!(
             *self
                 .project
@@ -521,26 +616,75 @@ impl PlayaApp {
                 .unwrap_or_else(|e| e.into_inner()),
             CompositorType::Cpu(_)
         );
-        let desired_is_cpu = matches!(
-            self.settings.compositor_backend,
-            playa_events::CompositorBackend::Cpu
-        );
+        let desired_is_cpu = self.gpu_unavailable.is_some() || !wants_gpu;
 
         if current_is_cpu != desired_is_cpu {
-            info!(
-                "Switching compositor to: {:?}",
-                self.settings.compositor_backend
-            );
-            let new_backend = match self.settings.compositor_backend {
-                playa_events::CompositorBackend::Cpu => CompositorType::Cpu(CpuCompositor),
-                playa_events::CompositorBackend::Gpu => {
-                    CompositorType::Wgpu(WgpuCompositor::new(device, queue))
+            let new_backend = if desired_is_cpu {
+                info!("Switching compositor to: Cpu");
+                CompositorType::Cpu(CpuCompositor)
+            } else {
+                // wgpu reports device errors by panicking in the default handler
+                let probe = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                    let mut gpu = WgpuCompositor::new(&rs.device, &rs.queue);
+                    gpu.self_test().map(|_| gpu)
+                }));
+                match probe {
+                    Ok(Ok(gpu)) => {
+                        info!("Switching compositor to: Gpu");
+                        CompositorType::Wgpu(gpu)
+                    }
+                    Ok(Err(e)) => {
+                        self.gpu_unavailable = Some(format!("self-test failed: {}", e));
+                        self.warn_gpu_fallback();
+                        return;
+                    }
+                    Err(_) => {
+                        self.gpu_unavailable = Some("initialization failed".to_string());
+                        self.warn_gpu_fallback();
+                        return;
+                    }
                 }
             };
             self.project.set_compositor(new_backend);
         }
     }
 
+    /// Log the GPU fallback and raise the notice, once per session.
+    fn warn_gpu_fallback(&mut self) {
+        if self.gpu_fallback_warned {
+            return;
+        }
+        self.gpu_fallback_warned = true;
+        self.gpu_fallback_notice = true;
+        log::warn!(
+            "GPU compositor unavailable ({}), compositing on the CPU",
+            self.gpu_unavailable.as_deref().unwrap_or("unknown")
+        );
+    }
+
+    /// One-time notice that compositing fell back to the CPU.
+    pub fn render_gpu_fallback_notice(&mut self, ctx: &egui::Context) {
+        if !self.gpu_fallback_notice {
+            return;
+        }
+        let reason = self.gpu_unavailable.as_deref().unwrap_or("unknown");
+        let mut dismiss = false;
+        egui::Window::new("GPU Compositing Unavailable")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(format!("The GPU compositor can't be used: {}.", reason));
+                ui.label("Compositing runs on the CPU instead: the viewport works, but slower.");
+                ui.label("Start with --force-cpu to skip the GPU check.");
+                ui.add_space(8.0);
+                dismiss = ui.button("OK").clicked();
+            });
+        if dismiss {
+            self.gpu_fallback_notice = false;
+        }
+    }
+
     /// Apply cache strategy changes from settings.
     pub fn apply_cache_strategy_if_changed(&mut self) {
         let desired = self.settings.cache.cache_strategy;
@@ -555,6 +699,25 @@ impl PlayaApp {
         self.applied_cache_strategy = desired;
     }
 
+    /// Resize the worker pool when the prefs override changes.
+    ///
+    /// Tracks the prefs value rather than the pool size, so a `--workers`
+    /// count from the command line holds until the user edits the pref.
+    pub fn apply_workers_if_changed(&mut self) {
+        let desired = match self.settings.workers_override {
+            0 => None,
+            n => Some(n as usize),
+        };
+        if desired == self.applied_workers {
+            return;
+        }
+
+        let threads = desired.unwrap_or_else(super::default_worker_count);
+        log::info!("Worker threads changed to: {}", threads);
+        self.workers.resize(threads);
+        self.applied_workers = desired;
+    }
+
     /// Full-window grabs use [`egui::ViewportCommand::Screenshot`] (decoded in
     /// [`PlayaApp::consume_egui_screenshots`]); raw-pixel grabs stay CPU-only (`capture_raw_frame`).
     fn handle_pending_screenshots(&mut self, ctx: &egui::Context) {
@@ -564,20 +727,43 @@ impl PlayaApp {
 
         let all_waiters: Vec<_> = std::mem::take(&mut self.pending_screenshots);
 
+        // PNG waiters hold until their frame is rendered (seek lands next tick)
+        let current_frame = self.player.current_frame(&self.project);
+        let frame_loaded = self
+            .frame
+            .as_ref()
+            .is_some_and(|f| f.status() == playa_engine::entities::frame::FrameStatus::Loaded);
+
         let mut window_waiters = Vec::new();
         let mut frame_waiters = Vec::new();
-        for (viewport_only, sender) in all_waiters {
-            if viewport_only {
-                window_waiters.push(sender);
-            } else {
-                frame_waiters.push(sender);
+        let mut png_waiters = Vec::new();
+        for (kind, sender) in all_waiters {
+            match kind {
+                ScreenshotKind::Window => window_waiters.push(sender),
+                ScreenshotKind::FrameJpeg => frame_waiters.push(sender),
+                ScreenshotKind::FramePng { frame, queued } => {
+                    let target = frame.unwrap_or(current_frame);
+                    if frame_loaded
+                        && target == current_frame
+                        && self.viewport_state.last_rendered_frame == Some(target)
+                    {
+                        png_waiters.push(sender);
+                    } else if queued.elapsed() < FRAME_CAPTURE_TIMEOUT {
+                        self.pending_screenshots.push((kind, sender));
+                    }
+                    // else: the HTTP side already answered 503
+                }
             }
         }
 
+        if window_waiters.is_empty() && frame_waiters.is_empty() && png_waiters.is_empty() {
+            return;
+        }
         log::info!(
-            "Screenshot: {} window + {} frame waiters",
+            "Screenshot: {} window + {} frame + {} png waiters",
             window_waiters.len(),
-            frame_waiters.len()
+            frame_waiters.len(),
+            png_waiters.len()
         );
 
         let frame_result = if frame_waiters.is_empty() {
@@ -590,6 +776,12 @@ impl PlayaApp {
                 let _ = waiter.send(result.clone());
             }
         }
+        if !png_waiters.is_empty() {
+            let result = self.capture_raw_frame_png();
+            for waiter in png_waiters {
+                let _ = waiter.send(result.clone());
+            }
+        }
 
         if !window_waiters.is_empty() {
             ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::new(
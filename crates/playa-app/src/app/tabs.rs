@@ -6,6 +6,8 @@
 //! - Project: file browser + sequences
 //! - Attributes: property editor
 //! - NodeEditor: visual composition graph
+//! - Cache: cache memory / hit rate / eviction stats
+//! - Metadata: source file header of the displayed frame
 //!
 //! Also includes DockTabs wrapper for egui_dock TabViewer.
 
@@ -13,6 +15,7 @@ use eframe::egui;
 use egui_dock::TabViewer;
 
 use crate::app::{DockTab, PlayaApp};
+use playa_engine::entities::NodeKind;
 use playa_engine::entities::node::Node;
 use playa_ui::ui;
 use playa_ui::widgets;
@@ -20,10 +23,49 @@ use playa_ui::widgets::node_editor::render_node_editor;
 use playa_ui::widgets::viewport::ViewportRefreshEvent;
 
 impl PlayaApp {
+    /// Render cache statistics tab (memory, hit rate, evictions).
+    pub fn render_cache_stats_tab(&mut self, ui: &mut egui::Ui) {
+        let comp = self.player.active_comp();
+        widgets::cache_stats::cache_stats::render(
+            ui,
+            &mut self.cache_stats_state,
+            Some(&self.cache_manager),
+            self.project.global_cache.as_ref(),
+            comp,
+            self.workers.num_threads(),
+        );
+    }
+
+    /// Render metadata tab: header of the file behind the displayed frame,
+    /// plus the displayed frame's content hash on request.
+    pub fn render_metadata_tab(&mut self, ui: &mut egui::Ui) {
+        let path = self.player.active_comp().and_then(|uuid| {
+            let frame = self.player.current_frame(&self.project);
+            let media = self.project.media.read().expect("media lock poisoned");
+            match media.get(&uuid)?.as_ref() {
+                NodeKind::Comp(comp) => comp.source_path_at(frame, &media),
+                NodeKind::File(file) => file.frame_path(frame),
+                _ => None,
+            }
+        });
+        widgets::metadata::metadata::render(
+            ui,
+            &mut self.metadata_state,
+            path.as_deref(),
+            self.frame.as_ref(),
+        );
+    }
+
     /// Render project browser tab.
     /// Dispatches project actions (file open, sequence select) to event bus.
     pub fn render_project_tab(&mut self, ui: &mut egui::Ui) {
-        let project_actions = widgets::project::render(ui, &mut self.player, &self.project);
+        let project_actions = widgets::project::render(
+            ui,
+            &mut self.player,
+            &self.project,
+            &mut self.settings.recent_items,
+            self.settings.project_thumbnail_size,
+        );
 
         // Store hover state for input routing
         self.project_hovered = project_actions.hovered;
@@ -40,6 +82,7 @@ impl PlayaApp {
         // Sync timeline toggles from settings
         self.timeline_state.snap_enabled = self.settings.timeline.timeline_snap_enabled;
         self.timeline_state.lock_work_area = self.settings.timeline.timeline_lock_work_area;
+        self.timeline_state.time_display = self.settings.timeline.timeline_time_display;
 
         // Collect layout names for ComboBox
         let layout_names: Vec<String> = self.settings.layouts.keys().cloned().collect();
@@ -49,6 +92,7 @@ impl PlayaApp {
             ui,
             &mut self.player,
             &self.project,
+            &self.workers,
             &mut self.shader_manager,
             &mut self.timeline_state,
             &self.event_bus,
@@ -67,6 +111,9 @@ impl PlayaApp {
         if shader_changed {
             let mut renderer = self.viewport_renderer.lock().unwrap();
             playa_ui::widgets::viewport::update_tonemap(&mut renderer, &self.shader_manager);
+            drop(renderer);
+            self.settings.current_shader = self.shader_manager.current_shader.clone();
+            self.viewport_state.request_refresh();
             log::info!("Shader changed to: {}", self.shader_manager.current_shader);
         }
     }
@@ -84,10 +131,15 @@ impl PlayaApp {
         let epoch_changed = self.viewport_state.last_rendered_epoch != current_epoch;
         let frame_changed = self.viewport_state.last_rendered_frame != Some(current_frame);
         // Check if frame is not fully ready (needs refresh when worker finishes)
+        // or was rendered at a different proxy level (e.g. proxy frame after pause)
+        let proxy_factor = self.project.proxy_level().factor();
         let frame_not_ready = self
             .frame
             .as_ref()
-            .map(|f| f.status() != playa_engine::entities::frame::FrameStatus::Loaded)
+            .map(|f| {
+                f.status() != playa_engine::entities::frame::FrameStatus::Loaded
+                    || f.proxy_factor() != proxy_factor
+            })
             .unwrap_or(true);
         // Also re-fetch if we have no frame yet (workers may have cached it)
         let no_frame = self.frame.is_none();
@@ -110,6 +162,7 @@ impl PlayaApp {
 
         // Display frame directly - Expired frames show valid pixels while recomputing
         let display_frame = self.frame.as_ref();
+        let panel_rect = ui.max_rect();
 
         let (viewport_actions, render_time) = widgets::viewport::render(
             ui,
@@ -122,16 +175,44 @@ impl PlayaApp {
             &mut self.shader_manager,
             &mut self.gizmo_state,
             self.show_help,
-            self.is_fullscreen,
             texture_needs_upload,
             self.settings.viewport.viewport_hover_highlight,
             self.settings.viewport.tools_selection_highlight,
             self.settings.viewport.hover_stroke_width,
             self.settings.viewport.hover_corner_length,
             self.settings.viewport.hover_opacity,
+            self.settings.viewport.viewport_background,
+            self.settings.viewport.grid_style(),
+            self.settings.viewport.zebra_thresholds(),
         );
         self.last_render_time_ms = render_time;
 
+        // Empty state: offer recent files/projects instead of a blank viewport
+        if self.frame.is_none()
+            && self.error_msg.is_none()
+            && !self.settings.recent_items.is_empty()
+        {
+            let mut recent_actions = widgets::project::ProjectActions::new();
+            egui::Area::new(ui.id().with("viewport_recent_items"))
+                .fixed_pos(panel_rect.center() - egui::vec2(160.0, 100.0))
+                .show(ui.ctx(), |ui| {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.label(egui::RichText::new("Recent").strong());
+                        ui.separator();
+                        widgets::project::render_recent_items(
+                            ui,
+                            &self.settings.recent_items,
+                            &mut recent_actions,
+                        );
+                        ui.separator();
+                        ui.weak("Drop files here or double-click to browse");
+                    });
+                });
+            for evt in recent_actions.events {
+                self.event_bus.emit_boxed(evt);
+            }
+        }
+
         // Store hover state for input routing
         self.viewport_hovered = viewport_actions.hovered;
 
@@ -143,6 +224,7 @@ impl PlayaApp {
         // Persist timeline options back to settings
         self.settings.timeline.timeline_snap_enabled = self.timeline_state.snap_enabled;
         self.settings.timeline.timeline_lock_work_area = self.timeline_state.lock_work_area;
+        self.settings.timeline.timeline_time_display = self.timeline_state.time_display;
     }
 
     /// Render node editor tab (composition as node graph).
@@ -185,18 +267,29 @@ impl PlayaApp {
 
         // If ae_focus is empty, fallback to active comp attrs
         if ae_focus.is_empty() {
-            if let Some(comp_uuid) = active {
+            // Edit a copy so the comp is only touched (and an undo step
+            // recorded) when the editor actually changed something
+            let current = active.and_then(|comp_uuid| {
+                self.project.with_comp(comp_uuid, |comp| {
+                    (comp_uuid, comp.name().to_string(), comp.attrs.clone())
+                })
+            });
+            if let Some((comp_uuid, comp_name, mut attrs)) = current
+                && playa_ui::widgets::ae::render(
+                    ui,
+                    &mut attrs,
+                    &mut self.attributes_state,
+                    &comp_name,
+                )
+            {
+                let pending = self
+                    .history
+                    .begin(&self.project, "Attributes", &[comp_uuid]);
                 self.project.modify_comp(comp_uuid, |comp| {
-                    let comp_name = comp.name().to_string();
-                    if playa_ui::widgets::ae::render(
-                        ui,
-                        &mut comp.attrs,
-                        &mut self.attributes_state,
-                        &comp_name,
-                    ) {
-                        comp.emit_attrs_changed();
-                    }
+                    comp.attrs = attrs;
+                    comp.emit_attrs_changed();
                 });
+                self.history.commit(&self.project, pending);
             }
             return;
         }
@@ -497,17 +590,20 @@ impl PlayaApp {
             // Single node - edit directly
             let node_uuid = ae_focus[0];
             let mut node_changed = false;
-            self.project.modify_node(node_uuid, |node| {
-                let name = node.name().to_string();
-                if playa_ui::widgets::ae::render(
-                    ui,
-                    node.attrs_mut(),
-                    &mut self.attributes_state,
-                    &name,
-                ) {
-                    node_changed = true;
-                }
+            let current = self.project.with_node(node_uuid, |node| {
+                (node.name().to_string(), node.attrs().clone())
             });
+            if let Some((name, mut attrs)) = current
+                && playa_ui::widgets::ae::render(ui, &mut attrs, &mut self.attributes_state, &name)
+            {
+                let pending = self
+                    .history
+                    .begin(&self.project, "Attributes", &[node_uuid]);
+                self.project
+                    .modify_node(node_uuid, |node| *node.attrs_mut() = attrs);
+                self.history.commit(&self.project, pending);
+                node_changed = true;
+            }
 
             // AINode footer — actions (Generate / Set Active / Delete)
             // and the read-only Generations history list. The standard
@@ -537,6 +633,7 @@ impl PlayaApp {
 
     /// Render attributes for multiple selected nodes.
     fn render_multi_node_attributes(&mut self, ui: &mut egui::Ui, ae_focus: &[uuid::Uuid]) {
+        use playa_engine::entities::comp_events::SetNodeAttrsEvent;
         use std::collections::{BTreeSet, HashSet};
 
         let mut common_keys: BTreeSet<String> = BTreeSet::new();
@@ -594,23 +691,30 @@ impl PlayaApp {
             &mut changed,
         );
 
-        // Apply changed attrs to all selected nodes
+        // Apply changed attrs to all selected nodes (mixed keys are only
+        // written once the user edits them)
         if !changed.is_empty() {
-            for uuid in ae_focus {
-                self.project.modify_node(*uuid, |node| {
-                    for (key, value) in &changed {
-                        node.attrs_mut().set(key.clone(), value.clone());
+            let attrs: Vec<(String, serde_json::Value)> = changed
+                .into_iter()
+                .filter_map(|(k, v)| match serde_json::to_value(&v) {
+                    Ok(j) => Some((k, j)),
+                    Err(err) => {
+                        log::warn!("[AE] node attr JSON {:?}: {}", k, err);
+                        None
                     }
-                });
+                })
+                .collect();
+            if !attrs.is_empty() {
+                self.event_bus.emit_boxed(Box::new(SetNodeAttrsEvent {
+                    node_uuids: ae_focus.to_vec(),
+                    attrs,
+                }));
             }
-
-            // Invalidate all modified sources + their dependents
-            self.invalidate_and_refresh(ae_focus);
         }
     }
 
     /// Invalidate cache for modified nodes and trigger refresh.
-    fn invalidate_and_refresh(&mut self, uuids: &[uuid::Uuid]) {
+    pub(super) fn invalidate_and_refresh(&mut self, uuids: &[uuid::Uuid]) {
         // Cancel all pending preload jobs (they'd load stale data)
         if let Some(manager) = self.project.cache_manager() {
             manager.increment_epoch();
@@ -674,6 +778,8 @@ impl<'a> TabViewer for DockTabs<'a> {
                     "Jobs".into()
                 }
             }
+            DockTab::CacheStats => "Cache".into(),
+            DockTab::Metadata => "Metadata".into(),
         }
     }
 
@@ -701,6 +807,8 @@ impl<'a> TabViewer for DockTabs<'a> {
             ];
             #[cfg(feature = "jobs")]
             all.push(DockTab::Jobs);
+            all.push(DockTab::CacheStats);
+            all.push(DockTab::Metadata);
 
             for variant in all {
                 let open = self.open_tabs.contains(&variant);
@@ -712,6 +820,8 @@ impl<'a> TabViewer for DockTabs<'a> {
                     DockTab::NodeEditor => "Node Editor",
                     #[cfg(feature = "jobs")]
                     DockTab::Jobs => "Jobs",
+                    DockTab::CacheStats => "Cache",
+                    DockTab::Metadata => "Metadata",
                 };
                 let prefix = if open { "✓ " } else { "  " };
                 let resp = ui.add_enabled(
@@ -742,6 +852,8 @@ impl<'a> TabViewer for DockTabs<'a> {
             DockTab::NodeEditor => self.app.render_node_editor_tab(ui),
             #[cfg(feature = "jobs")]
             DockTab::Jobs => self.app.render_jobs_tab(ui),
+            DockTab::CacheStats => self.app.render_cache_stats_tab(ui),
+            DockTab::Metadata => self.app.render_metadata_tab(ui),
         }
     }
 }
@@ -29,7 +29,8 @@ pub struct Args {
     #[arg(short = 'f', long = "file", value_name = "FILE")]
     pub files: Vec<PathBuf>,
 
-    /// Load playlist from JSON file
+    /// Load a review playlist (.m3u-like text or JSON item list; plays items
+    /// back to back) or a saved project JSON
     #[arg(short = 'p', long = "playlist", value_name = "PLAYLIST")]
     pub playlist: Option<PathBuf>,
 
@@ -69,6 +70,57 @@ pub struct Args {
     #[arg(short = 'v', long = "verbose", action = clap::ArgAction::Count)]
     pub verbosity: u8,
 
+    /// Hold single still images for N frames (overrides the prefs default)
+    #[arg(long = "still-frames", value_name = "N", value_parser = clap::value_parser!(i32).range(1..))]
+    pub still_frames: Option<i32>,
+
+    /// Encode without opening a window, then exit (needs --encode-output;
+    /// non-zero exit status on failure)
+    #[arg(long = "headless", requires = "encode_output")]
+    pub headless: bool,
+
+    /// Output of a headless encode: .mp4/.mov/.webm video, or an image
+    /// sequence pattern such as out.####.exr (exr, png, jpg, tif, tga)
+    #[arg(long = "encode-output", value_name = "PATH")]
+    pub encode_output: Option<PathBuf>,
+
+    /// Write a per-frame content hash manifest (OUT.hashes.json) next to a
+    /// headless image sequence, for QC with --verify-hashes
+    #[arg(long = "write-hashes", requires = "headless")]
+    pub write_hashes: bool,
+
+    /// Render the work area without a window and compare each frame's
+    /// content hash against MANIFEST; non-zero exit status on any mismatch
+    #[arg(
+        long = "verify-hashes",
+        value_name = "MANIFEST",
+        conflicts_with = "headless"
+    )]
+    pub verify_hashes: Option<PathBuf>,
+
+    /// Video codec for --encode-output (h264, h265, av1, prores, vp9, dnxhr;
+    /// default by container)
+    #[arg(long = "codec", value_name = "CODEC", value_parser = parse_codec)]
+    pub codec: Option<playa_ui::dialogs::encode::VideoCodec>,
+
+    /// Run commands from a startup script (one per line: load, seek N, play, encode PATH, ...)
+    #[arg(long = "script", value_name = "PATH")]
+    pub script: Option<PathBuf>,
+
+    /// Playback fps for image sequences and stills (overrides the prefs default)
+    #[arg(long = "fps", value_name = "FPS", value_parser = parse_fps)]
+    pub fps: Option<f32>,
+
+    /// Viewport background color as hex RRGGBB (e.g. 767676 for 18% gray), for
+    /// this session only
+    #[arg(long = "background-color", value_name = "RRGGBB", value_parser = parse_rgb_hex)]
+    pub background_color: Option<[u8; 3]>,
+
+    /// Composite on the CPU even when the GPU compositor is available
+    /// (slower; for RDP / VM / software-GL sessions)
+    #[arg(long = "force-cpu")]
+    pub force_cpu: bool,
+
     /// Custom configuration directory (overrides default platform paths)
     #[arg(short = 'c', long = "config-dir", value_name = "DIR")]
     pub config_dir: Option<PathBuf>,
@@ -81,3 +133,34 @@ pub struct Args {
     #[arg(long = "workers", value_name = "N", hide = true)]
     pub workers: Option<usize>,
 }
+
+/// Parse `RRGGBB` (optionally `#`-prefixed) into sRGB bytes
+fn parse_rgb_hex(s: &str) -> Result<[u8; 3], String> {
+    let hex = s.strip_prefix('#').unwrap_or(s);
+    if hex.len() != 6 || !hex.is_ascii() {
+        return Err(format!("expected RRGGBB hex color, got '{}'", s));
+    }
+    let channel = |i: usize| {
+        u8::from_str_radix(&hex[i..i + 2], 16)
+            .map_err(|_| format!("expected RRGGBB hex color, got '{}'", s))
+    };
+    Ok([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Parse a positive frame rate such as `24` or `23.976`.
+fn parse_fps(s: &str) -> Result<f32, String> {
+    match s.parse::<f32>() {
+        Ok(fps) if fps.is_finite() && fps > 0.0 => Ok(fps),
+        _ => Err(format!("expected a positive frame rate, got '{}'", s)),
+    }
+}
+
+/// Parse a video codec name for `--codec`.
+fn parse_codec(s: &str) -> Result<playa_ui::dialogs::encode::VideoCodec, String> {
+    playa_ui::dialogs::encode::VideoCodec::parse(s).ok_or_else(|| {
+        format!(
+            "unknown codec '{}' (h264, h265, av1, prores, vp9, dnxhr)",
+            s
+        )
+    })
+}
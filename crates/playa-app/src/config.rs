@@ -2,6 +2,7 @@
 //!
 //! Combines:
 //! - Path configuration (PathConfig) for config/data file locations
+//! - Display configuration read before the window opens (vsync)
 //! - Default values for app constants (fps, speed, dimensions)
 //! - Theme colors for consistent UI appearance
 
@@ -126,10 +127,52 @@ fn get_config_dir(config: &PathConfig) -> PathBuf {
 }
 
 /// Get the data directory
-fn get_data_dir(config: &PathConfig) -> PathBuf {
+pub fn get_data_dir(config: &PathConfig) -> PathBuf {
     get_app_dir(config, dirs_next::data_dir)
 }
 
+// ============================================================================
+// Display Configuration
+// ============================================================================
+
+/// Config file holding [`DisplayConfig`]
+pub const DISPLAY_FILE: &str = "display.json";
+
+/// Swap-chain options the window needs before it opens.
+///
+/// eframe restores the app state (`playa.json`) only once the window exists,
+/// too late to pick the present mode, so these prefs are mirrored to
+/// [`DISPLAY_FILE`] on save and take effect on the next start.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct DisplayConfig {
+    /// Wait for the monitor's vertical blank when presenting (no tearing)
+    pub vsync: bool,
+}
+
+impl Default for DisplayConfig {
+    fn default() -> Self {
+        Self { vsync: true }
+    }
+}
+
+impl DisplayConfig {
+    /// Read [`DISPLAY_FILE`]; defaults if missing or unreadable
+    pub fn load(config: &PathConfig) -> Self {
+        std::fs::read_to_string(config_file(DISPLAY_FILE, config))
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write [`DISPLAY_FILE`]
+    pub fn save(&self, config: &PathConfig) -> Result<()> {
+        let path = config_file(DISPLAY_FILE, config);
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(&path, json).with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
 pub use playa_engine::defaults::{DEFAULT_DIM, DEFAULT_FPS, DEFAULT_SRC_LEN};
 
 /// Default playback speed multiplier.
@@ -242,6 +285,24 @@ mod tests {
         assert!(path.to_string_lossy().contains("test.json"));
     }
 
+    #[test]
+    fn test_display_config_round_trip() {
+        let dir = std::env::temp_dir().join(format!("playa_display_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config = PathConfig {
+            config_dir: Some(dir.clone()),
+        };
+
+        // Missing file: vsync on
+        assert_eq!(DisplayConfig::load(&config), DisplayConfig::default());
+        assert!(DisplayConfig::default().vsync);
+
+        DisplayConfig { vsync: false }.save(&config).unwrap();
+        assert!(!DisplayConfig::load(&config).vsync);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_show_actual_paths() {
         println!("\n=== Platform-specific paths (no local files) ===");
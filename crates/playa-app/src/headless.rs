@@ -0,0 +1,273 @@
+//! Headless encode (`--headless --encode-output PATH`).
+//!
+//! Renders a comp to a video or image sequence and exits, without creating a
+//! window, egui context or GPU device, so it runs on render-farm nodes with no
+//! display. Frames are composed on the CPU (`Comp::get_frame` in blocking
+//! mode never uses the GPU blend bridge). FFmpeg is initialized by the binary
+//! entry point before `run_app`, as for the GUI.
+//!
+//! Input is the first clip on the command line (wrapped in a comp timed to
+//! it) or the active comp of a project JSON given with `-p`. Progress goes to
+//! stderr as a percentage; an [`EncodeError`] becomes the process error, so
+//! the exit status is non-zero.
+//!
+//! `--verify-hashes MANIFEST` loads the same way, renders the work area and
+//! compares each frame's content hash with a manifest written by a sequence
+//! export (`--write-hashes` / "Write hash manifest"), for conform QC.
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+
+use log::info;
+
+use crate::cli::Args;
+use playa_engine::core::cache_man::CacheManager;
+use playa_engine::entities::Comp;
+use playa_engine::entities::node::Node;
+use playa_engine::entities::{CacheStrategy, FileNode, Playlist, Project};
+use playa_ui::dialogs::encode::{
+    Container, EncodeError, EncodeProgress, EncodeStage, EncoderSettings, SequenceFormat,
+    SequenceSettings, VideoCodec, encode_comp, encode_image_sequence, read_hash_manifest,
+};
+use playa_ui::dialogs::prefs::PlaybackSettings;
+
+/// What the output path asks for, decided by its extension
+enum Output {
+    Video(EncoderSettings),
+    Sequence(SequenceSettings),
+}
+
+/// Encode per `args` and return; errors carry the message for stderr.
+pub fn run_headless(args: &Args) -> Result<(), Box<dyn std::error::Error>> {
+    let output_path = args
+        .encode_output
+        .clone()
+        .ok_or("--headless requires --encode-output PATH")?;
+    let output = output_for(&output_path, args.codec, args.write_hashes)?;
+    let (project, comp) = load_comp(args)?;
+    info!(
+        "Headless encode: {} -> {}",
+        comp.name(),
+        output_path.display()
+    );
+
+    let (tx, rx) = std::sync::mpsc::channel::<EncodeProgress>();
+    let printer = std::thread::spawn(move || print_progress(rx));
+    let cancel = Arc::new(AtomicBool::new(false));
+    let result = match output {
+        Output::Video(mut settings) => {
+            settings.fps = comp.fps();
+            encode_comp(&comp, &project, &settings, tx, cancel)
+        }
+        Output::Sequence(settings) => {
+            encode_image_sequence(&comp, &project, &output_path, &settings, tx, cancel)
+        }
+    };
+    let _ = printer.join();
+
+    result.map_err(|e: EncodeError| format!("Encode failed: {}", e))?;
+    eprintln!("Wrote {}", output_path.display());
+    Ok(())
+}
+
+/// Render the work area and check every frame's content hash against the
+/// manifest at `manifest_path`; mismatched, missing or unrendered frames are
+/// listed on stderr and fail the run.
+pub fn run_verify_hashes(
+    args: &Args,
+    manifest_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let manifest = read_hash_manifest(manifest_path)?;
+    let (project, comp) = load_comp(args)?;
+    let (start, end) = comp.play_range(true);
+    info!(
+        "Verifying hashes: {} frames {}..={} against {}",
+        comp.name(),
+        start,
+        end,
+        manifest_path.display()
+    );
+
+    let mut failed = 0;
+    for frame_idx in start..=end {
+        let hash = comp
+            .get_frame(frame_idx, &project, true)
+            .map(|frame| frame.content_hash());
+        let problem = match (hash, manifest.get(&frame_idx)) {
+            (Some(hash), Some(expected)) if &hash == expected => continue,
+            (Some(hash), Some(expected)) => format!("{} (manifest {})", hash, expected),
+            (Some(_), None) => "not in manifest".to_string(),
+            (None, _) => "frame not available".to_string(),
+        };
+        failed += 1;
+        eprintln!("Frame {}: {}", frame_idx, problem);
+    }
+
+    let total = (end - start + 1).max(0);
+    if failed > 0 {
+        return Err(format!("{} of {} frames failed hash verification", failed, total).into());
+    }
+    eprintln!(
+        "Verified {} frames against {}",
+        total,
+        manifest_path.display()
+    );
+    Ok(())
+}
+
+/// Project per [`load_project`] with the CLI range flags applied to its
+/// active comp; returns the project and a snapshot of that comp.
+fn load_comp(args: &Args) -> Result<(Project, Comp), String> {
+    let cache_manager = Arc::new(CacheManager::new(0.75, 2.0));
+    let mut project = load_project(args, cache_manager)?;
+    let comp_uuid = project
+        .active()
+        .filter(|uuid| project.contains_comp(*uuid))
+        .ok_or("No comp to render")?;
+
+    // Same range flags as the player: comp frames, inclusive
+    let (start, end) = match &args.range {
+        Some(range) => (Some(range[0]), Some(range[1])),
+        None => (args.range_start, args.range_end),
+    };
+    project.modify_comp(comp_uuid, |comp| {
+        if let Some(start) = start {
+            comp.mark_play_start(start);
+        }
+        if let Some(end) = end {
+            comp.mark_play_end(end);
+        }
+    });
+    let comp = project
+        .with_comp(comp_uuid, |c| c.clone())
+        .ok_or("No comp to render")?;
+    Ok((project, comp))
+}
+
+/// Video container or image sequence settings for `path`'s extension.
+fn output_for(
+    path: &Path,
+    codec: Option<VideoCodec>,
+    write_hashes: bool,
+) -> Result<Output, String> {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let (container, default_codec) = match ext.as_str() {
+        "mp4" => (Container::MP4, VideoCodec::H264),
+        "mov" => (Container::MOV, VideoCodec::ProRes),
+        "webm" => (Container::WebM, VideoCodec::VP9),
+        _ => {
+            let format = SequenceFormat::from_extension(&ext).ok_or_else(|| {
+                format!(
+                    "Unsupported output extension '{}' (mp4, mov, webm, exr, png, jpg, tif, tga)",
+                    ext
+                )
+            })?;
+            if codec.is_some() {
+                return Err("--codec applies to video outputs only".to_string());
+            }
+            return Ok(Output::Sequence(SequenceSettings {
+                format,
+                bit_depth: format.default_depth(),
+                write_hashes,
+                ..SequenceSettings::default()
+            }));
+        }
+    };
+    if write_hashes {
+        return Err("--write-hashes applies to image sequence outputs only".to_string());
+    }
+    Ok(Output::Video(EncoderSettings {
+        output_path: path.to_path_buf(),
+        container,
+        codec: codec.unwrap_or(default_codec),
+        ..EncoderSettings::default()
+    }))
+}
+
+/// Project JSON from `-p`, or the first clip on the command line wrapped in
+/// a comp; either way with the comp set active. Every frame is composed once,
+/// in order, so the cache keeps only the last one.
+fn load_project(args: &Args, cache_manager: Arc<CacheManager>) -> Result<Project, String> {
+    if let Some(path) = &args.playlist {
+        if Playlist::is_playlist_file(path) {
+            return Err(
+                "Review playlists are not supported headless; pass a clip or project".into(),
+            );
+        }
+        let mut project = Project::from_json(path)?;
+        project.attach_schemas();
+        project.rebuild_with_manager(cache_manager, CacheStrategy::LastOnly, None);
+        if project.active().is_none() {
+            let first = project
+                .order()
+                .into_iter()
+                .find(|uuid| project.contains_comp(*uuid));
+            project.set_active(first);
+        }
+        return Ok(project);
+    }
+
+    let paths: Vec<PathBuf> = args
+        .file_path
+        .iter()
+        .chain(args.files.iter())
+        .cloned()
+        .collect();
+    if paths.is_empty() {
+        return Err("Nothing to encode: pass a clip or -p project.json".to_string());
+    }
+    let mut nodes = FileNode::detect_from_paths(paths).map_err(|e| e.to_string())?;
+    if nodes.len() > 1 {
+        log::warn!("Headless encode uses the first of {} clips", nodes.len());
+    }
+    let mut node = nodes
+        .drain(..)
+        .next()
+        .ok_or("No valid sequences detected")?;
+
+    // Same load-time defaults as the GUI, from CLI flags only (prefs untouched)
+    let defaults = PlaybackSettings::default();
+    node.set_still_frames(args.still_frames.unwrap_or(defaults.still_frames));
+    if node.is_sequence() || node.is_still() {
+        node.set_fps(args.fps.unwrap_or(defaults.sequence_fps));
+    }
+
+    let mut project = Project::new_with_strategy(cache_manager, CacheStrategy::LastOnly);
+    let source = node.uuid();
+    project.add_node(node.into());
+    let comp = project
+        .create_comp_for_source(source, None)
+        .ok_or("Cannot create comp for clip")?;
+    project.set_active(Some(comp));
+    Ok(project)
+}
+
+/// Print `Encoding: NN%` on stderr whenever the percentage changes.
+fn print_progress(rx: std::sync::mpsc::Receiver<EncodeProgress>) {
+    let mut last = None;
+    let mut stderr = std::io::stderr();
+    for p in rx {
+        if p.stage != EncodeStage::Encoding || p.total_frames <= 0 {
+            continue;
+        }
+        let percent = (p.current_frame.max(0) as i64 * 100 / p.total_frames as i64) as i32;
+        if last != Some(percent) {
+            last = Some(percent);
+            let _ = write!(
+                stderr,
+                "\rEncoding: {:3}% ({}/{})",
+                percent, p.current_frame, p.total_frames
+            );
+            let _ = stderr.flush();
+        }
+    }
+    if last.is_some() {
+        let _ = writeln!(stderr);
+    }
+}
@@ -1,5 +1,5 @@
 //! Playa desktop host — [`PlayaApp`](crate::app::PlayaApp), orchestration,
-//! [`main_events`] routing, CLI, prefs paths, REST server.
+//! [`main_events`] routing, CLI, prefs paths, REST server, headless encode.
 
 #![allow(clippy::too_many_arguments)]
 #![allow(clippy::module_inception)]
@@ -7,6 +7,7 @@
 pub mod app;
 pub mod cli;
 pub mod config;
+pub mod headless;
 pub mod main_events;
 pub mod runner;
 pub mod server;
@@ -68,10 +68,12 @@ use playa_engine::entities::keys::{A_IN, A_OUT, A_SPEED, A_TRIM_IN, A_TRIM_OUT};
 use playa_engine::entities::node::Node;
 use playa_events::viewport_tool::SetToolEvent;
 use playa_ui::dialogs::encode::EncodeDialog;
+use playa_ui::dialogs::goto_frame::GotoFrameDialog;
 use playa_ui::dialogs::prefs::prefs_events::*;
 use playa_ui::widgets::node_editor::node_events::*;
 use playa_ui::widgets::project::project_events::*;
 use playa_ui::widgets::timeline::timeline_events::*;
+use playa_ui::widgets::viewport::bookmarks::{self, ViewBookmark};
 use playa_ui::widgets::viewport::viewport_events::*;
 
 /// After removing one or more comps, fix the active comp and node editor if the active was removed.
@@ -129,34 +131,26 @@ fn align_layers_to_frame(comp: &mut playa_engine::entities::Comp, use_start: boo
     }
 }
 
-/// Jump to next/prev layer edge in composition
-/// direction > 0: next edge, direction < 0: prev edge
-fn jump_to_edge(comp: &mut playa_engine::entities::Comp, forward: bool) {
-    let current = comp.frame();
-    let edges = comp.get_child_edges();
-    if edges.is_empty() {
-        return;
-    }
-    if forward {
-        // Find first edge after current, or wrap to first
-        if let Some((frame, _)) = edges.iter().find(|(f, _)| *f > current) {
-            comp.set_frame(*frame);
-        } else if let Some((frame, _)) = edges.first() {
-            comp.set_frame(*frame);
-        }
-    } else {
-        // Find last edge before current, or wrap to last
-        if let Some((frame, _)) = edges.iter().rev().find(|(f, _)| *f < current) {
-            comp.set_frame(*frame);
-        } else if let Some((frame, _)) = edges.last() {
-            comp.set_frame(*frame);
-        }
-    }
+/// Frames of the active comp to jump between (layer edges, markers, keyframes);
+/// [`Player::step_to`] picks the next one and wraps or clamps per loop mode.
+fn jump_targets(
+    player: &Player,
+    project: &Project,
+    get: impl FnOnce(&playa_engine::entities::Comp) -> Vec<i32>,
+) -> Vec<i32> {
+    player
+        .active_comp()
+        .and_then(|uuid| project.with_comp(uuid, get))
+        .unwrap_or_default()
 }
 
 /// Scan folder for image sequences using scanseq.
 /// Returns first frame of each detected sequence for Comp::detect_from_paths.
-fn scan_folder_for_media(root: &std::path::Path) -> Vec<PathBuf> {
+///
+/// Stills scanseq skips (short runs, single frames) are added grouped by
+/// common prefix, so `ref_01.jpg`..`ref_03.jpg` still become one clip.
+pub(crate) fn scan_folder_for_media(root: &std::path::Path) -> Vec<PathBuf> {
+    use playa_engine::utils::media;
     use scanseq::core::{Scanner, VIDEO_EXTS, scan_files};
 
     let mut all_paths: Vec<PathBuf> = Vec::new();
@@ -186,8 +180,25 @@ fn scan_folder_for_media(root: &std::path::Path) -> Vec<PathBuf> {
         }
     }
 
+    // Loose stills; frames of sequences found above collapse on detection
+    let image_exts: Vec<&str> = media::ALL_EXTS
+        .iter()
+        .copied()
+        .filter(|ext| !media::VIDEO_EXTS.contains(ext))
+        .collect();
+    match scan_files(&[root], true, &image_exts) {
+        Ok(mut stills) => {
+            stills.sort();
+            all_paths.extend(media::group_by_prefix(&stills));
+        }
+        Err(e) => {
+            trace!("Failed to scan stills: {}", e);
+        }
+    }
+
     // Sort for deterministic order
     all_paths.sort();
+    all_paths.dedup();
     all_paths
 }
 
@@ -221,6 +232,7 @@ pub struct AppEventContext<'a> {
     pub show_encode_dialog: &'a mut bool,
     pub show_attributes_editor: &'a mut bool,
     pub encode_dialog: &'a mut Option<EncodeDialog>,
+    pub goto_frame: &'a mut Option<GotoFrameDialog>,
     pub is_fullscreen: &'a mut bool,
     pub fullscreen_dirty: &'a mut bool,
     pub reset_settings_pending: &'a mut bool,
@@ -231,13 +243,18 @@ pub struct AppEventContext<'a> {
 pub struct EventResult {
     pub load_project: Option<PathBuf>,
     pub save_project: Option<PathBuf>,
+    pub load_playlist: Option<PathBuf>,
+    pub save_playlist: Option<PathBuf>,
     pub load_sequences: Option<Vec<PathBuf>>,
     pub new_comp: Option<(String, f32)>,
     pub new_camera: Option<String>,
     pub new_text: Option<(String, String)>,
+    /// (name, matte) - see `AddSolidEvent`
+    pub new_solid: Option<(String, bool)>,
     pub enqueue_frames: bool,
     pub quick_save: bool,
     pub show_open_dialog: bool,
+    pub open_most_recent: bool,
     /// Update AE panel focus (SelectionFocusEvent)
     pub ae_focus_update: Option<Vec<Uuid>>,
     /// AINode uuid to submit a fresh Generation for. Deferred because
@@ -249,6 +266,9 @@ pub struct EventResult {
     /// with a fresh seed + parent_gen_uuid linkage. Same deferred
     /// rationale as `generate_ainode`.
     pub iterate_generation: Option<(Uuid, Uuid)>,
+    /// Nodes changed by a batch attr edit; invalidated together (one epoch
+    /// bump, one preload) after the event loop.
+    pub invalidate_nodes: Vec<Uuid>,
 }
 
 impl EventResult {
@@ -261,6 +281,12 @@ impl EventResult {
         if other.save_project.is_some() {
             self.save_project = other.save_project;
         }
+        if other.load_playlist.is_some() {
+            self.load_playlist = other.load_playlist;
+        }
+        if other.save_playlist.is_some() {
+            self.save_playlist = other.save_playlist;
+        }
         if other.new_comp.is_some() {
             self.new_comp = other.new_comp;
         }
@@ -270,6 +296,9 @@ impl EventResult {
         if other.new_text.is_some() {
             self.new_text = other.new_text;
         }
+        if other.new_solid.is_some() {
+            self.new_solid = other.new_solid;
+        }
         self.enqueue_frames |= other.enqueue_frames;
         // Accumulate paths instead of overwriting
         if let Some(paths) = other.load_sequences {
@@ -280,13 +309,34 @@ impl EventResult {
         // Bool flags: set to true if any event sets them
         self.quick_save |= other.quick_save;
         self.show_open_dialog |= other.show_open_dialog;
+        self.open_most_recent |= other.open_most_recent;
         // AE focus: last write wins
         if other.ae_focus_update.is_some() {
             self.ae_focus_update = other.ae_focus_update;
         }
+        self.invalidate_nodes.extend(other.invalidate_nodes);
     }
 }
 
+/// Decode a JSON attr batch (Attribute Editor events), skipping bad values.
+fn decode_attrs(
+    tag: &str,
+    attrs: &[(String, serde_json::Value)],
+) -> Vec<(String, playa_engine::entities::AttrValue)> {
+    attrs
+        .iter()
+        .filter_map(
+            |(key, json_v)| match serde_json::from_value(json_v.clone()) {
+                Ok(value) => Some((key.clone(), value)),
+                Err(err) => {
+                    log::warn!("[{}] skip key {:?}: {}", tag, key, err);
+                    None
+                }
+            },
+        )
+        .collect()
+}
+
 /// Compute zoom and pan to fit a frame range into the timeline canvas.
 ///
 /// `default_ppf` (2.0) is the baseline pixels-per-frame at zoom 1.0.
@@ -319,6 +369,7 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         show_encode_dialog,
         show_attributes_editor,
         encode_dialog,
+        goto_frame,
         is_fullscreen,
         fullscreen_dirty,
         reset_settings_pending,
@@ -350,7 +401,10 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
     }
     if let Some(e) = downcast_event::<SetFrameEvent>(event) {
         trace!("SetFrame: moving to frame {}", e.0);
-        if let Some(comp_uuid) = player.active_comp() {
+        // A held frame ignores scrubs until the hold is released
+        if player.held_frame().is_none()
+            && let Some(comp_uuid) = player.active_comp()
+        {
             // Get old frame before setting new one (for distance calculation)
             let old_frame = project
                 .with_comp(comp_uuid, |comp| comp.frame())
@@ -386,11 +440,11 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         return Some(result);
     }
     if downcast_event::<StepForwardLargeEvent>(event).is_some() {
-        player.step(playa_engine::core::player::FRAME_JUMP_STEP, project);
+        player.step(settings.playback.step_frames.max(1), project);
         return Some(result);
     }
     if downcast_event::<StepBackwardLargeEvent>(event).is_some() {
-        player.step(-playa_engine::core::player::FRAME_JUMP_STEP, project);
+        player.step(-settings.playback.step_frames.max(1), project);
         return Some(result);
     }
     if downcast_event::<JumpToStartEvent>(event).is_some() {
@@ -402,14 +456,67 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         return Some(result);
     }
     if downcast_event::<JumpToPrevEdgeEvent>(event).is_some() {
+        let targets = jump_targets(player, project, |comp| {
+            comp.get_child_edges().into_iter().map(|(f, _)| f).collect()
+        });
+        player.step_to(&targets, false, project);
+        return Some(result);
+    }
+    if downcast_event::<JumpToNextEdgeEvent>(event).is_some() {
+        let targets = jump_targets(player, project, |comp| {
+            comp.get_child_edges().into_iter().map(|(f, _)| f).collect()
+        });
+        player.step_to(&targets, true, project);
+        return Some(result);
+    }
+    // === Markers ===
+    if downcast_event::<AddMarkerEvent>(event).is_some() {
         if let Some(comp_uuid) = player.active_comp() {
-            project.modify_comp(comp_uuid, |comp| jump_to_edge(comp, false));
+            project.modify_comp(comp_uuid, |comp| {
+                let frame = comp.frame();
+                // Keep the label of an existing marker on this frame
+                if comp.marker_at(frame).is_none() {
+                    comp.set_marker(playa_engine::entities::Marker::new(frame));
+                }
+            });
         }
         return Some(result);
     }
-    if downcast_event::<JumpToNextEdgeEvent>(event).is_some() {
+    if downcast_event::<JumpToPrevMarkerEvent>(event).is_some() {
+        let targets = jump_targets(player, project, |comp| {
+            comp.markers().into_iter().map(|m| m.frame).collect()
+        });
+        player.step_to(&targets, false, project);
+        return Some(result);
+    }
+    if downcast_event::<JumpToNextMarkerEvent>(event).is_some() {
+        let targets = jump_targets(player, project, |comp| {
+            comp.markers().into_iter().map(|m| m.frame).collect()
+        });
+        player.step_to(&targets, true, project);
+        return Some(result);
+    }
+    if downcast_event::<JumpToPrevKeyframeEvent>(event).is_some() {
+        let targets = jump_targets(player, project, |comp| comp.keyframe_frames());
+        player.step_to(&targets, false, project);
+        return Some(result);
+    }
+    if downcast_event::<JumpToNextKeyframeEvent>(event).is_some() {
+        let targets = jump_targets(player, project, |comp| comp.keyframe_frames());
+        player.step_to(&targets, true, project);
+        return Some(result);
+    }
+    if downcast_event::<GoToFrameEvent>(event).is_some() {
+        // Prefill with the playhead; the prompt emits SetFrameEvent on Enter
         if let Some(comp_uuid) = player.active_comp() {
-            project.modify_comp(comp_uuid, |comp| jump_to_edge(comp, true));
+            **goto_frame = project.with_comp(comp_uuid, |comp| {
+                GotoFrameDialog::new(
+                    comp.frame(),
+                    comp.fps(),
+                    (comp._in(), comp._out()),
+                    timeline_state.time_display,
+                )
+            });
         }
         return Some(result);
     }
@@ -435,38 +542,33 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
     // === Play Range Control ===
     if downcast_event::<SetPlayRangeStartEvent>(event).is_some() {
         log::trace!(
-            "[B] SetPlayRangeStartEvent received, active_comp={:?}",
+            "[I/B] SetPlayRangeStartEvent received, active_comp={:?}",
             player.active_comp()
         );
         if let Some(comp_uuid) = player.active_comp() {
             project.modify_comp(comp_uuid, |comp| {
                 let current = comp.frame();
-                comp.set_comp_play_start(current);
+                comp.mark_play_start(current);
             });
         }
         return Some(result);
     }
     if downcast_event::<SetPlayRangeEndEvent>(event).is_some() {
         log::trace!(
-            "[N] SetPlayRangeEndEvent received, active_comp={:?}",
+            "[O/N] SetPlayRangeEndEvent received, active_comp={:?}",
             player.active_comp()
         );
         if let Some(comp_uuid) = player.active_comp() {
             project.modify_comp(comp_uuid, |comp| {
                 let current = comp.frame();
-                comp.set_comp_play_end(current);
+                comp.mark_play_end(current);
             });
         }
         return Some(result);
     }
     if downcast_event::<ResetPlayRangeEvent>(event).is_some() {
         if let Some(comp_uuid) = player.active_comp() {
-            project.modify_comp(comp_uuid, |comp| {
-                let start = comp._in();
-                let end = comp._out();
-                comp.set_comp_play_start(start);
-                comp.set_comp_play_end(end);
-            });
+            project.modify_comp(comp_uuid, |comp| comp.clear_play_range());
         }
         return Some(result);
     }
@@ -480,6 +582,18 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         player.set_loop_enabled(e.0);
         return Some(result);
     }
+    if downcast_event::<ToggleFrameHoldEvent>(event).is_some() {
+        player.toggle_hold(project);
+        return Some(result);
+    }
+    if downcast_event::<ToggleSequencePlaybackEvent>(event).is_some() {
+        player.set_sequence_playback(!player.sequence_playback());
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<SetSequencePlaybackEvent>(event) {
+        player.set_sequence_playback(e.0);
+        return Some(result);
+    }
 
     // === Project Management ===
     if let Some(e) = downcast_event::<AddClipEvent>(event) {
@@ -514,6 +628,10 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         result.new_text = Some((e.name.clone(), e.text.clone()));
         return Some(result);
     }
+    if let Some(e) = downcast_event::<AddSolidEvent>(event) {
+        result.new_solid = Some((e.name.clone(), e.matte));
+        return Some(result);
+    }
     if let Some(e) = downcast_event::<AddAINodeEvent>(event) {
         use playa_engine::entities::node::Node;
         use playa_engine::entities::{AINode, NodeKind};
@@ -568,6 +686,14 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         result.load_project = Some(e.0.clone());
         return Some(result);
     }
+    if let Some(e) = downcast_event::<LoadPlaylistEvent>(event) {
+        result.load_playlist = Some(e.0.clone());
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<SavePlaylistEvent>(event) {
+        result.save_playlist = Some(e.0.clone());
+        return Some(result);
+    }
     if downcast_event::<QuickSaveEvent>(event).is_some() {
         result.quick_save = true;
         return Some(result);
@@ -576,6 +702,26 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         result.show_open_dialog = true;
         return Some(result);
     }
+    if downcast_event::<OpenMostRecentEvent>(event).is_some() {
+        result.open_most_recent = true;
+        return Some(result);
+    }
+    if downcast_event::<DuplicateSelectedMediaEvent>(event).is_some() {
+        // Deep-copy selected comps; the copies become the new selection
+        let copies: Vec<Uuid> = project
+            .selection()
+            .into_iter()
+            .filter_map(|uuid| project.duplicate_comp(uuid))
+            .collect();
+        if copies.is_empty() {
+            trace!("Duplicate: no comps selected");
+        } else {
+            log::info!("Duplicated {} comp(s)", copies.len());
+            project.set_selection(copies);
+            node_editor_state.mark_dirty();
+        }
+        return Some(result);
+    }
     if let Some(e) = downcast_event::<RemoveMediaEvent>(event) {
         handle_media_removal(&[e.0], project, player, node_editor_state);
         return Some(result);
@@ -698,6 +844,10 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         project.set_gizmo_prefs(&e.0);
         return Some(result);
     }
+    if let Some(e) = downcast_event::<SetViewportDefaultsEvent>(event) {
+        project.set_viewport_defaults(&e.0);
+        return Some(result);
+    }
     if downcast_event::<ToggleEncodeDialogEvent>(event).is_some() {
         **show_encode_dialog = !**show_encode_dialog;
         if **show_encode_dialog && encode_dialog.is_none() {
@@ -715,6 +865,11 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         settings.show_frame_numbers = !settings.show_frame_numbers;
         return Some(result);
     }
+    if downcast_event::<CycleProxyLevelEvent>(event).is_some() {
+        settings.playback.proxy_level = settings.playback.proxy_level.next();
+        log::info!("Proxy playback: {}", settings.playback.proxy_level.label());
+        return Some(result);
+    }
     if downcast_event::<ResetSettingsEvent>(event).is_some() {
         **reset_settings_pending = true;
         return Some(result);
@@ -796,6 +951,40 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         node_editor_state.layout_requested = true;
         return Some(result);
     }
+    if let Some(e) = downcast_event::<CreateMergeNodeEvent>(event) {
+        use playa_engine::entities::compositor::BlendMode;
+        match project.create_merge(e.a, e.b, BlendMode::from_attr(&e.blend_mode)) {
+            Some(merge_uuid) => {
+                // Activate so the composited result shows in the viewport
+                player.set_active_comp(Some(merge_uuid), project);
+                node_editor_state.set_comp(merge_uuid);
+                result.enqueue_frames = true;
+            }
+            None => log::warn!("Cannot create merge: inputs missing or not renderable"),
+        }
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<SetMergeInputEvent>(event) {
+        // Graph editor UI redraw (NOT comp dirty - set_merge_input() handles that)
+        match project.set_merge_input(e.merge, e.input, e.source) {
+            Ok(()) => node_editor_state.mark_dirty(),
+            Err(err) => log::warn!("Cannot rewire merge: {}", err),
+        }
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<ExportNodeGraphEvent>(event) {
+        let text = if e.dot {
+            node_editor_state.export_dot(project)
+        } else {
+            node_editor_state.export_json(project)
+        };
+        match text.map(|t| std::fs::write(&e.path, t)) {
+            Some(Ok(())) => log::info!("Exported node graph to {}", e.path.display()),
+            Some(Err(err)) => log::error!("Failed to export node graph: {}", err),
+            None => log::warn!("Cannot export node graph: no comp shown"),
+        }
+        return Some(result);
+    }
 
     // === Viewport State ===
     if let Some(e) = downcast_event::<ZoomViewportEvent>(event) {
@@ -814,67 +1003,73 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         viewport_state.set_mode_100();
         return Some(result);
     }
+    if downcast_event::<CycleGuidesEvent>(event).is_some() {
+        viewport_state.guides.cycle();
+        return Some(result);
+    }
+    if downcast_event::<ToggleCheckerEvent>(event).is_some() {
+        viewport_state.toggle_checker();
+        return Some(result);
+    }
+    if downcast_event::<ToggleOnionSkinEvent>(event).is_some() {
+        viewport_state.toggle_onion();
+        return Some(result);
+    }
+    if downcast_event::<RotateViewportEvent>(event).is_some() {
+        viewport_state.rotate_cw();
+        return Some(result);
+    }
+    if downcast_event::<FlipViewportEvent>(event).is_some() {
+        viewport_state.toggle_flip();
+        return Some(result);
+    }
+    if downcast_event::<FlopViewportEvent>(event).is_some() {
+        viewport_state.toggle_flop();
+        return Some(result);
+    }
+    if downcast_event::<ResetOrientationEvent>(event).is_some() {
+        viewport_state.reset_orientation();
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<StoreViewBookmarkEvent>(event) {
+        bookmarks::store(project, e.0, ViewBookmark::capture(viewport_state));
+        log::info!("Saved view bookmark {}", e.0);
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<RecallViewBookmarkEvent>(event) {
+        match bookmarks::recall(project, e.0) {
+            Some(bookmark) => bookmark.apply(viewport_state),
+            None => log::info!("View bookmark {} is empty", e.0),
+        }
+        return Some(result);
+    }
     // Tool change (Q/W/E/R)
     if let Some(e) = downcast_event::<SetToolEvent>(event) {
         project.set_tool(e.0.as_str());
+        // The region of interest only lives while its tool is active
+        if e.0 != playa_events::viewport_tool::ToolMode::Roi
+            && let Some(comp_uuid) = player.active_comp()
+        {
+            project.modify_comp(comp_uuid, |comp| comp.set_roi(None));
+        }
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<SetRoiEvent>(event) {
+        use playa_engine::entities::frame::PixelRect;
+        project.modify_comp(e.comp_uuid, |comp| {
+            comp.set_roi(e.rect.map(|[x, y, w, h]| {
+                PixelRect::new(x as usize, y as usize, w as usize, h as usize)
+            }));
+        });
         return Some(result);
     }
 
     // === Layer Operations ===
     if let Some(e) = downcast_event::<AddLayerEvent>(event) {
-        // Cycle check BEFORE any mutation - prevents comp A -> B -> A
-        if project.would_create_cycle(e.comp_uuid, e.source_uuid) {
-            log::error!("Cannot add layer: would create dependency cycle");
-            return Some(result);
-        }
-
-        // Get source info and generate name BEFORE write lock
-        // Use play_range() to get trimmed duration (respects B/N trim points)
-        let source_info = project.with_node(e.source_uuid, |s| {
-            let name = project.gen_name(s.name());
-            let (start, end) = s.play_range(true);
-            let trimmed_duration = (end - start + 1).max(1);
-            let renderable = s.is_renderable(); // false for camera/light/null/audio
-            let is_camera = s.as_camera().is_some();
-            (trimmed_duration, s.dim(), name, renderable, is_camera)
-        });
-
-        let add_result = {
-            let mut media = project.media.write().expect("media lock poisoned");
-            if let Some(arc_node) = media.get_mut(&e.comp_uuid) {
-                // Arc::make_mut: copy-on-write for mutation
-                let node = std::sync::Arc::make_mut(arc_node);
-                let (duration, source_dim, name, renderable, is_camera) =
-                    source_info.unwrap_or((1, (64, 64), "layer_1".to_string(), true, false));
-                // WHY CAMERA GETS Z=+1000:
-                // Camera uses RH convention: looks in -Z direction.
-                // Camera at Z=+1000 looks toward origin (Z=0) where layers live.
-                // User can adjust via layer.position in inspector.
-                let initial_pos = if is_camera {
-                    Some([0.0, 0.0, 800.0])
-                } else {
-                    None
-                };
-                node.add_child_layer(
-                    e.source_uuid,
-                    &name,
-                    e.start_frame,
-                    duration,
-                    e.insert_idx,
-                    source_dim,
-                    renderable,
-                    initial_pos,
-                )
-            } else {
-                Err(anyhow::anyhow!("Parent comp not found"))
-            }
-        };
-
-        if let Err(err) = add_result {
-            log::error!("Failed to add layer: {}", err);
-        } else {
+        match project.add_layer(e.comp_uuid, e.source_uuid, e.start_frame, e.insert_idx) {
             // Graph editor UI redraw (NOT comp dirty - add_layer() handles that)
-            node_editor_state.mark_dirty();
+            Ok(_) => node_editor_state.mark_dirty(),
+            Err(err) => log::error!("Failed to add layer: {}", err),
         }
         return Some(result);
     }
@@ -912,6 +1107,25 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         }
         return Some(result);
     }
+    if downcast_event::<RippleDeleteSelectedLayerEvent>(event).is_some() {
+        if let Some(active_uuid) = player.active_comp() {
+            // One modify_comp() for the whole ripple => a single AttrsChangedEvent
+            project.modify_comp(active_uuid, |comp| {
+                let to_remove: Vec<Uuid> = comp.layer_selection.clone();
+                for child_uuid in to_remove {
+                    // Timeline draw order: one track per layer index
+                    let order: Vec<usize> = (0..comp.layers.len()).collect();
+                    if let Some(range) = comp.ripple_delete(child_uuid, &order) {
+                        trace!("Ripple delete {}: frames {:?}", child_uuid, range);
+                    }
+                }
+                comp.layer_selection.clear();
+                comp.layer_selection_anchor = None;
+            });
+            node_editor_state.mark_dirty();
+        }
+        return Some(result);
+    }
     if let Some(e) = downcast_event::<ReorderLayerEvent>(event) {
         project.modify_comp(e.comp_uuid, |comp| {
             let children = comp.get_children();
@@ -996,7 +1210,9 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
     if let Some(e) = downcast_event::<SlideLayerEvent>(event) {
         project.modify_comp(e.comp_uuid, |comp| {
             use playa_engine::entities::AttrValue;
-            if let Some(uuid) = comp.idx_to_uuid(e.layer_idx) {
+            if let Some(uuid) = comp.idx_to_uuid(e.layer_idx)
+                && comp.get_layer(uuid).is_some_and(|l| !l.is_locked())
+            {
                 comp.set_child_attrs(
                     uuid,
                     vec![
@@ -1022,7 +1238,9 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         project.modify_comp(e.comp_uuid, |comp| {
             use playa_engine::entities::AttrValue;
             for layer_uuid in comp.layer_selection.clone() {
-                if let Some(layer) = comp.get_layer_mut(layer_uuid) {
+                if let Some(layer) = comp.get_layer_mut(layer_uuid)
+                    && !layer.is_locked()
+                {
                     let old_trim_in = layer.attrs.get_i32_or_zero(A_TRIM_IN);
                     let old_trim_out = layer.attrs.get_i32_or_zero(A_TRIM_OUT);
                     // Direct layer.attrs.set() doesn't mark comp dirty
@@ -1054,17 +1272,23 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         );
         project.modify_comp(e.comp_uuid, |comp| {
             use playa_engine::entities::AttrValue;
-            // Apply to all targeted layers (multi-selection support)
+            // Apply to all targeted layers (multi-selection support); keyed
+            // opacity / speed update their key at the playhead
+            let frame = comp.frame();
             for layer_uuid in &e.layer_uuids {
-                comp.set_child_attrs(
+                comp.set_child_attrs_at(
                     *layer_uuid,
+                    frame,
                     vec![
                         ("visible", AttrValue::Bool(e.visible)),
                         ("solo", AttrValue::Bool(e.solo)),
+                        ("mute", AttrValue::Bool(e.mute)),
+                        ("locked", AttrValue::Bool(e.locked)),
                         ("opacity", AttrValue::Float(e.opacity)),
                         ("blend_mode", AttrValue::Str(e.blend_mode.clone())),
                         (A_SPEED, AttrValue::Float(e.speed)),
                     ],
+                    &[],
                 );
             }
         });
@@ -1114,39 +1338,42 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
             e.layer_uuids,
             e.attrs
         );
+        let attrs = decode_attrs("SetLayerAttrs", &e.attrs);
+        // One modify_comp for the whole selection -> one AttrsChangedEvent
         project.modify_comp(e.comp_uuid, |comp| {
-            use playa_engine::entities::AttrValue;
-            for layer_uuid in &e.layer_uuids {
-                if let Some(layer) = comp.get_layer_mut(*layer_uuid) {
-                    for (key, json_v) in &e.attrs {
-                        match serde_json::from_value::<AttrValue>(json_v.clone()) {
-                            Ok(value) => layer.attrs.set(key, value),
-                            Err(err) => log::warn!("[SetLayerAttrs] skip key {:?}: {}", key, err),
-                        }
-                    }
-                    if layer.attrs.is_dirty() {
-                        comp.mark_dirty();
-                    }
-                }
-            }
+            comp.set_layers_attrs(&e.layer_uuids, &attrs);
         });
 
         return Some(result);
     }
+    // Generic node attrs change (Attribute Editor, several project nodes)
+    if let Some(e) = downcast_event::<SetNodeAttrsEvent>(event) {
+        log::trace!(
+            "[SetNodeAttrs] nodes={:?}, attrs={:?}",
+            e.node_uuids,
+            e.attrs
+        );
+        let attrs = decode_attrs("SetNodeAttrs", &e.attrs);
+        result.invalidate_nodes = project.set_nodes_attrs(&e.node_uuids, &attrs);
+        return Some(result);
+    }
     // Batch per-layer transform update (from viewport gizmo)
     if let Some(e) =
         downcast_event::<playa_engine::entities::comp_events::SetLayerTransformsEvent>(event)
     {
         project.modify_comp(e.comp_uuid, |comp| {
             use playa_engine::entities::AttrValue;
+            let record_keys: Vec<&str> = e.record_keys.iter().map(String::as_str).collect();
             for (layer_uuid, pos, rot, scale) in &e.updates {
-                comp.set_child_attrs(
+                comp.set_child_attrs_at(
                     *layer_uuid,
+                    e.frame,
                     vec![
                         ("position", AttrValue::Vec3(*pos)),
                         ("rotation", AttrValue::Vec3(*rot)),
                         ("scale", AttrValue::Vec3(*scale)),
                     ],
+                    &record_keys,
                 );
             }
         });
@@ -1189,6 +1416,19 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
 
         return Some(result);
     }
+    if let Some(e) = downcast_event::<RippleTrimLayersEndEvent>(event) {
+        project.modify_comp(e.0, |comp| {
+            let current_frame = comp.frame();
+            let order: Vec<usize> = (0..comp.layers.len()).collect();
+            for layer_uuid in comp.layer_selection.clone() {
+                if let Some(range) = comp.ripple_trim_end(layer_uuid, current_frame, &order) {
+                    trace!("Ripple trim {}: frames {:?}", layer_uuid, range);
+                }
+            }
+        });
+
+        return Some(result);
+    }
     if let Some(e) = downcast_event::<MoveLayerEvent>(event) {
         project.modify_comp(e.comp_uuid, |comp| {
             let _ = comp.move_child(e.layer_idx, e.new_start);
@@ -1201,14 +1441,17 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
     if let Some(e) = downcast_event::<DuplicateLayersEvent>(event) {
         trace!("DuplicateLayersEvent: comp={}", e.comp_uuid);
         // Duplicate selected layers, insert copies above originals
-        // Collect (layer_uuid, source_uuid, attrs_clone)
-        let layers_to_dup: Vec<(Uuid, Uuid, playa_engine::entities::Attrs)> = project
+        let layers_to_dup: Vec<(Uuid, String)> = project
             .with_comp(e.comp_uuid, |comp| {
                 comp.layer_selection
                     .iter()
                     .filter_map(|uuid| {
-                        comp.get_layer(*uuid)
-                            .map(|layer| (*uuid, layer.source_uuid(), layer.attrs.clone()))
+                        comp.get_layer(*uuid).map(|layer| {
+                            (
+                                *uuid,
+                                layer.attrs.get_str("name").unwrap_or("layer").to_string(),
+                            )
+                        })
                     })
                     .collect()
             })
@@ -1221,47 +1464,75 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
             // Generate names before taking write lock
             let names: Vec<String> = layers_to_dup
                 .iter()
-                .map(|(_, _, attrs)| {
-                    let src_name = attrs.get_str("name").unwrap_or("layer");
-                    project.gen_name(src_name)
-                })
+                .map(|(_, name)| project.gen_name(name))
                 .collect();
 
-            // Collect new UUIDs to select only duplicated layers
-            let mut new_uuids: Vec<Uuid> = Vec::new();
-
             project.modify_comp(e.comp_uuid, |comp| {
-                // Clear selection - will select only new layers
-                comp.layer_selection.clear();
-
-                for ((orig_uuid, source_uuid, mut attrs), new_name) in
-                    layers_to_dup.into_iter().zip(names)
-                {
-                    // Find insert position (above original)
-                    let insert_idx = comp.uuid_to_idx(orig_uuid).unwrap_or(0);
-                    // Update attrs with new name
-                    attrs.set(
-                        "name",
-                        playa_engine::entities::AttrValue::Str(new_name.clone()),
-                    );
-                    // Create new Layer using from_attrs
-                    let new_layer =
-                        playa_engine::entities::comp_node::Layer::from_attrs(source_uuid, attrs);
-                    let new_uuid = new_layer.uuid();
-                    // Direct layers.insert() doesn't mark dirty
-                    comp.layers.insert(insert_idx, new_layer);
-                    new_uuids.push(new_uuid);
-                    trace!("  Duplicated -> {} at idx {}", new_name, insert_idx);
-                }
                 // Select only the new duplicated layers
-                comp.layer_selection = new_uuids;
-                // Direct field changes (layers.insert, layer_selection) require explicit mark_dirty().
-                // modify_comp() will then emit AttrsChangedEvent.
-                comp.attrs.mark_dirty();
+                comp.layer_selection = layers_to_dup
+                    .iter()
+                    .zip(&names)
+                    .filter_map(|((orig_uuid, _), new_name)| {
+                        let new_uuid = comp.duplicate_layer(*orig_uuid, new_name);
+                        trace!("  Duplicated -> {} ({:?})", new_name, new_uuid);
+                        new_uuid
+                    })
+                    .collect();
             });
         }
         return Some(result);
     }
+    if let Some(e) = downcast_event::<SplitLayersEvent>(event) {
+        trace!("SplitLayersEvent: comp={}", e.comp_uuid);
+        // Cut every selected layer that spans the playhead; tails get fresh names
+        let to_split: Vec<(Uuid, String)> = project
+            .with_comp(e.comp_uuid, |comp| {
+                comp.layer_selection
+                    .iter()
+                    .filter_map(|uuid| {
+                        comp.get_layer(*uuid).map(|l| {
+                            (
+                                *uuid,
+                                l.attrs.get_str("name").unwrap_or("layer").to_string(),
+                            )
+                        })
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let names: Vec<String> = to_split
+            .iter()
+            .map(|(_, name)| project.gen_name(name))
+            .collect();
+
+        project.modify_comp(e.comp_uuid, |comp| {
+            let frame = comp.frame();
+            let mut tails: Vec<Uuid> = Vec::new();
+            for ((uuid, _), name) in to_split.into_iter().zip(names) {
+                let Some(idx) = comp.uuid_to_idx(uuid) else {
+                    continue;
+                };
+                // split_child() validates the frame and marks the comp dirty;
+                // modify_comp() then emits AttrsChangedEvent
+                match comp.split_child(idx, frame) {
+                    Some(tail_uuid) => {
+                        if let Some(tail) = comp.get_layer_mut(tail_uuid) {
+                            tail.attrs
+                                .set("name", playa_engine::entities::AttrValue::Str(name));
+                        }
+                        tails.push(tail_uuid);
+                    }
+                    None => trace!("Split: layer {} does not span frame {}", uuid, frame),
+                }
+            }
+            // Keep editing on the right-hand side of the cut
+            if !tails.is_empty() {
+                comp.layer_selection_anchor = tails.last().copied();
+                comp.layer_selection = tails;
+            }
+        });
+        return Some(result);
+    }
     if let Some(e) = downcast_event::<CopyLayersEvent>(event) {
         trace!("CopyLayersEvent: comp={}", e.comp_uuid);
         // Copy selected layers to clipboard
@@ -1452,6 +1723,45 @@ pub fn handle_app_event(event: &BoxedEvent, ctx: &mut AppEventContext<'_>) -> Op
         return Some(result);
     }
 
+    if let Some(e) = downcast_event::<SetMarkerEvent>(event) {
+        project.modify_comp(e.comp_uuid, |comp| {
+            comp.set_marker(
+                playa_engine::entities::Marker::new(e.frame)
+                    .with_label(e.label.clone())
+                    .with_color(e.color),
+            );
+        });
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<RemoveMarkerEvent>(event) {
+        project.modify_comp(e.comp_uuid, |comp| {
+            comp.remove_marker(e.frame);
+        });
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<AddAnnotationEvent>(event) {
+        use playa_engine::entities::Annotation;
+        match serde_json::from_value::<Annotation>(e.annotation.clone()) {
+            Ok(annotation) => {
+                project.modify_comp(e.comp_uuid, |comp| comp.add_annotation(annotation));
+            }
+            Err(err) => log::error!("Invalid annotation payload: {}", err),
+        }
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<PopAnnotationEvent>(event) {
+        project.modify_comp(e.comp_uuid, |comp| {
+            comp.pop_annotation(e.frame);
+        });
+        return Some(result);
+    }
+    if let Some(e) = downcast_event::<ClearAnnotationsEvent>(event) {
+        project.modify_comp(e.comp_uuid, |comp| {
+            comp.clear_annotations(e.frame);
+        });
+        return Some(result);
+    }
+
     // Event not handled
     None
 }
@@ -54,6 +54,14 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
         info!("No input file provided, starting with empty state (drag-and-drop supported)");
     }
 
+    // Render-farm mode: encode and exit before any window or GPU setup
+    if args.headless {
+        return crate::headless::run_headless(&args);
+    }
+    if let Some(ref manifest) = args.verify_hashes {
+        return crate::headless::run_verify_hashes(&args, manifest);
+    }
+
     const BACKEND: &str = "vfx-io";
 
     // Request `FLOAT32_FILTERABLE` so the viewport can linearly sample
@@ -85,6 +93,14 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
             }
         });
     }
+    // Vsync pref (display.json): AutoNoVsync presents immediately, may tear
+    let display = config::DisplayConfig::load(&path_config);
+    wgpu_options.present_mode = if display.vsync {
+        wgpu::PresentMode::AutoVsync
+    } else {
+        wgpu::PresentMode::AutoNoVsync
+    };
+    info!("Vsync: {}", if display.vsync { "on" } else { "off" });
 
     let native_options = eframe::NativeOptions {
         viewport: eframe::egui::ViewportBuilder::default()
@@ -97,6 +113,7 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
             .with_resizable(true)
             .with_drag_and_drop(true),
         persist_window: true,
+        vsync: display.vsync,
         renderer: eframe::Renderer::Wgpu,
         wgpu_options,
         #[cfg(not(target_arch = "wasm32"))]
@@ -186,8 +203,22 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
             app.status_bar = StatusBar::new();
             app.applied_mem_fraction = mem_fraction;
             app.applied_cache_strategy = app.settings.cache.cache_strategy;
-            app.applied_workers = desired_workers;
+            // Track the prefs value, so a CLI count survives until the pref is edited
+            app.applied_workers = (app.settings.workers_override > 0)
+                .then_some(app.settings.workers_override as usize);
             app.path_config = path_config_for_app;
+            // Custom hotkeys imported in an earlier session (HotkeyHandler is not serialized)
+            if !app.settings.custom_hotkeys.is_empty()
+                && let Err(e) = app
+                    .hotkey_handler
+                    .import_bindings(&app.settings.custom_hotkeys)
+            {
+                warn!("Ignoring stored custom hotkeys: {}", e);
+            }
+            // Recent items whose files were moved/deleted since last session
+            playa_ui::dialogs::prefs::prune_recent(&mut app.settings.recent_items);
+            // Previous session crashed? Offer its auto-save (prompt shown in the first frame)
+            app.detect_recovery_file();
 
             // serde skips `GpuBlendBridge` channels — rebuild before any worker touches `CompNode::compute`.
             app.ensure_gpu_blend_initialized();
@@ -216,6 +247,8 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
             app.player.set_fps_base(app.settings.playback.fps_base);
             app.player.set_fps_play(app.settings.playback.fps_base);
             app.player.set_loop_enabled(app.settings.playback.loop_enabled);
+            app.player
+                .set_sequence_playback(app.settings.playback.sequence_playback);
             app.shader_manager.current_shader = app.settings.current_shader.clone();
             app.show_help = app.settings.show_help;
             app.show_playlist = app.settings.show_playlist;
@@ -228,6 +261,23 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
                 app.show_help
             );
 
+            // Session-only: the compositor prefs stay as they are
+            app.force_cpu = args.force_cpu;
+
+            // Display-only, so it applies even without files on the command line.
+            // Session-only too: the prefs backdrop is what gets saved
+            if let Some(rgb) = args.background_color {
+                let pref = app.settings.viewport.viewport_background;
+                app.cli_background = Some((rgb, pref));
+                app.settings.viewport.viewport_background = rgb;
+            }
+
+            // Session-only, like --force-cpu: later imports hold stills too
+            app.cli_still_frames = args.still_frames;
+            if let Some(fps) = args.fps {
+                app.settings.playback.sequence_fps = fps;
+            }
+
             // CLI arguments have priority
             let has_cli_input =
                 args.file_path.is_some() || !args.files.is_empty() || args.playlist.is_some();
@@ -249,9 +299,25 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
                     let _ = app.load_sequences(all_files);
                 }
 
+                // Review playlist (.m3u-like or JSON item list): one comp per item,
+                // played back to back (sequence playback, loop off).
+                let review_playlist = args
+                    .playlist
+                    .as_ref()
+                    .filter(|path| playa_engine::entities::Playlist::is_playlist_file(path));
+                let playlist_loaded =
+                    review_playlist.is_some_and(|path| app.load_playlist(path.clone()).is_ok());
+                if playlist_loaded {
+                    // Session-only: the prefs value is what gets saved
+                    app.cli_sequence_playback_pref = Some(app.settings.playback.sequence_playback);
+                    app.player.set_sequence_playback(true);
+                }
+
                 // Load playlist as Project (startup-only path; runtime loading goes through
                 // PlayaApp::load_project in project_io.rs which has the same core steps).
-                if let Some(ref playlist_path) = args.playlist {
+                if let Some(ref playlist_path) = args.playlist
+                    && review_playlist.is_none()
+                {
                     info!("Loading playlist: {}", playlist_path.display());
                     match playa_engine::entities::Project::from_json(playlist_path) {
                         Ok(mut project) => {
@@ -299,7 +365,8 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
                     app.player.set_is_playing(true);
                 }
 
-                app.player.set_loop_enabled(args.loop_playback != 0);
+                app.player
+                    .set_loop_enabled(args.loop_playback != 0 && !playlist_loaded);
 
                 // Set play range
                 let (range_start, range_end) = if let Some(ref range) = args.range {
@@ -318,6 +385,18 @@ pub fn run_app(args: Args) -> Result<(), Box<dyn std::error::Error>> {
                 }
             }
 
+            // Startup script: queued for the API dispatch, runs on the first frame.
+            // Bad lines are logged and skipped; an unreadable file never aborts launch.
+            if let Some(ref script) = args.script {
+                match crate::server::load_script(script) {
+                    Ok(commands) => {
+                        info!("Script {}: {} command(s)", script.display(), commands.len());
+                        app.script_commands = commands;
+                    }
+                    Err(e) => warn!("{}", e),
+                }
+            }
+
             Ok(Box::new(app))
         }),
     )?;
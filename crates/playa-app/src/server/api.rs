@@ -12,11 +12,22 @@
 //! - [`ApiCommand`] - enum of commands sent to main thread (Play, Pause, SetFrame, etc.)
 //! - [`SharedApiState`] - thread-safe snapshots (player, comp, cache) updated by main thread
 //! - [`PlayerSnapshot`], [`CompSnapshot`], [`CacheSnapshot`] - JSON-serializable state copies
+//! - [`MarkerSnapshot`] - timeline marker (frame, label, color) of the active comp
+//! - `GET /api/frame/current.png` - composed frame as PNG via the screenshot queue
+//! - `GET /api/graph` - node graph of the active comp, fetched from the main thread
+//! - [`EncodeJob`], [`EncodeJobSnapshot`] - remote encode table (`/api/encode`)
+//! - `/api/comp/{uuid}/layer[/{child}]` - add / remove / patch layers; validated
+//!   here, applied on the main thread, replies via [`ApiReply`]
+//! - `GET /api/events` - server-sent events stream of [`StreamSnapshot`] on change
+//! - `GET /api/info` - export capabilities of this build (probed once, cached)
+//! - `GET /api/comp/{uuid}/cache` - per-frame cache status map ([`CacheMapSnapshot`])
 //!
 //! # Thread safety
 //!
 //! - `SharedApiState` uses `RwLock` for each field - main thread writes, HTTP handlers read
 //! - `ApiCommand` sent via `mpsc::Sender` - thread-safe, non-blocking
+//! - SSE streams block on a `Condvar` that the main thread signals via
+//!   [`SharedApiState::notify_changed`], so idle streams cost no CPU
 //! - CORS headers added to all responses for browser access
 //!
 //! # Used by
@@ -28,11 +39,33 @@ use crossbeam_channel as crossbeam;
 use eframe::egui;
 use rouille::{Request, Response};
 use serde::{Deserialize, Serialize};
-use std::sync::{Arc, RwLock, mpsc};
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, RwLock, mpsc};
 use std::thread;
 use std::time::Duration;
 use uuid::Uuid;
 
+use playa_engine::entities::attr_schemas::LAYER_SCHEMA;
+use playa_engine::entities::attrs::{AttrType, FLAG_INTERNAL, FLAG_READONLY};
+use playa_engine::entities::effects::EffectType;
+use playa_engine::entities::frame::FrameStatus;
+use playa_engine::entities::{AttrValue, CompWarning};
+use playa_ui::dialogs::encode::{EncoderSettings, encode_capabilities};
+
+/// How long `GET /api/frame/current.png` waits for the frame to finish
+/// rendering before answering 503 (the main thread drops the waiter too)
+pub const FRAME_CAPTURE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Idle interval after which `GET /api/events` sends a comment line so
+/// proxies don't close the connection
+const SSE_HEARTBEAT: Duration = Duration::from_secs(5);
+
+/// One-shot reply from the main thread: value, or HTTP status + message
+pub type ApiReply<T> = crossbeam::Sender<Result<T, (u16, String)>>;
+
 /// Commands sent from API handlers to main thread
 #[derive(Debug)]
 pub enum ApiCommand {
@@ -59,16 +92,75 @@ pub enum ApiCommand {
     NextFrame,
     /// Go to previous frame
     PrevFrame,
+    /// Set (or clear with `None`) the preload radius cap
+    SetMaxPreloadRadius(Option<i32>),
+    /// Add or update a timeline marker on the active comp
+    SetMarker {
+        frame: i32,
+        label: String,
+        color: Option<[f32; 3]>,
+    },
+    /// Add an effect to layer `layer` (0 = top) of the active comp
+    /// (startup scripts)
+    AddEffect { effect: EffectType, layer: usize },
+    /// Start a remote encode of `comp` (active comp if `None`). `fps` overrides
+    /// the comp rate. Replies with the job id, or an HTTP status + message.
+    Encode {
+        comp: Option<Uuid>,
+        fps: Option<f32>,
+        settings: Box<EncoderSettings>,
+        response: crossbeam::Sender<Result<u64, (u16, String)>>,
+    },
+    /// Add `source` as a layer of `comp` at `frame` (comp start if `None`),
+    /// at layer `index` (0 = top; bottom if `None`). Replies with the layer UUID.
+    AddLayer {
+        comp: Uuid,
+        source: Uuid,
+        frame: Option<i32>,
+        index: Option<usize>,
+        response: ApiReply<Uuid>,
+    },
+    /// Remove layer instance `layer` from `comp`
+    RemoveLayer {
+        comp: Uuid,
+        layer: Uuid,
+        response: ApiReply<()>,
+    },
+    /// Set attrs (already validated against the layer schema) on one layer
+    SetLayerAttrs {
+        comp: Uuid,
+        layer: Uuid,
+        attrs: Vec<(String, AttrValue)>,
+        response: ApiReply<()>,
+    },
+    /// Export the active comp's node graph (JSON, or Graphviz DOT when `dot`)
+    Graph {
+        dot: bool,
+        /// Channel to send the exported text back
+        response: crossbeam::Sender<Result<String, String>>,
+    },
+    /// Capture the composed frame as PNG, seeking to `frame` first if given
+    FramePng {
+        frame: Option<i32>,
+        /// Channel to send PNG bytes back
+        response: crossbeam::Sender<Result<Vec<u8>, String>>,
+    },
     Screenshot {
         /// If true, capture viewport render; if false, capture raw frame
         viewport_only: bool,
         /// Channel to send PNG bytes back
         response: crossbeam::Sender<Result<Vec<u8>, String>>,
     },
+    /// Cache status of every frame of `comp`, first frame first, with the
+    /// comp's first frame number
+    CacheMap {
+        comp: Uuid,
+        response: ApiReply<(i32, Vec<FrameStatus>)>,
+    },
 }
 
 /// Player state snapshot for API responses
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PlayerSnapshot {
     pub frame: i32,
     pub fps: f32,
@@ -87,13 +179,103 @@ pub struct CompSnapshot {
     pub duration: i32,
     pub in_frame: i32,
     pub out_frame: i32,
+    /// Timeline markers sorted by frame
+    #[serde(default)]
+    pub markers: Vec<MarkerSnapshot>,
+    /// Advisory fps / resolution mismatches of the comp's layers
+    #[serde(default)]
+    pub warnings: Vec<CompWarning>,
 }
 
-/// Cache stats for API responses
+/// Timeline marker for API responses / requests
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MarkerSnapshot {
+    pub frame: i32,
+    #[serde(default)]
+    pub label: String,
+    /// Linear 0..1 RGB; omitted on requests = default color
+    #[serde(default)]
+    pub color: Option<[f32; 3]>,
+}
+
+/// Cache stats for API responses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct CacheSnapshot {
     pub memory_used_mb: f32,
     pub memory_limit_mb: f32,
+    /// Preload cap in frames either side of the playhead (`null` = work area)
+    pub max_preload_radius: Option<i32>,
+}
+
+/// Wire value of a frame status in [`CacheMapSnapshot`]:
+///
+/// | byte | status | meaning |
+/// |------|--------|---------|
+/// | 0 | placeholder | nothing cached |
+/// | 1 | header | source known, no pixels yet |
+/// | 2 | loading | file decode in flight |
+/// | 3 | composing | comp render in flight |
+/// | 4 | expired | cached but stale, recompute pending |
+/// | 5 | loaded | cached and current |
+/// | 6 | error | load failed |
+pub fn status_byte(status: FrameStatus) -> u8 {
+    match status {
+        FrameStatus::Placeholder => 0,
+        FrameStatus::Header => 1,
+        FrameStatus::Loading => 2,
+        FrameStatus::Composing => 3,
+        FrameStatus::Expired => 4,
+        FrameStatus::Loaded => 5,
+        FrameStatus::Error => 6,
+    }
+}
+
+/// Cache map of one comp for `GET /api/comp/{uuid}/cache`: one
+/// [`status_byte`] per frame `start..=end`, base64 in `statuses`, or as
+/// `[byte, count]` runs in `runs` with `?encoding=rle`.
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheMapSnapshot {
+    pub comp: Uuid,
+    pub start: i32,
+    pub end: i32,
+    /// Frames with status `loaded`
+    pub loaded: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub statuses: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub runs: Option<Vec<(u8, usize)>>,
+}
+
+impl CacheMapSnapshot {
+    pub fn new(comp: Uuid, start: i32, statuses: &[FrameStatus], rle: bool) -> Self {
+        use base64::Engine;
+
+        let bytes: Vec<u8> = statuses.iter().map(|s| status_byte(*s)).collect();
+        let (statuses, runs) = if rle {
+            let mut runs: Vec<(u8, usize)> = Vec::new();
+            for &b in &bytes {
+                match runs.last_mut() {
+                    Some((last, count)) if *last == b => *count += 1,
+                    _ => runs.push((b, 1)),
+                }
+            }
+            (None, Some(runs))
+        } else {
+            let encoded = base64::engine::general_purpose::STANDARD.encode(&bytes);
+            (Some(encoded), None)
+        };
+        Self {
+            comp,
+            start,
+            end: start + bytes.len() as i32 - 1,
+            loaded: bytes
+                .iter()
+                .filter(|&&b| b == status_byte(FrameStatus::Loaded))
+                .count(),
+            statuses,
+            runs,
+        }
+    }
 }
 
 /// Full status response
@@ -104,6 +286,50 @@ pub struct StatusResponse {
     pub cache: CacheSnapshot,
 }
 
+/// Remote encode job status for `GET /api/encode/{id}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodeJobSnapshot {
+    pub id: u64,
+    pub comp: Uuid,
+    pub output_path: PathBuf,
+    /// `EncodeStage` name ("Validating", "Encoding", "Complete", ...), plus
+    /// "Queued" before the first progress update and "Cancelled"
+    pub stage: String,
+    pub current_frame: i32,
+    pub total_frames: i32,
+    /// 0..1
+    pub progress: f32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<EncodeJobError>,
+}
+
+impl EncodeJobSnapshot {
+    /// Finished one way or another (no worker thread left)
+    pub fn is_finished(&self) -> bool {
+        matches!(self.stage.as_str(), "Complete" | "Error" | "Cancelled")
+    }
+}
+
+/// `EncodeError` as JSON: variant name + display message
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncodeJobError {
+    pub kind: String,
+    pub message: String,
+}
+
+/// Entry in the encode job table: status + the cancel flag the encoder polls
+pub struct EncodeJob {
+    pub snapshot: EncodeJobSnapshot,
+    pub cancel: Arc<AtomicBool>,
+}
+
+/// Live state pushed to `GET /api/events` subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamSnapshot {
+    pub player: PlayerSnapshot,
+    pub cache: CacheSnapshot,
+}
+
 /// Shared state readable by API handlers (updated by main thread)
 pub struct SharedApiState {
     pub player: RwLock<PlayerSnapshot>,
@@ -111,6 +337,78 @@ pub struct SharedApiState {
     pub cache: RwLock<CacheSnapshot>,
     /// egui context for triggering immediate repaint (set lazily from main thread)
     pub egui_ctx: RwLock<Option<egui::Context>>,
+    /// Remote encode jobs by id (`/api/encode`); finished jobs stay for polling
+    pub encode_jobs: RwLock<BTreeMap<u64, EncodeJob>>,
+    /// Required `Authorization: Bearer` token (from prefs); `None` leaves the API open
+    pub auth_token: RwLock<Option<String>>,
+    /// Change counter for SSE streams, bumped by [`Self::notify_changed`]
+    generation: Mutex<u64>,
+    changed: Condvar,
+}
+
+impl SharedApiState {
+    /// Wake `GET /api/events` streams. Call after a player/cache snapshot changed.
+    pub fn notify_changed(&self) {
+        *self.generation.lock().unwrap() += 1;
+        self.changed.notify_all();
+    }
+
+    /// Block until the generation moves past `seen` or `timeout` elapses.
+    /// Returns the current generation (unchanged on timeout).
+    fn wait_changed(&self, seen: u64, timeout: Duration) -> u64 {
+        let guard = self.generation.lock().unwrap();
+        let (guard, _) = self
+            .changed
+            .wait_timeout_while(guard, timeout, |generation| *generation == seen)
+            .unwrap();
+        *guard
+    }
+
+    /// True while a remote encode is still running (one at a time)
+    pub fn encode_running(&self) -> bool {
+        self.encode_jobs
+            .read()
+            .unwrap()
+            .values()
+            .any(|job| !job.snapshot.is_finished())
+    }
+
+    /// Register a queued encode job and return its id
+    pub fn add_encode_job(&self, comp: Uuid, output_path: PathBuf, cancel: Arc<AtomicBool>) -> u64 {
+        let mut jobs = self.encode_jobs.write().unwrap();
+        let id = jobs.keys().next_back().map_or(1, |last| last + 1);
+        jobs.insert(
+            id,
+            EncodeJob {
+                snapshot: EncodeJobSnapshot {
+                    id,
+                    comp,
+                    output_path,
+                    stage: "Queued".to_string(),
+                    current_frame: 0,
+                    total_frames: 0,
+                    progress: 0.0,
+                    error: None,
+                },
+                cancel,
+            },
+        );
+        id
+    }
+
+    /// Mutate a job's status (no-op for unknown ids)
+    pub fn update_encode_job(&self, id: u64, f: impl FnOnce(&mut EncodeJobSnapshot)) {
+        if let Some(job) = self.encode_jobs.write().unwrap().get_mut(&id) {
+            f(&mut job.snapshot);
+        }
+    }
+
+    fn stream_snapshot(&self) -> StreamSnapshot {
+        StreamSnapshot {
+            player: self.player.read().unwrap().clone(),
+            cache: self.cache.read().unwrap().clone(),
+        }
+    }
 }
 
 impl Default for SharedApiState {
@@ -127,18 +425,195 @@ impl Default for SharedApiState {
             cache: RwLock::new(CacheSnapshot {
                 memory_used_mb: 0.0,
                 memory_limit_mb: 0.0,
+                max_preload_radius: None,
             }),
             egui_ctx: RwLock::new(None),
+            encode_jobs: RwLock::new(BTreeMap::new()),
+            auth_token: RwLock::new(None),
+            generation: Mutex::new(0),
+            changed: Condvar::new(),
         }
     }
 }
 
+/// `GET /api/events` stream: an endless `text/event-stream`.
+///
+/// tiny_http buffers response bodies in 8 KiB chunks and only flushes at the
+/// end, so the stream takes the connection over through rouille's upgrade
+/// hook and flushes after every event. An event is either a `data:` line
+/// with the new [`StreamSnapshot`] once the main thread reports a change,
+/// or a `:` heartbeat comment when [`SSE_HEARTBEAT`] passes quietly. The
+/// stream ends when the client disconnects and a write fails.
+struct EventStream {
+    state: Arc<SharedApiState>,
+    /// Generation the last event was built from (`None` before the first event)
+    seen: Option<u64>,
+    /// Last JSON sent, to skip no-op notifications
+    last_json: String,
+}
+
+impl EventStream {
+    fn new(state: Arc<SharedApiState>) -> Self {
+        Self {
+            state,
+            seen: None,
+            last_json: String::new(),
+        }
+    }
+
+    /// Wait for the next change or heartbeat and encode it as one event.
+    fn next_event(&mut self) -> Vec<u8> {
+        loop {
+            let generation = match self.seen {
+                // First event: send the current state right away
+                None => *self.state.generation.lock().unwrap(),
+                Some(seen) => {
+                    let generation = self.state.wait_changed(seen, SSE_HEARTBEAT);
+                    if generation == seen {
+                        return b": heartbeat\n\n".to_vec();
+                    }
+                    generation
+                }
+            };
+            self.seen = Some(generation);
+
+            let json = serde_json::to_string(&self.state.stream_snapshot()).unwrap_or_default();
+            if json != self.last_json {
+                let event = format!("data: {}\n\n", json).into_bytes();
+                self.last_json = json;
+                return event;
+            }
+        }
+    }
+
+    /// Write events to `socket` until the client goes away.
+    fn run(mut self, mut socket: impl Write) {
+        loop {
+            let event = self.next_event();
+            let sent = socket.write_all(&event).and_then(|()| socket.flush());
+            if sent.is_err() {
+                return;
+            }
+        }
+    }
+}
+
+impl rouille::Upgrade for EventStream {
+    fn build(&mut self, socket: Box<dyn rouille::ReadWrite + Send>) {
+        // Stream on its own thread so the rouille worker goes back to the pool
+        let stream = EventStream::new(Arc::clone(&self.state));
+        thread::spawn(move || stream.run(socket));
+    }
+}
+
 /// Request body for loading sequences
 #[derive(Debug, Deserialize)]
 struct LoadRequest {
     path: String,
 }
 
+/// Request body for `POST /api/cache`
+#[derive(Debug, Deserialize)]
+struct CacheRequest {
+    /// Frames either side of the playhead; `null` restores "fill work area"
+    max_preload_radius: Option<i32>,
+}
+
+/// Request body for `POST /api/encode`: any subset of `EncoderSettings`
+/// (missing fields use defaults) plus the comp to render. `"range": [start, end]`
+/// encodes just those comp frames instead of the work area.
+#[derive(Debug, Deserialize)]
+struct EncodeRequest {
+    /// Comp to encode; active comp when omitted
+    #[serde(default)]
+    comp: Option<Uuid>,
+    /// Output rate; comp fps when omitted
+    #[serde(default)]
+    fps: Option<f32>,
+    #[serde(flatten)]
+    settings: EncoderSettings,
+}
+
+/// Request body for `POST /api/comp/{uuid}/layer`
+#[derive(Debug, Deserialize)]
+struct AddLayerRequest {
+    source: Uuid,
+    #[serde(default)]
+    frame: Option<i32>,
+    #[serde(default)]
+    index: Option<usize>,
+}
+
+/// JSON schema of [`AddLayerRequest`], echoed in 400 responses
+fn add_layer_schema() -> serde_json::Value {
+    serde_json::json!({
+        "type": "object",
+        "required": ["source"],
+        "properties": {
+            "source": { "type": "string", "format": "uuid" },
+            "frame": { "type": "integer", "description": "start frame (default: comp start)" },
+            "index": { "type": "integer", "minimum": 0, "description": "0 = top (default: bottom)" }
+        },
+        "additionalProperties": false
+    })
+}
+
+/// JSON schema of a `PATCH /api/comp/{uuid}/layer/{child}` body: every
+/// user-editable layer attr with its plain-JSON type
+fn layer_attrs_schema() -> serde_json::Value {
+    let mut props = serde_json::Map::new();
+    for def in LAYER_SCHEMA
+        .iter()
+        .filter(|d| d.flags & (FLAG_INTERNAL | FLAG_READONLY) == 0)
+    {
+        let mut prop = match def.attr_type {
+            AttrType::Bool => serde_json::json!({ "type": "boolean" }),
+            AttrType::Int => serde_json::json!({ "type": "integer" }),
+            AttrType::Float => serde_json::json!({ "type": "number" }),
+            AttrType::String => serde_json::json!({ "type": "string" }),
+            AttrType::Uuid => serde_json::json!({ "type": "string", "format": "uuid" }),
+            AttrType::Vec3 => serde_json::json!({
+                "type": "array", "items": { "type": "number" }, "minItems": 3, "maxItems": 3
+            }),
+            AttrType::Vec4 => serde_json::json!({
+                "type": "array", "items": { "type": "number" }, "minItems": 4, "maxItems": 4
+            }),
+            AttrType::List | AttrType::Set => serde_json::json!({ "type": "array" }),
+            AttrType::Map | AttrType::Json => serde_json::json!({}),
+        };
+        // String attrs with combobox options are enums
+        if def.attr_type == AttrType::String && !def.ui_options.is_empty() {
+            prop["enum"] = serde_json::json!(def.ui_options);
+        }
+        props.insert(def.name.to_string(), prop);
+    }
+    serde_json::json!({
+        "type": "object",
+        "properties": props,
+        "additionalProperties": false,
+        "minProperties": 1
+    })
+}
+
+/// Validate a PATCH body against the layer schema and convert to attr values
+fn parse_layer_attrs(body: &serde_json::Value) -> Result<Vec<(String, AttrValue)>, String> {
+    let obj = body
+        .as_object()
+        .filter(|o| !o.is_empty())
+        .ok_or("Body must be a non-empty JSON object")?;
+    obj.iter()
+        .map(|(key, value)| {
+            let def = LAYER_SCHEMA
+                .get(key)
+                .filter(|d| d.flags & (FLAG_INTERNAL | FLAG_READONLY) == 0)
+                .ok_or_else(|| format!("Unknown or read-only layer attr '{}'", key))?;
+            let value = AttrValue::from_json(value, def.attr_type)
+                .ok_or_else(|| format!("Attr '{}' expects {:?}", key, def.attr_type))?;
+            Ok((key.clone(), value))
+        })
+        .collect()
+}
+
 /// Request body for emitting events
 #[derive(Debug, Deserialize)]
 struct EventRequest {
@@ -155,6 +630,9 @@ struct ApiResponse {
     message: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    /// Expected request body (JSON schema) on 400s
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema: Option<serde_json::Value>,
 }
 
 impl ApiResponse {
@@ -163,6 +641,7 @@ impl ApiResponse {
             success: true,
             message: None,
             error: None,
+            schema: None,
         }
     }
 
@@ -171,6 +650,7 @@ impl ApiResponse {
             success: true,
             message: Some(msg.to_string()),
             error: None,
+            schema: None,
         }
     }
 
@@ -179,10 +659,31 @@ impl ApiResponse {
             success: false,
             message: None,
             error: Some(msg.to_string()),
+            schema: None,
+        }
+    }
+
+    /// 400-style error that tells the client what the body should look like
+    fn err_schema(msg: &str, schema: serde_json::Value) -> Self {
+        Self {
+            schema: Some(schema),
+            ..Self::err(msg)
         }
     }
 }
 
+/// Compare secrets without an early exit, so response timing doesn't leak
+/// how much of a guessed token matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    let mut diff = a.len() ^ b.len();
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or(0);
+        let y = b.get(i).copied().unwrap_or(0);
+        diff |= usize::from(x ^ y);
+    }
+    diff == 0
+}
+
 /// REST API server
 pub struct ApiServer {
     port: u16,
@@ -232,6 +733,28 @@ impl ApiServer {
         }
     }
 
+    /// True if no token is configured or the request carries the right one.
+    ///
+    /// `/api/events` also takes `?token=`: browser `EventSource` can't set an
+    /// `Authorization` header.
+    fn authorized(request: &Request, state: &SharedApiState) -> bool {
+        let token = state.auth_token.read().unwrap();
+        let Some(token) = token.as_deref() else {
+            return true;
+        };
+        let query_token = (request.url() == "/api/events")
+            .then(|| request.get_param("token"))
+            .flatten();
+        let given = query_token.or_else(|| {
+            request
+                .header("Authorization")
+                .and_then(|value| value.trim().split_once(' '))
+                .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("Bearer"))
+                .map(|(_, given)| given.trim().to_string())
+        });
+        given.is_some_and(|given| constant_time_eq(given.as_bytes(), token.as_bytes()))
+    }
+
     fn handle_request(
         request: &Request,
         state: &Arc<SharedApiState>,
@@ -243,8 +766,23 @@ impl ApiServer {
         if request.method() == "OPTIONS" {
             return Response::empty_204()
                 .with_additional_header("Access-Control-Allow-Origin", "*")
-                .with_additional_header("Access-Control-Allow-Methods", "GET, POST, OPTIONS")
-                .with_additional_header("Access-Control-Allow-Headers", "Content-Type");
+                .with_additional_header(
+                    "Access-Control-Allow-Methods",
+                    "GET, POST, PATCH, DELETE, OPTIONS",
+                )
+                .with_additional_header(
+                    "Access-Control-Allow-Headers",
+                    "Content-Type, Authorization",
+                );
+        }
+
+        // Health check stays open so monitors work without the token
+        let is_health = request.method() == "GET" && request.url() == "/api/health";
+        if !is_health && !Self::authorized(request, state) {
+            return Response::json(&ApiResponse::err("Missing or invalid bearer token"))
+                .with_status_code(401)
+                .with_additional_header("WWW-Authenticate", "Bearer")
+                .with_additional_header("Access-Control-Allow-Origin", "*");
         }
 
         // Handle paths with parameters manually (router! doesn't capture well)
@@ -279,6 +817,36 @@ impl ApiServer {
             }
         }
 
+        // /api/comp/{uuid}/layer[/{child}] (POST add, DELETE remove, PATCH attrs)
+        if let Some(rest) = path.strip_prefix("/api/comp/") {
+            let parts: Vec<&str> = rest.split('/').collect();
+            if parts.len() >= 2 && parts[1] == "layer" {
+                return Self::handle_layer(request, tx, state, &parts)
+                    .with_additional_header("Access-Control-Allow-Origin", "*");
+            }
+            if parts.len() == 2 && parts[1] == "cache" {
+                let response = if request.method() == "GET" {
+                    Self::get_cache_map(request, tx, state, parts[0])
+                } else {
+                    Response::json(&ApiResponse::err("Method not allowed")).with_status_code(405)
+                };
+                return response.with_additional_header("Access-Control-Allow-Origin", "*");
+            }
+        }
+
+        // /api/encode/{id} (GET status, DELETE cancel)
+        if let Some(id_str) = path.strip_prefix("/api/encode/") {
+            let response = match (request.method(), id_str.parse::<u64>()) {
+                ("GET", Ok(id)) => Self::get_encode_job(state, id),
+                ("DELETE", Ok(id)) => Self::cancel_encode_job(state, id),
+                (_, Err(_)) => {
+                    Response::json(&ApiResponse::err("Invalid job id")).with_status_code(400)
+                }
+                _ => Response::json(&ApiResponse::err("Method not allowed")).with_status_code(405),
+            };
+            return response.with_additional_header("Access-Control-Allow-Origin", "*");
+        }
+
         let response = rouille::router!(request,
             // Status endpoints
             (GET) ["/api/status"] => {
@@ -293,6 +861,21 @@ impl ApiServer {
             (GET) ["/api/cache"] => {
                 Self::get_cache(state)
             },
+            (GET) ["/api/comp/markers"] => {
+                Self::get_markers(state)
+            },
+            // Build capabilities (EXR backend, encoders, FFmpeg version)
+            (GET) ["/api/info"] => {
+                Response::json(encode_capabilities())
+            },
+            // Live state stream (server-sent events)
+            (GET) ["/api/events"] => {
+                Self::handle_events_stream(state)
+            },
+            // Node graph of the active comp; ?format=dot for Graphviz
+            (GET) ["/api/graph"] => {
+                Self::handle_graph(request, tx, state)
+            },
 
             // Player control
             (POST) ["/api/player/play"] => {
@@ -328,6 +911,24 @@ impl ApiServer {
                 Self::handle_load(request, tx)
             },
 
+            // Cache settings (preload cap)
+            (POST) ["/api/cache"] => {
+                Self::handle_set_cache(request, tx)
+            },
+
+            // Timeline markers (add / update at frame)
+            (POST) ["/api/comp/markers"] => {
+                Self::handle_set_marker(request, tx)
+            },
+
+            // Remote encode (job status: /api/encode/{id})
+            (POST) ["/api/encode"] => {
+                Self::handle_encode(request, tx, state)
+            },
+            (GET) ["/api/encode"] => {
+                Self::list_encode_jobs(state)
+            },
+
             // Generic event emission
             (POST) ["/api/event"] => {
                 Self::handle_event(request, tx)
@@ -347,6 +948,10 @@ impl ApiServer {
             (GET) ["/api/screenshot/frame"] => {
                 Self::handle_screenshot(tx, state, false)  // viewport_only=false means raw frame
             },
+            // /api/frame/current.png[?frame=N] - composed frame as lossless PNG
+            (GET) ["/api/frame/current.png"] => {
+                Self::handle_frame_png(request, tx, state)
+            },
 
             // Fallback
             _ => {
@@ -383,6 +988,14 @@ impl ApiServer {
         }
     }
 
+    fn get_markers(state: &Arc<SharedApiState>) -> Response {
+        let comp = state.comp.read().unwrap().clone();
+        match comp {
+            Some(c) => Response::json(&c.markers),
+            None => Response::json(&ApiResponse::err("No active comp")).with_status_code(404),
+        }
+    }
+
     fn get_cache(state: &Arc<SharedApiState>) -> Response {
         let cache = state.cache.read().unwrap().clone();
         Response::json(&cache)
@@ -404,6 +1017,35 @@ impl ApiServer {
         }
     }
 
+    fn handle_set_cache(request: &Request, tx: &mpsc::Sender<ApiCommand>) -> Response {
+        match rouille::input::json_input::<CacheRequest>(request) {
+            Ok(req) if req.max_preload_radius.is_some_and(|r| r < 0) => {
+                Response::json(&ApiResponse::err("max_preload_radius must be >= 0"))
+                    .with_status_code(400)
+            }
+            Ok(req) => {
+                Self::send_command(tx, ApiCommand::SetMaxPreloadRadius(req.max_preload_radius))
+            }
+            Err(e) => Response::json(&ApiResponse::err(&format!("Invalid JSON: {}", e)))
+                .with_status_code(400),
+        }
+    }
+
+    fn handle_set_marker(request: &Request, tx: &mpsc::Sender<ApiCommand>) -> Response {
+        match rouille::input::json_input::<MarkerSnapshot>(request) {
+            Ok(m) => Self::send_command(
+                tx,
+                ApiCommand::SetMarker {
+                    frame: m.frame,
+                    label: m.label,
+                    color: m.color,
+                },
+            ),
+            Err(e) => Response::json(&ApiResponse::err(&format!("Invalid JSON: {}", e)))
+                .with_status_code(400),
+        }
+    }
+
     fn handle_event(request: &Request, tx: &mpsc::Sender<ApiCommand>) -> Response {
         match rouille::input::json_input::<EventRequest>(request) {
             Ok(req) => {
@@ -421,6 +1063,301 @@ impl ApiServer {
         }
     }
 
+    /// Send a command carrying an [`ApiReply`] and wait for the main thread.
+    fn call_main<T>(
+        tx: &mpsc::Sender<ApiCommand>,
+        state: &SharedApiState,
+        make: impl FnOnce(ApiReply<T>) -> ApiCommand,
+    ) -> Result<T, Response> {
+        let (resp_tx, resp_rx) = crossbeam::bounded(1);
+        tx.send(make(resp_tx)).map_err(|e| {
+            Response::json(&ApiResponse::err(&format!("Failed to send command: {}", e)))
+                .with_status_code(500)
+        })?;
+        if let Some(ctx) = state.egui_ctx.read().unwrap().as_ref() {
+            ctx.request_repaint();
+        }
+        match resp_rx.recv_timeout(Duration::from_secs(5)) {
+            Ok(Ok(value)) => Ok(value),
+            Ok(Err((code, err))) => {
+                Err(Response::json(&ApiResponse::err(&err)).with_status_code(code))
+            }
+            Err(_) => {
+                Err(Response::json(&ApiResponse::err("Main thread timeout")).with_status_code(503))
+            }
+        }
+    }
+
+    /// Per-frame cache statuses of a comp, read on the main thread (it owns
+    /// the project and the global cache).
+    fn get_cache_map(
+        request: &Request,
+        tx: &mpsc::Sender<ApiCommand>,
+        state: &SharedApiState,
+        comp: &str,
+    ) -> Response {
+        let Ok(comp) = Uuid::parse_str(comp) else {
+            return Response::json(&ApiResponse::err("Invalid comp UUID")).with_status_code(400);
+        };
+        let rle = match request.get_param("encoding").as_deref() {
+            None | Some("base64") => false,
+            Some("rle") => true,
+            Some(other) => {
+                return Response::json(&ApiResponse::err(&format!(
+                    "Unknown encoding '{}' (base64, rle)",
+                    other
+                )))
+                .with_status_code(400);
+            }
+        };
+        match Self::call_main(tx, state, |response| ApiCommand::CacheMap {
+            comp,
+            response,
+        }) {
+            Ok((start, statuses)) => {
+                Response::json(&CacheMapSnapshot::new(comp, start, &statuses, rle))
+            }
+            Err(resp) => resp,
+        }
+    }
+
+    /// `parts` = path after `/api/comp/`, split on `/`: `[comp, "layer", child?]`
+    fn handle_layer(
+        request: &Request,
+        tx: &mpsc::Sender<ApiCommand>,
+        state: &SharedApiState,
+        parts: &[&str],
+    ) -> Response {
+        let bad_uuid = |what: &str| {
+            Response::json(&ApiResponse::err(&format!("Invalid {} UUID", what)))
+                .with_status_code(400)
+        };
+        let Ok(comp) = Uuid::parse_str(parts[0]) else {
+            return bad_uuid("comp");
+        };
+
+        match (request.method(), parts.get(2)) {
+            ("POST", None) => {
+                let req = match rouille::input::json_input::<AddLayerRequest>(request) {
+                    Ok(req) => req,
+                    Err(e) => {
+                        return Response::json(&ApiResponse::err_schema(
+                            &format!("Invalid JSON: {}", e),
+                            add_layer_schema(),
+                        ))
+                        .with_status_code(400);
+                    }
+                };
+                let result = Self::call_main(tx, state, |response| ApiCommand::AddLayer {
+                    comp,
+                    source: req.source,
+                    frame: req.frame,
+                    index: req.index,
+                    response,
+                });
+                match result {
+                    Ok(layer) => {
+                        Response::json(&serde_json::json!({ "uuid": layer })).with_status_code(201)
+                    }
+                    Err(resp) => resp,
+                }
+            }
+            ("DELETE", Some(child)) => {
+                let Ok(layer) = Uuid::parse_str(child) else {
+                    return bad_uuid("layer");
+                };
+                match Self::call_main(tx, state, |response| ApiCommand::RemoveLayer {
+                    comp,
+                    layer,
+                    response,
+                }) {
+                    Ok(()) => Response::json(&ApiResponse::ok()),
+                    Err(resp) => resp,
+                }
+            }
+            ("PATCH", Some(child)) => {
+                let Ok(layer) = Uuid::parse_str(child) else {
+                    return bad_uuid("layer");
+                };
+                let attrs = match rouille::input::json_input::<serde_json::Value>(request)
+                    .map_err(|e| format!("Invalid JSON: {}", e))
+                    .and_then(|body| parse_layer_attrs(&body))
+                {
+                    Ok(attrs) => attrs,
+                    Err(e) => {
+                        return Response::json(&ApiResponse::err_schema(&e, layer_attrs_schema()))
+                            .with_status_code(400);
+                    }
+                };
+                match Self::call_main(tx, state, |response| ApiCommand::SetLayerAttrs {
+                    comp,
+                    layer,
+                    attrs,
+                    response,
+                }) {
+                    Ok(()) => Response::json(&ApiResponse::ok()),
+                    Err(resp) => resp,
+                }
+            }
+            _ => Response::json(&ApiResponse::err("Method not allowed")).with_status_code(405),
+        }
+    }
+
+    /// Start an encode on the main thread (it owns the comp + project) and
+    /// return `{"id": N}`; 409 while another encode is running.
+    fn handle_encode(
+        request: &Request,
+        tx: &mpsc::Sender<ApiCommand>,
+        state: &SharedApiState,
+    ) -> Response {
+        let req = match rouille::input::json_input::<EncodeRequest>(request) {
+            Ok(req) => req,
+            Err(e) => {
+                return Response::json(&ApiResponse::err(&format!("Invalid JSON: {}", e)))
+                    .with_status_code(400);
+            }
+        };
+        if state.encode_running() {
+            return Response::json(&ApiResponse::err("An encode is already running"))
+                .with_status_code(409);
+        }
+
+        let (resp_tx, resp_rx) = crossbeam::bounded(1);
+        if let Err(e) = tx.send(ApiCommand::Encode {
+            comp: req.comp,
+            fps: req.fps,
+            settings: Box::new(req.settings),
+            response: resp_tx,
+        }) {
+            return Response::json(&ApiResponse::err(&format!("Failed to send command: {}", e)))
+                .with_status_code(500);
+        }
+        if let Some(ctx) = state.egui_ctx.read().unwrap().as_ref() {
+            ctx.request_repaint();
+        }
+
+        match resp_rx.recv_timeout(Duration::from_secs(5)) {
+            Ok(Ok(id)) => Response::json(&serde_json::json!({ "id": id })).with_status_code(202),
+            Ok(Err((code, err))) => Response::json(&ApiResponse::err(&err)).with_status_code(code),
+            Err(_) => {
+                Response::json(&ApiResponse::err("Encode start timeout")).with_status_code(503)
+            }
+        }
+    }
+
+    fn list_encode_jobs(state: &SharedApiState) -> Response {
+        let jobs: Vec<EncodeJobSnapshot> = state
+            .encode_jobs
+            .read()
+            .unwrap()
+            .values()
+            .map(|job| job.snapshot.clone())
+            .collect();
+        Response::json(&jobs)
+    }
+
+    fn get_encode_job(state: &SharedApiState, id: u64) -> Response {
+        match state.encode_jobs.read().unwrap().get(&id) {
+            Some(job) => Response::json(&job.snapshot),
+            None => Response::json(&ApiResponse::err("No such encode job")).with_status_code(404),
+        }
+    }
+
+    /// Set the job's cancel flag; the encoder stops at the next frame
+    fn cancel_encode_job(state: &SharedApiState, id: u64) -> Response {
+        match state.encode_jobs.read().unwrap().get(&id) {
+            Some(job) if job.snapshot.is_finished() => {
+                Response::json(&ApiResponse::err("Encode already finished")).with_status_code(409)
+            }
+            Some(job) => {
+                job.cancel.store(true, Ordering::Relaxed);
+                Response::json(&ApiResponse::ok_msg("Cancel requested"))
+            }
+            None => Response::json(&ApiResponse::err("No such encode job")).with_status_code(404),
+        }
+    }
+
+    /// Open a server-sent events stream (`EventSource('/api/events')`).
+    fn handle_events_stream(state: &Arc<SharedApiState>) -> Response {
+        Response {
+            status_code: 200,
+            headers: vec![
+                ("Content-Type".into(), "text/event-stream".into()),
+                ("Cache-Control".into(), "no-cache".into()),
+            ],
+            data: rouille::ResponseBody::empty(),
+            upgrade: Some(Box::new(EventStream::new(Arc::clone(state)))),
+        }
+    }
+
+    /// Handle node graph request - the graph lives on the main thread, so ask
+    /// for it and wait like screenshots do
+    fn handle_graph(
+        request: &Request,
+        tx: &mpsc::Sender<ApiCommand>,
+        state: &SharedApiState,
+    ) -> Response {
+        let dot = request.get_param("format").as_deref() == Some("dot");
+        let (resp_tx, resp_rx) = crossbeam::bounded(1);
+        if let Err(e) = tx.send(ApiCommand::Graph {
+            dot,
+            response: resp_tx,
+        }) {
+            return Response::json(&ApiResponse::err(&format!("Failed to send command: {}", e)))
+                .with_status_code(500);
+        }
+        if let Some(ctx) = state.egui_ctx.read().unwrap().as_ref() {
+            ctx.request_repaint();
+        }
+
+        match resp_rx.recv_timeout(Duration::from_secs(5)) {
+            Ok(Ok(text)) if dot => Response::from_data("text/vnd.graphviz", text),
+            Ok(Ok(text)) => Response::from_data("application/json", text),
+            Ok(Err(err)) => Response::json(&ApiResponse::err(&err)).with_status_code(404),
+            Err(_) => {
+                Response::json(&ApiResponse::err("Graph export timeout")).with_status_code(504)
+            }
+        }
+    }
+
+    /// Handle current-frame PNG request. Optional `?frame=N` seeks first; the
+    /// main thread answers once that frame is rendered, else 503 on timeout.
+    fn handle_frame_png(
+        request: &Request,
+        tx: &mpsc::Sender<ApiCommand>,
+        state: &SharedApiState,
+    ) -> Response {
+        let frame = match request.get_param("frame") {
+            Some(s) => match s.parse::<i32>() {
+                Ok(f) => Some(f),
+                Err(_) => {
+                    return Response::json(&ApiResponse::err("Invalid frame number"))
+                        .with_status_code(400);
+                }
+            },
+            None => None,
+        };
+
+        let (resp_tx, resp_rx) = crossbeam::bounded(1);
+        if let Err(e) = tx.send(ApiCommand::FramePng {
+            frame,
+            response: resp_tx,
+        }) {
+            return Response::json(&ApiResponse::err(&format!("Failed to send command: {}", e)))
+                .with_status_code(500);
+        }
+        if let Some(ctx) = state.egui_ctx.read().unwrap().as_ref() {
+            ctx.request_repaint();
+        }
+
+        match resp_rx.recv_timeout(FRAME_CAPTURE_TIMEOUT) {
+            Ok(Ok(png_bytes)) => Response::from_data("image/png", png_bytes),
+            Ok(Err(err)) => Response::json(&ApiResponse::err(&err)).with_status_code(500),
+            Err(_) => Response::json(&ApiResponse::err("Frame not ready (viewport busy)"))
+                .with_status_code(503),
+        }
+    }
+
     /// Handle screenshot request - sends command and waits for JPEG response
     fn handle_screenshot(
         tx: &mpsc::Sender<ApiCommand>,
@@ -459,3 +1396,126 @@ impl ApiServer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpStream;
+
+    /// Read from `socket` until `buf` holds `count` complete SSE events
+    fn read_events(socket: &mut TcpStream, buf: &mut String, count: usize) {
+        let mut chunk = [0u8; 1024];
+        while buf.matches("\n\n").count() < count {
+            let n = socket.read(&mut chunk).expect("event within the timeout");
+            assert!(n > 0, "stream closed early: {buf:?}");
+            buf.push_str(&String::from_utf8_lossy(&chunk[..n]));
+        }
+    }
+
+    #[test]
+    fn events_stream_flushes_each_event() {
+        let state = Arc::new(SharedApiState::default());
+        let server_state = Arc::clone(&state);
+        let server = rouille::Server::new("127.0.0.1:0", move |_request| {
+            ApiServer::handle_events_stream(&server_state)
+        })
+        .unwrap();
+        let addr = server.server_addr();
+        thread::spawn(move || server.run());
+
+        let mut socket = TcpStream::connect(addr).unwrap();
+        socket
+            .set_read_timeout(Some(Duration::from_secs(2)))
+            .unwrap();
+        socket
+            .write_all(b"GET /api/events HTTP/1.1\r\nHost: localhost\r\n\r\n")
+            .unwrap();
+
+        // Headers, then the current state right away
+        let mut buf = String::new();
+        read_events(&mut socket, &mut buf, 1);
+        assert!(buf.contains("text/event-stream"));
+        assert!(buf.contains("data: {\"player\":{\"frame\":0,"));
+
+        // A change shows up without waiting for more output to fill a buffer
+        state.player.write().unwrap().frame = 42;
+        state.notify_changed();
+        read_events(&mut socket, &mut buf, 2);
+        assert!(buf.contains("\"frame\":42"));
+    }
+
+    /// State requiring `token`
+    fn with_token(token: &str) -> SharedApiState {
+        let state = SharedApiState::default();
+        *state.auth_token.write().unwrap() = Some(token.to_string());
+        state
+    }
+
+    fn get(url: &str, auth: Option<&str>) -> Request {
+        let headers = auth
+            .map(|value| vec![("Authorization".to_string(), value.to_string())])
+            .unwrap_or_default();
+        Request::fake_http("GET", url, headers, Vec::new())
+    }
+
+    #[test]
+    fn token_check() {
+        assert!(constant_time_eq(b"secret", b"secret"));
+        assert!(!constant_time_eq(b"secret", b"secreT"));
+        // Unequal lengths, including a matching prefix
+        assert!(!constant_time_eq(b"secret", b"secret2"));
+        assert!(!constant_time_eq(b"", b"secret"));
+
+        let open = SharedApiState::default();
+        assert!(ApiServer::authorized(&get("/api/status", None), &open));
+
+        let state = with_token("secret");
+        let authorized =
+            |url: &str, auth: Option<&str>| ApiServer::authorized(&get(url, auth), &state);
+        assert!(!authorized("/api/status", None));
+        assert!(!authorized("/api/status", Some("Bearer wrong")));
+        assert!(!authorized("/api/status", Some("Bearer secret2")));
+        assert!(!authorized("/api/status", Some("Basic secret")));
+        assert!(authorized("/api/status", Some("Bearer secret")));
+        assert!(authorized("/api/status", Some("bearer  secret ")));
+        // Query token only for the SSE route (EventSource can't send headers)
+        assert!(authorized("/api/events?token=secret", None));
+        assert!(!authorized("/api/events?token=wrong", None));
+        assert!(!authorized("/api/status?token=secret", None));
+    }
+
+    #[test]
+    fn missing_token_is_401_except_health() {
+        let state = Arc::new(with_token("secret"));
+        let (tx, _rx) = mpsc::channel();
+        let status =
+            |request: Request| ApiServer::handle_request(&request, &state, &tx).status_code;
+        assert_eq!(status(get("/api/status", None)), 401);
+        assert_eq!(status(get("/api/status", Some("Bearer wrong"))), 401);
+        assert_eq!(status(get("/api/status", Some("Bearer secret"))), 200);
+        assert_eq!(status(get("/api/health", None)), 200);
+    }
+
+    #[test]
+    fn cache_map_encodes_statuses() {
+        use FrameStatus::{Error, Loaded, Placeholder};
+        let comp = Uuid::new_v4();
+        let statuses = [Loaded, Loaded, Placeholder, Error, Loaded];
+
+        let map = CacheMapSnapshot::new(comp, 1001, &statuses, false);
+        assert_eq!((map.start, map.end, map.loaded), (1001, 1005, 3));
+        // [5, 5, 0, 6, 5]
+        assert_eq!(map.statuses.as_deref(), Some("BQUABgU="));
+        assert!(map.runs.is_none());
+
+        let map = CacheMapSnapshot::new(comp, 1001, &statuses, true);
+        assert_eq!(map.runs, Some(vec![(5, 2), (0, 1), (6, 1), (5, 1)]));
+        assert!(map.statuses.is_none());
+
+        // Empty comp: end lands one before start
+        let map = CacheMapSnapshot::new(comp, 10, &[], true);
+        assert_eq!((map.start, map.end, map.loaded), (10, 9, 0));
+        assert_eq!(map.runs, Some(Vec::new()));
+    }
+}
@@ -43,9 +43,13 @@
 //! |--------|-------------------------|----------------------------|
 //! | GET    | `/api/status`           | Full status (player/comp/cache) |
 //! | GET    | `/api/player`           | Player state only          |
-//! | GET    | `/api/comp`             | Active comp info           |
-//! | GET    | `/api/cache`            | Cache memory stats         |
+//! | GET    | `/api/comp`             | Active comp info (incl. fps/resolution `warnings`) |
+//! | GET    | `/api/cache`            | Cache memory stats + preload cap |
+//! | POST   | `/api/cache`            | Set preload cap (JSON `{max_preload_radius}`, `null` = off) |
+//! | GET    | `/api/comp/markers`     | Active comp timeline markers |
+//! | GET    | `/api/comp/{uuid}/cache` | Per-frame cache status bytes (`?encoding=rle` for runs) |
 //! | GET    | `/api/health`           | Health check               |
+//! | GET    | `/api/info`             | EXR backend, video encoders, sequence formats, FFmpeg version |
 //! | POST   | `/api/player/play`      | Start playback             |
 //! | POST   | `/api/player/pause`     | Pause playback             |
 //! | POST   | `/api/player/stop`      | Stop (pause + seek to 0)   |
@@ -53,8 +57,16 @@
 //! | POST   | `/api/player/fps/{n}`   | Set playback FPS           |
 //! | POST   | `/api/player/toggle-loop` | Toggle loop mode         |
 //! | POST   | `/api/project/load`     | Load sequence (JSON body)  |
+//! | POST   | `/api/comp/markers`     | Add/update marker (JSON `{frame, label, color?}`) |
 //! | POST   | `/api/event`            | Emit custom event          |
 
 mod api;
+mod script;
 
-pub use api::{ApiCommand, ApiServer, CacheSnapshot, CompSnapshot, PlayerSnapshot, SharedApiState};
+pub use script::{load_script, parse_script};
+
+pub use api::{
+    ApiCommand, ApiServer, CacheMapSnapshot, CacheSnapshot, CompSnapshot, EncodeJob,
+    EncodeJobError, EncodeJobSnapshot, FRAME_CAPTURE_TIMEOUT, MarkerSnapshot, PlayerSnapshot,
+    SharedApiState, StreamSnapshot,
+};
@@ -0,0 +1,211 @@
+//! Startup command files (`--script path`).
+//!
+//! # Purpose
+//!
+//! Lightweight automation without the REST server: a text file of commands
+//! parsed into [`ApiCommand`]s and run through the same dispatch as REST
+//! requests (`PlayaApp::handle_api_commands`) on the first frame.
+//!
+//! # Grammar
+//!
+//! One command per line; blank lines and `#` comments are ignored. Words are
+//! split on whitespace, so paths and labels run to the end of the line.
+//!
+//! | Command | Effect |
+//! |---------|--------|
+//! | `load PATH` | Load a sequence / video / image |
+//! | `play` / `pause` / `stop` | Transport |
+//! | `seek N` | Move the playhead to frame N |
+//! | `next` / `prev` | Step one frame |
+//! | `set-fps N` | Playback fps of the active comp |
+//! | `loop` | Toggle loop |
+//! | `marker N [LABEL]` | Add a marker at frame N |
+//! | `add-effect NAME [LAYER]` | Add an effect to layer LAYER (0 = top, default) |
+//! | `encode PATH [CODEC]` | Encode the active comp (h264, h265, av1, prores, vp9, dnxhr) |
+//! | `event NAME` | Emit a named event (same names as `POST /api/event`) |
+//! | `exit` | Quit (does not wait for a running encode) |
+//!
+//! A bad line is logged with its line number and skipped; the rest of the
+//! file still runs.
+
+use crossbeam_channel as crossbeam;
+use std::path::{Path, PathBuf};
+
+use super::ApiCommand;
+use playa_engine::entities::effects::EffectType;
+use playa_ui::dialogs::encode::{EncoderSettings, VideoCodec};
+
+/// Parse a command file; one entry per non-empty, non-comment line as
+/// `(line_number, command or error)`.
+pub fn parse_script(text: &str) -> Vec<(usize, Result<ApiCommand, String>)> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            Some((idx + 1, parse_line(line)))
+        })
+        .collect()
+}
+
+/// Read and parse `path`, logging every invalid line.
+pub fn load_script(path: &Path) -> Result<Vec<ApiCommand>, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read script {}: {}", path.display(), e))?;
+    let mut commands = Vec::new();
+    for (line, parsed) in parse_script(&text) {
+        match parsed {
+            Ok(cmd) => commands.push(cmd),
+            Err(e) => log::warn!("{}:{}: {}", path.display(), line, e),
+        }
+    }
+    Ok(commands)
+}
+
+fn parse_line(line: &str) -> Result<ApiCommand, String> {
+    let (verb, rest) = line
+        .split_once(char::is_whitespace)
+        .map(|(verb, rest)| (verb, rest.trim()))
+        .unwrap_or((line, ""));
+    let no_args = |cmd: ApiCommand| {
+        if rest.is_empty() {
+            Ok(cmd)
+        } else {
+            Err(format!("'{}' takes no arguments", verb))
+        }
+    };
+    match verb.to_ascii_lowercase().as_str() {
+        "play" => no_args(ApiCommand::Play),
+        "pause" => no_args(ApiCommand::Pause),
+        "stop" => no_args(ApiCommand::Stop),
+        "next" => no_args(ApiCommand::NextFrame),
+        "prev" => no_args(ApiCommand::PrevFrame),
+        "loop" => no_args(ApiCommand::ToggleLoop),
+        "exit" => no_args(ApiCommand::Exit),
+        "seek" => parse_arg::<i32>(rest, "frame").map(ApiCommand::SetFrame),
+        "set-fps" => match parse_arg::<f32>(rest, "fps")? {
+            fps if fps.is_finite() && fps > 0.0 => Ok(ApiCommand::SetFps(fps)),
+            fps => Err(format!("fps must be positive, got {}", fps)),
+        },
+        "load" => required(rest, "path").map(|path| ApiCommand::LoadSequence(path.to_string())),
+        "marker" => {
+            let (frame, label) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            Ok(ApiCommand::SetMarker {
+                frame: parse_arg(frame, "frame")?,
+                label: label.trim().to_string(),
+                color: None,
+            })
+        }
+        "add-effect" => {
+            let (name, layer) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let effect = parse_effect(required(name, "effect name")?)?;
+            let layer = match layer.trim() {
+                "" => 0,
+                index => parse_arg(index, "layer index")?,
+            };
+            Ok(ApiCommand::AddEffect { effect, layer })
+        }
+        "encode" => {
+            let (path, codec) = rest.rsplit_once(char::is_whitespace).unwrap_or((rest, ""));
+            // A trailing word that is not a codec belongs to the path
+            let (path, codec) = match VideoCodec::parse(codec.trim()) {
+                Some(codec) => (path.trim(), codec),
+                None => (rest, VideoCodec::H264),
+            };
+            let settings = EncoderSettings {
+                output_path: PathBuf::from(required(path, "output path")?),
+                container: codec.preferred_container(),
+                codec,
+                ..EncoderSettings::default()
+            };
+            // Nobody waits on the reply; failures are logged by the handler
+            let (response, _) = crossbeam::bounded(1);
+            Ok(ApiCommand::Encode {
+                comp: None,
+                fps: None,
+                settings: Box::new(settings),
+                response,
+            })
+        }
+        "event" => required(rest, "event name").map(|name| ApiCommand::EmitEvent {
+            event_type: name.to_string(),
+            payload: String::new(),
+        }),
+        _ => Err(format!("unknown command '{}'", verb)),
+    }
+}
+
+fn required<'a>(arg: &'a str, what: &str) -> Result<&'a str, String> {
+    if arg.is_empty() {
+        Err(format!("missing {}", what))
+    } else {
+        Ok(arg)
+    }
+}
+
+fn parse_arg<T: std::str::FromStr>(arg: &str, what: &str) -> Result<T, String> {
+    required(arg, what)?
+        .parse()
+        .map_err(|_| format!("invalid {} '{}'", what, arg))
+}
+
+/// Effect by display name, case and punctuation insensitive
+/// (`gaussian-blur`, `GaussianBlur`, `"Brightness/Contrast"`).
+fn parse_effect(name: &str) -> Result<EffectType, String> {
+    let key = |s: &str| {
+        s.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .collect::<String>()
+            .to_ascii_lowercase()
+    };
+    let wanted = key(name);
+    EffectType::all()
+        .iter()
+        .find(|t| key(t.display_name()) == wanted)
+        .cloned()
+        .ok_or_else(|| format!("unknown effect '{}'", name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_commands_and_reports_bad_lines() {
+        let script =
+            "# review\n\nload /shots/a b.0001.exr\nseek 1001\nset-fps 0\nfoo\nmarker 12 fix this\n";
+        let parsed = parse_script(script);
+        let lines: Vec<usize> = parsed.iter().map(|(line, _)| *line).collect();
+        assert_eq!(lines, vec![3, 4, 5, 6, 7]);
+        assert!(
+            matches!(&parsed[0].1, Ok(ApiCommand::LoadSequence(p)) if p == "/shots/a b.0001.exr")
+        );
+        assert!(matches!(parsed[1].1, Ok(ApiCommand::SetFrame(1001))));
+        assert!(parsed[2].1.is_err());
+        assert!(parsed[3].1.is_err());
+        assert!(
+            matches!(&parsed[4].1, Ok(ApiCommand::SetMarker { frame: 12, label, .. }) if label == "fix this")
+        );
+    }
+
+    #[test]
+    fn parses_effect_and_encode_arguments() {
+        assert!(matches!(
+            parse_line("add-effect Brightness/Contrast 2"),
+            Ok(ApiCommand::AddEffect {
+                effect: EffectType::BrightnessContrast,
+                layer: 2
+            })
+        ));
+        assert!(parse_line("add-effect sharpen").is_err());
+        match parse_line("encode /tmp/review cut.mov prores") {
+            Ok(ApiCommand::Encode { settings, .. }) => {
+                assert_eq!(settings.output_path, PathBuf::from("/tmp/review cut.mov"));
+                assert_eq!(settings.codec, VideoCodec::ProRes);
+            }
+            other => panic!("unexpected {:?}", other.map(|_| ())),
+        }
+    }
+}
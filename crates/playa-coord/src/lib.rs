@@ -55,7 +55,7 @@
 //! - Drawing an overlay on top of egui screen? Convert frame → viewport
 //!   → screen via the chain.
 
-use glam::{Affine2, Mat4, Vec2, Vec4};
+use glam::{Affine2, Mat2, Mat4, Vec2, Vec4};
 
 /// Image space → frame space.
 ///
@@ -305,6 +305,78 @@ pub fn screen_ndc_from_frame_ndc(
     )
 }
 
+// ---------------------------------------------------------------------------
+// Display orientation (viewport rotate / flip / flop + pixel aspect)
+// ---------------------------------------------------------------------------
+
+/// Frame-space display transform for the viewport: stretch X by
+/// `pixel_aspect`, mirror (`flip_h` mirrors X, `flip_v` mirrors Y), then
+/// rotate `quarter_turns` × 90° clockwise.
+///
+/// Display-only: sits between frame space and viewport space, so the full
+/// chain is `image → frame → display → viewport → screen`. Quarter turns are
+/// built from exact axis swaps (no `sin`/`cos`), so corners stay on integer
+/// pixels and the inverse is exact.
+#[inline]
+pub fn display_affine(quarter_turns: u8, flip_h: bool, flip_v: bool, pixel_aspect: f32) -> Affine2 {
+    // CW in Y-up: (x, y) → (y, -x)
+    let rot = match quarter_turns % 4 {
+        0 => Mat2::IDENTITY,
+        1 => Mat2::from_cols(Vec2::new(0.0, -1.0), Vec2::new(1.0, 0.0)),
+        2 => Mat2::from_cols(Vec2::new(-1.0, 0.0), Vec2::new(0.0, -1.0)),
+        _ => Mat2::from_cols(Vec2::new(0.0, 1.0), Vec2::new(-1.0, 0.0)),
+    };
+    let mirror = Vec2::new(
+        if flip_h { -1.0 } else { 1.0 },
+        if flip_v { -1.0 } else { 1.0 },
+    );
+    Affine2::from_mat2(rot * Mat2::from_diagonal(mirror * Vec2::new(pixel_aspect, 1.0)))
+}
+
+/// Lift a 2D affine into a 4×4 matrix (Z passes through untouched).
+#[inline]
+pub fn affine2_to_mat4(a: Affine2) -> Mat4 {
+    Mat4::from_cols(
+        Vec4::new(a.matrix2.x_axis.x, a.matrix2.x_axis.y, 0.0, 0.0),
+        Vec4::new(a.matrix2.y_axis.x, a.matrix2.y_axis.y, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 1.0, 0.0),
+        Vec4::new(a.translation.x, a.translation.y, 0.0, 1.0),
+    )
+}
+
+/// [`screen_ndc_from_frame_ndc`] with a display transform (see
+/// [`display_affine`]) applied in frame space before zoom + pan.
+///
+/// ```text
+/// screen_NDC = (display(cam_NDC * comp/2) * zoom + pan) / (viewport / 2)
+/// ```
+///
+/// With an identity `display` this equals [`screen_ndc_from_frame_ndc`].
+#[inline]
+pub fn screen_ndc_from_frame_ndc_display(
+    zoom: f32,
+    pan: Vec2,
+    display: Affine2,
+    comp_size: (usize, usize),
+    viewport_size: Vec2,
+) -> Mat4 {
+    let half_comp = Vec2::new(comp_size.0 as f32 * 0.5, comp_size.1 as f32 * 0.5);
+    let to_screen_ndc = Mat4::from_cols(
+        Vec4::new(zoom * 2.0 / viewport_size.x, 0.0, 0.0, 0.0),
+        Vec4::new(0.0, zoom * 2.0 / viewport_size.y, 0.0, 0.0),
+        Vec4::new(0.0, 0.0, 1.0, 0.0),
+        Vec4::new(
+            pan.x * 2.0 / viewport_size.x,
+            pan.y * 2.0 / viewport_size.y,
+            0.0,
+            1.0,
+        ),
+    );
+    to_screen_ndc
+        * affine2_to_mat4(display)
+        * Mat4::from_scale(glam::Vec3::new(half_comp.x, half_comp.y, 1.0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -696,4 +768,73 @@ mod tests {
             assert!(approx_eq(q, p), "expected identity, p={:?} -> q={:?}", p, q);
         }
     }
+
+    #[test]
+    fn display_affine_quarter_turns() {
+        // 90° CW: frame +X (right) lands on -Y (down), +Y (up) lands on +X.
+        let a = display_affine(1, false, false, 1.0);
+        assert!(approx_eq(a.transform_point2(Vec2::X), Vec2::NEG_Y));
+        assert!(approx_eq(a.transform_point2(Vec2::Y), Vec2::X));
+        // Four turns are identity; two turns are a point reflection.
+        let p = Vec2::new(12.0, -7.0);
+        let full = display_affine(4, false, false, 1.0);
+        assert!(approx_eq(full.transform_point2(p), p));
+        let half = display_affine(2, false, false, 1.0);
+        assert!(approx_eq(half.transform_point2(p), -p));
+    }
+
+    #[test]
+    fn display_affine_mirror_then_rotate() {
+        let p = Vec2::new(3.0, 5.0);
+        assert!(approx_eq(
+            display_affine(0, true, false, 1.0).transform_point2(p),
+            Vec2::new(-3.0, 5.0)
+        ));
+        assert!(approx_eq(
+            display_affine(0, false, true, 1.0).transform_point2(p),
+            Vec2::new(3.0, -5.0)
+        ));
+        // Pixel aspect stretches source X before rotation: lands on -Y.
+        assert!(approx_eq(
+            display_affine(1, false, false, 2.0).transform_point2(Vec2::X),
+            Vec2::new(0.0, -2.0)
+        ));
+        // Round-trip through the inverse is exact.
+        let a = display_affine(3, true, true, 1.5);
+        let back = a.inverse().transform_point2(a.transform_point2(p));
+        assert!(approx_eq(back, p));
+    }
+
+    #[test]
+    fn screen_ndc_display_identity_matches_plain() {
+        let comp = (1280, 720);
+        let vp = Vec2::new(1600.0, 900.0);
+        let pan = Vec2::new(40.0, -12.0);
+        let a = screen_ndc_from_frame_ndc(1.7, pan, comp, vp);
+        let b = screen_ndc_from_frame_ndc_display(1.7, pan, Affine2::IDENTITY, comp, vp);
+        assert!(a.abs_diff_eq(b, 1e-5), "a={a:?} b={b:?}");
+    }
+
+    /// Display matrix must agree with the point chain
+    /// `frame → display → viewport` lifted into NDC.
+    #[test]
+    fn screen_ndc_display_matches_point_chain() {
+        let comp = (1280, 720);
+        let vp = Vec2::new(1600.0, 900.0);
+        let (zoom, pan) = (0.8, Vec2::new(25.0, 10.0));
+        let display = display_affine(1, true, false, 2.0);
+        let m = screen_ndc_from_frame_ndc_display(zoom, pan, display, comp, vp);
+        for ndc in [Vec2::ZERO, Vec2::new(1.0, 1.0), Vec2::new(-0.5, 0.25)] {
+            let frame = ndc_to_frame(ndc, comp);
+            let viewport = frame_to_viewport(display.transform_point2(frame), zoom, pan);
+            let expected = viewport * 2.0 / vp;
+            let v = m * Vec4::new(ndc.x, ndc.y, 0.0, 1.0);
+            assert!(
+                approx_eq(Vec2::new(v.x, v.y), expected),
+                "ndc={ndc:?} got=({}, {}) expected={expected:?}",
+                v.x,
+                v.y
+            );
+        }
+    }
 }
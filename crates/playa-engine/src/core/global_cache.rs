@@ -18,13 +18,15 @@ use std::sync::{Arc, Mutex, RwLock};
 use uuid::Uuid;
 
 use crate::core::cache_man::CacheManager;
-use crate::entities::{CacheStatsSnapshot, CacheStrategy, Frame, FrameCache};
+use crate::entities::{CacheStatsSnapshot, CacheStrategy, Frame, FrameCache, ProxyLevel};
 
 /// Cache statistics for monitoring performance
 #[derive(Debug, Default)]
 pub struct CacheStats {
     hits: AtomicU64,
     misses: AtomicU64,
+    /// Frames dropped by LRU to stay within the memory/capacity budget
+    evictions: AtomicU64,
 }
 
 impl CacheStats {
@@ -32,6 +34,7 @@ impl CacheStats {
         Self {
             hits: AtomicU64::new(0),
             misses: AtomicU64::new(0),
+            evictions: AtomicU64::new(0),
         }
     }
 
@@ -51,6 +54,14 @@ impl CacheStats {
         self.misses.load(Ordering::Relaxed)
     }
 
+    pub fn record_eviction(&self) {
+        self.evictions.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn evictions(&self) -> u64 {
+        self.evictions.load(Ordering::Relaxed)
+    }
+
     pub fn total(&self) -> u64 {
         self.hits() + self.misses()
     }
@@ -67,9 +78,21 @@ impl CacheStats {
     pub fn reset(&self) {
         self.hits.store(0, Ordering::Relaxed);
         self.misses.store(0, Ordering::Relaxed);
+        self.evictions.store(0, Ordering::Relaxed);
     }
 }
 
+/// Cached frames of one comp bucketed by status (cache stats panel)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StatusCounts {
+    pub loaded: usize,
+    /// Loading / Composing / Header placeholders still being filled
+    pub loading: usize,
+    pub error: usize,
+    /// Stale (Expired) or other placeholder entries
+    pub other: usize,
+}
+
 /// Entry in LRU eviction queue
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 struct CacheKey {
@@ -241,7 +264,7 @@ impl GlobalFrameCache {
 
         // Apply strategy: LastOnly clears previous frames for this comp (except current)
         if *self.strategy.lock().unwrap_or_else(|e| e.into_inner()) == CacheStrategy::LastOnly {
-            self.clear_comp(comp_uuid, false, Some(frame_idx)); // Keep current frame
+            self.clear_comp_key(comp_uuid, false, Some(frame_idx)); // Keep current frame
         }
 
         // Insert frame
@@ -326,6 +349,7 @@ impl GlobalFrameCache {
             if let Some(evicted) = frames.remove(&key.frame_idx) {
                 let evicted_size = evicted.mem();
                 self.cache_manager.free_memory(evicted_size);
+                self.stats.record_eviction();
 
                 // Remove empty inner HashMap
                 if frames.is_empty() {
@@ -350,6 +374,12 @@ impl GlobalFrameCache {
     /// Use this for light attribute changes (opacity, blend_mode) that only
     /// require recomposing the current frame, not the entire comp.
     pub fn clear_frame(&self, comp_uuid: Uuid, frame_idx: i32) {
+        for level in ProxyLevel::ALL {
+            self.clear_frame_key(level.cache_uuid(comp_uuid), frame_idx);
+        }
+    }
+
+    fn clear_frame_key(&self, comp_uuid: Uuid, frame_idx: i32) {
         let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
         let mut lru = self.lru_order.lock().unwrap_or_else(|e| e.into_inner());
 
@@ -407,7 +437,15 @@ impl GlobalFrameCache {
     /// - `comp_uuid`: Composition to clear
     /// - `dehydrate`: If true, mark frames Expired (keep pixels). If false, remove completely.
     /// - `except`: If Some(idx), keep that frame (used by LastOnly strategy to preserve current frame)
+    ///
+    /// Proxy variants of the comp (see [`ProxyLevel::cache_uuid`]) are cleared too.
     pub fn clear_comp(&self, comp_uuid: Uuid, dehydrate: bool, except: Option<i32>) {
+        for level in ProxyLevel::ALL {
+            self.clear_comp_key(level.cache_uuid(comp_uuid), dehydrate, except);
+        }
+    }
+
+    fn clear_comp_key(&self, comp_uuid: Uuid, dehydrate: bool, except: Option<i32>) {
         let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
 
         if dehydrate {
@@ -486,6 +524,12 @@ impl GlobalFrameCache {
     /// More efficient than clear_comp when only part of timeline changed.
     /// Frames will be recreated as Header on next access.
     pub fn clear_range(&self, comp_uuid: Uuid, start: i32, end: i32) {
+        for level in ProxyLevel::ALL {
+            self.clear_range_key(level.cache_uuid(comp_uuid), start, end);
+        }
+    }
+
+    fn clear_range_key(&self, comp_uuid: Uuid, start: i32, end: i32) {
         let mut cache = self.cache.write().unwrap_or_else(|e| e.into_inner());
         let mut lru = self.lru_order.lock().unwrap_or_else(|e| e.into_inner());
 
@@ -616,11 +660,84 @@ impl GlobalFrameCache {
             .unwrap_or(0)
     }
 
+    /// Frame counts by status for one comp (full-res key; proxies excluded)
+    pub fn status_counts(&self, comp_uuid: Uuid) -> StatusCounts {
+        use crate::entities::FrameStatus;
+
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        let mut counts = StatusCounts::default();
+        for frame in cache.get(&comp_uuid).into_iter().flat_map(|f| f.values()) {
+            match frame.status() {
+                FrameStatus::Loaded => counts.loaded += 1,
+                FrameStatus::Loading | FrameStatus::Composing | FrameStatus::Header => {
+                    counts.loading += 1
+                }
+                FrameStatus::Error => counts.error += 1,
+                FrameStatus::Expired | FrameStatus::Placeholder => counts.other += 1,
+            }
+        }
+        counts
+    }
+
+    /// Frames of `start..=end` that are `Loaded` at any proxy level.
+    ///
+    /// Same rule as the timeline status strip; backs the status bar's
+    /// "cached %" readout for the work area.
+    pub fn loaded_in_range(&self, comp_uuid: Uuid, start: i32, end: i32) -> usize {
+        use crate::entities::FrameStatus;
+
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        let levels: Vec<&HashMap<i32, Frame>> = ProxyLevel::ALL
+            .into_iter()
+            .filter_map(|l| cache.get(&l.cache_uuid(comp_uuid)))
+            .collect();
+        (start..=end)
+            .filter(|idx| {
+                levels.iter().any(|frames| {
+                    frames
+                        .get(idx)
+                        .is_some_and(|f| f.status() == FrameStatus::Loaded)
+                })
+            })
+            .count()
+    }
+
+    /// Failed frames of one comp with their load error, sorted by frame
+    /// (all proxy levels, full-res first). Successful reloads replace the
+    /// failed entries, so this only lists frames that are still broken.
+    pub fn load_errors(&self, comp_uuid: Uuid) -> Vec<(i32, String)> {
+        use crate::entities::FrameStatus;
+        use std::collections::BTreeMap;
+
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        let mut errors: BTreeMap<i32, String> = BTreeMap::new();
+        for level in ProxyLevel::ALL {
+            let frames = cache.get(&level.cache_uuid(comp_uuid)).into_iter();
+            for (&idx, frame) in frames.flat_map(|f| f.iter()) {
+                if frame.status() == FrameStatus::Error {
+                    errors.entry(idx).or_insert_with(|| {
+                        frame
+                            .load_error()
+                            .unwrap_or_else(|| "unknown error".to_string())
+                    });
+                }
+            }
+        }
+        errors.into_iter().collect()
+    }
+
+    /// Load error of one cached frame, without touching LRU order or stats
+    pub fn load_error(&self, comp_uuid: Uuid, frame_idx: i32) -> Option<String> {
+        let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+        cache.get(&comp_uuid)?.get(&frame_idx)?.load_error()
+    }
+
     /// Get cache statistics snapshot (for trait impl)
     pub fn stats_snapshot(&self) -> CacheStatsSnapshot {
         CacheStatsSnapshot {
             hits: self.stats.hits(),
             misses: self.stats.misses(),
+            evictions: self.stats.evictions(),
             size: self.len(),
         }
     }
@@ -659,6 +776,7 @@ impl FrameCache for GlobalFrameCache {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::entities::FrameStatus;
 
     /// Create a test frame with Loaded status (required for cache insert)
     fn make_loaded_frame(width: usize, height: usize) -> Frame {
@@ -688,6 +806,43 @@ mod tests {
         assert_eq!(cache.comp_frame_count(comp_uuid), 1);
     }
 
+    #[test]
+    fn test_cache_proxy_levels_separate() {
+        let manager = Arc::new(CacheManager::new(0.75, 2.0));
+        let cache = GlobalFrameCache::new(100, manager, CacheStrategy::All);
+        let comp_uuid = Uuid::new_v4();
+        let half = ProxyLevel::Half.cache_uuid(comp_uuid);
+        assert_ne!(half, comp_uuid);
+
+        cache.insert(comp_uuid, 0, make_loaded_frame(64, 64));
+        cache.insert(half, 0, make_loaded_frame(32, 32));
+        assert_eq!(cache.get(comp_uuid, 0).unwrap().width(), 64);
+        assert_eq!(cache.get(half, 0).unwrap().width(), 32);
+
+        // Clearing the comp drops its proxy variants too
+        cache.clear_comp(comp_uuid, false, None);
+        assert!(!cache.contains(comp_uuid, 0));
+        assert!(!cache.contains(half, 0));
+    }
+
+    #[test]
+    fn test_loaded_in_range() {
+        let manager = Arc::new(CacheManager::new(0.75, 2.0));
+        let cache = GlobalFrameCache::new(100, manager, CacheStrategy::All);
+        let comp_uuid = Uuid::new_v4();
+
+        cache.insert(comp_uuid, 1, make_loaded_frame(8, 8));
+        cache.insert(comp_uuid, 2, make_loaded_frame(8, 8));
+        // Proxy-only frame still counts; out-of-range frame does not
+        let half = ProxyLevel::Half.cache_uuid(comp_uuid);
+        cache.insert(half, 3, make_loaded_frame(4, 4));
+        cache.insert(comp_uuid, 20, make_loaded_frame(8, 8));
+
+        assert_eq!(cache.loaded_in_range(comp_uuid, 0, 9), 3);
+        assert_eq!(cache.loaded_in_range(comp_uuid, 2, 2), 1);
+        assert_eq!(cache.loaded_in_range(Uuid::new_v4(), 0, 9), 0);
+    }
+
     #[test]
     fn test_cache_last_only_strategy() {
         let manager = Arc::new(CacheManager::new(0.75, 2.0));
@@ -761,6 +916,59 @@ mod tests {
         assert_eq!(stats.hit_rate(), 0.5);
     }
 
+    #[test]
+    fn test_eviction_counter_and_status_counts() {
+        let manager = Arc::new(CacheManager::new(0.75, 2.0));
+        let cache = GlobalFrameCache::new(100, manager, CacheStrategy::All);
+        let comp_uuid = Uuid::new_v4();
+
+        for i in 0..105 {
+            cache.insert(comp_uuid, i, make_loaded_frame(8, 8));
+        }
+        assert_eq!(cache.stats_snapshot().evictions, 5);
+        assert_eq!(cache.len(), 100);
+
+        let failed = Frame::from_u8_buffer_with_status(vec![0; 4], 1, 1, FrameStatus::Error);
+        cache.insert(comp_uuid, 200, failed);
+        let counts = cache.status_counts(comp_uuid);
+        assert_eq!((counts.loaded, counts.error), (99, 1));
+        assert_eq!(cache.status_counts(Uuid::new_v4()), StatusCounts::default());
+
+        cache.stats().reset();
+        assert_eq!(cache.stats().evictions(), 0);
+    }
+
+    #[test]
+    fn test_load_errors_listed_and_cleared() {
+        let manager = Arc::new(CacheManager::new(0.75, 2.0));
+        let cache = GlobalFrameCache::new(100, manager, CacheStrategy::All);
+        let comp_uuid = Uuid::new_v4();
+
+        let failed = Frame::from_u8_buffer_with_status(vec![0; 4], 1, 1, FrameStatus::Error);
+        failed.set_load_error(Some("/shots/a.0012.exr: truncated".to_string()));
+        cache.insert(comp_uuid, 12, failed);
+        cache.insert(
+            comp_uuid,
+            3,
+            Frame::from_u8_buffer_with_status(vec![0; 4], 1, 1, FrameStatus::Error),
+        );
+        cache.insert(comp_uuid, 5, make_loaded_frame(8, 8));
+
+        let errors = cache.load_errors(comp_uuid);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0], (3, "unknown error".to_string()));
+        assert!(errors[1].1.contains("a.0012.exr"));
+        assert!(cache.load_error(comp_uuid, 12).is_some());
+        assert!(cache.load_error(comp_uuid, 5).is_none());
+
+        // Successful reload replaces the failed entry
+        cache.insert(comp_uuid, 12, make_loaded_frame(8, 8));
+        assert_eq!(cache.load_errors(comp_uuid).len(), 1);
+
+        cache.clear_frame(comp_uuid, 3);
+        assert!(cache.load_errors(comp_uuid).is_empty());
+    }
+
     #[test]
     fn test_multiple_comps() {
         let manager = Arc::new(CacheManager::new(0.75, 2.0));
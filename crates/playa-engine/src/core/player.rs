@@ -29,17 +29,26 @@
 //! `update()` called at 60Hz, advances frame index based on FPS.
 //! Handles sequence boundaries (loop or stop at end).
 //!
+//! # Sequence Playback
+//!
+//! With `sequence_playback` on and loop off, forward playback doesn't end at
+//! the work-area end: the player emits [`PlayNextCompEvent`] and the app
+//! activates the next comp in project order ([`Player::next_comp`]) and keeps
+//! playing. [`PreloadNextCompEvent`] goes out [`SEQUENCE_PRELOAD_LEAD`] frames
+//! earlier so the next comp's head is cached by the time it's shown.
+//!
 //! # Selection Behavior
 //!
 //! `set_active_comp()` resets project selection to just the activated comp.
 //! This prevents multi-selection accumulation when adding/switching clips.
 
+use crate::core::player_events::{PlayNextCompEvent, PreloadNextCompEvent};
 use crate::entities::attr_schemas::PLAYER_SCHEMA;
 use crate::entities::frame::Frame;
 use crate::entities::{AttrValue, Attrs, Node, Project};
 use log::{info, trace};
 use serde::{Deserialize, Serialize};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use uuid::Uuid;
 
 /// FPS presets for jog/shuttle control
@@ -47,9 +56,20 @@ const FPS_PRESETS: &[f32] = &[
     1.0, 2.0, 4.0, 8.0, 12.0, 24.0, 30.0, 60.0, 120.0, 240.0, 480.0, 960.0,
 ];
 
-/// Frame step size for Shift+Arrow and Shift+PageUp/PageDown
+/// Default frame step size for Shift+Arrow and Shift+PageUp/PageDown
+/// (configurable in prefs)
 pub const FRAME_JUMP_STEP: i32 = 25;
 
+/// Frames before the work-area end at which sequence playback preloads the next comp
+pub const SEQUENCE_PRELOAD_LEAD: i32 = 12;
+
+/// Longest lag (seconds) fps-locked playback catches up on by dropping frames;
+/// past it (window drag, breakpoint, blocking load) the clock restarts instead
+const MAX_CATCH_UP_SECS: f32 = 1.0;
+
+/// Window over which [`Player::actual_fps`] is measured
+const FPS_METER_SECS: f32 = 0.5;
+
 /// Playback state manager (does NOT own Project)
 ///
 /// Player manages playback state only. Project is passed by reference
@@ -63,6 +83,9 @@ pub const FRAME_JUMP_STEP: i32 = 25;
 /// - `fps_play`: Float (temporary playback FPS)
 /// - `loop_enabled`: Bool
 /// - `play_direction`: Float (1.0 forward, -1.0 backward)
+/// - `sequence_playback`: Bool (roll into the next comp at the work-area end)
+/// - `held_frame`: Int (frame parked by the hold toggle, missing key = not held)
+/// - `lock_to_fps`: Bool (pace by the wall clock, dropping late frames)
 /// - `selected_seq_idx`: Option<usize> stored as Int (missing key = None)
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Player {
@@ -72,6 +95,14 @@ pub struct Player {
     /// Last frame timestamp (runtime-only, not serializable)
     #[serde(skip)]
     pub last_frame_time: Option<Instant>,
+
+    /// Displayed-frame counter: window start and frames shown since
+    #[serde(skip)]
+    fps_meter: Option<(Instant, u32)>,
+
+    /// Last measured displayed fps while playing
+    #[serde(skip)]
+    actual_fps: Option<f32>,
 }
 
 impl Player {
@@ -86,11 +117,14 @@ impl Player {
         attrs.set("fps_play", AttrValue::Float(24.0));
         attrs.set("loop_enabled", AttrValue::Bool(true));
         attrs.set("play_direction", AttrValue::Float(1.0));
+        attrs.set("sequence_playback", AttrValue::Bool(false));
         attrs.set_uuid_list("previous_comp_history", &[]);
 
         Self {
             attrs,
             last_frame_time: None,
+            fps_meter: None,
+            actual_fps: None,
         }
     }
 
@@ -178,6 +212,60 @@ impl Player {
         self.attrs.set("fps_play", AttrValue::Float(fps));
     }
 
+    /// Adopt the active comp's fps as base/play rate.
+    ///
+    /// Timing only: frames already cached stay valid, so the next tick
+    /// simply runs at the new rate. Returns true when the rate changed.
+    pub fn sync_fps(&mut self, project: &Project) -> bool {
+        let Some(fps) = self
+            .active_comp()
+            .and_then(|uuid| project.with_node(uuid, |node| node.fps()))
+            .filter(|fps| *fps > 0.0)
+        else {
+            return false;
+        };
+        if (fps - self.fps_base()).abs() < f32::EPSILON {
+            return false;
+        }
+        self.set_fps_base(fps);
+        self.set_fps_play(fps);
+        trace!("Player fps synced to active comp: {}", fps);
+        true
+    }
+
+    /// Frame parked by the hold toggle, if any.
+    ///
+    /// While held, playback ticks, steps, jumps and scrubs leave the playhead
+    /// alone; the hold survives play/pause until released.
+    pub fn held_frame(&self) -> Option<i32> {
+        self.attrs.get_i32("held_frame")
+    }
+
+    /// Hold the current frame, or release an existing hold.
+    ///
+    /// Releasing parks the playhead on the held frame so playback resumes
+    /// from there. Returns the new held frame (`None` after release).
+    pub fn toggle_hold(&mut self, project: &mut Project) -> Option<i32> {
+        match self.held_frame() {
+            Some(frame) => {
+                let _ = self.attrs.remove("held_frame");
+                if let Some(uuid) = self.active_comp() {
+                    project.modify_comp(uuid, |comp| comp.set_frame(frame));
+                }
+                self.last_frame_time = None;
+                trace!("Frame hold released at {}", frame);
+                None
+            }
+            None => {
+                self.active_comp()?;
+                let frame = self.current_frame(project);
+                self.attrs.set("held_frame", AttrValue::Int(frame));
+                trace!("Holding frame {}", frame);
+                Some(frame)
+            }
+        }
+    }
+
     /// Check if loop is enabled
     pub fn loop_enabled(&self) -> bool {
         self.attrs.get_bool_or("loop_enabled", true)
@@ -188,6 +276,45 @@ impl Player {
         self.attrs.set("loop_enabled", AttrValue::Bool(enabled));
     }
 
+    /// Check if sequence playback (auto-advance to the next comp) is enabled
+    pub fn sequence_playback(&self) -> bool {
+        self.attrs.get_bool_or("sequence_playback", false)
+    }
+
+    /// Set sequence playback
+    pub fn set_sequence_playback(&mut self, enabled: bool) {
+        self.attrs
+            .set("sequence_playback", AttrValue::Bool(enabled));
+    }
+
+    /// Check if playback is paced by the wall clock at the play fps
+    pub fn lock_to_fps(&self) -> bool {
+        self.attrs.get_bool_or("lock_to_fps", true)
+    }
+
+    /// Set fps lock: on = advance every frame that came due (late ones are
+    /// dropped) to hold the authored rate; off = at most one frame per update,
+    /// so every frame is shown but slow displays play slower
+    pub fn set_lock_to_fps(&mut self, enabled: bool) {
+        self.attrs.set("lock_to_fps", AttrValue::Bool(enabled));
+    }
+
+    /// Displayed frames per second while playing, measured over the last
+    /// half second (`None` when paused or not yet measured)
+    pub fn actual_fps(&self) -> Option<f32> {
+        self.actual_fps
+    }
+
+    /// Comp after `from` in project order (non-comp nodes skipped), for sequence playback
+    pub fn next_comp(&self, from: Uuid, project: &Project) -> Option<Uuid> {
+        project
+            .order()
+            .into_iter()
+            .skip_while(|uuid| *uuid != from)
+            .skip(1)
+            .find(|uuid| project.contains_comp(*uuid))
+    }
+
     /// Get play direction (1.0 forward, -1.0 backward)
     pub fn play_direction(&self) -> f32 {
         self.attrs.get_float_or("play_direction", 1.0)
@@ -312,8 +439,11 @@ impl Player {
             }
         }
 
-        // Stop playback during transition
+        // Stop playback during transition; a held frame belongs to the old comp
         self.set_is_playing(false);
+        if current != Some(uuid) {
+            let _ = self.attrs.remove("held_frame");
+        }
 
         // Switch to new comp
         self.set_active_comp_uuid(Some(uuid));
@@ -326,6 +456,10 @@ impl Player {
             comp.set_frame(frame);
             log::info!("Activated comp {} at frame {}", uuid, frame);
         });
+        // Advisory fps / resolution check (shown in the timeline outline)
+        project.validate_comp(uuid);
+        // Play at the comp's own rate
+        self.sync_fps(project);
 
         // Reset selection to just the active comp
         project.set_selection(vec![uuid]);
@@ -337,8 +471,20 @@ impl Player {
     /// Frame steps use `Project::modify_comp` → `CurrentFrameChangedEvent`. The app also emits
     /// [`SetFrameEvent`](crate::core::player_events::SetFrameEvent) each tick so `handle_app_event`
     /// applies the same scrub rules (e.g. epoch bump on large jumps when playback seeks).
+    ///
+    /// With [`lock_to_fps`](Self::lock_to_fps) the playhead follows the wall
+    /// clock: frames that came due between updates are skipped (the playhead
+    /// jumps straight to the last one) and the sub-frame remainder carries
+    /// over, so a 24 fps comp plays at 24 fps on any refresh rate.
     pub fn update(&mut self, project: &mut Project) -> Option<i32> {
+        self.update_at(project, Instant::now())
+    }
+
+    /// [`Self::update`] with the clock read by the caller
+    fn update_at(&mut self, project: &mut Project, now: Instant) -> Option<i32> {
         if !self.is_playing() || self.total_frames(project) == 0 {
+            self.fps_meter = None;
+            self.actual_fps = None;
             return None;
         }
 
@@ -350,25 +496,56 @@ impl Player {
             self.set_fps_play(fps_base);
         }
 
-        let now = Instant::now();
-
-        if let Some(last_time) = self.last_frame_time {
-            let elapsed = now.duration_since(last_time).as_secs_f32();
-            let frame_duration = 1.0 / self.fps_play();
+        let Some(last_time) = self.last_frame_time else {
+            self.last_frame_time = Some(now);
+            return None;
+        };
+        let elapsed = now.duration_since(last_time).as_secs_f32();
+        let frame_duration = 1.0 / self.fps_play();
+        if elapsed < frame_duration {
+            return None;
+        }
 
-            if elapsed >= frame_duration {
-                let new_frame = self.advance_frame(project);
-                self.last_frame_time = Some(now);
-                return new_frame;
-            }
+        let steps = if self.lock_to_fps() && elapsed <= MAX_CATCH_UP_SECS {
+            // Keep the cadence: the remainder counts toward the next frame
+            let steps = (elapsed / frame_duration) as u32;
+            self.last_frame_time =
+                Some(last_time + Duration::from_secs_f32(steps as f32 * frame_duration));
+            steps
         } else {
             self.last_frame_time = Some(now);
+            1
+        };
+        if steps > 1 {
+            trace!("Playback late: dropping {} frames", steps - 1);
+        }
+
+        let new_frame = self.advance_frames(project, steps);
+        if new_frame.is_some() {
+            self.count_shown_frame(now);
+        }
+        new_frame
+    }
+
+    /// Count one displayed frame toward [`actual_fps`](Self::actual_fps)
+    fn count_shown_frame(&mut self, now: Instant) {
+        let (start, shown) = self.fps_meter.get_or_insert((now, 0));
+        *shown += 1;
+        let secs = now.duration_since(*start).as_secs_f32();
+        if secs >= FPS_METER_SECS {
+            // Frames after the window's first one, over the time they took
+            self.actual_fps = Some((*shown - 1) as f32 / secs);
+            self.fps_meter = Some((now, 1));
         }
-        None
     }
 
-    /// Advance to next frame. Returns new frame if changed.
-    fn advance_frame(&mut self, project: &mut Project) -> Option<i32> {
+    /// Advance `steps` frames in the play direction in one playhead change,
+    /// wrapping (loop) or stopping at the end of the play range. Returns the
+    /// new frame if changed.
+    fn advance_frames(&mut self, project: &mut Project, steps: u32) -> Option<i32> {
+        if self.held_frame().is_some() {
+            return None;
+        }
         let total_frames = self.total_frames(project);
         if total_frames == 0 {
             return None;
@@ -382,9 +559,14 @@ impl Player {
         // Copy values before closure
         let play_direction = self.play_direction();
         let loop_enabled = self.loop_enabled();
+        let sequence = self.sequence_playback() && !loop_enabled;
+        // Preload point: LEAD frames before the end (reachable even in short work areas)
+        let preload_at = play_end - SEQUENCE_PRELOAD_LEAD.min(play_end - play_start - 1);
 
         // Track new frame and stop flag
         let mut should_stop = false;
+        let mut reached_end = false;
+        let mut crossed_preload = false;
         let mut new_frame: Option<i32> = None;
 
         if let Some(uuid) = self.active_comp() {
@@ -399,39 +581,33 @@ impl Player {
                     comp.set_frame(current);
                 }
 
-                if play_direction > 0.0 {
-                    // Forward
-                    let next = current + 1;
-                    if next > play_end {
-                        if loop_enabled {
-                            trace!("Frame loop: {} -> {}", current, play_start);
-                            comp.set_frame(play_start);
-                            new_frame = Some(play_start);
-                        } else {
-                            trace!("Reached play range end, stopping");
-                            comp.set_frame(play_end);
-                            new_frame = Some(play_end);
-                            should_stop = true;
-                        }
-                    } else {
-                        comp.set_frame(next);
-                        new_frame = Some(next);
-                    }
+                let target = if play_direction > 0.0 {
+                    current + steps as i32
                 } else {
-                    // Backward
-                    if current <= play_start {
-                        if loop_enabled {
-                            trace!("Frame loop: {} -> {}", current, play_end);
-                            comp.set_frame(play_end);
-                            new_frame = Some(play_end);
-                        } else {
-                            trace!("Reached play range start, stopping");
-                            should_stop = true;
-                        }
-                    } else {
-                        comp.set_frame(current - 1);
-                        new_frame = Some(current - 1);
-                    }
+                    current - steps as i32
+                };
+                let next = if (play_start..=play_end).contains(&target) {
+                    target
+                } else if loop_enabled {
+                    let len = play_end - play_start + 1;
+                    let wrapped = play_start + (target - play_start).rem_euclid(len);
+                    trace!("Frame loop: {} -> {}", current, wrapped);
+                    wrapped
+                } else if play_direction > 0.0 {
+                    trace!("Reached play range end, stopping");
+                    should_stop = true;
+                    reached_end = true;
+                    play_end
+                } else {
+                    trace!("Reached play range start, stopping");
+                    should_stop = true;
+                    play_start
+                };
+                crossed_preload = current < preload_at && next >= preload_at;
+                // Stopping backward on the start frame already shown: no change
+                if next != current || !should_stop || reached_end {
+                    comp.set_frame(next);
+                    new_frame = Some(next);
                 }
             });
         }
@@ -439,6 +615,18 @@ impl Player {
         if should_stop {
             self.set_is_playing(false);
         }
+        // Sequence playback: hand the transition to the app (it picks the next comp)
+        if sequence
+            && play_direction > 0.0
+            && let Some(uuid) = self.active_comp()
+        {
+            if crossed_preload {
+                project.emit_event(PreloadNextCompEvent(uuid));
+            }
+            if reached_end {
+                project.emit_event(PlayNextCompEvent(uuid));
+            }
+        }
         new_frame
     }
 
@@ -455,6 +643,9 @@ impl Player {
 
     /// Rewind to start
     pub fn to_start(&mut self, project: &mut Project) {
+        if self.held_frame().is_some() {
+            return;
+        }
         let (start, _) = self.play_range(project);
         trace!("Rewinding to frame {}", start);
         if let Some(uuid) = self.active_comp() {
@@ -467,6 +658,9 @@ impl Player {
 
     /// Skip to end
     pub fn to_end(&mut self, project: &mut Project) {
+        if self.held_frame().is_some() {
+            return;
+        }
         let (_, end) = self.play_range(project);
         trace!("Skipping to end: frame {}", end);
         if let Some(uuid) = self.active_comp() {
@@ -485,6 +679,9 @@ impl Player {
     /// so scrubbing/timeline can move outside work area while playback still
     /// respects play_range.
     pub fn set_frame(&mut self, frame: i32, project: &mut Project) {
+        if self.held_frame().is_some() {
+            return;
+        }
         if let Some(uuid) = self.active_comp() {
             project.modify_comp(uuid, |comp| {
                 let comp_start = comp._in();
@@ -544,11 +741,67 @@ impl Player {
             target
         };
 
-        if let Some(uuid) = self.active_comp() {
-            project.modify_comp(uuid, |comp| {
-                comp.set_frame(final_frame);
-            });
+        self.seek(current, final_frame, project);
+    }
+
+    /// Step to the nearest of `targets` (edges, markers, keyframes) after the
+    /// playhead, or before it when `forward` is false. Targets outside the work
+    /// area are ignored. Past the last target the playhead wraps to the first
+    /// one when loop is on, and stops at the work-area boundary otherwise.
+    pub fn step_to(&mut self, targets: &[i32], forward: bool, project: &mut Project) {
+        let current = self.current_frame(project);
+        let (play_start, play_end) = self.play_range(project);
+        let mut targets: Vec<i32> = targets
+            .iter()
+            .copied()
+            .filter(|f| (play_start..=play_end).contains(f))
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        let (Some(&first), Some(&last)) = (targets.first(), targets.last()) else {
+            return;
+        };
+
+        let next = if forward {
+            targets.iter().copied().find(|&f| f > current)
+        } else {
+            targets.iter().copied().rfind(|&f| f < current)
+        };
+        let target = match next {
+            Some(frame) => frame,
+            None if self.loop_enabled() => {
+                if forward {
+                    first
+                } else {
+                    last
+                }
+            }
+            None if forward => play_end,
+            None => play_start,
+        };
+        self.seek(current, target, project);
+    }
+
+    /// Move the active comp's playhead for a step/jump. Jumps of more than one
+    /// frame bump the cache epoch (like a scrub) so stale preload work is
+    /// dropped; `modify_comp` then emits `CurrentFrameChangedEvent`, which
+    /// recenters preload on the new frame.
+    fn seek(&mut self, from: i32, to: i32, project: &mut Project) {
+        let Some(uuid) = self.active_comp() else {
+            return;
+        };
+        if self.held_frame().is_some() {
+            return;
+        }
+        if (to - from).abs() > 1
+            && let Some(manager) = project.cache_manager()
+        {
+            manager.increment_epoch();
         }
+        project.modify_comp(uuid, |comp| {
+            comp.set_frame(to);
+        });
+        self.last_frame_time = None;
     }
 
     /// Internal helper to start jogging in the specified direction
@@ -629,3 +882,191 @@ impl Default for Player {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::cache_man::CacheManager;
+    use crate::core::event_bus::{EventBus, downcast_event};
+    use crate::entities::{CompNode, NodeKind};
+    use std::sync::Arc;
+
+    fn add_comp(project: &mut Project, name: &str, end: i32) -> Uuid {
+        let comp = CompNode::new(name, 0, end, 24.0);
+        let uuid = comp.uuid();
+        project.add_node(NodeKind::Comp(comp));
+        uuid
+    }
+
+    #[test]
+    fn test_next_comp_skips_files() {
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let a = add_comp(&mut project, "A", 9);
+        let file = project.create_file("shot.*.exr".to_string(), 1, 10, 24.0);
+        let b = add_comp(&mut project, "B", 9);
+
+        let player = Player::new();
+        assert_eq!(player.next_comp(a, &project), Some(b));
+        assert_eq!(player.next_comp(file, &project), Some(b));
+        assert_eq!(player.next_comp(b, &project), None);
+    }
+
+    #[test]
+    fn test_sequence_playback_emits_preload_then_next() {
+        let bus = EventBus::new();
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        project.set_event_emitter(bus.emitter());
+        let a = add_comp(&mut project, "A", 9);
+        add_comp(&mut project, "B", 9);
+
+        let mut player = Player::new();
+        player.set_active_comp(Some(a), &mut project);
+        player.set_play_range(0, 9, &mut project);
+        player.set_loop_enabled(false);
+        player.set_sequence_playback(true);
+        player.set_is_playing(true);
+
+        let mut preloads = Vec::new();
+        let mut next = None;
+        for _ in 0..10 {
+            let frame = player.advance_frames(&mut project, 1);
+            for event in bus.poll() {
+                if downcast_event::<PreloadNextCompEvent>(&event).is_some() {
+                    preloads.push(frame);
+                }
+                if let Some(e) = downcast_event::<PlayNextCompEvent>(&event) {
+                    next = Some(e.0);
+                }
+            }
+        }
+        // Work area shorter than the lead: preload right after the first frame
+        assert_eq!(preloads, vec![Some(1)]);
+        assert_eq!(next, Some(a));
+        assert!(!player.is_playing());
+    }
+
+    #[test]
+    fn test_fps_lock_drops_late_frames() {
+        use crate::entities::comp_events::CurrentFrameChangedEvent;
+
+        let bus = EventBus::new();
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        project.set_event_emitter(bus.emitter());
+        let a = add_comp(&mut project, "A", 99);
+        let mut player = Player::new();
+        player.set_active_comp(Some(a), &mut project);
+        player.set_is_playing(true);
+        let frame = Duration::from_secs_f32(1.0 / player.fps_play());
+        let start = Instant::now();
+        bus.poll();
+
+        // 3.5 frames late: jump to the third due frame, half a frame carried over
+        player.last_frame_time = Some(start);
+        let now = start + frame * 7 / 2;
+        assert_eq!(player.update_at(&mut project, now), Some(3));
+        let carried = player.last_frame_time.unwrap() - start;
+        assert!(carried.abs_diff(frame * 3) < Duration::from_micros(10));
+        // Skipped frames never become the playhead
+        let changes = bus
+            .poll()
+            .iter()
+            .filter(|e| downcast_event::<CurrentFrameChangedEvent>(e).is_some())
+            .count();
+        assert_eq!(changes, 1);
+
+        // Unlocked: one frame per update, clock restarts
+        player.set_lock_to_fps(false);
+        let now = now + frame * 7 / 2;
+        assert_eq!(player.update_at(&mut project, now), Some(4));
+        assert_eq!(player.last_frame_time, Some(now));
+
+        // A long stall restarts the clock rather than jumping ahead
+        player.set_lock_to_fps(true);
+        let now = now + Duration::from_secs(5);
+        assert_eq!(player.update_at(&mut project, now), Some(5));
+        assert_eq!(player.last_frame_time, Some(now));
+
+        // Late past the end: loop wraps, otherwise stop on the last frame
+        player.set_play_range(0, 9, &mut project);
+        player.set_frame(8, &mut project);
+        player.last_frame_time = Some(now);
+        let now = now + frame * 4;
+        assert_eq!(player.update_at(&mut project, now), Some(2));
+        player.set_loop_enabled(false);
+        player.set_frame(8, &mut project);
+        player.last_frame_time = Some(now);
+        let now = now + frame * 4;
+        assert_eq!(player.update_at(&mut project, now), Some(9));
+        assert!(!player.is_playing());
+
+        player.update(&mut project);
+        assert_eq!(player.actual_fps(), None);
+    }
+
+    #[test]
+    fn test_player_follows_active_comp_fps() {
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let a = add_comp(&mut project, "A", 9);
+        project.modify_comp(a, |comp| comp.set_fps(25.0));
+        let mut player = Player::new();
+        player.set_active_comp(Some(a), &mut project);
+        assert_eq!((player.fps_base(), player.fps_play()), (25.0, 25.0));
+
+        // Live edit: only the rate changes, nothing else to reload
+        project.modify_comp(a, |comp| comp.set_fps(30.0));
+        assert!(player.sync_fps(&project));
+        assert_eq!(player.fps_base(), 30.0);
+        assert!(!player.sync_fps(&project));
+    }
+
+    #[test]
+    fn test_frame_hold_survives_play_pause() {
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let a = add_comp(&mut project, "A", 20);
+        let mut player = Player::new();
+        player.set_active_comp(Some(a), &mut project);
+        player.set_frame(5, &mut project);
+        assert_eq!(player.toggle_hold(&mut project), Some(5));
+
+        // Play, step and scrub leave the held frame on screen
+        player.set_is_playing(true);
+        assert_eq!(player.advance_frames(&mut project, 1), None);
+        player.set_is_playing(false);
+        player.step(3, &mut project);
+        player.set_frame(12, &mut project);
+        player.set_is_playing(true);
+        assert_eq!(player.current_frame(&project), 5);
+        assert_eq!(player.held_frame(), Some(5));
+
+        // Release resumes from the held frame
+        assert_eq!(player.toggle_hold(&mut project), None);
+        assert_eq!(player.advance_frames(&mut project, 1), Some(6));
+    }
+
+    #[test]
+    fn test_step_to_wraps_or_clamps_by_loop_mode() {
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let a = add_comp(&mut project, "A", 100);
+        let mut player = Player::new();
+        player.set_active_comp(Some(a), &mut project);
+        player.set_play_range(10, 50, &mut project);
+        player.set_frame(10, &mut project);
+
+        // 5 and 80 lie outside the work area and are skipped
+        let targets = [40, 5, 20, 80];
+        player.step_to(&targets, true, &mut project);
+        assert_eq!(player.current_frame(&project), 20);
+        player.step_to(&targets, true, &mut project);
+        assert_eq!(player.current_frame(&project), 40);
+
+        player.set_loop_enabled(true);
+        player.step_to(&targets, true, &mut project);
+        assert_eq!(player.current_frame(&project), 20);
+
+        player.set_loop_enabled(false);
+        player.step_to(&targets, false, &mut project);
+        assert_eq!(player.current_frame(&project), 10);
+        player.step_to(&[], true, &mut project);
+        assert_eq!(player.current_frame(&project), 10);
+    }
+}
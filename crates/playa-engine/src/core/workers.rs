@@ -6,12 +6,21 @@
 //! - Zero lock contention between workers
 //!
 //! Epoch mechanism allows cancelling stale requests during fast timeline scrubbing.
+//!
+//! Three queues are drained high-first: `execute_*_priority` jobs (the frame
+//! under the playhead) jump ahead of everything queued normally, and
+//! `execute_*_background` jobs (preloads of comps other than the active one,
+//! see [`Workers::set_active_comp`]) run only when nothing else is queued.
+//!
+//! The pool can be resized live ([`Workers::resize`]): surplus threads park
+//! instead of exiting, so shrinking never interrupts a running job.
 
-use crossbeam::deque::{Injector, Worker};
+use crossbeam::deque::{Injector, Stealer, Worker};
 use log::trace;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::thread;
+use uuid::Uuid;
 
 use crate::entities::WorkerPool;
 
@@ -32,11 +41,17 @@ type Job = Box<dyn FnOnce() + Send + 'static>;
 /// });
 /// ```
 pub struct Workers {
-    injector: Arc<Injector<Job>>, // Global queue for external tasks
-    // Note: stealers Vec cloned into each thread, not stored here
-    handles: Vec<thread::JoinHandle<()>>, // Thread handles for proper shutdown
-    current_epoch: Arc<AtomicU64>,        // Epoch counter (shared with CacheManager)
-    shutdown: Arc<AtomicBool>,            // Shutdown signal
+    injector: Arc<Injector<Job>>,       // Global queue for external tasks
+    priority: Arc<Injector<Job>>,       // High tier, drained before `injector`
+    background: Arc<Injector<Job>>,     // Low tier, drained when all else is empty
+    active_comp: Mutex<Option<Uuid>>,   // Comp whose preloads skip the low tier
+    stealers: Mutex<Vec<Stealer<Job>>>, // Snapshot handed to threads spawned later
+    handles: Mutex<Vec<thread::JoinHandle<()>>>, // Thread handles for proper shutdown
+    active: Arc<AtomicUsize>,           // Threads with id >= active are parked
+    current_epoch: Arc<AtomicU64>,      // Epoch counter (shared with CacheManager)
+    finished: Arc<AtomicU64>,           // Epoch-checked jobs run to completion
+    running: Arc<AtomicUsize>,          // Epoch-checked jobs currently running
+    shutdown: Arc<AtomicBool>,          // Shutdown signal
 }
 
 impl Workers {
@@ -46,27 +61,78 @@ impl Workers {
     ///
     /// # Arguments
     ///
-    /// * `num_threads` - Number of worker threads
+    /// * `num_threads` - Number of worker threads (at least 1)
     /// * `epoch` - Shared epoch counter for cancelling stale requests
     pub fn new(num_threads: usize, epoch: Arc<AtomicU64>) -> Self {
-        let injector: Arc<Injector<Job>> = Arc::new(Injector::new());
-        let shutdown = Arc::new(AtomicBool::new(false));
+        let num_threads = num_threads.max(1);
+        let workers = Self {
+            injector: Arc::new(Injector::new()),
+            priority: Arc::new(Injector::new()),
+            background: Arc::new(Injector::new()),
+            active_comp: Mutex::new(None),
+            stealers: Mutex::new(Vec::new()),
+            handles: Mutex::new(Vec::new()),
+            active: Arc::new(AtomicUsize::new(num_threads)),
+            current_epoch: epoch,
+            finished: Arc::new(AtomicU64::new(0)),
+            running: Arc::new(AtomicUsize::new(0)),
+            shutdown: Arc::new(AtomicBool::new(false)),
+        };
+        workers.spawn_threads(num_threads);
 
-        let mut workers_local: Vec<Worker<Job>> = Vec::new();
-        let mut stealers = Vec::new();
-        let mut handles = Vec::new();
+        trace!(
+            "Workers initialized: {} threads (work-stealing)",
+            num_threads
+        );
+        workers
+    }
+
+    /// Number of threads currently taking work
+    pub fn num_threads(&self) -> usize {
+        self.active.load(Ordering::Relaxed)
+    }
+
+    /// Resize the pool live (clamped to at least 1 thread).
+    ///
+    /// Shrinking parks the surplus threads: each finishes the job it is
+    /// running, then stops taking work. Queued jobs stay in the injector for
+    /// the remaining threads, and epoch-checked jobs are still skipped if they
+    /// went stale meanwhile. Growing wakes parked threads first and spawns
+    /// new ones only past the highest count used so far.
+    pub fn resize(&self, num_threads: usize) {
+        let num_threads = num_threads.max(1);
+        self.spawn_threads(num_threads);
+        let prev = self.active.swap(num_threads, Ordering::SeqCst);
+        if prev != num_threads {
+            trace!("Workers resized: {} -> {} threads", prev, num_threads);
+        }
+    }
+
+    /// Spawn threads until `total` exist (no-op if already that many)
+    fn spawn_threads(&self, total: usize) {
+        let mut handles = self.handles.lock().expect("workers lock");
+        let mut stealers = self.stealers.lock().expect("workers lock");
+        let first = handles.len();
+        if total <= first {
+            return;
+        }
 
         // Create per-worker deques
-        for _ in 0..num_threads {
+        let mut workers_local: Vec<Worker<Job>> = Vec::new();
+        for _ in first..total {
             let worker: Worker<Job> = Worker::new_fifo();
             stealers.push(worker.stealer());
             workers_local.push(worker);
         }
 
         // Spawn worker threads
-        for (worker_id, worker) in workers_local.into_iter().enumerate() {
-            let injector = Arc::clone(&injector);
-            let shutdown = Arc::clone(&shutdown);
+        for (offset, worker) in workers_local.into_iter().enumerate() {
+            let worker_id = first + offset;
+            let injector = Arc::clone(&self.injector);
+            let priority = Arc::clone(&self.priority);
+            let background = Arc::clone(&self.background);
+            let shutdown = Arc::clone(&self.shutdown);
+            let active = Arc::clone(&self.active);
             let stealers = stealers.clone();
 
             let handle = thread::Builder::new()
@@ -76,19 +142,34 @@ impl Workers {
 
                     // Work-stealing loop
                     loop {
-                        // 1. Try own queue first (FIFO: older tasks execute first)
+                        // 0. Parked by resize(): take no work until woken
+                        if worker_id >= active.load(Ordering::Relaxed) {
+                            if shutdown.load(Ordering::Relaxed) {
+                                break;
+                            }
+                            thread::sleep(std::time::Duration::from_millis(5));
+                            continue;
+                        }
+
+                        // 1. High-priority tier always wins
+                        if let Some(job) = priority.steal().success() {
+                            job();
+                            continue;
+                        }
+
+                        // 2. Try own queue (FIFO: older tasks execute first)
                         if let Some(job) = worker.pop() {
                             job();
                             continue;
                         }
 
-                        // 2. Try global injector
+                        // 3. Try global injector
                         if let Some(job) = injector.steal().success() {
                             job();
                             continue;
                         }
 
-                        // 3. Try stealing from other workers (oldest tasks first)
+                        // 4. Try stealing from other workers (oldest tasks first)
                         let mut found_work = false;
                         for stealer in &stealers {
                             if let Some(job) = stealer.steal().success() {
@@ -102,12 +183,18 @@ impl Workers {
                             continue;
                         }
 
-                        // 4. Check shutdown
+                        // 5. Inactive comps' preloads, once the rest is drained
+                        if let Some(job) = background.steal().success() {
+                            job();
+                            continue;
+                        }
+
+                        // 6. Check shutdown
                         if shutdown.load(Ordering::Relaxed) {
                             break;
                         }
 
-                        // 5. No work - short sleep to avoid CPU spin
+                        // 7. No work - short sleep to avoid CPU spin
                         // Using 1ms sleep instead of pure yield to reduce CPU usage
                         thread::sleep(std::time::Duration::from_millis(1));
                     }
@@ -118,18 +205,6 @@ impl Workers {
 
             handles.push(handle);
         }
-
-        trace!(
-            "Workers initialized: {} threads (work-stealing)",
-            num_threads
-        );
-
-        Self {
-            injector,
-            handles,
-            current_epoch: epoch,
-            shutdown,
-        }
     }
 
     /// Execute closure on worker thread (high priority - goes to front).
@@ -174,20 +249,90 @@ impl Workers {
     where
         F: FnOnce() + Send + 'static,
     {
-        let current_epoch = Arc::clone(&self.current_epoch);
+        // Push to injector (high priority path)
+        self.injector.push(self.wrap_epoch(epoch, f));
+    }
 
-        // Wrap job with epoch check
-        // Why: Check happens at execution time, not enqueue time
-        // This allows epoch to change after enqueue but before execution
-        let wrapped = move || {
+    /// Like [`execute_with_epoch`](Self::execute_with_epoch), but queued in the
+    /// high tier: runs before any normally queued job that has not started.
+    ///
+    /// For the one frame the user is looking at (scrub target); neighbours go
+    /// through the normal queue so the tier stays short.
+    pub fn execute_with_epoch_priority<F>(&self, epoch: u64, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.priority.push(self.wrap_epoch(epoch, f));
+    }
+
+    /// Like [`execute_with_epoch`](Self::execute_with_epoch), but queued in the
+    /// low tier: runs only when no normal or priority job is waiting.
+    ///
+    /// For preloads of comps other than the active one (thumbnails, the next
+    /// comp of a sequence) so they never delay the main viewport.
+    pub fn execute_with_epoch_background<F>(&self, epoch: u64, f: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.background.push(self.wrap_epoch(epoch, f));
+    }
+
+    /// Set the comp shown in the main viewport (`None` = no bias).
+    ///
+    /// Preloads of any other comp go to the background tier.
+    pub fn set_active_comp(&self, comp: Option<Uuid>) {
+        *self.active_comp.lock().expect("workers lock") = comp;
+    }
+
+    /// True if `comp` is the active comp, or none is set
+    pub fn is_active_comp(&self, comp: Uuid) -> bool {
+        self.active_comp
+            .lock()
+            .expect("workers lock")
+            .is_none_or(|active| active == comp)
+    }
+
+    /// Epoch-checked jobs (frame loads / composes) run to completion so far.
+    ///
+    /// Stale jobs skipped by the epoch check are not counted; sample it twice
+    /// for a decode rate (status bar).
+    pub fn jobs_finished(&self) -> u64 {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// True while an epoch-checked job runs or any tier has jobs queued
+    pub fn is_busy(&self) -> bool {
+        self.running.load(Ordering::Relaxed) > 0
+            || !self.priority.is_empty()
+            || !self.injector.is_empty()
+            || !self.background.is_empty()
+            || self
+                .stealers
+                .lock()
+                .expect("workers lock")
+                .iter()
+                .any(|s| !s.is_empty())
+    }
+
+    /// Wrap job with epoch check
+    /// Why: Check happens at execution time, not enqueue time
+    /// This allows epoch to change after enqueue but before execution
+    fn wrap_epoch<F>(&self, epoch: u64, f: F) -> Job
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        let current_epoch = Arc::clone(&self.current_epoch);
+        let finished = Arc::clone(&self.finished);
+        let running = Arc::clone(&self.running);
+        Box::new(move || {
             if current_epoch.load(Ordering::Relaxed) == epoch {
+                running.fetch_add(1, Ordering::Relaxed);
                 f(); // Execute only if epoch still matches
+                running.fetch_sub(1, Ordering::Relaxed);
+                finished.fetch_add(1, Ordering::Relaxed);
             }
             // Otherwise silently skip (epoch changed, request is stale)
-        };
-
-        // Push to injector (high priority path)
-        self.injector.push(Box::new(wrapped));
+        })
     }
 }
 
@@ -195,7 +340,8 @@ impl Drop for Workers {
     fn drop(&mut self) {
         use std::time::{Duration, Instant};
 
-        let num_threads = self.handles.len();
+        let handles = std::mem::take(self.handles.get_mut().unwrap_or_else(|e| e.into_inner()));
+        let num_threads = handles.len();
         trace!("Workers shutting down ({} threads)...", num_threads);
 
         // Signal all workers to stop
@@ -206,7 +352,6 @@ impl Drop for Workers {
         // so threads should finish quickly. Timeout is a safety net.
         let deadline = Instant::now() + Duration::from_millis(500);
 
-        let handles = std::mem::take(&mut self.handles);
         for handle in handles {
             // Poll until thread finished or timeout
             while !handle.is_finished() {
@@ -234,4 +379,153 @@ impl WorkerPool for Workers {
         // Delegate to inherent method, unboxing is handled
         Workers::execute_with_epoch(self, epoch, f)
     }
+
+    fn execute_with_epoch_priority(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>) {
+        Workers::execute_with_epoch_priority(self, epoch, f)
+    }
+
+    fn execute_with_epoch_background(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>) {
+        Workers::execute_with_epoch_background(self, epoch, f)
+    }
+
+    fn is_active_comp(&self, comp: Uuid) -> bool {
+        Workers::is_active_comp(self, comp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn run_jobs(workers: &Workers, count: usize) {
+        let (tx, rx) = mpsc::channel();
+        for i in 0..count {
+            let tx = tx.clone();
+            workers.execute(move || tx.send(i).unwrap());
+        }
+        for _ in 0..count {
+            rx.recv_timeout(Duration::from_secs(10)).expect("job ran");
+        }
+    }
+
+    #[test]
+    fn test_resize_keeps_running_jobs() {
+        let workers = Workers::new(2, Arc::new(AtomicU64::new(0)));
+        assert_eq!(workers.num_threads(), 2);
+
+        // Serial decode: one thread still drains the queue
+        workers.resize(1);
+        assert_eq!(workers.num_threads(), 1);
+        run_jobs(&workers, 8);
+
+        workers.resize(0);
+        assert_eq!(workers.num_threads(), 1);
+
+        workers.resize(4);
+        assert_eq!(workers.num_threads(), 4);
+        assert_eq!(workers.handles.lock().unwrap().len(), 4);
+        run_jobs(&workers, 32);
+
+        // Shrinking parks threads rather than joining them
+        workers.resize(2);
+        assert_eq!(workers.handles.lock().unwrap().len(), 4);
+        run_jobs(&workers, 8);
+    }
+
+    #[test]
+    fn test_priority_frame_runs_before_neighbours() {
+        let workers = Workers::new(1, Arc::new(AtomicU64::new(0)));
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let (tx, rx) = mpsc::channel();
+
+        // Hold the only thread so the whole spiral is queued before it drains
+        workers.execute(move || gate_rx.recv().unwrap());
+        for offset in 1..=8 {
+            for frame in [50 - offset, 50 + offset] {
+                let tx = tx.clone();
+                workers.execute_with_epoch(0, move || tx.send(frame).unwrap());
+            }
+        }
+        let center = tx.clone();
+        workers.execute_with_epoch_priority(0, move || center.send(50).unwrap());
+        gate_tx.send(()).unwrap();
+
+        let order: Vec<i32> = (0..17)
+            .map(|_| rx.recv_timeout(Duration::from_secs(10)).unwrap())
+            .collect();
+        assert_eq!(order[0], 50);
+        assert_eq!(&order[1..3], &[49, 51]);
+    }
+
+    #[test]
+    fn test_inactive_comp_preload_runs_last() {
+        let workers = Workers::new(1, Arc::new(AtomicU64::new(0)));
+        let active = Uuid::new_v4();
+        workers.set_active_comp(Some(active));
+        assert!(workers.is_active_comp(active));
+        assert!(!workers.is_active_comp(Uuid::new_v4()));
+
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        let (tx, rx) = mpsc::channel();
+        workers.execute(move || gate_rx.recv().unwrap());
+        for frame in 0..4 {
+            let tx = tx.clone();
+            workers.execute_with_epoch_background(0, move || tx.send(100 + frame).unwrap());
+        }
+        for frame in 0..4 {
+            let tx = tx.clone();
+            workers.execute_with_epoch(0, move || tx.send(frame).unwrap());
+        }
+        workers.execute_with_epoch_priority(0, move || tx.send(-1).unwrap());
+        gate_tx.send(()).unwrap();
+
+        let order: Vec<i32> = (0..9)
+            .map(|_| rx.recv_timeout(Duration::from_secs(10)).unwrap())
+            .collect();
+        assert_eq!(order, vec![-1, 0, 1, 2, 3, 100, 101, 102, 103]);
+
+        workers.set_active_comp(None);
+        assert!(workers.is_active_comp(Uuid::new_v4()));
+    }
+
+    #[test]
+    fn test_stale_epoch_skipped_after_resize() {
+        let epoch = Arc::new(AtomicU64::new(1));
+        let workers = Workers::new(1, Arc::clone(&epoch));
+        let (tx, rx) = mpsc::channel();
+        let stale = tx.clone();
+        epoch.store(2, Ordering::SeqCst);
+        workers.resize(3);
+        workers.execute_with_epoch(1, move || stale.send("stale").unwrap());
+        workers.execute_with_epoch(2, move || tx.send("fresh").unwrap());
+        assert_eq!(rx.recv_timeout(Duration::from_secs(10)), Ok("fresh"));
+        assert!(rx.recv_timeout(Duration::from_millis(50)).is_err());
+    }
+
+    #[test]
+    fn test_finished_counts_fresh_jobs_only() {
+        let epoch = Arc::new(AtomicU64::new(2));
+        let workers = Workers::new(2, Arc::clone(&epoch));
+        assert!(!workers.is_busy());
+
+        let (gate_tx, gate_rx) = mpsc::channel::<()>();
+        workers.execute_with_epoch(2, move || gate_rx.recv().unwrap());
+        for _ in 0..5 {
+            workers.execute_with_epoch(2, || {});
+        }
+        workers.execute_with_epoch(1, || {});
+        assert!(workers.is_busy());
+        gate_tx.send(()).unwrap();
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(10);
+        while workers.jobs_finished() < 6 && std::time::Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(1));
+        }
+        // The stale job is skipped without being counted
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(workers.jobs_finished(), 6);
+        assert!(!workers.is_busy());
+    }
 }
@@ -0,0 +1,433 @@
+//! Review annotations: freehand strokes, arrows and text notes drawn over
+//! comp frames (RV / Frame.io style markup).
+//!
+//! Stored on the comp's `Attrs` under [`A_ANNOTATIONS`](super::keys::A_ANNOTATIONS)
+//! as a `List` of `Map { frame, kind, color, size, points, text }`, keyed by
+//! absolute comp frame like markers, so they serialize with the project.
+//! Points are comp pixels (top-left origin, Y-down): the viewport overlay
+//! maps them through the current zoom / pan, and [`burn_in`] rasterizes them
+//! into exported frames. Non-DAG: cached frames never contain annotations.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::attrs::AttrValue;
+use super::frame::{Frame, PixelBuffer};
+use super::text_node::TextNode;
+
+/// Default annotation palette (sRGB display colors, straight alpha)
+pub const ANNOTATION_COLORS: &[[f32; 4]] = &[
+    [1.0, 0.25, 0.25, 1.0],
+    [1.0, 0.85, 0.2, 1.0],
+    [0.3, 0.9, 0.4, 1.0],
+    [0.3, 0.75, 1.0, 1.0],
+    [1.0, 1.0, 1.0, 1.0],
+];
+
+/// Default stroke width in comp pixels
+pub const DEFAULT_STROKE_SIZE: f32 = 4.0;
+/// Default text height in comp pixels
+pub const DEFAULT_TEXT_SIZE: f32 = 36.0;
+
+/// Shape of an annotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Hash, Serialize, Deserialize)]
+pub enum AnnotationKind {
+    /// Polyline through every recorded point
+    #[default]
+    Freehand,
+    /// Straight arrow from the first point to the last
+    Arrow,
+    /// Text note anchored (top-left) at the first point
+    Text,
+}
+
+impl AnnotationKind {
+    pub const ALL: [AnnotationKind; 3] = [
+        AnnotationKind::Freehand,
+        AnnotationKind::Arrow,
+        AnnotationKind::Text,
+    ];
+
+    /// Attr value stored under `kind`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AnnotationKind::Freehand => "freehand",
+            AnnotationKind::Arrow => "arrow",
+            AnnotationKind::Text => "text",
+        }
+    }
+
+    /// Parse a `kind` attr value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "freehand" => Some(AnnotationKind::Freehand),
+            "arrow" => Some(AnnotationKind::Arrow),
+            "text" => Some(AnnotationKind::Text),
+            _ => None,
+        }
+    }
+
+    /// Human-readable name for menus
+    pub fn label(self) -> &'static str {
+        match self {
+            AnnotationKind::Freehand => "Freehand",
+            AnnotationKind::Arrow => "Arrow",
+            AnnotationKind::Text => "Text",
+        }
+    }
+}
+
+/// Single review annotation on one comp frame
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    /// Absolute comp frame
+    pub frame: i32,
+    pub kind: AnnotationKind,
+    /// Display color (sRGB 0..1, straight alpha)
+    pub color: [f32; 4],
+    /// Stroke width, or text height for [`AnnotationKind::Text`] (comp pixels)
+    pub size: f32,
+    /// Stroke points in comp pixels (top-left origin, Y-down)
+    pub points: Vec<[f32; 2]>,
+    /// Note text ([`AnnotationKind::Text`] only)
+    pub text: String,
+}
+
+impl Annotation {
+    /// Empty annotation with the default color and size for `kind`
+    pub fn new(frame: i32, kind: AnnotationKind) -> Self {
+        Self {
+            frame,
+            kind,
+            color: ANNOTATION_COLORS[0],
+            size: match kind {
+                AnnotationKind::Text => DEFAULT_TEXT_SIZE,
+                _ => DEFAULT_STROKE_SIZE,
+            },
+            points: Vec::new(),
+            text: String::new(),
+        }
+    }
+
+    /// Builder: set color
+    pub fn with_color(mut self, color: [f32; 4]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Builder: set stroke width / text height
+    pub fn with_size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Builder: set points
+    pub fn with_points(mut self, points: Vec<[f32; 2]>) -> Self {
+        self.points = points;
+        self
+    }
+
+    /// Builder: set note text
+    pub fn with_text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Encode as an attr map entry. Points are stored as `Vec3` (z unused).
+    pub fn to_attr(&self) -> AttrValue {
+        let mut map = HashMap::new();
+        map.insert("frame".to_string(), AttrValue::Int(self.frame));
+        map.insert(
+            "kind".to_string(),
+            AttrValue::Str(self.kind.as_str().to_string()),
+        );
+        map.insert("color".to_string(), AttrValue::Vec4(self.color));
+        map.insert("size".to_string(), AttrValue::Float(self.size));
+        map.insert(
+            "points".to_string(),
+            AttrValue::List(
+                self.points
+                    .iter()
+                    .map(|&[x, y]| AttrValue::Vec3([x, y, 0.0]))
+                    .collect(),
+            ),
+        );
+        if !self.text.is_empty() {
+            map.insert("text".to_string(), AttrValue::Str(self.text.clone()));
+        }
+        AttrValue::Map(map)
+    }
+
+    /// Decode an attr map entry. Entries without a frame or with an unknown
+    /// kind are rejected; other missing fields fall back to defaults.
+    pub fn from_attr(value: &AttrValue) -> Option<Self> {
+        let AttrValue::Map(map) = value else {
+            return None;
+        };
+        let frame = match map.get("frame")? {
+            AttrValue::Int(f) => *f,
+            _ => return None,
+        };
+        let kind = match map.get("kind") {
+            Some(AttrValue::Str(s)) => AnnotationKind::parse(s)?,
+            _ => AnnotationKind::default(),
+        };
+        let mut annotation = Self::new(frame, kind);
+        if let Some(AttrValue::Vec4(color)) = map.get("color") {
+            annotation.color = *color;
+        }
+        if let Some(AttrValue::Float(size)) = map.get("size") {
+            annotation.size = *size;
+        }
+        if let Some(AttrValue::List(points)) = map.get("points") {
+            annotation.points = points
+                .iter()
+                .filter_map(|p| match p {
+                    AttrValue::Vec3([x, y, _]) => Some([*x, *y]),
+                    _ => None,
+                })
+                .collect();
+        }
+        if let Some(AttrValue::Str(text)) = map.get("text") {
+            annotation.text = text.clone();
+        }
+        Some(annotation)
+    }
+}
+
+/// The two barbs of an arrowhead at `tip` for a shaft from `tail`, sized
+/// from the stroke width. `None` for a zero-length arrow.
+///
+/// Shared by the viewport overlay and [`burn_in`] so both draw the same head.
+pub fn arrow_head(tail: [f32; 2], tip: [f32; 2], size: f32) -> Option<[[f32; 2]; 2]> {
+    let (dx, dy) = (tip[0] - tail[0], tip[1] - tail[1]);
+    let len = (dx * dx + dy * dy).sqrt();
+    if len <= f32::EPSILON {
+        return None;
+    }
+    let head = (size * 4.0).max(10.0).min(len * 0.5);
+    let (ux, uy) = (dx / len, dy / len);
+    let (sin, cos) = 28f32.to_radians().sin_cos();
+    let barb = |s: f32| {
+        // Back along the shaft, rotated by +-28 degrees
+        let (bx, by) = (-ux * cos + uy * s * sin, -uy * cos - ux * s * sin);
+        [tip[0] + bx * head, tip[1] + by * head]
+    };
+    Some([barb(1.0), barb(-1.0)])
+}
+
+/// Rasterize `annotations` into a copy of `frame`.
+///
+/// Annotation coordinates are comp pixels; proxy frames are drawn at their
+/// reduced scale. Frames without annotations are returned as-is.
+pub fn burn_in(frame: &Frame, annotations: &[Annotation]) -> Frame {
+    if annotations.is_empty() {
+        return frame.clone();
+    }
+    let (w, h) = frame.resolution();
+    let scale = 1.0 / frame.proxy_factor() as f32;
+    frame.paint_overlay(&rasterize(annotations, w, h, scale))
+}
+
+/// Straight sRGB RGBA overlay of `w x h` pixels holding every annotation
+/// (later annotations on top).
+fn rasterize(annotations: &[Annotation], w: usize, h: usize, scale: f32) -> Vec<[f32; 4]> {
+    let mut overlay = vec![[0.0; 4]; w * h];
+    let mut coverage = vec![0.0f32; w * h];
+    for annotation in annotations {
+        let points: Vec<[f32; 2]> = annotation
+            .points
+            .iter()
+            .map(|&[x, y]| [x * scale, y * scale])
+            .collect();
+        let (Some(&first), Some(&last)) = (points.first(), points.last()) else {
+            continue;
+        };
+        let size = annotation.size * scale;
+        let segments: Vec<([f32; 2], [f32; 2])> = match annotation.kind {
+            AnnotationKind::Text => {
+                draw_text(&mut overlay, (w, h), annotation, first, size);
+                continue;
+            }
+            AnnotationKind::Freehand if points.len() == 1 => vec![(first, first)],
+            AnnotationKind::Freehand => points.windows(2).map(|s| (s[0], s[1])).collect(),
+            AnnotationKind::Arrow => {
+                let mut segments = vec![(first, last)];
+                if let Some(barbs) = arrow_head(first, last, size) {
+                    segments.extend(barbs.map(|b| (last, b)));
+                }
+                segments
+            }
+        };
+
+        // Coverage per stroke, so overlapping segments don't double-blend
+        coverage.fill(0.0);
+        let radius = size.max(1.0) * 0.5;
+        for (a, b) in segments {
+            stamp_segment(&mut coverage, (w, h), a, b, radius);
+        }
+        for (px, &c) in overlay.iter_mut().zip(&coverage) {
+            if c > 0.0 {
+                blend_over(px, annotation.color, c);
+            }
+        }
+    }
+    overlay
+}
+
+/// Max-accumulate antialiased coverage of a round-capped segment
+fn stamp_segment(
+    coverage: &mut [f32],
+    (w, h): (usize, usize),
+    a: [f32; 2],
+    b: [f32; 2],
+    radius: f32,
+) {
+    let pad = radius + 1.0;
+    let x0 = (a[0].min(b[0]) - pad).floor().max(0.0) as usize;
+    let y0 = (a[1].min(b[1]) - pad).floor().max(0.0) as usize;
+    let x1 = ((a[0].max(b[0]) + pad).ceil().max(0.0) as usize).min(w);
+    let y1 = ((a[1].max(b[1]) + pad).ceil().max(0.0) as usize).min(h);
+    let (dx, dy) = (b[0] - a[0], b[1] - a[1]);
+    let len_sq = dx * dx + dy * dy;
+    for y in y0..y1 {
+        for x in x0..x1 {
+            let (px, py) = (x as f32 + 0.5, y as f32 + 0.5);
+            let t = if len_sq > 0.0 {
+                (((px - a[0]) * dx + (py - a[1]) * dy) / len_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let (ex, ey) = (px - (a[0] + t * dx), py - (a[1] + t * dy));
+            let c = (radius + 0.5 - (ex * ex + ey * ey).sqrt()).clamp(0.0, 1.0);
+            let slot = &mut coverage[y * w + x];
+            *slot = slot.max(c);
+        }
+    }
+}
+
+/// Render a text note with cosmic-text and blend it in at `origin`
+fn draw_text(
+    overlay: &mut [[f32; 4]],
+    (w, h): (usize, usize),
+    annotation: &Annotation,
+    origin: [f32; 2],
+    size: f32,
+) {
+    if annotation.text.is_empty() {
+        return;
+    }
+    let mut text = TextNode::new("annotation", &annotation.text);
+    text.set_font_size(size.max(1.0));
+    text.set_color(annotation.color);
+    let glyphs = text.render_text();
+    let (gw, gh) = glyphs.resolution();
+    let buffer = glyphs.buffer();
+    let PixelBuffer::U8(pixels) = buffer.as_ref() else {
+        return;
+    };
+    let (ox, oy) = (origin[0].round() as i64, origin[1].round() as i64);
+    for row in 0..gh {
+        let y = oy + row as i64;
+        if y < 0 || y >= h as i64 {
+            continue;
+        }
+        for col in 0..gw {
+            let x = ox + col as i64;
+            if x < 0 || x >= w as i64 {
+                continue;
+            }
+            let s = &pixels[(row * gw + col) * 4..][..4];
+            if s[3] == 0 {
+                continue;
+            }
+            let color = [
+                s[0] as f32 / 255.0,
+                s[1] as f32 / 255.0,
+                s[2] as f32 / 255.0,
+                1.0,
+            ];
+            blend_over(
+                &mut overlay[y as usize * w + x as usize],
+                color,
+                s[3] as f32 / 255.0,
+            );
+        }
+    }
+}
+
+/// Straight-alpha "over" of `color` at `coverage` onto `dst`
+fn blend_over(dst: &mut [f32; 4], color: [f32; 4], coverage: f32) {
+    let a = color[3] * coverage;
+    let out_a = a + dst[3] * (1.0 - a);
+    if out_a <= 0.0 {
+        return;
+    }
+    for c in 0..3 {
+        dst[c] = (color[c] * a + dst[c] * dst[3] * (1.0 - a)) / out_a;
+    }
+    dst[3] = out_a;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::frame::{PixelBuffer, PixelFormat};
+
+    #[test]
+    fn test_annotation_attr_roundtrip() {
+        let a = Annotation::new(12, AnnotationKind::Arrow)
+            .with_color(ANNOTATION_COLORS[3])
+            .with_size(6.0)
+            .with_points(vec![[10.0, 20.0], [30.5, 40.25]]);
+        assert_eq!(Annotation::from_attr(&a.to_attr()), Some(a));
+
+        let t = Annotation::new(3, AnnotationKind::Text)
+            .with_points(vec![[1.0, 2.0]])
+            .with_text("soften edge");
+        assert_eq!(Annotation::from_attr(&t.to_attr()), Some(t));
+
+        let mut map = HashMap::new();
+        map.insert("frame".to_string(), AttrValue::Int(7));
+        map.insert("kind".to_string(), AttrValue::Str("blob".to_string()));
+        assert!(Annotation::from_attr(&AttrValue::Map(map)).is_none());
+        assert!(Annotation::from_attr(&AttrValue::Map(HashMap::new())).is_none());
+    }
+
+    #[test]
+    fn test_burn_in_strokes() {
+        let frame = Frame::from_buffer(
+            PixelBuffer::U8([0u8, 0, 0, 255].repeat(32 * 16)),
+            PixelFormat::Rgba8,
+            32,
+            16,
+        );
+        let stroke = Annotation::new(0, AnnotationKind::Freehand)
+            .with_color([1.0, 0.0, 0.0, 1.0])
+            .with_size(4.0)
+            .with_points(vec![[2.0, 8.0], [30.0, 8.0]]);
+        let out = burn_in(&frame, &[stroke]);
+        let buffer = out.buffer();
+        let PixelBuffer::U8(px) = buffer.as_ref() else {
+            panic!("pixel format changed");
+        };
+        let at = |x: usize, y: usize| &px[(y * 32 + x) * 4..][..4];
+        assert_eq!(at(16, 8), &[255, 0, 0, 255]); // on the stroke
+        assert_eq!(at(16, 1), &[0, 0, 0, 255]); // away from it
+        assert!(std::sync::Arc::ptr_eq(
+            &burn_in(&frame, &[]).buffer(),
+            &frame.buffer()
+        ));
+    }
+
+    #[test]
+    fn test_arrow_head() {
+        assert!(arrow_head([5.0, 5.0], [5.0, 5.0], 4.0).is_none());
+        let [a, b] = arrow_head([0.0, 0.0], [100.0, 0.0], 4.0).unwrap();
+        // Barbs trail the tip, mirrored across the shaft
+        assert!(a[0] < 100.0 && b[0] < 100.0);
+        assert!((a[0] - b[0]).abs() < 1e-4);
+        assert!((a[1] + b[1]).abs() < 1e-4);
+    }
+}
@@ -64,6 +64,8 @@ const TRANSFORM: &[AttrDef] = &[
     AttrDef::with_order("rotation", AttrType::Vec3, DAG_DISP_KEY, 40.1),
     AttrDef::with_order("scale", AttrType::Vec3, DAG_DISP_KEY, 40.2),
     AttrDef::with_order("pivot", AttrType::Vec3, DAG_DISP_KEY, 50.0),
+    // Keyframes for the keyable attrs above (see `keyframe.rs`)
+    AttrDef::with_order("keyframes", AttrType::Map, INT_DAG, 50.1),
 ];
 
 /// Node editor position (UI only, non-DAG)
@@ -95,9 +97,35 @@ const FILE_SPECIFIC: &[AttrDef] = &[
     AttrDef::with_order("file_mask", AttrType::String, DAG_DISP, 60.1),
     AttrDef::with_order("file_start", AttrType::Int, DAG_DISP, 60.2),
     AttrDef::with_order("file_end", AttrType::Int, DAG_DISP, 60.3),
-    AttrDef::with_order("padding", AttrType::Int, DAG, 60.4),
-    // FPS from source (readonly)
-    AttrDef::with_order("fps", AttrType::Float, DISP_RO, 20.6),
+    AttrDef::with_order("padding", AttrType::Int, DAG_DISP, 60.4),
+    // Gap handling: hold last frame / black / red error frame
+    AttrDef::with_ui_order(
+        "missing_frames",
+        AttrType::String,
+        DAG_DISP,
+        &["hold", "black", "error"],
+        60.5,
+    ),
+    // Single still: hold duration in frames (editable after load)
+    AttrDef::with_order("still_frames", AttrType::Int, DAG_DISP, 60.6),
+    // Source alpha association: auto = premultiplied for EXR, straight otherwise
+    AttrDef::with_ui_order(
+        "alpha_mode",
+        AttrType::String,
+        DAG_DISP,
+        &["auto", "straight", "premultiplied"],
+        60.7,
+    ),
+    // Playback FPS: container rate for video, prefs default for sequences (editable)
+    AttrDef::with_order("fps", AttrType::Float, DISP, 20.6),
+    // Anamorphic squeeze (viewport stretches for display only)
+    AttrDef::with_ui_order(
+        "pixel_aspect",
+        AttrType::Float,
+        DAG_DISP,
+        &["0.5", "4", "0.01"],
+        10.2,
+    ),
 ];
 
 pub static FILE_SCHEMA: LazyLock<AttrSchema> = LazyLock::new(|| {
@@ -119,6 +147,10 @@ const COMP_SPECIFIC: &[AttrDef] = &[
     AttrDef::with_order("frame", AttrType::Int, 0, 20.7),
     // Timeline bookmarks: Map of digit "0"-"9" -> frame number
     AttrDef::with_order("bookmarks", AttrType::Map, 0, 90.0),
+    // Timeline markers: List of Map { frame, label, color } (non-DAG)
+    AttrDef::with_order("markers", AttrType::List, 0, 90.1),
+    // Merge node flag (two-input A over B comp); structural, never edited
+    AttrDef::with_order("merge", AttrType::Bool, INT, 90.2),
     // Soft-marker mode for comp bounds. true (default) = rebound() auto-fits
     // A_IN/A_OUT to layer extents. false = user-pinned bounds (AE-style).
     // Non-DAG: toggling does not invalidate composed-frame cache.
@@ -133,6 +165,11 @@ const COMP_SPECIFIC: &[AttrDef] = &[
         &["rgba8", "rgba_f16", "rgba_f32"],
         20.9,
     ),
+    // Viewport ROI tool: composite only this rect (DAG - re-renders on change)
+    AttrDef::with_order("roi", AttrType::Vec4, INT_DAG, 90.3),
+    // Review annotations: List of Map { frame, kind, color, size, points, text }
+    // (non-DAG: drawn over the viewport, burned in on export only)
+    AttrDef::with_order("annotations", AttrType::List, INT, 90.4),
 ];
 
 pub static COMP_SCHEMA: LazyLock<AttrSchema> = LazyLock::new(|| {
@@ -170,9 +207,31 @@ const LAYER_SPECIFIC: &[AttrDef] = &[
     AttrDef::with_order("renderable", AttrType::Bool, DAG_DISP, 30.4), // false for camera/light/null/audio
     AttrDef::with_order("mute", AttrType::Bool, DAG_DISP, 30.2),
     AttrDef::with_order("solo", AttrType::Bool, DAG_DISP, 30.1),
+    AttrDef::with_order("locked", AttrType::Bool, DISP, 30.25), // edit guard, not DAG
+    AttrDef::with_order("frame_blend", AttrType::Bool, DAG_DISP, 30.5), // retime blending
+    AttrDef::with_order("time_remap", AttrType::Bool, DAG_DISP, 30.6),
+    AttrDef::with_order("remap_frame", AttrType::Float, DAG_DISP_KEY, 30.7), // remap curve
+    AttrDef::with_order("remap_ease", AttrType::Bool, DAG_DISP, 30.75),      // eased remap keys
     // Track-matte reference. UUID of a `RefNode` in `project.media`
     // whose target+channel masks this layer's composited alpha.
     AttrDef::with_order("mask_ref_uuid", AttrType::Uuid, DAG_DISP, 31.0),
+    // Vector mask shape, rasterized into the layer alpha (see `layer_mask`)
+    AttrDef::with_ui_order(
+        "mask_shape",
+        AttrType::String,
+        DAG_DISP,
+        &["none", "rectangle", "ellipse"],
+        31.1,
+    ),
+    AttrDef::with_order("mask_rect", AttrType::Vec4, DAG_DISP, 31.2),
+    AttrDef::with_ui_order(
+        "mask_feather",
+        AttrType::Float,
+        DAG_DISP_KEY,
+        &["0", "200", "1"],
+        31.3,
+    ),
+    AttrDef::with_order("mask_invert", AttrType::Bool, DAG_DISP, 31.4),
 ];
 
 pub static LAYER_SCHEMA: LazyLock<AttrSchema> = LazyLock::new(|| {
@@ -202,10 +261,13 @@ const PROJECT_SPECIFIC: &[AttrDef] = &[
         "tool",
         AttrType::String,
         0, // Viewport tool
-        &["select", "move", "rotate", "scale"],
+        &[
+            "select", "move", "rotate", "scale", "roi", "annotate", "mask",
+        ],
         90.3,
     ),
     AttrDef::with_order("prefs", AttrType::Map, INT, 90.4), // UI: project preferences (gizmo, etc)
+    AttrDef::with_order("view_bookmarks", AttrType::Json, INT, 90.5), // UI: viewport view slots
 ];
 
 pub static PROJECT_SCHEMA: LazyLock<AttrSchema> =
@@ -222,6 +284,9 @@ const PLAYER_SPECIFIC: &[AttrDef] = &[
     AttrDef::with_order("fps_play", AttrType::Float, 0, 90.2),
     AttrDef::with_order("loop_enabled", AttrType::Bool, 0, 90.3),
     AttrDef::with_order("play_direction", AttrType::Float, 0, 90.4),
+    AttrDef::with_order("sequence_playback", AttrType::Bool, 0, 90.5),
+    AttrDef::with_order("held_frame", AttrType::Int, 0, 90.6),
+    AttrDef::with_order("lock_to_fps", AttrType::Bool, 0, 90.7),
 ];
 
 pub static PLAYER_SCHEMA: LazyLock<AttrSchema> =
@@ -262,7 +327,7 @@ const CAMERA_SPECIFIC: &[AttrDef] = &[
         &["0.01", "10", "0.01"],
         62.0,
     ),
-    // Depth of field (future)
+    // Depth of field (per-layer blur ramp by camera depth)
     AttrDef::with_order("dof_enabled", AttrType::Bool, DAG_DISP, 63.0),
     AttrDef::with_order("focus_distance", AttrType::Float, DAG_DISP_KEY, 63.1),
     AttrDef::with_ui_order(
@@ -308,6 +373,15 @@ const TEXT_SPECIFIC: &[AttrDef] = &[
         &["1", "500", "1"],
         60.2,
     ),
+    AttrDef::with_order("bold", AttrType::Bool, DAG_DISP, 60.21),
+    AttrDef::with_order("italic", AttrType::Bool, DAG_DISP, 60.22),
+    AttrDef::with_ui_order(
+        "letter_spacing",
+        AttrType::Float,
+        DAG_DISP_KEY,
+        &["-0.2", "1", "0.01"],
+        60.23,
+    ),
     AttrDef::with_order("color", AttrType::Vec4, DAG_DISP_KEY, 60.3),
     AttrDef::with_ui_order(
         "alignment",
@@ -325,6 +399,16 @@ const TEXT_SPECIFIC: &[AttrDef] = &[
     ),
     // Background
     AttrDef::with_order("bg_color", AttrType::Vec4, DAG_DISP, 60.6),
+    // Animation: text offset inside the canvas, typewriter reveal (-1 = all)
+    AttrDef::with_order("position", AttrType::Vec3, DAG_DISP_KEY, 60.7),
+    AttrDef::with_ui_order(
+        "reveal",
+        AttrType::Int,
+        DAG_DISP_KEY,
+        &["-1", "1000", "1"],
+        60.8,
+    ),
+    AttrDef::with_order("keyframes", AttrType::Map, INT_DAG, 60.9),
 ];
 
 // ============================================================================
@@ -380,3 +464,32 @@ pub static AI_SCHEMA: LazyLock<AttrSchema> =
 pub static TEXT_SCHEMA: LazyLock<AttrSchema> = LazyLock::new(|| {
     AttrSchema::from_slices("TextNode", &[IDENTITY, TEXT_SPECIFIC, TIMING, OPACITY])
 });
+
+// ============================================================================
+// SolidNode Schema
+// ============================================================================
+
+/// Solid-specific attributes (fill color, optional two-color gradient)
+const SOLID_SPECIFIC: &[AttrDef] = &[
+    // Resolution (editable for generators)
+    AttrDef::with_order("width", AttrType::Int, DAG_DISP, 10.0),
+    AttrDef::with_order("height", AttrType::Int, DAG_DISP, 10.1),
+    AttrDef::with_order("color", AttrType::Vec4, DAG_DISP, 60.0),
+    AttrDef::with_ui_order(
+        "gradient",
+        AttrType::String,
+        DAG_DISP,
+        &["none", "horizontal", "vertical", "linear", "radial"],
+        60.1,
+    ),
+    AttrDef::with_order("color2", AttrType::Vec4, DAG_DISP, 60.2),
+    // Ramp placement (linear angle, radial center/extent)
+    AttrDef::with_order("angle", AttrType::Float, DAG_DISP, 60.3),
+    AttrDef::with_order("center_x", AttrType::Float, DAG_DISP, 60.4),
+    AttrDef::with_order("center_y", AttrType::Float, DAG_DISP, 60.5),
+    AttrDef::with_order("radius", AttrType::Float, DAG_DISP, 60.6),
+];
+
+pub static SOLID_SCHEMA: LazyLock<AttrSchema> = LazyLock::new(|| {
+    AttrSchema::from_slices("SolidNode", &[IDENTITY, SOLID_SPECIFIC, TIMING, OPACITY])
+});
@@ -405,6 +405,37 @@ impl PartialEq for AttrValue {
 
 impl Eq for AttrValue {}
 
+impl AttrValue {
+    /// Convert plain JSON (`0.5`, `"screen"`, `[0, 10, 0]`) into a value of
+    /// `attr_type`, for callers that don't speak the tagged serde form (REST).
+    /// `None` on a type mismatch. List/Map/Set still expect the tagged form.
+    pub fn from_json(value: &serde_json::Value, attr_type: AttrType) -> Option<Self> {
+        let floats = |n: usize| -> Option<Vec<f32>> {
+            let arr = value.as_array().filter(|a| a.len() == n)?;
+            arr.iter().map(|v| v.as_f64().map(|f| f as f32)).collect()
+        };
+        match attr_type {
+            AttrType::Bool => value.as_bool().map(AttrValue::Bool),
+            AttrType::Int => value
+                .as_i64()
+                .and_then(|i| i32::try_from(i).ok())
+                .map(AttrValue::Int),
+            AttrType::Float => value.as_f64().map(|f| AttrValue::Float(f as f32)),
+            AttrType::String => value.as_str().map(|s| AttrValue::Str(s.to_string())),
+            AttrType::Uuid => value
+                .as_str()
+                .and_then(|s| Uuid::parse_str(s).ok())
+                .map(AttrValue::Uuid),
+            AttrType::Vec3 => floats(3).map(|v| AttrValue::Vec3([v[0], v[1], v[2]])),
+            AttrType::Vec4 => floats(4).map(|v| AttrValue::Vec4([v[0], v[1], v[2], v[3]])),
+            AttrType::Json => Some(AttrValue::Json(value.to_string())),
+            AttrType::List | AttrType::Map | AttrType::Set => {
+                serde_json::from_value(value.clone()).ok()
+            }
+        }
+    }
+}
+
 /// Attribute container: string key → typed value.
 ///
 /// Includes dirty tracking for cache invalidation.
@@ -946,6 +977,34 @@ impl Clone for Attrs {
 mod flag_tests {
     use super::*;
 
+    #[test]
+    fn attr_value_from_plain_json() {
+        use serde_json::json;
+        assert_eq!(
+            AttrValue::from_json(&json!(0.5), AttrType::Float),
+            Some(AttrValue::Float(0.5))
+        );
+        assert_eq!(
+            AttrValue::from_json(&json!("screen"), AttrType::String),
+            Some(AttrValue::Str("screen".into()))
+        );
+        assert_eq!(
+            AttrValue::from_json(&json!([1, 2.5, 0]), AttrType::Vec3),
+            Some(AttrValue::Vec3([1.0, 2.5, 0.0]))
+        );
+        assert_eq!(
+            AttrValue::from_json(&json!(7), AttrType::Int),
+            Some(AttrValue::Int(7))
+        );
+        // Type mismatches are rejected
+        assert_eq!(AttrValue::from_json(&json!("x"), AttrType::Float), None);
+        assert_eq!(AttrValue::from_json(&json!([1, 2]), AttrType::Vec3), None);
+        assert_eq!(
+            AttrValue::from_json(&json!(1u64 << 40), AttrType::Int),
+            None
+        );
+    }
+
     #[test]
     fn attrflags_serde_roundtrips_as_bits() {
         let f = AttrFlags::SOURCE | AttrFlags::READONLY;
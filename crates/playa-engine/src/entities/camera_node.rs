@@ -13,6 +13,13 @@ use super::frame::Frame;
 use super::keys::{A_IN, A_OUT, A_SPEED, A_SRC_LEN, A_TRIM_IN, A_TRIM_OUT};
 use super::node::{ComputeContext, Node};
 
+/// Vertical film gate (full-frame 35mm) for focal length <-> FOV conversion
+pub const FILM_GATE_MM: f32 = 24.0;
+/// DOF blur radius in pixels for a layer at twice the focus distance at f/1
+pub const DOF_BLUR_SCALE: f32 = 16.0;
+/// Upper bound on DOF blur radius in pixels (keeps the CPU blur bounded)
+pub const DOF_MAX_RADIUS: f32 = 64.0;
+
 /// Camera node for 3D compositing.
 ///
 /// Standard layer attributes:
@@ -26,7 +33,11 @@ use super::node::{ComputeContext, Node};
 /// - use_poi: if true, use POI; if false, use rotation
 /// - fov: field of view in degrees (default 39.6 like AE)
 /// - near_clip, far_clip: clipping planes
-/// - dof_enabled, focus_distance, aperture: depth of field (future)
+/// - dof_enabled, focus_distance, aperture: depth of field (screen-space
+///   blur per layer, ramped by the layer's depth from the camera)
+///
+/// Position/rotation on the camera's layer can be keyframed
+/// (see [`keyframe`](super::keyframe)).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CameraNode {
     pub attrs: Attrs,
@@ -142,6 +153,40 @@ impl CameraNode {
         self.attrs.get_float("aperture").unwrap_or(2.8)
     }
 
+    /// Focal length in mm equivalent to the vertical FOV on [`FILM_GATE_MM`]
+    pub fn focal_length(&self) -> f32 {
+        let half = (self.fov().to_radians() * 0.5).tan().max(f32::EPSILON);
+        FILM_GATE_MM * 0.5 / half
+    }
+
+    /// Set the FOV from a focal length in mm (framing by lens)
+    pub fn set_focal_length(&mut self, mm: f32) {
+        let fov = 2.0 * (FILM_GATE_MM * 0.5 / mm.max(0.1)).atan();
+        self.attrs.set("fov", AttrValue::Float(fov.to_degrees()));
+    }
+
+    // === Depth of field ===
+
+    /// Distance from the camera to `point` along the view axis.
+    pub fn depth_of(&self, position: [f32; 3], rotation: [f32; 3], point: [f32; 3]) -> f32 {
+        let view = self.view_matrix(position, rotation);
+        view.transform_point3(Vec3::from(point)).z.abs()
+    }
+
+    /// Blur radius in pixels for a layer at `depth`; 0 when DOF is off or in focus.
+    ///
+    /// Screen-space approximation: the radius grows with the relative
+    /// distance from the focal plane and shrinks as the f-number rises.
+    pub fn dof_radius(&self, depth: f32) -> f32 {
+        if !self.dof_enabled() {
+            return 0.0;
+        }
+        let focus = self.focus_distance().max(1.0);
+        let aperture = self.aperture().max(0.1);
+        let radius = (depth - focus).abs() / focus * DOF_BLUR_SCALE / aperture;
+        radius.min(DOF_MAX_RADIUS)
+    }
+
     // === Matrix builders ===
 
     /// Build view matrix (world -> camera space).
@@ -326,6 +371,35 @@ mod tests {
         assert!(!cam.is_orthographic());
     }
 
+    #[test]
+    fn test_focal_length_roundtrip() {
+        let mut cam = CameraNode::new("Camera");
+        cam.set_focal_length(50.0);
+        assert!((cam.focal_length() - 50.0).abs() < 0.01);
+        // Longer lens = narrower field of view
+        let wide = cam.fov();
+        cam.set_focal_length(85.0);
+        assert!(cam.fov() < wide);
+    }
+
+    #[test]
+    fn test_dof_radius_ramp() {
+        let mut cam = CameraNode::new("Camera");
+        assert_eq!(cam.dof_radius(3000.0), 0.0);
+
+        cam.attrs.set("dof_enabled", AttrValue::Bool(true));
+        cam.attrs.set("focus_distance", AttrValue::Float(1000.0));
+        cam.attrs.set("aperture", AttrValue::Float(2.0));
+        assert_eq!(cam.dof_radius(1000.0), 0.0);
+        assert!((cam.dof_radius(2000.0) - DOF_BLUR_SCALE / 2.0).abs() < 1e-4);
+        assert!(cam.dof_radius(1500.0) < cam.dof_radius(2000.0));
+        assert_eq!(cam.dof_radius(1.0e6), DOF_MAX_RADIUS);
+
+        // Depth is measured along the view axis from the camera layer
+        let depth = cam.depth_of([0.0, 0.0, 1000.0], [0.0, 0.0, 0.0], [300.0, 0.0, -500.0]);
+        assert!((depth - 1500.0).abs() < 1e-3);
+    }
+
     #[test]
     fn test_projection_matrix_orthographic() {
         let mut cam = CameraNode::new("Test");
@@ -52,20 +52,26 @@
 
 use std::cell::RefCell;
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
 
 use half::f16;
 use log::trace;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use super::annotation::Annotation;
 use super::attr_schemas::{COMP_SCHEMA, LAYER_SCHEMA};
 use super::attrs::{AttrValue, Attrs};
 use super::compositor::{BlendMode, CpuCompositor, LayerPayload};
 use super::effects::Effect;
-use super::frame::{Frame, FrameStatus, PixelBuffer, PixelFormat};
+use super::frame::{AlphaMode, Frame, FrameStatus, PixelBuffer, PixelFormat};
 use super::gpu_blend_bridge::GpuBlendReport;
+use super::keyframe;
 use super::keys::*;
+use super::layer_mask::LayerMask;
+use super::marker::Marker;
 use super::node::{ComputeContext, Node};
+use super::traits::ProxyLevel;
 use super::transform;
 use playa_time::{Round, Speed};
 
@@ -153,8 +159,14 @@ impl Layer {
         attrs.set(A_VISIBLE, AttrValue::Bool(true));
         attrs.set("renderable", AttrValue::Bool(true));
         attrs.set(A_SOLO, AttrValue::Bool(false));
+        attrs.set(A_MUTE, AttrValue::Bool(false));
+        attrs.set(A_LOCKED, AttrValue::Bool(false));
         attrs.set(A_BLEND_MODE, AttrValue::Str("normal".to_string()));
         attrs.set(A_SPEED, AttrValue::Float(1.0));
+        attrs.set(A_FRAME_BLEND, AttrValue::Bool(false));
+        attrs.set(A_TIME_REMAP, AttrValue::Bool(false));
+        attrs.set(A_REMAP_FRAME, AttrValue::Float(0.0));
+        attrs.set(A_REMAP_EASE, AttrValue::Bool(false));
         attrs.set(A_WIDTH, AttrValue::UInt(dim.0 as u32));
         attrs.set(A_HEIGHT, AttrValue::UInt(dim.1 as u32));
         // Transform in frame space (origin = center, Y-up)
@@ -163,6 +175,11 @@ impl Layer {
         attrs.set(A_ROTATION, AttrValue::Vec3([0.0, 0.0, 0.0]));
         attrs.set(A_SCALE, AttrValue::Vec3([1.0, 1.0, 1.0]));
         attrs.set(A_PIVOT, AttrValue::Vec3([0.0, 0.0, 0.0]));
+        // Mask shape off; the rect covers the whole source once enabled
+        attrs.set(A_MASK_SHAPE, AttrValue::Str("none".to_string()));
+        attrs.set(A_MASK_RECT, AttrValue::Vec4([0.0, 0.0, 1.0, 1.0]));
+        attrs.set(A_MASK_FEATHER, AttrValue::Float(0.0));
+        attrs.set(A_MASK_INVERT, AttrValue::Bool(false));
 
         // Clear dirty after construction - these are initial values, not changes
         attrs.clear_dirty();
@@ -198,6 +215,12 @@ impl Layer {
         self.attrs.set(A_MASK_REF_UUID, AttrValue::Uuid(ref_uuid));
     }
 
+    /// Mask shape evaluated at a comp frame (see [`LayerMask`]); `None`
+    /// when `mask_shape` is missing or "none".
+    pub fn mask_at(&self, frame: i32) -> Option<LayerMask> {
+        LayerMask::from_attrs(&self.attrs, frame)
+    }
+
     /// Create layer from existing attrs (for duplication/paste).
     /// Sets new uuid, keeps source_uuid from attrs.
     pub fn from_attrs(source_uuid: Uuid, mut attrs: Attrs) -> Self {
@@ -209,6 +232,17 @@ impl Layer {
         }
     }
 
+    /// Deep copy with a fresh instance uuid (and fresh effect uuids).
+    /// Source, timing, transform, keyframes and effect stack are kept.
+    pub fn duplicate(&self) -> Self {
+        let mut layer = self.clone();
+        layer.attrs.set_uuid(A_UUID, Uuid::new_v4());
+        for effect in &mut layer.effects {
+            effect.uuid = Uuid::new_v4();
+        }
+        layer
+    }
+
     /// Attach schema after deserialization
     pub fn attach_schema(&mut self) {
         self.attrs.attach_schema(&*LAYER_SCHEMA);
@@ -255,17 +289,102 @@ impl Layer {
     /// `"in"`) already accounts for trim via the timeline position. When playhead
     /// is at `play_start` (= `in + trim_in/speed`), `offset = trim_in/speed`, so
     /// `local_frame = trim_in` — exactly the first visible source frame.
+    ///
+    /// With time remap enabled the `remap_frame` curve replaces the linear
+    /// `offset * speed` mapping (see [`Self::remap_frame_at`]).
     pub fn parent_to_local(&self, parent_frame: i32) -> i32 {
+        if let Some(frame) = self.remap_frame_at(parent_frame) {
+            return frame.round() as i32;
+        }
         let start = self.start(); // = "in" (full bar start)
         let speed = Speed::new(self.attrs.get_float(A_SPEED).unwrap_or(1.0));
         let offset = parent_frame - start;
         speed.scale_timeline_to_src(offset, Round::Round)
     }
 
+    /// Unrounded [`Self::parent_to_local`]: fractional source frame for frame blending.
+    pub fn parent_to_local_frac(&self, parent_frame: i32) -> f64 {
+        if let Some(frame) = self.remap_frame_at(parent_frame) {
+            return frame;
+        }
+        let speed = Speed::new(self.attrs.get_float(A_SPEED).unwrap_or(1.0));
+        (parent_frame - self.start()) as f64 * speed.magnitude() as f64
+    }
+
+    /// Whether the `remap_frame` curve drives this layer's source time.
+    pub fn time_remap(&self) -> bool {
+        self.attrs.get_bool(A_TIME_REMAP).unwrap_or(false)
+    }
+
+    /// Source frame from the time-remap curve at a parent frame, `None` when
+    /// remapping is off. Keys sit on parent (comp) frames and interpolate
+    /// linearly, so a flat segment freezes the source and a steeper one ramps
+    /// speed up; with `remap_ease` each segment eases out of and into its keys
+    /// (smoothstep) for gradual speed ramps. The value is fractional for
+    /// frame blending.
+    pub fn remap_frame_at(&self, parent_frame: i32) -> Option<f64> {
+        if !self.time_remap() {
+            return None;
+        }
+        let ease: fn(f32) -> f32 = if self.attrs.get_bool(A_REMAP_EASE).unwrap_or(false) {
+            |t| t * t * (3.0 - 2.0 * t)
+        } else {
+            |t| t
+        };
+        match keyframe::eval_shaped(&self.attrs, A_REMAP_FRAME, parent_frame as f32, ease)? {
+            AttrValue::Float(f) => Some(f as f64),
+            AttrValue::Int(i) => Some(i as f64),
+            _ => None,
+        }
+    }
+
+    /// Whether retimed frames blend adjacent source frames instead of duplicating.
+    pub fn frame_blend(&self) -> bool {
+        self.attrs.get_bool(A_FRAME_BLEND).unwrap_or(false)
+    }
+
     pub fn is_visible(&self) -> bool {
         self.attrs.get_bool(A_VISIBLE).unwrap_or(true)
     }
 
+    pub fn is_solo(&self) -> bool {
+        self.attrs.get_bool(A_SOLO).unwrap_or(false)
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.attrs.get_bool(A_MUTE).unwrap_or(false)
+    }
+
+    /// Locked layers ignore timeline edits (move, trim, slide, split, delete)
+    pub fn is_locked(&self) -> bool {
+        self.attrs.get_bool(A_LOCKED).unwrap_or(false)
+    }
+
+    /// Whether the layer composites: visible, not muted, and soloed when
+    /// `comp_has_solo` (see [`CompNode::has_solo`]). Solo only narrows the
+    /// set - a hidden or muted layer stays out even when soloed.
+    pub fn is_active(&self, comp_has_solo: bool) -> bool {
+        self.is_visible() && !self.is_muted() && (!comp_has_solo || self.is_solo())
+    }
+
+    /// Vec3 attr (transform channel) at a comp frame: keyframes win over
+    /// the static value.
+    pub fn vec3_at(&self, key: &str, frame: i32, default: [f32; 3]) -> [f32; 3] {
+        match keyframe::eval(&self.attrs, key, frame as f32) {
+            Some(AttrValue::Vec3(v)) => v,
+            _ => default,
+        }
+    }
+
+    /// Evaluated (position, rotation, scale) at a comp frame
+    pub fn transform_at(&self, frame: i32) -> ([f32; 3], [f32; 3], [f32; 3]) {
+        (
+            self.vec3_at(A_POSITION, frame, [0.0, 0.0, 0.0]),
+            self.vec3_at(A_ROTATION, frame, [0.0, 0.0, 0.0]),
+            self.vec3_at(A_SCALE, frame, [1.0, 1.0, 1.0]),
+        )
+    }
+
     pub fn opacity(&self) -> f32 {
         self.attrs.get_float(A_OPACITY).unwrap_or(1.0)
     }
@@ -273,20 +392,52 @@ impl Layer {
     pub fn blend_mode(&self) -> BlendMode {
         self.attrs
             .get_str(A_BLEND_MODE)
-            .map(|s| match s {
-                "screen" => BlendMode::Screen,
-                "add" => BlendMode::Add,
-                "subtract" => BlendMode::Subtract,
-                "multiply" => BlendMode::Multiply,
-                "divide" => BlendMode::Divide,
-                "difference" => BlendMode::Difference,
-                "overlay" => BlendMode::Overlay,
-                _ => BlendMode::Normal,
-            })
+            .map(BlendMode::from_attr)
             .unwrap_or(BlendMode::Normal)
     }
 }
 
+/// Advisory mismatch between a layer's source and its comp.
+///
+/// Produced by [`CompNode::validate`]; never blocks compositing (a 30fps clip
+/// in a 24fps comp still plays, just not frame-for-frame).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompWarning {
+    /// Layer instance UUID
+    pub layer: Uuid,
+    pub layer_name: String,
+    pub kind: CompWarningKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CompWarningKind {
+    /// Source fps differs from the comp fps
+    FpsMismatch { source: f32, comp: f32 },
+    /// Source resolution differs from the comp resolution
+    ResolutionMismatch {
+        source: (usize, usize),
+        comp: (usize, usize),
+    },
+}
+
+impl std::fmt::Display for CompWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            CompWarningKind::FpsMismatch { source, comp } => write!(
+                f,
+                "{}: source is {} fps, comp is {} fps",
+                self.layer_name, source, comp
+            ),
+            CompWarningKind::ResolutionMismatch { source, comp } => write!(
+                f,
+                "{}: source is {}x{}, comp is {}x{}",
+                self.layer_name, source.0, source.1, comp.0, comp.1
+            ),
+        }
+    }
+}
+
 /// Node that composites multiple layers.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CompNode {
@@ -302,6 +453,9 @@ pub struct CompNode {
     /// Hovered layer (for viewport hover highlight, runtime only)
     #[serde(skip)]
     pub hovered_layer: Option<Uuid>,
+    /// Last [`Self::validate`] result (runtime only, see `Project::validate_comp`)
+    #[serde(skip)]
+    pub warnings: Vec<CompWarning>,
 }
 
 impl CompNode {
@@ -334,6 +488,7 @@ impl CompNode {
             layer_selection: Vec::new(),
             layer_selection_anchor: None,
             hovered_layer: None,
+            warnings: Vec::new(),
         }
     }
 
@@ -343,6 +498,24 @@ impl CompNode {
         self
     }
 
+    /// Deep copy under a new comp uuid and `name`: every layer is duplicated
+    /// (fresh layer / effect uuids, same sources) so cache keys and layer
+    /// events never alias the original. Runtime selection is dropped.
+    pub fn duplicate(&self, name: &str) -> Self {
+        let mut attrs = self.attrs.clone();
+        attrs.set_uuid(A_UUID, Uuid::new_v4());
+        attrs.set(A_NAME, AttrValue::Str(name.to_string()));
+        attrs.clear_dirty();
+        Self {
+            attrs,
+            layers: self.layers.iter().map(Layer::duplicate).collect(),
+            layer_selection: Vec::new(),
+            layer_selection_anchor: None,
+            hovered_layer: None,
+            warnings: Vec::new(),
+        }
+    }
+
     /// Attach schema after deserialization (comp + all layers)
     pub fn attach_schema(&mut self) {
         self.attrs.attach_schema(&*COMP_SCHEMA);
@@ -360,12 +533,159 @@ impl CompNode {
         self.attrs.get_str(A_NAME).unwrap_or("Untitled")
     }
 
+    /// Whether any layer is soloed; then only soloed layers composite
+    pub fn has_solo(&self) -> bool {
+        self.layers.iter().any(Layer::is_solo)
+    }
+
     /// Set current playhead frame.
     /// Changing playhead doesn't invalidate cache (frame is non-DAG in schema).
     pub fn set_frame(&mut self, frame: i32) {
         self.attrs.set(A_FRAME, super::attrs::AttrValue::Int(frame));
     }
 
+    // --- Merge ---
+
+    /// True for Merge nodes created by [`Project::create_merge`](super::project::Project::create_merge)
+    pub fn is_merge(&self) -> bool {
+        self.attrs.get_bool(A_MERGE).unwrap_or(false)
+    }
+
+    /// Merge inputs as `(A, B)` source UUIDs: A = top layer, B = background.
+    /// `None` for regular comps or a merge that lost an input.
+    pub fn merge_inputs(&self) -> Option<(Uuid, Uuid)> {
+        if !self.is_merge() || self.layers.len() < 2 {
+            return None;
+        }
+        Some((self.layers[0].source_uuid(), self.layers[1].source_uuid()))
+    }
+
+    // --- Markers ---
+
+    /// Timeline markers sorted by frame
+    pub fn markers(&self) -> Vec<Marker> {
+        let mut markers: Vec<Marker> = self
+            .attrs
+            .get_list(A_MARKERS)
+            .map(|list| list.iter().filter_map(Marker::from_attr).collect())
+            .unwrap_or_default();
+        markers.sort_by_key(|m| m.frame);
+        markers
+    }
+
+    /// Marker at exactly `frame`, if any
+    pub fn marker_at(&self, frame: i32) -> Option<Marker> {
+        self.markers().into_iter().find(|m| m.frame == frame)
+    }
+
+    /// Add a marker, replacing any existing marker on the same frame
+    pub fn set_marker(&mut self, marker: Marker) {
+        let mut markers = self.markers();
+        markers.retain(|m| m.frame != marker.frame);
+        markers.push(marker);
+        self.store_markers(markers);
+    }
+
+    /// Remove the marker at `frame`. Returns true if one was removed.
+    pub fn remove_marker(&mut self, frame: i32) -> bool {
+        let mut markers = self.markers();
+        let before = markers.len();
+        markers.retain(|m| m.frame != frame);
+        let removed = markers.len() != before;
+        if removed {
+            self.store_markers(markers);
+        }
+        removed
+    }
+
+    /// Frame of the first marker after `frame`
+    pub fn next_marker(&self, frame: i32) -> Option<i32> {
+        self.markers()
+            .into_iter()
+            .map(|m| m.frame)
+            .find(|&f| f > frame)
+    }
+
+    /// Frame of the last marker before `frame`
+    pub fn prev_marker(&self, frame: i32) -> Option<i32> {
+        self.markers()
+            .into_iter()
+            .map(|m| m.frame)
+            .rfind(|&f| f < frame)
+    }
+
+    /// Comp frames holding a key on the comp or any layer (sorted, deduplicated)
+    pub fn keyframe_frames(&self) -> Vec<i32> {
+        let mut frames = keyframe::key_frames(&self.attrs);
+        for layer in &self.layers {
+            frames.extend(keyframe::key_frames(&layer.attrs));
+        }
+        frames.sort_unstable();
+        frames.dedup();
+        frames
+    }
+
+    fn store_markers(&mut self, mut markers: Vec<Marker>) {
+        markers.sort_by_key(|m| m.frame);
+        self.attrs
+            .set_list(A_MARKERS, markers.iter().map(Marker::to_attr).collect());
+    }
+
+    /// All review annotations, in drawing order
+    pub fn annotations(&self) -> Vec<Annotation> {
+        self.attrs
+            .get_list(A_ANNOTATIONS)
+            .map(|list| list.iter().filter_map(Annotation::from_attr).collect())
+            .unwrap_or_default()
+    }
+
+    /// Review annotations drawn on `frame`, in drawing order
+    pub fn annotations_at(&self, frame: i32) -> Vec<Annotation> {
+        self.annotations()
+            .into_iter()
+            .filter(|a| a.frame == frame)
+            .collect()
+    }
+
+    /// Append an annotation (drawn above the existing ones on its frame)
+    pub fn add_annotation(&mut self, annotation: Annotation) {
+        let mut annotations = self.annotations();
+        annotations.push(annotation);
+        self.store_annotations(annotations);
+    }
+
+    /// Remove the newest annotation on `frame` (undo last stroke).
+    /// Returns true if one was removed.
+    pub fn pop_annotation(&mut self, frame: i32) -> bool {
+        let mut annotations = self.annotations();
+        let Some(idx) = annotations.iter().rposition(|a| a.frame == frame) else {
+            return false;
+        };
+        annotations.remove(idx);
+        self.store_annotations(annotations);
+        true
+    }
+
+    /// Remove every annotation on `frame`, or on all frames with `None`.
+    /// Returns the number removed.
+    pub fn clear_annotations(&mut self, frame: Option<i32>) -> usize {
+        let mut annotations = self.annotations();
+        let before = annotations.len();
+        annotations.retain(|a| frame.is_some_and(|f| a.frame != f));
+        let removed = before - annotations.len();
+        if removed > 0 {
+            self.store_annotations(annotations);
+        }
+        removed
+    }
+
+    fn store_annotations(&mut self, annotations: Vec<Annotation>) {
+        self.attrs.set_list(
+            A_ANNOTATIONS,
+            annotations.iter().map(Annotation::to_attr).collect(),
+        );
+    }
+
     /// Play range (work area) - returns (start, end)
     pub fn play_range(&self, _use_work_area: bool) -> (i32, i32) {
         self.work_area()
@@ -393,11 +713,87 @@ impl CompNode {
             .set(A_TRIM_OUT, super::attrs::AttrValue::Int(trim_out));
     }
 
+    /// Mark the work-area start (I / B), e.g. live during playback.
+    /// Marking past the current end resets the end to the comp out point so
+    /// the range never inverts (an inverted range would stall playback).
+    pub fn mark_play_start(&mut self, frame: i32) {
+        let frame = frame.clamp(self._in(), self._out());
+        if frame > self.play_range(true).1 {
+            self.set_comp_play_end(self._out());
+        }
+        self.set_comp_play_start(frame);
+    }
+
+    /// Mark the work-area end (O / N); mirror of [`Self::mark_play_start`].
+    pub fn mark_play_end(&mut self, frame: i32) {
+        let frame = frame.clamp(self._in(), self._out());
+        if frame < self.play_range(true).0 {
+            self.set_comp_play_start(self._in());
+        }
+        self.set_comp_play_end(frame);
+    }
+
+    /// Reset the work area to the full comp bounds (clear in/out).
+    pub fn clear_play_range(&mut self) {
+        self.set_comp_play_start(self._in());
+        self.set_comp_play_end(self._out());
+    }
+
     /// Called when comp becomes active
     pub fn on_activate(&mut self) {
         self.rebound();
     }
 
+    /// Flag layers whose source fps or resolution differs from the comp.
+    ///
+    /// Advisory only. Control layers (camera, light, null, audio) are
+    /// skipped, and fps is only compared for sources that carry one (a
+    /// generator has no frame rate of its own).
+    pub fn validate(
+        &self,
+        media: &std::collections::HashMap<Uuid, std::sync::Arc<super::node_kind::NodeKind>>,
+    ) -> Vec<CompWarning> {
+        let comp_fps = self.fps();
+        let comp_dim = self.dim();
+        let mut warnings = Vec::new();
+
+        for layer in &self.layers {
+            let Some(source) = media.get(&layer.source_uuid()) else {
+                continue;
+            };
+            if !source.is_renderable() {
+                continue;
+            }
+            let warn = |kind| CompWarning {
+                layer: layer.uuid(),
+                layer_name: layer.attrs.get_str(A_NAME).unwrap_or("layer").to_string(),
+                kind,
+            };
+
+            if let Some(source_fps) = source.attrs().get_float(A_FPS)
+                && (source_fps - comp_fps).abs() > 0.01
+            {
+                warnings.push(warn(CompWarningKind::FpsMismatch {
+                    source: source_fps,
+                    comp: comp_fps,
+                }));
+            }
+            let source_dim = source.dim();
+            if source_dim != comp_dim {
+                warnings.push(warn(CompWarningKind::ResolutionMismatch {
+                    source: source_dim,
+                    comp: comp_dim,
+                }));
+            }
+        }
+        warnings
+    }
+
+    /// Warnings from the last validation pass for one layer
+    pub fn layer_warnings(&self, layer_uuid: Uuid) -> impl Iterator<Item = &CompWarning> {
+        self.warnings.iter().filter(move |w| w.layer == layer_uuid)
+    }
+
     /// Calculate actual bounds from all visible layers.
     /// Uses dynamic src_len from media for accurate layer timing.
     pub fn bounds(
@@ -572,6 +968,7 @@ impl CompNode {
             workers: None,
             epoch: 0,
             gpu_blend_bridge: None,
+            proxy: ProxyLevel::Full, // Blocking callers (encode) always want full res
         };
         self.compute(frame_idx, &ctx)
     }
@@ -660,12 +1057,10 @@ impl CompNode {
             // Check if source is a camera
             if let Some(source) = media.get(&layer.source_uuid()) {
                 if let Some(camera) = source.as_camera() {
-                    // Position/rotation come from Layer attrs, not CameraNode
-                    let pos = layer
-                        .attrs
-                        .get_vec3(A_POSITION)
-                        .unwrap_or([0.0, 0.0, -1000.0]);
-                    let rot = layer.attrs.get_vec3(A_ROTATION).unwrap_or([0.0, 0.0, 0.0]);
+                    // Position/rotation come from Layer attrs (keyframed camera moves
+                    // evaluate at this frame), not CameraNode
+                    let pos = layer.vec3_at(A_POSITION, frame_idx, [0.0, 0.0, -1000.0]);
+                    let rot = layer.vec3_at(A_ROTATION, frame_idx, [0.0, 0.0, 0.0]);
                     return Some((camera, pos, rot));
                 }
             }
@@ -711,10 +1106,32 @@ impl CompNode {
         false
     }
 
+    /// Whether this frame can be composited at proxy resolution.
+    ///
+    /// The 2D path scales cleanly (positions/pivots divide by the proxy factor);
+    /// camera projection and tilted (X/Y rotated) layers work in comp pixel
+    /// units, so those frames are composed at full resolution instead.
+    pub fn supports_proxy(
+        &self,
+        frame_idx: i32,
+        media: &std::collections::HashMap<Uuid, std::sync::Arc<super::node_kind::NodeKind>>,
+    ) -> bool {
+        if self.active_camera(frame_idx, media).is_some() {
+            return false;
+        }
+        !self.layers.iter().any(|layer| {
+            let rot = layer.vec3_at(A_ROTATION, frame_idx, [0.0, 0.0, 0.0]);
+            layer.is_visible() && (rot[0] != 0.0 || rot[1] != 0.0)
+        })
+    }
+
     // --- Compat methods (for migration from old Comp) ---
 
-    /// Alias for remove_layer
+    /// [`remove_layer`](Self::remove_layer) for user edits: locked layers stay
     pub fn remove_child(&mut self, layer_uuid: Uuid) -> Option<Layer> {
+        if self.get_layer(layer_uuid)?.is_locked() {
+            return None;
+        }
         self.remove_layer(layer_uuid)
     }
 
@@ -764,6 +1181,35 @@ impl CompNode {
         self.attrs.set(A_COMP_DEPTH, AttrValue::Str(wire.to_string()));
     }
 
+    /// Region of interest in comp pixels; `None` composites the full frame.
+    pub fn roi(&self) -> Option<super::frame::PixelRect> {
+        let [x, y, w, h] = self.attrs.get_vec4(A_ROI)?;
+        (w >= 1.0 && h >= 1.0).then(|| {
+            super::frame::PixelRect::new(
+                x.max(0.0) as usize,
+                y.max(0.0) as usize,
+                w as usize,
+                h as usize,
+            )
+        })
+    }
+
+    /// Set or clear the region of interest. Either way the comp is marked
+    /// dirty so cached frames re-composite.
+    pub fn set_roi(&mut self, roi: Option<super::frame::PixelRect>) {
+        match roi {
+            Some(r) => self.attrs.set_vec4(
+                A_ROI,
+                [r.x as f32, r.y as f32, r.width as f32, r.height as f32],
+            ),
+            None => {
+                if self.attrs.remove(A_ROI).is_some() {
+                    self.mark_dirty();
+                }
+            }
+        }
+    }
+
     /// Layer index to UUID
     pub fn idx_to_uuid(&self, idx: usize) -> Option<Uuid> {
         self.layers.get(idx).map(|l| l.uuid())
@@ -847,6 +1293,37 @@ impl CompNode {
             .saturating_sub(1)
     }
 
+    /// Source file of the topmost visible, renderable layer at `frame_idx`
+    /// (layer order, solo and mute respected), descending into nested comps.
+    /// `None` when no file layer covers the frame.
+    pub fn source_path_at(
+        &self,
+        frame_idx: i32,
+        media: &std::collections::HashMap<Uuid, std::sync::Arc<super::node_kind::NodeKind>>,
+    ) -> Option<std::path::PathBuf> {
+        let has_solo = self.has_solo();
+        self.layers.iter().find_map(|layer| {
+            let (play_start, play_end) = self.get_layer_work_area(layer, media);
+            if frame_idx < play_start
+                || frame_idx > play_end
+                || !layer.is_active(has_solo)
+                || !layer.attrs.get_bool("renderable").unwrap_or(true)
+            {
+                return None;
+            }
+            let source = media.get(&layer.source_uuid())?;
+            let source_in = source.attrs().get_i32(A_IN).unwrap_or(0);
+            let source_out = source.attrs().get_i32(A_OUT).unwrap_or(0);
+            let source_frame =
+                (source_in + layer.parent_to_local(frame_idx)).clamp(source_in, source_out);
+            match source.as_ref() {
+                super::node_kind::NodeKind::File(file) => file.frame_path(source_frame),
+                super::node_kind::NodeKind::Comp(comp) => comp.source_path_at(source_frame, media),
+                _ => None,
+            }
+        })
+    }
+
     /// Get layer work area using source's actual duration. See [`Self::get_layer_end`].
     pub fn get_layer_work_area(
         &self,
@@ -891,11 +1368,65 @@ impl CompNode {
         }
     }
 
-    /// Move layers by delta frames
+    /// Set the same attrs on every layer in `layer_uuids` (Attribute Editor
+    /// multi-select, REST). Keyed channels update their key at the comp's
+    /// current frame, as in [`set_child_attrs_at`](Self::set_child_attrs_at).
+    /// The comp is marked dirty once if any layer changed, so
+    /// `Project::modify_comp` emits a single invalidation for the batch.
+    /// Returns the number of layers found.
+    pub fn set_layers_attrs(
+        &mut self,
+        layer_uuids: &[Uuid],
+        attrs: &[(String, super::attrs::AttrValue)],
+    ) -> usize {
+        let frame = self.frame();
+        let mut found = 0;
+        let mut dirty = false;
+        for layer_uuid in layer_uuids {
+            if let Some(layer) = self.get_layer_mut(*layer_uuid) {
+                for (key, value) in attrs {
+                    set_attr_at(&mut layer.attrs, key, frame, value.clone(), false);
+                }
+                dirty |= layer.attrs.is_dirty();
+                found += 1;
+            }
+        }
+        if dirty {
+            self.mark_dirty();
+        }
+        found
+    }
+
+    /// Set layer attrs at a comp frame. Attrs that already carry keyframes
+    /// update (or add) their key at `frame` so edits stay visible on animated
+    /// channels; attrs listed in `record_keys` get keyed too. Others set the
+    /// static value.
+    pub fn set_child_attrs_at(
+        &mut self,
+        layer_uuid: Uuid,
+        frame: i32,
+        attrs: Vec<(&str, super::attrs::AttrValue)>,
+        record_keys: &[&str],
+    ) {
+        if let Some(layer) = self.get_layer_mut(layer_uuid) {
+            for (key, value) in attrs {
+                let record = record_keys.contains(&key);
+                set_attr_at(&mut layer.attrs, key, frame, value, record);
+            }
+            if layer.attrs.is_dirty() {
+                self.mark_dirty();
+            }
+        }
+    }
+
+    /// Move layers by delta frames (locked layers are skipped)
     pub fn move_layers(&mut self, layer_uuids: &[Uuid], delta: i32) {
         log::trace!("move_layers: uuids={:?}, delta={}", layer_uuids, delta);
         for uuid in layer_uuids {
             if let Some(layer) = self.get_layer_mut(*uuid) {
+                if layer.is_locked() {
+                    continue;
+                }
                 let current_in = layer.attrs.get_i32(A_IN).unwrap_or(0);
                 layer
                     .attrs
@@ -923,9 +1454,12 @@ impl CompNode {
     /// delta is in TIMELINE frames, will be converted to SOURCE frames via speed.
     /// For "in": positive delta = trim more from start (play_start moves right)
     /// For "out": negative delta = trim more from end (play_end moves left)
+    /// Locked layers are skipped.
     pub fn trim_layers(&mut self, layer_uuids: &[Uuid], edge: &str, delta: i32) {
         for uuid in layer_uuids {
-            if let Some(layer) = self.get_layer_mut(*uuid) {
+            if let Some(layer) = self.get_layer_mut(*uuid)
+                && !layer.is_locked()
+            {
                 // Convert timeline delta to source frames.
                 let speed = Speed::new(layer.attrs.get_float(A_SPEED).unwrap_or(1.0));
                 let delta_source = speed.scale_timeline_to_src(delta, Round::Round);
@@ -957,6 +1491,52 @@ impl CompNode {
         self.rebound();
     }
 
+    /// Split child at `frame`: the original keeps `[layer_start, frame]`, a new
+    /// child inserted above it plays `[frame + 1, layer_end]`.
+    ///
+    /// Both halves keep the same source, `in` and speed, only `trim_in`/`trim_out`
+    /// change, so they map every frame to the same source frame as before and
+    /// play back-to-back as the original. Returns the new child's UUID, or
+    /// `None` if `frame` is outside `[layer_start, layer_end)` or the layer is
+    /// locked.
+    pub fn split_child(&mut self, child_idx: usize, frame: i32) -> Option<Uuid> {
+        let layer = self.layers.get_mut(child_idx)?;
+        if layer.is_locked() {
+            return None;
+        }
+        let (start, end) = (layer.attrs.layer_start(), layer.attrs.layer_end());
+        if frame < start || frame >= end {
+            return None;
+        }
+        let speed = Speed::new(layer.attrs.get_float_or(A_SPEED, 1.0));
+        let src_len = layer.attrs.src_len();
+        // Source offset of the first frame of the tail half
+        let split_src = speed.scale_timeline_to_src(frame + 1 - layer.start(), Round::Round);
+
+        let mut tail_attrs = layer.attrs.clone();
+        tail_attrs.set(A_TRIM_IN, AttrValue::Int(split_src));
+        layer
+            .attrs
+            .set(A_TRIM_OUT, AttrValue::Int(src_len - split_src));
+
+        let tail = Layer::from_attrs(layer.source_uuid(), tail_attrs);
+        let tail_uuid = tail.uuid();
+        self.add_layer(tail, Some(child_idx));
+        Some(tail_uuid)
+    }
+
+    /// Duplicate a layer directly above the original (same index, so the
+    /// original moves down one row) and name the copy `name`. Returns the
+    /// new layer's uuid, `None` if `layer_uuid` isn't in this comp.
+    pub fn duplicate_layer(&mut self, layer_uuid: Uuid, name: &str) -> Option<Uuid> {
+        let idx = self.uuid_to_idx(layer_uuid)?;
+        let mut layer = self.layers[idx].duplicate();
+        layer.attrs.set(A_NAME, AttrValue::Str(name.to_string()));
+        let uuid = layer.uuid();
+        self.add_layer(layer, Some(idx));
+        Some(uuid)
+    }
+
     /// Add child layer.
     ///
     /// Layer position defaults to (0,0,0) which is CENTER of comp in frame space.
@@ -1040,6 +1620,24 @@ impl CompNode {
         edges
     }
 
+    /// Layer edges within `radius` frames of `frame`, nearest first.
+    /// Edges are clip boundaries: a layer's first visible frame and one past its
+    /// last, so snapping a dragged edge onto one butts the clips together.
+    /// Layers in `exclude` (the ones being dragged) are skipped.
+    pub fn get_child_edges_near(&self, frame: i32, radius: i32, exclude: &[Uuid]) -> Vec<i32> {
+        let mut edges: Vec<i32> = self
+            .layers
+            .iter()
+            .filter(|l| !exclude.contains(&l.uuid()))
+            .filter(|l| l.attrs.layer_start() <= l.attrs.layer_end())
+            .flat_map(|l| [l.attrs.layer_start(), l.attrs.layer_end() + 1])
+            .filter(|e| (e - frame).abs() <= radius)
+            .collect();
+        edges.sort_by_key(|e| ((e - frame).abs(), *e));
+        edges.dedup();
+        edges
+    }
+
     /// Compute visual row for each layer (greedy non-overlapping layout)
     pub fn compute_layer_rows(
         &self,
@@ -1115,7 +1713,7 @@ impl CompNode {
     }
 
     /// Get frame cache statuses from global cache.
-    /// Returns status for each frame in the comp's range.
+    /// Returns status for each frame in the comp's range (any proxy level).
     pub fn cache_frame_statuses(
         &self,
         global_cache: Option<&std::sync::Arc<crate::core::global_cache::GlobalFrameCache>>,
@@ -1135,8 +1733,10 @@ impl CompNode {
 
         for frame_offset in 0..duration {
             let frame_idx = comp_start + frame_offset;
-            let status = cache
-                .get_status(comp_uuid, frame_idx)
+            // Full-res status first; proxy frames count as cached for the indicator
+            let status = ProxyLevel::ALL
+                .into_iter()
+                .find_map(|l| cache.get_status(l.cache_uuid(comp_uuid), frame_idx))
                 .unwrap_or(FrameStatus::Placeholder);
             statuses.push(status);
         }
@@ -1144,24 +1744,120 @@ impl CompNode {
         Some(statuses)
     }
 
-    /// Move single layer to new start position
+    /// Load error recorded on a cached frame of this comp (any proxy level).
+    ///
+    /// Backs the timeline tooltip on failed status strip frames.
+    pub fn cache_load_error(
+        &self,
+        global_cache: Option<&std::sync::Arc<crate::core::global_cache::GlobalFrameCache>>,
+        frame_idx: i32,
+    ) -> Option<String> {
+        let cache = global_cache?;
+        ProxyLevel::ALL
+            .into_iter()
+            .find_map(|l| cache.load_error(l.cache_uuid(self.uuid()), frame_idx))
+    }
+
+    /// Move single layer to new start position (errors on a locked layer)
     pub fn move_child(&mut self, layer_idx: usize, new_start: i32) -> anyhow::Result<()> {
         let layer = self
             .layers
             .get_mut(layer_idx)
             .ok_or_else(|| anyhow::anyhow!("Layer index out of bounds"))?;
+        if layer.is_locked() {
+            anyhow::bail!("Layer is locked");
+        }
         layer.attrs.set(A_IN, AttrValue::Int(new_start));
         self.mark_dirty();
         self.rebound();
         Ok(())
     }
 
+    /// Layers that follow `layer_uuid` on the timeline: every layer in
+    /// `child_order` (one track per layer, as drawn) whose full bar starts
+    /// after the target's full bar ends. Layers overlapping it stay put.
+    fn ripple_downstream(&self, layer_uuid: Uuid, child_order: &[usize]) -> Vec<Uuid> {
+        let Some(idx) = self.uuid_to_idx(layer_uuid) else {
+            return Vec::new();
+        };
+        let end = self.layers[idx].attrs.full_bar_end();
+        child_order
+            .iter()
+            .filter_map(|&i| self.layers.get(i))
+            .filter(|l| l.attrs.full_bar_start() > end)
+            .map(|l| l.uuid())
+            .collect()
+    }
+
+    /// Remove a layer and shift the layers after it earlier by its visible
+    /// duration, closing the gap. `child_order` is the timeline's layer
+    /// order. Returns the affected frame range, `None` if the layer is
+    /// missing or locked.
+    pub fn ripple_delete(&mut self, layer_uuid: Uuid, child_order: &[usize]) -> Option<(i32, i32)> {
+        let layer = self.get_layer(layer_uuid)?;
+        if layer.is_locked() {
+            return None;
+        }
+        let (start, end) = (layer.attrs.layer_start(), layer.attrs.layer_end());
+        let downstream = self.ripple_downstream(layer_uuid, child_order);
+        let last = self.ripple_extent(&downstream).max(end);
+        self.remove_layer(layer_uuid)?;
+        self.ripple_move(&downstream, start - end - 1);
+        Some((start, last))
+    }
+
+    /// Set a layer's play end and shift the layers after it (by
+    /// `child_order`, as for [`ripple_delete`](Self::ripple_delete)) by the
+    /// change in duration (negative = pulled earlier). Returns the affected
+    /// frame range.
+    pub fn ripple_trim_end(
+        &mut self,
+        layer_uuid: Uuid,
+        new_play_end: i32,
+        child_order: &[usize],
+    ) -> Option<(i32, i32)> {
+        let idx = self.uuid_to_idx(layer_uuid)?;
+        let old_end = self.layers[idx].attrs.layer_end();
+        let downstream = self.ripple_downstream(layer_uuid, child_order);
+        let last = self.ripple_extent(&downstream).max(old_end);
+        self.set_child_end(idx, new_play_end).ok()?;
+        let new_end = self.layers[idx].attrs.layer_end();
+        self.ripple_move(&downstream, new_end - old_end);
+        Some((old_end.min(new_end), last.max(last + new_end - old_end)))
+    }
+
+    /// Last visible frame among `layers` (i32::MIN if empty)
+    fn ripple_extent(&self, layers: &[Uuid]) -> i32 {
+        layers
+            .iter()
+            .filter_map(|uuid| self.get_layer(*uuid))
+            .map(|l| l.attrs.layer_end())
+            .max()
+            .unwrap_or(i32::MIN)
+    }
+
+    /// Shift `layers` by `delta` frames via [`move_child`](Self::move_child)
+    fn ripple_move(&mut self, layers: &[Uuid], delta: i32) {
+        if delta == 0 {
+            return;
+        }
+        for uuid in layers {
+            if let Some(idx) = self.uuid_to_idx(*uuid) {
+                let start = self.layers[idx].start();
+                let _ = self.move_child(idx, start + delta);
+            }
+        }
+    }
+
     /// Set layer play start (adjusts trim_in)
     pub fn set_child_start(&mut self, layer_idx: usize, new_play_start: i32) -> anyhow::Result<()> {
         let layer = self
             .layers
             .get_mut(layer_idx)
             .ok_or_else(|| anyhow::anyhow!("Layer index out of bounds"))?;
+        if layer.is_locked() {
+            anyhow::bail!("Layer is locked");
+        }
         let layer_in = layer.attrs.get_i32(A_IN).unwrap_or(0);
         let speed = Speed::new(layer.attrs.get_float(A_SPEED).unwrap_or(1.0));
         // trim_in in source frames (negative = extend before source start).
@@ -1178,6 +1874,9 @@ impl CompNode {
             .layers
             .get_mut(layer_idx)
             .ok_or_else(|| anyhow::anyhow!("Layer index out of bounds"))?;
+        if layer.is_locked() {
+            anyhow::bail!("Layer is locked");
+        }
         let layer_end = layer.end();
         let speed = Speed::new(layer.attrs.get_float(A_SPEED).unwrap_or(1.0));
         // trim_out in source frames (negative = extend after source end).
@@ -1221,12 +1920,15 @@ impl CompNode {
         // a layer demands it — comp_depth is a FLOOR, not a cap.
         let mut target_format = self.depth();
         let mut all_loaded = true;
+        // First failed source load, carried onto the composed frame
+        let mut load_error: Option<String> = None;
+        // Display PAR of the output: taken from the bottom-most non-square source
+        let mut pixel_aspect: Option<f32> = None;
+        // Proxy pass composites at 1/N of the comp size (see `supports_proxy`)
+        let proxy = ctx.proxy.factor();
 
         // Check if any layer has solo enabled
-        let has_solo = self
-            .layers
-            .iter()
-            .any(|l| l.attrs.get_bool(A_SOLO).unwrap_or(false));
+        let has_solo = self.has_solo();
 
         // Collect visible renderable layers with their Z positions for sorting.
         // Each entry: (layer_index, z_position) - index used for stable sort fallback.
@@ -1259,13 +1961,9 @@ impl CompNode {
                 continue;
             }
 
-            // Skip invisible
-            if !layer.is_visible() {
-                continue;
-            }
-
-            // Solo mode: skip non-solo layers when any layer is solo'd
-            if has_solo && !layer.attrs.get_bool(A_SOLO).unwrap_or(false) {
+            // Skip hidden and muted layers, and non-solo layers when any
+            // layer is solo'd
+            if !layer.is_active(has_solo) {
                 continue;
             }
 
@@ -1275,7 +1973,7 @@ impl CompNode {
             }
 
             // Get Z position for depth sorting
-            let pos = layer.attrs.get_vec3(A_POSITION).unwrap_or([0.0, 0.0, 0.0]);
+            let pos = layer.vec3_at(A_POSITION, frame_idx, [0.0, 0.0, 0.0]);
             renderable_layers.push((idx, pos[2]));
         }
 
@@ -1299,14 +1997,13 @@ impl CompNode {
         // Get active camera for this frame (if any)
         // Camera provides view-projection matrix for 3D perspective/ortho rendering
         // Position/rotation come from Layer attrs, not CameraNode
-        let view_projection: Option<glam::Mat4> =
-            self.active_camera(frame_idx, ctx.media)
-                .map(|(cam, pos, rot)| {
-                    let dim = self.dim();
-                    let aspect = dim.0 as f32 / dim.1 as f32;
-                    let comp_height = dim.1 as f32;
-                    cam.view_projection_matrix(pos, rot, aspect, comp_height)
-                });
+        let active_camera = self.active_camera(frame_idx, ctx.media);
+        let view_projection: Option<glam::Mat4> = active_camera.map(|(cam, pos, rot)| {
+            let dim = self.dim();
+            let aspect = dim.0 as f32 / dim.1 as f32;
+            let comp_height = dim.1 as f32;
+            cam.view_projection_matrix(pos, rot, aspect, comp_height)
+        });
 
         // Render layers in sorted order
         for (layer_idx, _z) in renderable_layers {
@@ -1318,8 +2015,19 @@ impl CompNode {
                 continue;
             };
 
-            // Convert to source frame with hold first/last for extended layers
-            let local_frame = layer.parent_to_local(frame_idx);
+            // Convert to source frame with hold first/last for extended layers.
+            // Frame blending keeps the fractional part instead of rounding.
+            let (local_frame, blend_t) = if layer.frame_blend() {
+                let local = layer.parent_to_local_frac(frame_idx);
+                let t = (local - local.floor()) as f32;
+                if t > 1e-3 && t < 1.0 - 1e-3 {
+                    (local.floor() as i32, Some(t))
+                } else {
+                    (layer.parent_to_local(frame_idx), None)
+                }
+            } else {
+                (layer.parent_to_local(frame_idx), None)
+            };
             let source_in = source_node.attrs().get_i32(A_IN).unwrap_or(0);
             let source_out = source_node.attrs().get_i32(A_OUT).unwrap_or(0);
             // Clamp to source range: hold first frame if before, hold last if after
@@ -1327,18 +2035,65 @@ impl CompNode {
 
             // Recursively compute source frame
             if let Some(mut frame) = source_node.compute(source_frame, ctx) {
+                let mask = layer.mask_at(frame_idx);
+                // Effect-cache identity: the fetched source plus what happens to
+                // it before effects. Blended frames mix two sources: not cached.
+                let effect_source = (blend_t.is_none()
+                    && !layer.effects.is_empty()
+                    && frame.status() == FrameStatus::Loaded)
+                    .then(|| {
+                        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+                        (proxy, mask.map(|m| m.cache_key())).hash(&mut hasher);
+                        super::effects::cache::SourceKey::new(&frame, hasher.finish())
+                    });
                 if frame.status() != FrameStatus::Loaded {
                     all_loaded = false;
                 }
+                if frame.status() == FrameStatus::Error && load_error.is_none() {
+                    let name = source_node.name();
+                    load_error = Some(frame.load_error().unwrap_or_else(|| {
+                        format!("{}: frame {} failed to load", name, source_frame)
+                    }));
+                }
+
+                // Frame blending: mix in the next source frame by the fractional part
+                if let Some(t) = blend_t {
+                    let next = (source_frame + 1).min(source_out);
+                    if next != source_frame
+                        && let Some(next_frame) = source_node.compute(next, ctx)
+                    {
+                        if next_frame.status() != FrameStatus::Loaded {
+                            all_loaded = false;
+                        } else if frame.status() == FrameStatus::Loaded
+                            && let Some(mixed) = Frame::lerp(&frame, &next_frame, t)
+                        {
+                            frame = mixed;
+                        }
+                    }
+                }
+
+                // Blends assume straight alpha: normalize premultiplied sources
+                // (after frame blending, which is correct on premultiplied data)
+                if frame.alpha_mode() == AlphaMode::Premultiplied {
+                    frame = frame.unpremultiply();
+                }
+
+                // Sources that don't decode at proxy res (text, solids) get downscaled here
+                if frame.proxy_factor() < proxy {
+                    frame = frame.downscale(proxy / frame.proxy_factor());
+                }
+
+                // Mask shape: cut the layer alpha in source space so effects
+                // and the transform see the masked layer
+                if let Some(mask) = mask {
+                    frame = mask.apply(&frame);
+                }
 
                 // Apply layer effects in order (blur, color correction, etc.)
                 // Effects are processed before transform so they work in layer-local space.
+                // CPU results are cached by (source buffer, prep, effect stack), so
+                // transform-only edits and re-scrubs reuse the effected frame.
                 //
-                // TODO: Cache effected frames separately from composed frames.
-                // Currently, changing only transform re-runs effects. With per-layer effect cache:
-                // - Key: (source_uuid, source_frame, effects_hash)
-                // - Transform changes reuse cached effected frame
-                // - Effect changes invalidate only that layer's effect cache
                 // Split layer effects between GPU and CPU paths.
                 //
                 // When the GPU compositor is active, every effect that
@@ -1351,36 +2106,54 @@ impl CompNode {
                 // on CPU as before.
                 let on_gpu_path = ctx.gpu_blend_bridge.is_some();
                 let mut gpu_effects: Vec<super::compositor::GpuEffect> = Vec::new();
-                if !layer.effects.is_empty() {
-                    for fx in &layer.effects {
-                        if !fx.enabled {
-                            continue;
-                        }
-                        if on_gpu_path
-                            && let Some(g) = fx.to_gpu()
-                        {
-                            gpu_effects.push(g);
-                            continue;
-                        }
-                        // CPU fallback for this effect: run a single
-                        // `apply` and update the in-flight frame.
-                        if let Some(fx_frame) = super::effects::apply(&frame, fx) {
-                            frame = fx_frame;
-                        }
+                let mut cpu_effects: Vec<&Effect> = Vec::new();
+                for fx in &layer.effects {
+                    if !fx.enabled {
+                        continue;
+                    }
+                    if on_gpu_path
+                        && let Some(g) = fx.to_gpu()
+                    {
+                        gpu_effects.push(g);
+                        continue;
                     }
+                    cpu_effects.push(fx);
+                }
+                if !cpu_effects.is_empty() {
+                    frame = super::effects::cache::apply_cached(
+                        frame,
+                        &cpu_effects,
+                        frame_idx,
+                        effect_source.as_ref(),
+                    );
                 }
 
                 // Get layer transform attributes
-                let pos = layer.attrs.get_vec3(A_POSITION).unwrap_or([0.0, 0.0, 0.0]);
-                let rot = layer.attrs.get_vec3(A_ROTATION).unwrap_or([0.0, 0.0, 0.0]);
-                let scl = layer.attrs.get_vec3(A_SCALE).unwrap_or([1.0, 1.0, 1.0]);
-                let pvt = layer.attrs.get_vec3(A_PIVOT).unwrap_or([0.0, 0.0, 0.0]);
+                // (evaluated at this frame so keyframed motion animates)
+                let (pos, rot, scl) = layer.transform_at(frame_idx);
+                let pvt = layer.vec3_at(A_PIVOT, frame_idx, [0.0, 0.0, 0.0]);
                 // Convert rotation to radians (XYZ Euler angles)
                 let rot_rad = [
                     rot[0].to_radians(),
                     rot[1].to_radians(),
                     rot[2].to_radians(),
                 ];
+
+                // Depth of field: screen-space blur ramped by the layer's depth
+                // from the active camera (no per-pixel depth, so whole layers blur)
+                if let Some((cam, cam_pos, cam_rot)) = active_camera
+                    && cam.dof_enabled()
+                {
+                    let radius = cam.dof_radius(cam.depth_of(cam_pos, cam_rot, pos)) / proxy as f32;
+                    if radius >= 0.5 {
+                        let mut blur_attrs = Attrs::new();
+                        blur_attrs.set("radius", AttrValue::Float(radius));
+                        if let Some(blurred) = super::effects::blur::apply(&frame, &blur_attrs) {
+                            frame = blurred;
+                        }
+                    }
+                }
+
                 let src_size = (frame.width(), frame.height());
 
                 // Phase C: unified path for both CPU and GPU backends.
@@ -1395,7 +2168,7 @@ impl CompNode {
                 // CPU for the small fraction of comps with tilted
                 // layers, and avoids the per-pixel ray-march cost in
                 // the shader.
-                let canvas = self.dim();
+                let canvas = proxy_dim(self.dim(), proxy);
                 let layer_is_tilted_local = rot_rad[0] != 0.0 || rot_rad[1] != 0.0;
 
                 let needs_transform = !transform::is_identity(pos, rot_rad, scl, pvt)
@@ -1448,8 +2221,15 @@ impl CompNode {
                     } else {
                         // 2D path: compositor resamples raw src via
                         // canvas→src 3×3. Frame stays at src dimensions.
+                        // Proxy: pixel offsets shrink with the canvas, scale/rotation don't.
+                        let k = 1.0 / proxy as f32;
                         transform::build_inverse_canvas_to_src_3x3(
-                            pos, rot_rad[2], scl, pvt, canvas, src_size,
+                            [pos[0] * k, pos[1] * k, pos[2]],
+                            rot_rad[2],
+                            scl,
+                            [pvt[0] * k, pvt[1] * k, pvt[2]],
+                            canvas,
+                            src_size,
                         )
                     }
                 } else {
@@ -1486,6 +2266,11 @@ impl CompNode {
                 let opacity = layer.opacity();
                 let blend = layer.blend_mode();
 
+                if pixel_aspect.is_none() {
+                    let par = frame.pixel_aspect();
+                    pixel_aspect = ((par - 1.0).abs() > 1e-4).then_some(par);
+                }
+
                 source_frames.push(LayerPayload {
                     frame,
                     opacity,
@@ -1522,7 +2307,7 @@ impl CompNode {
         }
 
         // Use first visible layer's dimensions, fallback to comp dims
-        let dim = self.get_first_size().unwrap_or_else(|| self.dim());
+        let dim = proxy_dim(self.get_first_size().unwrap_or_else(|| self.dim()), proxy);
 
         // Promote frames to target format
         for layer in source_frames.iter_mut() {
@@ -1547,7 +2332,12 @@ impl CompNode {
         // ownership of `source_frames` never crossed threads, so Cpu blending reuses buffers without an
         // extra `Vec`. After a successful send, Ui-side `CompositorType::blend_with_dim` owns frames;
         // `Completed(None)` means there is nothing to blend back here (parity with GpuCompositor internals).
-        let result = if let Some(bridge) = ctx.gpu_blend_bridge {
+        // ROI: blend only the region on this worker. The clipped CPU blend is
+        // cheap and keeps the UI-thread GPU path out of partial renders.
+        let roi = self.roi().map(|r| r.downscale(proxy));
+        let result = if roi.is_some() {
+            THREAD_COMPOSITOR.with(|comp| comp.borrow().blend_clipped(source_frames, dim, roi))
+        } else if let Some(bridge) = ctx.gpu_blend_bridge {
             match bridge.delegate_blend_blocking(source_frames, dim) {
                 GpuBlendReport::Completed(Some(frame)) => Some(frame),
                 GpuBlendReport::Completed(None) => {
@@ -1578,11 +2368,21 @@ impl CompNode {
             stack.borrow_mut().remove(&my_uuid);
         });
 
-        // Mark incomplete if not all source frames loaded yet
+        // Mark failed if a source failed to load, incomplete if not all
+        // source frames loaded yet
         result.inspect(|frame| {
-            if !all_loaded {
+            if let Some(err) = &load_error {
+                let _ = frame.set_status(FrameStatus::Error);
+                frame.set_load_error(Some(err.clone()));
+            } else if !all_loaded {
                 let _ = frame.set_status(FrameStatus::Composing);
             }
+            if proxy > 1 {
+                frame.set_proxy_factor(proxy);
+            }
+            if let Some(par) = pixel_aspect {
+                frame.set_pixel_aspect(par);
+            }
         })
     }
 }
@@ -1618,11 +2418,21 @@ impl Node for CompNode {
             return None;
         }
 
+        // Camera / tilted layers resample in comp pixel units - compose those at full res
+        let full_ctx;
+        let ctx = if ctx.proxy != ProxyLevel::Full && !self.supports_proxy(frame_idx, ctx.media) {
+            full_ctx = ctx.with_proxy(ProxyLevel::Full);
+            &full_ctx
+        } else {
+            ctx
+        };
+        let cache_uuid = ctx.proxy.cache_uuid(self.uuid());
+
         // Check dirty: self, layers, or sources (recursive via is_dirty(Some(ctx)))
         let is_dirty = self.is_dirty(Some(ctx));
         // Check cache - if has Loaded frame and no dirty, return cached
         // If cached frame is Loading, recompute to check if sources are now Loaded
-        let cached_frame = ctx.cache.get(self.uuid(), frame_idx);
+        let cached_frame = ctx.cache.get(cache_uuid, frame_idx);
         let cache_is_loading = cached_frame
             .as_ref()
             .map(|f| f.status() != FrameStatus::Loaded)
@@ -1649,7 +2459,7 @@ impl Node for CompNode {
         let composed = self.compose_internal(frame_idx, ctx)?;
 
         // Cache result (even if Loading - will be replaced when sources finish)
-        ctx.cache.insert(self.uuid(), frame_idx, composed.clone());
+        ctx.cache.insert(cache_uuid, frame_idx, composed.clone());
 
         // Always clear dirty after compose - dirty means "attrs changed", not "frame loaded"
         // Frame status (Loading vs Loaded) is tracked separately via FrameStatus
@@ -1745,54 +2555,63 @@ impl Node for CompNode {
             return;
         };
         let uuid = self.uuid();
+        let proxy = ctx.proxy;
+        let active = workers.is_active_comp(uuid);
         let enqueue_compute = |frame_idx: i32| {
             let cache = std::sync::Arc::clone(cache_arc);
             let media = std::sync::Arc::clone(media_arc);
             let epoch = ctx.epoch;
 
-            workers.execute_with_epoch(
-                epoch,
-                Box::new(move || {
-                    // Check status in worker thread (not UI)
-                    if let Some(status) = cache.get_status(uuid, frame_idx)
-                        && matches!(status, FrameStatus::Loaded | FrameStatus::Loading)
-                    {
-                        return;
-                    }
+            let job = Box::new(move || {
+                // Check status in worker thread (not UI)
+                if let Some(status) = cache.get_status(proxy.cache_uuid(uuid), frame_idx)
+                    && matches!(status, FrameStatus::Loaded | FrameStatus::Loading)
+                {
+                    return;
+                }
 
-                    // CRITICAL: Take snapshot and release lock immediately!
-                    // Without this, workers hold read lock during compute (50-500ms),
-                    // blocking UI thread from acquiring write lock → jank.
-                    //
-                    // Snapshot clones HashMap structure + Arc refcounts (microseconds).
-                    // Actual NodeKind data is NOT copied - Arc provides shared ownership.
-                    let media_snapshot: std::collections::HashMap<
-                        uuid::Uuid,
-                        std::sync::Arc<super::node_kind::NodeKind>,
-                    > = {
-                        let guard = media.read().expect("media lock");
-                        guard.clone() // Clone HashMap of Arcs, not the nodes themselves
-                    }; // Lock released here - UI can proceed!
-
-                    let Some(node_arc) = media_snapshot.get(&uuid) else {
-                        return;
-                    };
-                    let Some(comp) = node_arc.as_comp() else {
-                        return;
-                    };
-
-                    let compute_ctx = ComputeContext {
-                        cache: cache.as_ref(),
-                        cache_arc: None, // Not needed for nested compute
-                        media: &media_snapshot,
-                        media_arc: None,
-                        workers: None,
-                        epoch,
-                        gpu_blend_bridge: None,
-                    };
-                    comp.compute(frame_idx, &compute_ctx);
-                }),
-            );
+                // CRITICAL: Take snapshot and release lock immediately!
+                // Without this, workers hold read lock during compute (50-500ms),
+                // blocking UI thread from acquiring write lock → jank.
+                //
+                // Snapshot clones HashMap structure + Arc refcounts (microseconds).
+                // Actual NodeKind data is NOT copied - Arc provides shared ownership.
+                let media_snapshot: std::collections::HashMap<
+                    uuid::Uuid,
+                    std::sync::Arc<super::node_kind::NodeKind>,
+                > = {
+                    let guard = media.read().expect("media lock");
+                    guard.clone() // Clone HashMap of Arcs, not the nodes themselves
+                }; // Lock released here - UI can proceed!
+
+                let Some(node_arc) = media_snapshot.get(&uuid) else {
+                    return;
+                };
+                let Some(comp) = node_arc.as_comp() else {
+                    return;
+                };
+
+                let compute_ctx = ComputeContext {
+                    cache: cache.as_ref(),
+                    cache_arc: None, // Not needed for nested compute
+                    media: &media_snapshot,
+                    media_arc: None,
+                    workers: None,
+                    epoch,
+                    gpu_blend_bridge: None,
+                    proxy,
+                };
+                comp.compute(frame_idx, &compute_ctx);
+            });
+            // The displayed frame jumps the queue; the spiral fills in behind it.
+            // Other comps (thumbnails, next in sequence) wait for the active one.
+            if !active {
+                workers.execute_with_epoch_background(epoch, job);
+            } else if frame_idx == center {
+                workers.execute_with_epoch_priority(epoch, job);
+            } else {
+                workers.execute_with_epoch(epoch, job);
+            }
         };
 
         // Spiral from center up to radius
@@ -1896,14 +2715,32 @@ impl CompNode {
             workers: Some(workers),
             epoch,
             gpu_blend_bridge,
+            proxy: project.proxy_level(),
         };
 
-        self.preload(center, radius, &ctx);
+        // Prefs cap (memory-bound machines) applies to every preload
+        self.preload(center, project.capped_preload_radius(radius), &ctx);
     }
 }
 
 // --- Helpers ---
 
+/// Write one layer attr at `frame`: keyed channels (or `record` ones) set a
+/// key, the rest the static value.
+fn set_attr_at(attrs: &mut Attrs, key: &str, frame: i32, value: AttrValue, record: bool) {
+    if record || !keyframe::keys(attrs, key).is_empty() {
+        keyframe::set_key(attrs, key, frame, value);
+    } else {
+        attrs.set(key, value);
+    }
+}
+
+/// Frame size at proxy `factor` (matches [`Frame::downscale`] rounding).
+fn proxy_dim((w, h): (usize, usize), factor: usize) -> (usize, usize) {
+    let f = factor.max(1);
+    (w.div_ceil(f).max(1), h.div_ceil(f).max(1))
+}
+
 fn promote_frame(frame: &Frame, target: PixelFormat) -> Frame {
     match (frame.pixel_format(), target) {
         (PixelFormat::Rgba8, PixelFormat::Rgba8)
@@ -1984,6 +2821,189 @@ mod tests {
         assert!(node.layers.is_empty());
     }
 
+    #[test]
+    fn test_comp_annotations() {
+        use crate::entities::annotation::AnnotationKind;
+        let mut comp = CompNode::new("Test", 0, 100, 24.0);
+        assert!(comp.annotations_at(5).is_empty());
+
+        let stroke =
+            |frame| Annotation::new(frame, AnnotationKind::Freehand).with_points(vec![[1.0, 2.0]]);
+        comp.add_annotation(stroke(5));
+        comp.add_annotation(stroke(5).with_size(9.0));
+        comp.add_annotation(stroke(8));
+        assert_eq!(comp.annotations_at(5).len(), 2);
+        assert_eq!(comp.annotations_at(8).len(), 1);
+
+        assert!(comp.pop_annotation(5));
+        assert_eq!(comp.annotations_at(5), vec![stroke(5)]);
+        assert!(!comp.pop_annotation(6));
+
+        assert_eq!(comp.clear_annotations(Some(8)), 1);
+        assert_eq!(comp.annotations().len(), 1);
+        assert_eq!(comp.clear_annotations(None), 1);
+        assert!(comp.annotations().is_empty());
+    }
+
+    #[test]
+    fn test_comp_markers() {
+        let mut comp = CompNode::new("Markers", 0, 100, 24.0);
+        assert!(comp.markers().is_empty());
+        assert_eq!(comp.next_marker(0), None);
+
+        comp.set_marker(Marker::new(40).with_label("b"));
+        comp.set_marker(Marker::new(10).with_label("a"));
+        comp.set_marker(Marker::new(40).with_label("b2"));
+        let frames: Vec<i32> = comp.markers().iter().map(|m| m.frame).collect();
+        assert_eq!(frames, vec![10, 40]);
+        assert_eq!(comp.marker_at(40).unwrap().label, "b2");
+
+        assert_eq!(comp.next_marker(10), Some(40));
+        assert_eq!(comp.next_marker(40), None);
+        assert_eq!(comp.prev_marker(40), Some(10));
+        assert_eq!(comp.prev_marker(5), None);
+
+        assert!(comp.remove_marker(10));
+        assert!(!comp.remove_marker(10));
+        assert_eq!(comp.markers().len(), 1);
+    }
+
+    #[test]
+    fn test_comp_roi_clips_blend() {
+        use super::super::frame::PixelRect;
+
+        let mut comp = CompNode::new("ROI", 0, 10, 24.0);
+        assert_eq!(comp.roi(), None);
+        comp.attrs.clear_dirty();
+        let rect = PixelRect::new(1, 1, 2, 1);
+        comp.set_roi(Some(rect));
+        assert_eq!(comp.roi(), Some(rect));
+        assert!(comp.attrs.is_dirty());
+        comp.attrs.clear_dirty();
+        comp.set_roi(None);
+        assert_eq!(comp.roi(), None);
+        assert!(comp.attrs.is_dirty());
+
+        // White layer over the black base: only the ROI turns white
+        let layers = vec![
+            LayerPayload::pre_rendered(
+                create_base_frame((4, 3), PixelFormat::Rgba8),
+                1.0,
+                BlendMode::Normal,
+            ),
+            LayerPayload::pre_rendered(
+                Frame::from_u8_buffer(vec![255; 4 * 3 * 4], 4, 3),
+                1.0,
+                BlendMode::Normal,
+            ),
+        ];
+        let out = CpuCompositor
+            .blend_clipped(layers, (4, 3), Some(rect))
+            .unwrap();
+        let PixelBuffer::U8(px) = &*out.buffer() else {
+            panic!("expected U8 buffer");
+        };
+        let white: Vec<usize> = (0..12).filter(|i| px[i * 4] == 255).collect();
+        assert_eq!(white, [5, 6]);
+    }
+
+    #[test]
+    fn test_mark_play_range() {
+        let mut comp = CompNode::new("Marks", 0, 100, 24.0);
+        comp.mark_play_start(20);
+        comp.mark_play_end(60);
+        assert_eq!(comp.play_range(true), (20, 60));
+        // Marking in past out resets out instead of inverting the range
+        comp.mark_play_start(80);
+        assert_eq!(comp.play_range(true), (80, 100));
+        comp.mark_play_end(10);
+        assert_eq!(comp.play_range(true), (0, 10));
+        comp.mark_play_end(500);
+        assert_eq!(comp.play_range(true), (0, 100));
+        comp.mark_play_start(30);
+        comp.clear_play_range();
+        assert_eq!(comp.play_range(true), (0, 100));
+    }
+
+    #[test]
+    fn test_child_edges_near() {
+        let mut comp = CompNode::new("Snap", 0, 200, 24.0);
+        let a = Layer::new(Uuid::new_v4(), "A", 0, 50, (64, 64));
+        let b = Layer::new(Uuid::new_v4(), "B", 60, 40, (64, 64));
+        let a_uuid = a.uuid();
+        comp.add_layer(a, None);
+        comp.add_layer(b, None);
+
+        // Boundaries: A = [0, 50), B = [60, 100); nearest first
+        assert_eq!(comp.get_child_edges_near(55, 5, &[]), vec![50, 60]);
+        assert_eq!(comp.get_child_edges_near(58, 10, &[]), vec![60, 50]);
+        assert_eq!(comp.get_child_edges_near(58, 10, &[a_uuid]), vec![60]);
+        assert!(comp.get_child_edges_near(150, 10, &[]).is_empty());
+    }
+
+    #[test]
+    fn test_split_child() {
+        let mut comp = CompNode::new("Split", 0, 200, 24.0);
+        let mut layer = Layer::new(Uuid::new_v4(), "L", 10, 50, (64, 64));
+        layer.attrs.set(A_TRIM_IN, AttrValue::Int(5));
+        comp.add_layer(layer, None);
+
+        // Out of bounds: before start, and at the last frame (empty tail)
+        assert!(comp.split_child(0, 14).is_none());
+        assert!(comp.split_child(0, 59).is_none());
+        assert!(comp.split_child(3, 20).is_none());
+
+        let tail_uuid = comp.split_child(0, 30).unwrap();
+        assert_eq!(comp.layers.len(), 2);
+        let tail = &comp.layers[0].attrs;
+        let head = &comp.layers[1].attrs;
+        assert_eq!(comp.layers[0].uuid(), tail_uuid);
+        assert_eq!((head.layer_start(), head.layer_end()), (15, 30));
+        assert_eq!((tail.layer_start(), tail.layer_end()), (31, 59));
+        // Same source mapping: in and speed untouched
+        assert_eq!(head.get_i32(A_IN), tail.get_i32(A_IN));
+        assert_eq!(tail.get_i32(A_TRIM_IN), Some(21));
+        assert_eq!(head.get_i32(A_TRIM_OUT), Some(29));
+    }
+
+    #[test]
+    fn test_ripple_delete_and_trim() {
+        let mut comp = CompNode::new("Ripple", 0, 200, 24.0);
+        // A [0,20) B [20,50) C [60,70); D [10,40) overlaps A.
+        // E shows [75,95) but its full bar starts at 65
+        let mut add = |name: &str, start: i32, duration: i32, trim_in: i32| {
+            let mut layer = Layer::new(Uuid::new_v4(), name, start, duration, (64, 64));
+            layer.attrs.set(A_TRIM_IN, AttrValue::Int(trim_in));
+            let uuid = layer.uuid();
+            comp.add_layer(layer, None);
+            uuid
+        };
+        let a = add("A", 0, 20, 0);
+        let b = add("B", 20, 30, 0);
+        let c = add("C", 60, 10, 0);
+        let d = add("D", 10, 30, 0);
+        let e = add("E", 65, 30, 10);
+        let start_of = |comp: &CompNode, uuid| comp.get_layer(uuid).unwrap().attrs.layer_start();
+        let order = |comp: &CompNode| (0..comp.layers.len()).collect::<Vec<_>>();
+        assert_eq!(start_of(&comp, e), 75);
+
+        // Every track after A's bar ripples; D overlaps A and stays
+        assert_eq!(comp.ripple_delete(a, &order(&comp)), Some((0, 94)));
+        assert_eq!(start_of(&comp, b), 0);
+        assert_eq!(start_of(&comp, c), 40);
+        assert_eq!(start_of(&comp, d), 10);
+        assert_eq!(start_of(&comp, e), 55);
+
+        // Trim B's end from 29 to 19: C and E follow 10 frames earlier, D
+        // (still overlapping B) doesn't
+        assert_eq!(comp.ripple_trim_end(b, 19, &order(&comp)), Some((19, 74)));
+        assert_eq!(comp.get_layer(b).unwrap().attrs.layer_end(), 19);
+        assert_eq!(start_of(&comp, c), 30);
+        assert_eq!(start_of(&comp, d), 10);
+        assert_eq!(start_of(&comp, e), 45);
+        assert!(comp.ripple_delete(Uuid::new_v4(), &order(&comp)).is_none());
+    }
+
     #[test]
     fn test_layer_creation() {
         let source_uuid = Uuid::new_v4();
@@ -2010,6 +3030,18 @@ mod tests {
         assert_eq!(layer.mask_ref_uuid(), None);
     }
 
+    #[test]
+    fn layer_mask_shape_off_by_default() {
+        let mut layer = Layer::new(Uuid::new_v4(), "L", 0, 50, (1920, 1080));
+        assert_eq!(layer.mask_at(0), None);
+        layer
+            .attrs
+            .set(A_MASK_SHAPE, AttrValue::Str("rectangle".to_string()));
+        let mask = layer.mask_at(0).expect("mask enabled");
+        assert_eq!(mask.rect, [0.0, 0.0, 1.0, 1.0]);
+        assert!(!mask.invert);
+    }
+
     #[test]
     fn layer_json_without_mask_ref_loads_clean() {
         // Frozen back-compat: a legacy playa.json save with no
@@ -2110,6 +3142,299 @@ mod tests {
         assert!(node.layers.is_empty());
     }
 
+    #[test]
+    fn test_set_child_attrs_at_keys_transform() {
+        let mut comp = CompNode::new("Keys", 0, 100, 24.0);
+        let layer = Layer::new(Uuid::new_v4(), "Layer 1", 0, 50, (1920, 1080));
+        let layer_uuid = layer.uuid();
+        comp.add_layer(layer, None);
+
+        // Unkeyed edit sets the static value
+        let pos = |x: f32| vec![(A_POSITION, AttrValue::Vec3([x, 0.0, 0.0]))];
+        comp.set_child_attrs_at(layer_uuid, 0, pos(5.0), &[]);
+        let layer = comp.get_layer(layer_uuid).unwrap();
+        assert_eq!(layer.transform_at(30).0, [5.0, 0.0, 0.0]);
+
+        // Recorded keys animate; later edits on the keyed channel update keys
+        comp.set_child_attrs_at(layer_uuid, 0, pos(0.0), &[A_POSITION]);
+        comp.set_child_attrs_at(layer_uuid, 10, pos(100.0), &[]);
+        let layer = comp.get_layer(layer_uuid).unwrap();
+        assert_eq!(layer.transform_at(5).0, [50.0, 0.0, 0.0]);
+        assert_eq!(layer.transform_at(20).0, [100.0, 0.0, 0.0]);
+        // Unkeyed channels keep their static defaults
+        assert_eq!(layer.transform_at(5).2, [1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_time_remap_flat_segment_freezes() {
+        let mut comp = CompNode::new("Remap", 0, 100, 24.0);
+        let layer = Layer::new(Uuid::new_v4(), "Layer 1", 10, 50, (64, 64));
+        let layer_uuid = layer.uuid();
+        comp.add_layer(layer, None);
+        assert_eq!(comp.get_layer(layer_uuid).unwrap().parent_to_local(20), 10);
+
+        // Ramp 0..10 over comp 10..20, hold source 10 until 30, then 2x speed
+        let remap = |frame: f32| vec![(A_REMAP_FRAME, AttrValue::Float(frame))];
+        for (frame, source) in [(10, 0.0), (20, 10.0), (30, 10.0), (40, 30.0)] {
+            comp.set_child_attrs_at(layer_uuid, frame, remap(source), &[A_REMAP_FRAME]);
+        }
+        // Curve is ignored until remapping is switched on
+        assert_eq!(comp.get_layer(layer_uuid).unwrap().parent_to_local(25), 15);
+        comp.set_child_attrs(layer_uuid, vec![(A_TIME_REMAP, AttrValue::Bool(true))]);
+
+        let layer = comp.get_layer(layer_uuid).unwrap();
+        assert_eq!(layer.parent_to_local(15), 5);
+        // Flat segment: every comp frame maps to the same source frame
+        for frame in 20..=30 {
+            assert_eq!(layer.parent_to_local(frame), 10);
+        }
+        assert_eq!(layer.parent_to_local(35), 20);
+        assert!((layer.parent_to_local_frac(36) - 22.0).abs() < 1e-6);
+        // Held past the last key
+        assert_eq!(layer.parent_to_local(50), 30);
+    }
+
+    #[test]
+    fn test_time_remap_ease_ramps_speed() {
+        let mut comp = CompNode::new("Remap", 0, 100, 24.0);
+        let layer = Layer::new(Uuid::new_v4(), "Layer 1", 0, 50, (64, 64));
+        let layer_uuid = layer.uuid();
+        comp.add_layer(layer, None);
+        for (frame, source) in [(0, 0.0), (20, 20.0)] {
+            comp.set_child_attrs_at(
+                layer_uuid,
+                frame,
+                vec![(A_REMAP_FRAME, AttrValue::Float(source))],
+                &[A_REMAP_FRAME],
+            );
+        }
+        comp.set_child_attrs(layer_uuid, vec![(A_TIME_REMAP, AttrValue::Bool(true))]);
+        let linear = |f: i32| comp.get_layer(layer_uuid).unwrap().parent_to_local_frac(f);
+        assert!((linear(5) - 5.0).abs() < 1e-6);
+
+        comp.set_child_attrs(layer_uuid, vec![(A_REMAP_EASE, AttrValue::Bool(true))]);
+        let layer = comp.get_layer(layer_uuid).unwrap();
+        let eased = |f: i32| layer.parent_to_local_frac(f);
+        // Slow out of the first key, fastest mid-segment, slow into the last
+        assert!(eased(1) < 0.5);
+        assert!((eased(10) - 10.0).abs() < 1e-5);
+        assert!(eased(11) - eased(10) > 1.0);
+        assert!(eased(20) - eased(19) < 0.5);
+        assert_eq!(layer.parent_to_local(20), 20);
+    }
+
+    #[test]
+    fn test_source_path_at_topmost_file() {
+        use super::super::file_node::FileNode;
+        use super::super::node_kind::NodeKind;
+        use std::path::PathBuf;
+        use std::sync::Arc;
+
+        let plate = FileNode::new("/shots/plate.*.exr".to_string(), 1001, 1100, 24.0);
+        let fg = FileNode::new("/shots/fg.*.exr".to_string(), 1, 20, 24.0);
+        let (plate_uuid, fg_uuid) = (plate.uuid(), fg.uuid());
+        let media: std::collections::HashMap<Uuid, Arc<NodeKind>> = [
+            (plate_uuid, Arc::new(NodeKind::File(plate))),
+            (fg_uuid, Arc::new(NodeKind::File(fg))),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut comp = CompNode::new("Comp", 0, 100, 24.0);
+        comp.add_layer(Layer::new(plate_uuid, "plate", 0, 100, (64, 64)), None);
+        let fg_layer = Layer::new(fg_uuid, "fg", 10, 20, (64, 64));
+        let fg_layer_uuid = fg_layer.uuid();
+        comp.add_layer(fg_layer, Some(0));
+
+        let path = |comp: &CompNode, frame| comp.source_path_at(frame, &media);
+        assert_eq!(path(&comp, 5), Some(PathBuf::from("/shots/plate.1006.exr")));
+        // Layer 0 is on top while it covers the frame
+        assert_eq!(path(&comp, 12), Some(PathBuf::from("/shots/fg.0003.exr")));
+        comp.set_child_attrs(fg_layer_uuid, vec![(A_VISIBLE, AttrValue::Bool(false))]);
+        assert_eq!(
+            path(&comp, 12),
+            Some(PathBuf::from("/shots/plate.1013.exr"))
+        );
+        assert_eq!(path(&comp, 200), None);
+    }
+
+    #[test]
+    fn test_solo_mute_precedence() {
+        use super::super::file_node::FileNode;
+        use super::super::node_kind::NodeKind;
+        use std::path::PathBuf;
+        use std::sync::Arc;
+
+        let plate = FileNode::new("/shots/plate.*.exr".to_string(), 1001, 1100, 24.0);
+        let fg = FileNode::new("/shots/fg.*.exr".to_string(), 1, 20, 24.0);
+        let (plate_uuid, fg_uuid) = (plate.uuid(), fg.uuid());
+        let media: std::collections::HashMap<Uuid, Arc<NodeKind>> = [
+            (plate_uuid, Arc::new(NodeKind::File(plate))),
+            (fg_uuid, Arc::new(NodeKind::File(fg))),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut comp = CompNode::new("Comp", 0, 100, 24.0);
+        let plate_layer = Layer::new(plate_uuid, "plate", 0, 100, (64, 64));
+        let plate_layer_uuid = plate_layer.uuid();
+        comp.add_layer(plate_layer, None);
+        let fg_layer = Layer::new(fg_uuid, "fg", 10, 20, (64, 64));
+        let fg_layer_uuid = fg_layer.uuid();
+        comp.add_layer(fg_layer, Some(0));
+        let path = |comp: &CompNode| comp.source_path_at(12, &media);
+        let set = |comp: &mut CompNode, uuid, key, on| {
+            comp.clear_dirty();
+            comp.set_child_attrs(uuid, vec![(key, AttrValue::Bool(on))]);
+            // Solo / mute feed the DAG: the comp must recomposite
+            assert!(comp.is_dirty(None));
+        };
+        assert_eq!(path(&comp), Some(PathBuf::from("/shots/fg.0003.exr")));
+
+        // Soloing the plate drops the visible fg above it
+        set(&mut comp, plate_layer_uuid, A_SOLO, true);
+        assert!(comp.has_solo());
+        assert!(!comp.get_layer(fg_layer_uuid).unwrap().is_active(true));
+        assert_eq!(path(&comp), Some(PathBuf::from("/shots/plate.1013.exr")));
+        // Solo doesn't resurrect a hidden layer
+        set(&mut comp, plate_layer_uuid, A_VISIBLE, false);
+        assert_eq!(path(&comp), None);
+        set(&mut comp, plate_layer_uuid, A_VISIBLE, true);
+        set(&mut comp, plate_layer_uuid, A_SOLO, false);
+
+        // Muted layers skip; mute wins over solo
+        set(&mut comp, fg_layer_uuid, A_MUTE, true);
+        assert_eq!(path(&comp), Some(PathBuf::from("/shots/plate.1013.exr")));
+        set(&mut comp, fg_layer_uuid, A_SOLO, true);
+        assert_eq!(path(&comp), None);
+    }
+
+    #[test]
+    fn test_locked_layer_ignores_timeline_edits() {
+        let mut comp = CompNode::new("Comp", 0, 100, 24.0);
+        let layer = Layer::new(Uuid::new_v4(), "locked", 10, 50, (64, 64));
+        let uuid = layer.uuid();
+        comp.add_layer(layer, None);
+        comp.set_child_attrs(uuid, vec![(A_LOCKED, AttrValue::Bool(true))]);
+        let range = |comp: &CompNode| {
+            let l = comp.get_layer(uuid).unwrap();
+            (l.attrs.layer_start(), l.attrs.layer_end())
+        };
+        let before = range(&comp);
+
+        comp.move_layers(&[uuid], 5);
+        comp.trim_layers(&[uuid], "in", 3);
+        assert!(comp.move_child(0, 0).is_err());
+        assert!(comp.set_child_end(0, 20).is_err());
+        assert!(comp.split_child(0, 30).is_none());
+        assert!(comp.ripple_delete(uuid, &[0]).is_none());
+        assert!(comp.remove_child(uuid).is_none());
+        assert_eq!(range(&comp), before);
+        assert_eq!(comp.layers.len(), 1);
+
+        // Unlocked again, edits apply
+        comp.set_child_attrs(uuid, vec![(A_LOCKED, AttrValue::Bool(false))]);
+        comp.move_layers(&[uuid], 5);
+        assert_eq!(range(&comp).0, before.0 + 5);
+    }
+
+    #[test]
+    fn test_set_layers_attrs_batch() {
+        let mut comp = CompNode::new("Comp", 0, 100, 24.0);
+        let layers: Vec<Uuid> = (0..3)
+            .map(|i| {
+                let layer = Layer::new(Uuid::new_v4(), &format!("l{i}"), 0, 100, (64, 64));
+                let uuid = layer.uuid();
+                comp.add_layer(layer, None);
+                uuid
+            })
+            .collect();
+        // Start from mixed blend modes
+        comp.set_child_attrs(
+            layers[0],
+            vec![(A_BLEND_MODE, AttrValue::Str("screen".to_string()))],
+        );
+        comp.clear_dirty();
+
+        // Two of three selected, plus a stale uuid that is skipped
+        let selected = [layers[0], layers[2], Uuid::new_v4()];
+        let found = comp.set_layers_attrs(
+            &selected,
+            &[
+                (A_BLEND_MODE.to_string(), AttrValue::Str("multiply".to_string())),
+                (A_OPACITY.to_string(), AttrValue::Float(0.5)),
+            ],
+        );
+        assert_eq!(found, 2);
+        assert!(comp.is_dirty(None));
+        for uuid in [layers[0], layers[2]] {
+            let layer = comp.get_layer(uuid).unwrap();
+            assert_eq!(layer.blend_mode(), BlendMode::Multiply);
+            assert_eq!(layer.opacity(), 0.5);
+        }
+        let untouched = comp.get_layer(layers[1]).unwrap();
+        assert_eq!(untouched.blend_mode(), BlendMode::Normal);
+        assert_eq!(untouched.opacity(), 1.0);
+    }
+
+    #[test]
+    fn test_set_layers_attrs_updates_keys_at_current_frame() {
+        let mut comp = CompNode::new("Comp", 0, 100, 24.0);
+        let layer = Layer::new(Uuid::new_v4(), "l", 0, 100, (64, 64));
+        let uuid = layer.uuid();
+        comp.add_layer(layer, None);
+        let pos = |x: f32| vec![(A_POSITION, AttrValue::Vec3([x, 0.0, 0.0]))];
+        comp.set_child_attrs_at(uuid, 0, pos(0.0), &[A_POSITION]);
+        comp.set_child_attrs_at(uuid, 20, pos(100.0), &[A_POSITION]);
+
+        // Attribute Editor / REST edit at frame 10: the keyed channel gets a
+        // key there, the unkeyed one a static value
+        comp.set_frame(10);
+        comp.set_layers_attrs(
+            &[uuid],
+            &[
+                (A_POSITION.to_string(), AttrValue::Vec3([30.0, 0.0, 0.0])),
+                (A_OPACITY.to_string(), AttrValue::Float(0.5)),
+            ],
+        );
+        let layer = comp.get_layer(uuid).unwrap();
+        assert_eq!(keyframe::keys(&layer.attrs, A_POSITION).len(), 3);
+        assert_eq!(layer.transform_at(10).0, [30.0, 0.0, 0.0]);
+        assert_eq!(layer.transform_at(20).0, [100.0, 0.0, 0.0]);
+        assert!(keyframe::keys(&layer.attrs, A_OPACITY).is_empty());
+        assert_eq!(layer.opacity(), 0.5);
+    }
+
+    #[test]
+    fn test_duplicate_layer_is_independent() {
+        let mut comp = CompNode::new("Comp", 0, 100, 24.0);
+        let source = Uuid::new_v4();
+        let mut layer = Layer::new(source, "bg", 10, 50, (64, 64));
+        layer
+            .effects
+            .push(Effect::new(super::super::effects::EffectType::GaussianBlur));
+        let orig = layer.uuid();
+        comp.add_layer(layer, None);
+        comp.add_layer(Layer::new(Uuid::new_v4(), "fg", 0, 100, (64, 64)), None);
+
+        let copy = comp.duplicate_layer(orig, "bg_2").unwrap();
+        assert_ne!(copy, orig);
+        // Copy takes the original's row, directly above it
+        assert_eq!(comp.uuid_to_idx(copy), Some(0));
+        assert_eq!(comp.uuid_to_idx(orig), Some(1));
+        let (a, b) = (comp.get_layer(orig).unwrap(), comp.get_layer(copy).unwrap());
+        assert_eq!(b.source_uuid(), source);
+        assert_eq!((b.start(), b.end()), (a.start(), a.end()));
+        assert_eq!(b.attrs.get_str(A_NAME), Some("bg_2"));
+        assert_eq!(b.effects.len(), 1);
+        assert_ne!(b.effects[0].uuid, a.effects[0].uuid);
+
+        comp.set_child_attrs(copy, vec![(A_OPACITY, AttrValue::Float(0.25))]);
+        assert_eq!(comp.get_layer(copy).unwrap().opacity(), 0.25);
+        assert_eq!(comp.get_layer(orig).unwrap().opacity(), 1.0);
+        assert_eq!(comp.duplicate_layer(Uuid::new_v4(), "x"), None);
+    }
+
     #[test]
     fn test_node_trait() {
         let node = CompNode::new("Test", 0, 100, 24.0);
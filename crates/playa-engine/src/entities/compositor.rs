@@ -28,11 +28,11 @@
 //! **Still asymmetric:** the Cpu path ignores the matrix bundle — transforms are baked into pixels
 //! earlier in compose. Skipping Cpu transform there while feeding raw mats only to Gpu is future work.
 
-use crate::entities::frame::{Frame, FrameStatus, PixelBuffer, PixelFormat};
+use crate::entities::frame::{Frame, FrameStatus, PixelBuffer, PixelFormat, PixelRect};
 use crate::render_gpu::WgpuCompositor;
 
 /// Supported blend modes for layer compositing.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum BlendMode {
     Normal,
     Screen,
@@ -44,6 +44,57 @@ pub enum BlendMode {
     Overlay,
 }
 
+impl BlendMode {
+    /// All modes in UI order (matches the `blend_mode` attr schema)
+    pub const ALL: [BlendMode; 8] = [
+        BlendMode::Normal,
+        BlendMode::Screen,
+        BlendMode::Add,
+        BlendMode::Subtract,
+        BlendMode::Multiply,
+        BlendMode::Divide,
+        BlendMode::Difference,
+        BlendMode::Overlay,
+    ];
+
+    /// `blend_mode` attr value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BlendMode::Normal => "normal",
+            BlendMode::Screen => "screen",
+            BlendMode::Add => "add",
+            BlendMode::Subtract => "subtract",
+            BlendMode::Multiply => "multiply",
+            BlendMode::Divide => "divide",
+            BlendMode::Difference => "difference",
+            BlendMode::Overlay => "overlay",
+        }
+    }
+
+    /// Parse a `blend_mode` attr value; unknown strings fall back to Normal
+    pub fn from_attr(s: &str) -> Self {
+        Self::ALL
+            .into_iter()
+            .find(|m| m.as_str() == s)
+            .unwrap_or(BlendMode::Normal)
+    }
+
+    /// Map a Photoshop blend mode key (`norm`, `mul `, ...); `None` if unsupported
+    pub fn from_psd_key(key: &str) -> Option<Self> {
+        match key {
+            "norm" | "pass" => Some(BlendMode::Normal),
+            "scrn" => Some(BlendMode::Screen),
+            "lddg" => Some(BlendMode::Add),
+            "fsub" => Some(BlendMode::Subtract),
+            "mul " => Some(BlendMode::Multiply),
+            "fdiv" => Some(BlendMode::Divide),
+            "diff" => Some(BlendMode::Difference),
+            "over" => Some(BlendMode::Overlay),
+            _ => None,
+        }
+    }
+}
+
 /// Compositor type enum - allows switching between CPU/GPU backends.
 /// Note: Clone creates CPU compositor (GPU resources can't be cloned)
 #[derive(Debug)]
@@ -404,17 +455,38 @@ impl CpuCompositor {
         &self,
         layers: Vec<LayerPayload>,
         dim: (usize, usize),
+    ) -> Option<Frame> {
+        self.blend_clipped(layers, dim, None)
+    }
+
+    /// [`Self::blend_with_dim`] restricted to `clip` (ROI rendering).
+    ///
+    /// Only pixels inside the rect are blended; outside it the canvas
+    /// keeps the base layer (the comp's black canvas). `None` blends
+    /// the whole canvas.
+    pub(crate) fn blend_clipped(
+        &self,
+        layers: Vec<LayerPayload>,
+        dim: (usize, usize),
+        clip: Option<PixelRect>,
     ) -> Option<Frame> {
         if layers.is_empty() {
             return None;
         }
+        // A rect fully off-canvas blends nothing over the base
+        let clip = match clip {
+            Some(rect) => rect
+                .clamp_to(dim.0, dim.1)
+                .unwrap_or(PixelRect::new(0, 0, 0, 0)),
+            None => PixelRect::new(0, 0, dim.0, dim.1),
+        };
         let needs_resample = layers
             .iter()
             .any(|l| l.inv_matrix != IDENTITY_TRANSFORM || l.camera_path.is_some());
         if needs_resample {
-            Self::blend_matrix_aware(layers, dim)
+            Self::blend_matrix_aware(layers, dim, clip)
         } else {
-            Self::blend_legacy_pre_rendered(layers, dim)
+            Self::blend_legacy_pre_rendered(layers, dim, clip)
         }
     }
 
@@ -423,6 +495,7 @@ impl CpuCompositor {
     fn blend_legacy_pre_rendered(
         layers: Vec<LayerPayload>,
         dim: (usize, usize),
+        clip: PixelRect,
     ) -> Option<Frame> {
         use log::trace;
         trace!(
@@ -492,8 +565,10 @@ impl CpuCompositor {
 
             let lw = layer_frame.width();
             let lh = layer_frame.height();
-            let overlap_w = width.min(lw);
-            let overlap_h = height.min(lh);
+            // Blend the layer/canvas overlap, limited to the clip rect
+            let (x0, y0) = (clip.x, clip.y);
+            let overlap_w = width.min(lw).min(clip.right()).saturating_sub(x0);
+            let overlap_h = height.min(lh).min(clip.bottom()).saturating_sub(y0);
             if overlap_w == 0 || overlap_h == 0 {
                 continue;
             }
@@ -502,9 +577,9 @@ impl CpuCompositor {
                 ($blend_fn:ident, $curr:expr, $layer:expr, $out:expr) => {{
                     let base_stride = width * 4;
                     let layer_stride = lw * 4;
-                    for y in 0..overlap_h {
-                        let b_off = y * base_stride;
-                        let l_off = y * layer_stride;
+                    for y in y0..y0 + overlap_h {
+                        let b_off = y * base_stride + x0 * 4;
+                        let l_off = y * layer_stride + x0 * 4;
                         let base_slice = &$curr[b_off..b_off + overlap_w * 4];
                         let layer_slice = &$layer[l_off..l_off + overlap_w * 4];
                         let out_slice = &mut $out[b_off..b_off + overlap_w * 4];
@@ -645,8 +720,13 @@ impl CpuCompositor {
     ///
     /// First layer is treated as REPLACE (mirrors the legacy path
     /// where source_frames[0] is the canvas-sized base — comp_node
-    /// always inserts a black canvas there).
-    fn blend_matrix_aware(layers: Vec<LayerPayload>, dim: (usize, usize)) -> Option<Frame> {
+    /// always inserts a black canvas there). Layers above it only
+    /// touch pixels inside `clip`.
+    fn blend_matrix_aware(
+        layers: Vec<LayerPayload>,
+        dim: (usize, usize),
+        clip: PixelRect,
+    ) -> Option<Frame> {
         use log::trace;
         use rayon::prelude::*;
         trace!(
@@ -689,8 +769,15 @@ impl CpuCompositor {
             acc.par_chunks_mut(width * 4)
                 .enumerate()
                 .for_each(|(y, row)| {
+                    let cols = if idx == 0 {
+                        0..width
+                    } else if (clip.y..clip.bottom()).contains(&y) {
+                        clip.x..clip.right()
+                    } else {
+                        return;
+                    };
                     let cy = y as f32 + 0.5;
-                    for x in 0..width {
+                    for x in cols {
                         let cx = x as f32 + 0.5;
                         let (sx, sy) =
                             Self::canvas_to_src_cpu(cx, cy, layer, canvas_w, canvas_h);
@@ -9,6 +9,10 @@
 //! 2. Horizontal pass: convolve each row with kernel
 //! 3. Vertical pass: convolve each column with kernel
 //!
+//! sRGB sources (8-bit, or anything tagged [`ColorSpace::Srgb`]) are
+//! linearized before convolving and re-encoded after; averaging encoded
+//! values darkens edges between bright and dark regions.
+//!
 //! # Usage
 //!
 //! ```ignore
@@ -19,7 +23,9 @@
 use half::f16 as F16;
 
 use crate::entities::attrs::Attrs;
-use crate::entities::frame::{Frame, PixelBuffer, PixelFormat};
+use crate::entities::frame::{
+    ColorSpace, Frame, PixelBuffer, PixelFormat, linear_to_srgb, srgb_to_linear,
+};
 
 /// Apply Gaussian blur effect to a frame.
 ///
@@ -40,29 +46,42 @@ pub fn apply(frame: &Frame, attrs: &Attrs) -> Option<Frame> {
     let (width, height) = frame.resolution();
     let buffer = frame.buffer();
 
-    // Convert to f32 for processing (unified pipeline)
-    let src_f32 = to_f32_buffer(&buffer, width, height);
+    // Convert to f32 for processing (unified pipeline), linear light
+    let space = frame.color_space();
+    let mut src_f32 = to_f32_buffer(&buffer, width, height);
+    if space == ColorSpace::Srgb {
+        map_rgb_in_place(&mut src_f32, srgb_to_linear);
+    }
 
     // Build Gaussian kernel
     let kernel = gaussian_kernel(radius);
 
     // Separable blur: horizontal then vertical pass
     let temp = convolve_axis(&src_f32, width, height, &kernel, true);
-    let result = convolve_axis(&temp, width, height, &kernel, false);
+    let mut result = convolve_axis(&temp, width, height, &kernel, false);
+    if space == ColorSpace::Srgb {
+        map_rgb_in_place(&mut result, linear_to_srgb);
+    }
 
     // Convert back to original format
     let out_buffer = from_f32_buffer(&result, frame.pixel_format(), width, height);
 
-    Some(Frame::from_buffer(
-        out_buffer,
-        frame.pixel_format(),
-        width,
-        height,
-    ))
+    let out = Frame::from_buffer(out_buffer, frame.pixel_format(), width, height);
+    out.set_color_space(space);
+    Some(out)
+}
+
+/// Apply a transfer function to the RGB channels of f32 RGBA (alpha untouched).
+pub(super) fn map_rgb_in_place(data: &mut [f32], f: fn(f32) -> f32) {
+    for px in data.chunks_exact_mut(4) {
+        for c in &mut px[..3] {
+            *c = f(*c);
+        }
+    }
 }
 
 /// Convert any PixelBuffer to f32 RGBA for processing.
-fn to_f32_buffer(buffer: &PixelBuffer, width: usize, height: usize) -> Vec<f32> {
+pub(super) fn to_f32_buffer(buffer: &PixelBuffer, width: usize, height: usize) -> Vec<f32> {
     let size = width * height * 4;
     let mut result = Vec::with_capacity(size);
 
@@ -86,7 +105,12 @@ fn to_f32_buffer(buffer: &PixelBuffer, width: usize, height: usize) -> Vec<f32>
 }
 
 /// Convert f32 buffer back to original pixel format.
-fn from_f32_buffer(data: &[f32], format: PixelFormat, width: usize, height: usize) -> PixelBuffer {
+pub(super) fn from_f32_buffer(
+    data: &[f32],
+    format: PixelFormat,
+    width: usize,
+    height: usize,
+) -> PixelBuffer {
     match format {
         PixelFormat::Rgba8 => {
             let mut result = Vec::with_capacity(width * height * 4);
@@ -215,4 +239,32 @@ mod tests {
         let result = apply(&frame, &attrs);
         assert!(result.is_some());
     }
+
+    #[test]
+    fn test_u8_edge_blurs_in_linear_light() {
+        // Black | white vertical edge, 8 px wide
+        let (w, h) = (8, 1);
+        let mut buf = Vec::with_capacity(w * h * 4);
+        for x in 0..w {
+            let v = if x < w / 2 { 0 } else { 255 };
+            buf.extend_from_slice(&[v, v, v, 255]);
+        }
+        let frame = Frame::from_u8_buffer(buf, w, h);
+        let mut attrs = Attrs::new();
+        attrs.set("radius", crate::entities::attrs::AttrValue::Float(8.0));
+
+        let out = apply(&frame, &attrs).unwrap();
+        assert_eq!(out.color_space(), ColorSpace::Srgb);
+        let PixelBuffer::U8(px) = out.buffer().as_ref().clone() else {
+            panic!("expected U8 buffer");
+        };
+        // Symmetric pair straddling the edge averages to linear 0.5, which is
+        // sRGB ~0.735 (not the 0.5 a naive sRGB-space blur gives)
+        let pair = [px[3 * 4], px[4 * 4]];
+        let mean =
+            (srgb_to_linear(pair[0] as f32 / 255.0) + srgb_to_linear(pair[1] as f32 / 255.0)) / 2.0;
+        assert!((mean - 0.5).abs() < 0.01, "mean {mean}");
+        assert!(px[3 * 4] > 128);
+        assert_eq!(px[3], 255);
+    }
 }
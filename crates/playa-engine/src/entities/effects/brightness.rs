@@ -14,11 +14,15 @@
 //! 2. Multiply by contrast factor (1.0 + contrast)
 //! 3. Add 0.5 back to restore center
 //! 4. Add brightness offset
+//!
+//! sRGB sources (8-bit, or anything tagged [`ColorSpace::Srgb`]) are
+//! linearized before the adjustment and re-encoded after, so the offset acts
+//! on light rather than on display-encoded values.
 
 use half::f16 as F16;
 
 use crate::entities::attrs::Attrs;
-use crate::entities::frame::{Frame, PixelBuffer};
+use crate::entities::frame::{ColorSpace, Frame, PixelBuffer, linear_to_srgb, srgb_to_linear};
 
 /// Apply brightness/contrast adjustment to a frame.
 ///
@@ -42,27 +46,27 @@ pub fn apply(frame: &Frame, attrs: &Attrs) -> Option<Frame> {
 
     let (width, height) = frame.resolution();
     let buffer = frame.buffer();
+    let space = frame.color_space();
+
+    // Apply: (v - 0.5) * cf + 0.5 + brightness, in linear light
+    let adjust = |v: f32| {
+        if space == ColorSpace::Srgb {
+            linear_to_srgb((srgb_to_linear(v) - 0.5) * cf + 0.5 + brightness)
+        } else {
+            (v - 0.5) * cf + 0.5 + brightness
+        }
+    };
 
     let out_buffer = match buffer.as_ref() {
         PixelBuffer::U8(data) => {
             let mut result = Vec::with_capacity(data.len());
 
             for chunk in data.chunks_exact(4) {
-                // Convert to 0-1 range for processing
-                let r = chunk[0] as f32 / 255.0;
-                let g = chunk[1] as f32 / 255.0;
-                let b = chunk[2] as f32 / 255.0;
-                let a = chunk[3]; // Alpha unchanged
-
-                // Apply: (v - 0.5) * cf + 0.5 + brightness
-                let r_out = ((r - 0.5) * cf + 0.5 + brightness).clamp(0.0, 1.0);
-                let g_out = ((g - 0.5) * cf + 0.5 + brightness).clamp(0.0, 1.0);
-                let b_out = ((b - 0.5) * cf + 0.5 + brightness).clamp(0.0, 1.0);
-
-                result.push((r_out * 255.0) as u8);
-                result.push((g_out * 255.0) as u8);
-                result.push((b_out * 255.0) as u8);
-                result.push(a);
+                for &v in &chunk[..3] {
+                    let out = adjust(v as f32 / 255.0).clamp(0.0, 1.0);
+                    result.push((out * 255.0).round() as u8);
+                }
+                result.push(chunk[3]); // Alpha unchanged
             }
 
             PixelBuffer::U8(result)
@@ -71,21 +75,12 @@ pub fn apply(frame: &Frame, attrs: &Attrs) -> Option<Frame> {
         PixelBuffer::F16(data) => {
             let mut result = Vec::with_capacity(data.len());
 
+            // No clamping for HDR - allow out-of-range
             for chunk in data.chunks_exact(4) {
-                let r = chunk[0].to_f32();
-                let g = chunk[1].to_f32();
-                let b = chunk[2].to_f32();
-                let a = chunk[3]; // Alpha unchanged
-
-                // Apply adjustment (no clamping for HDR - allow out-of-range)
-                let r_out = (r - 0.5) * cf + 0.5 + brightness;
-                let g_out = (g - 0.5) * cf + 0.5 + brightness;
-                let b_out = (b - 0.5) * cf + 0.5 + brightness;
-
-                result.push(F16::from_f32(r_out));
-                result.push(F16::from_f32(g_out));
-                result.push(F16::from_f32(b_out));
-                result.push(a);
+                for v in &chunk[..3] {
+                    result.push(F16::from_f32(adjust(v.to_f32())));
+                }
+                result.push(chunk[3]); // Alpha unchanged
             }
 
             PixelBuffer::F16(result)
@@ -94,33 +89,21 @@ pub fn apply(frame: &Frame, attrs: &Attrs) -> Option<Frame> {
         PixelBuffer::F32(data) => {
             let mut result = Vec::with_capacity(data.len());
 
+            // No clamping for HDR
             for chunk in data.chunks_exact(4) {
-                let r = chunk[0];
-                let g = chunk[1];
-                let b = chunk[2];
-                let a = chunk[3]; // Alpha unchanged
-
-                // Apply adjustment (no clamping for HDR)
-                let r_out = (r - 0.5) * cf + 0.5 + brightness;
-                let g_out = (g - 0.5) * cf + 0.5 + brightness;
-                let b_out = (b - 0.5) * cf + 0.5 + brightness;
-
-                result.push(r_out);
-                result.push(g_out);
-                result.push(b_out);
-                result.push(a);
+                for &v in &chunk[..3] {
+                    result.push(adjust(v));
+                }
+                result.push(chunk[3]); // Alpha unchanged
             }
 
             PixelBuffer::F32(result)
         }
     };
 
-    Some(Frame::from_buffer(
-        out_buffer,
-        frame.pixel_format(),
-        width,
-        height,
-    ))
+    let out = Frame::from_buffer(out_buffer, frame.pixel_format(), width, height);
+    out.set_color_space(space);
+    Some(out)
 }
 
 #[cfg(test)]
@@ -168,4 +151,36 @@ mod tests {
             assert!(data[0] > 200); // R should be brighter
         }
     }
+
+    #[test]
+    fn test_u8_brightness_in_linear_light() {
+        // sRGB 0.5 mid-gray is ~0.216 linear; +0.3 there re-encodes to ~0.75,
+        // not the 0.8 an offset on encoded values gives
+        let frame = Frame::from_u8_buffer(vec![128, 128, 128, 200], 1, 1);
+        let mut attrs = Attrs::new();
+        attrs.set("brightness", AttrValue::Float(0.3));
+        attrs.set("contrast", AttrValue::Float(0.0));
+
+        let out = apply(&frame, &attrs).unwrap();
+        assert_eq!(out.color_space(), ColorSpace::Srgb);
+        let PixelBuffer::U8(px) = out.buffer().as_ref().clone() else {
+            panic!("expected U8 buffer");
+        };
+        let expected = linear_to_srgb(srgb_to_linear(128.0 / 255.0) + 0.3);
+        assert_eq!(px[0], (expected * 255.0).round() as u8);
+        assert!(px[0] < 195);
+        assert_eq!(px[3], 200);
+
+        // Linear float frames are adjusted as-is
+        let frame = Frame::from_buffer(
+            PixelBuffer::F32(vec![0.25, 0.25, 0.25, 1.0]),
+            crate::entities::frame::PixelFormat::RgbaF32,
+            1,
+            1,
+        );
+        let PixelBuffer::F32(px) = apply(&frame, &attrs).unwrap().buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        assert!((px[0] - 0.55).abs() < 1e-6);
+    }
 }
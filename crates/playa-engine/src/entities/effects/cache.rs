@@ -0,0 +1,335 @@
+//! Effect-result cache: CPU effect stacks run once per source frame.
+//!
+//! Effect parameters aren't animated, so a static stack on a clip gives the
+//! same result every time the same source frame comes back. Compose hands
+//! [`apply_cached`] the source frame's identity ([`SourceKey`]) and the
+//! results are kept by `(source buffer, pre-effect prep, effect stack)`:
+//!
+//! - **source**: the decoded buffer the layer started from. Entries hold a
+//!   `Weak` to it, so the address can't be reused while the entry lives and a
+//!   re-decoded or edited source (new buffer) simply misses.
+//! - **prep**: whatever compose did to the frame before effects (proxy
+//!   downscale, mask), hashed by the caller.
+//! - **stack**: type + attrs of every CPU-path effect, so editing a parameter
+//!   misses too. Time-varying effects (grain) add the comp frame.
+//!
+//! Entries whose source buffer is gone are dropped on insert; the rest are
+//! evicted oldest-first past the budget. Once a project [`attach`]es its
+//! [`CacheManager`], the budget is [`EFFECT_CACHE_FRACTION`] of the configured
+//! memory limit and cached results count towards the manager's usage, so the
+//! frame cache makes room for them.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex, Weak};
+
+use log::trace;
+
+use super::{Effect, apply};
+use crate::core::cache_man::CacheManager;
+use crate::entities::frame::{Frame, FrameStatus, PixelBuffer};
+
+/// Share of the cache memory limit that effect results may use
+pub const EFFECT_CACHE_FRACTION: f64 = 0.125;
+
+/// Budget until a [`CacheManager`] is attached (tests, tools)
+const UNATTACHED_BUDGET: usize = 256 * 1024 * 1024;
+
+lazy_static::lazy_static! {
+    static ref RESULTS: Mutex<EffectCache> = Mutex::new(EffectCache::new(UNATTACHED_BUDGET));
+}
+
+/// Identity of the frame an effect stack starts from.
+pub struct SourceKey {
+    buffer: Weak<PixelBuffer>,
+    prep: u64,
+}
+
+impl SourceKey {
+    /// `source` is the frame as fetched from the source node; `prep` hashes
+    /// the per-layer processing applied before effects.
+    pub fn new(source: &Frame, prep: u64) -> Self {
+        Self {
+            buffer: Arc::downgrade(&source.buffer()),
+            prep,
+        }
+    }
+}
+
+/// (source buffer address, prep hash, stack hash)
+type Key = (usize, u64, u64);
+
+struct Entry {
+    /// Keeps the source address reserved; dead once the source is dropped
+    source: Weak<PixelBuffer>,
+    frame: Frame,
+    bytes: usize,
+}
+
+/// Effect results by source frame and stack, oldest first in `order`.
+pub struct EffectCache {
+    entries: HashMap<Key, Entry>,
+    order: VecDeque<Key>,
+    bytes: usize,
+    /// Budget while no manager is attached
+    budget: usize,
+    /// Sizes the budget and tracks our bytes once attached
+    manager: Weak<CacheManager>,
+}
+
+impl EffectCache {
+    pub fn new(budget: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            bytes: 0,
+            budget,
+            manager: Weak::new(),
+        }
+    }
+
+    /// Size the budget from `manager`'s limit and count cached bytes in its
+    /// usage (moved over from a previously attached manager).
+    pub fn attach(&mut self, manager: &Arc<CacheManager>) {
+        if let Some(old) = self.manager.upgrade() {
+            old.free_memory(self.bytes);
+        }
+        manager.add_memory(self.bytes);
+        self.manager = Arc::downgrade(manager);
+    }
+
+    /// Current budget in bytes (follows memory limit changes)
+    pub fn budget(&self) -> usize {
+        match self.manager.upgrade() {
+            Some(manager) => (manager.mem().1 as f64 * EFFECT_CACHE_FRACTION) as usize,
+            None => self.budget,
+        }
+    }
+
+    /// Account `bytes` leaving the cache
+    fn release(&mut self, bytes: usize) {
+        self.bytes -= bytes;
+        if let Some(manager) = self.manager.upgrade() {
+            manager.free_memory(bytes);
+        }
+    }
+
+    fn get(&self, key: &Key) -> Option<Frame> {
+        self.entries
+            .get(key)
+            .filter(|e| e.source.strong_count() > 0)
+            .map(|e| e.frame.clone())
+    }
+
+    fn insert(&mut self, key: Key, source: Weak<PixelBuffer>, frame: Frame) {
+        let bytes = frame.mem();
+        let budget = self.budget();
+        if bytes > budget {
+            return;
+        }
+        self.remove(&key);
+        // Results of dropped sources can never hit again
+        let dead: Vec<Key> = self
+            .entries
+            .iter()
+            .filter(|(_, e)| e.source.strong_count() == 0)
+            .map(|(k, _)| *k)
+            .collect();
+        for k in dead {
+            self.remove(&k);
+        }
+        while self.bytes + bytes > budget {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(e) = self.entries.remove(&oldest) {
+                self.release(e.bytes);
+            }
+        }
+        self.bytes += bytes;
+        if let Some(manager) = self.manager.upgrade() {
+            manager.add_memory(bytes);
+        }
+        self.order.push_back(key);
+        self.entries.insert(
+            key,
+            Entry {
+                source,
+                frame,
+                bytes,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &Key) {
+        if let Some(e) = self.entries.remove(key) {
+            self.release(e.bytes);
+            self.order.retain(|k| k != key);
+        }
+    }
+
+    /// Cached results and their size in bytes
+    pub fn usage(&self) -> (usize, usize) {
+        (self.entries.len(), self.bytes)
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.release(self.bytes);
+    }
+}
+
+/// Hash of an effect stack; includes `frame_idx` when any effect varies
+/// over time.
+pub fn stack_hash(effects: &[&Effect], frame_idx: i32) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for fx in effects {
+        std::mem::discriminant(&fx.effect_type).hash(&mut hasher);
+        fx.enabled.hash(&mut hasher);
+        fx.attrs.hash_all().hash(&mut hasher);
+        if fx.effect_type.is_time_varying() {
+            frame_idx.hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+/// Run `effects` on `frame` in order (CPU path), reusing the stored result
+/// when the same source went through the same stack before. `source` is
+/// `None` when the frame can't be identified (e.g. blended from two source
+/// frames); the stack then always runs.
+pub fn apply_cached(
+    frame: Frame,
+    effects: &[&Effect],
+    frame_idx: i32,
+    source: Option<&SourceKey>,
+) -> Frame {
+    if effects.is_empty() {
+        return frame;
+    }
+    let key = source.map(|s| {
+        (
+            s.buffer.as_ptr() as usize,
+            s.prep,
+            stack_hash(effects, frame_idx),
+        )
+    });
+    if let Some(key) = key
+        && let Some(hit) = RESULTS.lock().unwrap().get(&key)
+    {
+        trace!("effects: cache hit for {} effect(s)", effects.len());
+        return hit;
+    }
+
+    let mut frame = frame;
+    for fx in effects {
+        if let Some(fx_frame) = apply(&frame, fx, frame_idx) {
+            frame = fx_frame;
+        }
+    }
+
+    if let (Some(key), Some(source)) = (key, source)
+        && frame.status() == FrameStatus::Loaded
+    {
+        RESULTS
+            .lock()
+            .unwrap()
+            .insert(key, source.buffer.clone(), frame.clone());
+    }
+    frame
+}
+
+/// Cached effect results and their size in bytes.
+pub fn usage() -> (usize, usize) {
+    RESULTS.lock().unwrap().usage()
+}
+
+/// Drop every cached effect result.
+pub fn clear() {
+    RESULTS.lock().unwrap().clear();
+}
+
+/// Budget the process-wide cache from `manager` (see [`EffectCache::attach`]).
+pub fn attach(manager: &Arc<CacheManager>) {
+    RESULTS.lock().unwrap().attach(manager);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::attrs::AttrValue;
+    use crate::entities::effects::EffectType;
+
+    fn frame(w: usize, h: usize) -> Frame {
+        Frame::from_f32_buffer(vec![0.5; w * h * 4], w, h)
+    }
+
+    #[test]
+    fn test_cache_hits_until_params_or_source_change() {
+        let src = frame(8, 8);
+        let key = SourceKey::new(&src, 1);
+        let mut blur = Effect::new(EffectType::GaussianBlur);
+        let a = apply_cached(src.clone(), &[&blur], 0, Some(&key));
+        let b = apply_cached(src.clone(), &[&blur], 5, Some(&key));
+        // Static stack: the second frame reuses the first result
+        assert!(Arc::ptr_eq(&a.buffer(), &b.buffer()));
+
+        blur.attrs.set("radius", AttrValue::Float(2.0));
+        let c = apply_cached(src.clone(), &[&blur], 5, Some(&key));
+        assert!(!Arc::ptr_eq(&a.buffer(), &c.buffer()));
+
+        // Same pixels, new buffer: a fresh decode misses
+        let other = frame(8, 8);
+        let d = apply_cached(other.clone(), &[&blur], 5, Some(&SourceKey::new(&other, 1)));
+        assert!(!Arc::ptr_eq(&c.buffer(), &d.buffer()));
+    }
+
+    #[test]
+    fn test_time_varying_stack_keys_on_frame() {
+        let grain = Effect::new(EffectType::AddGrain);
+        let blur = Effect::new(EffectType::GaussianBlur);
+        assert_ne!(stack_hash(&[&grain], 1), stack_hash(&[&grain], 2));
+        assert_eq!(stack_hash(&[&blur], 1), stack_hash(&[&blur], 2));
+    }
+
+    #[test]
+    fn test_budget_and_dead_sources() {
+        let mut cache = EffectCache::new(3 * 8 * 8 * 16);
+        let sources: Vec<Frame> = (0..4).map(|_| frame(8, 8)).collect();
+        for (i, src) in sources.iter().enumerate() {
+            let weak = Arc::downgrade(&src.buffer());
+            cache.insert((i, 0, 0), weak, frame(8, 8));
+        }
+        // Oldest evicted to stay within three frames
+        assert_eq!(cache.usage().0, 3);
+        assert!(cache.get(&(0, 0, 0)).is_none());
+
+        drop(sources);
+        let keep = frame(8, 8);
+        cache.insert((9, 0, 0), Arc::downgrade(&keep.buffer()), frame(8, 8));
+        assert_eq!(cache.usage().0, 1);
+    }
+
+    #[test]
+    fn test_attached_budget_counts_in_manager() {
+        let manager = Arc::new(CacheManager::new(0.5, 0.0));
+        let limit = manager.mem().1;
+        let mut cache = EffectCache::new(0);
+        cache.attach(&manager);
+        assert_eq!(
+            cache.budget(),
+            (limit as f64 * EFFECT_CACHE_FRACTION) as usize
+        );
+
+        let src = frame(8, 8);
+        let result = frame(8, 8);
+        let bytes = result.mem();
+        cache.insert((1, 0, 0), Arc::downgrade(&src.buffer()), result);
+        assert_eq!(manager.mem().0, bytes);
+
+        cache.clear();
+        assert_eq!(manager.mem().0, 0);
+    }
+}
@@ -0,0 +1,155 @@
+//! Color balance (lift / gamma / gain) effect implementation.
+//!
+//! The classic three-way corrector, per channel in linear light:
+//! `output = (input * gain + lift * (1 - input)) ^ (1 / gamma)`
+//!
+//! # Parameters
+//!
+//! - `lift`: RGB offset of the blacks, 0.0 = no change
+//! - `gamma`: RGB midtone power, 1.0 = no change
+//! - `gain`: RGB multiplier of the whites, 1.0 = no change
+//!
+//! Lift fades out towards 1.0, so it raises blacks while leaving white where
+//! gain puts it; above 1.0 (HDR) lift has no effect. Negative values keep
+//! their sign through the gamma power. sRGB sources are linearized first and
+//! re-encoded after. Alpha is unchanged.
+
+use crate::entities::attrs::Attrs;
+use crate::entities::frame::{ColorSpace, Frame, linear_to_srgb, srgb_to_linear};
+
+use super::blur::{from_f32_buffer, map_rgb_in_place, to_f32_buffer};
+
+/// Per-channel grade resolved from effect attrs
+#[derive(Clone, Copy, Debug)]
+struct Grade {
+    lift: [f32; 3],
+    gamma: [f32; 3],
+    gain: [f32; 3],
+}
+
+impl Grade {
+    fn is_identity(&self) -> bool {
+        (0..3).all(|c| {
+            self.lift[c].abs() < 0.0001
+                && (self.gamma[c] - 1.0).abs() < 0.0001
+                && (self.gain[c] - 1.0).abs() < 0.0001
+        })
+    }
+
+    /// Grade one linear channel value `v` of channel `c`
+    fn apply(&self, v: f32, c: usize) -> f32 {
+        let v = v * self.gain[c] + self.lift[c] * (1.0 - v).max(0.0);
+        let inv_gamma = 1.0 / self.gamma[c].max(0.01);
+        v.signum() * v.abs().powf(inv_gamma)
+    }
+}
+
+/// Apply lift/gamma/gain to a frame.
+///
+/// # Parameters
+/// - `frame`: Source frame to grade
+/// - `attrs`: Effect attributes ("lift", "gamma", "gain" as Vec3)
+///
+/// # Returns
+/// New graded Frame, or None if processing fails
+pub fn apply(frame: &Frame, attrs: &Attrs) -> Option<Frame> {
+    let grade = Grade {
+        lift: attrs.get_vec3("lift").unwrap_or([0.0; 3]),
+        gamma: attrs.get_vec3("gamma").unwrap_or([1.0; 3]),
+        gain: attrs.get_vec3("gain").unwrap_or([1.0; 3]),
+    };
+
+    // No adjustment needed
+    if grade.is_identity() {
+        return Some(frame.clone());
+    }
+
+    let (width, height) = frame.resolution();
+    let buffer = frame.buffer();
+
+    // Convert to f32 for processing (unified pipeline), linear light
+    let space = frame.color_space();
+    let mut data = to_f32_buffer(&buffer, width, height);
+    if space == ColorSpace::Srgb {
+        map_rgb_in_place(&mut data, srgb_to_linear);
+    }
+
+    // No clamping for HDR - allow out-of-range
+    for px in data.chunks_exact_mut(4) {
+        for (c, v) in px[..3].iter_mut().enumerate() {
+            *v = grade.apply(*v, c);
+        }
+    }
+
+    if space == ColorSpace::Srgb {
+        map_rgb_in_place(&mut data, linear_to_srgb);
+    }
+
+    // Convert back to original format
+    let out_buffer = from_f32_buffer(&data, frame.pixel_format(), width, height);
+
+    let out = Frame::from_buffer(out_buffer, frame.pixel_format(), width, height);
+    out.set_color_space(space);
+    Some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::attrs::AttrValue;
+    use crate::entities::frame::{PixelBuffer, PixelFormat};
+
+    /// Black, mid grey, white and an HDR highlight, 4x1 float
+    fn ramp() -> Frame {
+        let mut data = Vec::new();
+        for v in [0.0, 0.18, 1.0, 4.0] {
+            data.extend_from_slice(&[v, v, v, 1.0]);
+        }
+        let frame = Frame::from_buffer(PixelBuffer::F32(data), PixelFormat::RgbaF32, 4, 1);
+        frame.set_color_space(ColorSpace::Linear);
+        frame
+    }
+
+    fn graded(attrs: &Attrs) -> Vec<f32> {
+        let out = apply(&ramp(), attrs).unwrap();
+        match out.buffer().as_ref() {
+            PixelBuffer::F32(data) => data.chunks_exact(4).map(|p| p[0]).collect(),
+            _ => panic!("expected F32 buffer"),
+        }
+    }
+
+    #[test]
+    fn test_lift_raises_blacks_keeps_whites() {
+        let mut attrs = Attrs::new();
+        attrs.set("lift", AttrValue::Vec3([0.1, 0.1, 0.1]));
+        let out = graded(&attrs);
+        assert!((out[0] - 0.1).abs() < 1e-6); // black lifted
+        assert!(out[1] > 0.18 && out[1] < 0.28); // mids partially
+        assert!((out[2] - 1.0).abs() < 1e-6); // white untouched
+        assert!((out[3] - 4.0).abs() < 1e-6); // HDR untouched
+    }
+
+    #[test]
+    fn test_gain_scales_highlights() {
+        let mut attrs = Attrs::new();
+        attrs.set("gain", AttrValue::Vec3([2.0, 1.0, 1.0]));
+        let out = graded(&attrs);
+        assert_eq!(out[0], 0.0); // black stays black
+        assert!((out[2] - 2.0).abs() < 1e-6);
+        assert!((out[3] - 8.0).abs() < 1e-6); // no clamping
+    }
+
+    #[test]
+    fn test_gamma_per_channel_and_alpha() {
+        let mut attrs = Attrs::new();
+        attrs.set("gamma", AttrValue::Vec3([1.0, 2.0, 1.0]));
+        let out = apply(&ramp(), &attrs).unwrap();
+        let PixelBuffer::F32(data) = out.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        // Mid grey: red unchanged, green brightened by sqrt
+        assert!((data[4] - 0.18).abs() < 1e-6);
+        assert!((data[5] - 0.18f32.sqrt()).abs() < 1e-6);
+        assert!(data.chunks_exact(4).all(|p| p[3] == 1.0));
+    }
+}
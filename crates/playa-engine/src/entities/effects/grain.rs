@@ -0,0 +1,280 @@
+//! Film grain effect implementation.
+//!
+//! Overlays procedural grain: `output = input + noise * intensity * alpha`
+//!
+//! # Parameters
+//!
+//! - `intensity`: grain amplitude in linear light, 0.0 = no change
+//! - `size`: grain cell size in full-resolution pixels (scaled down for proxies)
+//! - `correlation`: 0.0 (independent channels) to 1.0 (shared across channels)
+//! - `monochrome`: one noise value for all channels, ignores `correlation`
+//! - `seed`: offset so stacked or neighbouring layers don't grain in lockstep
+//!
+//! # Algorithm
+//!
+//! 1. Seed = frame index + `seed`, so grain animates yet a given frame always
+//!    renders identically
+//! 2. Per grain cell and channel, a stateless integer hash gives a value in
+//!    -1..1; cells larger than a pixel are bilinearly interpolated
+//! 3. Channels mix their own noise with a shared one by `correlation`,
+//!    keeping unit amplitude
+//! 4. Float buffers add the noise directly (linear, no clamping for HDR);
+//!    U8 is linearized from sRGB first and re-encoded afterwards
+//!
+//! Alpha is unchanged; noise is scaled by it so grain stays inside the matte.
+
+use half::f16 as F16;
+
+use crate::entities::attrs::Attrs;
+use crate::entities::frame::{Frame, PixelBuffer, linear_to_srgb, srgb_to_linear};
+
+/// Channel slot of the shared (luma) noise
+const SHARED: u32 = 3;
+
+/// Grain parameters resolved from effect attrs
+#[derive(Clone, Copy, Debug)]
+struct Params {
+    intensity: f32,
+    /// Cell size in pixels of the frame being processed
+    size: f32,
+    /// Weights of the shared and per-channel noise (unit power)
+    shared: f32,
+    own: f32,
+    seed: u32,
+}
+
+impl Params {
+    /// Grain offsets for R, G, B at pixel (x, y)
+    fn noise(&self, x: usize, y: usize) -> [f32; 3] {
+        let (fx, fy) = (x as f32 / self.size, y as f32 / self.size);
+        let shared = if self.shared > 0.0 {
+            self.sample(fx, fy, SHARED)
+        } else {
+            0.0
+        };
+        let mut out = [shared; 3];
+        if self.own > 0.0 {
+            for (c, v) in out.iter_mut().enumerate() {
+                *v = self.shared * shared + self.own * self.sample(fx, fy, c as u32);
+            }
+        }
+        out
+    }
+
+    /// Value noise for `channel` at fractional cell coords (fx, fy)
+    fn sample(&self, fx: f32, fy: f32, channel: u32) -> f32 {
+        if self.size <= 1.0 {
+            return cell(fx as i32, fy as i32, channel, self.seed);
+        }
+        let (x0, y0) = (fx.floor(), fy.floor());
+        let (tx, ty) = (fx - x0, fy - y0);
+        let (x0, y0) = (x0 as i32, y0 as i32);
+        let top = lerp(
+            cell(x0, y0, channel, self.seed),
+            cell(x0 + 1, y0, channel, self.seed),
+            tx,
+        );
+        let bottom = lerp(
+            cell(x0, y0 + 1, channel, self.seed),
+            cell(x0 + 1, y0 + 1, channel, self.seed),
+            tx,
+        );
+        lerp(top, bottom, ty)
+    }
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+/// Deterministic noise in -1..1 for one grain cell (PCG-style integer hash).
+fn cell(x: i32, y: i32, channel: u32, seed: u32) -> f32 {
+    let mut h = (x as u32).wrapping_mul(0x8da6_b343)
+        ^ (y as u32).wrapping_mul(0xd816_3841)
+        ^ channel.wrapping_mul(0xcb1a_b31f)
+        ^ seed.wrapping_mul(0x1656_67b1);
+    h = h.wrapping_mul(747_796_405).wrapping_add(2_891_336_453);
+    h = ((h >> ((h >> 28) + 4)) ^ h).wrapping_mul(277_803_737);
+    h = (h >> 22) ^ h;
+    (h as f32 / u32::MAX as f32) * 2.0 - 1.0
+}
+
+/// Apply film grain to a frame.
+///
+/// # Parameters
+/// - `frame`: Source frame
+/// - `attrs`: Effect attributes ("intensity", "size", "correlation", "monochrome", "seed")
+/// - `frame_idx`: Frame being rendered; seeds the noise
+///
+/// # Returns
+/// New grained Frame, or None if processing fails
+pub fn apply(frame: &Frame, attrs: &Attrs, frame_idx: i32) -> Option<Frame> {
+    let intensity = attrs.get_float("intensity").unwrap_or(0.0).max(0.0);
+
+    // No grain needed
+    if intensity < 0.0001 {
+        return Some(frame.clone());
+    }
+
+    let correlation = if attrs.get_bool("monochrome").unwrap_or(false) {
+        1.0
+    } else {
+        attrs
+            .get_float("correlation")
+            .unwrap_or(0.0)
+            .clamp(0.0, 1.0)
+    };
+    let size = attrs.get_float("size").unwrap_or(1.0).max(0.0) / frame.proxy_factor() as f32;
+    let params = Params {
+        intensity,
+        size: size.max(1e-3),
+        shared: correlation,
+        own: (1.0 - correlation * correlation).sqrt(),
+        seed: (frame_idx as u32).wrapping_add(attrs.get_i32("seed").unwrap_or(0) as u32),
+    };
+
+    let (width, height) = frame.resolution();
+    let buffer = frame.buffer();
+
+    // Add noise to one pixel's linear RGB, given its alpha
+    let grain = |rgb: [f32; 3], a: f32, i: usize| -> [f32; 3] {
+        let n = params.noise(i % width, i / width);
+        let amp = params.intensity * a.clamp(0.0, 1.0);
+        [
+            rgb[0] + n[0] * amp,
+            rgb[1] + n[1] * amp,
+            rgb[2] + n[2] * amp,
+        ]
+    };
+
+    let out_buffer = match buffer.as_ref() {
+        PixelBuffer::U8(data) => {
+            let mut result = Vec::with_capacity(data.len());
+
+            for (i, chunk) in data.chunks_exact(4).enumerate() {
+                // 8-bit is display-referred sRGB: grain in linear, re-encode
+                let rgb = [0, 1, 2].map(|c| srgb_to_linear(chunk[c] as f32 / 255.0));
+                let a = chunk[3]; // Alpha unchanged
+                for v in grain(rgb, a as f32 / 255.0, i) {
+                    let encoded = linear_to_srgb(v.clamp(0.0, 1.0)) * 255.0;
+                    result.push(encoded.round().clamp(0.0, 255.0) as u8);
+                }
+                result.push(a);
+            }
+
+            PixelBuffer::U8(result)
+        }
+
+        PixelBuffer::F16(data) => {
+            let mut result = Vec::with_capacity(data.len());
+
+            for (i, chunk) in data.chunks_exact(4).enumerate() {
+                let rgb = [0, 1, 2].map(|c| chunk[c].to_f32());
+                let a = chunk[3]; // Alpha unchanged
+                // No clamping for HDR - allow out-of-range
+                for v in grain(rgb, a.to_f32(), i) {
+                    result.push(F16::from_f32(v));
+                }
+                result.push(a);
+            }
+
+            PixelBuffer::F16(result)
+        }
+
+        PixelBuffer::F32(data) => {
+            let mut result = Vec::with_capacity(data.len());
+
+            for (i, chunk) in data.chunks_exact(4).enumerate() {
+                let a = chunk[3]; // Alpha unchanged
+                result.extend(grain([chunk[0], chunk[1], chunk[2]], a, i));
+                result.push(a);
+            }
+
+            PixelBuffer::F32(result)
+        }
+    };
+
+    Some(Frame::from_buffer(
+        out_buffer,
+        frame.pixel_format(),
+        width,
+        height,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::attrs::AttrValue;
+    use crate::entities::frame::{FrameStatus, PixelFormat};
+
+    fn gray_frame() -> Frame {
+        Frame::from_buffer(
+            PixelBuffer::F32([0.18, 0.18, 0.18, 1.0].repeat(16 * 16)),
+            PixelFormat::RgbaF32,
+            16,
+            16,
+        )
+    }
+
+    fn f32_pixels(frame: &Frame) -> Vec<f32> {
+        match frame.buffer().as_ref() {
+            PixelBuffer::F32(data) => data.clone(),
+            _ => panic!("expected F32 buffer"),
+        }
+    }
+
+    fn grain_attrs(intensity: f32) -> Attrs {
+        let mut attrs = Attrs::new();
+        attrs.set("intensity", AttrValue::Float(intensity));
+        attrs.set("size", AttrValue::Float(1.0));
+        attrs
+    }
+
+    #[test]
+    fn test_zero_intensity_is_noop() {
+        let frame = gray_frame();
+        let out = apply(&frame, &grain_attrs(0.0), 7).unwrap();
+        assert_eq!(f32_pixels(&out), f32_pixels(&frame));
+
+        let u8_frame =
+            Frame::from_u8_buffer_with_status(vec![10, 128, 250, 200], 1, 1, FrameStatus::Loaded);
+        let out = apply(&u8_frame, &grain_attrs(0.0), 7).unwrap();
+        if let PixelBuffer::U8(data) = out.buffer().as_ref() {
+            assert_eq!(&data[..], &[10, 128, 250, 200]);
+        }
+    }
+
+    #[test]
+    fn test_seeded_per_frame() {
+        let frame = gray_frame();
+        let attrs = grain_attrs(0.05);
+        let a = f32_pixels(&apply(&frame, &attrs, 10).unwrap());
+        let b = f32_pixels(&apply(&frame, &attrs, 10).unwrap());
+        let c = f32_pixels(&apply(&frame, &attrs, 11).unwrap());
+        assert_eq!(a, b); // Reproducible for a given frame
+        assert_ne!(a, c); // Animates across frames
+        assert!(
+            a.iter()
+                .all(|v| (v - 0.18).abs() <= 0.05 + 1e-6 || *v == 1.0)
+        );
+    }
+
+    #[test]
+    fn test_monochrome_shares_noise() {
+        let frame = gray_frame();
+        let mut attrs = grain_attrs(0.05);
+        attrs.set("monochrome", AttrValue::Bool(true));
+        let px = f32_pixels(&apply(&frame, &attrs, 3).unwrap());
+        for chunk in px.chunks_exact(4) {
+            assert_eq!(chunk[0], chunk[1]);
+            assert_eq!(chunk[1], chunk[2]);
+            assert_eq!(chunk[3], 1.0);
+        }
+
+        // Chromatic grain differs per channel
+        attrs.set("monochrome", AttrValue::Bool(false));
+        let px = f32_pixels(&apply(&frame, &attrs, 3).unwrap());
+        assert!(px.chunks_exact(4).any(|c| c[0] != c[1]));
+    }
+}
@@ -0,0 +1,195 @@
+//! Invert / Solarize effect implementation.
+//!
+//! Inverts color channels around a white point:
+//! `inverted = white_point - input`, then `output = lerp(input, inverted, mix)`
+//!
+//! # Parameters
+//!
+//! - `mix`: 0.0 (no change) to 1.0 (full inversion)
+//! - `white_point`: value inversion pivots on for float buffers (linear
+//!   HDR data has no implicit 1.0 white); U8 always inverts as `255 - v`
+//! - `solarize`: threshold as a fraction of the white point; only channel
+//!   values at or above it invert. 0.0 = plain invert
+//! - `invert_alpha`: also invert the alpha channel
+//!
+//! # Algorithm
+//!
+//! 1. Per channel, skip values below `solarize * white`
+//! 2. Invert: `white - v`
+//! 3. Blend with the original by `mix`
+
+use half::f16 as F16;
+
+use crate::entities::attrs::Attrs;
+use crate::entities::frame::{Frame, PixelBuffer};
+
+/// Invert parameters resolved from effect attrs
+#[derive(Clone, Copy, Debug)]
+struct Params {
+    mix: f32,
+    threshold: f32,
+    invert_alpha: bool,
+}
+
+impl Params {
+    /// Invert a single channel value `v` around `white`
+    fn apply(&self, v: f32, white: f32) -> f32 {
+        if v < self.threshold * white {
+            return v;
+        }
+        let inverted = white - v;
+        v + (inverted - v) * self.mix
+    }
+}
+
+/// Apply invert/solarize to a frame.
+///
+/// # Parameters
+/// - `frame`: Source frame to invert
+/// - `attrs`: Effect attributes ("mix", "white_point", "solarize", "invert_alpha")
+///
+/// # Returns
+/// New inverted Frame, or None if processing fails
+pub fn apply(frame: &Frame, attrs: &Attrs) -> Option<Frame> {
+    let params = Params {
+        mix: attrs.get_float("mix").unwrap_or(1.0).clamp(0.0, 1.0),
+        threshold: attrs.get_float("solarize").unwrap_or(0.0).max(0.0),
+        invert_alpha: attrs.get_bool("invert_alpha").unwrap_or(false),
+    };
+    let white = attrs.get_float("white_point").unwrap_or(1.0);
+
+    // No inversion needed
+    if params.mix < 0.0001 {
+        return Some(frame.clone());
+    }
+
+    let (width, height) = frame.resolution();
+    let buffer = frame.buffer();
+
+    let out_buffer = match buffer.as_ref() {
+        PixelBuffer::U8(data) => {
+            let mut result = Vec::with_capacity(data.len());
+
+            for chunk in data.chunks_exact(4) {
+                for (i, &v) in chunk.iter().enumerate() {
+                    if i == 3 && !params.invert_alpha {
+                        result.push(v);
+                        continue;
+                    }
+                    // 8-bit is display-referred: white is always 255
+                    let out = params.apply(v as f32, 255.0);
+                    result.push(out.round().clamp(0.0, 255.0) as u8);
+                }
+            }
+
+            PixelBuffer::U8(result)
+        }
+
+        PixelBuffer::F16(data) => {
+            let mut result = Vec::with_capacity(data.len());
+
+            for chunk in data.chunks_exact(4) {
+                for (i, &v) in chunk.iter().enumerate() {
+                    let out = match i {
+                        3 if !params.invert_alpha => v,
+                        // Alpha inverts around 1.0, not the color white point
+                        3 => F16::from_f32(params.apply(v.to_f32(), 1.0)),
+                        // No clamping for HDR - allow out-of-range
+                        _ => F16::from_f32(params.apply(v.to_f32(), white)),
+                    };
+                    result.push(out);
+                }
+            }
+
+            PixelBuffer::F16(result)
+        }
+
+        PixelBuffer::F32(data) => {
+            let mut result = Vec::with_capacity(data.len());
+
+            for chunk in data.chunks_exact(4) {
+                for (i, &v) in chunk.iter().enumerate() {
+                    let out = match i {
+                        3 if !params.invert_alpha => v,
+                        3 => params.apply(v, 1.0),
+                        _ => params.apply(v, white),
+                    };
+                    result.push(out);
+                }
+            }
+
+            PixelBuffer::F32(result)
+        }
+    };
+
+    Some(Frame::from_buffer(
+        out_buffer,
+        frame.pixel_format(),
+        width,
+        height,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::attrs::AttrValue;
+    use crate::entities::frame::{FrameStatus, PixelFormat};
+
+    fn f32_frame(rgba: [f32; 4]) -> Frame {
+        Frame::from_buffer(PixelBuffer::F32(rgba.to_vec()), PixelFormat::RgbaF32, 1, 1)
+    }
+
+    fn first_f32(frame: &Frame) -> Vec<f32> {
+        match frame.buffer().as_ref() {
+            PixelBuffer::F32(data) => data[..4].to_vec(),
+            _ => panic!("expected F32 buffer"),
+        }
+    }
+
+    #[test]
+    fn test_mid_gray_stays_when_inverted_around_itself() {
+        // Linear 18% gray inverted around a white point of twice its value
+        let frame = f32_frame([0.18, 0.18, 0.18, 1.0]);
+        let mut attrs = Attrs::new();
+        attrs.set("white_point", AttrValue::Float(0.36));
+
+        let out = first_f32(&apply(&frame, &attrs).unwrap());
+        for c in &out[..3] {
+            assert!((c - 0.18).abs() < 1e-6);
+        }
+        assert_eq!(out[3], 1.0);
+    }
+
+    #[test]
+    fn test_u8_invert_mix_and_alpha() {
+        let frame =
+            Frame::from_u8_buffer_with_status(vec![0, 100, 255, 200], 1, 1, FrameStatus::Loaded);
+        let mut attrs = Attrs::new();
+        attrs.set("invert_alpha", AttrValue::Bool(true));
+        let out = apply(&frame, &attrs).unwrap();
+        if let PixelBuffer::U8(data) = out.buffer().as_ref() {
+            assert_eq!(&data[..4], &[255, 155, 0, 55]);
+        }
+
+        // Half mix lands every channel on mid-gray
+        attrs.set("mix", AttrValue::Float(0.5));
+        attrs.set("invert_alpha", AttrValue::Bool(false));
+        let out = apply(&frame, &attrs).unwrap();
+        if let PixelBuffer::U8(data) = out.buffer().as_ref() {
+            assert_eq!(&data[..4], &[128, 128, 128, 200]);
+        }
+    }
+
+    #[test]
+    fn test_solarize_threshold() {
+        let frame = f32_frame([0.2, 0.8, 0.5, 1.0]);
+        let mut attrs = Attrs::new();
+        attrs.set("solarize", AttrValue::Float(0.5));
+
+        let out = first_f32(&apply(&frame, &attrs).unwrap());
+        assert!((out[0] - 0.2).abs() < 1e-6); // below threshold: untouched
+        assert!((out[1] - 0.2).abs() < 1e-6); // above: inverted
+        assert!((out[2] - 0.5).abs() < 1e-6); // at threshold: inverted
+    }
+}
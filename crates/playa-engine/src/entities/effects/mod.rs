@@ -14,11 +14,14 @@
 //! compose_internal():
 //!   source_frame = load_source()
 //!   for effect in layer.effects:
-//!       source_frame = effects::apply(source_frame, effect)
+//!       source_frame = effects::apply(source_frame, effect, frame_idx)
 //!   transform(source_frame, ...)  // effects applied BEFORE transform
 //!   blend(source_frame, ...)
 //! ```
 //!
+//! CPU-path results are cached per source frame and effect stack (see
+//! [`cache`]), so static effects on a clip run once per frame while scrubbing.
+//!
 //! # Effect Types
 //!
 //! | Type | Parameters | Description |
@@ -26,6 +29,10 @@
 //! | **GaussianBlur** | `radius: 0-100` | Separable blur, O(n*r) per pass |
 //! | **BrightnessContrast** | `brightness: -1..1`, `contrast: -1..1` | Color adjustment |
 //! | **AdjustHSV** | `hue_shift: -180..180`, `saturation: 0..2`, `value: 0..2` | HSV color space |
+//! | **Invert** | `mix: 0..1`, `white_point`, `solarize: 0..1`, `invert_alpha` | Invert / solarize |
+//! | **MotionBlur** | `angle`, `length: 0-500`, `samples: 2-256`, `gaussian` | Directional blur |
+//! | **ColorBalance** | `lift`, `gamma`, `gain` (RGB Vec3) | Three-way color corrector |
+//! | **AddGrain** | `intensity`, `size`, `correlation: 0..1`, `monochrome`, `seed` | Film grain, seeded per frame |
 //!
 //! # UI Integration
 //!
@@ -60,7 +67,12 @@
 
 pub mod blur;
 pub mod brightness;
+pub mod cache;
+pub mod color_balance;
+pub mod grain;
 pub mod hsv;
+pub mod invert;
+pub mod motion_blur;
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -84,6 +96,14 @@ pub enum EffectType {
     BrightnessContrast,
     /// HSV color space adjustments (hue shift, saturation, value)
     AdjustHSV,
+    /// Invert around a white point, with mix and solarize threshold
+    Invert,
+    /// Procedural film grain, animated by frame index
+    AddGrain,
+    /// Directional blur along an angle
+    MotionBlur,
+    /// Per-channel lift / gamma / gain
+    ColorBalance,
 }
 
 impl EffectType {
@@ -93,15 +113,29 @@ impl EffectType {
             EffectType::GaussianBlur => "Gaussian Blur",
             EffectType::BrightnessContrast => "Brightness/Contrast",
             EffectType::AdjustHSV => "Adjust HSV",
+            EffectType::Invert => "Invert",
+            EffectType::AddGrain => "Add Grain",
+            EffectType::MotionBlur => "Motion Blur",
+            EffectType::ColorBalance => "Color Balance",
         }
     }
 
+    /// Whether the result changes from frame to frame with the same input
+    /// (seeded per frame), so cached results must be keyed on the frame
+    pub fn is_time_varying(&self) -> bool {
+        matches!(self, EffectType::AddGrain)
+    }
+
     /// Get the attribute schema for this effect type
     pub fn schema(&self) -> &'static AttrSchema {
         match self {
             EffectType::GaussianBlur => &FX_GAUSSIAN_BLUR_SCHEMA,
             EffectType::BrightnessContrast => &FX_BRIGHTNESS_CONTRAST_SCHEMA,
             EffectType::AdjustHSV => &FX_HSV_ADJUST_SCHEMA,
+            EffectType::Invert => &FX_INVERT_SCHEMA,
+            EffectType::AddGrain => &FX_GRAIN_SCHEMA,
+            EffectType::MotionBlur => &FX_MOTION_BLUR_SCHEMA,
+            EffectType::ColorBalance => &FX_COLOR_BALANCE_SCHEMA,
         }
     }
 
@@ -111,6 +145,10 @@ impl EffectType {
             EffectType::GaussianBlur,
             EffectType::BrightnessContrast,
             EffectType::AdjustHSV,
+            EffectType::Invert,
+            EffectType::AddGrain,
+            EffectType::MotionBlur,
+            EffectType::ColorBalance,
         ]
     }
 }
@@ -148,6 +186,66 @@ const HSV_ATTRS: &[AttrDef] = &[
     AttrDef::with_ui_order("value", AttrType::Float, FX, &["0", "2", "0.01"], 2.0),
 ];
 
+/// Invert schema
+const INVERT_ATTRS: &[AttrDef] = &[
+    // mix: 0.0 (no change) to 1.0 (full inversion)
+    AttrDef::with_ui_order("mix", AttrType::Float, FX, &["0", "1", "0.01"], 0.0),
+    // white_point: float buffers invert as white_point - v (U8 always uses 255)
+    AttrDef::with_ui_order(
+        "white_point",
+        AttrType::Float,
+        FX,
+        &["0", "100", "0.01"],
+        1.0,
+    ),
+    // solarize: only values >= threshold * white_point invert, 0.0 = plain invert
+    AttrDef::with_ui_order("solarize", AttrType::Float, FX, &["0", "1", "0.01"], 2.0),
+    // invert_alpha: also invert the alpha channel
+    AttrDef::with_order("invert_alpha", AttrType::Bool, FLAG_DAG | FLAG_DISPLAY, 3.0),
+];
+
+/// Film grain schema
+const GRAIN_ATTRS: &[AttrDef] = &[
+    // intensity: grain amplitude in linear light, 0.0 = no change
+    AttrDef::with_ui_order("intensity", AttrType::Float, FX, &["0", "1", "0.005"], 0.0),
+    // size: grain cell size in pixels
+    AttrDef::with_ui_order("size", AttrType::Float, FX, &["0.5", "10", "0.1"], 1.0),
+    // correlation: 0.0 (independent channels) to 1.0 (shared)
+    AttrDef::with_ui_order("correlation", AttrType::Float, FX, &["0", "1", "0.01"], 2.0),
+    // monochrome: same grain on every channel
+    AttrDef::with_order("monochrome", AttrType::Bool, FLAG_DAG | FLAG_DISPLAY, 3.0),
+    // seed: offset added to the frame index
+    AttrDef::with_order("seed", AttrType::Int, FLAG_DAG | FLAG_DISPLAY, 4.0),
+];
+
+/// Motion blur schema
+const MOTION_BLUR_ATTRS: &[AttrDef] = &[
+    // angle: streak direction in degrees, 0 = horizontal
+    AttrDef::with_ui_order("angle", AttrType::Float, FX, &["-180", "180", "1"], 0.0),
+    // length: streak length in pixels (0 = no blur)
+    AttrDef::with_ui_order("length", AttrType::Float, FX, &["0", "500", "0.5"], 1.0),
+    // samples: taps along the streak (quality vs speed)
+    AttrDef::with_ui_order(
+        "samples",
+        AttrType::Int,
+        FLAG_DAG | FLAG_DISPLAY,
+        &["2", "256", "1"],
+        2.0,
+    ),
+    // gaussian: Gaussian falloff instead of an even box smear
+    AttrDef::with_order("gaussian", AttrType::Bool, FLAG_DAG | FLAG_DISPLAY, 3.0),
+];
+
+/// Color balance schema (Vec3 params: one value per RGB channel)
+const COLOR_BALANCE_ATTRS: &[AttrDef] = &[
+    // lift: raises blacks, 0.0 = no change
+    AttrDef::with_ui_order("lift", AttrType::Vec3, FX, &["-1", "1", "0.005"], 0.0),
+    // gamma: midtone power, 1.0 = no change
+    AttrDef::with_ui_order("gamma", AttrType::Vec3, FX, &["0.1", "4", "0.01"], 1.0),
+    // gain: scales whites, 1.0 = no change
+    AttrDef::with_ui_order("gain", AttrType::Vec3, FX, &["0", "4", "0.01"], 2.0),
+];
+
 /// Schema for Gaussian Blur effect
 pub static FX_GAUSSIAN_BLUR_SCHEMA: LazyLock<AttrSchema> =
     LazyLock::new(|| AttrSchema::new("FX_GaussianBlur", BLUR_ATTRS));
@@ -160,6 +258,22 @@ pub static FX_BRIGHTNESS_CONTRAST_SCHEMA: LazyLock<AttrSchema> =
 pub static FX_HSV_ADJUST_SCHEMA: LazyLock<AttrSchema> =
     LazyLock::new(|| AttrSchema::new("FX_AdjustHSV", HSV_ATTRS));
 
+/// Schema for Invert effect
+pub static FX_INVERT_SCHEMA: LazyLock<AttrSchema> =
+    LazyLock::new(|| AttrSchema::new("FX_Invert", INVERT_ATTRS));
+
+/// Schema for Add Grain effect
+pub static FX_GRAIN_SCHEMA: LazyLock<AttrSchema> =
+    LazyLock::new(|| AttrSchema::new("FX_AddGrain", GRAIN_ATTRS));
+
+/// Schema for Motion Blur effect
+pub static FX_MOTION_BLUR_SCHEMA: LazyLock<AttrSchema> =
+    LazyLock::new(|| AttrSchema::new("FX_MotionBlur", MOTION_BLUR_ATTRS));
+
+/// Schema for Color Balance effect
+pub static FX_COLOR_BALANCE_SCHEMA: LazyLock<AttrSchema> =
+    LazyLock::new(|| AttrSchema::new("FX_ColorBalance", COLOR_BALANCE_ATTRS));
+
 // ============================================================================
 // Effect Struct
 // ============================================================================
@@ -200,6 +314,30 @@ impl Effect {
                 attrs.set("saturation", AttrValue::Float(1.0));
                 attrs.set("value", AttrValue::Float(1.0));
             }
+            EffectType::Invert => {
+                attrs.set("mix", AttrValue::Float(1.0));
+                attrs.set("white_point", AttrValue::Float(1.0));
+                attrs.set("solarize", AttrValue::Float(0.0));
+                attrs.set("invert_alpha", AttrValue::Bool(false));
+            }
+            EffectType::AddGrain => {
+                attrs.set("intensity", AttrValue::Float(0.05));
+                attrs.set("size", AttrValue::Float(1.0));
+                attrs.set("correlation", AttrValue::Float(0.7));
+                attrs.set("monochrome", AttrValue::Bool(false));
+                attrs.set("seed", AttrValue::Int(0));
+            }
+            EffectType::MotionBlur => {
+                attrs.set("angle", AttrValue::Float(0.0));
+                attrs.set("length", AttrValue::Float(20.0));
+                attrs.set("samples", AttrValue::Int(16));
+                attrs.set("gaussian", AttrValue::Bool(false));
+            }
+            EffectType::ColorBalance => {
+                attrs.set("lift", AttrValue::Vec3([0.0, 0.0, 0.0]));
+                attrs.set("gamma", AttrValue::Vec3([1.0, 1.0, 1.0]));
+                attrs.set("gain", AttrValue::Vec3([1.0, 1.0, 1.0]));
+            }
         }
 
         attrs.clear_dirty();
@@ -251,6 +389,11 @@ impl Effect {
                 let radius = self.attrs.get_float("radius").unwrap_or(5.0);
                 Some(GpuEffect::GaussianBlur { radius })
             }
+            // No GPU port yet: runs on the CPU path
+            EffectType::Invert
+            | EffectType::AddGrain
+            | EffectType::MotionBlur
+            | EffectType::ColorBalance => None,
         }
     }
 }
@@ -260,24 +403,36 @@ impl Effect {
 // ============================================================================
 
 /// Apply an effect to a frame, returning modified frame.
+/// `frame_idx` is the frame being rendered (seeds time-varying effects).
 /// Returns None if effect processing fails.
-pub fn apply(frame: &Frame, effect: &Effect) -> Option<Frame> {
+pub fn apply(frame: &Frame, effect: &Effect, frame_idx: i32) -> Option<Frame> {
     if !effect.enabled {
         return Some(frame.clone());
     }
 
-    match effect.effect_type {
+    let out = match effect.effect_type {
         EffectType::GaussianBlur => blur::apply(frame, &effect.attrs),
         EffectType::BrightnessContrast => brightness::apply(frame, &effect.attrs),
         EffectType::AdjustHSV => hsv::apply(frame, &effect.attrs),
+        EffectType::Invert => invert::apply(frame, &effect.attrs),
+        EffectType::AddGrain => grain::apply(frame, &effect.attrs, frame_idx),
+        EffectType::MotionBlur => motion_blur::apply(frame, &effect.attrs),
+        EffectType::ColorBalance => color_balance::apply(frame, &effect.attrs),
+    }?;
+    // Effects build fresh frames with empty attrs; keep the proxy tag so the
+    // next effect still scales its pixel-sized params (grain, motion blur)
+    let proxy = frame.proxy_factor();
+    if out.proxy_factor() != proxy {
+        out.set_proxy_factor(proxy);
     }
+    Some(out)
 }
 
 /// Apply all effects from a list to a frame, in order.
 /// Skips disabled effects. Returns original frame if list is empty.
-pub fn apply_all(mut frame: Frame, effects: &[Effect]) -> Option<Frame> {
+pub fn apply_all(mut frame: Frame, effects: &[Effect], frame_idx: i32) -> Option<Frame> {
     for effect in effects {
-        frame = apply(&frame, effect)?;
+        frame = apply(&frame, effect, frame_idx)?;
     }
     Some(frame)
 }
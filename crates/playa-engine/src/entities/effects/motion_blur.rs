@@ -0,0 +1,215 @@
+//! Directional (motion) blur effect implementation.
+//!
+//! Smears each pixel along a single direction, unlike the isotropic
+//! [`super::blur`] Gaussian. Every output pixel averages `samples` bilinear
+//! taps spread over a line of `length` pixels centered on it.
+//!
+//! # Parameters
+//!
+//! - `angle`: direction in degrees, 0 = horizontal, 90 = vertical (down)
+//! - `length`: streak length in full-resolution pixels (scaled down for proxies)
+//! - `samples`: taps along the line; more = smoother streaks, slower
+//! - `gaussian`: weight taps with a Gaussian falloff instead of a box
+//!
+//! Like the Gaussian blur, sRGB sources are averaged in linear light, float
+//! buffers are never clamped, and alpha is blurred along with color.
+
+use crate::entities::attrs::Attrs;
+use crate::entities::frame::{ColorSpace, Frame, linear_to_srgb, srgb_to_linear};
+
+use super::blur::{from_f32_buffer, map_rgb_in_place, to_f32_buffer};
+
+/// Upper bound on taps per pixel
+pub const MAX_SAMPLES: i32 = 256;
+
+/// Apply directional blur to a frame.
+///
+/// # Parameters
+/// - `frame`: Source frame to blur
+/// - `attrs`: Effect attributes ("angle", "length", "samples", "gaussian")
+///
+/// # Returns
+/// New blurred Frame, or None if processing fails
+pub fn apply(frame: &Frame, attrs: &Attrs) -> Option<Frame> {
+    let length = attrs.get_float("length").unwrap_or(0.0) / frame.proxy_factor() as f32;
+    let samples = attrs.get_i32("samples").unwrap_or(16).clamp(2, MAX_SAMPLES) as usize;
+
+    // No blur needed below a pixel
+    if length < 1.0 {
+        return Some(frame.clone());
+    }
+
+    let angle = attrs.get_float("angle").unwrap_or(0.0).to_radians();
+    let taps = line_taps(
+        length,
+        angle,
+        samples,
+        attrs.get_bool("gaussian").unwrap_or(false),
+    );
+
+    let (width, height) = frame.resolution();
+    let buffer = frame.buffer();
+
+    // Convert to f32 for processing (unified pipeline), linear light
+    let space = frame.color_space();
+    let mut src = to_f32_buffer(&buffer, width, height);
+    if space == ColorSpace::Srgb {
+        map_rgb_in_place(&mut src, srgb_to_linear);
+    }
+
+    let mut result = vec![0.0f32; src.len()];
+    for y in 0..height {
+        for x in 0..width {
+            let mut acc = [0.0f32; 4];
+            for &(dx, dy, weight) in &taps {
+                let px = sample_bilinear(&src, width, height, x as f32 + dx, y as f32 + dy);
+                for (a, v) in acc.iter_mut().zip(px) {
+                    *a += v * weight;
+                }
+            }
+            let idx = (y * width + x) * 4;
+            result[idx..idx + 4].copy_from_slice(&acc);
+        }
+    }
+
+    if space == ColorSpace::Srgb {
+        map_rgb_in_place(&mut result, linear_to_srgb);
+    }
+
+    // Convert back to original format
+    let out_buffer = from_f32_buffer(&result, frame.pixel_format(), width, height);
+
+    let out = Frame::from_buffer(out_buffer, frame.pixel_format(), width, height);
+    out.set_color_space(space);
+    Some(out)
+}
+
+/// Tap offsets `(dx, dy, weight)` evenly spaced over a centered line.
+///
+/// Weights sum to 1.0. The Gaussian falloff puts the line ends at 2 sigma.
+fn line_taps(length: f32, angle: f32, samples: usize, gaussian: bool) -> Vec<(f32, f32, f32)> {
+    let (sin, cos) = angle.sin_cos();
+    let mut taps: Vec<(f32, f32, f32)> = (0..samples)
+        .map(|i| {
+            // -0.5..0.5 along the line
+            let t = i as f32 / (samples - 1) as f32 - 0.5;
+            let weight = if gaussian { (-8.0 * t * t).exp() } else { 1.0 };
+            (t * length * cos, t * length * sin, weight)
+        })
+        .collect();
+
+    let sum: f32 = taps.iter().map(|t| t.2).sum();
+    for tap in &mut taps {
+        tap.2 /= sum;
+    }
+    taps
+}
+
+/// Bilinear RGBA lookup at a fractional position; edge pixels are clamped.
+fn sample_bilinear(src: &[f32], width: usize, height: usize, fx: f32, fy: f32) -> [f32; 4] {
+    let fx = fx.clamp(0.0, (width - 1) as f32);
+    let fy = fy.clamp(0.0, (height - 1) as f32);
+    let (x0, y0) = (fx.floor() as usize, fy.floor() as usize);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+
+    let at = |x: usize, y: usize, c: usize| src[(y * width + x) * 4 + c];
+    let mut out = [0.0f32; 4];
+    for (c, v) in out.iter_mut().enumerate() {
+        let top = at(x0, y0, c) + (at(x1, y0, c) - at(x0, y0, c)) * tx;
+        let bottom = at(x0, y1, c) + (at(x1, y1, c) - at(x0, y1, c)) * tx;
+        *v = top + (bottom - top) * ty;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::attrs::AttrValue;
+    use crate::entities::frame::{PixelBuffer, PixelFormat};
+
+    /// 9x5 black frame with one HDR pixel at (4, 2)
+    fn bright_pixel() -> Frame {
+        let (w, h) = (9, 5);
+        let mut data = [0.0, 0.0, 0.0, 1.0].repeat(w * h);
+        let idx = (2 * w + 4) * 4;
+        data[idx..idx + 3].copy_from_slice(&[40.0, 40.0, 40.0]);
+        Frame::from_buffer(PixelBuffer::F32(data), PixelFormat::RgbaF32, w, h)
+    }
+
+    fn streak_attrs(angle: f32) -> Attrs {
+        let mut attrs = Attrs::new();
+        attrs.set("angle", AttrValue::Float(angle));
+        attrs.set("length", AttrValue::Float(4.0));
+        attrs.set("samples", AttrValue::Int(5));
+        attrs
+    }
+
+    #[test]
+    fn test_streak_along_zero_degrees() {
+        let out = apply(&bright_pixel(), &streak_attrs(0.0)).unwrap();
+        let PixelBuffer::F32(px) = out.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        let red = |x: usize, y: usize| px[(y * 9 + x) * 4];
+
+        // Taps at -2..2 px: the streak covers x = 2..6 on row 2 only
+        for x in 0..9 {
+            let expected = if (2..=6).contains(&x) { 8.0 } else { 0.0 };
+            assert!((red(x, 2) - expected).abs() < 1e-4, "x={x}");
+        }
+        for y in [0, 1, 3, 4] {
+            assert!((0..9).all(|x| red(x, y) == 0.0));
+        }
+        // Energy conserved, HDR value not clamped, alpha kept
+        let row_sum: f32 = (0..9).map(|x| red(x, 2)).sum();
+        assert!((row_sum - 40.0).abs() < 1e-3);
+        assert!(px.chunks_exact(4).all(|p| (p[3] - 1.0).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_vertical_streak_and_noop() {
+        let out = apply(&bright_pixel(), &streak_attrs(90.0)).unwrap();
+        let PixelBuffer::F32(px) = out.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        // Column 4 lit, neighbours dark
+        assert!(px[(9 + 4) * 4] > 1.0);
+        assert!(px[(9 + 3) * 4].abs() < 1e-4);
+
+        let mut attrs = streak_attrs(0.0);
+        attrs.set("length", AttrValue::Float(0.0));
+        let frame = bright_pixel();
+        let same = apply(&frame, &attrs).unwrap();
+        let (same, orig) = (same.buffer(), frame.buffer());
+        let (PixelBuffer::F32(a), PixelBuffer::F32(b)) = (same.as_ref(), orig.as_ref()) else {
+            panic!("expected F32 buffers");
+        };
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn test_proxy_factor_survives_effect_chain() {
+        use crate::entities::effects::{Effect, EffectType, apply as apply_effect};
+
+        // Half-res frame: a 4 px streak covers 2 proxy pixels
+        let frame = bright_pixel();
+        frame.set_proxy_factor(2);
+        let mut bright = Effect::new(EffectType::BrightnessContrast);
+        bright.attrs.set("brightness", AttrValue::Float(0.01));
+        let mut streak = Effect::new(EffectType::MotionBlur);
+        streak.attrs = streak_attrs(0.0);
+
+        let graded = apply_effect(&frame, &bright, 0).unwrap();
+        assert_eq!(graded.proxy_factor(), 2);
+        let out = apply_effect(&graded, &streak, 0).unwrap();
+        assert_eq!(out.proxy_factor(), 2);
+        let PixelBuffer::F32(px) = out.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        let red = |x: usize| px[(2 * 9 + x) * 4];
+        assert!((3..=5).all(|x| red(x) > 1.0));
+        assert!(red(2) < 1.0 && red(6) < 1.0);
+    }
+}
@@ -3,19 +3,137 @@
 //! Replaces the COMP_FILE mode from Comp. This node type has no inputs
 //! and produces frames by loading them from disk based on file_mask pattern.
 
-use std::collections::HashMap;
+use std::collections::HashSet;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex};
 
 use log::info;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use super::attr_schemas::FILE_SCHEMA;
-use super::attrs::{AttrValue, Attrs};
-use super::frame::{CropAlign, Frame};
+use super::attrs::{AttrFlags, AttrValue, Attrs};
+use super::frame::{AlphaMode, CropAlign, Frame};
 use super::keys::*;
 use super::node::{ComputeContext, Node};
+use crate::defaults::{DEFAULT_DIM, DEFAULT_SRC_LEN};
 use crate::utils::media;
+use playa_io::svg;
+
+/// What [`FileNode::compute`] shows for sequence frames missing on disk.
+///
+/// Stored on the node as the `missing_frames` string attr so it round-trips
+/// with the project and shows up as a dropdown in the Attribute Editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MissingFramePolicy {
+    /// Reuse the nearest earlier frame on disk (next one for leading gaps).
+    #[default]
+    Hold,
+    /// Opaque black frame.
+    Black,
+    /// Red error frame, same as any other failed load.
+    Error,
+}
+
+impl MissingFramePolicy {
+    /// Canonical attr string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hold => "hold",
+            Self::Black => "black",
+            Self::Error => "error",
+        }
+    }
+
+    /// Parse attr string; unknown/missing values fall back to [`Self::Hold`].
+    pub fn from_attr(s: Option<&str>) -> Self {
+        match s {
+            Some("black") => Self::Black,
+            Some("error") => Self::Error,
+            _ => Self::Hold,
+        }
+    }
+}
+
+/// What a sequence frame resolves to once the missing-frame policy applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum FrameSource {
+    /// Load this file (the frame itself, a held neighbour, or - for
+    /// [`MissingFramePolicy::Error`] - the missing path so the load fails)
+    Path(PathBuf),
+    /// Opaque black frame
+    Black,
+}
+
+/// Missing frames already reported. `compute` runs on every cache miss, so
+/// without this an incomplete delivery would warn on each repaint / reload.
+static WARNED_MISSING: LazyLock<Mutex<HashSet<PathBuf>>> = LazyLock::new(Default::default);
+
+fn warn_missing_once(path: &Path, policy: MissingFramePolicy) {
+    let mut warned = WARNED_MISSING.lock().unwrap_or_else(|e| e.into_inner());
+    if warned.insert(path.to_path_buf()) {
+        log::warn!(
+            "Missing frame {} ({} policy)",
+            path.display(),
+            policy.as_str()
+        );
+    }
+}
+
+/// Alpha association of the source files, stored as the `alpha_mode` string attr.
+///
+/// [`FileNode::compute`] tags loaded frames with the resolved [`AlphaMode`] so
+/// the compositor can normalize premultiplied sources before blending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SourceAlpha {
+    /// Guess from the format: EXR is premultiplied, everything else straight.
+    #[default]
+    Auto,
+    /// RGB is independent of alpha.
+    Straight,
+    /// RGB is already multiplied by alpha.
+    Premultiplied,
+}
+
+impl SourceAlpha {
+    /// Canonical attr string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Straight => "straight",
+            Self::Premultiplied => "premultiplied",
+        }
+    }
+
+    /// Parse attr string; unknown/missing values fall back to [`Self::Auto`].
+    pub fn from_attr(s: Option<&str>) -> Self {
+        match s {
+            Some("straight") => Self::Straight,
+            Some("premultiplied") => Self::Premultiplied,
+            _ => Self::Auto,
+        }
+    }
+
+    /// Concrete alpha mode for a source file.
+    pub fn resolve(self, path: &Path) -> AlphaMode {
+        match self {
+            Self::Straight => AlphaMode::Straight,
+            Self::Premultiplied => AlphaMode::Premultiplied,
+            Self::Auto => {
+                let is_exr = path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("exr"));
+                if is_exr {
+                    AlphaMode::Premultiplied
+                } else {
+                    AlphaMode::Straight
+                }
+            }
+        }
+    }
+}
 
 /// Node that loads frames from image sequences or video files.
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -50,6 +168,15 @@ impl FileNode {
         attrs.set(A_FRAME, AttrValue::Int(start));
         attrs.set(A_WIDTH, AttrValue::UInt(64));
         attrs.set(A_HEIGHT, AttrValue::UInt(64));
+        attrs.set(A_PIXEL_ASPECT, AttrValue::Float(1.0));
+        attrs.set(
+            A_MISSING_FRAMES,
+            AttrValue::Str(MissingFramePolicy::default().as_str().to_string()),
+        );
+        attrs.set(
+            A_ALPHA_MODE,
+            AttrValue::Str(SourceAlpha::default().as_str().to_string()),
+        );
 
         Self { attrs }
     }
@@ -79,31 +206,221 @@ impl FileNode {
         self.attrs.get_i32(A_FILE_END)
     }
 
+    pub fn missing_frame_policy(&self) -> MissingFramePolicy {
+        MissingFramePolicy::from_attr(self.attrs.get_str(A_MISSING_FRAMES))
+    }
+
+    pub fn source_alpha(&self) -> SourceAlpha {
+        SourceAlpha::from_attr(self.attrs.get_str(A_ALPHA_MODE))
+    }
+
+    /// Pixel aspect ratio (1.0 = square pixels, 2.0 = 2x anamorphic)
+    pub fn pixel_aspect(&self) -> f32 {
+        self.attrs
+            .get_float(A_PIXEL_ASPECT)
+            .filter(|v| *v > 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// True for a node loaded from one still image: no frame field, or a
+    /// numbered sequence of a single frame (`ref.0001.jpg`). Never video or
+    /// an animated GIF / APNG.
+    pub fn is_still(&self) -> bool {
+        self.file_mask().is_some_and(|mask| {
+            let single_frame = self.file_start().is_some() && self.file_start() == self.file_end();
+            (!self.is_sequence() || single_frame)
+                && !media::is_video(Path::new(&mask))
+                && !self.is_animated()
+        })
+    }
+
+    /// True for a numbered image sequence: the file mask has a frame field
+    /// (`*`, `#`, `%0Nd` or `%0Nx`, see [`media::split_frame_mask`])
+    pub fn is_sequence(&self) -> bool {
+        self.file_mask()
+            .is_some_and(|mask| media::split_frame_mask(&mask, 0).is_some())
+    }
+
+    /// True for a multi-frame GIF / APNG; its frames address as `ref.gif@N`
+    /// like video. Stills of the same formats span a single source frame.
+    pub fn is_animated(&self) -> bool {
+        self.file_mask()
+            .is_some_and(|mask| media::is_anim_image(Path::new(&mask)))
+            && self.file_end() > self.file_start()
+    }
+
+    /// Hold duration of a still in frames; `None` for sequences and videos
+    pub fn still_frames(&self) -> Option<i32> {
+        if !self.is_still() {
+            return None;
+        }
+        Some(self.attrs.get_i32(A_STILL_FRAMES).unwrap_or(1).max(1))
+    }
+
+    /// Hold a still for `frames` frames (no-op for sequences and videos).
+    ///
+    /// All frames map to the single source file and share one cache slot.
+    pub fn set_still_frames(&mut self, frames: i32) {
+        if !self.is_still() {
+            return;
+        }
+        let frames = frames.max(1);
+        self.attrs.set(A_STILL_FRAMES, AttrValue::Int(frames));
+        self.attrs
+            .set(A_OUT, AttrValue::Int(self._in() + frames - 1));
+    }
+
+    /// Playback rate; timing only, so cached frames stay valid.
+    pub fn set_fps(&mut self, fps: f32) {
+        self.attrs.set(A_FPS, AttrValue::Float(fps.max(0.001)));
+    }
+
+    /// Digits of the frame field in `*` masks (0 = unpadded, default 4)
+    pub fn padding(&self) -> usize {
+        self.attrs
+            .get_u32("padding")
+            .or_else(|| self.attrs.get_i32("padding").map(|p| p.max(0) as u32))
+            .unwrap_or(4) as usize
+    }
+
+    /// Pad `*` masks to `width` digits (0 = unpadded: `shot.1.exr`, `shot.10.exr`)
+    pub fn set_padding(&mut self, width: usize) {
+        self.attrs.set("padding", AttrValue::UInt(width as u32));
+    }
+
+    pub fn set_missing_frame_policy(&mut self, policy: MissingFramePolicy) {
+        self.attrs.set(
+            A_MISSING_FRAMES,
+            AttrValue::Str(policy.as_str().to_string()),
+        );
+    }
+
+    pub fn set_source_alpha(&mut self, alpha: SourceAlpha) {
+        self.attrs
+            .set(A_ALPHA_MODE, AttrValue::Str(alpha.as_str().to_string()));
+    }
+
     // Timing methods (_in, _out, fps, dim, frame_count, frame, work_area)
     // are provided by Node trait with defaults from config.rs
 
     // --- Internal ---
 
+    /// Source path shown at comp frame `frame_idx` (same in/out mapping as
+    /// `compute`, without the missing-frame policy); `None` outside the
+    /// source range.
+    pub fn frame_path(&self, frame_idx: i32) -> Option<PathBuf> {
+        let (comp_start, comp_end) = (self._in(), self._out());
+        if comp_end < comp_start {
+            return None;
+        }
+        let local_idx = frame_idx.clamp(comp_start, comp_end) - comp_start;
+        let seq_start = self.file_start().unwrap_or(comp_start);
+        let seq_end = self.file_end().unwrap_or(comp_end);
+        let seq_frame = if self.is_still() {
+            seq_start
+        } else {
+            seq_start.saturating_add(local_idx)
+        };
+        if seq_frame > seq_end {
+            return None;
+        }
+        self.resolve_frame_path(seq_frame)
+    }
+
     /// Resolve a per-frame source path from the file mask. Public so the
     /// encode pass-through path can read source EXRs directly without going
     /// through the compositor.
+    ///
+    /// `*` masks pad to the `padding` attr (0 = unpadded); `#` / `%0Nd` /
+    /// `%0Nx` masks carry their own width, same parser as the exporter.
     pub fn resolve_frame_path(&self, frame_number: i32) -> Option<PathBuf> {
         let mask = self.file_mask()?;
-        if media::is_video(Path::new(&mask)) {
-            // Video files use @frame suffix to target specific frame
+        if media::is_video(Path::new(&mask)) || self.is_animated() {
+            // Video files and animated images use @frame suffix to target specific frame
             return Some(PathBuf::from(format!("{}@{}", mask, frame_number)));
         }
+        let path = match media::split_frame_mask(&mask, self.padding()) {
+            Some((prefix, pattern, suffix)) => PathBuf::from(format!(
+                "{}{}{}",
+                prefix,
+                pattern.format(frame_number),
+                suffix
+            )),
+            None => PathBuf::from(mask),
+        };
+
+        if media::is_svg(&path) {
+            // Vector source: rasterize at the node's size so it stays crisp
+            let (w, h) = self.dim();
+            return Some(svg::sized_path(&path, w as u32, h as u32));
+        }
+        Some(path)
+    }
 
-        if mask.contains('*') {
-            let padding = self.attrs.get_u32("padding").unwrap_or(4) as usize;
-            let mut parts = mask.splitn(2, '*');
-            let prefix = parts.next().unwrap_or_default();
-            let suffix = parts.next().unwrap_or_default();
-            let path = format!("{}{:0padding$}{}", prefix, frame_number, suffix);
-            Some(PathBuf::from(path))
-        } else {
-            Some(PathBuf::from(mask))
+    /// Nearest sequence frame that exists on disk for a missing `seq_frame`:
+    /// searches backwards first (hold last good frame), then forwards so a
+    /// gap at the head of the range still shows something. `seq_frame` may
+    /// lie outside `[seq_start, seq_end]`; only that range is searched.
+    ///
+    /// The sequence directory is listed once and candidates are looked up in
+    /// the listing, so a long gap costs one `read_dir` rather than a stat per
+    /// frame.
+    fn nearest_existing_path(
+        &self,
+        seq_frame: i32,
+        seq_start: i32,
+        seq_end: i32,
+    ) -> Option<PathBuf> {
+        let missing = media::disk_path(&self.resolve_frame_path(seq_frame)?);
+        let dir = missing
+            .parent()
+            .filter(|p| !p.as_os_str().is_empty())
+            .unwrap_or(Path::new("."));
+        let on_disk: HashSet<OsString> = std::fs::read_dir(dir)
+            .ok()?
+            .flatten()
+            .map(|entry| entry.file_name())
+            .collect();
+        (seq_start..seq_frame.min(seq_end.saturating_add(1)))
+            .rev()
+            .chain(seq_frame.max(seq_start.saturating_sub(1)) + 1..=seq_end)
+            .filter_map(|f| self.resolve_frame_path(f))
+            .find(|p| {
+                media::disk_path(p)
+                    .file_name()
+                    .is_some_and(|name| on_disk.contains(name))
+            })
+    }
+
+    /// Resolve sequence frame `seq_frame` to what should be shown. Gaps in
+    /// the sequence and frames past either end of `[seq_start, seq_end]`
+    /// (work area longer than the delivery) go through the missing-frame
+    /// policy, so playback keeps going over incomplete sequences. `None` =
+    /// placeholder (no path, or a video frame outside the clip).
+    fn frame_source(&self, seq_frame: i32, seq_start: i32, seq_end: i32) -> Option<FrameSource> {
+        let path = self
+            .resolve_frame_path(seq_frame)
+            .filter(|p| !p.as_os_str().is_empty())?;
+        let in_range = (seq_start..=seq_end).contains(&seq_frame);
+        // Video frames are indices into one container, not files
+        // (PSD layer / SVG size paths check the file itself)
+        if media::is_video(&path) {
+            return in_range.then_some(FrameSource::Path(path));
+        }
+        if in_range && media::disk_path(&path).exists() {
+            return Some(FrameSource::Path(path));
         }
+        let policy = self.missing_frame_policy();
+        warn_missing_once(&path, policy);
+        Some(match policy {
+            MissingFramePolicy::Hold => FrameSource::Path(
+                self.nearest_existing_path(seq_frame, seq_start, seq_end)
+                    .unwrap_or(path),
+            ),
+            MissingFramePolicy::Black => FrameSource::Black,
+            // load() fails and marks the frame as Error
+            MissingFramePolicy::Error => FrameSource::Path(path),
+        })
     }
 
     // placeholder_frame() provided by Node trait
@@ -170,35 +487,55 @@ impl Node for FileNode {
             return Some(self.placeholder_frame());
         }
 
-        // Map local frame_idx to absolute sequence number
+        // Map local frame_idx to absolute sequence number; a held still
+        // maps every frame to its one source and caches it once
+        let still = self.is_still();
         let seq_start = self.file_start().unwrap_or(self._in());
         let seq_end = self.file_end().unwrap_or(self._out());
-        let seq_frame = seq_start.saturating_add(local_idx);
-        if seq_frame < seq_start || seq_frame > seq_end {
-            return Some(self.placeholder_frame());
-        }
-
-        // Check cache
-        let my_uuid = self.uuid();
-        if let Some(frame) = ctx.cache.get(my_uuid, frame_idx) {
+        let seq_frame = if still {
+            seq_start
+        } else {
+            seq_start.saturating_add(local_idx)
+        };
+        let cache_idx = if still { comp_start } else { frame_idx };
+
+        // Check cache (proxy frames live under a salted key)
+        let my_uuid = ctx.proxy.cache_uuid(self.uuid());
+        let proxy = ctx.proxy.factor();
+        if let Some(frame) = ctx.cache.get(my_uuid, cache_idx) {
             return Some(frame);
         }
 
-        // Cache miss: create frame
-        let frame_path = self.resolve_frame_path(seq_frame).unwrap_or_default();
-        if frame_path.as_os_str().is_empty() {
-            return Some(self.placeholder_frame());
-        }
+        // Cache miss: create frame (gaps / missing ends per the node's
+        // missing-frame policy; the result is cached like any other frame)
+        let frame_path = match self.frame_source(seq_frame, seq_start, seq_end) {
+            Some(FrameSource::Path(path)) => path,
+            Some(FrameSource::Black) => {
+                let (w, h) = self.dim();
+                let frame = Frame::black(w.div_ceil(proxy), h.div_ceil(proxy));
+                frame.set_proxy_factor(proxy);
+                frame.set_pixel_aspect(self.pixel_aspect());
+                ctx.cache.insert(my_uuid, cache_idx, frame.clone());
+                return Some(frame);
+            }
+            None => return Some(self.placeholder_frame()),
+        };
 
-        let frame = self.frame_from_path(frame_path);
+        let alpha_mode = self.source_alpha().resolve(&frame_path);
+        let mut frame = self.frame_from_path(frame_path);
 
         // Load pixels from disk (compute() is always called from workers)
         if let Err(e) = frame.load() {
             log::warn!("Failed to load frame {}: {:?}", frame_idx, e);
+        } else if proxy > 1 {
+            // Downscale on decode: only the proxy-sized buffer is kept in cache
+            frame = frame.downscale(proxy);
         }
+        frame.set_pixel_aspect(self.pixel_aspect());
+        frame.set_alpha_mode(alpha_mode);
 
         // Insert into cache
-        ctx.cache.insert(my_uuid, frame_idx, frame.clone());
+        ctx.cache.insert(my_uuid, cache_idx, frame.clone());
 
         Some(frame)
     }
@@ -233,6 +570,17 @@ impl Node for FileNode {
         self.work_area()
     }
 
+    fn _out(&self) -> i32 {
+        // Still hold duration wins over a stale `out` (editable in the AE)
+        match self.still_frames() {
+            Some(frames) => self._in() + frames - 1,
+            None => self
+                .attrs
+                .get_i32(A_OUT)
+                .unwrap_or_else(|| self.attrs.get_i32(A_SRC_LEN).unwrap_or(DEFAULT_SRC_LEN)),
+        }
+    }
+
     fn frame_count(&self) -> i32 {
         (self._out() - self._in() + 1).max(0)
     }
@@ -249,12 +597,14 @@ impl Node for FileNode {
 use super::loader::Loader;
 use crate::entities::frame::FrameError;
 use playa_io::VideoMetadata;
+use playa_io::anim::{self, AnimInfo};
 
 impl FileNode {
     /// Detect image/video sequences from paths and create FileNodes.
     ///
     /// Analyzes file paths to detect image sequences (by trailing frame numbers)
-    /// or video files, and creates appropriate FileNode instances.
+    /// or video files, and creates appropriate FileNode instances. Several frames
+    /// of one sequence collapse to a single node; output follows input order.
     pub fn detect_from_paths(paths: Vec<PathBuf>) -> Result<Vec<FileNode>, FrameError> {
         let mut nodes = Vec::new();
 
@@ -265,10 +615,17 @@ impl FileNode {
                 continue;
             }
 
-            // Try to detect if this is part of an image sequence
-            if let Some((prefix, _number, ext, padding)) = split_sequence_path(&path)? {
-                let pattern = format!("{}*.{}", prefix, ext);
-                match detect_sequence_from_pattern(&pattern, padding) {
+            // Animated GIF / APNG: one node spanning its frames
+            if media::is_anim_image(&path)
+                && let Ok(Some(info)) = anim::probe(&path)
+            {
+                nodes.push(create_anim_node(&path, info));
+                continue;
+            }
+
+            // Try to detect the sequence this frame belongs to (siblings on disk)
+            if let Some((file_mask, start, end, padding)) = media::detect_sequence(&path) {
+                match create_sequence_node(&path, file_mask, start, end, padding) {
                     Ok(node) => nodes.push(node),
                     Err(e) => {
                         info!("Failed to detect sequence for {}: {}", path.display(), e);
@@ -283,60 +640,32 @@ impl FileNode {
             }
         }
 
-        // Deduplicate nodes by file_mask
-        let mut unique: HashMap<String, FileNode> = HashMap::new();
-        for node in nodes {
-            if let Some(mask) = node.file_mask() {
-                unique.entry(mask).or_insert(node);
-            }
-        }
+        // Deduplicate nodes by file_mask, keeping input order so callers can
+        // rely on the first node being the first path they passed
+        let mut seen: HashSet<String> = HashSet::new();
+        nodes.retain(|node| node.file_mask().is_some_and(|mask| seen.insert(mask)));
 
-        Ok(unique.into_values().collect())
+        Ok(nodes)
     }
 }
 
-/// Detect sequence from glob pattern.
-fn detect_sequence_from_pattern(pattern: &str, padding: usize) -> Result<FileNode, FrameError> {
-    let paths = glob_paths(pattern)?;
-    if paths.is_empty() {
-        return Err(FrameError::Image(format!(
-            "No files matched pattern: {}",
-            pattern
-        )));
-    }
-
-    // Group by (prefix, ext), storing (number, path, padding)
-    let mut groups: HashMap<(String, String), Vec<(usize, PathBuf, usize)>> = HashMap::new();
-
-    for path in paths {
-        if let Some((prefix, number, ext, pad)) = split_sequence_path(&path)? {
-            let key = (prefix, ext);
-            groups.entry(key).or_default().push((number, path, pad));
-        }
-    }
-
-    // Select largest group as main sequence
-    let (key, frames_data) = groups
-        .into_iter()
-        .max_by_key(|(_, v)| v.len())
-        .ok_or_else(|| FrameError::Image("No valid sequence files found".into()))?;
-
-    let (prefix, ext) = key;
-    let (min_frame, max_frame) = frames_data
-        .iter()
-        .fold((usize::MAX, 0usize), |(min_f, max_f), (num, _, _)| {
-            (min_f.min(*num), max_f.max(*num))
-        });
-
-    // Get frame dimensions from first frame
-    let first_path = &frames_data[0].1;
-    let attrs = Loader::header(first_path)?;
+/// Create FileNode spanning a detected sequence.
+///
+/// `path` is the frame the user picked; its header provides dimensions and
+/// metadata for the whole sequence. Gaps inside `start..=end` are left to the
+/// loader.
+fn create_sequence_node(
+    path: &Path,
+    file_mask: String,
+    start: i32,
+    end: i32,
+    padding: usize,
+) -> Result<FileNode, FrameError> {
+    let attrs = Loader::header(path)?;
     let width = attrs.get_u32(A_WIDTH).unwrap_or(64) as usize;
     let height = attrs.get_u32(A_HEIGHT).unwrap_or(64) as usize;
 
-    // Create FileNode
-    let file_mask = format!("{}*.{}", prefix, ext);
-    let mut node = FileNode::new(file_mask.clone(), min_frame as i32, max_frame as i32, 24.0);
+    let mut node = FileNode::new(file_mask.clone(), start, end, 24.0);
 
     // Absorb the FULL source metadata header (every `exr:*` attr: chromaticities,
     // timecode, fps, owner, comments, camera matrices, …) so it reaches the
@@ -349,15 +678,15 @@ fn detect_sequence_from_pattern(pattern: &str, padding: usize) -> Result<FileNod
     node.attrs.set(A_HEIGHT, AttrValue::UInt(height as u32));
     node.attrs.set("padding", AttrValue::UInt(padding as u32));
 
-    // Set name from first file
-    if let Some(filename) = first_path.file_stem().and_then(|s| s.to_str()) {
+    // Set name from the picked file
+    if let Some(filename) = path.file_stem().and_then(|s| s.to_str()) {
         node.attrs.set(A_NAME, AttrValue::Str(filename.to_string()));
     }
 
     info!(
         "Created sequence FileNode: {} ({} frames, {}x{})",
         file_mask,
-        frames_data.len(),
+        end - start + 1,
         width,
         height
     );
@@ -372,8 +701,16 @@ fn create_single_file_node(path: &Path) -> Result<FileNode, FrameError> {
     }
 
     let attrs = Loader::header(path)?;
-    let width = attrs.get_u32(A_WIDTH).unwrap_or(64) as usize;
-    let height = attrs.get_u32(A_HEIGHT).unwrap_or(64) as usize;
+    let mut width = attrs.get_u32(A_WIDTH).unwrap_or(64) as usize;
+    let mut height = attrs.get_u32(A_HEIGHT).unwrap_or(64) as usize;
+    if media::is_svg(path) {
+        // Vectors rasterize at comp resolution by default; resize via width/height
+        let (w, h) = svg::fit_size(
+            (width as u32, height as u32),
+            (DEFAULT_DIM.0 as u32, DEFAULT_DIM.1 as u32),
+        );
+        (width, height) = (w as usize, h as usize);
+    }
 
     let file_mask = path.to_string_lossy().to_string();
     let mut node = FileNode::new(file_mask.clone(), 0, 0, 24.0);
@@ -384,6 +721,8 @@ fn create_single_file_node(path: &Path) -> Result<FileNode, FrameError> {
 
     node.attrs.set(A_WIDTH, AttrValue::UInt(width as u32));
     node.attrs.set(A_HEIGHT, AttrValue::UInt(height as u32));
+    // One frame until the caller applies its hold duration
+    node.set_still_frames(1);
 
     if let Some(filename) = path.file_stem().and_then(|s| s.to_str()) {
         node.attrs.set(A_NAME, AttrValue::Str(filename.to_string()));
@@ -443,75 +782,37 @@ fn create_video_node(path: &Path) -> Result<FileNode, FrameError> {
     Ok(node)
 }
 
-/// Expand a glob pattern into a list of paths.
-fn glob_paths(pattern: &str) -> Result<Vec<PathBuf>, FrameError> {
-    // Normalize path separators for cross-platform glob compatibility
-    let pattern = pattern.replace('\\', "/");
-    info!("glob_paths: pattern = {}", pattern);
-
-    // Use case-insensitive matching for Windows compatibility (TGA vs tga)
-    let options = glob::MatchOptions {
-        case_sensitive: false,
-        ..Default::default()
-    };
-
-    let mut paths = Vec::new();
-    for entry in glob::glob_with(&pattern, options)
-        .map_err(|e| FrameError::Image(format!("Glob error for pattern {}: {}", pattern, e)))?
-    {
-        match entry {
-            Ok(path) => paths.push(path),
-            Err(e) => return Err(FrameError::Image(format!("Glob entry error: {}", e))),
-        }
-    }
-    info!("glob_paths: found {} files", paths.len());
-    Ok(paths)
-}
+/// Create FileNode for an animated GIF / APNG: one frame per image frame,
+/// fps from the average frame delay.
+fn create_anim_node(path: &Path, info: AnimInfo) -> FileNode {
+    let last_frame = info.frame_count as i32 - 1;
+    let mut node = FileNode::new(path.to_string_lossy().to_string(), 0, last_frame, info.fps);
 
-/// Split a sequence filename into (prefix, number, ext, padding).
-///
-/// Example: "/path/seq.0001.exr" -> ("/path/seq.", 1, "exr", 4)
-fn split_sequence_path(path: &Path) -> Result<Option<(String, usize, String, usize)>, FrameError> {
-    let ext = match path.extension().and_then(|s| s.to_str()) {
-        Some(e) => e.to_string(),
-        None => return Ok(None),
-    };
-
-    let stem = match path.file_stem().and_then(|s| s.to_str()) {
-        Some(s) => s,
-        None => return Ok(None),
-    };
-
-    // Find trailing digits in stem
-    let mut digit_start = stem.len();
-    for (i, ch) in stem.char_indices().rev() {
-        if ch.is_ascii_digit() {
-            digit_start = i;
-        } else {
-            break;
-        }
-    }
+    node.attrs.set(A_WIDTH, AttrValue::UInt(info.width));
+    node.attrs.set(A_HEIGHT, AttrValue::UInt(info.height));
+    node.attrs.set("padding", AttrValue::UInt(0));
+    node.attrs
+        .set("frames", AttrValue::UInt(info.frame_count as u32));
+    // Per-frame delays as authored (playback uses the average fps)
+    let delays = info.delays_ms.iter().map(|&ms| AttrValue::Float(ms as f32));
+    node.attrs
+        .set("anim:delays_ms", AttrValue::List(delays.collect()));
+    node.attrs.add_flags("anim:delays_ms", AttrFlags::SOURCE);
 
-    if digit_start == stem.len() {
-        // No trailing digits -> not a sequence frame
-        return Ok(None);
+    if let Some(filename) = path.file_stem().and_then(|s| s.to_str()) {
+        node.attrs.set(A_NAME, AttrValue::Str(filename.to_string()));
     }
 
-    let number_str = &stem[digit_start..];
-    let number = number_str
-        .parse::<usize>()
-        .map_err(|e| FrameError::Image(format!("Invalid frame number '{}': {}", number_str, e)))?;
-    let prefix_local = &stem[..digit_start];
-    let padding = number_str.len();
-
-    // Build full prefix including parent directory
-    let parent = path.parent().map(|p| p.to_string_lossy().to_string());
-    let full_prefix = match parent {
-        Some(p) if !p.is_empty() => format!("{}/{}", p, prefix_local),
-        _ => prefix_local.to_string(),
-    };
+    info!(
+        "Created animated FileNode: {} ({} frames @ {} fps, {}x{})",
+        path.display(),
+        info.frame_count,
+        info.fps,
+        info.width,
+        info.height
+    );
 
-    Ok(Some((full_prefix, number, ext, padding)))
+    node
 }
 
 #[cfg(test)]
@@ -528,6 +829,204 @@ mod tests {
         assert_eq!(node.frame_count(), 100);
     }
 
+    #[test]
+    fn test_missing_frame_policy_attr() {
+        let mut node = FileNode::new("test.*.exr".to_string(), 1, 100, 24.0);
+        assert_eq!(node.missing_frame_policy(), MissingFramePolicy::Hold);
+
+        node.set_missing_frame_policy(MissingFramePolicy::Black);
+        assert_eq!(node.attrs.get_str(A_MISSING_FRAMES), Some("black"));
+        assert_eq!(node.missing_frame_policy(), MissingFramePolicy::Black);
+
+        // Projects saved before the attr existed fall back to Hold
+        node.attrs.remove(A_MISSING_FRAMES);
+        assert_eq!(node.missing_frame_policy(), MissingFramePolicy::Hold);
+    }
+
+    #[test]
+    fn test_missing_frames_at_sequence_ends() {
+        let dir = std::env::temp_dir().join(format!("playa_gap_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for f in 1005..=1007 {
+            std::fs::write(dir.join(format!("shot.{f}.exr")), b"").unwrap();
+        }
+        let file = |f: i32| dir.join(format!("shot.{f}.exr"));
+        let mask = dir.join("shot.*.exr").to_string_lossy().to_string();
+        // Comp expects 1001-1010, only 1005-1007 were delivered
+        let mut node = FileNode::new(mask, 1001, 1010, 24.0);
+        let source = |node: &FileNode, f: i32| node.frame_source(f, 1001, 1010);
+
+        assert_eq!(source(&node, 1006), Some(FrameSource::Path(file(1006))));
+        // Hold: head gap shows the first delivered frame, tail the last
+        assert_eq!(source(&node, 1001), Some(FrameSource::Path(file(1005))));
+        assert_eq!(source(&node, 1009), Some(FrameSource::Path(file(1007))));
+        // Past the file range (work area longer than the sequence)
+        assert_eq!(
+            node.frame_source(1012, 1001, 1010),
+            Some(FrameSource::Path(file(1007)))
+        );
+
+        node.set_missing_frame_policy(MissingFramePolicy::Black);
+        assert_eq!(source(&node, 1002), Some(FrameSource::Black));
+        node.set_missing_frame_policy(MissingFramePolicy::Error);
+        assert_eq!(source(&node, 1002), Some(FrameSource::Path(file(1002))));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_frame_mask_patterns() {
+        let path = |mask: &str, frame: i32| {
+            FileNode::new(mask.to_string(), 1, 100, 24.0)
+                .resolve_frame_path(frame)
+                .unwrap()
+        };
+        assert_eq!(path("shot_#.exr", 7), PathBuf::from("shot_7.exr"));
+        assert_eq!(path("shot_%05d.exr", 7), PathBuf::from("shot_00007.exr"));
+        assert_eq!(path("shot.*.exr", 7), PathBuf::from("shot.0007.exr"));
+
+        let mut node = FileNode::new("shot.*.exr".to_string(), 1, 100, 24.0);
+        node.set_padding(0);
+        assert_eq!(
+            node.resolve_frame_path(1),
+            Some(PathBuf::from("shot.1.exr"))
+        );
+        assert_eq!(
+            node.resolve_frame_path(12),
+            Some(PathBuf::from("shot.12.exr"))
+        );
+        assert!(node.is_sequence() && !node.is_still());
+
+        // Edited as a plain int in the Attribute Editor
+        node.attrs.set("padding", AttrValue::Int(3));
+        assert_eq!(
+            node.resolve_frame_path(-4),
+            Some(PathBuf::from("shot.-04.exr"))
+        );
+
+        let still = FileNode::new("shot.1.exr".to_string(), 1, 1, 24.0);
+        assert!(still.is_still() && !still.is_sequence());
+        assert_eq!(
+            still.resolve_frame_path(5),
+            Some(PathBuf::from("shot.1.exr"))
+        );
+    }
+
+    #[test]
+    fn test_source_alpha_attr() {
+        let mut node = FileNode::new("test.*.exr".to_string(), 1, 100, 24.0);
+        assert_eq!(node.source_alpha(), SourceAlpha::Auto);
+        assert_eq!(
+            SourceAlpha::Auto.resolve(Path::new("a.0001.EXR")),
+            AlphaMode::Premultiplied
+        );
+        assert_eq!(
+            SourceAlpha::Auto.resolve(Path::new("a.0001.png")),
+            AlphaMode::Straight
+        );
+
+        node.set_source_alpha(SourceAlpha::Straight);
+        assert_eq!(node.attrs.get_str(A_ALPHA_MODE), Some("straight"));
+        assert_eq!(
+            node.source_alpha().resolve(Path::new("a.0001.exr")),
+            AlphaMode::Straight
+        );
+
+        node.attrs.remove(A_ALPHA_MODE);
+        assert_eq!(node.source_alpha(), SourceAlpha::Auto);
+    }
+
+    #[test]
+    fn test_still_hold_duration() {
+        let mut node = FileNode::new("/plates/ref.jpg".to_string(), 0, 0, 24.0);
+        assert!(node.is_still());
+        assert_eq!(node.frame_count(), 1);
+
+        node.set_still_frames(48);
+        assert_eq!(node.still_frames(), Some(48));
+        assert_eq!((node._in(), node._out()), (0, 47));
+        // Every frame resolves to the same source file
+        assert_eq!(node.resolve_frame_path(0), node.resolve_frame_path(47));
+
+        // Editing the attr directly (Attribute Editor) retimes the node
+        node.attrs.set(A_STILL_FRAMES, AttrValue::Int(12));
+        assert_eq!(node.frame_count(), 12);
+
+        // Sequences and videos ignore the hold duration
+        let mut seq = FileNode::new("test.*.exr".to_string(), 1, 100, 24.0);
+        seq.set_still_frames(5);
+        assert_eq!(seq.still_frames(), None);
+        assert_eq!(seq.frame_count(), 100);
+        assert!(!FileNode::new("clip.mov".to_string(), 0, 99, 24.0).is_still());
+
+        // A lone numbered file detects as a one-frame sequence: still a still
+        let mut numbered = FileNode::new("ref.*.jpg".to_string(), 1, 1, 24.0);
+        assert!(numbered.is_sequence() && numbered.is_still());
+        numbered.set_still_frames(24);
+        assert_eq!(numbered.frame_count(), 24);
+        assert_eq!(numbered.frame_path(23), numbered.frame_path(0));
+        assert_eq!(numbered.frame_path(0), Some(PathBuf::from("ref.0001.jpg")));
+    }
+
+    #[test]
+    fn test_sequence_fps_override() {
+        let mut seq = FileNode::new("test.*.exr".to_string(), 1, 100, 24.0);
+        assert!(seq.is_sequence());
+        seq.set_fps(25.0);
+        assert_eq!(seq.fps(), 25.0);
+        // Timing only: the frame range is unchanged
+        assert_eq!((seq._in(), seq._out()), (1, 100));
+        assert!(!FileNode::new("clip.mov".to_string(), 0, 99, 24.0).is_sequence());
+    }
+
+    #[test]
+    fn test_animated_image_frames() {
+        let anim = FileNode::new("/refs/walk.gif".to_string(), 0, 2, 20.0);
+        assert!(anim.is_animated() && !anim.is_still());
+        assert_eq!(anim.frame_count(), 3);
+        let frame = anim.resolve_frame_path(1).unwrap();
+        assert_eq!(frame, PathBuf::from("/refs/walk.gif@1"));
+        assert_eq!(media::disk_path(&frame), PathBuf::from("/refs/walk.gif"));
+
+        // Single-frame GIF / plain PNG: a still
+        let still = FileNode::new("/refs/logo.gif".to_string(), 0, 0, 24.0);
+        assert!(!still.is_animated() && still.is_still());
+        assert_eq!(
+            still.resolve_frame_path(0),
+            Some(PathBuf::from("/refs/logo.gif"))
+        );
+    }
+
+    #[test]
+    fn test_svg_rasterizes_at_node_size() {
+        let dir = std::env::temp_dir().join(format!("playa_svg_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("logo.svg");
+        std::fs::write(
+            &path,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="200" height="100">
+                <circle cx="100" cy="50" r="40" fill="white"/>
+            </svg>"#,
+        )
+        .unwrap();
+
+        // Imported at comp resolution, aspect kept
+        let mut node = create_single_file_node(&path).unwrap();
+        assert_eq!(node.dim(), (1920, 960));
+        assert!(node.is_still());
+
+        for (w, h) in [(1920, 960), (400, 200)] {
+            node.attrs.set(A_WIDTH, AttrValue::UInt(w));
+            node.attrs.set(A_HEIGHT, AttrValue::UInt(h));
+            let frame_path = node.resolve_frame_path(0).unwrap();
+            assert_eq!(media::disk_path(&frame_path), path);
+            let frame = Loader::load(&frame_path).unwrap();
+            assert_eq!(frame.resolution(), (w as usize, h as usize));
+        }
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_file_node_trait() {
         let node = FileNode::new("test.*.exr".to_string(), 1, 100, 24.0);
@@ -543,27 +1042,21 @@ mod tests {
             return;
         }
 
-        // Test split_sequence_path
-        let result = split_sequence_path(test_path).unwrap();
-        println!("split_sequence_path result: {:?}", result);
+        // Test media::detect_sequence
+        let result = media::detect_sequence(test_path);
+        println!("detect_sequence result: {:?}", result);
         assert!(result.is_some(), "Should detect as sequence");
 
-        let (prefix, number, ext, padding) = result.unwrap();
-        println!("prefix: {}", prefix);
-        println!("number: {}", number);
-        println!("ext: {}", ext);
+        let (mask, start, end, padding) = result.unwrap();
+        println!("mask: {}", mask);
+        println!("start: {}", start);
+        println!("end: {}", end);
         println!("padding: {}", padding);
-
-        // Test glob pattern
-        let pattern = format!("{}*.{}", prefix, ext);
-        println!("glob pattern: {}", pattern);
-
-        let paths = glob_paths(&pattern).unwrap();
-        println!("glob found {} files", paths.len());
         assert!(
-            paths.len() > 1,
-            "Should find multiple files, got {}",
-            paths.len()
+            end > start,
+            "Should find multiple frames, got {}..{}",
+            start,
+            end
         );
     }
 
@@ -33,8 +33,10 @@ use std::sync::{Arc, Mutex};
 use half::f16 as F16;
 
 // Import utilities
-use super::keys::{A_HEIGHT, A_WIDTH};
-use crate::entities::Attrs;
+use super::keys::{
+    A_ALPHA_MODE, A_COLOR_SPACE, A_HEIGHT, A_LOAD_ERROR, A_PIXEL_ASPECT, A_PROXY_FACTOR, A_WIDTH,
+};
+use crate::entities::{AttrValue, Attrs};
 
 /// Pixel buffer format - stores different precision levels
 #[derive(Debug, Clone)]
@@ -53,11 +55,55 @@ pub enum PixelFormat {
 }
 
 /// Crop alignment mode
-#[derive(Debug, Clone, Copy, PartialEq)]
-#[allow(dead_code)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
 pub enum CropAlign {
-    Center,  // Center-align when cropping or padding
-    LeftTop, // Align to top-left corner (reserved for future use)
+    #[default]
+    Center, // Center-align when cropping or padding
+    LeftTop, // Align to top-left corner
+}
+
+impl CropAlign {
+    /// All alignments in selector order
+    pub const ALL: [CropAlign; 2] = [CropAlign::Center, CropAlign::LeftTop];
+
+    /// Display name for UI selectors
+    pub fn label(self) -> &'static str {
+        match self {
+            CropAlign::Center => "Center",
+            CropAlign::LeftTop => "Left-top",
+        }
+    }
+
+    /// Offset of a `inner` span inside an `outer` span
+    fn offset(self, outer: usize, inner: usize) -> usize {
+        match self {
+            CropAlign::Center => outer.saturating_sub(inner) / 2,
+            CropAlign::LeftTop => 0,
+        }
+    }
+}
+
+/// How a frame is fitted into a target resolution of a different shape
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum FitMode {
+    #[default]
+    Crop, // Scale to cover the target, crop the overflow
+    Letterbox, // Scale to fit inside the target, pad with black
+    Stretch,   // Scale each axis independently (distorts)
+}
+
+impl FitMode {
+    /// All fit modes in selector order
+    pub const ALL: [FitMode; 3] = [FitMode::Crop, FitMode::Letterbox, FitMode::Stretch];
+
+    /// Display name for UI selectors
+    pub fn label(self) -> &'static str {
+        match self {
+            FitMode::Crop => "Crop",
+            FitMode::Letterbox => "Letterbox",
+            FitMode::Stretch => "Stretch",
+        }
+    }
 }
 
 /// Pixel bit depth for Frame construction
@@ -77,6 +123,523 @@ pub enum TonemapMode {
     #[default]
     ACES, // ACES filmic tone mapping curve
     Reinhard, // Reinhard tone mapping (photographic)
+    ReinhardExtended, // Reinhard with a white point that maps to 1.0
+    Hable, // Hable / Uncharted 2 filmic curve
+}
+
+/// Input value that Reinhard-extended maps to pure white
+pub const REINHARD_WHITE: f32 = 4.0;
+
+/// Linear white point of the Hable curve (Uncharted 2 default)
+const HABLE_WHITE: f32 = 11.2;
+
+/// Exposure bias applied before the Hable curve
+const HABLE_EXPOSURE_BIAS: f32 = 2.0;
+
+impl TonemapMode {
+    /// All modes in selector order
+    pub const ALL: [TonemapMode; 5] = [
+        TonemapMode::ACES,
+        TonemapMode::Reinhard,
+        TonemapMode::ReinhardExtended,
+        TonemapMode::Hable,
+        TonemapMode::Clamp,
+    ];
+
+    /// Display name for UI selectors
+    pub fn label(self) -> &'static str {
+        match self {
+            TonemapMode::Clamp => "Clamp",
+            TonemapMode::ACES => "ACES",
+            TonemapMode::Reinhard => "Reinhard",
+            TonemapMode::ReinhardExtended => "Reinhard (extended)",
+            TonemapMode::Hable => "Hable",
+        }
+    }
+
+    /// Map one linear channel value to display range [0,1]
+    pub fn apply(self, x: f32) -> f32 {
+        match self {
+            TonemapMode::Clamp => tonemap_clamp(x),
+            TonemapMode::ACES => tonemap_aces(x),
+            TonemapMode::Reinhard => tonemap_reinhard(x),
+            TonemapMode::ReinhardExtended => tonemap_reinhard_extended(x, REINHARD_WHITE),
+            TonemapMode::Hable => tonemap_hable(x),
+        }
+    }
+}
+
+/// Clamp to [0,1] (no highlight rolloff)
+pub fn tonemap_clamp(x: f32) -> f32 {
+    x.clamp(0.0, 1.0)
+}
+
+/// ACES filmic tone mapping (Narkowicz 2015)
+pub fn tonemap_aces(x: f32) -> f32 {
+    // Negative input would flip the rational curve back up to white
+    let x = x.max(0.0);
+    let a = 2.51;
+    let b = 0.03;
+    let c = 2.43;
+    let d = 0.59;
+    let e = 0.14;
+    ((x * (a * x + b)) / (x * (c * x + d) + e)).clamp(0.0, 1.0)
+}
+
+/// Reinhard photographic tone mapping: `x / (1 + x)`
+pub fn tonemap_reinhard(x: f32) -> f32 {
+    let x = x.max(0.0);
+    (x / (1.0 + x)).clamp(0.0, 1.0)
+}
+
+/// Reinhard with white point: `x * (1 + x / white²) / (1 + x)`, so `white` maps to 1.0
+pub fn tonemap_reinhard_extended(x: f32, white: f32) -> f32 {
+    let x = x.max(0.0);
+    (x * (1.0 + x / (white * white)) / (1.0 + x)).clamp(0.0, 1.0)
+}
+
+/// Hable / Uncharted 2 filmic curve, normalized so `HABLE_WHITE` maps to 1.0
+pub fn tonemap_hable(x: f32) -> f32 {
+    fn partial(x: f32) -> f32 {
+        let a = 0.15; // shoulder strength
+        let b = 0.50; // linear strength
+        let c = 0.10; // linear angle
+        let d = 0.20; // toe strength
+        let e = 0.02; // toe numerator
+        let f = 0.30; // toe denominator
+        ((x * (a * x + c * b) + d * e) / (x * (a * x + b) + d * f)) - e / f
+    }
+    let x = x.max(0.0);
+    (partial(x * HABLE_EXPOSURE_BIAS) / partial(HABLE_WHITE)).clamp(0.0, 1.0)
+}
+
+/// Transfer function the RGB values of a frame are encoded with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSpace {
+    Linear, // Scene-linear (EXR, composite math)
+    Srgb,   // sRGB curve (8-bit images, display-referred)
+}
+
+impl ColorSpace {
+    /// Attr value stored under `A_COLOR_SPACE`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ColorSpace::Linear => "linear",
+            ColorSpace::Srgb => "srgb",
+        }
+    }
+
+    /// Parse an `A_COLOR_SPACE` attr value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "linear" => Some(ColorSpace::Linear),
+            "srgb" => Some(ColorSpace::Srgb),
+            _ => None,
+        }
+    }
+}
+
+/// How the RGB values of a frame relate to its alpha
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphaMode {
+    Straight,      // RGB independent of alpha (PNG, compositor math)
+    Premultiplied, // RGB already multiplied by alpha (EXR)
+}
+
+impl AlphaMode {
+    /// Attr value stored under `A_ALPHA_MODE`
+    pub fn as_str(self) -> &'static str {
+        match self {
+            AlphaMode::Straight => "straight",
+            AlphaMode::Premultiplied => "premultiplied",
+        }
+    }
+
+    /// Parse an `A_ALPHA_MODE` attr value
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "straight" => Some(AlphaMode::Straight),
+            "premultiplied" => Some(AlphaMode::Premultiplied),
+            _ => None,
+        }
+    }
+}
+
+/// sRGB-encoded value → linear (IEC 61966-2-1, 0.04045 breakpoint)
+pub fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+/// Linear value → sRGB-encoded (inverse of [`srgb_to_linear`])
+pub fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Luminance of scene value 1.0 when encoding PQ (BT.2408 HDR reference white)
+pub const PQ_REFERENCE_WHITE: f32 = 203.0;
+
+/// Absolute luminance in cd/m² → SMPTE ST 2084 (PQ) signal, 0..1 for 0..10000 nits
+pub fn linear_to_pq(nits: f32) -> f32 {
+    const M1: f32 = 2610.0 / 16384.0;
+    const M2: f32 = 2523.0 / 4096.0 * 128.0;
+    const C1: f32 = 3424.0 / 4096.0;
+    const C2: f32 = 2413.0 / 4096.0 * 32.0;
+    const C3: f32 = 2392.0 / 4096.0 * 32.0;
+    let y = (nits / 10000.0).clamp(0.0, 1.0).powf(M1);
+    ((C1 + C2 * y) / (1.0 + C3 * y)).powf(M2)
+}
+
+/// Linear Rec.709 RGB → linear Rec.2020 RGB (same D65 white, ITU-R BT.2087)
+pub fn rec709_to_rec2020(rgb: [f32; 3]) -> [f32; 3] {
+    const M: [[f32; 3]; 3] = [
+        [0.6274, 0.3293, 0.0433],
+        [0.0691, 0.9195, 0.0114],
+        [0.0164, 0.0880, 0.8956],
+    ];
+    M.map(|row| row[0] * rgb[0] + row[1] * rgb[1] + row[2] * rgb[2])
+}
+
+/// Resampling filter for [`Frame::resize`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum ResizeFilter {
+    Nearest, // Pixel replication, no blending
+    #[default]
+    Bilinear, // Triangle filter (box-like average when downscaling)
+    Lanczos, // Lanczos-3: sharpest, may ring on hard edges
+}
+
+impl ResizeFilter {
+    /// All filters in selector order
+    pub const ALL: [ResizeFilter; 3] = [
+        ResizeFilter::Nearest,
+        ResizeFilter::Bilinear,
+        ResizeFilter::Lanczos,
+    ];
+
+    /// Display name for UI selectors
+    pub fn label(self) -> &'static str {
+        match self {
+            ResizeFilter::Nearest => "Nearest",
+            ResizeFilter::Bilinear => "Bilinear",
+            ResizeFilter::Lanczos => "Lanczos",
+        }
+    }
+
+    /// Kernel radius in source pixels at 1:1 scale
+    fn radius(self) -> f32 {
+        match self {
+            ResizeFilter::Nearest => 0.5,
+            ResizeFilter::Bilinear => 1.0,
+            ResizeFilter::Lanczos => 3.0,
+        }
+    }
+
+    /// Kernel weight at distance `x` (in filter-scaled source pixels)
+    fn weight(self, x: f32) -> f32 {
+        let x = x.abs();
+        match self {
+            ResizeFilter::Nearest => {
+                if x < 0.5 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            ResizeFilter::Bilinear => (1.0 - x).max(0.0),
+            ResizeFilter::Lanczos => {
+                if x < 1e-6 {
+                    1.0
+                } else if x < 3.0 {
+                    let px = std::f32::consts::PI * x;
+                    3.0 * px.sin() * (px / 3.0).sin() / (px * px)
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+/// Per-output-pixel source taps `(index, weight)` for a 1D resample
+/// from `src` to `dst` pixels (pixel-center aligned, edges clamped).
+///
+/// When downscaling the kernel widens by the scale so every source pixel
+/// contributes (no aliasing); weights are normalized to sum to 1.
+fn resample_taps(src: usize, dst: usize, filter: ResizeFilter) -> Vec<Vec<(usize, f32)>> {
+    let scale = src as f32 / dst as f32;
+    let last = src.saturating_sub(1);
+    (0..dst)
+        .map(|i| {
+            if filter == ResizeFilter::Nearest {
+                let x = (((i as f32 + 0.5) * scale) as usize).min(last);
+                return vec![(x, 1.0)];
+            }
+            let center = (i as f32 + 0.5) * scale - 0.5;
+            let fscale = scale.max(1.0);
+            let support = filter.radius() * fscale;
+            let lo = (center - support).floor() as i64;
+            let hi = (center + support).ceil() as i64;
+            let mut taps: Vec<(usize, f32)> = (lo..=hi)
+                .filter_map(|j| {
+                    let w = filter.weight((j as f32 - center) / fscale);
+                    (w != 0.0).then(|| (j.clamp(0, last as i64) as usize, w))
+                })
+                .collect();
+            let sum: f32 = taps.iter().map(|t| t.1).sum();
+            if sum.abs() > 1e-6 {
+                for t in &mut taps {
+                    t.1 /= sum;
+                }
+            }
+            taps
+        })
+        .collect()
+}
+
+/// Resample interleaved f32 RGBA along one axis.
+fn resample_axis(
+    src: &[f32],
+    (w, h): (usize, usize),
+    taps: &[Vec<(usize, f32)>],
+    horizontal: bool,
+) -> Vec<f32> {
+    let (nw, nh) = if horizontal {
+        (taps.len(), h)
+    } else {
+        (w, taps.len())
+    };
+    let mut out = Vec::with_capacity(nw * nh * 4);
+    for y in 0..nh {
+        for x in 0..nw {
+            let mut acc = [0.0f32; 4];
+            let pixel_taps = if horizontal { &taps[x] } else { &taps[y] };
+            for &(s, weight) in pixel_taps {
+                let i = if horizontal {
+                    (y * w + s) * 4
+                } else {
+                    (s * w + x) * 4
+                };
+                for c in 0..4 {
+                    acc[c] += src[i + c] * weight;
+                }
+            }
+            out.extend_from_slice(&acc);
+        }
+    }
+    out
+}
+
+/// Copy the top-left `w x h` of RGBA rows (`stride` pixels wide) onto a
+/// `nw x nh` canvas of `zero` RGB / `one` alpha at `(dx, dy)`.
+fn pad_rgba<T: Copy>(
+    src: &[T],
+    stride: usize,
+    (w, h): (usize, usize),
+    (nw, nh): (usize, usize),
+    (dx, dy): (usize, usize),
+    zero: T,
+    one: T,
+) -> Vec<T> {
+    let mut out = [zero, zero, zero, one].repeat(nw * nh);
+    for y in 0..h {
+        let s = y * stride * 4;
+        let d = ((dy + y) * nw + dx) * 4;
+        out[d..d + w * 4].copy_from_slice(&src[s..s + w * 4]);
+    }
+    out
+}
+
+/// Map the RGB channels of interleaved RGBA pixels, alpha through `alpha`
+fn map_rgb<T: Copy, U>(src: &[T], rgb: impl Fn(T) -> U, alpha: impl Fn(T) -> U) -> Vec<U> {
+    src.chunks_exact(4)
+        .flat_map(|px| [rgb(px[0]), rgb(px[1]), rgb(px[2]), alpha(px[3])])
+        .collect()
+}
+
+/// Scale the RGB channels of interleaved RGBA pixels by `factor(alpha)`
+fn scale_rgb<T: Copy>(
+    src: &[T],
+    to_f32: impl Fn(T) -> f32,
+    from_f32: impl Fn(f32) -> T,
+    factor: impl Fn(f32) -> f32,
+) -> Vec<T> {
+    src.chunks_exact(4)
+        .flat_map(|px| {
+            let k = factor(to_f32(px[3]));
+            [
+                from_f32(to_f32(px[0]) * k),
+                from_f32(to_f32(px[1]) * k),
+                from_f32(to_f32(px[2]) * k),
+                px[3],
+            ]
+        })
+        .collect()
+}
+
+/// Scale the alpha of interleaved RGBA pixels by one `coverage` entry per pixel
+fn scale_alpha<T: Copy>(src: &[T], coverage: &[f32], scale: impl Fn(T, f32) -> T) -> Vec<T> {
+    src.chunks_exact(4)
+        .zip(coverage)
+        .flat_map(|(px, &k)| [px[0], px[1], px[2], scale(px[3], k)])
+        .collect()
+}
+
+/// Alpha-blend a straight RGBA overlay (one entry per pixel) over
+/// interleaved RGBA pixels. `encode` maps overlay RGB into the target transfer.
+fn paint_rgba<T: Copy>(
+    src: &[T],
+    overlay: &[[f32; 4]],
+    to_f32: impl Fn(T) -> f32,
+    from_f32: impl Fn(f32) -> T,
+    encode: impl Fn(f32) -> f32,
+    premultiplied: bool,
+) -> Vec<T> {
+    let mut out = src.to_vec();
+    for (px, o) in out.chunks_exact_mut(4).zip(overlay) {
+        let a = o[3];
+        if a <= 0.0 {
+            continue;
+        }
+        let da = to_f32(px[3]);
+        let out_a = a + da * (1.0 - a);
+        for c in 0..3 {
+            let oc = encode(o[c]);
+            let dc = to_f32(px[c]);
+            px[c] = from_f32(if premultiplied {
+                oc * a + dc * (1.0 - a)
+            } else {
+                (oc * a + dc * da * (1.0 - a)) / out_a
+            });
+        }
+        px[3] = from_f32(out_a);
+    }
+    out
+}
+
+/// Pixel rectangle in image space (top-left origin, Y-down).
+///
+/// Region of interest for clipped compositing and [`Frame::region_stats`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PixelRect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl PixelRect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Exclusive right edge
+    pub fn right(&self) -> usize {
+        self.x + self.width
+    }
+
+    /// Exclusive bottom edge
+    pub fn bottom(&self) -> usize {
+        self.y + self.height
+    }
+
+    /// Part of the rect inside a `width x height` image; `None` if nothing overlaps.
+    pub fn clamp_to(&self, width: usize, height: usize) -> Option<PixelRect> {
+        let (x1, y1) = (self.right().min(width), self.bottom().min(height));
+        (self.x < x1 && self.y < y1)
+            .then(|| PixelRect::new(self.x, self.y, x1 - self.x, y1 - self.y))
+    }
+
+    /// Same region at 1/`factor` resolution (proxy frames), rounded outward.
+    pub fn downscale(&self, factor: usize) -> PixelRect {
+        let f = factor.max(1);
+        let (x, y) = (self.x / f, self.y / f);
+        PixelRect::new(
+            x,
+            y,
+            self.right().div_ceil(f) - x,
+            self.bottom().div_ceil(f) - y,
+        )
+    }
+}
+
+/// Per-channel RGBA statistics over a [`PixelRect`] (U8 normalized to 0..1).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RegionStats {
+    pub min: [f32; 4],
+    pub max: [f32; 4],
+    pub mean: [f32; 4],
+    /// Pixels sampled (rect clamped to the frame)
+    pub pixels: usize,
+}
+
+/// Feed `src` into `hasher` as normalized little-endian f32 samples.
+///
+/// `-0.0` folds to `0.0` and every NaN to the canonical quiet NaN, so equal
+/// pixel values hash the same whatever their storage or bit pattern.
+fn hash_samples<T: Copy>(hasher: &mut sha2::Sha256, src: &[T], to_f32: impl Fn(T) -> f32) {
+    use sha2::Digest;
+
+    // Chunked so a 4K f32 frame never needs a second full-size buffer
+    let mut bytes = Vec::with_capacity(4096 * 4);
+    for chunk in src.chunks(4096) {
+        bytes.clear();
+        for &v in chunk {
+            let v = to_f32(v);
+            let v = if v.is_nan() {
+                f32::NAN
+            } else if v == 0.0 {
+                0.0
+            } else {
+                v
+            };
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        hasher.update(&bytes);
+    }
+}
+
+/// Min / max / mean of the RGBA pixels of `src` (`w` wide) inside `rect`.
+fn region_stats<T: Copy>(
+    src: &[T],
+    w: usize,
+    rect: PixelRect,
+    to_f32: impl Fn(T) -> f32,
+) -> RegionStats {
+    let mut min = [f32::INFINITY; 4];
+    let mut max = [f32::NEG_INFINITY; 4];
+    // f64 sums: 4K regions overflow f32 precision
+    let mut sum = [0.0f64; 4];
+    for y in rect.y..rect.bottom() {
+        let row = &src[(y * w + rect.x) * 4..(y * w + rect.right()) * 4];
+        for px in row.chunks_exact(4) {
+            for c in 0..4 {
+                let v = to_f32(px[c]);
+                min[c] = min[c].min(v);
+                max[c] = max[c].max(v);
+                sum[c] += v as f64;
+            }
+        }
+    }
+    let pixels = rect.width * rect.height;
+    RegionStats {
+        min,
+        max,
+        mean: sum.map(|s| (s / pixels as f64) as f32),
+        pixels,
+    }
 }
 
 /// Frame loading status (for cache indicator)
@@ -242,6 +805,15 @@ impl Frame {
         Self::new(width, height, PixelDepth::U8)
     }
 
+    /// Create opaque black U8 frame with `Loaded` status (missing-frame fill)
+    pub fn black(width: usize, height: usize) -> Self {
+        let mut buffer = vec![0u8; width * height * 4];
+        for px in buffer.chunks_exact_mut(4) {
+            px[3] = 255;
+        }
+        Self::from_u8_buffer_with_status(buffer, width, height, FrameStatus::Loaded)
+    }
+
     /// Convenience method: Create 16-bit half-float F16 frame
     /// Note: Rarely used - EXR loader creates F16 frames directly via PixelBuffer
     #[allow(dead_code)]
@@ -338,8 +910,8 @@ impl Frame {
         }
     }
 
-    /// Create frame from any PixelBuffer (used by Loader)
-    pub(crate) fn from_buffer(
+    /// Create frame from any PixelBuffer (used by Loader and exporters)
+    pub fn from_buffer(
         buffer: PixelBuffer,
         pixel_format: PixelFormat,
         width: usize,
@@ -511,11 +1083,18 @@ impl Frame {
 
         match super::loader::Loader::load(&path).and_then(|src| self.ingest_loaded_frame(src)) {
             Ok(mem_size) => {
-                self.data.lock().unwrap().status = FrameStatus::Loaded;
+                let mut data = self.data.lock().unwrap();
+                data.status = FrameStatus::Loaded;
+                data.attrs.remove(A_LOAD_ERROR);
                 Ok(mem_size)
             }
             Err(e) => {
-                self.data.lock().unwrap().status = FrameStatus::Error;
+                let mut data = self.data.lock().unwrap();
+                data.status = FrameStatus::Error;
+                data.attrs.set(
+                    A_LOAD_ERROR,
+                    AttrValue::Str(format!("{}: {}", path.display(), e)),
+                );
                 Err(e)
             }
         }
@@ -629,6 +1208,7 @@ impl Frame {
                 )));
                 data.pixel_format = PixelFormat::Rgba8;
                 data.status = FrameStatus::Header;
+                data.attrs.remove(A_LOAD_ERROR);
 
                 trace!("Reset error to Header: {}x{}", data.width, data.height);
                 Ok(0)
@@ -876,7 +1456,7 @@ impl Frame {
     ///
     /// # Arguments
     ///
-    /// - `mode`: Tonemapping algorithm (Clamp, ACES, Reinhard)
+    /// - `mode`: Tonemapping algorithm (see [`TonemapMode::apply`])
     ///
     /// # Returns
     ///
@@ -909,32 +1489,7 @@ impl Frame {
                     let b = chunk[2].to_f32();
                     let a = chunk[3].to_f32();
 
-                    let (r_tm, g_tm, b_tm) = match mode {
-                        TonemapMode::Clamp => {
-                            (r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0))
-                        }
-                        TonemapMode::ACES => {
-                            // ACES filmic tone mapping (Narkowicz 2015)
-                            let tonemap_aces = |x: f32| {
-                                let a = 2.51;
-                                let b = 0.03;
-                                let c = 2.43;
-                                let d = 0.59;
-                                let e = 0.14;
-                                ((x * (a * x + b)) / (x * (c * x + d) + e)).clamp(0.0, 1.0)
-                            };
-                            (tonemap_aces(r), tonemap_aces(g), tonemap_aces(b))
-                        }
-                        TonemapMode::Reinhard => {
-                            // Reinhard photographic tone mapping
-                            let tonemap_reinhard = |x: f32| (x / (1.0 + x)).clamp(0.0, 1.0);
-                            (
-                                tonemap_reinhard(r),
-                                tonemap_reinhard(g),
-                                tonemap_reinhard(b),
-                            )
-                        }
-                    };
+                    let (r_tm, g_tm, b_tm) = (mode.apply(r), mode.apply(g), mode.apply(b));
 
                     // Convert [0,1] float → [0,255] u8
                     ldr_buf.push((r_tm * 255.0) as u8);
@@ -966,32 +1521,7 @@ impl Frame {
                     let b = chunk[2];
                     let a = chunk[3];
 
-                    let (r_tm, g_tm, b_tm) = match mode {
-                        TonemapMode::Clamp => {
-                            (r.clamp(0.0, 1.0), g.clamp(0.0, 1.0), b.clamp(0.0, 1.0))
-                        }
-                        TonemapMode::ACES => {
-                            // ACES filmic tone mapping (Narkowicz 2015)
-                            let tonemap_aces = |x: f32| {
-                                let a = 2.51;
-                                let b = 0.03;
-                                let c = 2.43;
-                                let d = 0.59;
-                                let e = 0.14;
-                                ((x * (a * x + b)) / (x * (c * x + d) + e)).clamp(0.0, 1.0)
-                            };
-                            (tonemap_aces(r), tonemap_aces(g), tonemap_aces(b))
-                        }
-                        TonemapMode::Reinhard => {
-                            // Reinhard photographic tone mapping
-                            let tonemap_reinhard = |x: f32| (x / (1.0 + x)).clamp(0.0, 1.0);
-                            (
-                                tonemap_reinhard(r),
-                                tonemap_reinhard(g),
-                                tonemap_reinhard(b),
-                            )
-                        }
-                    };
+                    let (r_tm, g_tm, b_tm) = (mode.apply(r), mode.apply(g), mode.apply(b));
 
                     // Convert [0,1] float → [0,255] u8
                     ldr_buf.push((r_tm * 255.0) as u8);
@@ -1016,6 +1546,690 @@ impl Frame {
             }
         }
     }
+
+    /// Proxy downscale factor this frame was produced at (1 = full resolution).
+    ///
+    /// Viewport multiplies the buffer size by this to get the logical image size.
+    pub fn proxy_factor(&self) -> usize {
+        let data = self.data.lock().unwrap();
+        data.attrs.get_u32(A_PROXY_FACTOR).unwrap_or(1).max(1) as usize
+    }
+
+    /// Tag frame with the proxy factor it was rendered at (no resampling).
+    pub fn set_proxy_factor(&self, factor: usize) {
+        let mut data = self.data.lock().unwrap();
+        data.attrs
+            .set(A_PROXY_FACTOR, AttrValue::UInt(factor.max(1) as u32));
+    }
+
+    /// Pixel aspect ratio to display this frame at (1.0 = square pixels).
+    pub fn pixel_aspect(&self) -> f32 {
+        let data = self.data.lock().unwrap();
+        data.attrs
+            .get_float(A_PIXEL_ASPECT)
+            .filter(|v| *v > 0.0)
+            .unwrap_or(1.0)
+    }
+
+    /// Tag frame with its display pixel aspect ratio (no resampling).
+    pub fn set_pixel_aspect(&self, aspect: f32) {
+        let mut data = self.data.lock().unwrap();
+        data.attrs.set(A_PIXEL_ASPECT, AttrValue::Float(aspect));
+    }
+
+    /// Transfer function of the RGB values.
+    ///
+    /// Untagged frames follow the loaders' convention: U8 is sRGB, float is linear.
+    pub fn color_space(&self) -> ColorSpace {
+        let data = self.data.lock().unwrap();
+        data.attrs
+            .get_str(A_COLOR_SPACE)
+            .and_then(ColorSpace::parse)
+            .unwrap_or(match data.pixel_format {
+                PixelFormat::Rgba8 => ColorSpace::Srgb,
+                PixelFormat::RgbaF16 | PixelFormat::RgbaF32 => ColorSpace::Linear,
+            })
+    }
+
+    /// Tag frame with the transfer of its values (no conversion).
+    pub fn set_color_space(&self, space: ColorSpace) {
+        let mut data = self.data.lock().unwrap();
+        data.attrs
+            .set(A_COLOR_SPACE, AttrValue::Str(space.as_str().to_string()));
+    }
+
+    /// Decode sRGB values to linear (alpha untouched).
+    ///
+    /// Already-linear frames are returned as-is, so repeated calls never
+    /// double-convert. U8 buffers are promoted to F32: eight bits can't hold
+    /// linear values without crushing the shadows.
+    pub fn to_linear(&self) -> Frame {
+        if self.color_space() == ColorSpace::Linear {
+            return self.clone();
+        }
+        self.convert_transfer(srgb_to_linear, ColorSpace::Linear)
+    }
+
+    /// Encode linear values with the sRGB curve (alpha untouched).
+    ///
+    /// Already-sRGB frames are returned as-is. The pixel format is kept.
+    pub fn to_srgb(&self) -> Frame {
+        if self.color_space() == ColorSpace::Srgb {
+            return self.clone();
+        }
+        self.convert_transfer(linear_to_srgb, ColorSpace::Srgb)
+    }
+
+    /// Apply a per-channel transfer function and tag the result with `space`
+    fn convert_transfer(&self, f: fn(f32) -> f32, space: ColorSpace) -> Frame {
+        let data = self.data.lock().unwrap();
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(src) if space == ColorSpace::Linear => {
+                PixelBuffer::F32(map_rgb(src, |v| f(v as f32 / 255.0), |a| a as f32 / 255.0))
+            }
+            PixelBuffer::U8(src) => PixelBuffer::U8(map_rgb(
+                src,
+                |v| (f(v as f32 / 255.0) * 255.0).round().clamp(0.0, 255.0) as u8,
+                |a| a,
+            )),
+            PixelBuffer::F16(src) => {
+                PixelBuffer::F16(map_rgb(src, |v| F16::from_f32(f(v.to_f32())), |a| a))
+            }
+            PixelBuffer::F32(src) => PixelBuffer::F32(map_rgb(src, f, |a| a)),
+        };
+        let pixel_format = match &buffer {
+            PixelBuffer::U8(_) => PixelFormat::Rgba8,
+            PixelBuffer::F16(_) => PixelFormat::RgbaF16,
+            PixelBuffer::F32(_) => PixelFormat::RgbaF32,
+        };
+
+        let mut attrs = data.attrs.clone();
+        attrs.set(A_COLOR_SPACE, AttrValue::Str(space.as_str().to_string()));
+
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format,
+                width: data.width,
+                height: data.height,
+                status: data.status,
+                attrs,
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Alpha association of the RGB values (untagged frames are straight).
+    pub fn alpha_mode(&self) -> AlphaMode {
+        let data = self.data.lock().unwrap();
+        data.attrs
+            .get_str(A_ALPHA_MODE)
+            .and_then(AlphaMode::parse)
+            .unwrap_or(AlphaMode::Straight)
+    }
+
+    /// Tag frame with its alpha association (no conversion).
+    pub fn set_alpha_mode(&self, mode: AlphaMode) {
+        let mut data = self.data.lock().unwrap();
+        data.attrs
+            .set(A_ALPHA_MODE, AttrValue::Str(mode.as_str().to_string()));
+    }
+
+    /// Why this frame failed to load (file path + loader message), if it did.
+    ///
+    /// Set by [`Frame::load`]; composed frames carry the first failing
+    /// source's message.
+    pub fn load_error(&self) -> Option<String> {
+        let data = self.data.lock().unwrap();
+        data.attrs.get_str(A_LOAD_ERROR).map(str::to_string)
+    }
+
+    /// Attach (or clear with `None`) a load error message.
+    pub fn set_load_error(&self, message: Option<String>) {
+        let mut data = self.data.lock().unwrap();
+        match message {
+            Some(msg) => data.attrs.set(A_LOAD_ERROR, AttrValue::Str(msg)),
+            None => {
+                data.attrs.remove(A_LOAD_ERROR);
+            }
+        }
+    }
+
+    /// Multiply RGB by alpha. Already-premultiplied frames are returned as-is.
+    pub fn premultiply(&self) -> Frame {
+        if self.alpha_mode() == AlphaMode::Premultiplied {
+            return self.clone();
+        }
+        self.convert_alpha(AlphaMode::Premultiplied)
+    }
+
+    /// Divide RGB by alpha. Already-straight frames are returned as-is.
+    ///
+    /// Fully transparent pixels keep their RGB: there's nothing to divide by.
+    pub fn unpremultiply(&self) -> Frame {
+        if self.alpha_mode() == AlphaMode::Straight {
+            return self.clone();
+        }
+        self.convert_alpha(AlphaMode::Straight)
+    }
+
+    /// Rescale RGB by alpha into `mode` and tag the result (pixel format kept)
+    fn convert_alpha(&self, mode: AlphaMode) -> Frame {
+        let factor = |a: f32| match mode {
+            AlphaMode::Premultiplied => a,
+            AlphaMode::Straight if a > 0.0 => 1.0 / a,
+            AlphaMode::Straight => 1.0,
+        };
+        let data = self.data.lock().unwrap();
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(src) => PixelBuffer::U8(scale_rgb(
+                src,
+                |v| v as f32 / 255.0,
+                |v| (v * 255.0).round().clamp(0.0, 255.0) as u8,
+                factor,
+            )),
+            PixelBuffer::F16(src) => {
+                PixelBuffer::F16(scale_rgb(src, |v| v.to_f32(), F16::from_f32, factor))
+            }
+            PixelBuffer::F32(src) => PixelBuffer::F32(scale_rgb(src, |v| v, |v| v, factor)),
+        };
+
+        let mut attrs = data.attrs.clone();
+        attrs.set(A_ALPHA_MODE, AttrValue::Str(mode.as_str().to_string()));
+
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: data.pixel_format,
+                width: data.width,
+                height: data.height,
+                status: data.status,
+                attrs,
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Alpha-blend a straight, sRGB-encoded RGBA overlay over this frame
+    /// (annotation burn-in). `overlay` holds one entry per pixel, row-major.
+    ///
+    /// Overlay colors are converted to the frame's transfer; pixel format,
+    /// alpha mode and attrs are kept.
+    pub(crate) fn paint_overlay(&self, overlay: &[[f32; 4]]) -> Frame {
+        let encode: fn(f32) -> f32 = match self.color_space() {
+            ColorSpace::Linear => srgb_to_linear,
+            ColorSpace::Srgb => |v: f32| v,
+        };
+        let premultiplied = self.alpha_mode() == AlphaMode::Premultiplied;
+        let data = self.data.lock().unwrap();
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(src) => PixelBuffer::U8(paint_rgba(
+                src,
+                overlay,
+                |v| v as f32 / 255.0,
+                |v| (v * 255.0).round().clamp(0.0, 255.0) as u8,
+                encode,
+                premultiplied,
+            )),
+            PixelBuffer::F16(src) => PixelBuffer::F16(paint_rgba(
+                src,
+                overlay,
+                |v| v.to_f32(),
+                F16::from_f32,
+                encode,
+                premultiplied,
+            )),
+            PixelBuffer::F32(src) => PixelBuffer::F32(paint_rgba(
+                src,
+                overlay,
+                |v| v,
+                |v| v,
+                encode,
+                premultiplied,
+            )),
+        };
+
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: data.pixel_format,
+                width: data.width,
+                height: data.height,
+                status: data.status,
+                attrs: data.attrs.clone(),
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Multiply alpha by `coverage` (one entry per pixel, row-major), e.g. a
+    /// rasterized layer mask. RGB, pixel format, alpha mode and attrs are
+    /// kept, so straight frames stay straight.
+    pub(crate) fn multiply_alpha(&self, coverage: &[f32]) -> Frame {
+        let data = self.data.lock().unwrap();
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(src) => PixelBuffer::U8(scale_alpha(src, coverage, |v, k| {
+                (v as f32 * k).round().clamp(0.0, 255.0) as u8
+            })),
+            PixelBuffer::F16(src) => PixelBuffer::F16(scale_alpha(src, coverage, |v, k| {
+                F16::from_f32(v.to_f32() * k)
+            })),
+            PixelBuffer::F32(src) => PixelBuffer::F32(scale_alpha(src, coverage, |v, k| v * k)),
+        };
+
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: data.pixel_format,
+                width: data.width,
+                height: data.height,
+                status: data.status,
+                attrs: data.attrs.clone(),
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Per-channel min / max / mean over `rect` (ROI inspection).
+    ///
+    /// The rect is clamped to the frame; `None` when it lies fully outside.
+    pub fn region_stats(&self, rect: PixelRect) -> Option<RegionStats> {
+        let data = self.data.lock().unwrap();
+        let (w, h) = (data.width, data.height);
+        let rect = rect.clamp_to(w, h)?;
+        Some(match data.buffer.as_ref() {
+            PixelBuffer::U8(src) => region_stats(src, w, rect, |v| v as f32 / 255.0),
+            PixelBuffer::F16(src) => region_stats(src, w, rect, |v| v.to_f32()),
+            PixelBuffer::F32(src) => region_stats(src, w, rect, |v| v),
+        })
+    }
+
+    /// Content hash of the pixels for QC: lowercase SHA-256 hex.
+    ///
+    /// Stable across runs and independent of the [`PixelBuffer`]
+    /// representation: samples are normalized to f32 first (u8 as `v / 255`),
+    /// and the resolution is hashed along with them. Frame attributes
+    /// (color space, pixel aspect, ...) are not part of the hash.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let (buffer, width, height) = {
+            let data = self.data.lock().unwrap();
+            (Arc::clone(&data.buffer), data.width, data.height)
+        };
+        let mut hasher = Sha256::new();
+        hasher.update((width as u64).to_le_bytes());
+        hasher.update((height as u64).to_le_bytes());
+        match buffer.as_ref() {
+            PixelBuffer::U8(src) => hash_samples(&mut hasher, src, |v| v as f32 / 255.0),
+            PixelBuffer::F16(src) => hash_samples(&mut hasher, src, |v| v.to_f32()),
+            PixelBuffer::F32(src) => hash_samples(&mut hasher, src, |v| v),
+        }
+        hasher
+            .finalize()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    /// Box-filter downscale by an integer `factor` (proxy decode).
+    ///
+    /// Returns a new frame of `ceil(w / factor) x ceil(h / factor)` with the same
+    /// pixel format and status, tagged with the accumulated [`Frame::proxy_factor`].
+    /// `factor <= 1` returns a clone.
+    pub fn downscale(&self, factor: usize) -> Frame {
+        if factor <= 1 {
+            return self.clone();
+        }
+        let data = self.data.lock().unwrap();
+        let (w, h) = (data.width, data.height);
+        let (nw, nh) = (w.div_ceil(factor).max(1), h.div_ceil(factor).max(1));
+
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(src) => PixelBuffer::U8(box_downscale(
+                src,
+                (w, h),
+                factor,
+                |v| v as f32,
+                |v| v.round().clamp(0.0, 255.0) as u8,
+            )),
+            PixelBuffer::F16(src) => PixelBuffer::F16(box_downscale(
+                src,
+                (w, h),
+                factor,
+                |v| v.to_f32(),
+                F16::from_f32,
+            )),
+            PixelBuffer::F32(src) => {
+                PixelBuffer::F32(box_downscale(src, (w, h), factor, |v| v, |v| v))
+            }
+        };
+
+        let mut attrs = data.attrs.clone();
+        let prev = attrs.get_u32(A_PROXY_FACTOR).unwrap_or(1).max(1);
+        attrs.set(A_PROXY_FACTOR, AttrValue::UInt(prev * factor as u32));
+
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: data.pixel_format,
+                width: nw,
+                height: nh,
+                status: data.status,
+                attrs,
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Horizontal linear resample to `new_width` (anamorphic bake on encode).
+    ///
+    /// Height, pixel format, status and attrs are kept; the result is tagged
+    /// with square pixels since the aspect is now in the pixel data.
+    pub fn stretch_width(&self, new_width: usize) -> Frame {
+        let data = self.data.lock().unwrap();
+        let (w, h) = (data.width, data.height);
+        let nw = new_width.max(1);
+        if nw == w {
+            drop(data);
+            return self.clone();
+        }
+
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(src) => PixelBuffer::U8(linear_resample_x(
+                src,
+                (w, h),
+                nw,
+                |v| v as f32,
+                |v| v.round().clamp(0.0, 255.0) as u8,
+            )),
+            PixelBuffer::F16(src) => PixelBuffer::F16(linear_resample_x(
+                src,
+                (w, h),
+                nw,
+                |v| v.to_f32(),
+                F16::from_f32,
+            )),
+            PixelBuffer::F32(src) => {
+                PixelBuffer::F32(linear_resample_x(src, (w, h), nw, |v| v, |v| v))
+            }
+        };
+
+        let mut attrs = data.attrs.clone();
+        attrs.set(A_PIXEL_ASPECT, AttrValue::Float(1.0));
+
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: data.pixel_format,
+                width: nw,
+                height: h,
+                status: data.status,
+                attrs,
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Scale into exactly `new_w x new_h` following `fit` (aspect-preserving
+    /// modes place the image by `align`).
+    ///
+    /// Same-size frames are returned as-is. Letterbox bars are opaque black.
+    pub fn fit(
+        &self,
+        new_w: usize,
+        new_h: usize,
+        fit: FitMode,
+        align: CropAlign,
+        filter: ResizeFilter,
+    ) -> Frame {
+        let (nw, nh) = (new_w.max(1), new_h.max(1));
+        let (w, h) = self.resolution();
+        if (w, h) == (nw, nh) {
+            return self.clone();
+        }
+        let (sx, sy) = (nw as f32 / w.max(1) as f32, nh as f32 / h.max(1) as f32);
+        let scaled = |s: f32| {
+            (
+                ((w as f32 * s).round() as usize).max(1),
+                ((h as f32 * s).round() as usize).max(1),
+            )
+        };
+        match fit {
+            FitMode::Stretch => self.resize(nw, nh, filter),
+            FitMode::Crop => {
+                let (cw, ch) = scaled(sx.max(sy));
+                self.resize(cw.max(nw), ch.max(nh), filter)
+                    .crop_copy(nw, nh, align)
+            }
+            FitMode::Letterbox => {
+                let (fw, fh) = scaled(sx.min(sy));
+                let inner = self.resize(fw.min(nw), fh.min(nh), filter);
+                inner.pad_black(nw, nh, align)
+            }
+        }
+    }
+
+    /// Place this frame on an opaque black `new_w x new_h` canvas at `align`
+    fn pad_black(&self, new_w: usize, new_h: usize, align: CropAlign) -> Frame {
+        let data = self.data.lock().unwrap();
+        let (w, h) = (data.width.min(new_w), data.height.min(new_h));
+        let offset = (align.offset(new_w, w), align.offset(new_h, h));
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(src) => PixelBuffer::U8(pad_rgba(
+                src,
+                data.width,
+                (w, h),
+                (new_w, new_h),
+                offset,
+                0,
+                255,
+            )),
+            PixelBuffer::F16(src) => PixelBuffer::F16(pad_rgba(
+                src,
+                data.width,
+                (w, h),
+                (new_w, new_h),
+                offset,
+                F16::ZERO,
+                F16::ONE,
+            )),
+            PixelBuffer::F32(src) => PixelBuffer::F32(pad_rgba(
+                src,
+                data.width,
+                (w, h),
+                (new_w, new_h),
+                offset,
+                0.0,
+                1.0,
+            )),
+        };
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: data.pixel_format,
+                width: new_w,
+                height: new_h,
+                status: data.status,
+                attrs: data.attrs.clone(),
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Resample to `new_w x new_h` with `filter` (separable, any scale ratio).
+    ///
+    /// Filtering runs in linear light: sRGB frames are decoded first and
+    /// re-encoded after, float frames are resampled as-is. Straight-alpha
+    /// frames are premultiplied for the filter and divided back after, so
+    /// transparent pixels' colors don't bleed into edges. The pixel format,
+    /// status and attrs are kept. Zero sizes are clamped to 1.
+    pub fn resize(&self, new_w: usize, new_h: usize, filter: ResizeFilter) -> Frame {
+        let (nw, nh) = (new_w.max(1), new_h.max(1));
+        if (nw, nh) == self.resolution() {
+            return self.clone();
+        }
+        // Nearest only copies pixels: no need to leave the encoded domain
+        let linearize = filter != ResizeFilter::Nearest && self.color_space() == ColorSpace::Srgb;
+        let premultiply =
+            filter != ResizeFilter::Nearest && self.alpha_mode() == AlphaMode::Straight;
+
+        let data = self.data.lock().unwrap();
+        let (w, h) = (data.width, data.height);
+        let mut src: Vec<f32> = match data.buffer.as_ref() {
+            PixelBuffer::U8(b) => b.iter().map(|&v| v as f32 / 255.0).collect(),
+            PixelBuffer::F16(b) => b.iter().map(|v| v.to_f32()).collect(),
+            PixelBuffer::F32(b) => b.clone(),
+        };
+        if linearize {
+            src = map_rgb(&src, srgb_to_linear, |a| a);
+        }
+        if premultiply {
+            src = scale_rgb(&src, |v| v, |v| v, |a| a);
+        }
+
+        let temp = resample_axis(&src, (w, h), &resample_taps(w, nw, filter), true);
+        let mut out = resample_axis(&temp, (nw, h), &resample_taps(h, nh, filter), false);
+        if premultiply {
+            out = scale_rgb(&out, |v| v, |v| v, |a| if a > 0.0 { 1.0 / a } else { 1.0 });
+        }
+        if linearize {
+            out = map_rgb(&out, linear_to_srgb, |a| a);
+        }
+
+        let buffer = match data.buffer.as_ref() {
+            PixelBuffer::U8(_) => PixelBuffer::U8(
+                out.iter()
+                    .map(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+                    .collect(),
+            ),
+            PixelBuffer::F16(_) => {
+                PixelBuffer::F16(out.iter().map(|&v| F16::from_f32(v)).collect())
+            }
+            PixelBuffer::F32(_) => PixelBuffer::F32(out),
+        };
+
+        Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: data.pixel_format,
+                width: nw,
+                height: nh,
+                status: data.status,
+                attrs: data.attrs.clone(),
+            })),
+            filename: self.filename.clone(),
+        }
+    }
+
+    /// Linear blend of two frames: `a * (1 - t) + b * t` (frame blending for retime).
+    ///
+    /// Returns `None` if the frames differ in size or pixel format.
+    /// The result keeps `a`'s file, status and attrs.
+    pub fn lerp(a: &Frame, b: &Frame, t: f32) -> Option<Frame> {
+        let t = t.clamp(0.0, 1.0);
+        // Same frame (e.g. held at source end): nothing to blend
+        if Arc::ptr_eq(&a.data, &b.data) {
+            return Some(a.clone());
+        }
+        let da = a.data.lock().unwrap();
+        let db = b.data.lock().unwrap();
+        if (da.width, da.height) != (db.width, db.height) || da.pixel_format != db.pixel_format {
+            return None;
+        }
+
+        let mix = |x: f32, y: f32| x + (y - x) * t;
+        let buffer = match (da.buffer.as_ref(), db.buffer.as_ref()) {
+            (PixelBuffer::U8(x), PixelBuffer::U8(y)) => PixelBuffer::U8(
+                x.iter()
+                    .zip(y)
+                    .map(|(&p, &q)| mix(p as f32, q as f32).round() as u8)
+                    .collect(),
+            ),
+            (PixelBuffer::F16(x), PixelBuffer::F16(y)) => PixelBuffer::F16(
+                x.iter()
+                    .zip(y)
+                    .map(|(p, q)| F16::from_f32(mix(p.to_f32(), q.to_f32())))
+                    .collect(),
+            ),
+            (PixelBuffer::F32(x), PixelBuffer::F32(y)) => {
+                PixelBuffer::F32(x.iter().zip(y).map(|(&p, &q)| mix(p, q)).collect())
+            }
+            _ => return None,
+        };
+
+        Some(Frame {
+            data: Arc::new(Mutex::new(FrameData {
+                buffer: Arc::new(buffer),
+                pixel_format: da.pixel_format,
+                width: da.width,
+                height: da.height,
+                status: da.status,
+                attrs: da.attrs.clone(),
+            })),
+            filename: a.filename.clone(),
+        })
+    }
+}
+
+/// Average `factor x factor` RGBA blocks (edge blocks use the pixels that exist).
+fn box_downscale<T: Copy>(
+    src: &[T],
+    (w, h): (usize, usize),
+    factor: usize,
+    to_f32: impl Fn(T) -> f32,
+    from_f32: impl Fn(f32) -> T,
+) -> Vec<T> {
+    let (nw, nh) = (w.div_ceil(factor).max(1), h.div_ceil(factor).max(1));
+    let mut out = Vec::with_capacity(nw * nh * 4);
+    for ny in 0..nh {
+        let (y0, y1) = (ny * factor, ((ny + 1) * factor).min(h));
+        for nx in 0..nw {
+            let (x0, x1) = (nx * factor, ((nx + 1) * factor).min(w));
+            let mut acc = [0.0f32; 4];
+            for y in y0..y1 {
+                for x in x0..x1 {
+                    let i = (y * w + x) * 4;
+                    for c in 0..4 {
+                        acc[c] += to_f32(src[i + c]);
+                    }
+                }
+            }
+            let n = ((y1 - y0) * (x1 - x0)).max(1) as f32;
+            out.extend(acc.iter().map(|&v| from_f32(v / n)));
+        }
+    }
+    out
+}
+
+/// Linear resample of interleaved RGBA rows from `w` to `nw` pixels (pixel-center aligned).
+fn linear_resample_x<T: Copy>(
+    src: &[T],
+    (w, h): (usize, usize),
+    nw: usize,
+    to_f32: impl Fn(T) -> f32,
+    from_f32: impl Fn(f32) -> T,
+) -> Vec<T> {
+    let mut out = Vec::with_capacity(nw * h * 4);
+    if w == 0 {
+        return out;
+    }
+    let scale = w as f32 / nw as f32;
+    for y in 0..h {
+        let row = &src[y * w * 4..(y + 1) * w * 4];
+        for nx in 0..nw {
+            let sx = ((nx as f32 + 0.5) * scale - 0.5).clamp(0.0, (w - 1) as f32);
+            let x0 = sx.floor() as usize;
+            let x1 = (x0 + 1).min(w - 1);
+            let t = sx - x0 as f32;
+            for c in 0..4 {
+                let a = to_f32(row[x0 * 4 + c]);
+                let b = to_f32(row[x1 * 4 + c]);
+                out.push(from_f32(a + (b - a) * t));
+            }
+        }
+    }
+    out
 }
 
 /// Frame format conversion trait
@@ -1046,6 +2260,17 @@ pub trait FrameConversion {
     /// # Returns
     /// RGB48 data (width * height * 3 u16 values, little-endian)
     fn to_rgb48(&self) -> Result<Vec<u16>, FrameError>;
+
+    /// Convert to PQ-encoded RGB48 (HDR10), removes alpha
+    ///
+    /// Pixels are linearized (U8 / sRGB-tagged buffers), 1.0 is placed at
+    /// [`PQ_REFERENCE_WHITE`] and values above it keep their HDR range up
+    /// to 10000 nits. With `rec2020` the Rec.709 primaries are converted to
+    /// Rec.2020 first.
+    ///
+    /// # Returns
+    /// RGB48 data (width * height * 3 u16 values)
+    fn to_rgb48_pq(&self, rec2020: bool) -> Result<Vec<u16>, FrameError>;
 }
 
 impl FrameConversion for Frame {
@@ -1133,6 +2358,42 @@ impl FrameConversion for Frame {
             }
         }
     }
+
+    fn to_rgb48_pq(&self, rec2020: bool) -> Result<Vec<u16>, FrameError> {
+        let buffer = self.buffer();
+        let (width, height) = self.resolution();
+        let srgb = self.color_space() == ColorSpace::Srgb;
+
+        let mut rgb48 = Vec::with_capacity(width * height * 3);
+        let mut push = |rgb: [f32; 3]| {
+            let rgb = if srgb { rgb.map(srgb_to_linear) } else { rgb };
+            let rgb = if rec2020 { rec709_to_rec2020(rgb) } else { rgb };
+            for v in rgb {
+                let pq = linear_to_pq(v.max(0.0) * PQ_REFERENCE_WHITE);
+                rgb48.push((pq * 65535.0).round() as u16);
+            }
+        };
+
+        match &*buffer {
+            PixelBuffer::U8(rgba) => {
+                for chunk in rgba.chunks_exact(4) {
+                    push([0, 1, 2].map(|c| chunk[c] as f32 / 255.0));
+                }
+            }
+            PixelBuffer::F16(rgba) => {
+                for chunk in rgba.chunks_exact(4) {
+                    push([0, 1, 2].map(|c| chunk[c].to_f32()));
+                }
+            }
+            PixelBuffer::F32(rgba) => {
+                for chunk in rgba.chunks_exact(4) {
+                    push([chunk[0], chunk[1], chunk[2]]);
+                }
+            }
+        }
+
+        Ok(rgb48)
+    }
 }
 
 #[cfg(test)]
@@ -1152,6 +2413,123 @@ mod tests {
         assert_eq!(frame.pixel_format(), PixelFormat::Rgba8);
     }
 
+    /// Test: Proxy downscale averages blocks and tags the factor
+    #[test]
+    fn test_frame_downscale() {
+        let mut buf = vec![0u8; 4 * 4 * 4];
+        // Top-left 2x2 block: two white pixels, two black
+        buf[0..4].copy_from_slice(&[255, 255, 255, 255]);
+        buf[4..8].copy_from_slice(&[255, 255, 255, 255]);
+        let frame = Frame::from_u8_buffer(buf, 4, 4);
+
+        let half = frame.downscale(2);
+        assert_eq!(half.resolution(), (2, 2));
+        assert_eq!(half.proxy_factor(), 2);
+        assert_eq!(half.status(), FrameStatus::Loaded);
+        if let PixelBuffer::U8(px) = &*half.buffer() {
+            assert_eq!(px[0], 128);
+            assert_eq!(px[4], 0);
+        } else {
+            panic!("expected U8 buffer");
+        }
+
+        // Odd sizes round up, factors accumulate
+        let quarter = half.downscale(3);
+        assert_eq!(quarter.resolution(), (1, 1));
+        assert_eq!(quarter.proxy_factor(), 6);
+        assert_eq!(frame.proxy_factor(), 1);
+    }
+
+    /// Test: content hash follows pixel values, not buffer storage
+    #[test]
+    fn test_content_hash() {
+        let u8_frame = Frame::from_u8_buffer(vec![0, 51, 255, 255, 255, 0, 0, 255], 2, 1);
+        let values = [0.0, 0.2, 1.0, 1.0, 1.0, 0.0, 0.0, 1.0];
+        let f32_frame = Frame::from_f32_buffer(values.to_vec(), 2, 1);
+        let f16_frame =
+            Frame::from_f16_buffer(values.iter().map(|&v| F16::from_f32(v)).collect(), 2, 1);
+
+        let hash = u8_frame.content_hash();
+        assert_eq!(hash.len(), 64);
+        assert_eq!(hash, u8_frame.content_hash());
+        assert_eq!(hash, f32_frame.content_hash());
+        // 0.2 is not exact in half precision: a different pixel value
+        assert_ne!(hash, f16_frame.content_hash());
+
+        // Signed zero and NaN payloads are normalized
+        let neg = Frame::from_f32_buffer(vec![-0.0, f32::NAN, 1.0, 1.0], 1, 1);
+        let pos = Frame::from_f32_buffer(vec![0.0, -f32::NAN, 1.0, 1.0], 1, 1);
+        assert_eq!(neg.content_hash(), pos.content_hash());
+
+        // Same samples, different shape
+        let tall = Frame::from_f32_buffer(values.to_vec(), 1, 2);
+        assert_ne!(hash, tall.content_hash());
+    }
+
+    /// Test: ROI stats cover only the region, clamped to the frame
+    #[test]
+    fn test_region_stats() {
+        // 4x2: left half black, right half (x >= 2) rising red, alpha 1
+        let mut buf = Vec::new();
+        for _y in 0..2 {
+            for x in 0..4 {
+                let r = if x >= 2 { x as f32 * 0.25 } else { 0.0 };
+                buf.extend_from_slice(&[r, 0.0, 0.0, 1.0]);
+            }
+        }
+        let frame = Frame::from_f32_buffer(buf, 4, 2);
+
+        let stats = frame.region_stats(PixelRect::new(2, 0, 2, 2)).unwrap();
+        assert_eq!(stats.pixels, 4);
+        assert_eq!(stats.min, [0.5, 0.0, 0.0, 1.0]);
+        assert_eq!(stats.max, [0.75, 0.0, 0.0, 1.0]);
+        assert_eq!(stats.mean, [0.625, 0.0, 0.0, 1.0]);
+
+        // Overhanging rect is clamped; fully outside yields nothing
+        let clamped = frame.region_stats(PixelRect::new(3, 1, 10, 10)).unwrap();
+        assert_eq!((clamped.pixels, clamped.max[0]), (1, 0.75));
+        assert!(frame.region_stats(PixelRect::new(4, 0, 2, 2)).is_none());
+
+        // Proxy: rounded outward
+        assert_eq!(
+            PixelRect::new(3, 1, 4, 2).downscale(2),
+            PixelRect::new(1, 0, 3, 2)
+        );
+    }
+
+    /// Test: pixel aspect defaults to square and survives proxy downscale
+    #[test]
+    fn test_frame_pixel_aspect() {
+        let frame = Frame::from_u8_buffer(vec![0u8; 4 * 4 * 4], 4, 4);
+        assert_eq!(frame.pixel_aspect(), 1.0);
+
+        frame.set_pixel_aspect(2.0);
+        assert_eq!(frame.pixel_aspect(), 2.0);
+        assert_eq!(frame.downscale(2).pixel_aspect(), 2.0);
+
+        // Baking the aspect doubles the width and resets PAR to square
+        let baked = frame.stretch_width(8);
+        assert_eq!(baked.resolution(), (8, 4));
+        assert_eq!(baked.pixel_aspect(), 1.0);
+    }
+
+    /// Test: 0.5 blend of black and white yields mid-gray
+    #[test]
+    fn test_frame_lerp_mid_gray() {
+        let black = Frame::black(2, 2);
+        let white = Frame::from_u8_buffer(vec![255u8; 2 * 2 * 4], 2, 2);
+
+        let mid = Frame::lerp(&black, &white, 0.5).expect("same size/format");
+        if let PixelBuffer::U8(px) = &*mid.buffer() {
+            assert_eq!(&px[0..4], &[128, 128, 128, 255]);
+        } else {
+            panic!("expected U8 buffer");
+        }
+
+        // Mismatched sizes don't blend
+        assert!(Frame::lerp(&black, &Frame::black(4, 4), 0.5).is_none());
+    }
+
     /// Test: Frame creation with file path
     /// Validates: Status transitions to Header
     #[test]
@@ -1174,6 +2552,12 @@ mod tests {
 
         // After failed load, status should be Error
         assert_eq!(frame.status(), FrameStatus::Error);
+
+        // The reason names the file; resetting for a retry clears it
+        let error = frame.load_error().expect("load error recorded");
+        assert!(error.contains("/nonexistent/path/test.jpg"), "{error}");
+        frame.set_status(FrameStatus::Header).unwrap();
+        assert_eq!(frame.load_error(), None);
     }
 
     /// Test: PixelBuffer variant sizes
@@ -1220,4 +2604,349 @@ mod tests {
         let _ = frame.load();
         assert_eq!(frame.status(), FrameStatus::Error);
     }
+
+    /// Test: Tonemap operators at pinned input values
+    /// Validates: Curves stay stable (0, mid-gray, 1.0, 4.0)
+    #[test]
+    fn test_tonemap_operators() {
+        let cases: [(TonemapMode, [f32; 4]); 5] = [
+            (TonemapMode::Clamp, [0.0, 0.18, 1.0, 1.0]),
+            (TonemapMode::ACES, [0.0, 0.266899, 0.803797, 0.973417]),
+            (TonemapMode::Reinhard, [0.0, 0.152542, 0.5, 0.8]),
+            (TonemapMode::ReinhardExtended, [0.0, 0.154258, 0.53125, 1.0]),
+            (TonemapMode::Hable, [0.0, 0.128338, 0.492919, 0.918030]),
+        ];
+        for (mode, expected) in cases {
+            for (x, want) in [0.0, 0.18, 1.0, 4.0].into_iter().zip(expected) {
+                let got = mode.apply(x);
+                assert!(
+                    (got - want).abs() < 1e-5,
+                    "{:?}({}) = {}, expected {}",
+                    mode,
+                    x,
+                    got,
+                    want
+                );
+            }
+            // Negative input never goes below black, huge input never above white
+            assert!(mode.apply(-1.0).abs() < 1e-6);
+            assert!(mode.apply(1e6) <= 1.0);
+        }
+        assert_eq!(
+            tonemap_reinhard_extended(REINHARD_WHITE, REINHARD_WHITE),
+            1.0
+        );
+
+        // Frame-level: F32 input goes through the selected operator
+        let hdr = Frame::from_buffer(
+            PixelBuffer::F32(vec![1.0, 4.0, 0.0, 1.0]),
+            PixelFormat::RgbaF32,
+            1,
+            1,
+        );
+        let ldr = hdr.tonemap(TonemapMode::Reinhard).unwrap();
+        if let PixelBuffer::U8(px) = &*ldr.buffer() {
+            assert_eq!(&px[..4], &[127, 204, 0, 255]);
+        } else {
+            panic!("expected U8 buffer");
+        }
+    }
+
+    /// Test: sRGB transfer functions around the 0.04045 breakpoint
+    #[test]
+    fn test_srgb_transfer_breakpoints() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-5;
+        // Linear segment meets the power segment at 0.04045 / 0.0031308
+        assert!(close(srgb_to_linear(0.04045), 0.04045 / 12.92));
+        assert!(close(srgb_to_linear(0.04045), 0.0031308));
+        assert!(close(srgb_to_linear(0.040451), 0.0031308));
+        assert!(close(linear_to_srgb(0.0031308), 0.04045));
+        assert!(close(srgb_to_linear(0.02), 0.02 / 12.92));
+        assert!(close(srgb_to_linear(0.5), 0.214041));
+        assert!(close(linear_to_srgb(0.18), 0.461356));
+        assert_eq!(srgb_to_linear(0.0), 0.0);
+        assert!(close(srgb_to_linear(1.0), 1.0));
+        for i in 0..=20 {
+            let v = i as f32 / 20.0;
+            assert!(close(linear_to_srgb(srgb_to_linear(v)), v));
+        }
+    }
+
+    /// Test: PQ curve reference points and Rec.709 → Rec.2020 white/primaries
+    #[test]
+    fn test_pq_and_rec2020() {
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-3;
+        assert!(close(linear_to_pq(0.0), 0.0));
+        assert!(close(linear_to_pq(100.0), 0.5081));
+        assert!(close(linear_to_pq(1000.0), 0.7518));
+        assert!(close(linear_to_pq(10000.0), 1.0));
+        assert!(close(linear_to_pq(20000.0), 1.0)); // clipped
+
+        // White stays white; pure 709 red is inside the 2020 gamut
+        let white = rec709_to_rec2020([1.0, 1.0, 1.0]);
+        assert!(white.iter().all(|v| close(*v, 1.0)));
+        let red = rec709_to_rec2020([1.0, 0.0, 0.0]);
+        assert!(red[0] < 1.0 && red[1] > 0.0 && red[2] > 0.0);
+
+        // Frame encode: sRGB white lands on reference white, HDR above it
+        let frame = Frame::from_u8_buffer(vec![255, 255, 255, 255], 1, 1);
+        let white_pq = (linear_to_pq(PQ_REFERENCE_WHITE) * 65535.0).round() as u16;
+        assert_eq!(frame.to_rgb48_pq(true).unwrap(), vec![white_pq; 3]);
+        let hdr = Frame::from_f32_buffer(vec![4.0, 0.0, -1.0, 1.0], 1, 1);
+        let px = hdr.to_rgb48_pq(false).unwrap();
+        assert!(px[0] > white_pq);
+        assert_eq!(px[2], px[1]); // negatives clip to black
+    }
+
+    /// Test: Frame color-space conversion and tagging
+    /// Validates: U8 promotes to F32, alpha untouched, no double conversion
+    #[test]
+    fn test_frame_to_linear_and_back() {
+        let frame = Frame::from_u8_buffer(vec![128, 0, 255, 64], 1, 1);
+        assert_eq!(frame.color_space(), ColorSpace::Srgb);
+
+        let lin = frame.to_linear();
+        assert_eq!(lin.color_space(), ColorSpace::Linear);
+        assert_eq!(lin.pixel_format(), PixelFormat::RgbaF32);
+        let PixelBuffer::F32(px) = lin.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        assert!((px[0] - srgb_to_linear(128.0 / 255.0)).abs() < 1e-6);
+        assert_eq!(px[1], 0.0);
+        assert!((px[2] - 1.0).abs() < 1e-6);
+        assert!((px[3] - 64.0 / 255.0).abs() < 1e-6);
+
+        // Second call is a no-op
+        let again = lin.to_linear();
+        assert!(Arc::ptr_eq(&again.buffer(), &lin.buffer()));
+
+        let back = lin.to_srgb();
+        assert_eq!(back.color_space(), ColorSpace::Srgb);
+        let PixelBuffer::F32(px) = back.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        assert!((px[0] - 128.0 / 255.0).abs() < 1e-5);
+        assert!((px[3] - 64.0 / 255.0).abs() < 1e-6);
+        assert!(Arc::ptr_eq(&back.to_srgb().buffer(), &back.buffer()));
+
+        // Float frames default to linear
+        assert_eq!(Frame::new_f16(2, 2).color_space(), ColorSpace::Linear);
+    }
+
+    /// Test: premultiply → unpremultiply round-trips RGB where alpha > 0
+    #[test]
+    fn test_premultiply_round_trip() {
+        let frame = Frame::from_f32_buffer(
+            vec![0.8, 0.4, 0.2, 0.5, 1.0, 1.0, 1.0, 1.0, 0.3, 0.6, 0.9, 0.0],
+            3,
+            1,
+        );
+        assert_eq!(frame.alpha_mode(), AlphaMode::Straight);
+
+        let pre = frame.premultiply();
+        assert_eq!(pre.alpha_mode(), AlphaMode::Premultiplied);
+        let PixelBuffer::F32(px) = pre.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        assert_eq!(&px[0..4], &[0.4, 0.2, 0.1, 0.5]);
+        assert_eq!(&px[8..12], &[0.0, 0.0, 0.0, 0.0]);
+        assert!(Arc::ptr_eq(&pre.premultiply().buffer(), &pre.buffer()));
+
+        let back = pre.unpremultiply();
+        assert_eq!(back.alpha_mode(), AlphaMode::Straight);
+        let PixelBuffer::F32(px) = back.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        for (got, want) in px[0..8]
+            .iter()
+            .zip([0.8, 0.4, 0.2, 0.5, 1.0, 1.0, 1.0, 1.0])
+        {
+            assert!((got - want).abs() < 1e-6);
+        }
+
+        // 8-bit keeps its format; rounding stays within a code value
+        let frame = Frame::from_u8_buffer(vec![200, 100, 50, 128], 1, 1);
+        let back = frame.premultiply().unpremultiply();
+        assert_eq!(back.pixel_format(), PixelFormat::Rgba8);
+        let PixelBuffer::U8(px) = back.buffer().as_ref().clone() else {
+            panic!("expected U8 buffer");
+        };
+        for (got, want) in px.iter().zip([200u8, 100, 50, 128]) {
+            assert!(got.abs_diff(want) <= 1);
+        }
+    }
+
+    /// Test: Nearest 2x upscale duplicates pixels
+    #[test]
+    fn test_resize_nearest_upscale_duplicates() {
+        let frame = Frame::from_u8_buffer(vec![10, 20, 30, 255, 200, 100, 50, 128], 2, 1);
+        let up = frame.resize(4, 2, ResizeFilter::Nearest);
+        assert_eq!(up.resolution(), (4, 2));
+        let PixelBuffer::U8(px) = up.buffer().as_ref().clone() else {
+            panic!("expected U8 buffer");
+        };
+        let (a, b) = ([10, 20, 30, 255], [200, 100, 50, 128]);
+        for row in px.chunks_exact(16) {
+            assert_eq!(&row[0..4], &a);
+            assert_eq!(&row[4..8], &a);
+            assert_eq!(&row[8..12], &b);
+            assert_eq!(&row[12..16], &b);
+        }
+    }
+
+    /// Test: Bilinear downscale averages in linear light
+    #[test]
+    fn test_resize_bilinear_downscale_averages() {
+        // Float (linear): 2x2 -> 1x1 is the plain mean
+        let hdr = Frame::from_buffer(
+            PixelBuffer::F32(vec![
+                0.0, 0.0, 0.0, 1.0, 1.0, 2.0, 4.0, 1.0, //
+                1.0, 2.0, 4.0, 1.0, 0.0, 0.0, 0.0, 1.0,
+            ]),
+            PixelFormat::RgbaF32,
+            2,
+            2,
+        );
+        let small = hdr.resize(1, 1, ResizeFilter::Bilinear);
+        let PixelBuffer::F32(px) = small.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        for (got, want) in px.iter().zip([0.5, 1.0, 2.0, 1.0]) {
+            assert!((got - want).abs() < 1e-6, "{got} != {want}");
+        }
+
+        // Uniform regions stay uniform at any ratio, any filter
+        let flat = Frame::from_buffer(
+            PixelBuffer::F32([0.25, 0.5, 0.75, 1.0].repeat(7 * 5)),
+            PixelFormat::RgbaF32,
+            7,
+            5,
+        );
+        for filter in ResizeFilter::ALL {
+            let PixelBuffer::F32(px) = flat.resize(3, 9, filter).buffer().as_ref().clone() else {
+                panic!("expected F32 buffer");
+            };
+            assert_eq!(px.len(), 3 * 9 * 4);
+            for (i, v) in px.iter().enumerate() {
+                assert!((v - [0.25, 0.5, 0.75, 1.0][i % 4]).abs() < 1e-5);
+            }
+        }
+
+        // 8-bit black/white pair averages to linear 0.5 (sRGB 188), not 128
+        let ldr = Frame::from_u8_buffer(vec![0, 0, 0, 255, 255, 255, 255, 255], 2, 1);
+        let PixelBuffer::U8(px) = ldr
+            .resize(1, 1, ResizeFilter::Bilinear)
+            .buffer()
+            .as_ref()
+            .clone()
+        else {
+            panic!("expected U8 buffer");
+        };
+        assert_eq!(&px[..], &[188, 188, 188, 255]);
+    }
+
+    /// Test: Resize filters straight alpha premultiplied
+    /// Validates: transparent pixels' colors don't fringe edges
+    #[test]
+    fn test_resize_premultiplies_straight_alpha() {
+        // Opaque red next to fully transparent green
+        let edge = Frame::from_buffer(
+            PixelBuffer::F32(vec![1.0, 0.0, 0.0, 1.0, 0.0, 1.0, 0.0, 0.0]),
+            PixelFormat::RgbaF32,
+            2,
+            1,
+        );
+        let half = edge.resize(1, 1, ResizeFilter::Bilinear);
+        assert_eq!(half.alpha_mode(), AlphaMode::Straight);
+        let PixelBuffer::F32(px) = half.buffer().as_ref().clone() else {
+            panic!("expected F32 buffer");
+        };
+        // Half-covered red, no green bleeding in from the transparent pixel
+        for (got, want) in px.iter().zip([1.0, 0.0, 0.0, 0.5]) {
+            assert!((got - want).abs() < 1e-6, "{got} != {want}");
+        }
+
+        // Premultiplied frames are filtered as they are
+        edge.set_alpha_mode(AlphaMode::Premultiplied);
+        let PixelBuffer::F32(px) = edge
+            .resize(1, 1, ResizeFilter::Bilinear)
+            .buffer()
+            .as_ref()
+            .clone()
+        else {
+            panic!("expected F32 buffer");
+        };
+        for (got, want) in px.iter().zip([0.5, 0.5, 0.0, 0.5]) {
+            assert!((got - want).abs() < 1e-6, "{got} != {want}");
+        }
+    }
+
+    /// Test: Fit modes land on the exact target size
+    /// Validates: Letterbox pads black, crop covers, stretch fills
+    #[test]
+    fn test_fit_modes() {
+        // 4x2 white frame into a 4x4 target
+        let white = Frame::from_buffer(
+            PixelBuffer::F32([1.0f32; 4].repeat(8)),
+            PixelFormat::RgbaF32,
+            4,
+            2,
+        );
+        let px = |f: &Frame| match f.buffer().as_ref() {
+            PixelBuffer::F32(p) => p.clone(),
+            _ => panic!("expected F32 buffer"),
+        };
+        let row = |p: &[f32], y: usize| p[y * 16..(y + 1) * 16].to_vec();
+
+        let boxed = white.fit(
+            4,
+            4,
+            FitMode::Letterbox,
+            CropAlign::Center,
+            ResizeFilter::Bilinear,
+        );
+        assert_eq!(boxed.resolution(), (4, 4));
+        let p = px(&boxed);
+        assert_eq!(row(&p, 0), [0.0, 0.0, 0.0, 1.0].repeat(4));
+        assert_eq!(row(&p, 1), vec![1.0; 16]);
+        assert_eq!(row(&p, 2), vec![1.0; 16]);
+        assert_eq!(row(&p, 3), [0.0, 0.0, 0.0, 1.0].repeat(4));
+
+        let top = white.fit(
+            4,
+            4,
+            FitMode::Letterbox,
+            CropAlign::LeftTop,
+            ResizeFilter::Bilinear,
+        );
+        assert_eq!(row(&px(&top), 0), vec![1.0; 16]);
+        assert_eq!(row(&px(&top), 3), [0.0, 0.0, 0.0, 1.0].repeat(4));
+
+        for fit in [FitMode::Crop, FitMode::Stretch] {
+            let out = white.fit(4, 4, fit, CropAlign::Center, ResizeFilter::Bilinear);
+            assert_eq!(out.resolution(), (4, 4));
+            assert!(px(&out).iter().all(|v| (v - 1.0).abs() < 1e-5), "{fit:?}");
+        }
+
+        // Crop keeps the center: left half red, right half blue, 4x2 -> 2x2
+        let mut split = Vec::new();
+        for _ in 0..2 {
+            for x in 0..4 {
+                split.extend_from_slice(if x < 2 {
+                    &[1.0, 0.0, 0.0, 1.0]
+                } else {
+                    &[0.0, 0.0, 1.0, 1.0]
+                });
+            }
+        }
+        let split = Frame::from_buffer(PixelBuffer::F32(split), PixelFormat::RgbaF32, 4, 2);
+        let c = split.fit(
+            2,
+            2,
+            FitMode::Crop,
+            CropAlign::Center,
+            ResizeFilter::Nearest,
+        );
+        assert_eq!(&px(&c)[..8], &[1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0]);
+    }
 }
@@ -0,0 +1,249 @@
+//! Keyframe animation for keyable attributes.
+//!
+//! Keys are stored on the owning `Attrs` under
+//! [`A_KEYFRAMES`](super::keys::A_KEYFRAMES) as a `Map` of attr name ->
+//! `List` of `Map { frame, value }`, so they serialize with the project like
+//! markers do. Numeric values (Int/Float/Vec3/Vec4) interpolate linearly
+//! between neighbouring keys, anything else holds the previous key. Frames
+//! are evaluated as `f32`, so sub-frame samples get fractional in-between
+//! values; Int results are floored (a typewriter count only advances once
+//! the next character is fully reached).
+
+use std::collections::HashMap;
+
+use super::attrs::{AttrValue, Attrs};
+use super::keys::A_KEYFRAMES;
+
+/// Single key on one attribute
+#[derive(Debug, Clone, PartialEq)]
+pub struct Keyframe {
+    /// Frame in the owner's own timeline (the index `Node::compute` receives)
+    pub frame: i32,
+    pub value: AttrValue,
+}
+
+impl Keyframe {
+    pub fn new(frame: i32, value: AttrValue) -> Self {
+        Self { frame, value }
+    }
+
+    /// Encode as an attr map entry
+    pub fn to_attr(&self) -> AttrValue {
+        let mut map = HashMap::new();
+        map.insert("frame".to_string(), AttrValue::Int(self.frame));
+        map.insert("value".to_string(), self.value.clone());
+        AttrValue::Map(map)
+    }
+
+    /// Decode an attr map entry; entries without frame or value are rejected
+    pub fn from_attr(value: &AttrValue) -> Option<Self> {
+        let AttrValue::Map(map) = value else {
+            return None;
+        };
+        let frame = match map.get("frame")? {
+            AttrValue::Int(f) => *f,
+            _ => return None,
+        };
+        Some(Self::new(frame, map.get("value")?.clone()))
+    }
+}
+
+/// Keys for `key`, sorted by frame (empty if not animated)
+pub fn keys(attrs: &Attrs, key: &str) -> Vec<Keyframe> {
+    let mut keys: Vec<Keyframe> = attrs
+        .get_map(A_KEYFRAMES)
+        .and_then(|tracks| match tracks.get(key) {
+            Some(AttrValue::List(list)) => {
+                Some(list.iter().filter_map(Keyframe::from_attr).collect())
+            }
+            _ => None,
+        })
+        .unwrap_or_default();
+    keys.sort_by_key(|k| k.frame);
+    keys
+}
+
+/// Frames holding a key on any attribute, sorted and deduplicated
+pub fn key_frames(attrs: &Attrs) -> Vec<i32> {
+    let mut frames: Vec<i32> = attrs
+        .get_map(A_KEYFRAMES)
+        .map(|tracks| {
+            tracks
+                .values()
+                .filter_map(|t| match t {
+                    AttrValue::List(list) => Some(list),
+                    _ => None,
+                })
+                .flatten()
+                .filter_map(Keyframe::from_attr)
+                .map(|k| k.frame)
+                .collect()
+        })
+        .unwrap_or_default();
+    frames.sort_unstable();
+    frames.dedup();
+    frames
+}
+
+/// True if any attribute carries keys
+pub fn is_animated(attrs: &Attrs) -> bool {
+    attrs.get_map(A_KEYFRAMES).is_some_and(|tracks| {
+        tracks
+            .values()
+            .any(|t| matches!(t, AttrValue::List(l) if !l.is_empty()))
+    })
+}
+
+/// Add or replace the key at `frame`
+pub fn set_key(attrs: &mut Attrs, key: &str, frame: i32, value: AttrValue) {
+    let mut track = keys(attrs, key);
+    track.retain(|k| k.frame != frame);
+    track.push(Keyframe::new(frame, value));
+    track.sort_by_key(|k| k.frame);
+    write_track(attrs, key, &track);
+}
+
+/// Remove the key at `frame`; returns false if there was none
+pub fn remove_key(attrs: &mut Attrs, key: &str, frame: i32) -> bool {
+    let mut track = keys(attrs, key);
+    let before = track.len();
+    track.retain(|k| k.frame != frame);
+    if track.len() == before {
+        return false;
+    }
+    write_track(attrs, key, &track);
+    true
+}
+
+/// Value of `key` at `frame`: interpolated from keys when animated,
+/// otherwise the static attr value.
+pub fn eval(attrs: &Attrs, key: &str, frame: f32) -> Option<AttrValue> {
+    eval_shaped(attrs, key, frame, |t| t)
+}
+
+/// [`eval`] with the position between two keys (0..1) passed through `shape`
+/// before interpolating, e.g. a smoothstep to ease in and out of keys.
+pub fn eval_shaped(
+    attrs: &Attrs,
+    key: &str,
+    frame: f32,
+    shape: fn(f32) -> f32,
+) -> Option<AttrValue> {
+    let track = keys(attrs, key);
+    let (Some(first), Some(last)) = (track.first(), track.last()) else {
+        return attrs.get(key).cloned();
+    };
+    if frame <= first.frame as f32 {
+        return Some(first.value.clone());
+    }
+    if frame >= last.frame as f32 {
+        return Some(last.value.clone());
+    }
+    let next = track.iter().position(|k| k.frame as f32 > frame)?;
+    let (a, b) = (&track[next - 1], &track[next]);
+    let t = (frame - a.frame as f32) / (b.frame - a.frame) as f32;
+    Some(lerp(&a.value, &b.value, shape(t)))
+}
+
+/// Write a whole track through `Attrs::set` so dirty tracking sees the change
+fn write_track(attrs: &mut Attrs, key: &str, track: &[Keyframe]) {
+    let mut tracks = attrs.get_map(A_KEYFRAMES).cloned().unwrap_or_default();
+    if track.is_empty() {
+        tracks.remove(key);
+    } else {
+        tracks.insert(
+            key.to_string(),
+            AttrValue::List(track.iter().map(Keyframe::to_attr).collect()),
+        );
+    }
+    attrs.set(A_KEYFRAMES, AttrValue::Map(tracks));
+}
+
+fn lerp(a: &AttrValue, b: &AttrValue, t: f32) -> AttrValue {
+    let mix = |x: f32, y: f32| x + (y - x) * t;
+    match (a, b) {
+        (AttrValue::Float(x), AttrValue::Float(y)) => AttrValue::Float(mix(*x, *y)),
+        (AttrValue::Int(x), AttrValue::Int(y)) => {
+            AttrValue::Int(mix(*x as f32, *y as f32).floor() as i32)
+        }
+        (AttrValue::Vec3(x), AttrValue::Vec3(y)) => {
+            AttrValue::Vec3(std::array::from_fn(|i| mix(x[i], y[i])))
+        }
+        (AttrValue::Vec4(x), AttrValue::Vec4(y)) => {
+            AttrValue::Vec4(std::array::from_fn(|i| mix(x[i], y[i])))
+        }
+        // Non-interpolable (or mismatched) values hold until the next key
+        _ => a.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_eval_interpolates_fractional_position() {
+        let mut attrs = Attrs::new();
+        attrs.set("position", AttrValue::Vec3([5.0, 5.0, 0.0]));
+        assert_eq!(
+            eval(&attrs, "position", 3.0),
+            Some(AttrValue::Vec3([5.0, 5.0, 0.0]))
+        );
+
+        set_key(&mut attrs, "position", 0, AttrValue::Vec3([0.0, 0.0, 0.0]));
+        set_key(
+            &mut attrs,
+            "position",
+            3,
+            AttrValue::Vec3([10.0, -3.0, 0.0]),
+        );
+        assert!(is_animated(&attrs));
+
+        let Some(AttrValue::Vec3(p)) = eval(&attrs, "position", 1.0) else {
+            panic!("expected Vec3");
+        };
+        assert!((p[0] - 10.0 / 3.0).abs() < 1e-5 && (p[1] + 1.0).abs() < 1e-5);
+        let Some(AttrValue::Vec3(p)) = eval(&attrs, "position", 1.5) else {
+            panic!("expected Vec3");
+        };
+        assert!((p[0] - 5.0).abs() < 1e-5);
+        // Held outside the keyed range
+        assert_eq!(
+            eval(&attrs, "position", -4.0),
+            Some(AttrValue::Vec3([0.0; 3]))
+        );
+        assert_eq!(
+            eval(&attrs, "position", 9.0),
+            Some(AttrValue::Vec3([10.0, -3.0, 0.0]))
+        );
+    }
+
+    #[test]
+    fn test_int_keys_floor_and_remove() {
+        let mut attrs = Attrs::new();
+        set_key(&mut attrs, "reveal", 0, AttrValue::Int(0));
+        set_key(&mut attrs, "reveal", 10, AttrValue::Int(5));
+        assert_eq!(eval(&attrs, "reveal", 3.0), Some(AttrValue::Int(1)));
+        assert_eq!(eval(&attrs, "reveal", 4.0), Some(AttrValue::Int(2)));
+
+        // Replacing a key keeps one entry per frame
+        set_key(&mut attrs, "reveal", 10, AttrValue::Int(8));
+        assert_eq!(keys(&attrs, "reveal").len(), 2);
+
+        assert!(remove_key(&mut attrs, "reveal", 0));
+        assert!(!remove_key(&mut attrs, "reveal", 0));
+        assert!(remove_key(&mut attrs, "reveal", 10));
+        assert!(!is_animated(&attrs));
+    }
+
+    #[test]
+    fn test_key_frames_merge_tracks() {
+        let mut attrs = Attrs::new();
+        assert!(key_frames(&attrs).is_empty());
+        set_key(&mut attrs, "opacity", 12, AttrValue::Float(1.0));
+        set_key(&mut attrs, "opacity", 0, AttrValue::Float(0.0));
+        set_key(&mut attrs, "reveal", 12, AttrValue::Int(3));
+        set_key(&mut attrs, "reveal", 5, AttrValue::Int(1));
+        assert_eq!(key_frames(&attrs), vec![0, 5, 12]);
+    }
+}
@@ -30,12 +30,27 @@ pub const A_FRAME: &str = "frame";
 /// from layer extents on every layer mutation. When `false`, `A_IN/A_OUT`
 /// are user-pinned soft markers (After Effects-style "comp duration").
 pub const A_AUTO_BOUNDS: &str = "auto_bounds";
+/// Comp-only: timeline markers, `List` of `Map { frame, label, color }`
+/// (see [`Marker`](super::marker::Marker))
+pub const A_MARKERS: &str = "markers";
+/// Comp-only: review annotations, `List` of `Map { frame, kind, color, size,
+/// points, text }` (see [`Annotation`](super::annotation::Annotation))
+pub const A_ANNOTATIONS: &str = "annotations";
+/// Comp-only: merge node flag. The comp is a two-input Merge (layer 0 = A
+/// over layer 1 = B, blended with A's `blend_mode`); the node editor draws it
+/// with A/B input pins.
+pub const A_MERGE: &str = "merge";
+/// Animation keys, `Map` of attr name -> `List` of `Map { frame, value }`
+/// (see [`keyframe`](super::keyframe))
+pub const A_KEYFRAMES: &str = "keyframes";
 
 // === Compose flags ===
 /// Solo flag - only render this layer
 pub const A_SOLO: &str = "solo";
 /// Mute flag - skip this layer in compose
 pub const A_MUTE: &str = "mute";
+/// Lock flag - timeline edits (move / trim / slide / split / delete) skip this layer
+pub const A_LOCKED: &str = "locked";
 /// Visibility flag
 pub const A_VISIBLE: &str = "visible";
 /// Listed in Project UI (false = hidden preview comp)
@@ -80,10 +95,36 @@ pub const A_FILE_END: &str = "file_end";
 pub const A_WIDTH: &str = "width";
 /// Height in pixels (0 = auto-detect)
 pub const A_HEIGHT: &str = "height";
+/// On `Frame` attrs: proxy downscale factor the buffer was rendered at (1 = full res)
+pub const A_PROXY_FACTOR: &str = "proxy_factor";
+/// Pixel aspect ratio (pixel width / height; 2.0 = 2x anamorphic). Display only:
+/// on `FileNode` from source metadata or user-set, on `Frame` the PAR to display at.
+pub const A_PIXEL_ASPECT: &str = "pixel_aspect";
+/// On `Frame` attrs: transfer of the RGB values (`"srgb"` / `"linear"`).
+/// Missing → inferred from the pixel format (U8 = sRGB, float = linear).
+pub const A_COLOR_SPACE: &str = "color_space";
+/// Alpha association. On `Frame`: `"straight"` / `"premultiplied"` (missing →
+/// straight). On `FileNode`: source alpha type, `"auto"` (default), `"straight"`
+/// or `"premultiplied"`; see `SourceAlpha`.
+pub const A_ALPHA_MODE: &str = "alpha_mode";
+/// On `Frame` attrs: why the frame failed to load (`"<path>: <loader error>"`).
+/// Only set while the status is `Error`; a successful reload removes it.
+pub const A_LOAD_ERROR: &str = "load_error";
 
 // === Layer attributes ===
 /// Source length in frames (invariant, doesn't change with speed)
 pub const A_SRC_LEN: &str = "src_len";
+/// Retime frame blending: mix adjacent source frames when speed lands between them
+pub const A_FRAME_BLEND: &str = "frame_blend";
+/// Time remap: when true, the keyed `remap_frame` curve drives the source frame
+/// instead of `speed`
+pub const A_TIME_REMAP: &str = "time_remap";
+/// Time remap curve: source frame (Float, keyed at comp frames); unkeyed, the
+/// static value holds that source frame
+pub const A_REMAP_FRAME: &str = "remap_frame";
+/// Time remap: ease in and out of every `remap_frame` key (speed ramps settle
+/// at each key) instead of interpolating linearly
+pub const A_REMAP_EASE: &str = "remap_ease";
 
 // === CompNode color depth ===
 /// On `CompNode`: pixel format of the composite output, locked at the
@@ -92,6 +133,18 @@ pub const A_SRC_LEN: &str = "src_len";
 /// values are `"rgba8"`, `"rgba_f16"`, `"rgba_f32"`. Missing attr →
 /// behaviour falls back to the legacy infer-from-layers path.
 pub const A_COMP_DEPTH: &str = "comp_depth";
+/// On `CompNode`: region of interest `[x, y, width, height]` in comp pixels
+/// (top-left origin, Y-down). While set, only that region is composited.
+pub const A_ROI: &str = "roi";
+
+// === FileNode missing frames ===
+/// On `FileNode`: what to show for sequence frames missing on disk.
+/// Stored as string — `"hold"` (default), `"black"` or `"error"`; see
+/// `MissingFramePolicy`.
+pub const A_MISSING_FRAMES: &str = "missing_frames";
+/// On `FileNode` loaded from a single still: how many frames the image is
+/// held for (Int, >= 1). Every frame maps to the one source file.
+pub const A_STILL_FRAMES: &str = "still_frames";
 
 // === AINode attributes ===
 /// On `AINode`: free-form prompt template (provider-agnostic).
@@ -120,6 +173,17 @@ pub const A_ACTIVE_GENERATION: &str = "active_generation";
 /// composited alpha. Nil = no mask. `#[serde(default)]` keeps legacy
 /// saves loading.
 pub const A_MASK_REF_UUID: &str = "mask_ref_uuid";
+
+// === Layer mask shape attributes (see `layer_mask`) ===
+/// On `Layer`: "none", "rectangle" or "ellipse".
+pub const A_MASK_SHAPE: &str = "mask_shape";
+/// On `Layer`: mask bounds `[x0, y0, x1, y1]`, normalized to the layer
+/// source (0..1, top-left origin).
+pub const A_MASK_RECT: &str = "mask_rect";
+/// On `Layer`: soft edge width inside the shape, in source pixels.
+pub const A_MASK_FEATHER: &str = "mask_feather";
+/// On `Layer`: keep the outside of the shape instead of the inside.
+pub const A_MASK_INVERT: &str = "mask_invert";
 /// On `RefNode`: UUID of the node the ref points at. Nil = unset.
 pub const A_TARGET_UUID: &str = "target_uuid";
 /// On `RefNode`: which channel of the target's frame to read. Stored
@@ -0,0 +1,219 @@
+//! Layer mask shapes: a rectangle or ellipse that limits where a layer
+//! composites.
+//!
+//! The shape lives on the layer as plain attrs (`mask_shape`, `mask_rect`,
+//! `mask_feather`, `mask_invert`) in **layer source space**: `mask_rect` is
+//! normalized to the source frame (0..1, top-left origin), so it follows the
+//! layer transform and survives proxy downscales unchanged. Compose
+//! rasterizes it into the layer alpha before effects and blending.
+//!
+//! Feather is an inward falloff measured in full-resolution source pixels:
+//! alpha is untouched deeper than `feather` inside the edge, ramps linearly
+//! to zero at the edge and stays zero outside. Inverting flips the coverage.
+
+use super::attrs::{AttrValue, Attrs};
+use super::frame::Frame;
+use super::keyframe;
+use super::keys::{A_MASK_FEATHER, A_MASK_INVERT, A_MASK_RECT, A_MASK_SHAPE};
+
+/// Mask outline type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaskShape {
+    Rectangle,
+    Ellipse,
+}
+
+impl MaskShape {
+    /// Parse the `mask_shape` attr; `None` for "none" and unknown names.
+    pub fn from_name(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "rectangle" | "rect" => Some(MaskShape::Rectangle),
+            "ellipse" => Some(MaskShape::Ellipse),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MaskShape::Rectangle => "rectangle",
+            MaskShape::Ellipse => "ellipse",
+        }
+    }
+}
+
+/// A layer's mask evaluated at one frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct LayerMask {
+    pub shape: MaskShape,
+    /// `[x0, y0, x1, y1]` normalized to the layer source (corners in any order)
+    pub rect: [f32; 4],
+    /// Inward soft edge, full-resolution source pixels
+    pub feather: f32,
+    pub invert: bool,
+}
+
+impl LayerMask {
+    /// Read the mask attrs at `frame`; `None` when the layer has no mask.
+    pub fn from_attrs(attrs: &Attrs, frame: i32) -> Option<Self> {
+        let shape = MaskShape::from_name(attrs.get_str(A_MASK_SHAPE)?)?;
+        let rect = attrs.get_vec4(A_MASK_RECT).unwrap_or([0.0, 0.0, 1.0, 1.0]);
+        let feather = match keyframe::eval(attrs, A_MASK_FEATHER, frame as f32) {
+            Some(AttrValue::Float(f)) => f,
+            _ => 0.0,
+        };
+        Some(Self {
+            shape,
+            rect,
+            feather: feather.max(0.0),
+            invert: attrs.get_bool(A_MASK_INVERT).unwrap_or(false),
+        })
+    }
+
+    /// Mask bounds in pixels of a `w` x `h` frame: (min, max) corners.
+    pub fn pixel_bounds(&self, w: usize, h: usize) -> ([f32; 2], [f32; 2]) {
+        let [x0, y0, x1, y1] = self.rect;
+        let (w, h) = (w as f32, h as f32);
+        (
+            [x0.min(x1) * w, y0.min(y1) * h],
+            [x0.max(x1) * w, y0.max(y1) * h],
+        )
+    }
+
+    /// Per-pixel coverage (0..1, row-major) for a `w` x `h` frame at
+    /// `proxy` (feather shrinks with the frame).
+    pub fn coverage(&self, w: usize, h: usize, proxy: usize) -> Vec<f32> {
+        let (min, max) = self.pixel_bounds(w, h);
+        let center = [(min[0] + max[0]) * 0.5, (min[1] + max[1]) * 0.5];
+        let half = [(max[0] - min[0]) * 0.5, (max[1] - min[1]) * 0.5];
+        let feather = self.feather / proxy.max(1) as f32;
+
+        let mut out = Vec::with_capacity(w * h);
+        for y in 0..h {
+            for x in 0..w {
+                // Pixel center relative to the shape center
+                let px = x as f32 + 0.5 - center[0];
+                let py = y as f32 + 0.5 - center[1];
+                let inside = match self.shape {
+                    MaskShape::Rectangle => (half[0] - px.abs()).min(half[1] - py.abs()),
+                    MaskShape::Ellipse => ellipse_depth(px, py, half),
+                };
+                let c = if inside <= 0.0 {
+                    0.0
+                } else if feather > 0.0 {
+                    (inside / feather).min(1.0)
+                } else {
+                    1.0
+                };
+                out.push(if self.invert { 1.0 - c } else { c });
+            }
+        }
+        out
+    }
+
+    /// Hashable form of the mask (float fields by bits) for cache keys.
+    pub fn cache_key(&self) -> (&'static str, [u32; 4], u32, bool) {
+        (
+            self.shape.as_str(),
+            self.rect.map(f32::to_bits),
+            self.feather.to_bits(),
+            self.invert,
+        )
+    }
+
+    /// Multiply `frame`'s alpha by the mask coverage.
+    pub fn apply(&self, frame: &Frame) -> Frame {
+        let coverage = self.coverage(frame.width(), frame.height(), frame.proxy_factor());
+        frame.multiply_alpha(&coverage)
+    }
+}
+
+/// Approximate distance inside an axis-aligned ellipse (negative outside),
+/// measured along the ray from the center.
+fn ellipse_depth(px: f32, py: f32, half: [f32; 2]) -> f32 {
+    if half[0] <= 0.0 || half[1] <= 0.0 {
+        return -1.0;
+    }
+    let k = ((px / half[0]).powi(2) + (py / half[1]).powi(2)).sqrt();
+    if k < 1e-6 {
+        return half[0].min(half[1]);
+    }
+    // The edge along this ray sits at (px, py) / k
+    (px * px + py * py).sqrt() * (1.0 / k - 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::frame::PixelBuffer;
+
+    fn alpha(frame: &Frame) -> Vec<f32> {
+        match frame.buffer().as_ref() {
+            PixelBuffer::F32(p) => p.chunks(4).map(|px| px[3]).collect(),
+            _ => panic!("expected f32"),
+        }
+    }
+
+    fn rect_mask(feather: f32) -> LayerMask {
+        // Pixels 2..8 of a 10-wide row; top/bottom edges well off the row
+        LayerMask {
+            shape: MaskShape::Rectangle,
+            rect: [0.2, -10.0, 0.8, 10.0],
+            feather,
+            invert: false,
+        }
+    }
+
+    #[test]
+    fn test_rectangle_zeroes_outside_and_feathers_edge() {
+        let frame = Frame::from_f32_buffer(vec![1.0; 10 * 4], 10, 1);
+
+        let hard = alpha(&rect_mask(0.0).apply(&frame));
+        assert_eq!(hard, [0.0, 0.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 0.0, 0.0]);
+
+        // 2px falloff: pixel centers 0.5 and 1.5 px inside the edge
+        let soft = alpha(&rect_mask(2.0).apply(&frame));
+        assert_eq!(&soft[..2], [0.0, 0.0]);
+        assert!((soft[2] - 0.25).abs() < 1e-6);
+        assert!((soft[3] - 0.75).abs() < 1e-6);
+        assert_eq!(soft[4], 1.0);
+        assert!((soft[7] - 0.25).abs() < 1e-6);
+        assert_eq!(&soft[8..], [0.0, 0.0]);
+
+        // Proxy frames shrink the feather with the pixels
+        assert_eq!(rect_mask(2.0).coverage(10, 1, 2)[2], 0.5);
+    }
+
+    #[test]
+    fn test_invert_and_ellipse() {
+        let mut mask = rect_mask(0.0);
+        mask.invert = true;
+        let cov = mask.coverage(10, 1, 1);
+        assert_eq!(cov[0], 1.0);
+        assert_eq!(cov[5], 0.0);
+
+        let ellipse = LayerMask {
+            shape: MaskShape::Ellipse,
+            rect: [0.0, 0.0, 1.0, 1.0],
+            feather: 0.0,
+            invert: false,
+        };
+        let cov = ellipse.coverage(10, 10, 1);
+        assert_eq!(cov[5 * 10 + 5], 1.0);
+        assert_eq!(cov[0], 0.0); // corner is outside the inscribed ellipse
+    }
+
+    #[test]
+    fn test_from_attrs() {
+        let mut attrs = Attrs::new();
+        assert_eq!(LayerMask::from_attrs(&attrs, 0), None);
+        attrs.set(A_MASK_SHAPE, AttrValue::Str("none".into()));
+        assert_eq!(LayerMask::from_attrs(&attrs, 0), None);
+        attrs.set(A_MASK_SHAPE, AttrValue::Str("ellipse".into()));
+        attrs.set(A_MASK_FEATHER, AttrValue::Float(4.0));
+        let mask = LayerMask::from_attrs(&attrs, 0).unwrap();
+        assert_eq!(mask.shape, MaskShape::Ellipse);
+        assert_eq!(mask.rect, [0.0, 0.0, 1.0, 1.0]);
+        assert_eq!(mask.feather, 4.0);
+        assert!(!mask.invert);
+    }
+}
@@ -1,7 +1,8 @@
-//! Raster / sequence loading — delegated to [`playa_io`] (FFmpeg / EXR / generic).
+//! Raster / sequence loading — delegated to [`playa_io`] (FFmpeg / EXR / DPX / PSD / SVG / generic).
 
 use std::path::Path;
 
+use playa_io::psd::PsdDocument;
 use playa_io::{AttrKv, RawPixelBuffer, RawPixelFormat, decode_raster, header_attrs};
 
 use super::frame::{Frame, FrameError, PixelBuffer, PixelFormat};
@@ -95,4 +96,10 @@ impl Loader {
             dec.height,
         ))
     }
+
+    /// Layer structure of a PSD (bounds, opacity, blend keys; no pixels).
+    /// Layer pixels load lazily through [`Loader::load`] on `doc.psd#<index>`.
+    pub fn psd_layers(path: &Path) -> Result<PsdDocument, FrameError> {
+        playa_io::psd::read_layers(path).map_err(Into::into)
+    }
 }
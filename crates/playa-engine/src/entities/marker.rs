@@ -0,0 +1,107 @@
+//! Timeline markers: named, colored notes pinned to comp frames.
+//!
+//! Stored on the comp's `Attrs` under [`A_MARKERS`](super::keys::A_MARKERS)
+//! as a `List` of `Map { frame, label, color }` so they serialize with the
+//! project like bookmarks do. Non-DAG: adding or editing a marker never
+//! invalidates cached frames.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::attrs::AttrValue;
+
+/// Default marker color palette (cycled by the timeline context menu)
+pub const MARKER_COLORS: &[[f32; 3]] = &[
+    [1.0, 0.75, 0.2],
+    [1.0, 0.35, 0.35],
+    [0.35, 0.85, 0.4],
+    [0.35, 0.65, 1.0],
+    [0.8, 0.45, 1.0],
+];
+
+/// Single timeline marker
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Marker {
+    /// Absolute comp frame
+    pub frame: i32,
+    /// Short label / review note ("fix flicker here")
+    pub label: String,
+    /// Display color (linear 0..1 RGB)
+    pub color: [f32; 3],
+}
+
+impl Marker {
+    /// Marker with the default color and an empty label
+    pub fn new(frame: i32) -> Self {
+        Self {
+            frame,
+            label: String::new(),
+            color: MARKER_COLORS[0],
+        }
+    }
+
+    /// Builder: set label
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+
+    /// Builder: set color
+    pub fn with_color(mut self, color: [f32; 3]) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Encode as an attr map entry
+    pub fn to_attr(&self) -> AttrValue {
+        let mut map = HashMap::new();
+        map.insert("frame".to_string(), AttrValue::Int(self.frame));
+        map.insert("label".to_string(), AttrValue::Str(self.label.clone()));
+        map.insert("color".to_string(), AttrValue::Vec3(self.color));
+        AttrValue::Map(map)
+    }
+
+    /// Decode an attr map entry. Entries without a frame are rejected;
+    /// missing label/color fall back to defaults.
+    pub fn from_attr(value: &AttrValue) -> Option<Self> {
+        let AttrValue::Map(map) = value else {
+            return None;
+        };
+        let frame = match map.get("frame")? {
+            AttrValue::Int(f) => *f,
+            _ => return None,
+        };
+        let mut marker = Self::new(frame);
+        if let Some(AttrValue::Str(label)) = map.get("label") {
+            marker.label = label.clone();
+        }
+        if let Some(AttrValue::Vec3(color)) = map.get("color") {
+            marker.color = *color;
+        }
+        Some(marker)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_marker_attr_roundtrip() {
+        let m = Marker::new(42)
+            .with_label("fix flicker here")
+            .with_color(MARKER_COLORS[2]);
+        assert_eq!(Marker::from_attr(&m.to_attr()), Some(m));
+    }
+
+    #[test]
+    fn test_marker_from_attr_defaults() {
+        let mut map = HashMap::new();
+        map.insert("frame".to_string(), AttrValue::Int(7));
+        let m = Marker::from_attr(&AttrValue::Map(map)).unwrap();
+        assert_eq!(m, Marker::new(7));
+        assert!(Marker::from_attr(&AttrValue::Map(HashMap::new())).is_none());
+        assert!(Marker::from_attr(&AttrValue::Int(7)).is_none());
+    }
+}
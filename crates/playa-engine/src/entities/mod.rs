@@ -3,6 +3,7 @@
 //! and compositor produce `Frame` data that UI/encoding consume.
 
 pub mod ai_node;
+pub mod annotation;
 pub mod attr_schemas;
 pub mod attrs;
 pub mod camera_node;
@@ -13,29 +14,41 @@ pub mod effects;
 pub mod file_node;
 pub mod frame;
 pub mod gpu_blend_bridge;
+pub mod keyframe;
 pub mod keys;
+pub mod layer_mask;
 pub mod loader;
+pub mod marker;
 pub mod node;
 pub mod node_kind;
+pub mod playlist;
 pub mod project;
 pub mod ref_node;
+pub mod solid_node;
 pub mod space;
 pub mod text_node;
+pub mod thumbnail;
 pub mod track_matte;
 pub mod traits;
 pub mod transform;
+pub mod waveform;
 
 pub use attrs::{AttrFlags, AttrValue, Attrs};
 // Type alias for backwards compatibility
 pub type Comp = CompNode;
-pub use comp_node::{CompNode, Layer as NodeLayer};
+pub use annotation::{Annotation, AnnotationKind};
+pub use comp_node::{CompNode, CompWarning, CompWarningKind, Layer as NodeLayer};
 pub use compositor::CompositorType;
-pub use file_node::FileNode;
+pub use file_node::{FileNode, MissingFramePolicy};
 pub use frame::{Frame, FrameStatus};
 pub use gpu_blend_bridge::{GpuBlendBridge, GpuBlendReport, GpuBlendRequest, gpu_blend_arc_pair};
+pub use keyframe::Keyframe;
+pub use layer_mask::{LayerMask, MaskShape};
+pub use marker::Marker;
 // Layer is now only in comp_node.rs (pub use comp_node::Layer as NodeLayer above)
 pub use node::{ComputeContext, Node};
 pub use node_kind::NodeKind;
+pub use playlist::{Playlist, PlaylistItem};
 pub use project::{NodeIter, NodeIterItem, Project};
 
 pub use ai_node::{AINode, Generation, RefSnapshot, sha256_hex};
@@ -43,5 +56,8 @@ pub use camera_node::CameraNode;
 pub use effects::{Effect, EffectType};
 pub use playa_io::{SourceImage, pick_display_layer};
 pub use ref_node::{Channel, RefNode};
+pub use solid_node::SolidNode;
 pub use text_node::TextNode;
-pub use traits::{CacheStatsSnapshot, CacheStrategy, FrameCache, WorkerPool};
+pub use thumbnail::{Thumbnail, ThumbnailCache};
+pub use traits::{CacheStatsSnapshot, CacheStrategy, FrameCache, ProxyLevel, WorkerPool};
+pub use waveform::{AudioPeaks, WaveformCache};
@@ -21,12 +21,12 @@ use uuid::Uuid;
 use crate::defaults::{DEFAULT_DIM, DEFAULT_FPS, DEFAULT_SRC_LEN};
 
 use super::attrs::Attrs;
-use super::frame::Frame;
+use super::frame::{Frame, FrameStatus};
 use super::gpu_blend_bridge::GpuBlendBridge;
 use super::keys::{
     A_FPS, A_FRAME, A_HEIGHT, A_IN, A_OUT, A_SRC_LEN, A_TRIM_IN, A_TRIM_OUT, A_WIDTH,
 };
-use super::traits::{FrameCache, WorkerPool};
+use super::traits::{FrameCache, ProxyLevel, WorkerPool};
 
 /// Context passed to node compute and preload functions.
 /// Contains references to project resources needed for computation.
@@ -69,6 +69,49 @@ pub struct ComputeContext<'a> {
     ///   builds a [`ComputeContext`] without offload (deterministic, no channel wait).
     /// - Nested preload snapshots (preload must not block on Ui while Ui is still scheduling workers).
     pub gpu_blend_bridge: Option<&'a GpuBlendBridge>,
+    /// Proxy resolution for this compute pass. Non-Full levels make FileNodes
+    /// downscale on decode and comps composite at 1/N; frames are cached under
+    /// [`ProxyLevel::cache_uuid`] so they never collide with full-res frames.
+    pub proxy: ProxyLevel,
+}
+
+impl<'a> ComputeContext<'a> {
+    /// Same context at a different proxy level.
+    pub fn with_proxy(&self, proxy: ProxyLevel) -> ComputeContext<'a> {
+        ComputeContext {
+            cache: self.cache,
+            cache_arc: self.cache_arc.clone(),
+            media: self.media,
+            media_arc: self.media_arc.clone(),
+            workers: self.workers,
+            epoch: self.epoch,
+            gpu_blend_bridge: self.gpu_blend_bridge,
+            proxy,
+        }
+    }
+
+    /// Frame cached by content: `hash` (of the attrs that shape the image)
+    /// salts `node_uuid` into a cache slot of its own, so edits that leave
+    /// the image alone (timing) find the previous result. `render` runs on a
+    /// miss.
+    pub fn content_cached(
+        &self,
+        node_uuid: Uuid,
+        hash: u64,
+        render: impl FnOnce() -> Frame,
+    ) -> Frame {
+        let slot = Uuid::from_u128(node_uuid.as_u128() ^ u128::from(hash));
+        if let Some(frame) = self
+            .cache
+            .get(slot, 0)
+            .filter(|f| f.status() == FrameStatus::Loaded)
+        {
+            return frame;
+        }
+        let frame = render();
+        self.cache.insert(slot, 0, frame.clone());
+        frame
+    }
 }
 
 /// Base trait for all node types.
@@ -16,6 +16,7 @@ use super::file_node::FileNode;
 use super::frame::Frame;
 use super::node::{ComputeContext, Node};
 use super::ref_node::RefNode;
+use super::solid_node::SolidNode;
 use super::text_node::TextNode;
 
 /// Enum containing all possible node types.
@@ -27,6 +28,7 @@ pub enum NodeKind {
     Comp(CompNode),
     Camera(CameraNode),
     Text(TextNode),
+    Solid(SolidNode),
     Ref(RefNode),
     AI(AINode),
 }
@@ -52,6 +54,11 @@ impl NodeKind {
         matches!(self, NodeKind::Text(_))
     }
 
+    /// Check if this is a solid/gradient generator node
+    pub fn is_solid(&self) -> bool {
+        matches!(self, NodeKind::Solid(_))
+    }
+
     /// Check if this is a reference node (utility — points at another
     /// node + channel selector).
     pub fn is_ref(&self) -> bool {
@@ -176,6 +183,22 @@ impl NodeKind {
         }
     }
 
+    /// Get as SolidNode reference
+    pub fn as_solid(&self) -> Option<&SolidNode> {
+        match self {
+            NodeKind::Solid(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Get as SolidNode mutable reference
+    pub fn as_solid_mut(&mut self) -> Option<&mut SolidNode> {
+        match self {
+            NodeKind::Solid(n) => Some(n),
+            _ => None,
+        }
+    }
+
     /// Get as `RefNode` reference.
     pub fn as_ref_node(&self) -> Option<&RefNode> {
         match self {
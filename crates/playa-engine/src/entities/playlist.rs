@@ -0,0 +1,241 @@
+//! Review playlists: an ordered list of media paths with optional frame ranges.
+//!
+//! Two on-disk forms, picked by extension on save and sniffed on load:
+//!
+//! JSON (`.json`):
+//! ```json
+//! { "items": [ { "path": "sh010/plate.1001.exr", "in": 1001, "out": 1050 },
+//!              { "path": "sh020.mov" } ] }
+//! ```
+//!
+//! Text (`.m3u`, `.m3u8`, `.playlist`, anything else): one path per line,
+//! `#` lines are comments, and an m3u-style `#RANGE:<in>,<out>` directive
+//! applies to the next path.
+//! ```text
+//! #PLAYA-PLAYLIST
+//! #RANGE:1001,1050
+//! sh010/plate.1001.exr
+//! sh020.mov
+//! ```
+//!
+//! Relative paths resolve against the playlist's directory. A range sets the
+//! loaded clip's work area (source frame numbers, inclusive).
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// First line written to text playlists
+pub const PLAYLIST_HEADER: &str = "#PLAYA-PLAYLIST";
+
+/// Directive carrying the next item's `in,out` range
+const RANGE_DIRECTIVE: &str = "#RANGE:";
+
+/// One reviewable item: a sequence frame, video or still.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaylistItem {
+    pub path: PathBuf,
+    /// Work-area start (source frame number)
+    #[serde(rename = "in", default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<i32>,
+    /// Work-area end (source frame number, inclusive)
+    #[serde(rename = "out", default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<i32>,
+}
+
+impl PlaylistItem {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            start: None,
+            end: None,
+        }
+    }
+
+    pub fn with_range(mut self, start: i32, end: i32) -> Self {
+        self.start = Some(start);
+        self.end = Some(end);
+        self
+    }
+}
+
+/// Ordered review list.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Playlist {
+    pub items: Vec<PlaylistItem>,
+}
+
+impl Playlist {
+    /// Parse playlist text; JSON when it starts with `{`, the line format otherwise.
+    ///
+    /// Relative item paths are joined onto `base_dir`.
+    pub fn parse(text: &str, base_dir: &Path) -> Result<Self, String> {
+        let mut playlist = if text.trim_start().starts_with('{') {
+            serde_json::from_str(text).map_err(|e| format!("Parse playlist error: {}", e))?
+        } else {
+            Self::parse_lines(text)?
+        };
+        for item in &mut playlist.items {
+            if item.path.is_relative() {
+                item.path = base_dir.join(&item.path);
+            }
+        }
+        Ok(playlist)
+    }
+
+    fn parse_lines(text: &str) -> Result<Self, String> {
+        let mut items = Vec::new();
+        let mut range: Option<(i32, i32)> = None;
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if let Some(spec) = line.strip_prefix(RANGE_DIRECTIVE) {
+                let parsed = spec
+                    .split_once(',')
+                    .and_then(|(a, b)| Some((a.trim().parse().ok()?, b.trim().parse().ok()?)));
+                range = Some(parsed.ok_or_else(|| {
+                    format!(
+                        "Parse playlist error: bad range on line {}: {}",
+                        n + 1,
+                        line
+                    )
+                })?);
+                continue;
+            }
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut item = PlaylistItem::new(line);
+            if let Some((start, end)) = range.take() {
+                item = item.with_range(start, end);
+            }
+            items.push(item);
+        }
+        Ok(Self { items })
+    }
+
+    /// Line format (see module docs); round-trips through [`Self::parse`].
+    pub fn to_text(&self) -> String {
+        let mut out = format!("{}\n", PLAYLIST_HEADER);
+        for item in &self.items {
+            if let (Some(start), Some(end)) = (item.start, item.end) {
+                out.push_str(&format!("{}{},{}\n", RANGE_DIRECTIVE, start, end));
+            }
+            out.push_str(&format!("{}\n", item.path.display()));
+        }
+        out
+    }
+
+    /// Load from disk (format sniffed from the content).
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path).map_err(|e| format!("Read playlist error: {}", e))?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        Self::parse(&text, base_dir)
+    }
+
+    /// Save to disk: JSON for `.json`, the line format otherwise.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let path = path.as_ref();
+        let text = if is_json(path) {
+            serde_json::to_string_pretty(self)
+                .map_err(|e| format!("Serialize playlist error: {}", e))?
+        } else {
+            self.to_text()
+        };
+        fs::write(path, text).map_err(|e| format!("Write playlist error: {}", e))
+    }
+
+    /// True if `path` holds a playlist rather than a saved project.
+    ///
+    /// Non-JSON files are always playlists; JSON ones are when they carry a
+    /// top-level `items` array (projects serialize `attrs` + `media`).
+    pub fn is_playlist_file(path: &Path) -> bool {
+        if !is_json(path) {
+            return true;
+        }
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+            .is_some_and(|v| v.get("items").is_some_and(|items| items.is_array()))
+    }
+}
+
+fn is_json(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Playlist {
+        Playlist {
+            items: vec![
+                PlaylistItem::new("/shots/sh010/plate.1001.exr").with_range(1001, 1050),
+                PlaylistItem::new("/shots/sh020.mov"),
+                PlaylistItem::new("/shots/sh030/plate.0001.exr").with_range(-5, 20),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_text_round_trip() {
+        let playlist = sample();
+        let text = playlist.to_text();
+        assert!(text.starts_with(PLAYLIST_HEADER));
+        assert_eq!(
+            Playlist::parse(&text, Path::new("/other")).unwrap(),
+            playlist
+        );
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let playlist = sample();
+        let json = serde_json::to_string(&playlist).unwrap();
+        assert!(json.contains("\"in\":1001"));
+        assert!(!json.contains("\"in\":null"));
+        assert_eq!(
+            Playlist::parse(&json, Path::new("/other")).unwrap(),
+            playlist
+        );
+    }
+
+    #[test]
+    fn test_relative_paths_and_comments() {
+        let text = "# review reel\n\n#RANGE: 10 , 20\nsh010.mov\nsh020.mov\n";
+        let playlist = Playlist::parse(text, Path::new("/reels")).unwrap();
+        assert_eq!(
+            playlist.items,
+            vec![
+                PlaylistItem::new("/reels/sh010.mov").with_range(10, 20),
+                PlaylistItem::new("/reels/sh020.mov"),
+            ]
+        );
+        assert!(Playlist::parse("#RANGE:10\nsh.mov", Path::new("/")).is_err());
+    }
+
+    #[test]
+    fn test_save_load_and_sniff() {
+        let dir = std::env::temp_dir().join(format!("playa_playlist_{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let playlist = sample();
+
+        for name in ["reel.json", "reel.m3u"] {
+            let path = dir.join(name);
+            playlist.save(&path).unwrap();
+            assert!(Playlist::is_playlist_file(&path));
+            assert_eq!(Playlist::load(&path).unwrap(), playlist);
+        }
+
+        // A saved project is not a playlist
+        let project = dir.join("project.json");
+        fs::write(&project, r#"{"attrs": {}, "media": {}}"#).unwrap();
+        assert!(!Playlist::is_playlist_file(&project));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -41,36 +41,42 @@ use std::sync::{Arc, Mutex, RwLock};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-pub use playa_events::GizmoPrefs;
+pub use playa_events::{GizmoPrefs, ViewportDefaults};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ProjectPrefs {
     pub gizmo: GizmoPrefs,
+    pub viewport: ViewportDefaults,
 }
 
 impl Default for ProjectPrefs {
     fn default() -> Self {
         Self {
             gizmo: GizmoPrefs::default(),
+            viewport: ViewportDefaults::default(),
         }
     }
 }
 
-use super::CacheStrategy;
+use super::{CacheStrategy, ProxyLevel};
 use super::attr_schemas::PROJECT_SCHEMA;
 use super::attrs::AttrValue;
 use super::comp_events::{AttrsChangedEvent, CurrentFrameChangedEvent};
-use super::comp_node::CompNode;
+use super::comp_node::{CompNode, CompWarning};
+use super::compositor::BlendMode;
 use super::file_node::FileNode;
-use super::frame::Frame;
+use super::frame::{Frame, FrameError};
 use super::keys::*;
+use super::loader::Loader;
 use super::node::Node;
 use super::node_kind::NodeKind;
 use super::{Attrs, CompositorType};
 use crate::core::cache_man::CacheManager;
 use crate::core::event_bus::EventEmitter;
 use crate::core::global_cache::GlobalFrameCache;
+use crate::defaults::DEFAULT_FPS;
+use playa_io::psd::{self, PsdDocument, PsdLayerKind};
 
 /// Top-level project / scene.
 ///
@@ -78,7 +84,7 @@ use crate::core::global_cache::GlobalFrameCache;
 /// - `order`: List<Uuid> - UI order of media items
 /// - `selection`: List<Uuid> - current selection (ordered)
 /// - `active`: Uuid (optional, missing key = None) - currently active item
-/// - `prefs`: Map - project preferences
+/// - `prefs`: Map - project preferences (gizmo, viewport defaults)
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Project {
     /// All serializable project state (includes order, selection, active)
@@ -111,6 +117,14 @@ pub struct Project {
     #[serde(default = "Project::default_compositor")]
     pub compositor: Mutex<CompositorType>,
 
+    /// Proxy level used for viewport preload (runtime-only, driven by App playback state)
+    #[serde(skip)]
+    proxy_level: Mutex<ProxyLevel>,
+
+    /// Upper bound on preload radius (runtime-only, from app prefs; `None` = work area)
+    #[serde(skip)]
+    max_preload_radius: Mutex<Option<i32>>,
+
     /// Global cache manager (runtime-only, set on creation/load)
     #[serde(skip)]
     cache_manager: Option<Arc<CacheManager>>,
@@ -140,6 +154,8 @@ impl Clone for Project {
                     .unwrap_or_else(|e| e.into_inner())
                     .clone(),
             ),
+            proxy_level: Mutex::new(self.proxy_level()),
+            max_preload_radius: Mutex::new(self.max_preload_radius()),
             cache_manager: self.cache_manager.clone(),
             global_cache: self.global_cache.clone(),
             last_save_path: self.last_save_path.clone(),
@@ -164,6 +180,8 @@ impl Project {
             strategy
         );
 
+        super::effects::cache::attach(&cache_manager);
+
         // Create global frame cache with specified capacity and strategy
         let global_cache = Arc::new(GlobalFrameCache::new(
             10000, // Default capacity: 10k frames
@@ -182,6 +200,8 @@ impl Project {
             media: Arc::new(RwLock::new(HashMap::new())),
             selection_anchor: None,
             compositor: Mutex::new(CompositorType::default()),
+            proxy_level: Mutex::new(ProxyLevel::Full),
+            max_preload_radius: Mutex::new(None),
             cache_manager: Some(cache_manager),
             global_cache: Some(global_cache),
             last_save_path: None,
@@ -195,6 +215,13 @@ impl Project {
         self.event_emitter = Some(emitter);
     }
 
+    /// Emit `event` on the app bus (no-op until an emitter is set).
+    pub fn emit_event<E: crate::core::event_bus::Event + Clone>(&self, event: E) {
+        if let Some(ref emitter) = self.event_emitter {
+            emitter.emit(event);
+        }
+    }
+
     /// Attach schemas to all entities after deserialization.
     /// Must be called after from_json() since schemas are not serialized.
     pub fn attach_schemas(&mut self) {
@@ -212,6 +239,7 @@ impl Project {
                     NodeKind::Comp(c) => c.attach_schema(),
                     NodeKind::Camera(c) => c.attach_schema(),
                     NodeKind::Text(t) => t.attach_schema(),
+                    NodeKind::Solid(s) => s.attach_schema(),
                     NodeKind::Ref(r) => r.attach_schema(),
                     NodeKind::AI(a) => a.attach_schema(),
                 }
@@ -241,11 +269,19 @@ impl Project {
 
         let mut map = HashMap::new();
         map.insert("gizmo".to_string(), AttrValue::Map(gizmo));
+        if !prefs.viewport.is_empty()
+            && let Ok(json) = serde_json::to_string(&prefs.viewport)
+        {
+            map.insert("viewport".to_string(), AttrValue::Json(json));
+        }
         map
     }
 
     fn prefs_from_map(map: &std::collections::HashMap<String, AttrValue>) -> ProjectPrefs {
         let mut prefs = ProjectPrefs::default();
+        if let Some(AttrValue::Json(json)) = map.get("viewport") {
+            prefs.viewport = serde_json::from_str(json).unwrap_or_default();
+        }
         let Some(AttrValue::Map(gizmo)) = map.get("gizmo") else {
             return prefs;
         };
@@ -388,6 +424,18 @@ impl Project {
         self.set_prefs(&prefs);
     }
 
+    /// Convenience: get viewport defaults.
+    pub fn viewport_defaults(&self) -> ViewportDefaults {
+        self.prefs().viewport
+    }
+
+    /// Convenience: set viewport defaults (preserves other preference sections).
+    pub fn set_viewport_defaults(&mut self, viewport: &ViewportDefaults) {
+        let mut prefs = self.prefs();
+        prefs.viewport = viewport.clone();
+        self.set_prefs(&prefs);
+    }
+
     /// Get last save path for quick save
     pub fn last_save_path(&self) -> Option<std::path::PathBuf> {
         self.last_save_path.clone()
@@ -501,6 +549,328 @@ impl Project {
         }
     }
 
+    /// Create a Merge node compositing `a` over `b` with `blend`.
+    ///
+    /// A merge is a regular comp flagged [`A_MERGE`] with exactly two layers
+    /// (A on top, B as background), so it renders through the normal compose
+    /// pipeline and caches like any comp. Resolution and fps follow B; the
+    /// duration covers the longer input. Returns `None` if either input is
+    /// missing or not renderable.
+    pub fn create_merge(&mut self, a: Uuid, b: Uuid, blend: BlendMode) -> Option<Uuid> {
+        let info = |uuid: Uuid| {
+            self.with_node(uuid, |n| {
+                (
+                    n.is_renderable(),
+                    self.gen_name(n.name()),
+                    n.dim(),
+                    n.fps(),
+                    n.frame_count(),
+                )
+            })
+            .filter(|(renderable, ..)| *renderable)
+        };
+        let (_, a_name, a_dim, _, a_len) = info(a)?;
+        let (_, b_name, b_dim, fps, b_len) = info(b)?;
+        let duration = a_len.max(b_len).max(1);
+
+        let mut comp = CompNode::new(&self.gen_name("Merge"), 0, duration - 1, fps);
+        comp.attrs.set(A_MERGE, AttrValue::Bool(true));
+        comp.attrs.set(A_WIDTH, AttrValue::UInt(b_dim.0 as u32));
+        comp.attrs.set(A_HEIGHT, AttrValue::UInt(b_dim.1 as u32));
+        comp.add_child_layer(b, &b_name, 0, b_len.max(1), None, b_dim, true, None)
+            .ok()?;
+        let a_layer = comp
+            .add_child_layer(a, &a_name, 0, a_len.max(1), Some(0), a_dim, true, None)
+            .ok()?;
+        if let Some(attrs) = comp.layers_attrs_get_mut(&a_layer) {
+            attrs.set(A_BLEND_MODE, AttrValue::Str(blend.as_str().to_string()));
+        }
+
+        let uuid = comp.uuid();
+        self.add_node(NodeKind::Comp(comp));
+        log::info!(
+            "Created merge {} ({} over {}, {})",
+            uuid,
+            a,
+            b,
+            blend.as_str()
+        );
+        Some(uuid)
+    }
+
+    /// Rewire input `pin` (0 = A, 1 = B) of the merge `merge_uuid` to `source`.
+    ///
+    /// The input layer keeps its blend mode and transform; its source, name,
+    /// length and size follow the new node, and a new B also resizes the
+    /// merge. Fails if `merge_uuid` is not a merge, `source` is missing or
+    /// not renderable, or the wire would create a cycle.
+    pub fn set_merge_input(
+        &self,
+        merge_uuid: Uuid,
+        pin: usize,
+        source: Uuid,
+    ) -> Result<(), String> {
+        if self.would_create_cycle(merge_uuid, source) {
+            return Err("would create dependency cycle".to_string());
+        }
+        let (name, dim, len) = self
+            .with_node(source, |n| {
+                n.is_renderable()
+                    .then(|| (self.gen_name(n.name()), n.dim(), n.frame_count()))
+            })
+            .flatten()
+            .ok_or_else(|| format!("merge input {source} missing or not renderable"))?;
+        let layer_uuid = self
+            .with_comp(merge_uuid, |c| {
+                c.merge_inputs()
+                    .and_then(|_| c.layers.get(pin))
+                    .map(|l| l.uuid())
+            })
+            .flatten()
+            .ok_or_else(|| format!("{merge_uuid} is not a merge with input {pin}"))?;
+
+        self.modify_comp(merge_uuid, |comp| {
+            if pin == 1 {
+                comp.attrs.set(A_WIDTH, AttrValue::UInt(dim.0 as u32));
+                comp.attrs.set(A_HEIGHT, AttrValue::UInt(dim.1 as u32));
+            }
+            if let Some(attrs) = comp.layers_attrs_get_mut(&layer_uuid) {
+                attrs.set_uuid(A_SOURCE_UUID, source);
+                attrs.set(A_NAME, AttrValue::Str(name));
+                attrs.set(A_SRC_LEN, AttrValue::Int(len.max(1)));
+                attrs.set(A_WIDTH, AttrValue::UInt(dim.0 as u32));
+                attrs.set(A_HEIGHT, AttrValue::UInt(dim.1 as u32));
+            }
+        });
+        log::info!("Merge {} input {} -> {}", merge_uuid, pin, source);
+        Ok(())
+    }
+
+    /// Import a layered PSD as a comp, held for `frames` frames.
+    ///
+    /// Each RGB raster layer becomes a still FileNode (`doc.psd#<index>`)
+    /// placed in the comp at its document position, with the PSD layer's
+    /// name, opacity, visibility and blend mode. Groups become nested comps
+    /// carrying the group's opacity, visibility and blend mode; pass-through
+    /// groups flatten into their parent with their visibility and opacity
+    /// folded into the children. Clipped layers decode already masked by
+    /// their base and take on its visibility and opacity. Blend modes without
+    /// a [`BlendMode`] equivalent flatten to Normal; adjustment /
+    /// smart-object layers are skipped, each with a warning.
+    pub fn import_psd(&mut self, path: &Path, frames: i32) -> Result<Uuid, FrameError> {
+        let doc = Loader::psd_layers(path)?;
+        Ok(self.import_psd_document(path, &doc, frames))
+    }
+
+    fn import_psd_document(&mut self, path: &Path, doc: &PsdDocument, frames: i32) -> Uuid {
+        let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("psd");
+        let name = self.gen_name(stem);
+        let uuid = self.import_psd_group(path, doc, None, &name, frames.max(1));
+        log::info!(
+            "Imported PSD {} as comp {} ({} records)",
+            path.display(),
+            uuid,
+            doc.layers.len()
+        );
+        uuid
+    }
+
+    /// Comp of the PSD layers owned by `group` (`None` = document root);
+    /// nested groups are imported recursively.
+    fn import_psd_group(
+        &mut self,
+        path: &Path,
+        doc: &PsdDocument,
+        group: Option<usize>,
+        name: &str,
+        frames: i32,
+    ) -> Uuid {
+        let mut comp = CompNode::new(name, 0, frames - 1, DEFAULT_FPS);
+        comp.attrs.set(A_WIDTH, AttrValue::UInt(doc.width));
+        comp.attrs.set(A_HEIGHT, AttrValue::UInt(doc.height));
+
+        // PSD layers are stored bottom first; inserting each at the top keeps the stack order
+        for layer in &doc.layers {
+            let (owner, mut visible, mut opacity) = doc.owner(layer);
+            if owner != group {
+                continue;
+            }
+            let (source_uuid, (w, h), position) = match layer.kind {
+                PsdLayerKind::Raster if layer.width > 0 && layer.height > 0 => {
+                    let (w, h) = (layer.width as usize, layer.height as usize);
+                    let mask = psd::layer_path(path, layer.index)
+                        .to_string_lossy()
+                        .to_string();
+                    let mut file = FileNode::new(mask, 0, 0, DEFAULT_FPS);
+                    file.attrs.set(A_NAME, AttrValue::Str(layer.name.clone()));
+                    file.attrs.set(A_WIDTH, AttrValue::UInt(layer.width));
+                    file.attrs.set(A_HEIGHT, AttrValue::UInt(layer.height));
+                    file.set_still_frames(frames);
+                    let file_uuid = file.uuid();
+                    self.add_node(NodeKind::File(file));
+
+                    // Document top-left origin -> frame space (centre origin, +Y up)
+                    let x = layer.left as f32 + w as f32 / 2.0 - doc.width as f32 / 2.0;
+                    let y = doc.height as f32 / 2.0 - (layer.top as f32 + h as f32 / 2.0);
+                    (file_uuid, (w, h), Some([x, y, 0.0]))
+                }
+                PsdLayerKind::Raster => continue, // empty layer
+                // Children already placed in this comp
+                PsdLayerKind::Group if layer.blend_key == psd::PASS_THROUGH => continue,
+                PsdLayerKind::Group => {
+                    let name = self.gen_name(&layer.name);
+                    let uuid = self.import_psd_group(path, doc, Some(layer.index), &name, frames);
+                    (uuid, (doc.width as usize, doc.height as usize), None)
+                }
+                PsdLayerKind::GroupEnd => continue,
+                kind => {
+                    log::warn!(
+                        "PSD {}: skipping {:?} layer '{}'",
+                        path.display(),
+                        kind,
+                        layer.name
+                    );
+                    continue;
+                }
+            };
+
+            // The clipping base's visibility and opacity apply to the whole clipping group
+            if let Some(base) = layer.clip_base.map(|i| &doc.layers[i]) {
+                visible &= base.visible;
+                opacity *= base.opacity as f32 / 255.0;
+            } else if layer.clipped {
+                log::warn!(
+                    "PSD {}: layer '{}' is clipped to a group or adjustment layer, importing unclipped",
+                    path.display(),
+                    layer.name
+                );
+            }
+
+            let Ok(layer_uuid) = comp.add_child_layer(
+                source_uuid,
+                &layer.name,
+                0,
+                frames,
+                Some(0),
+                (w, h),
+                true,
+                position,
+            ) else {
+                continue;
+            };
+
+            let blend = BlendMode::from_psd_key(&layer.blend_key).unwrap_or_else(|| {
+                log::warn!(
+                    "PSD {}: blend mode '{}' of layer '{}' not supported, using Normal",
+                    path.display(),
+                    layer.blend_key,
+                    layer.name
+                );
+                BlendMode::Normal
+            });
+            if let Some(attrs) = comp.layers_attrs_get_mut(&layer_uuid) {
+                attrs.set(A_OPACITY, AttrValue::Float(opacity));
+                attrs.set(A_VISIBLE, AttrValue::Bool(visible));
+                attrs.set(A_BLEND_MODE, AttrValue::Str(blend.as_str().to_string()));
+            }
+        }
+
+        let uuid = comp.uuid();
+        self.add_node(NodeKind::Comp(comp));
+        uuid
+    }
+
+    /// Add `source_uuid` as a layer of `comp_uuid` starting at `start_frame`.
+    ///
+    /// `insert_idx` is the layer index (0 = top); `None` appends at the bottom.
+    /// The layer spans the source's trimmed play range; cameras start at
+    /// Z=+800 so they look at the layers. Refuses dependency cycles.
+    /// Returns the new layer's instance UUID.
+    pub fn add_layer(
+        &self,
+        comp_uuid: Uuid,
+        source_uuid: Uuid,
+        start_frame: i32,
+        insert_idx: Option<usize>,
+    ) -> Result<Uuid, String> {
+        // Cycle check BEFORE any mutation - prevents comp A -> B -> A
+        if self.would_create_cycle(comp_uuid, source_uuid) {
+            return Err("would create dependency cycle".to_string());
+        }
+
+        // Get source info and generate name BEFORE write lock
+        // Use play_range() to get trimmed duration (respects B/N trim points)
+        let (duration, source_dim, name, renderable, is_camera) = self
+            .with_node(source_uuid, |s| {
+                let name = self.gen_name(s.name());
+                let (start, end) = s.play_range(true);
+                let trimmed_duration = (end - start + 1).max(1);
+                let renderable = s.is_renderable(); // false for camera/light/null/audio
+                let is_camera = s.as_camera().is_some();
+                (trimmed_duration, s.dim(), name, renderable, is_camera)
+            })
+            .unwrap_or((1, (64, 64), "layer_1".to_string(), true, false));
+
+        let mut media = self.media.write().expect("media lock poisoned");
+        let Some(arc_node) = media.get_mut(&comp_uuid) else {
+            return Err("parent comp not found".to_string());
+        };
+        // Arc::make_mut: copy-on-write for mutation
+        let node = Arc::make_mut(arc_node);
+        // WHY CAMERA GETS Z=+1000:
+        // Camera uses RH convention: looks in -Z direction.
+        // Camera at Z=+1000 looks toward origin (Z=0) where layers live.
+        // User can adjust via layer.position in inspector.
+        let initial_pos = if is_camera {
+            Some([0.0, 0.0, 800.0])
+        } else {
+            None
+        };
+        let layer_uuid = node
+            .add_child_layer(
+                source_uuid,
+                &name,
+                start_frame,
+                duration,
+                insert_idx,
+                source_dim,
+                renderable,
+                initial_pos,
+            )
+            .map_err(|e| e.to_string())?;
+        drop(media);
+
+        self.validate_comp(comp_uuid);
+        Ok(layer_uuid)
+    }
+
+    /// Run [`CompNode::validate`] and store the result on the comp for the UI.
+    ///
+    /// Called when a layer is added and when the comp is activated. New
+    /// warnings are logged; nothing is blocked. Returns the warnings (empty
+    /// if `comp_uuid` is not a comp).
+    pub fn validate_comp(&self, comp_uuid: Uuid) -> Vec<CompWarning> {
+        let mut media = self.media.write().expect("media lock poisoned");
+        let Some(comp) = media.get(&comp_uuid).and_then(|n| n.as_comp()) else {
+            return Vec::new();
+        };
+        let warnings = comp.validate(&media);
+        if comp.warnings == warnings {
+            return warnings;
+        }
+        for warning in warnings.iter().filter(|w| !comp.warnings.contains(w)) {
+            log::warn!("Comp '{}': {}", comp.name(), warning);
+        }
+        // Runtime-only field: set directly, no dirty flag / recompose
+        if let Some(comp) = media
+            .get_mut(&comp_uuid)
+            .and_then(|n| Arc::make_mut(n).as_comp_mut())
+        {
+            comp.warnings = warnings.clone();
+        }
+        warnings
+    }
+
     /// Serialize project to JSON file.
     pub fn to_json<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
         let json = serde_json::to_string_pretty(self)
@@ -602,6 +972,41 @@ impl Project {
         *self.compositor.lock().unwrap_or_else(|e| e.into_inner()) = compositor;
     }
 
+    /// Current proxy level for preload/viewport lookups.
+    pub fn proxy_level(&self) -> ProxyLevel {
+        *self.proxy_level.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Set proxy level. App sets the preferred level while playing and
+    /// `ProxyLevel::Full` when paused, so the paused frame swaps to full res.
+    pub fn set_proxy_level(&self, level: ProxyLevel) {
+        *self.proxy_level.lock().unwrap_or_else(|e| e.into_inner()) = level;
+    }
+
+    /// Cap on frames preloaded either side of the playhead (`None` = no cap).
+    pub fn max_preload_radius(&self) -> Option<i32> {
+        *self
+            .max_preload_radius
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Set the preload cap. Bounds every preload, including "entire comp"
+    /// requests, so long sequences don't fill RAM on small machines.
+    pub fn set_max_preload_radius(&self, radius: Option<i32>) {
+        *self
+            .max_preload_radius
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = radius.map(|r| r.max(0));
+    }
+
+    /// Requested preload `radius` (negative = entire comp) bounded by the cap.
+    pub fn capped_preload_radius(&self, radius: i32) -> i32 {
+        let radius = if radius < 0 { i32::MAX } else { radius };
+        self.max_preload_radius()
+            .map_or(radius, |cap| radius.min(cap))
+    }
+
     // === Node access methods ===
 
     /// Access node by reference via closure (no clone).
@@ -641,9 +1046,20 @@ impl Project {
 
     /// Get cached frame for comp (non-blocking, returns None if not in cache)
     /// Viewport uses this - actual computation happens in workers via preload.
+    ///
+    /// Looks up the current proxy level first, then falls back to any other level
+    /// so the viewport keeps showing pixels while the preferred level computes.
     pub fn compute_frame(&self, comp_uuid: Uuid, frame_idx: i32) -> Option<Frame> {
         let cache = self.global_cache.as_ref()?;
-        cache.get(comp_uuid, frame_idx)
+        let level = self.proxy_level();
+        cache
+            .get(level.cache_uuid(comp_uuid), frame_idx)
+            .or_else(|| {
+                ProxyLevel::ALL
+                    .into_iter()
+                    .filter(|&l| l != level)
+                    .find_map(|l| cache.get(l.cache_uuid(comp_uuid), frame_idx))
+            })
     }
 
     /// Update node in media pool
@@ -707,6 +1123,31 @@ impl Project {
         }
     }
 
+    /// Set the same attrs on every node in `uuids` (Attribute Editor
+    /// multi-select).
+    ///
+    /// Unlike [`modify_node`](Self::modify_node) this emits no per-node
+    /// `AttrsChangedEvent`: returns the nodes whose DAG attrs changed so the
+    /// caller can invalidate the whole batch once. Missing uuids are skipped.
+    pub fn set_nodes_attrs(&self, uuids: &[Uuid], attrs: &[(String, AttrValue)]) -> Vec<Uuid> {
+        let mut media = self.media.write().expect("media lock poisoned");
+        let mut changed = Vec::new();
+        for uuid in uuids {
+            let Some(arc_node) = media.get_mut(uuid) else {
+                continue;
+            };
+            let node = Arc::make_mut(arc_node);
+            for (key, value) in attrs {
+                node.attrs_mut().set(key.clone(), value.clone());
+            }
+            if node.is_dirty(None) {
+                node.clear_dirty();
+                changed.push(*uuid);
+            }
+        }
+        changed
+    }
+
     /// Modify CompNode in-place via closure.
     ///
     /// Auto-emits `AttrsChangedEvent` when comp or any layer is dirty after modification (first —
@@ -787,6 +1228,59 @@ impl Project {
         uuid
     }
 
+    /// Wrap a source in a new listed comp: one layer at frame 0, comp sized and
+    /// timed to the source. `range` (source frame numbers, inclusive) becomes
+    /// the comp's work area. Returns `None` if the source doesn't exist.
+    pub fn create_comp_for_source(
+        &mut self,
+        source_uuid: Uuid,
+        range: Option<(i32, i32)>,
+    ) -> Option<Uuid> {
+        let (name, dim, duration, fps, renderable, src_in) = self.with_node(source_uuid, |n| {
+            (
+                n.name().to_string(),
+                n.dim(),
+                n.frame_count(),
+                n.fps(),
+                n.is_renderable(),
+                n._in(),
+            )
+        })?;
+
+        let mut comp = CompNode::new(&name, 0, (duration - 1).max(0), fps);
+        let _ = comp.add_child_layer(source_uuid, &name, 0, duration, None, dim, renderable, None);
+        comp.fit_dim_to_first_layer();
+        if let Some((start, end)) = range {
+            comp.set_comp_play_start(start.min(end) - src_in);
+            comp.set_comp_play_end(start.max(end) - src_in);
+            comp.set_frame(comp.work_area().0);
+        }
+
+        let uuid = comp.uuid();
+        self.add_node(NodeKind::Comp(comp));
+        Some(uuid)
+    }
+
+    /// Deep-copy a comp (new uuid, duplicated layers and attrs) and list it
+    /// right after the original. Returns the copy's uuid, `None` if `uuid`
+    /// isn't a comp.
+    pub fn duplicate_comp(&mut self, uuid: Uuid) -> Option<Uuid> {
+        let name = self.with_comp(uuid, |comp| comp.name().to_string())?;
+        let name = self.gen_name(&name);
+        let copy = self.with_comp(uuid, |comp| comp.duplicate(&name))?;
+        let new_uuid = copy.uuid();
+        self.add_node(NodeKind::Comp(copy));
+        let mut order = self.order();
+        order.retain(|u| *u != new_uuid);
+        let idx = order
+            .iter()
+            .position(|u| *u == uuid)
+            .map_or(order.len(), |i| i + 1);
+        order.insert(idx, new_uuid);
+        self.set_order(order);
+        Some(new_uuid)
+    }
+
     /// Create and add new FileNode, returns its UUID
     pub fn create_file(&mut self, file_mask: String, start: i32, end: i32, fps: f32) -> Uuid {
         let file = FileNode::new(file_mask, start, end, fps);
@@ -839,6 +1333,7 @@ impl Project {
         let media = self.media.read().expect("media lock poisoned");
         log::info!("Project::set_cache_manager() called, {} nodes", media.len());
         drop(media);
+        super::effects::cache::attach(&manager);
         self.cache_manager = Some(manager);
     }
 
@@ -1103,12 +1598,153 @@ mod arc_rwlock_hashmap {
 mod tests {
     use super::*;
     use crate::core::cache_man::CacheManager;
+    use crate::entities::comp_node::CompWarningKind;
 
     fn test_project() -> Project {
         let cache_manager = Arc::new(CacheManager::new(0.75, 2.0));
         Project::new(cache_manager)
     }
 
+    #[test]
+    fn test_preload_radius_cap() {
+        let project = test_project();
+        // No cap: "entire comp" stays unbounded
+        assert_eq!(project.capped_preload_radius(-1), i32::MAX);
+        assert_eq!(project.capped_preload_radius(500), 500);
+
+        project.set_max_preload_radius(Some(120));
+        assert_eq!(project.capped_preload_radius(-1), 120);
+        assert_eq!(project.capped_preload_radius(i32::MAX), 120);
+        assert_eq!(project.capped_preload_radius(50), 50);
+        assert_eq!(project.clone().max_preload_radius(), Some(120));
+
+        project.set_max_preload_radius(None);
+        assert_eq!(project.capped_preload_radius(500), 500);
+    }
+
+    #[test]
+    fn test_create_comp_for_source_sets_work_area() {
+        let mut project = test_project();
+        let file = project.create_file("shot.*.exr".to_string(), 1001, 1100, 24.0);
+
+        let comp = project
+            .create_comp_for_source(file, Some((1011, 1020)))
+            .expect("comp");
+        let (bounds, work, frame) = project
+            .with_comp(comp, |c| ((c._in(), c._out()), c.work_area(), c.frame()))
+            .unwrap();
+        assert_eq!(bounds, (0, 99));
+        assert_eq!(work, (10, 19));
+        assert_eq!(frame, 10);
+        assert_eq!(project.order(), vec![file, comp]);
+
+        assert!(
+            project
+                .create_comp_for_source(Uuid::new_v4(), None)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_viewport_defaults_roundtrip() {
+        let mut project = test_project();
+        assert!(project.viewport_defaults().is_empty());
+
+        let defaults = ViewportDefaults {
+            view_transform: Some("tonemap_aces".to_string()),
+            background: Some([118, 118, 118]),
+            checker: None,
+        };
+        project.set_viewport_defaults(&defaults);
+        // Gizmo section survives, viewport section reads back
+        assert_eq!(
+            project.gizmo_prefs().pref_manip_size,
+            GizmoPrefs::default().pref_manip_size
+        );
+        assert_eq!(project.viewport_defaults(), defaults);
+
+        let json = serde_json::to_string(&project).unwrap();
+        let loaded: Project = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.viewport_defaults(), defaults);
+    }
+
+    #[test]
+    fn test_set_nodes_attrs_batch() {
+        let mut project = test_project();
+        let files: Vec<Uuid> = (0..3)
+            .map(|i| {
+                let file = FileNode::new(format!("/shots/s{i}.*.exr"), 1, 10, 24.0);
+                let uuid = file.uuid();
+                project.add_node(NodeKind::File(file));
+                uuid
+            })
+            .collect();
+        let alpha_mode = |uuid| {
+            project
+                .with_node(uuid, |n| {
+                    n.attrs().get_str("alpha_mode").map(str::to_string)
+                })
+                .flatten()
+        };
+        let straight = || ("alpha_mode".to_string(), AttrValue::Str("straight".into()));
+
+        // Two of three selected, plus a stale uuid that is skipped
+        let selected = [files[0], files[2], Uuid::new_v4()];
+        let changed = project.set_nodes_attrs(&selected, &[straight()]);
+        assert_eq!(changed, vec![files[0], files[2]]);
+        assert_eq!(alpha_mode(files[0]).as_deref(), Some("straight"));
+        assert_ne!(alpha_mode(files[1]).as_deref(), Some("straight"));
+        assert_eq!(alpha_mode(files[2]).as_deref(), Some("straight"));
+
+        // Same value again, or a non-DAG attr: written, nothing to invalidate
+        assert!(project.set_nodes_attrs(&selected, &[straight()]).is_empty());
+        let fps = (A_FPS.to_string(), AttrValue::Float(30.0));
+        assert!(project.set_nodes_attrs(&selected, &[fps]).is_empty());
+        assert_eq!(project.with_node(files[2], |n| n.fps()), Some(30.0));
+    }
+
+    #[test]
+    fn test_duplicate_comp_is_independent() {
+        let mut project = test_project();
+        let mut comp = CompNode::new("shot", 0, 100, 24.0);
+        let source = Uuid::new_v4();
+        let layer = comp
+            .add_child_layer(source, "plate", 0, 100, None, (64, 64), true, None)
+            .unwrap();
+        let orig = comp.uuid();
+        let work_area = comp.work_area();
+        project.add_node(NodeKind::Comp(comp));
+        let other = CompNode::new("other", 0, 10, 24.0);
+        let other_uuid = other.uuid();
+        project.add_node(NodeKind::Comp(other));
+
+        let copy = project.duplicate_comp(orig).unwrap();
+        assert_ne!(copy, orig);
+        // Listed right after the original
+        assert_eq!(project.order(), vec![orig, copy, other_uuid]);
+        let (copy_layer, copy_source, name) = project
+            .with_comp(copy, |c| {
+                let l = &c.layers[0];
+                (l.uuid(), l.source_uuid(), c.name().to_string())
+            })
+            .unwrap();
+        assert_ne!(copy_layer, layer);
+        assert_eq!(copy_source, source);
+        assert_ne!(name, "shot");
+
+        project.modify_comp(copy, |c| {
+            c.set_child_attrs(copy_layer, vec![(A_OPACITY, AttrValue::Float(0.25))]);
+            c.set_comp_play_start(5);
+        });
+        let orig_state = project
+            .with_comp(orig, |c| {
+                (c.get_layer(layer).unwrap().opacity(), c.work_area())
+            })
+            .unwrap();
+        assert_eq!(orig_state, (1.0, work_area));
+        assert_eq!(project.duplicate_comp(Uuid::new_v4()), None);
+    }
+
     #[test]
     fn test_iter_node_empty() {
         let project = test_project();
@@ -1161,4 +1797,323 @@ mod tests {
         // Unrelated nodes are not ancestors
         assert!(!project.is_ancestor(a, b));
     }
+
+    #[test]
+    fn test_create_merge() {
+        let mut project = test_project();
+        let a = CompNode::new("fg", 0, 49, 24.0);
+        let mut b = CompNode::new("bg", 0, 99, 25.0);
+        b.attrs.set(A_WIDTH, AttrValue::UInt(640));
+        b.attrs.set(A_HEIGHT, AttrValue::UInt(480));
+        let (a_uuid, b_uuid) = (a.uuid(), b.uuid());
+        project.add_node(NodeKind::Comp(a));
+        project.add_node(NodeKind::Comp(b));
+
+        let merge = project
+            .create_merge(a_uuid, b_uuid, BlendMode::Screen)
+            .unwrap();
+        project
+            .with_comp(merge, |comp| {
+                assert!(comp.is_merge());
+                assert_eq!(comp.merge_inputs(), Some((a_uuid, b_uuid)));
+                assert_eq!(comp.layers[0].blend_mode(), BlendMode::Screen);
+                assert_eq!(comp.dim(), (640, 480));
+                assert_eq!(comp.fps(), 25.0);
+                assert_eq!(comp.frame_count(), 100);
+            })
+            .unwrap();
+
+        assert!(
+            project
+                .create_merge(a_uuid, Uuid::new_v4(), BlendMode::Normal)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_merge_blends_inputs() {
+        use super::super::ref_node::Channel;
+        use super::super::solid_node::SolidNode;
+        use super::super::track_matte::sample_channel;
+
+        let mut project = test_project();
+        let mut solid = |name: &str, rgba: [f32; 4]| {
+            let mut node = SolidNode::new(name);
+            node.set_size(4, 4);
+            node.set_color(rgba);
+            let uuid = node.uuid();
+            project.add_node(NodeKind::Solid(node));
+            uuid
+        };
+        let red = solid("red", [0.5, 0.0, 0.0, 1.0]);
+        let blue = solid("blue", [0.0, 0.0, 0.5, 1.0]);
+        let green = solid("green", [0.0, 0.5, 0.0, 1.0]);
+        let merge = project.create_merge(red, blue, BlendMode::Add).unwrap();
+
+        let rgb = |project: &Project| {
+            let comp = project.clone_comp(merge).unwrap();
+            let frame = comp.get_frame(0, project, true).unwrap();
+            [Channel::Red, Channel::Green, Channel::Blue]
+                .map(|c| (sample_channel(&frame, 0, 0, c) * 10.0).round() / 10.0)
+        };
+        assert_eq!(rgb(&project), [0.5, 0.0, 0.5]);
+
+        // Rewiring A changes the output (the app clears the comp's cache on
+        // the AttrsChangedEvent this emits)
+        project.set_merge_input(merge, 0, green).unwrap();
+        if let Some(cache) = project.global_cache.as_ref() {
+            cache.clear_comp(merge, false, None);
+        }
+        assert_eq!(rgb(&project), [0.0, 0.5, 0.5]);
+        let inputs = project.with_comp(merge, |c| c.merge_inputs()).flatten();
+        assert_eq!(inputs, Some((green, blue)));
+
+        // Not a merge / cycles are refused
+        assert!(project.set_merge_input(blue, 0, green).is_err());
+        assert!(project.set_merge_input(merge, 1, merge).is_err());
+    }
+
+    #[test]
+    fn test_import_psd_document() {
+        use playa_io::psd::PsdLayer;
+
+        let layer = |index, name: &str, kind, rect: (i32, i32, u32, u32), blend: &str| PsdLayer {
+            index,
+            name: name.to_string(),
+            kind,
+            left: rect.0,
+            top: rect.1,
+            width: rect.2,
+            height: rect.3,
+            opacity: 255,
+            visible: true,
+            blend_key: blend.to_string(),
+            group: None,
+            clipped: false,
+            clip_base: None,
+        };
+        let mut sky = layer(
+            1,
+            "Sky patch",
+            PsdLayerKind::Raster,
+            (100, 50, 200, 100),
+            "scrn",
+        );
+        sky.opacity = 51;
+        sky.visible = false;
+        let doc = PsdDocument {
+            width: 400,
+            height: 300,
+            depth: 8,
+            channels: 3,
+            layers: vec![
+                layer(
+                    0,
+                    "Background",
+                    PsdLayerKind::Raster,
+                    (0, 0, 400, 300),
+                    "norm",
+                ),
+                sky,
+                layer(2, "Levels", PsdLayerKind::Adjustment, (0, 0, 0, 0), "norm"),
+                layer(3, "Glow", PsdLayerKind::Raster, (0, 0, 10, 10), "vLit"),
+            ],
+        };
+
+        let mut project = test_project();
+        let uuid = project.import_psd_document(Path::new("/mattes/city.psd"), &doc, 24);
+        let (names, sources) = project
+            .with_comp(uuid, |comp| {
+                assert_eq!(comp.dim(), (400, 300));
+                assert_eq!(comp.frame_count(), 24);
+                // Top of the stack first; the adjustment layer is skipped
+                let names: Vec<String> = comp
+                    .layers
+                    .iter()
+                    .map(|l| l.attrs.get_str(A_NAME).unwrap_or_default().to_string())
+                    .collect();
+
+                let sky = &comp.layers[1];
+                assert_eq!(sky.blend_mode(), BlendMode::Screen);
+                assert!((sky.opacity() - 0.2).abs() < 1e-6);
+                assert!(!sky.is_visible());
+                // Doc rect centre (200, 100) -> frame space (0, 50)
+                assert_eq!(sky.transform_at(0).0, [0.0, 50.0, 0.0]);
+                // Unsupported blend mode flattens to Normal
+                assert_eq!(comp.layers[0].blend_mode(), BlendMode::Normal);
+
+                let sources: Vec<Uuid> = comp.layers.iter().map(|l| l.source_uuid()).collect();
+                (names, sources)
+            })
+            .unwrap();
+        assert_eq!(names, ["Glow", "Sky patch", "Background"]);
+
+        let (mask, dim, frames) = project
+            .with_node(sources[1], |n| {
+                let file = n.as_file().unwrap();
+                (file.file_mask(), n.dim(), n.frame_count())
+            })
+            .unwrap();
+        assert_eq!(mask.as_deref(), Some("/mattes/city.psd#1"));
+        assert_eq!(dim, (200, 100));
+        assert_eq!(frames, 24);
+    }
+
+    #[test]
+    fn test_import_psd_groups_and_clipping() {
+        use playa_io::psd::PsdLayer;
+
+        let layer = |index, name: &str, kind, group| PsdLayer {
+            index,
+            name: name.to_string(),
+            kind,
+            left: 0,
+            top: 0,
+            width: if kind == PsdLayerKind::Raster { 100 } else { 0 },
+            height: if kind == PsdLayerKind::Raster { 100 } else { 0 },
+            opacity: 255,
+            visible: true,
+            blend_key: "norm".to_string(),
+            group,
+            clipped: false,
+            clip_base: None,
+        };
+        // Bottom first: clip base, clipped layer, then a hidden multiply group
+        // inside a half-opacity pass-through group
+        let mut base = layer(0, "Base", PsdLayerKind::Raster, None);
+        base.opacity = 128;
+        let mut shade = layer(1, "Shade", PsdLayerKind::Raster, None);
+        shade.clipped = true;
+        shade.clip_base = Some(0);
+        let mut trees = layer(5, "Trees", PsdLayerKind::Group, Some(6));
+        trees.visible = false;
+        trees.blend_key = "mul ".to_string();
+        let mut set = layer(6, "Set", PsdLayerKind::Group, None);
+        set.opacity = 128;
+        set.blend_key = "pass".to_string();
+        let doc = PsdDocument {
+            width: 100,
+            height: 100,
+            depth: 8,
+            channels: 3,
+            layers: vec![
+                base,
+                shade,
+                layer(2, "</Set>", PsdLayerKind::GroupEnd, Some(6)),
+                layer(3, "</Trees>", PsdLayerKind::GroupEnd, Some(5)),
+                layer(4, "Tree", PsdLayerKind::Raster, Some(5)),
+                trees,
+                set,
+            ],
+        };
+
+        let mut project = test_project();
+        let uuid = project.import_psd_document(Path::new("/mattes/forest.psd"), &doc, 24);
+        let half = 128.0 / 255.0;
+        let group_comp = project
+            .with_comp(uuid, |comp| {
+                let names: Vec<&str> = comp
+                    .layers
+                    .iter()
+                    .map(|l| l.attrs.get_str(A_NAME).unwrap_or_default())
+                    .collect();
+                // The pass-through group flattens into the root
+                assert_eq!(names, ["Trees", "Shade", "Base"]);
+
+                // Hidden group: one hidden layer, pass-through opacity folded in
+                let trees = &comp.layers[0];
+                assert!(!trees.is_visible());
+                assert_eq!(trees.blend_mode(), BlendMode::Multiply);
+                assert!((trees.opacity() - half).abs() < 1e-6);
+
+                // Clipped layer takes the base's opacity
+                assert!((comp.layers[1].opacity() - half).abs() < 1e-6);
+                trees.source_uuid()
+            })
+            .unwrap();
+
+        let (dim, children) = project
+            .with_comp(group_comp, |comp| {
+                let children: Vec<(String, bool)> = comp
+                    .layers
+                    .iter()
+                    .map(|l| {
+                        let name = l.attrs.get_str(A_NAME).unwrap_or_default();
+                        (name.to_string(), l.is_visible())
+                    })
+                    .collect();
+                (comp.dim(), children)
+            })
+            .unwrap();
+        assert_eq!(dim, (100, 100));
+        assert_eq!(children, [("Tree".to_string(), true)]);
+    }
+
+    #[test]
+    fn test_add_layer() {
+        let mut project = test_project();
+        let comp = CompNode::new("main", 0, 99, 24.0);
+        let src = CompNode::new("src", 0, 29, 24.0);
+        let (comp_uuid, src_uuid) = (comp.uuid(), src.uuid());
+        project.add_node(NodeKind::Comp(comp));
+        project.add_node(NodeKind::Comp(src));
+
+        let layer = project.add_layer(comp_uuid, src_uuid, 10, None).unwrap();
+        project
+            .with_comp(comp_uuid, |comp| {
+                assert_eq!(comp.get_children_sources(), vec![(layer, src_uuid)]);
+                let attrs = comp.layers_attrs_get(&layer).unwrap();
+                assert_eq!(attrs.layer_start(), 10);
+            })
+            .unwrap();
+
+        // Cycles and unknown comps are rejected
+        assert!(project.add_layer(src_uuid, comp_uuid, 0, None).is_err());
+        assert!(project.add_layer(comp_uuid, comp_uuid, 0, None).is_err());
+        assert!(
+            project
+                .add_layer(Uuid::new_v4(), src_uuid, 0, None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_comp_flags_mismatches() {
+        let mut project = test_project();
+        let mut comp = CompNode::new("main", 0, 99, 24.0);
+        // Match the 64x64 placeholder size of unloaded file nodes
+        comp.attrs.set(A_WIDTH, AttrValue::UInt(64));
+        comp.attrs.set(A_HEIGHT, AttrValue::UInt(64));
+        let comp_uuid = comp.uuid();
+        project.add_node(NodeKind::Comp(comp));
+        let matching = project.create_file("a.*.exr".to_string(), 1, 10, 24.0);
+        let clip = project.create_file("b.*.exr".to_string(), 1, 10, 30.0);
+
+        project.add_layer(comp_uuid, matching, 0, None).unwrap();
+        assert!(project.validate_comp(comp_uuid).is_empty());
+
+        let layer = project.add_layer(comp_uuid, clip, 0, None).unwrap();
+        let warnings = project
+            .with_comp(comp_uuid, |c| c.warnings.clone())
+            .unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].layer, layer);
+        assert_eq!(
+            warnings[0].kind,
+            CompWarningKind::FpsMismatch {
+                source: 30.0,
+                comp: 24.0
+            }
+        );
+        assert!(warnings[0].to_string().contains("30 fps"));
+
+        // Resolution mismatch is flagged too; nothing is blocked
+        project.modify_comp(comp_uuid, |c| {
+            c.attrs.set(A_WIDTH, AttrValue::UInt(1280));
+            c.attrs.set(A_HEIGHT, AttrValue::UInt(720));
+        });
+        let warnings = project.validate_comp(comp_uuid);
+        assert_eq!(warnings.len(), 3);
+        assert!(project.validate_comp(Uuid::new_v4()).is_empty());
+    }
 }
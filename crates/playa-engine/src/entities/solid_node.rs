@@ -0,0 +1,427 @@
+//! SolidNode - generates a constant color or two-color gradient Frame.
+//!
+//! Backing plates and slate cards without importing an image. The output is
+//! float RGBA (straight alpha) at the node's own resolution and composites
+//! like any other source layer. It never changes over time, so one frame per
+//! settings hash is generated and reused.
+//!
+//! Ramps double as mattes: a black-to-white linear or radial gradient sized
+//! to the comp (`SolidNode::new_matte`) feeds the track-matte luma channel
+//! for vignettes and graduated grades without external assets.
+
+use log::trace;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::attr_schemas::SOLID_SCHEMA;
+use super::attrs::{AttrValue, Attrs};
+use super::frame::Frame;
+use super::keys::{
+    A_HEIGHT, A_IN, A_OPACITY, A_OUT, A_SPEED, A_SRC_LEN, A_TRIM_IN, A_TRIM_OUT, A_WIDTH,
+};
+use super::node::{ComputeContext, Node};
+use crate::defaults::DEFAULT_DIM;
+
+/// Attributes that affect the generated image (timing excluded).
+const RENDER_KEYS: &[&str] = &[
+    "color", "color2", "gradient", "angle", "center_x", "center_y", "radius", A_OPACITY, A_WIDTH,
+    A_HEIGHT,
+];
+
+/// Ramp placement: linear direction and radial center/extent.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RampShape {
+    /// Linear direction in degrees, counter-clockwise; 0 runs left to right
+    pub angle: f32,
+    /// Radial center, normalized 0..1 (0,0 = top-left)
+    pub center: [f32; 2],
+    /// Radial extent as a fraction of the half diagonal
+    pub radius: f32,
+}
+
+impl Default for RampShape {
+    fn default() -> Self {
+        Self {
+            angle: 0.0,
+            center: [0.5, 0.5],
+            radius: 1.0,
+        }
+    }
+}
+
+/// Gradient shape between `color` and `color2`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Gradient {
+    /// Flat `color`
+    None,
+    /// `color` on the left to `color2` on the right
+    Horizontal,
+    /// `color` at the top to `color2` at the bottom
+    Vertical,
+    /// `color` to `color2` along `angle`, spanning the frame
+    Linear,
+    /// `color` at the center to `color2` at `radius`
+    Radial,
+}
+
+impl Gradient {
+    pub fn from_name(s: &str) -> Self {
+        match s.to_lowercase().as_str() {
+            "horizontal" => Gradient::Horizontal,
+            "vertical" => Gradient::Vertical,
+            "linear" => Gradient::Linear,
+            "radial" => Gradient::Radial,
+            _ => Gradient::None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Gradient::None => "none",
+            Gradient::Horizontal => "horizontal",
+            Gradient::Vertical => "vertical",
+            Gradient::Linear => "linear",
+            Gradient::Radial => "radial",
+        }
+    }
+
+    /// Blend weight of `color2` at pixel (x, y) in a w x h frame
+    fn weight(self, shape: &RampShape, x: usize, y: usize, w: usize, h: usize) -> f32 {
+        // Pixel centers, normalized to 0..1
+        let u = (x as f32 + 0.5) / w as f32;
+        let v = (y as f32 + 0.5) / h as f32;
+        let (wf, hf) = (w as f32, h as f32);
+        match self {
+            Gradient::None => 0.0,
+            Gradient::Horizontal => u,
+            Gradient::Vertical => v,
+            Gradient::Linear => {
+                // Project onto the direction (y up); the frame corners land
+                // exactly on 0 and 1 whatever the angle
+                let (sin, cos) = shape.angle.to_radians().sin_cos();
+                let along = (u - 0.5) * wf * cos + (0.5 - v) * hf * sin;
+                let extent = (wf * cos).abs() + (hf * sin).abs();
+                (along / extent + 0.5).clamp(0.0, 1.0)
+            }
+            Gradient::Radial => {
+                // Aspect-correct distance from center; radius 1.0 reaches
+                // the corners of a centered ramp
+                let dx = (u - shape.center[0]) * wf;
+                let dy = (v - shape.center[1]) * hf;
+                let reach = 0.5 * (wf * wf + hf * hf).sqrt() * shape.radius.max(1e-6);
+                ((dx * dx + dy * dy).sqrt() / reach).min(1.0)
+            }
+        }
+    }
+}
+
+/// Solid node - constant color or gradient source.
+///
+/// Attributes:
+/// - color: RGBA [0-1] (gradient start)
+/// - color2: RGBA [0-1] (gradient end)
+/// - gradient: "none", "horizontal", "vertical", "linear", "radial"
+/// - angle: linear direction in degrees
+/// - center_x/center_y: radial center, normalized 0..1
+/// - radius: radial extent (1.0 = half diagonal)
+/// - width/height: output resolution
+/// - opacity: multiplies alpha
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SolidNode {
+    pub attrs: Attrs,
+}
+
+impl SolidNode {
+    /// Create new solid node (mid grey, project default resolution).
+    pub fn new(name: &str) -> Self {
+        let mut attrs = Attrs::with_schema(&SOLID_SCHEMA);
+
+        // Identity
+        attrs.set("uuid", AttrValue::Uuid(Uuid::new_v4()));
+        attrs.set("name", AttrValue::Str(name.to_string()));
+
+        // Fill
+        attrs.set("color", AttrValue::Vec4([0.18, 0.18, 0.18, 1.0]));
+        attrs.set("color2", AttrValue::Vec4([0.0, 0.0, 0.0, 1.0]));
+        attrs.set("gradient", AttrValue::Str("none".to_string()));
+        attrs.set("angle", AttrValue::Float(0.0));
+        attrs.set("center_x", AttrValue::Float(0.5));
+        attrs.set("center_y", AttrValue::Float(0.5));
+        attrs.set("radius", AttrValue::Float(1.0));
+
+        // Resolution
+        attrs.set(A_WIDTH, AttrValue::Int(DEFAULT_DIM.0 as i32));
+        attrs.set(A_HEIGHT, AttrValue::Int(DEFAULT_DIM.1 as i32));
+
+        // Timing (unified: in, out, trim_in, trim_out, src_len, speed)
+        attrs.set(A_IN, AttrValue::Int(0));
+        attrs.set(A_OUT, AttrValue::Int(100));
+        attrs.set(A_SRC_LEN, AttrValue::Int(100));
+        attrs.set(A_TRIM_IN, AttrValue::Int(0));
+        attrs.set(A_TRIM_OUT, AttrValue::Int(0));
+        attrs.set(A_SPEED, AttrValue::Float(1.0));
+        attrs.set(A_OPACITY, AttrValue::Float(1.0));
+
+        attrs.clear_dirty();
+        Self { attrs }
+    }
+
+    /// Create a black-to-white linear ramp at `dim`, ready for use as a
+    /// luma track matte (pass the comp resolution so the mask lines up).
+    pub fn new_matte(name: &str, dim: (usize, usize)) -> Self {
+        let mut node = Self::new(name);
+        node.set_color([0.0, 0.0, 0.0, 1.0]);
+        node.set_gradient(Gradient::Linear, [1.0, 1.0, 1.0, 1.0]);
+        node.set_size(dim.0 as i32, dim.1 as i32);
+        node.attrs.clear_dirty();
+        node
+    }
+
+    /// Attach schema after deserialization.
+    pub fn attach_schema(&mut self) {
+        self.attrs.attach_schema(&SOLID_SCHEMA);
+    }
+
+    // === Getters ===
+
+    pub fn color(&self) -> [f32; 4] {
+        self.attrs
+            .get_vec4("color")
+            .unwrap_or([0.18, 0.18, 0.18, 1.0])
+    }
+
+    pub fn color2(&self) -> [f32; 4] {
+        self.attrs
+            .get_vec4("color2")
+            .unwrap_or([0.0, 0.0, 0.0, 1.0])
+    }
+
+    pub fn gradient(&self) -> Gradient {
+        Gradient::from_name(self.attrs.get_str("gradient").unwrap_or("none"))
+    }
+
+    pub fn shape(&self) -> RampShape {
+        let d = RampShape::default();
+        RampShape {
+            angle: self.attrs.get_float("angle").unwrap_or(d.angle),
+            center: [
+                self.attrs.get_float("center_x").unwrap_or(d.center[0]),
+                self.attrs.get_float("center_y").unwrap_or(d.center[1]),
+            ],
+            radius: self.attrs.get_float("radius").unwrap_or(d.radius),
+        }
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.attrs.get_float(A_OPACITY).unwrap_or(1.0)
+    }
+
+    // === Setters ===
+
+    pub fn set_color(&mut self, rgba: [f32; 4]) {
+        self.attrs.set("color", AttrValue::Vec4(rgba));
+    }
+
+    pub fn set_gradient(&mut self, gradient: Gradient, color2: [f32; 4]) {
+        self.attrs
+            .set("gradient", AttrValue::Str(gradient.as_str().to_string()));
+        self.attrs.set("color2", AttrValue::Vec4(color2));
+    }
+
+    pub fn set_shape(&mut self, shape: RampShape) {
+        self.attrs.set("angle", AttrValue::Float(shape.angle));
+        self.attrs
+            .set("center_x", AttrValue::Float(shape.center[0]));
+        self.attrs
+            .set("center_y", AttrValue::Float(shape.center[1]));
+        self.attrs.set("radius", AttrValue::Float(shape.radius));
+    }
+
+    pub fn set_size(&mut self, width: i32, height: i32) {
+        self.attrs.set(A_WIDTH, AttrValue::Int(width));
+        self.attrs.set(A_HEIGHT, AttrValue::Int(height));
+    }
+
+    /// Hash of everything that affects the image (cache key).
+    pub fn render_hash(&self) -> u64 {
+        self.attrs.hash_filtered(Some(RENDER_KEYS), None)
+    }
+
+    // === Rendering ===
+
+    /// Fill a float RGBA frame with the color/gradient.
+    fn render(&self) -> Frame {
+        let (w, h) = self.dim();
+        let (c1, c2) = (self.color(), self.color2());
+        let gradient = self.gradient();
+        let shape = self.shape();
+        let opacity = self.opacity().clamp(0.0, 1.0);
+
+        let mut pixels = Vec::with_capacity(w * h * 4);
+        for y in 0..h {
+            for x in 0..w {
+                let t = gradient.weight(&shape, x, y, w, h);
+                for c in 0..4 {
+                    pixels.push(c1[c] + (c2[c] - c1[c]) * t);
+                }
+                let a = pixels.len() - 1;
+                pixels[a] = (pixels[a] * opacity).clamp(0.0, 1.0);
+            }
+        }
+        trace!("SolidNode: generated {}x{} ({})", w, h, gradient.as_str());
+        Frame::from_f32_buffer(pixels, w, h)
+    }
+}
+
+impl Node for SolidNode {
+    fn uuid(&self) -> Uuid {
+        self.attrs.get_uuid("uuid").unwrap_or_else(Uuid::nil)
+    }
+
+    fn name(&self) -> &str {
+        self.attrs.get_str("name").unwrap_or("Solid")
+    }
+
+    fn node_type(&self) -> &'static str {
+        "Solid"
+    }
+
+    fn attrs(&self) -> &Attrs {
+        &self.attrs
+    }
+
+    fn attrs_mut(&mut self) -> &mut Attrs {
+        &mut self.attrs
+    }
+
+    fn inputs(&self) -> Vec<Uuid> {
+        vec![] // Generator: no inputs
+    }
+
+    /// Output resolution from the editable width/height (Int) attrs
+    fn dim(&self) -> (usize, usize) {
+        let w = self.attrs.get_i32(A_WIDTH).unwrap_or(DEFAULT_DIM.0 as i32);
+        let h = self.attrs.get_i32(A_HEIGHT).unwrap_or(DEFAULT_DIM.1 as i32);
+        (w.max(1) as usize, h.max(1) as usize)
+    }
+
+    /// Generated frame; the same for every frame index.
+    fn compute(&self, _frame: i32, ctx: &ComputeContext) -> Option<Frame> {
+        use super::frame::FrameStatus;
+
+        // Constant over time: a single cache slot
+        if let Some(cached) = ctx.cache.get(self.uuid(), 0)
+            && cached.status() == FrameStatus::Loaded
+        {
+            return Some(cached);
+        }
+
+        // Expired entries also come from timing-only edits; regenerate only
+        // when the image-affecting attrs changed
+        let frame_out = ctx.content_cached(self.uuid(), self.render_hash(), || self.render());
+        ctx.cache.insert(self.uuid(), 0, frame_out.clone());
+        Some(frame_out)
+    }
+
+    fn is_dirty(&self, _ctx: Option<&ComputeContext>) -> bool {
+        self.attrs.is_dirty()
+    }
+
+    fn mark_dirty(&self) {
+        self.attrs.mark_dirty();
+    }
+
+    fn clear_dirty(&self) {
+        self.attrs.clear_dirty();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::frame::PixelBuffer;
+
+    fn pixels(frame: &Frame) -> Vec<f32> {
+        match frame.buffer().as_ref() {
+            PixelBuffer::F32(p) => p.clone(),
+            _ => panic!("solid frames are f32"),
+        }
+    }
+
+    #[test]
+    fn test_solid_fill_and_opacity() {
+        let mut node = SolidNode::new("Backing");
+        node.set_size(4, 2);
+        node.set_color([1.0, 0.5, 0.25, 1.0]);
+        node.attrs.set(A_OPACITY, AttrValue::Float(0.5));
+
+        let frame = node.render();
+        assert_eq!(frame.resolution(), (4, 2));
+        for px in pixels(&frame).chunks(4) {
+            assert_eq!(px, [1.0, 0.5, 0.25, 0.5]);
+        }
+    }
+
+    #[test]
+    fn test_gradient_runs_between_colors() {
+        let mut node = SolidNode::new("Slate");
+        node.set_size(4, 4);
+        node.set_color([0.0, 0.0, 0.0, 1.0]);
+        node.set_gradient(Gradient::Horizontal, [1.0, 1.0, 1.0, 1.0]);
+
+        let px = pixels(&node.render());
+        // Left pixel center 1/8 of the way, right one 7/8
+        assert!((px[0] - 0.125).abs() < 1e-6);
+        assert!((px[3 * 4] - 0.875).abs() < 1e-6);
+
+        node.set_gradient(Gradient::Radial, [1.0, 1.0, 1.0, 1.0]);
+        let px = pixels(&node.render());
+        // Corners brighter than the center
+        assert!(px[0] > px[(2 * 4 + 2) * 4]);
+    }
+
+    #[test]
+    fn test_matte_ramp_angle_and_center() {
+        let mut node = SolidNode::new_matte("Matte", (8, 4));
+        assert_eq!(node.dim(), (8, 4));
+        assert!(!node.attrs.is_dirty());
+
+        // 0 degrees: dark on the left, bright on the right
+        let px = pixels(&node.render());
+        assert!(px[0] < px[7 * 4]);
+
+        // 90 degrees: dark at the bottom, bright at the top
+        node.set_shape(RampShape {
+            angle: 90.0,
+            ..RampShape::default()
+        });
+        let px = pixels(&node.render());
+        let bottom = (3 * 8) * 4;
+        assert!(px[0] > px[bottom]);
+        assert!((px[0] - px[7 * 4]).abs() < 1e-6);
+
+        // Radial around an off-center point: black there, white far away
+        node.set_gradient(Gradient::Radial, [1.0, 1.0, 1.0, 1.0]);
+        node.set_shape(RampShape {
+            center: [0.0625, 0.125],
+            radius: 0.25,
+            ..RampShape::default()
+        });
+        let px = pixels(&node.render());
+        assert_eq!(px[0], 0.0);
+        assert_eq!(px[(3 * 8 + 7) * 4], 1.0);
+    }
+
+    #[test]
+    fn test_render_hash_ignores_timing() {
+        let mut node = SolidNode::new("Backing");
+        let base = node.render_hash();
+        node.attrs.set(A_OUT, AttrValue::Int(500));
+        assert_eq!(node.render_hash(), base);
+        node.set_color([1.0, 0.0, 0.0, 1.0]);
+        assert_ne!(node.render_hash(), base);
+        let colored = node.render_hash();
+        node.attrs.set("angle", AttrValue::Float(45.0));
+        assert_ne!(node.render_hash(), colored);
+    }
+}
@@ -7,24 +7,53 @@
 //! - Multi-line layout
 
 use cosmic_text::{
-    Attrs as TextAttrs, Buffer, Color, Family, FontSystem, Metrics, Shaping, SwashCache,
+    Align, Attrs as TextAttrs, Buffer, Color, Family, FontSystem, Metrics, Shaping, Style,
+    SwashCache, Weight, fontdb,
 };
+use log::warn;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
 use std::sync::Mutex;
 use uuid::Uuid;
 
 use super::attr_schemas::TEXT_SCHEMA;
 use super::attrs::{AttrValue, Attrs};
 use super::frame::Frame;
-use super::keys::{A_HEIGHT, A_IN, A_OUT, A_SPEED, A_SRC_LEN, A_TRIM_IN, A_TRIM_OUT, A_WIDTH};
+use super::keyframe;
+use super::keys::{
+    A_HEIGHT, A_IN, A_KEYFRAMES, A_OPACITY, A_OUT, A_POSITION, A_SPEED, A_SRC_LEN, A_TRIM_IN,
+    A_TRIM_OUT, A_WIDTH,
+};
 use super::node::{ComputeContext, Node};
 
 // Global font system (expensive to create, reuse across all TextNodes)
 lazy_static::lazy_static! {
     static ref FONT_SYSTEM: Mutex<FontSystem> = Mutex::new(FontSystem::new());
     static ref SWASH_CACHE: Mutex<SwashCache> = Mutex::new(SwashCache::new());
+    // Font files loaded by path -> family name registered in FONT_SYSTEM (None = failed)
+    static ref LOADED_FONTS: Mutex<HashMap<String, Option<String>>> = Mutex::new(HashMap::new());
 }
 
+/// Attributes that affect the rasterized image (timing/opacity excluded).
+const RENDER_KEYS: &[&str] = &[
+    "text",
+    "font",
+    "font_size",
+    "bold",
+    "italic",
+    "letter_spacing",
+    "color",
+    "alignment",
+    "line_height",
+    "bg_color",
+    A_POSITION,
+    "reveal",
+    A_OPACITY,
+    A_WIDTH,
+    A_HEIGHT,
+];
+
 /// Text alignment options.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TextAlign {
@@ -49,6 +78,43 @@ impl TextAlign {
             TextAlign::Right => "right",
         }
     }
+
+    fn to_cosmic(self) -> Align {
+        match self {
+            TextAlign::Left => Align::Left,
+            TextAlign::Center => Align::Center,
+            TextAlign::Right => Align::Right,
+        }
+    }
+}
+
+/// Byte offset after the first `n` visible characters (newlines don't count).
+fn reveal_split(text: &str, n: usize) -> usize {
+    text.char_indices()
+        .filter(|(_, c)| *c != '\n')
+        .nth(n)
+        .map(|(i, _)| i)
+        .unwrap_or(text.len())
+}
+
+/// Register a .ttf/.otf file with the font system once; returns its family name.
+fn load_font_file(font_system: &mut FontSystem, path: &str) -> Option<String> {
+    let mut loaded = LOADED_FONTS.lock().unwrap();
+    if let Some(family) = loaded.get(path) {
+        return family.clone();
+    }
+    let db = font_system.db_mut();
+    let ids = db.load_font_source(fontdb::Source::File(Path::new(path).to_path_buf()));
+    let family = ids
+        .first()
+        .and_then(|id| db.face(*id))
+        .and_then(|face| face.families.first())
+        .map(|(name, _)| name.clone());
+    if family.is_none() {
+        warn!("TextNode: failed to load font file {}", path);
+    }
+    loaded.insert(path.to_string(), family.clone());
+    family
 }
 
 /// Text node - generates rasterized text image.
@@ -57,11 +123,23 @@ impl TextAlign {
 /// - text: the text content (supports \n for newlines)
 /// - font: font family name or path to .ttf/.otf
 /// - font_size: size in pixels
+/// - bold / italic: face style
+/// - letter_spacing: extra spacing in em units
 /// - color: RGBA [0-1]
 /// - alignment: "left", "center", "right"
 /// - line_height: multiplier (1.0 = normal)
 /// - bg_color: background RGBA [0-1]
-/// - width/height: output dimensions (0 = auto-size)
+/// - width/height: output canvas (0 = auto-size to the text bounds)
+/// - position: text offset inside the canvas in pixels (x, y)
+/// - reveal: number of visible characters, -1 = all (typewriter)
+/// - opacity: multiplies text and background alpha
+///
+/// Keyable attrs (position, reveal, opacity, ...) can carry keyframes
+/// (see [`keyframe`](super::keyframe)); `compute()` evaluates them at the
+/// requested frame.
+///
+/// Alignment is applied per line within the canvas width, so multi-line
+/// titles center/right-align line by line.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct TextNode {
     pub attrs: Attrs,
@@ -80,6 +158,9 @@ impl TextNode {
         attrs.set("text", AttrValue::Str(text.to_string()));
         attrs.set("font", AttrValue::Str("sans-serif".to_string()));
         attrs.set("font_size", AttrValue::Float(72.0));
+        attrs.set("bold", AttrValue::Bool(false));
+        attrs.set("italic", AttrValue::Bool(false));
+        attrs.set("letter_spacing", AttrValue::Float(0.0));
         attrs.set("color", AttrValue::Vec4([1.0, 1.0, 1.0, 1.0])); // white
         attrs.set("alignment", AttrValue::Str("left".to_string()));
         attrs.set("line_height", AttrValue::Float(1.2));
@@ -87,6 +168,10 @@ impl TextNode {
         // Background (transparent by default)
         attrs.set("bg_color", AttrValue::Vec4([0.0, 0.0, 0.0, 0.0]));
 
+        // Animation
+        attrs.set(A_POSITION, AttrValue::Vec3([0.0, 0.0, 0.0]));
+        attrs.set("reveal", AttrValue::Int(-1));
+
         // Dimensions (0 = auto)
         attrs.set(A_WIDTH, AttrValue::Int(0));
         attrs.set(A_HEIGHT, AttrValue::Int(0));
@@ -98,7 +183,7 @@ impl TextNode {
         attrs.set(A_TRIM_IN, AttrValue::Int(0));
         attrs.set(A_TRIM_OUT, AttrValue::Int(0));
         attrs.set(A_SPEED, AttrValue::Float(1.0));
-        attrs.set("opacity", AttrValue::Float(1.0));
+        attrs.set(A_OPACITY, AttrValue::Float(1.0));
 
         attrs.clear_dirty();
         Self { attrs }
@@ -134,6 +219,18 @@ impl TextNode {
         self.attrs.get_float("font_size").unwrap_or(72.0)
     }
 
+    pub fn bold(&self) -> bool {
+        self.attrs.get_bool_or("bold", false)
+    }
+
+    pub fn italic(&self) -> bool {
+        self.attrs.get_bool_or("italic", false)
+    }
+
+    pub fn letter_spacing(&self) -> f32 {
+        self.attrs.get_float_or("letter_spacing", 0.0)
+    }
+
     pub fn color(&self) -> [f32; 4] {
         self.attrs.get_vec4("color").unwrap_or([1.0, 1.0, 1.0, 1.0])
     }
@@ -161,6 +258,20 @@ impl TextNode {
         self.attrs.get_i32(A_HEIGHT).unwrap_or(0)
     }
 
+    pub fn position(&self) -> [f32; 3] {
+        self.attrs.get_vec3(A_POSITION).unwrap_or([0.0, 0.0, 0.0])
+    }
+
+    /// Visible character count (newlines excluded); `None` = all
+    pub fn reveal(&self) -> Option<usize> {
+        let n = self.attrs.get_i32("reveal").unwrap_or(-1);
+        (n >= 0).then_some(n as usize)
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.attrs.get_float(A_OPACITY).unwrap_or(1.0)
+    }
+
     // === Setters ===
 
     pub fn set_text(&mut self, text: &str) {
@@ -175,16 +286,64 @@ impl TextNode {
         self.attrs.set("color", AttrValue::Vec4(rgba));
     }
 
+    pub fn set_font(&mut self, font: &str) {
+        self.attrs.set("font", AttrValue::Str(font.to_string()));
+    }
+
+    pub fn set_alignment(&mut self, align: TextAlign) {
+        self.attrs
+            .set("alignment", AttrValue::Str(align.as_str().to_string()));
+    }
+
+    pub fn set_size(&mut self, width: i32, height: i32) {
+        self.attrs.set(A_WIDTH, AttrValue::Int(width));
+        self.attrs.set(A_HEIGHT, AttrValue::Int(height));
+    }
+
+    // === Animation ===
+
+    /// Key `key` to `value` at `frame`
+    pub fn set_key(&mut self, key: &str, frame: i32, value: AttrValue) {
+        keyframe::set_key(&mut self.attrs, key, frame, value);
+    }
+
+    /// True if any attribute carries keyframes
+    pub fn is_animated(&self) -> bool {
+        keyframe::is_animated(&self.attrs)
+    }
+
+    /// Copy with every keyed attr replaced by its value at `frame`.
+    pub fn at_frame(&self, frame: f32) -> TextNode {
+        let mut node = self.clone();
+        if let Some(tracks) = self.attrs.get_map(A_KEYFRAMES) {
+            for key in tracks.keys() {
+                if let Some(value) = keyframe::eval(&self.attrs, key, frame) {
+                    node.attrs.set(key.as_str(), value);
+                }
+            }
+        }
+        node
+    }
+
+    /// Hash of everything that affects the raster (cache key).
+    pub fn render_hash(&self) -> u64 {
+        self.attrs.hash_filtered(Some(RENDER_KEYS), None)
+    }
+
     // === Rendering ===
 
-    /// Render text to RGBA buffer.
-    fn render_text(&self) -> Frame {
+    /// Render text to an 8-bit straight-alpha frame (auto-sized when width/height are 0).
+    pub fn render_text(&self) -> Frame {
         let text = self.text();
         let font_size = self.font_size();
         let line_height_mult = self.line_height();
-        let color = self.color();
-        let bg = self.bg_color();
+        let opacity = self.opacity().clamp(0.0, 1.0);
+        let mut color = self.color();
+        let mut bg = self.bg_color();
+        color[3] *= opacity;
+        bg[3] *= opacity;
         let alignment = self.alignment();
+        let [offset_x, offset_y, _] = self.position();
         let font_family = self.font();
 
         // Lock font system
@@ -198,71 +357,85 @@ impl TextNode {
         // Create text buffer
         let mut buffer = Buffer::new(&mut font_system, metrics);
 
-        // Determine buffer width for layout
-        let layout_width = if self.width() > 0 {
-            self.width() as f32
+        // Resolve family: font files are registered once and referenced by name,
+        // anything else is a generic or installed family name
+        let is_path = font_family.contains('/') || font_family.contains('\\');
+        let loaded_family = if is_path {
+            load_font_file(&mut font_system, &font_family)
         } else {
-            // Auto-width: use large value, will trim later
-            4096.0
+            None
         };
-
-        buffer.set_size(&mut font_system, Some(layout_width), None);
-
-        // Set text with attributes
-        let family = if font_family.contains('/') || font_family.contains('\\') {
-            // Path to font file - cosmic-text will try to load it
-            Family::Name(&font_family)
-        } else {
-            // Named family
-            match font_family.to_lowercase().as_str() {
+        let generic = font_family.to_lowercase();
+        let family = match loaded_family.as_deref() {
+            Some(name) => Family::Name(name),
+            None if is_path => Family::SansSerif,
+            None => match generic.as_str() {
                 "serif" => Family::Serif,
                 "monospace" | "mono" => Family::Monospace,
                 "cursive" => Family::Cursive,
                 "fantasy" => Family::Fantasy,
-                _ => Family::SansSerif,
-            }
+                "" | "sans" | "sans-serif" => Family::SansSerif,
+                _ => Family::Name(&font_family),
+            },
         };
 
-        let text_attrs = TextAttrs::new().family(family);
-        buffer.set_text(
+        let text_attrs = TextAttrs::new()
+            .family(family)
+            .weight(if self.bold() {
+                Weight::BOLD
+            } else {
+                Weight::NORMAL
+            })
+            .style(if self.italic() {
+                Style::Italic
+            } else {
+                Style::Normal
+            })
+            .letter_spacing(self.letter_spacing());
+
+        // Pass 1: unconstrained layout to measure the widest line
+        buffer.set_size(&mut font_system, None, None);
+        // Typewriter: hidden characters stay in the layout (so lines don't
+        // reflow while revealing) but draw fully transparent
+        let split = self
+            .reveal()
+            .map(|n| reveal_split(&text, n))
+            .unwrap_or(text.len());
+        let hidden_attrs = text_attrs.clone().color(Color::rgba(0, 0, 0, 0));
+        buffer.set_rich_text(
             &mut font_system,
-            &text,
+            [
+                (&text[..split], text_attrs.clone()),
+                (&text[split..], hidden_attrs),
+            ],
             &text_attrs,
             Shaping::Advanced,
-            None,
+            Some(alignment.to_cosmic()),
         );
-
-        // Shape and layout
         buffer.shape_until_scroll(&mut font_system, false);
+        let text_width = buffer
+            .layout_runs()
+            .map(|run| run.line_w)
+            .fold(0.0f32, f32::max)
+            .ceil() as usize;
 
-        // Calculate actual bounds
-        let (text_width, text_height) = {
-            let mut max_x = 0.0f32;
-            let mut max_y = 0.0f32;
-
-            for run in buffer.layout_runs() {
-                for glyph in run.glyphs.iter() {
-                    let x = glyph.x + glyph.w;
-                    if x > max_x {
-                        max_x = x;
-                    }
-                }
-                let y = run.line_y + line_height;
-                if y > max_y {
-                    max_y = y;
-                }
-            }
-
-            (max_x.ceil() as usize, max_y.ceil() as usize)
-        };
-
-        // Final dimensions
+        // Final canvas width; alignment is resolved per line against it
         let width = if self.width() > 0 {
             self.width() as usize
         } else {
             text_width.max(1)
         };
 
+        // Pass 2: lay out at canvas width (wraps if text is wider than the canvas)
+        buffer.set_size(&mut font_system, Some(width as f32), None);
+        buffer.shape_until_scroll(&mut font_system, false);
+
+        let text_height = buffer
+            .layout_runs()
+            .map(|run| run.line_top + run.line_height)
+            .fold(0.0f32, f32::max)
+            .ceil() as usize;
+
         let height = if self.height() > 0 {
             self.height() as usize
         } else {
@@ -299,33 +472,22 @@ impl TextNode {
             &mut swash_cache,
             text_color,
             |x, y, w, h, color| {
-                // Calculate alignment offset
-                let align_offset = match alignment {
-                    TextAlign::Left => 0.0,
-                    TextAlign::Center => (width as f32 - text_width as f32) / 2.0,
-                    TextAlign::Right => width as f32 - text_width as f32,
-                };
-
-                let px = (x as f32 + align_offset) as i32;
-                let py = y;
-
-                // Bounds check
-                if px < 0 || py < 0 || px >= width as i32 || py >= height as i32 {
-                    return;
-                }
-
-                let px = px as usize;
-                let py = py as usize;
+                // Position offset (sub-pixel keys round to the pixel grid)
+                let px = x as i64 + offset_x.round() as i64;
+                let py = y as i64 + offset_y.round() as i64;
 
                 // Draw the glyph coverage rectangle
-                for dy in 0..h as usize {
-                    for dx in 0..w as usize {
-                        let dest_x = px + dx;
-                        let dest_y = py + dy;
-
-                        if dest_x >= width || dest_y >= height {
+                for dy in 0..h as i64 {
+                    for dx in 0..w as i64 {
+                        let (dest_x, dest_y) = (px + dx, py + dy);
+                        if dest_x < 0
+                            || dest_y < 0
+                            || dest_x >= width as i64
+                            || dest_y >= height as i64
+                        {
                             continue;
                         }
+                        let (dest_x, dest_y) = (dest_x as usize, dest_y as usize);
 
                         let idx = (dest_y * width + dest_x) * 4;
 
@@ -382,24 +544,32 @@ impl Node for TextNode {
     }
 
     /// Render text to Frame.
-    fn compute(&self, _frame: i32, ctx: &ComputeContext) -> Option<Frame> {
+    fn compute(&self, frame: i32, ctx: &ComputeContext) -> Option<Frame> {
         use super::frame::FrameStatus;
 
+        // Static text caches a single frame; animated text caches every frame
+        let cache_frame = if self.is_animated() { frame } else { 0 };
+
         // Check cache - but must re-render if Expired (stale after attr change)
-        if let Some(cached) = ctx.cache.get(self.uuid(), 0) {
+        if let Some(cached) = ctx.cache.get(self.uuid(), cache_frame) {
             if cached.status() == FrameStatus::Loaded {
                 return Some(cached);
             }
             // Expired or other status - need to re-render
         }
 
-        // Render text
-        let frame = self.render_text();
+        // Expired entries are also produced by non-render edits (timing);
+        // the raster is cached under the evaluated text attrs hash, so
+        // unchanged text never re-rasterizes
+        let evaluated = self.at_frame(frame as f32);
+        let frame_out = ctx.content_cached(self.uuid(), evaluated.render_hash(), || {
+            evaluated.render_text()
+        });
 
-        // Cache result (frame 0 since text is static)
-        ctx.cache.insert(self.uuid(), 0, frame.clone());
+        ctx.cache
+            .insert(self.uuid(), cache_frame, frame_out.clone());
 
-        Some(frame)
+        Some(frame_out)
     }
 
     fn is_dirty(&self, _ctx: Option<&ComputeContext>) -> bool {
@@ -436,4 +606,94 @@ mod tests {
         assert_eq!(TextAlign::from_str("right"), TextAlign::Right);
         assert_eq!(TextAlign::from_str("CENTER"), TextAlign::Center);
     }
+
+    #[test]
+    fn test_render_hash_tracks_render_attrs_only() {
+        let mut node = TextNode::new("Title", "Hello");
+        let base = node.render_hash();
+
+        node.attrs.set(A_IN, AttrValue::Int(10));
+        node.attrs.set(A_SPEED, AttrValue::Float(2.0));
+        assert_eq!(node.render_hash(), base);
+
+        node.set_alignment(TextAlign::Center);
+        assert_ne!(node.render_hash(), base);
+    }
+
+    #[test]
+    fn test_typewriter_keys_evaluate_per_frame() {
+        let mut node = TextNode::new("Title", "Hi\nthere");
+        node.set_key("reveal", 0, AttrValue::Int(0));
+        node.set_key("reveal", 7, AttrValue::Int(7));
+        node.set_key(A_POSITION, 0, AttrValue::Vec3([0.0, 0.0, 0.0]));
+        node.set_key(A_POSITION, 4, AttrValue::Vec3([10.0, 0.0, 0.0]));
+        assert!(node.is_animated());
+
+        let f1 = node.at_frame(1.0);
+        assert_eq!(f1.reveal(), Some(1));
+        assert_eq!(f1.position(), [2.5, 0.0, 0.0]);
+        assert_eq!(node.at_frame(2.5).position(), [6.25, 0.0, 0.0]);
+        assert_ne!(f1.render_hash(), node.at_frame(2.0).render_hash());
+
+        // Newlines are skipped when counting revealed characters
+        assert_eq!(reveal_split("Hi\nthere", 3), 4);
+        assert_eq!(reveal_split("Hi", 10), 2);
+    }
+
+    #[test]
+    fn test_render_canvas_size_and_multiline() {
+        let mut node = TextNode::new("Lower third", "Line one\nLonger second line");
+        node.set_font_size(24.0);
+
+        let auto = node.render_text();
+        let (w, h) = auto.resolution();
+        assert!(w > 1 && h > 1);
+
+        node.set_size(640, 120);
+        node.set_alignment(TextAlign::Right);
+        assert_eq!(node.render_text().resolution(), (640, 120));
+    }
+
+    #[test]
+    fn test_raster_cached_by_render_hash() {
+        use crate::core::cache_man::CacheManager;
+        use crate::core::global_cache::GlobalFrameCache;
+        use crate::entities::{CacheStrategy, ProxyLevel};
+        use std::sync::Arc;
+
+        let cache = GlobalFrameCache::new(
+            100,
+            Arc::new(CacheManager::new(0.75, 2.0)),
+            CacheStrategy::All,
+        );
+        let media = HashMap::new();
+        let ctx = ComputeContext {
+            cache: &cache,
+            cache_arc: None,
+            media: &media,
+            media_arc: None,
+            workers: None,
+            epoch: 0,
+            gpu_blend_bridge: None,
+            proxy: ProxyLevel::Full,
+        };
+        let mut node = TextNode::new("Title", "Hello");
+        let renders = std::cell::Cell::new(0);
+        let render = |node: &TextNode| {
+            ctx.content_cached(node.uuid(), node.render_hash(), || {
+                renders.set(renders.get() + 1);
+                node.render_text()
+            })
+        };
+
+        render(&node);
+        // Timing edit: same raster
+        node.attrs.set(A_IN, AttrValue::Int(10));
+        render(&node);
+        assert_eq!(renders.get(), 1);
+
+        node.set_alignment(TextAlign::Center);
+        render(&node);
+        assert_eq!(renders.get(), 2);
+    }
 }
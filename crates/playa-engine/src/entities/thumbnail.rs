@@ -0,0 +1,473 @@
+//! Small representative frames for the Project panel.
+//!
+//! Each clip/comp gets one thumbnail: its work-area midpoint, computed at
+//! quarter proxy on a background thread and resized to fit a square box.
+//! Entries are keyed by a content hash of the node (attrs, layers, effects and
+//! nested sources), so editing a comp re-renders its thumbnail while playhead
+//! moves don't. The UI polls [`ThumbnailCache::get_or_request`] every frame and
+//! draws nothing until the first result is ready; stale thumbnails stay visible
+//! while their replacement renders. A node's key is rehashed at most every
+//! [`KEY_TTL`], so repaints don't walk every comp tree.
+//!
+//! The timeline ruler's scrub preview uses the same cache for arbitrary frames
+//! ([`ThumbnailCache::get_or_request_frame`]): a cached full-res frame is
+//! shrunk when there is one, otherwise the frame is computed at quarter proxy
+//! in the background tier of the [`Workers`] pool. Only one scrub render is
+//! queued at a time, so sweeping the cursor across the ruler renders the frame
+//! under it instead of queuing every frame it passed.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use log::debug;
+use uuid::Uuid;
+
+use super::frame::{Frame, FrameStatus, PixelFormat, ResizeFilter, TonemapMode};
+use super::keys::A_FRAME;
+use super::node::{ComputeContext, Node};
+use super::node_kind::NodeKind;
+use super::project::Project;
+use super::traits::{FrameCache, ProxyLevel};
+use crate::core::workers::Workers;
+
+/// Default thumbnail box edge in pixels
+pub const DEFAULT_THUMBNAIL_SIZE: u32 = 64;
+
+/// Nested comps deeper than this don't contribute to the content hash
+const MAX_HASH_DEPTH: usize = 16;
+
+/// Scrub preview frames kept before the oldest are dropped
+const MAX_SCRUB_FRAMES: usize = 256;
+
+/// How long a node's thumbnail key is reused before it is rehashed (edits show
+/// up within this delay)
+pub const KEY_TTL: Duration = Duration::from_millis(500);
+
+lazy_static::lazy_static! {
+    static ref THUMBNAILS: ThumbnailCache = ThumbnailCache::default();
+}
+
+/// Rendered thumbnail: 8-bit sRGB, straight alpha, at most `size` on either edge.
+#[derive(Clone, Debug)]
+pub struct Thumbnail {
+    /// Content hash + size it was rendered for; changes whenever the image does
+    pub key: u64,
+    pub frame: Frame,
+}
+
+struct Entry {
+    /// Key of the latest request (may be newer than `thumb.key` while pending)
+    key: u64,
+    thumb: Option<Thumbnail>,
+    pending: bool,
+}
+
+/// Scrub preview thumbnails by (node, frame), oldest first in `order`.
+#[derive(Default)]
+struct ScrubFrames {
+    thumbs: HashMap<(Uuid, i32), Thumbnail>,
+    order: VecDeque<(Uuid, i32)>,
+    pending: bool,
+    /// Key of the last requested (node, frame, size), so hovering one frame
+    /// hashes the node once rather than on every repaint
+    hovered: Option<((Uuid, i32, u32), u64)>,
+}
+
+/// Clears [`ScrubFrames::pending`] when the scrub render job finishes, or is
+/// dropped unrun because the workers' epoch moved on.
+struct ScrubPending(Arc<Mutex<ScrubFrames>>);
+
+impl Drop for ScrubPending {
+    fn drop(&mut self) {
+        self.0.lock().expect("thumbnail cache lock").pending = false;
+    }
+}
+
+impl ScrubFrames {
+    fn insert(&mut self, at: (Uuid, i32), thumb: Thumbnail) {
+        if self.thumbs.insert(at, thumb).is_none() {
+            self.order.push_back(at);
+        }
+        while self.order.len() > MAX_SCRUB_FRAMES {
+            if let Some(old) = self.order.pop_front() {
+                self.thumbs.remove(&old);
+            }
+        }
+    }
+}
+
+/// Per-node cache of Project panel thumbnails, plus per-frame scrub previews.
+#[derive(Default)]
+pub struct ThumbnailCache {
+    entries: Arc<Mutex<HashMap<Uuid, Entry>>>,
+    frames: Arc<Mutex<ScrubFrames>>,
+    /// Memoized keys: node -> (hashed at, size, key)
+    keys: Mutex<HashMap<Uuid, (Instant, u32, u64)>>,
+}
+
+impl ThumbnailCache {
+    /// Process-wide cache shared by all Project panels
+    pub fn global() -> &'static ThumbnailCache {
+        &THUMBNAILS
+    }
+
+    /// Thumbnail for `uuid`, or `None` until the first render finishes.
+    ///
+    /// When the node's content hash changed since the last render, a new render
+    /// is queued and the previous thumbnail is returned meanwhile. Renders run
+    /// one at a time, so opening a large project doesn't flood the machine;
+    /// `on_ready` runs on the render thread once a result is stored.
+    pub fn get_or_request(
+        &self,
+        project: &Project,
+        uuid: Uuid,
+        size: u32,
+        on_ready: impl FnOnce() + Send + 'static,
+    ) -> Option<Thumbnail> {
+        let key = self.key_at(project, uuid, size, Instant::now());
+        let mut entries = self.entries.lock().expect("thumbnail cache lock");
+        let current = entries.get(&uuid).and_then(|e| e.thumb.clone());
+        if entries.get(&uuid).is_some_and(|e| e.key == key) || entries.values().any(|e| e.pending) {
+            return current;
+        }
+        let Some(cache) = project.global_cache.clone() else {
+            return current;
+        };
+        entries.insert(
+            uuid,
+            Entry {
+                key,
+                thumb: current.clone(),
+                pending: true,
+            },
+        );
+        drop(entries);
+
+        let shared = Arc::clone(&self.entries);
+        let media = Arc::clone(&project.media);
+        let spawned = thread::Builder::new()
+            .name("playa-thumbnail".to_string())
+            .spawn(move || {
+                // Snapshot and release the lock before computing (see ComputeContext)
+                let snapshot = media.read().expect("media lock").clone();
+                let frame = render_thumbnail(&snapshot, cache.as_ref(), uuid, size);
+                if frame.is_none() {
+                    debug!("No thumbnail for {}", uuid);
+                }
+                let mut entries = shared.lock().expect("thumbnail cache lock");
+                if let Some(entry) = entries.get_mut(&uuid) {
+                    entry.pending = false;
+                    if let Some(frame) = frame {
+                        entry.thumb = Some(Thumbnail { key, frame });
+                    }
+                }
+                drop(entries);
+                on_ready();
+            });
+        if spawned.is_err()
+            && let Some(entry) = self
+                .entries
+                .lock()
+                .expect("thumbnail cache lock")
+                .get_mut(&uuid)
+        {
+            entry.pending = false;
+        }
+        current
+    }
+
+    /// Thumbnail of `uuid` at `frame`, or `None` until it is rendered.
+    ///
+    /// Like [`Self::get_or_request`], but per frame and for the ruler scrub
+    /// preview. The render is queued in the low tier of `workers`, behind
+    /// playback and preloads. While another scrub render is in flight nothing
+    /// new is queued (the caller asks again on the `on_ready` repaint); an
+    /// outdated preview is returned while its replacement renders. The
+    /// content hash is memoized for the last requested frame, so an edit made
+    /// while hovering shows once the cursor moves to another frame.
+    pub fn get_or_request_frame(
+        &self,
+        project: &Project,
+        workers: &Workers,
+        uuid: Uuid,
+        frame: i32,
+        size: u32,
+        on_ready: impl FnOnce() + Send + 'static,
+    ) -> Option<Thumbnail> {
+        let mut frames = self.frames.lock().expect("thumbnail cache lock");
+        let key = match frames.hovered {
+            Some((at, key)) if at == (uuid, frame, size) => key,
+            _ => {
+                let media = project.media.read().expect("media lock");
+                let mut hasher = DefaultHasher::new();
+                content_hash(&media, uuid, 0).hash(&mut hasher);
+                size.hash(&mut hasher);
+                let key = hasher.finish();
+                frames.hovered = Some(((uuid, frame, size), key));
+                key
+            }
+        };
+
+        let current = frames.thumbs.get(&(uuid, frame)).cloned();
+        if current.as_ref().is_some_and(|t| t.key == key) || frames.pending {
+            return current;
+        }
+        let Some(cache) = project.global_cache.clone() else {
+            return current;
+        };
+        frames.pending = true;
+        drop(frames);
+
+        let pending = ScrubPending(Arc::clone(&self.frames));
+        let media = Arc::clone(&project.media);
+        workers.execute_with_epoch_background(workers.current_epoch(), move || {
+            let snapshot = media.read().expect("media lock").clone();
+            let thumb = render_thumbnail_at(&snapshot, cache.as_ref(), uuid, frame, size);
+            if let Some(thumb) = thumb {
+                pending
+                    .0
+                    .lock()
+                    .expect("thumbnail cache lock")
+                    .insert((uuid, frame), Thumbnail { key, frame: thumb });
+            }
+            drop(pending);
+            on_ready();
+        });
+        current
+    }
+
+    /// Thumbnail key (content hash + size) of `uuid`, reused for [`KEY_TTL`]
+    /// after it was computed.
+    pub fn key_at(&self, project: &Project, uuid: Uuid, size: u32, now: Instant) -> u64 {
+        let mut keys = self.keys.lock().expect("thumbnail cache lock");
+        if let Some(&(at, s, key)) = keys.get(&uuid)
+            && s == size
+            && now.saturating_duration_since(at) < KEY_TTL
+        {
+            return key;
+        }
+        let media = project.media.read().expect("media lock");
+        let mut hasher = DefaultHasher::new();
+        content_hash(&media, uuid, 0).hash(&mut hasher);
+        size.hash(&mut hasher);
+        let key = hasher.finish();
+        keys.insert(uuid, (now, size, key));
+        key
+    }
+
+    /// Keep only thumbnails of nodes matching `keep` (drops removed nodes)
+    pub fn retain(&self, keep: impl Fn(&Uuid) -> bool) {
+        self.entries
+            .lock()
+            .expect("thumbnail cache lock")
+            .retain(|uuid, _| keep(uuid));
+        self.keys
+            .lock()
+            .expect("thumbnail cache lock")
+            .retain(|uuid, _| keep(uuid));
+    }
+}
+
+/// Hash of everything that affects a node's image, ignoring the playhead.
+///
+/// Comps fold in their layers, effects and (recursively) layer sources, so a
+/// change deep inside a nested comp still refreshes the outer thumbnail.
+pub fn content_hash(media: &HashMap<Uuid, Arc<NodeKind>>, uuid: Uuid, depth: usize) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    uuid.hash(&mut hasher);
+    let Some(node) = media.get(&uuid) else {
+        return hasher.finish();
+    };
+    node.attrs()
+        .hash_filtered(None, Some(&[A_FRAME]))
+        .hash(&mut hasher);
+    if let Some(comp) = node.as_comp() {
+        for layer in &comp.layers {
+            layer.attrs.hash_all().hash(&mut hasher);
+            for fx in &layer.effects {
+                fx.enabled.hash(&mut hasher);
+                fx.attrs.hash_all().hash(&mut hasher);
+            }
+            if depth < MAX_HASH_DEPTH {
+                content_hash(media, layer.source_uuid(), depth + 1).hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+/// Thumbnail dimensions fitting a `w x h` frame (pixel aspect `par`) in `size`
+fn fit_dims(w: usize, h: usize, par: f32, size: u32) -> (usize, usize) {
+    let display_w = w as f32 * par;
+    let scale = size as f32 / display_w.max(h as f32);
+    (
+        (display_w * scale).round().max(1.0) as usize,
+        (h as f32 * scale).round().max(1.0) as usize,
+    )
+}
+
+/// Compute the work-area midpoint of `uuid` at quarter proxy and shrink it to
+/// an 8-bit sRGB straight-alpha frame fitting `size`.
+pub fn render_thumbnail(
+    media: &HashMap<Uuid, Arc<NodeKind>>,
+    cache: &dyn FrameCache,
+    uuid: Uuid,
+    size: u32,
+) -> Option<Frame> {
+    let (start, end) = media.get(&uuid)?.work_area();
+    let frame = compute_quarter(media, cache, uuid, start + (end - start) / 2)?;
+    fit_thumbnail(&frame, size)
+}
+
+/// Thumbnail of `uuid` at `frame_idx`: the cached full-res frame when it is
+/// loaded, otherwise computed at quarter proxy.
+pub fn render_thumbnail_at(
+    media: &HashMap<Uuid, Arc<NodeKind>>,
+    cache: &dyn FrameCache,
+    uuid: Uuid,
+    frame_idx: i32,
+    size: u32,
+) -> Option<Frame> {
+    let frame = cache
+        .get(uuid, frame_idx)
+        .filter(|f| f.status() == FrameStatus::Loaded)
+        .or_else(|| compute_quarter(media, cache, uuid, frame_idx))?;
+    fit_thumbnail(&frame, size)
+}
+
+fn compute_quarter(
+    media: &HashMap<Uuid, Arc<NodeKind>>,
+    cache: &dyn FrameCache,
+    uuid: Uuid,
+    frame_idx: i32,
+) -> Option<Frame> {
+    let node = media.get(&uuid)?;
+    let ctx = ComputeContext {
+        cache,
+        cache_arc: None,
+        media,
+        media_arc: None,
+        workers: None,
+        epoch: 0,
+        gpu_blend_bridge: None,
+        proxy: ProxyLevel::Quarter,
+    };
+    node.compute(frame_idx, &ctx)
+}
+
+/// Shrink a loaded frame to an 8-bit sRGB straight-alpha frame fitting `size`.
+fn fit_thumbnail(frame: &Frame, size: u32) -> Option<Frame> {
+    let (w, h) = frame.resolution();
+    if frame.status() != FrameStatus::Loaded || w == 0 || h == 0 || size == 0 {
+        return None;
+    }
+
+    let (tw, th) = fit_dims(w, h, frame.pixel_aspect(), size);
+    let small = frame
+        .unpremultiply()
+        .resize(tw, th, ResizeFilter::Bilinear)
+        .to_srgb();
+    if small.pixel_format() == PixelFormat::Rgba8 {
+        Some(small)
+    } else {
+        small.tonemap(TonemapMode::Clamp).ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entities::CompNode;
+    use crate::entities::attrs::AttrValue;
+    use crate::entities::keys::A_NAME;
+
+    #[test]
+    fn test_fit_dims_keeps_aspect() {
+        assert_eq!(fit_dims(1920, 1080, 1.0, 64), (64, 36));
+        assert_eq!(fit_dims(1080, 1920, 1.0, 64), (36, 64));
+        // 2x anamorphic displays twice as wide
+        assert_eq!(fit_dims(960, 1080, 2.0, 64), (64, 36));
+        assert_eq!(fit_dims(4000, 1, 1.0, 64), (64, 1));
+    }
+
+    #[test]
+    fn test_scrub_frames_drop_oldest() {
+        let uuid = Uuid::new_v4();
+        let thumb = |key| Thumbnail {
+            key,
+            frame: Frame::placeholder(4, 4),
+        };
+        let mut frames = ScrubFrames::default();
+        for f in 0..MAX_SCRUB_FRAMES as i32 {
+            frames.insert((uuid, f), thumb(0));
+        }
+        // Re-rendering a kept frame doesn't count as a new entry
+        frames.insert((uuid, 0), thumb(1));
+        assert_eq!(frames.thumbs.len(), MAX_SCRUB_FRAMES);
+
+        frames.insert((uuid, -1), thumb(0));
+        assert_eq!(frames.thumbs.len(), MAX_SCRUB_FRAMES);
+        assert!(!frames.thumbs.contains_key(&(uuid, 0)));
+        assert!(frames.thumbs.contains_key(&(uuid, -1)));
+    }
+
+    #[test]
+    fn test_stale_scrub_job_clears_pending() {
+        let frames = Arc::new(Mutex::new(ScrubFrames::default()));
+        frames.lock().unwrap().pending = true;
+        let pending = ScrubPending(Arc::clone(&frames));
+        // A job skipped for a stale epoch is dropped without running
+        let job: Box<dyn FnOnce() + Send> = Box::new(move || drop(pending));
+        drop(job);
+        assert!(!frames.lock().unwrap().pending);
+    }
+
+    #[test]
+    fn test_content_hash_ignores_playhead() {
+        let comp = CompNode::new("Shot", 0, 100, 24.0);
+        let uuid = comp.uuid();
+        let mut media = HashMap::new();
+        media.insert(uuid, Arc::new(NodeKind::Comp(comp.clone())));
+        let base = content_hash(&media, uuid, 0);
+
+        let mut moved = comp.clone();
+        moved.attrs.set(A_FRAME, AttrValue::Int(42));
+        media.insert(uuid, Arc::new(NodeKind::Comp(moved)));
+        assert_eq!(content_hash(&media, uuid, 0), base);
+
+        let mut edited = comp;
+        edited
+            .attrs
+            .set(A_NAME, AttrValue::Str("Renamed".to_string()));
+        media.insert(uuid, Arc::new(NodeKind::Comp(edited)));
+        assert_ne!(content_hash(&media, uuid, 0), base);
+    }
+
+    #[test]
+    fn test_key_rehashed_after_ttl() {
+        use crate::core::cache_man::CacheManager;
+
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let comp = CompNode::new("Shot", 0, 100, 24.0);
+        let uuid = comp.uuid();
+        project.add_node(NodeKind::Comp(comp));
+
+        let cache = ThumbnailCache::default();
+        let t0 = Instant::now();
+        let base = cache.key_at(&project, uuid, 64, t0);
+        project.modify_comp(uuid, |c| {
+            c.attrs.set(A_NAME, AttrValue::Str("Renamed".to_string()));
+        });
+
+        // Repaints within the TTL reuse the key; a new size or an expired key rehashes
+        assert_eq!(cache.key_at(&project, uuid, 64, t0 + KEY_TTL / 2), base);
+        assert_ne!(cache.key_at(&project, uuid, 32, t0), base);
+        let edited = cache.key_at(&project, uuid, 64, t0 + KEY_TTL);
+        assert_ne!(edited, base);
+        assert_eq!(cache.key_at(&project, uuid, 64, t0 + KEY_TTL * 2), edited);
+    }
+}
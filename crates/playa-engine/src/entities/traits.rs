@@ -20,11 +20,71 @@ pub enum CacheStrategy {
     All,
 }
 
+/// Proxy resolution used for playback/scrub decoding.
+///
+/// Proxy frames are cached under a salted node uuid (see [`ProxyLevel::cache_uuid`])
+/// so full-res and proxy frames for the same (node, frame) never collide.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, Default,
+)]
+pub enum ProxyLevel {
+    /// Full resolution (no downscale)
+    #[default]
+    Full,
+    /// 1/2 resolution
+    Half,
+    /// 1/4 resolution
+    Quarter,
+}
+
+impl ProxyLevel {
+    /// All levels, full first.
+    pub const ALL: [ProxyLevel; 3] = [ProxyLevel::Full, ProxyLevel::Half, ProxyLevel::Quarter];
+
+    /// Integer downscale factor (1, 2, 4).
+    pub fn factor(self) -> usize {
+        match self {
+            ProxyLevel::Full => 1,
+            ProxyLevel::Half => 2,
+            ProxyLevel::Quarter => 4,
+        }
+    }
+
+    /// Cycle Full -> Half -> Quarter -> Full (hotkey toggle).
+    pub fn next(self) -> Self {
+        match self {
+            ProxyLevel::Full => ProxyLevel::Half,
+            ProxyLevel::Half => ProxyLevel::Quarter,
+            ProxyLevel::Quarter => ProxyLevel::Full,
+        }
+    }
+
+    /// Short UI label.
+    pub fn label(self) -> &'static str {
+        match self {
+            ProxyLevel::Full => "Full",
+            ProxyLevel::Half => "1/2",
+            ProxyLevel::Quarter => "1/4",
+        }
+    }
+
+    /// Cache key uuid for this level. Full uses the node uuid unchanged;
+    /// proxy levels flip the top bits so they land in separate cache slots.
+    pub fn cache_uuid(self, node_uuid: Uuid) -> Uuid {
+        match self {
+            ProxyLevel::Full => node_uuid,
+            _ => Uuid::from_u128(node_uuid.as_u128() ^ ((self.factor() as u128) << 120)),
+        }
+    }
+}
+
 /// Simple cache statistics (subset exposed via trait)
 #[derive(Debug, Clone, Copy, Default)]
 pub struct CacheStatsSnapshot {
     pub hits: u64,
     pub misses: u64,
+    /// Frames evicted to stay within budget (climbing = cache thrashing)
+    pub evictions: u64,
     pub size: usize,
 }
 
@@ -79,6 +139,26 @@ pub trait WorkerPool: Send + Sync {
     /// If epoch changed before execution, the closure is skipped.
     /// This allows fast timeline scrubbing without wasted work.
     fn execute_with_epoch(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>);
+
+    /// Same as [`execute_with_epoch`](Self::execute_with_epoch), but ahead of
+    /// normally queued work (the displayed frame). Pools without a priority
+    /// tier just queue it normally.
+    fn execute_with_epoch_priority(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>) {
+        self.execute_with_epoch(epoch, f)
+    }
+
+    /// Same as [`execute_with_epoch`](Self::execute_with_epoch), but behind
+    /// all normally queued work (preloads of inactive comps). Pools without a
+    /// background tier just queue it normally.
+    fn execute_with_epoch_background(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>) {
+        self.execute_with_epoch(epoch, f)
+    }
+
+    /// True if `comp` is the comp in the main viewport (always, for pools
+    /// that don't track one).
+    fn is_active_comp(&self, _comp: Uuid) -> bool {
+        true
+    }
 }
 
 /// Blanket impl: Arc<T> implements traits if T does
@@ -112,4 +192,16 @@ impl<T: WorkerPool + ?Sized> WorkerPool for Arc<T> {
     fn execute_with_epoch(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>) {
         (**self).execute_with_epoch(epoch, f)
     }
+
+    fn execute_with_epoch_priority(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>) {
+        (**self).execute_with_epoch_priority(epoch, f)
+    }
+
+    fn execute_with_epoch_background(&self, epoch: u64, f: Box<dyn FnOnce() + Send + 'static>) {
+        (**self).execute_with_epoch_background(epoch, f)
+    }
+
+    fn is_active_comp(&self, comp: Uuid) -> bool {
+        (**self).is_active_comp(comp)
+    }
 }
@@ -0,0 +1,161 @@
+//! Audio waveform peaks for video/audio file sources.
+//!
+//! The timeline draws a min/max envelope inside clip bodies. Decoding a whole
+//! audio stream is slow, so peaks are computed once per source on a background
+//! thread and cached by source UUID; the UI polls [`WaveformCache::get_or_request`]
+//! every frame and simply draws nothing until the envelope is ready. The
+//! envelope is resolution-independent (see [`AudioPeaks::resample`]), so zooming
+//! never re-decodes.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use log::debug;
+use uuid::Uuid;
+
+pub use playa_io::AudioPeaks;
+
+use super::file_node::FileNode;
+
+/// Envelope resolution: fine enough for 1px columns at high timeline zoom
+pub const WAVEFORM_BINS_PER_SEC: f32 = 500.0;
+
+lazy_static::lazy_static! {
+    static ref WAVEFORMS: WaveformCache = WaveformCache::default();
+}
+
+enum Entry {
+    /// Worker thread still decoding
+    Pending,
+    /// Decoded; `None` when the source has no audio (or failed to decode)
+    Ready(Option<Arc<AudioPeaks>>),
+}
+
+/// Per-source cache of audio peak envelopes.
+#[derive(Default)]
+pub struct WaveformCache {
+    entries: Arc<Mutex<HashMap<Uuid, Entry>>>,
+}
+
+impl WaveformCache {
+    /// Process-wide cache shared by all timelines
+    pub fn global() -> &'static WaveformCache {
+        &WAVEFORMS
+    }
+
+    /// Peaks for `source`, or `None` while decoding / when it has no audio.
+    ///
+    /// The first call for a source spawns the decode thread; `on_ready` runs on
+    /// that thread once the result is stored (e.g. to request a UI repaint).
+    pub fn get_or_request(
+        &self,
+        source: Uuid,
+        path: &Path,
+        on_ready: impl FnOnce() + Send + 'static,
+    ) -> Option<Arc<AudioPeaks>> {
+        let mut entries = self.entries.lock().expect("waveform cache lock");
+        match entries.get(&source) {
+            Some(Entry::Ready(peaks)) => return peaks.clone(),
+            Some(Entry::Pending) => return None,
+            None => {}
+        }
+        entries.insert(source, Entry::Pending);
+        drop(entries);
+
+        let shared = Arc::clone(&self.entries);
+        let path: PathBuf = path.to_path_buf();
+        let spawned = thread::Builder::new()
+            .name("playa-waveform".to_string())
+            .spawn(move || {
+                let peaks = match playa_io::decode_audio_peaks(&path, WAVEFORM_BINS_PER_SEC) {
+                    Ok(peaks) => peaks.map(Arc::new),
+                    Err(e) => {
+                        debug!("No waveform for {}: {:?}", path.display(), e);
+                        None
+                    }
+                };
+                // Stored even if invalidated meanwhile: the result is still valid for the path
+                shared
+                    .lock()
+                    .expect("waveform cache lock")
+                    .insert(source, Entry::Ready(peaks));
+                on_ready();
+            });
+        if spawned.is_err() {
+            self.entries
+                .lock()
+                .expect("waveform cache lock")
+                .insert(source, Entry::Ready(None));
+        }
+        None
+    }
+
+    /// Peaks for a file node's audio track; non-video sources never decode.
+    pub fn get_for_file(
+        &self,
+        source: Uuid,
+        node: &FileNode,
+        on_ready: impl FnOnce() + Send + 'static,
+    ) -> Option<Arc<AudioPeaks>> {
+        let mask = node.file_mask()?;
+        let path = Path::new(&mask);
+        if !playa_io::media::is_video(path) {
+            return None;
+        }
+        self.get_or_request(source, path, on_ready)
+    }
+
+    /// True while the decode thread for `source` is running
+    pub fn is_pending(&self, source: Uuid) -> bool {
+        matches!(
+            self.entries
+                .lock()
+                .expect("waveform cache lock")
+                .get(&source),
+            Some(Entry::Pending)
+        )
+    }
+
+    /// Drop cached peaks (e.g. after the source file changed on disk)
+    pub fn invalidate(&self, source: Uuid) {
+        self.entries
+            .lock()
+            .expect("waveform cache lock")
+            .remove(&source);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    #[test]
+    fn test_missing_source_resolves_without_peaks() {
+        let cache = WaveformCache::default();
+        let source = Uuid::new_v4();
+        let path = Path::new("/nonexistent/clip.mov");
+        let (tx, rx) = mpsc::channel();
+
+        assert!(
+            cache
+                .get_or_request(source, path, move || tx.send(()).unwrap())
+                .is_none()
+        );
+        rx.recv_timeout(Duration::from_secs(10))
+            .expect("decode thread finished");
+        assert!(!cache.is_pending(source));
+        // Resolved as "no audio": repeat requests neither block nor respawn
+        assert!(
+            cache
+                .get_or_request(source, path, || panic!("respawned"))
+                .is_none()
+        );
+
+        cache.invalidate(source);
+        assert!(!cache.is_pending(source));
+    }
+}
@@ -2,7 +2,8 @@
 //! `entities::effects::brightness`.
 //!
 //! Shader formula matches the CPU implementation exactly:
-//! `out.rgb = (in.rgb - 0.5) * (1 + contrast) + 0.5 + brightness`.
+//! `out.rgb = (in.rgb - 0.5) * (1 + contrast) + 0.5 + brightness`,
+//! in linear light (8-bit targets hold sRGB values and are decoded first).
 //! Alpha untouched.
 
 use std::collections::HashMap;
@@ -14,7 +15,9 @@ const WGSL: &str = include_str!("../shaders/effects/brightness.wgsl");
 struct Uniforms {
     brightness: f32,
     contrast: f32,
-    _pad: [f32; 2],
+    /// 1.0 when the values are sRGB-encoded (8-bit)
+    srgb: f32,
+    _pad: f32,
 }
 
 /// Per-output-format render pipeline cache for the brightness effect.
@@ -162,7 +165,12 @@ impl BrightnessRunner {
         let uni = Uniforms {
             brightness,
             contrast,
-            _pad: [0.0; 2],
+            srgb: if format == wgpu::TextureFormat::Rgba8Unorm {
+                1.0
+            } else {
+                0.0
+            },
+            _pad: 0.0,
         };
         let uniform_buf = self.uniform_buf.as_ref().unwrap();
         queue.write_buffer(uniform_buf, 0, bytemuck::bytes_of(&uni));
@@ -2,11 +2,13 @@
 //
 // CPU equivalent: entities::effects::brightness::apply.
 // out.rgb = (in.rgb - 0.5) * (1 + contrast) + 0.5 + brightness; out.a = in.a
+// sRGB-encoded input (srgb = 1) is adjusted in linear light and re-encoded.
 
 struct Uniforms {
     brightness: f32,
     contrast: f32,
-    _pad: vec2<f32>,
+    srgb: f32,
+    _pad: f32,
 }
 
 @group(0) @binding(0) var<uniform> u: Uniforms;
@@ -26,12 +28,25 @@ fn vs_main(@location(0) pos: vec2<f32>, @location(1) uv: vec2<f32>) -> VsOut {
     return o;
 }
 
+fn srgb_to_linear(v: vec3<f32>) -> vec3<f32> {
+    return select(pow((v + 0.055) / 1.055, vec3<f32>(2.4)), v / 12.92, v <= vec3<f32>(0.04045));
+}
+
+fn linear_to_srgb(v: vec3<f32>) -> vec3<f32> {
+    return select(1.055 * pow(v, vec3<f32>(1.0 / 2.4)) - 0.055, v * 12.92, v <= vec3<f32>(0.0031308));
+}
+
 @fragment
 fn fs_main(inp: VsOut) -> @location(0) vec4<f32> {
     let src = textureSample(t_in, s_tex, inp.uv);
     let cf = 1.0 + u.contrast;
-    let r = (src.r - 0.5) * cf + 0.5 + u.brightness;
-    let g = (src.g - 0.5) * cf + 0.5 + u.brightness;
-    let b = (src.b - 0.5) * cf + 0.5 + u.brightness;
-    return vec4<f32>(r, g, b, src.a);
+    var rgb = src.rgb;
+    if (u.srgb > 0.5) {
+        rgb = srgb_to_linear(rgb);
+    }
+    rgb = (rgb - 0.5) * cf + 0.5 + u.brightness;
+    if (u.srgb > 0.5) {
+        rgb = linear_to_srgb(rgb);
+    }
+    return vec4<f32>(rgb, src.a);
 }
@@ -599,6 +599,47 @@ impl WgpuCompositor {
         Some(out)
     }
 
+    /// Blend two small layers on the GPU and compare with the CPU result.
+    ///
+    /// Catches adapters that create the pipeline fine but render nothing
+    /// (software GL over RDP / in VMs reads back black), so the host can fall
+    /// back to the CPU compositor instead of showing an empty viewport.
+    pub fn self_test(&mut self) -> Result<(), String> {
+        const SIZE: usize = 4;
+        let solid = |rgba: [u8; 4]| {
+            let pixels = rgba.repeat(SIZE * SIZE);
+            Frame::from_u8_buffer(pixels, SIZE, SIZE)
+        };
+        let layers = vec![
+            LayerPayload::pre_rendered(solid([200, 40, 40, 255]), 1.0, BlendMode::Normal),
+            LayerPayload::pre_rendered(solid([40, 40, 200, 255]), 0.5, BlendMode::Normal),
+        ];
+        let expected = CpuCompositor
+            .blend(layers.clone())
+            .ok_or("CPU reference blend failed")?;
+        let actual = self.blend_inner(layers)?;
+        let (want, got) = (expected.buffer(), actual.buffer());
+        let (PixelBuffer::U8(want), PixelBuffer::U8(got)) = (want.as_ref(), got.as_ref()) else {
+            return Err("self-test read back an unexpected pixel format".into());
+        };
+        match want
+            .iter()
+            .zip(got.iter())
+            .position(|(a, b)| a.abs_diff(*b) > 2)
+        {
+            None if want.len() == got.len() => Ok(()),
+            None => Err(format!(
+                "read back {} bytes, expected {}",
+                got.len(),
+                want.len()
+            )),
+            Some(i) => Err(format!(
+                "GPU output differs from CPU (byte {}: {} vs {})",
+                i, got[i], want[i]
+            )),
+        }
+    }
+
     fn blend_inner(&mut self, layers: Vec<LayerPayload>) -> Result<Frame, String> {
         if layers.is_empty() {
             return Err("no layers".into());
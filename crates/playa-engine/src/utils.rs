@@ -1,6 +1,88 @@
-//! Utility functions (`media` delegates to [`playa_io::media`]).
+//! Utility functions (`media` delegates to [`playa_io::media`], `psd` to
+//! [`playa_io::psd`], `timecode` to [`playa_time::timecode`]).
 
 /// Media file extensions and `@frame` path parsing.
 pub mod media {
     pub use playa_io::media::*;
 }
+
+/// Layered PSD reading and `doc.psd#<layer>` path helpers.
+pub mod psd {
+    pub use playa_io::psd::*;
+}
+
+/// SMPTE timecode for display and frame entry, taking the `f32` fps comps store.
+pub mod timecode {
+    pub use playa_time::TimeDisplay;
+    use playa_time::{Fps, format_time, parse_time};
+
+    /// Format `frame` as `HH:MM:SS:FF` (`;` before frames when drop-frame).
+    /// Drop-frame only applies at 29.97 / 59.94; other rates fall back to NDF.
+    pub fn frame_to_tc(frame: i32, fps: f32, drop_frame: bool) -> String {
+        format_time(
+            frame,
+            Fps::from_f32_lossy(fps),
+            TimeDisplay::Timecode { drop_frame },
+        )
+    }
+
+    /// Parse `HH:MM:SS:FF` (or shorter `MM:SS:FF`, `SS:FF`) back to a frame.
+    /// A `;` separator selects drop-frame math.
+    pub fn tc_to_frame(tc: &str, fps: f32) -> Option<i32> {
+        parse_time(
+            tc,
+            Fps::from_f32_lossy(fps),
+            TimeDisplay::Timecode { drop_frame: false },
+        )
+    }
+
+    /// Render `frame` in the user's display mode.
+    pub fn display_frame(frame: i32, fps: f32, mode: TimeDisplay) -> String {
+        format_time(frame, Fps::from_f32_lossy(fps), mode)
+    }
+
+    /// Parse a frame-entry field: anything with `:` / `;` is timecode, otherwise
+    /// the value is read in the current display mode (frames or seconds).
+    pub fn parse_frame_entry(text: &str, fps: f32, mode: TimeDisplay) -> Option<i32> {
+        if text.contains([':', ';']) {
+            return tc_to_frame(text, fps);
+        }
+        let mode = match mode {
+            TimeDisplay::Timecode { .. } => TimeDisplay::Frames,
+            other => other,
+        };
+        parse_time(text, Fps::from_f32_lossy(fps), mode)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_tc_roundtrip_ndf_and_df() {
+            assert_eq!(frame_to_tc(86_400, 24.0, false), "01:00:00:00");
+            assert_eq!(tc_to_frame("01:00:00:00", 24.0), Some(86_400));
+            // 29.97 DF: one hour is 107892 frames
+            assert_eq!(frame_to_tc(107_892, 29.97, true), "01:00:00;00");
+            assert_eq!(tc_to_frame("01:00:00;00", 29.97), Some(107_892));
+            // DF is ignored for non-NTSC rates
+            assert_eq!(frame_to_tc(25, 25.0, true), "00:00:01:00");
+        }
+
+        #[test]
+        fn test_parse_frame_entry() {
+            let tc = TimeDisplay::Timecode { drop_frame: false };
+            assert_eq!(parse_frame_entry("00:00:02:00", 24.0, tc), Some(48));
+            assert_eq!(parse_frame_entry("48", 24.0, tc), Some(48));
+            assert_eq!(
+                parse_frame_entry("01:00", 24.0, TimeDisplay::Frames),
+                Some(24)
+            );
+            assert_eq!(
+                parse_frame_entry("2.0", 24.0, TimeDisplay::Seconds),
+                Some(48)
+            );
+            assert_eq!(parse_frame_entry("abc", 24.0, TimeDisplay::Frames), None);
+        }
+    }
+}
@@ -34,6 +34,51 @@ pub struct JumpToBookmarkEvent {
     pub slot: u8,
 }
 
+/// Add or update the timeline marker at `frame`.
+#[derive(Clone, Debug)]
+pub struct SetMarkerEvent {
+    pub comp_uuid: Uuid,
+    pub frame: i32,
+    pub label: String,
+    pub color: [f32; 3],
+}
+
+#[derive(Clone, Debug)]
+pub struct RemoveMarkerEvent {
+    pub comp_uuid: Uuid,
+    pub frame: i32,
+}
+
+/// Set (or clear with `None`) the comp's region of interest:
+/// `[x, y, width, height]` in comp pixels, top-left origin.
+#[derive(Clone, Debug)]
+pub struct SetRoiEvent {
+    pub comp_uuid: Uuid,
+    pub rect: Option<[u32; 4]>,
+}
+
+/// Append a review annotation to its frame.
+/// Payload is the serialized engine `Annotation` (JSON, like `SetLayerAttrsEvent`).
+#[derive(Clone, Debug)]
+pub struct AddAnnotationEvent {
+    pub comp_uuid: Uuid,
+    pub annotation: Value,
+}
+
+/// Remove the newest annotation on `frame` (undo last stroke).
+#[derive(Clone, Debug)]
+pub struct PopAnnotationEvent {
+    pub comp_uuid: Uuid,
+    pub frame: i32,
+}
+
+/// Remove every annotation on `frame`, or on all frames with `None`.
+#[derive(Clone, Debug)]
+pub struct ClearAnnotationsEvent {
+    pub comp_uuid: Uuid,
+    pub frame: Option<i32>,
+}
+
 // === Layer Operations ===
 
 #[derive(Clone, Debug)]
@@ -53,6 +98,10 @@ pub struct RemoveLayerEvent {
 #[derive(Clone, Debug)]
 pub struct RemoveSelectedLayerEvent;
 
+/// Remove selected layers and close the gaps they leave on their rows.
+#[derive(Clone, Debug)]
+pub struct RippleDeleteSelectedLayerEvent;
+
 #[derive(Clone, Debug)]
 pub struct MoveLayerEvent {
     pub comp_uuid: Uuid,
@@ -101,12 +150,19 @@ pub struct TrimLayersStartEvent(pub Uuid);
 #[derive(Clone, Debug)]
 pub struct TrimLayersEndEvent(pub Uuid);
 
+/// Trim selected layers' ends to the playhead, shifting later layers on the
+/// same row by the change.
+#[derive(Clone, Debug)]
+pub struct RippleTrimLayersEndEvent(pub Uuid);
+
 #[derive(Clone, Debug)]
 pub struct LayerAttributesChangedEvent {
     pub comp_uuid: Uuid,
     pub layer_uuids: Vec<Uuid>,
     pub visible: bool,
     pub solo: bool,
+    pub mute: bool,
+    pub locked: bool,
     pub opacity: f32,
     pub blend_mode: String,
     pub speed: f32,
@@ -135,10 +191,25 @@ pub struct SetLayerAttrsEvent {
     pub attrs: Vec<(String, Value)>,
 }
 
+/// Generic node attribute batch (Attribute Editor multi-select of project
+/// nodes). Every node gets the same values; the cache is invalidated once for
+/// the whole batch. JSON payload like [`SetLayerAttrsEvent`].
+#[derive(Clone, Debug)]
+pub struct SetNodeAttrsEvent {
+    pub node_uuids: Vec<Uuid>,
+    pub attrs: Vec<(String, Value)>,
+}
+
+/// Batch (position, rotation, scale) update from the viewport.
+/// Channels that already carry keyframes update their key at `frame`.
 #[derive(Clone, Debug)]
 pub struct SetLayerTransformsEvent {
     pub comp_uuid: Uuid,
+    /// Comp frame the edit applies to
+    pub frame: i32,
     pub updates: Vec<(Uuid, [f32; 3], [f32; 3], [f32; 3])>,
+    /// Channels ("position" / "rotation" / "scale") to record as keyframes at `frame`
+    pub record_keys: Vec<String>,
 }
 
 #[derive(Clone, Debug)]
@@ -174,6 +245,12 @@ pub struct DuplicateLayersEvent {
     pub comp_uuid: Uuid,
 }
 
+/// Split selected layers at the playhead (editorial cut).
+#[derive(Clone, Debug)]
+pub struct SplitLayersEvent {
+    pub comp_uuid: Uuid,
+}
+
 #[derive(Clone, Debug)]
 pub struct CopyLayersEvent {
     pub comp_uuid: Uuid,
@@ -15,5 +15,5 @@ pub mod viewport;
 pub mod viewport_tool;
 
 pub use bus::{BoxedEvent, CompEventEmitter, Event, EventBus, EventEmitter, downcast_event};
-pub use prefs::{CompositorBackend, CompositorBackendChangedEvent, GizmoPrefs};
+pub use prefs::{CompositorBackend, CompositorBackendChangedEvent, GizmoPrefs, ViewportDefaults};
 pub use viewport_tool::{SetToolEvent, ToolMode};
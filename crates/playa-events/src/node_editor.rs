@@ -1,5 +1,9 @@
 //! Node editor widget events.
 
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
 #[derive(Clone, Debug)]
 pub struct NodeEditorFitAllEvent;
 
@@ -8,3 +12,28 @@ pub struct NodeEditorFitSelectedEvent;
 
 #[derive(Clone, Debug)]
 pub struct NodeEditorLayoutEvent;
+
+/// Create a Merge node compositing `a` over `b`. `blend_mode` is a layer
+/// `blend_mode` attr value ("normal", "screen", ...).
+#[derive(Clone, Debug)]
+pub struct CreateMergeNodeEvent {
+    pub a: Uuid,
+    pub b: Uuid,
+    pub blend_mode: String,
+}
+
+/// Rewire input `input` (0 = A, 1 = B) of the Merge node `merge` to `source`.
+#[derive(Clone, Debug)]
+pub struct SetMergeInputEvent {
+    pub merge: Uuid,
+    pub input: usize,
+    pub source: Uuid,
+}
+
+/// Write the node graph of the shown comp to `path`: Graphviz DOT when `dot`,
+/// else structured JSON (see `NodeEditorState::export_json`).
+#[derive(Clone, Debug)]
+pub struct ExportNodeGraphEvent {
+    pub path: PathBuf,
+    pub dot: bool,
+}
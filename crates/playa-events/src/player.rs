@@ -37,12 +37,37 @@ pub struct JumpToPrevEdgeEvent;
 #[derive(Clone, Debug)]
 pub struct JumpToNextEdgeEvent;
 
+/// Open the go-to-frame prompt (frame number or timecode) for the active comp.
+#[derive(Clone, Debug)]
+pub struct GoToFrameEvent;
+
 #[derive(Clone, Debug)]
 pub struct JogForwardEvent;
 
 #[derive(Clone, Debug)]
 pub struct JogBackwardEvent;
 
+// === Markers ===
+
+/// Drop a marker at the playhead of the active comp.
+#[derive(Clone, Debug)]
+pub struct AddMarkerEvent;
+
+#[derive(Clone, Debug)]
+pub struct JumpToPrevMarkerEvent;
+
+#[derive(Clone, Debug)]
+pub struct JumpToNextMarkerEvent;
+
+// === Keyframes ===
+
+/// Jump to the previous frame keyed on the active comp or any of its layers.
+#[derive(Clone, Debug)]
+pub struct JumpToPrevKeyframeEvent;
+
+#[derive(Clone, Debug)]
+pub struct JumpToNextKeyframeEvent;
+
 // === FPS Control ===
 
 #[derive(Clone, Debug)]
@@ -70,6 +95,31 @@ pub struct ToggleLoopEvent;
 #[derive(Clone, Debug)]
 pub struct SetLoopEvent(pub bool);
 
+// === Frame Hold ===
+
+/// Park on the current frame (or release the hold): playback and scrubbing
+/// leave it on screen until toggled off.
+#[derive(Clone, Debug)]
+pub struct ToggleFrameHoldEvent;
+
+// === Sequence Playback ===
+
+#[derive(Clone, Debug)]
+pub struct ToggleSequencePlaybackEvent;
+
+#[derive(Clone, Debug)]
+pub struct SetSequencePlaybackEvent(pub bool);
+
+/// Sequence playback is nearing the end of this comp's work area: warm up the
+/// comp that plays next.
+#[derive(Clone, Debug)]
+pub struct PreloadNextCompEvent(pub Uuid);
+
+/// Sequence playback finished this comp's work area: activate the next comp
+/// in project order and keep playing.
+#[derive(Clone, Debug)]
+pub struct PlayNextCompEvent(pub Uuid);
+
 // === Preload ===
 
 #[allow(dead_code)]
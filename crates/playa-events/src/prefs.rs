@@ -1,9 +1,11 @@
 //! Preferences / settings payload + events.
 //!
-//! [`GizmoPrefs`] is stored inside project JSON; defined here so UI and engine
-//! share one type without coupling widgets to `entities::project` internals.
+//! [`GizmoPrefs`] and [`ViewportDefaults`] are stored inside project JSON;
+//! defined here so UI and engine share one type without coupling widgets to
+//! `entities::project` internals.
 
 use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -25,6 +27,29 @@ impl Default for GizmoPrefs {
     }
 }
 
+/// Per-project viewport defaults, applied to the live viewport when the
+/// project loads.
+///
+/// Precedence: app prefs < project defaults < live user changes. `None` leaves
+/// the current value alone; changes made during the session stick until the
+/// next project load and are never written back here.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ViewportDefaults {
+    /// View transform (viewport shader preset, e.g. `tonemap_aces`)
+    pub view_transform: Option<String>,
+    /// Viewport backdrop (sRGB)
+    pub background: Option<[u8; 3]>,
+    /// Alpha checkerboard
+    pub checker: Option<bool>,
+}
+
+impl ViewportDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct ResetSettingsEvent;
 
@@ -49,9 +74,16 @@ pub struct ToggleFullscreenEvent;
 #[derive(Clone, Debug)]
 pub struct ToggleFrameNumbersEvent;
 
+/// Cycle playback proxy resolution Full → 1/2 → 1/4.
+#[derive(Clone, Debug)]
+pub struct CycleProxyLevelEvent;
+
 #[derive(Clone, Debug)]
 pub struct SetGizmoPrefsEvent(pub GizmoPrefs);
 
+#[derive(Clone, Debug)]
+pub struct SetViewportDefaultsEvent(pub ViewportDefaults);
+
 /// CPU vs GPU compositor backend (persisted via AppSettings).
 #[derive(
     Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize, Default,
@@ -68,7 +100,7 @@ pub struct CompositorBackendChangedEvent {
     pub backend: CompositorBackend,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub enum HotkeyWindow {
     Global,
     Viewport,
@@ -76,3 +108,15 @@ pub enum HotkeyWindow {
     Project,
     NodeEditor,
 }
+
+/// Write the current hotkey bindings to a JSON file (Settings > Hotkeys).
+#[derive(Debug, Clone)]
+pub struct ExportHotkeysEvent(pub PathBuf);
+
+/// Replace hotkey bindings with a previously exported JSON file.
+#[derive(Debug, Clone)]
+pub struct ImportHotkeysEvent(pub PathBuf);
+
+/// Drop custom hotkeys and restore the built-in defaults.
+#[derive(Debug, Clone)]
+pub struct ResetHotkeysEvent;
@@ -29,6 +29,15 @@ pub struct AddTextEvent {
     pub text: String,
 }
 
+/// Create a solid/gradient generator (`SolidNode`) for backings and slates.
+/// With `matte` set it starts as a black-to-white ramp at the active comp's
+/// resolution, ready to use as a luma track matte.
+#[derive(Clone, Debug)]
+pub struct AddSolidEvent {
+    pub name: String,
+    pub matte: bool,
+}
+
 /// Create an empty `AINode` in the project. The provider defaults to
 /// `"seedance.text_to_video"`; the user edits prompt / provider / etc.
 /// via the standard Attribute Editor afterwards.
@@ -77,6 +86,10 @@ pub struct RemoveMediaEvent(pub Uuid);
 #[derive(Clone, Debug)]
 pub struct RemoveSelectedMediaEvent;
 
+/// Deep-copy the selected comps (new uuids, duplicated layers)
+#[derive(Clone, Debug)]
+pub struct DuplicateSelectedMediaEvent;
+
 #[derive(Clone, Debug)]
 pub struct ClearAllMediaEvent;
 
@@ -86,12 +99,32 @@ pub struct SaveProjectEvent(pub PathBuf);
 #[derive(Clone, Debug)]
 pub struct LoadProjectEvent(pub PathBuf);
 
+/// Append a review playlist's items as clips (see `Playlist`)
+#[derive(Clone, Debug)]
+pub struct LoadPlaylistEvent(pub PathBuf);
+
+/// Write the project's clips out as a review playlist
+#[derive(Clone, Debug)]
+pub struct SavePlaylistEvent(pub PathBuf);
+
 #[derive(Clone, Debug)]
 pub struct QuickSaveEvent;
 
 #[derive(Clone, Debug)]
 pub struct OpenProjectDialogEvent;
 
+/// Undo the last timeline / attribute edit
+#[derive(Clone, Debug)]
+pub struct UndoEvent;
+
+/// Redo the last undone edit
+#[derive(Clone, Debug)]
+pub struct RedoEvent;
+
+/// Reopen the most recent file/project from the recent-items list
+#[derive(Clone, Debug)]
+pub struct OpenMostRecentEvent;
+
 #[derive(Clone, Debug)]
 pub struct SelectMediaEvent(pub Uuid);
 
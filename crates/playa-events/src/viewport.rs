@@ -16,3 +16,39 @@ pub struct Viewport100Event;
 
 #[derive(Clone, Debug)]
 pub struct ViewportRefreshEvent;
+
+/// Toggle checkerboard display behind transparent pixels.
+#[derive(Clone, Debug)]
+pub struct ToggleCheckerEvent;
+
+/// Toggle onion-skinning of neighbor frames.
+#[derive(Clone, Debug)]
+pub struct ToggleOnionSkinEvent;
+
+/// Cycle viewport framing guides (safe areas / center / aspect mask).
+#[derive(Clone, Debug)]
+pub struct CycleGuidesEvent;
+
+/// Rotate the viewport display 90° clockwise (non-destructive).
+#[derive(Clone, Debug)]
+pub struct RotateViewportEvent;
+
+/// Mirror the viewport display horizontally (flip).
+#[derive(Clone, Debug)]
+pub struct FlipViewportEvent;
+
+/// Mirror the viewport display vertically (flop).
+#[derive(Clone, Debug)]
+pub struct FlopViewportEvent;
+
+/// Reset viewport rotation / flip / flop to upright.
+#[derive(Clone, Debug)]
+pub struct ResetOrientationEvent;
+
+/// Save the current viewport view to bookmark slot 1-9 (stored in the project).
+#[derive(Clone, Debug)]
+pub struct StoreViewBookmarkEvent(pub u8);
+
+/// Restore the viewport view saved in bookmark slot 1-9.
+#[derive(Clone, Debug)]
+pub struct RecallViewBookmarkEvent(pub u8);
@@ -8,14 +8,23 @@ pub enum ToolMode {
     Move,
     Rotate,
     Scale,
+    /// Region of interest: drag a rect to composite and inspect only that area
+    Roi,
+    /// Review markup: freehand / arrow / text annotations on the current frame
+    Annotate,
+    /// Draw / edit the selected layer's rectangle or ellipse mask
+    Mask,
 }
 
 impl ToolMode {
-    pub const ALL: [ToolMode; 4] = [
+    pub const ALL: [ToolMode; 7] = [
         ToolMode::Select,
         ToolMode::Move,
         ToolMode::Rotate,
         ToolMode::Scale,
+        ToolMode::Roi,
+        ToolMode::Annotate,
+        ToolMode::Mask,
     ];
 
     pub fn as_str(&self) -> &'static str {
@@ -24,6 +33,9 @@ impl ToolMode {
             ToolMode::Move => "move",
             ToolMode::Rotate => "rotate",
             ToolMode::Scale => "scale",
+            ToolMode::Roi => "roi",
+            ToolMode::Annotate => "annotate",
+            ToolMode::Mask => "mask",
         }
     }
 
@@ -32,6 +44,9 @@ impl ToolMode {
             "move" => ToolMode::Move,
             "rotate" => ToolMode::Rotate,
             "scale" => ToolMode::Scale,
+            "roi" => ToolMode::Roi,
+            "annotate" => ToolMode::Annotate,
+            "mask" => ToolMode::Mask,
             _ => ToolMode::Select,
         }
     }
@@ -42,6 +57,9 @@ impl ToolMode {
             ToolMode::Move => "Move",
             ToolMode::Rotate => "Rotate",
             ToolMode::Scale => "Scale",
+            ToolMode::Roi => "ROI",
+            ToolMode::Annotate => "Annotate",
+            ToolMode::Mask => "Mask",
         }
     }
 
@@ -51,6 +69,9 @@ impl ToolMode {
             ToolMode::Move => "W",
             ToolMode::Rotate => "E",
             ToolMode::Scale => "R",
+            ToolMode::Roi => "D",
+            ToolMode::Annotate => "S",
+            ToolMode::Mask => "V",
         }
     }
 }
@@ -0,0 +1,269 @@
+//! Animated GIF / APNG decoding for reference clips.
+//!
+//! Multi-frame files address single frames with the video `@frame` suffix
+//! (`ref.gif@12`). Frames come out fully composited as straight-alpha RGBA8:
+//! the `image` decoders place partial frames at their offsets and apply GIF
+//! disposal (keep / background / previous) before handing the canvas over.
+//! The clip's fps derives from the average frame delay.
+//!
+//! Neither format allows random access, so frame N decodes frames `0..=N`.
+//! Reference GIFs are small and this stays cheap; single-frame files are plain
+//! stills and go through the generic image path.
+
+use image::codecs::gif::GifDecoder;
+use image::codecs::png::PngDecoder;
+use image::{AnimationDecoder, Frames};
+use log::trace;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use crate::dispatch::AttrKv;
+use crate::error::IoError;
+use crate::media;
+use crate::pixel::{DecodedRaster, RawPixelBuffer, RawPixelFormat};
+
+/// Browsers play GIF delays of 0-10 ms at 100 ms; match them.
+const GIF_MIN_DELAY_MS: f64 = 20.0;
+const GIF_DEFAULT_DELAY_MS: f64 = 100.0;
+
+/// Frame count, size and timing of an animated image.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AnimInfo {
+    pub width: u32,
+    pub height: u32,
+    pub frame_count: usize,
+    /// Playback rate from the average frame delay
+    pub fps: f32,
+    /// Per-frame delays as stored in the file
+    pub delays_ms: Vec<f64>,
+}
+
+fn img_err(path: &Path, e: impl std::fmt::Display) -> IoError {
+    IoError::Image(format!("Failed to decode {}: {}", path.display(), e))
+}
+
+fn is_gif(path: &Path) -> bool {
+    path.extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| s.eq_ignore_ascii_case("gif"))
+}
+
+/// Frame iterator over `file`; `None` for a PNG without animation control.
+fn open_frames(file: &Path) -> Result<Option<Frames<'static>>, IoError> {
+    let reader =
+        BufReader::new(File::open(file).map_err(|e| {
+            IoError::LoadError(format!("Failed to open {}: {}", file.display(), e))
+        })?);
+    if is_gif(file) {
+        let decoder = GifDecoder::new(reader).map_err(|e| img_err(file, e))?;
+        return Ok(Some(decoder.into_frames()));
+    }
+    let decoder = PngDecoder::new(reader).map_err(|e| img_err(file, e))?;
+    if !decoder.is_apng().map_err(|e| img_err(file, e))? {
+        return Ok(None);
+    }
+    let apng = decoder.apng().map_err(|e| img_err(file, e))?;
+    Ok(Some(apng.into_frames()))
+}
+
+/// Average fps for `delays_ms`, with GIF's near-zero delays played at 100 ms.
+fn fps_from_delays(delays_ms: &[f64], gif: bool) -> f32 {
+    let effective = delays_ms.iter().map(|&ms| match ms {
+        ms if gif && ms < GIF_MIN_DELAY_MS => GIF_DEFAULT_DELAY_MS,
+        ms if ms <= 0.0 => GIF_DEFAULT_DELAY_MS,
+        ms => ms,
+    });
+    let avg = effective.sum::<f64>() / delays_ms.len().max(1) as f64;
+    // Keep 3 decimals so 1000/30 ms reads as 30.303 rather than float noise
+    ((1000.0 / avg * 1000.0).round() / 1000.0) as f32
+}
+
+/// Probe an animated image; `Ok(None)` for single-frame files (stills).
+pub fn probe(path: &Path) -> Result<Option<AnimInfo>, IoError> {
+    let (file, _) = media::parse_video_path(path);
+    let Some(frames) = open_frames(&file)? else {
+        return Ok(None);
+    };
+    let mut size = (0, 0);
+    let mut delays_ms = Vec::new();
+    for frame in frames {
+        let frame = frame.map_err(|e| img_err(&file, e))?;
+        let (num, den) = frame.delay().numer_denom_ms();
+        delays_ms.push(num as f64 / den.max(1) as f64);
+        size = frame.buffer().dimensions();
+    }
+    if delays_ms.len() < 2 {
+        return Ok(None);
+    }
+    Ok(Some(AnimInfo {
+        width: size.0,
+        height: size.1,
+        frame_count: delays_ms.len(),
+        fps: fps_from_delays(&delays_ms, is_gif(&file)),
+        delays_ms,
+    }))
+}
+
+/// Header probe for `ref.gif@N`: canvas size, frame count and derived fps.
+pub fn header(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
+    trace!("Reading animated image header: {}", path.display());
+    let info = probe(path)?
+        .ok_or_else(|| IoError::UnsupportedFormat(format!("{} is not animated", path.display())))?;
+    Ok(vec![
+        ("width".into(), AttrKv::UInt(info.width)),
+        ("height".into(), AttrKv::UInt(info.height)),
+        (
+            "format".into(),
+            AttrKv::Str(format!(
+                "Animated {}",
+                if is_gif(&media::parse_video_path(path).0) {
+                    "GIF"
+                } else {
+                    "PNG"
+                }
+            )),
+        ),
+        ("channels".into(), AttrKv::UInt(4)),
+        ("frames".into(), AttrKv::UInt(info.frame_count as u32)),
+        ("fps".into(), AttrKv::Float(info.fps)),
+        ("anim:delays_ms".into(), AttrKv::FloatArray(info.delays_ms)),
+    ])
+}
+
+/// Decode frame N of `ref.gif@N` (frame 0 without a suffix), composited.
+pub fn decode(path: &Path) -> Result<DecodedRaster, IoError> {
+    trace!("Decoding animated image frame: {}", path.display());
+    let (file, frame_idx) = media::parse_video_path(path);
+    let index = frame_idx.unwrap_or(0);
+    let mut frames = open_frames(&file)?
+        .ok_or_else(|| IoError::UnsupportedFormat(format!("{} is not animated", file.display())))?;
+    let frame = frames
+        .nth(index)
+        .ok_or_else(|| IoError::LoadError(format!("{} has no frame {}", file.display(), index)))?
+        .map_err(|e| img_err(&file, e))?;
+    let buffer = frame.into_buffer();
+    let (width, height) = buffer.dimensions();
+    Ok(DecodedRaster {
+        buffer: RawPixelBuffer::U8(buffer.into_raw()),
+        format: RawPixelFormat::Rgba8,
+        width: width as usize,
+        height: height as usize,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// LZW stream with a clear code before every index, so the code width
+    /// stays at 3 bits and no dictionary is needed (min code size 2).
+    fn lzw(indices: &[u8]) -> Vec<u8> {
+        const CLEAR: u32 = 4;
+        const EOI: u32 = 5;
+        let codes = indices.iter().flat_map(|&i| [CLEAR, i as u32]).chain([EOI]);
+        let (mut out, mut acc, mut bits) = (Vec::new(), 0u32, 0);
+        for code in codes {
+            acc |= code << bits;
+            bits += 3;
+            while bits >= 8 {
+                out.push(acc as u8);
+                acc >>= 8;
+                bits -= 8;
+            }
+        }
+        if bits > 0 {
+            out.push(acc as u8);
+        }
+        out
+    }
+
+    /// Frame: (left, top, w, h, palette index, disposal, delay in 1/100 s)
+    type TestFrame = (u16, u16, u16, u16, u8, u8, u16);
+
+    /// GIF89a with palette 0 black, 1 red, 2 green, 3 blue
+    fn make_gif(width: u16, height: u16, frames: &[TestFrame]) -> Vec<u8> {
+        let mut gif = b"GIF89a".to_vec();
+        gif.extend_from_slice(&width.to_le_bytes());
+        gif.extend_from_slice(&height.to_le_bytes());
+        gif.extend_from_slice(&[0x81, 0, 0]); // 4-entry global palette
+        gif.extend_from_slice(&[0, 0, 0, 255, 0, 0, 0, 255, 0, 0, 0, 255]);
+        for &(left, top, w, h, index, disposal, delay) in frames {
+            gif.extend_from_slice(&[0x21, 0xF9, 4, disposal << 2]);
+            gif.extend_from_slice(&delay.to_le_bytes());
+            gif.extend_from_slice(&[0, 0]);
+            gif.push(0x2C);
+            for v in [left, top, w, h] {
+                gif.extend_from_slice(&v.to_le_bytes());
+            }
+            gif.extend_from_slice(&[0, 2]); // no local palette, min code size 2
+            let data = lzw(&vec![index; w as usize * h as usize]);
+            for block in data.chunks(255) {
+                gif.push(block.len() as u8);
+                gif.extend_from_slice(block);
+            }
+            gif.push(0);
+        }
+        gif.push(0x3B);
+        gif
+    }
+
+    fn pixel(r: &DecodedRaster, x: usize, y: usize) -> [u8; 4] {
+        let RawPixelBuffer::U8(px) = &r.buffer else {
+            panic!("expected U8 buffer");
+        };
+        let i = (y * r.width + x) * 4;
+        px[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_three_frame_gif() {
+        const KEEP: u8 = 1;
+        const BACKGROUND: u8 = 2;
+        const PREVIOUS: u8 = 3;
+        let gif = make_gif(
+            4,
+            4,
+            &[
+                (0, 0, 4, 4, 1, KEEP, 4),       // red canvas
+                (2, 2, 2, 2, 2, PREVIOUS, 4),   // green patch, then restored
+                (0, 0, 1, 1, 3, BACKGROUND, 7), // blue corner
+            ],
+        );
+        let dir = std::env::temp_dir().join(format!("playa_anim_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("ref.gif");
+        std::fs::write(&path, gif).unwrap();
+
+        let info = probe(&path).unwrap().expect("animated");
+        assert_eq!((info.width, info.height, info.frame_count), (4, 4, 3));
+        assert_eq!(info.delays_ms, [40.0, 40.0, 70.0]);
+        assert_eq!(info.fps, 20.0); // 50 ms average
+
+        let frame = |n| decode(&path.with_file_name(format!("ref.gif@{n}"))).unwrap();
+        const RED: [u8; 4] = [255, 0, 0, 255];
+        const GREEN: [u8; 4] = [0, 255, 0, 255];
+        const BLUE: [u8; 4] = [0, 0, 255, 255];
+
+        let f1 = frame(1);
+        assert_eq!((f1.width, f1.height), (4, 4));
+        assert_eq!(pixel(&f1, 3, 3), GREEN); // partial update at its offset
+        assert_eq!(pixel(&f1, 0, 0), RED); // composited onto frame 0
+
+        let f2 = frame(2);
+        assert_eq!(pixel(&f2, 0, 0), BLUE);
+        assert_eq!(pixel(&f2, 3, 3), RED); // "previous" disposal undid the patch
+        assert_eq!(pixel(&f2, 1, 1), RED);
+
+        assert!(decode(&path.with_file_name("ref.gif@3")).is_err());
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_fps_from_delays() {
+        assert_eq!(fps_from_delays(&[40.0, 40.0], false), 25.0);
+        // Zero / 10 ms GIF delays play at 100 ms
+        assert_eq!(fps_from_delays(&[0.0, 10.0], true), 10.0);
+        assert_eq!(fps_from_delays(&[10.0, 10.0], false), 100.0);
+    }
+}
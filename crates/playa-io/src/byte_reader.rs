@@ -0,0 +1,173 @@
+//! Bounds-checked byte reader for the hand-written header parsers (DPX /
+//! Cineon, PSD, EXR header).
+//!
+//! Every read checks its range (with overflowing offsets treated as out of
+//! range) and fails with [`Truncated`] instead of panicking, so corrupt or cut
+//! files surface as load errors. Multi-byte fields use the reader's
+//! [`Endian`].
+
+use crate::error::IoError;
+
+/// Byte order of multi-byte fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Endian {
+    Big,
+    Little,
+}
+
+/// A read ran past the end of the data
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Truncated {
+    /// Format name for the error message, e.g. `"PSD"`
+    pub what: &'static str,
+    /// Offset of the failed read
+    pub at: usize,
+}
+
+impl From<Truncated> for IoError {
+    fn from(t: Truncated) -> Self {
+        IoError::LoadError(format!("{} truncated at byte {}", t.what, t.at))
+    }
+}
+
+/// Cursor over a byte slice. `Copy`, so positioned reads ([`Self::at`]) and
+/// sub-blocks don't disturb the original.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ByteReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+    /// Offset of `data` in the outermost buffer (non-zero for sub-blocks)
+    base: usize,
+    endian: Endian,
+    what: &'static str,
+}
+
+macro_rules! read_num {
+    ($($name:ident: $ty:ty),* $(,)?) => {
+        $(
+            pub fn $name(&mut self) -> Result<$ty, Truncated> {
+                let b = self.bytes()?;
+                Ok(match self.endian {
+                    Endian::Big => <$ty>::from_be_bytes(b),
+                    Endian::Little => <$ty>::from_le_bytes(b),
+                })
+            }
+        )*
+    };
+}
+
+impl<'a> ByteReader<'a> {
+    /// Reader at the start of `data`; `what` names the format in errors.
+    pub fn new(data: &'a [u8], endian: Endian, what: &'static str) -> Self {
+        Self {
+            data,
+            pos: 0,
+            base: 0,
+            endian,
+            what,
+        }
+    }
+
+    /// Copy of this reader positioned at absolute offset `pos`
+    pub fn at(&self, pos: usize) -> Self {
+        Self { pos, ..*self }
+    }
+
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// Position in the outermost buffer, counting the offsets of enclosing
+    /// [`Self::sub`] blocks
+    pub fn abs_pos(&self) -> usize {
+        self.base + self.pos
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_sub(self.pos)
+    }
+
+    fn truncated(&self) -> Truncated {
+        Truncated {
+            what: self.what,
+            at: self.pos,
+        }
+    }
+
+    /// Next `n` bytes
+    pub fn take(&mut self, n: usize) -> Result<&'a [u8], Truncated> {
+        let bytes = self
+            .pos
+            .checked_add(n)
+            .and_then(|end| self.data.get(self.pos..end))
+            .ok_or_else(|| self.truncated())?;
+        self.pos += n;
+        Ok(bytes)
+    }
+
+    /// Next `n` bytes as a reader of their own (same byte order)
+    pub fn sub(&mut self, n: usize) -> Result<Self, Truncated> {
+        let base = self.abs_pos();
+        Ok(Self {
+            base,
+            ..Self::new(self.take(n)?, self.endian, self.what)
+        })
+    }
+
+    pub fn bytes<const N: usize>(&mut self) -> Result<[u8; N], Truncated> {
+        Ok(self.take(N)?.try_into().expect("length checked by take"))
+    }
+
+    pub fn u8(&mut self) -> Result<u8, Truncated> {
+        Ok(self.bytes::<1>()?[0])
+    }
+
+    read_num!(u16: u16, i16: i16, u32: u32, i32: i32, f32: f32, f64: f64);
+
+    /// Null-terminated string, lossily decoded as UTF-8
+    pub fn cstr(&mut self) -> Result<String, Truncated> {
+        let rest = self.data.get(self.pos..).unwrap_or_default();
+        let len = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or_else(|| self.truncated())?;
+        let s = String::from_utf8_lossy(&rest[..len]).into_owned();
+        self.pos += len + 1;
+        Ok(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endian_and_bounds() {
+        let data = [0x01, 0x02, 0x03, 0x04, b'h', b'i', 0];
+        let mut be = ByteReader::new(&data, Endian::Big, "test");
+        assert_eq!(be.u16(), Ok(0x0102));
+        assert_eq!(
+            ByteReader::new(&data, Endian::Little, "test").u32(),
+            Ok(0x0403_0201)
+        );
+        assert_eq!(be.at(4).cstr().as_deref(), Ok("hi"));
+        // Positioned copies leave the original cursor alone
+        assert_eq!(be.pos(), 2);
+
+        assert_eq!(
+            be.at(6).u16(),
+            Err(Truncated {
+                what: "test",
+                at: 6
+            })
+        );
+        assert!(be.at(usize::MAX).take(2).is_err());
+        assert!(be.at(7).cstr().is_err());
+        assert_eq!(be.remaining(), 5);
+
+        // Sub-blocks report positions in the outer buffer
+        let mut sub = be.sub(4).unwrap();
+        sub.take(1).unwrap();
+        assert_eq!((sub.pos(), sub.abs_pos()), (1, 3));
+    }
+}
@@ -3,9 +3,14 @@
 use log::trace;
 use std::path::Path;
 
+use crate::anim;
+use crate::dpx;
 use crate::error::IoError;
+use crate::exr_header;
 use crate::media;
 use crate::pixel::{DecodedRaster, RawPixelBuffer, RawPixelFormat};
+use crate::psd;
+use crate::svg;
 use crate::video;
 
 /// Serialized header field for engine `Attrs` bridging.
@@ -33,10 +38,23 @@ pub enum AttrKv {
     Matrix4([f32; 16]),
 }
 
+/// Display-ready metadata for the inspector panel: titled rows (key, value)
+/// plus nested groups (e.g. an EXR part's channel list).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MetaGroup {
+    pub title: String,
+    pub rows: Vec<(String, String)>,
+    pub children: Vec<MetaGroup>,
+}
+
 enum FileKind {
     Video,
     Exr,
     Hdr,
+    Dpx,
+    Psd,
+    Svg,
+    Anim,
     Generic,
 }
 
@@ -47,31 +65,70 @@ fn classify_ext(ext: &str) -> FileKind {
         FileKind::Exr
     } else if ext == "hdr" {
         FileKind::Hdr
+    } else if ext == "dpx" || ext == "cin" {
+        FileKind::Dpx
+    } else if ext == "psd" {
+        FileKind::Psd
+    } else if ext == "svg" {
+        FileKind::Svg
     } else {
         FileKind::Generic
     }
 }
 
 fn path_ext(path: &Path) -> String {
+    // `doc.psd#3` / `logo.svg#WxH` / `ref.gif@12` address a PSD layer / SVG
+    // raster size / animation frame
+    let path = media::disk_path(path);
     path.extension()
         .and_then(|s| s.to_str())
         .unwrap_or("")
         .to_lowercase()
 }
 
+/// Classify a path; `ref.gif@12` addresses a frame of an animated image.
+fn classify(path: &Path) -> FileKind {
+    if media::is_anim_image(path) && media::parse_video_path(path).1.is_some() {
+        return FileKind::Anim;
+    }
+    classify_ext(&path_ext(path))
+}
+
 pub fn header_attrs(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
-    match classify_ext(&path_ext(path)) {
+    match classify(path) {
         FileKind::Video => header_video(path),
         FileKind::Exr => header_exr(path),
+        FileKind::Dpx => dpx::header(path),
+        FileKind::Psd => psd::header(path),
+        FileKind::Svg => svg::header(path),
+        FileKind::Anim => anim::header(path),
         FileKind::Hdr | FileKind::Generic => header_generic(path),
     }
 }
 
+/// Everything the file's header says, grouped for display.
+///
+/// EXR headers are read attribute-by-attribute ([`exr_header`]), whatever the
+/// decode backend. Generic images report size and format without decoding
+/// pixels, plus EXIF and PNG text chunks. Other kinds group their
+/// [`header_attrs`] by namespace (`video:`, `format:`, …).
+pub fn inspect(path: &Path) -> Result<Vec<MetaGroup>, IoError> {
+    match classify(path) {
+        FileKind::Exr => exr_header::read(&media::disk_path(path)),
+        FileKind::Hdr | FileKind::Generic => inspect_generic(path),
+        _ => header_attrs(path).map(group_attrs),
+    }
+}
+
 pub fn decode_raster(path: &Path) -> Result<DecodedRaster, IoError> {
-    match classify_ext(&path_ext(path)) {
+    match classify(path) {
         FileKind::Video => decode_video(path),
         FileKind::Exr => decode_exr(path),
         FileKind::Hdr => decode_hdr(path),
+        FileKind::Dpx => dpx::decode(path),
+        FileKind::Psd => psd::decode(path),
+        FileKind::Svg => svg::decode(path),
+        FileKind::Anim => anim::decode(path),
         FileKind::Generic => decode_generic(path),
     }
 }
@@ -180,8 +237,13 @@ fn header_exr(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
         .first()
         .ok_or_else(|| IoError::Exr("EXR has no layers".to_string()))?;
 
-    let width = first.width;
-    let height = first.height;
+    // Frames are placed in the display window (see `decode_exr`), so that is
+    // the size the engine sees; both windows ride along for the viewport overlay
+    let windows = exr_header::windows(path).ok();
+    let (width, height) = windows
+        .map(|w| w.display_size())
+        .map(|(w, h)| (w as u32, h as u32))
+        .unwrap_or((first.width, first.height));
     let channel_count = first.spec.channel_names.len() as u32;
     let layer_count = layered.layers.len() as u32;
     let compression_str = first
@@ -223,6 +285,23 @@ fn header_exr(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
     if layer_count > 1 {
         v.push(("layer_names".into(), AttrKv::Str(layer_names)));
     }
+    if let Some(windows) = windows {
+        let ints = |b: [i32; 4]| AttrKv::IntArray(b.iter().map(|&i| i as i64).collect());
+        v.push(("data_window".into(), ints(windows.data)));
+        v.push(("display_window".into(), ints(windows.display)));
+    }
+    // Standard `pixelAspectRatio` header (OIIO spells it `PixelAspectRatio`)
+    if let Some(par) = ["pixelAspectRatio", "PixelAspectRatio"]
+        .iter()
+        .find_map(|k| first.spec.attributes.get(*k))
+        .and_then(|v| match v {
+            vfx_core::AttrValue::Float(f) => Some(*f as f32),
+            _ => None,
+        })
+        .filter(|par| *par > 0.0)
+    {
+        v.push(("pixel_aspect".into(), AttrKv::Float(par)));
+    }
 
     // Absorb the FULL authored attribute set from every part, namespaced under
     // `exr:` (and `exr:<layer>:` for parts beyond the first) so nothing the file
@@ -282,24 +361,47 @@ fn decode_exr(path: &Path) -> Result<DecodedRaster, IoError> {
 
     // Preserve the source precision: half EXRs round-trip f32→f16 losslessly
     // (every f16 value is exactly representable in f32), keeping the cache compact.
-    if authored == vfx_io::PixelFormat::F16 {
+    let raster = if authored == vfx_io::PixelFormat::F16 {
         let buffer: Vec<f16> = rgba_f32.iter().map(|&v| f16::from_f32(v)).collect();
         trace!("Loaded EXR HALF: {}x{} (f16)", width, height);
-        Ok(DecodedRaster {
+        DecodedRaster {
             buffer: RawPixelBuffer::F16(buffer),
             format: RawPixelFormat::RgbaF16,
             width,
             height,
-        })
+        }
     } else {
         trace!("Loaded EXR FLOAT: {}x{} (f32)", width, height);
-        Ok(DecodedRaster {
+        DecodedRaster {
             buffer: RawPixelBuffer::F32(rgba_f32),
             format: RawPixelFormat::RgbaF32,
             width,
             height,
-        })
+        }
+    };
+    Ok(place_in_display_window(path, raster))
+}
+
+/// Put a data-window raster at its offset inside the display window.
+///
+/// Partial renders leave the rest transparent; overscan past the display
+/// window is cropped. A raster that is not data-window sized (the backend
+/// already placed it) or a header without windows passes through unchanged.
+#[cfg(feature = "exr")]
+fn place_in_display_window(path: &Path, raster: DecodedRaster) -> DecodedRaster {
+    let Ok(windows) = exr_header::windows(path) else {
+        return raster;
+    };
+    let display = windows.display_size();
+    if windows.data == windows.display || (raster.width, raster.height) != windows.data_size() {
+        return raster;
     }
+    let (x, y) = windows.data_offset();
+    trace!(
+        "EXR data window {:?} placed in display window {:?}",
+        windows.data, windows.display
+    );
+    raster.placed(x, y, display.0, display.1)
 }
 
 fn header_generic(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
@@ -331,14 +433,136 @@ fn header_generic(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
         ("channels".into(), AttrKv::UInt(channels)),
     ];
 
-    // Absorb EXIF (JPEG/TIFF/HEIF/…), namespaced `exif:<TagName>`. Optional and
-    // best-effort: a file without EXIF, or an unreadable EXIF block, simply adds
-    // no keys and never fails the header probe.
+    // Absorb EXIF (JPEG/TIFF/HEIF/…), namespaced `exif:<TagName>`, and PNG
+    // text chunks as `png:<keyword>`. Optional and best-effort: a file without
+    // either, or an unreadable block, simply adds no keys and never fails the
+    // header probe.
     v.extend(read_exif(path));
+    v.extend(read_png_text(path));
 
     Ok(v)
 }
 
+/// Size / format / EXIF / PNG text of a generic image, without decoding pixels.
+fn inspect_generic(path: &Path) -> Result<Vec<MetaGroup>, IoError> {
+    let reader = image::ImageReader::open(path)
+        .and_then(|r| r.with_guessed_format())
+        .map_err(|e| IoError::Image(format!("Failed to open image: {}", e)))?;
+    let format = reader.format();
+    let (width, height) = reader
+        .into_dimensions()
+        .map_err(|e| IoError::Image(format!("Image header error: {}", e)))?;
+    let mut v = vec![
+        ("width".into(), AttrKv::UInt(width)),
+        ("height".into(), AttrKv::UInt(height)),
+    ];
+    if let Some(format) = format {
+        v.push(("format".into(), AttrKv::Str(format!("{:?}", format))));
+    }
+    v.extend(read_exif(path));
+    v.extend(read_png_text(path));
+    Ok(group_attrs(v))
+}
+
+/// Group flat header attrs by namespace: unprefixed keys under "File", then
+/// one group per `prefix:` in first-seen order.
+fn group_attrs(entries: Vec<(String, AttrKv)>) -> Vec<MetaGroup> {
+    let mut groups = vec![MetaGroup {
+        title: "File".to_string(),
+        ..MetaGroup::default()
+    }];
+    for (key, kv) in entries {
+        let (title, name) = key.split_once(':').unwrap_or(("File", key.as_str()));
+        let row = (name.to_string(), display_kv(&kv));
+        match groups.iter_mut().find(|g| g.title == title) {
+            Some(group) => group.rows.push(row),
+            None => groups.push(MetaGroup {
+                title: title.to_string(),
+                rows: vec![row],
+                children: Vec::new(),
+            }),
+        }
+    }
+    groups
+}
+
+fn display_kv(kv: &AttrKv) -> String {
+    let join = |items: Vec<String>| format!("[{}]", items.join(", "));
+    match kv {
+        AttrKv::Str(s) => s.clone(),
+        AttrKv::UInt(u) => u.to_string(),
+        AttrKv::Float(f) => f.to_string(),
+        AttrKv::Int64(i) => i.to_string(),
+        AttrKv::IntArray(a) => join(a.iter().map(|v| v.to_string()).collect()),
+        AttrKv::FloatArray(a) => join(a.iter().map(|v| v.to_string()).collect()),
+        AttrKv::Matrix3(m) => join(m.iter().map(|v| v.to_string()).collect()),
+        AttrKv::Matrix4(m) => join(m.iter().map(|v| v.to_string()).collect()),
+    }
+}
+
+/// PNG `tEXt` / `iTXt` chunks as `png:<keyword>` → text; compressed text
+/// (`zTXt`, compressed `iTXt`) is listed but not inflated. Chunks are walked
+/// by seeking, so image data is never read. Empty for non-PNG files or on
+/// any read error.
+fn read_png_text(path: &Path) -> Vec<(String, AttrKv)> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', 0x0d, 0x0a, 0x1a, 0x0a];
+    let Ok(file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    let mut reader = std::io::BufReader::new(file);
+    let mut signature = [0u8; 8];
+    if reader.read_exact(&mut signature).is_err() || signature != SIGNATURE {
+        return Vec::new();
+    }
+    let split = |b: &[u8]| -> Option<(String, Vec<u8>)> {
+        let nul = b.iter().position(|c| *c == 0)?;
+        Some((
+            String::from_utf8_lossy(&b[..nul]).into_owned(),
+            b[nul + 1..].to_vec(),
+        ))
+    };
+    let mut out = Vec::new();
+    let mut header = [0u8; 8];
+    while reader.read_exact(&mut header).is_ok() {
+        let len = u32::from_be_bytes([header[0], header[1], header[2], header[3]]);
+        let kind = [header[4], header[5], header[6], header[7]];
+        if !matches!(&kind, b"tEXt" | b"zTXt" | b"iTXt") {
+            if &kind == b"IEND" || reader.seek(SeekFrom::Current(len as i64 + 4)).is_err() {
+                break;
+            }
+            continue;
+        }
+        // Chunk body plus CRC
+        let mut body = vec![0u8; len as usize + 4];
+        if reader.read_exact(&mut body).is_err() {
+            break;
+        }
+        body.truncate(len as usize);
+        let entry = match &kind {
+            // Latin-1 text
+            b"tEXt" => split(&body).map(|(k, t)| (k, t.iter().map(|&c| c as char).collect())),
+            b"zTXt" => split(&body).map(|(k, t)| (k, format!("(compressed, {} bytes)", t.len()))),
+            _ => split(&body).and_then(|(k, rest)| {
+                let compressed = *rest.first()? != 0;
+                // Skip compression flag/method, language tag, translated keyword
+                let (_, rest) = split(rest.get(2..)?)?;
+                let (_, text) = split(&rest)?;
+                Some(if compressed {
+                    (k, format!("(compressed, {} bytes)", text.len()))
+                } else {
+                    (k, String::from_utf8_lossy(&text).into_owned())
+                })
+            }),
+        };
+        if let Some((key, text)) = entry {
+            out.push((format!("png:{key}"), AttrKv::Str(text)));
+        }
+    }
+    out
+}
+
 /// Read EXIF tags from the primary image IFD as namespaced `exif:<TagName>` →
 /// `AttrKv::Str` (human display value). Returns empty on any failure / no EXIF.
 /// Restricted to the primary IFD so thumbnail-IFD duplicates don't clobber keys.
@@ -404,3 +628,43 @@ fn decode_generic(path: &Path) -> Result<DecodedRaster, IoError> {
         height,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inspect_png_text_without_decode() {
+        let mut png = Vec::new();
+        image::RgbaImage::new(3, 2)
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .unwrap();
+        // tEXt chunk right after IHDR (signature + 25-byte IHDR chunk); the CRC
+        // is not checked by the reader
+        let body = b"Author\0lookdev";
+        let mut chunk = (body.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(b"tEXt");
+        chunk.extend_from_slice(body);
+        chunk.extend_from_slice(&[0; 4]);
+        png.splice(33..33, chunk);
+
+        let dir = std::env::temp_dir().join(format!("playa_inspect_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("still.png");
+        std::fs::write(&path, png).unwrap();
+
+        let groups = inspect(&path).unwrap();
+        assert_eq!(groups[0].title, "File");
+        assert!(
+            groups[0]
+                .rows
+                .contains(&("width".to_string(), "3".to_string()))
+        );
+        let text = groups.iter().find(|g| g.title == "png").expect("png group");
+        assert_eq!(
+            text.rows,
+            vec![("Author".to_string(), "lookdev".to_string())]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,555 @@
+//! DPX (SMPTE 268M) and Kodak Cineon reader for scanned-film plates.
+//!
+//! Both formats are parsed straight from the file header (no external deps):
+//! dimensions, bit depth, packing, endianness and transfer characteristic.
+//! Pixels are unpacked to RGBA F16. Log-encoded data (Cineon always, DPX when
+//! the transfer is *printing density*) goes through the standard Kodak
+//! log→lin curve (ref white 685, ref black 95, negative gamma 0.6), so the
+//! engine always receives scene-linear values; the header reports the original
+//! encoding under `dpx:transfer` / `cineon:transfer` and the resulting
+//! `colorspace`.
+//!
+//! Supported: 8/16-bit, 10-bit filled (method A / B, the common "10-bit packed"
+//! layout) and 12-bit filled; RGB, RGBA and luminance descriptors. Tightly
+//! packed (packing 0) 10/12-bit data is rejected with `UnsupportedFormat`.
+
+use half::f16;
+use log::trace;
+use std::path::Path;
+
+use crate::byte_reader::{ByteReader, Endian};
+use crate::dispatch::AttrKv;
+use crate::error::IoError;
+use crate::pixel::{DecodedRaster, RawPixelBuffer, RawPixelFormat};
+
+const DPX_MAGIC: u32 = 0x5344_5058; // "SDPX"
+const CINEON_MAGIC: u32 = 0x802A_5FD7;
+
+/// Kodak reference white code value (10-bit).
+const REF_WHITE: f32 = 685.0;
+/// Kodak reference black code value (10-bit).
+const REF_BLACK: f32 = 95.0;
+/// Film negative gamma used by the Cineon conversion.
+const NEG_GAMMA: f32 = 0.6;
+/// Printing density per 10-bit code value.
+const DENSITY_PER_CV: f32 = 0.002;
+
+/// DPX transfer code for printing density (log).
+const TRANSFER_PRINTING_DENSITY: u8 = 1;
+/// DPX transfer code for generic logarithmic data.
+const TRANSFER_LOGARITHMIC: u8 = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Dpx,
+    Cineon,
+}
+
+/// Parsed subset of a DPX / Cineon header — everything needed to unpack pixels.
+#[derive(Debug, Clone)]
+struct Header {
+    container: Container,
+    big_endian: bool,
+    width: usize,
+    height: usize,
+    /// Components per pixel (1 = luma, 3 = RGB, 4 = RGBA).
+    channels: usize,
+    bit_depth: u8,
+    /// DPX packing code (0 packed, 1 filled method A, 2 filled method B).
+    packing: u16,
+    data_offset: usize,
+    /// Extra bytes after each scanline.
+    eol_padding: usize,
+    transfer: u8,
+    /// True when the stored values are log (printing density).
+    is_log: bool,
+    /// Pixel aspect ratio from the orientation header, when authored.
+    pixel_aspect: Option<f32>,
+}
+
+/// Header / pixel reader in the file's byte order.
+fn reader(data: &[u8], big_endian: bool) -> ByteReader<'_> {
+    let endian = if big_endian {
+        Endian::Big
+    } else {
+        Endian::Little
+    };
+    ByteReader::new(data, endian, "DPX")
+}
+
+/// Undefined-field sentinel used by both formats.
+const UNDEFINED_U32: u32 = 0xFFFF_FFFF;
+
+fn parse_header(data: &[u8]) -> Result<Header, IoError> {
+    let magic = data
+        .get(0..4)
+        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or_else(|| IoError::LoadError("DPX file too short".to_string()))?;
+
+    let h = if magic == DPX_MAGIC || magic.swap_bytes() == DPX_MAGIC {
+        parse_dpx_header(data, magic == DPX_MAGIC)?
+    } else if magic == CINEON_MAGIC || magic.swap_bytes() == CINEON_MAGIC {
+        parse_cineon_header(data, magic == CINEON_MAGIC)?
+    } else {
+        return Err(IoError::UnsupportedFormat(format!(
+            "Not a DPX/Cineon file (magic {magic:#010x})"
+        )));
+    };
+    if !matches!(h.bit_depth, 8 | 10 | 12 | 16) {
+        return Err(IoError::UnsupportedFormat(format!(
+            "{:?} bit depth {} not supported",
+            h.container, h.bit_depth
+        )));
+    }
+    if h.width == 0 || h.height == 0 {
+        return Err(IoError::LoadError(format!(
+            "{:?} header has empty dimensions {}x{}",
+            h.container, h.width, h.height
+        )));
+    }
+    Ok(h)
+}
+
+fn parse_dpx_header(data: &[u8], big_endian: bool) -> Result<Header, IoError> {
+    let r = reader(data, big_endian);
+
+    let file_data_offset = r.at(4).u32()? as usize;
+    // Image information header starts at 768; first image element at 780.
+    let width = r.at(772).u32()? as usize;
+    let height = r.at(776).u32()? as usize;
+    let descriptor = r.at(800).u8()?;
+    let transfer = r.at(801).u8()?;
+    let bit_depth = r.at(803).u8()?;
+    let packing = r.at(804).u16()?;
+    let encoding = r.at(806).u16()?;
+    let element_offset = r.at(808).u32()?;
+    let eol_padding = r.at(812).u32()?;
+
+    let channels = match descriptor {
+        // R, G, B, A, luma — single component.
+        1..=4 | 6 => 1,
+        50 => 3,
+        51 => 4,
+        other => {
+            return Err(IoError::UnsupportedFormat(format!(
+                "DPX descriptor {other} not supported"
+            )));
+        }
+    };
+    if encoding != 0 {
+        return Err(IoError::UnsupportedFormat(
+            "RLE-encoded DPX not supported".to_string(),
+        ));
+    }
+
+    let data_offset = if element_offset != 0 && element_offset != UNDEFINED_U32 {
+        element_offset as usize
+    } else {
+        file_data_offset
+    };
+
+    Ok(Header {
+        container: Container::Dpx,
+        big_endian,
+        width,
+        height,
+        channels,
+        bit_depth,
+        packing,
+        data_offset,
+        eol_padding: if eol_padding == UNDEFINED_U32 {
+            0
+        } else {
+            eol_padding as usize
+        },
+        transfer,
+        is_log: matches!(transfer, TRANSFER_PRINTING_DENSITY | TRANSFER_LOGARITHMIC),
+        pixel_aspect: dpx_pixel_aspect(&r),
+    })
+}
+
+/// Orientation header pixel aspect (horizontal:vertical at 1628/1632).
+/// Short files and undefined / zero fields yield `None`.
+fn dpx_pixel_aspect(r: &ByteReader) -> Option<f32> {
+    let h = r.at(1628).u32().ok()?;
+    let v = r.at(1632).u32().ok()?;
+    let defined = |x: u32| x != 0 && x != UNDEFINED_U32;
+    (defined(h) && defined(v)).then(|| h as f32 / v as f32)
+}
+
+fn parse_cineon_header(data: &[u8], big_endian: bool) -> Result<Header, IoError> {
+    let r = reader(data, big_endian);
+
+    let data_offset = r.at(4).u32()? as usize;
+    let channels = r.at(193).u8()? as usize;
+    // Channel 0 descriptor block at 196 (all channels share geometry in practice).
+    let bit_depth = r.at(198).u8()?;
+    let width = r.at(200).u32()? as usize;
+    let height = r.at(204).u32()? as usize;
+    let interleave = r.at(680).u8()?;
+    let packing = r.at(681).u8()?;
+    let eol_padding = r.at(684).u32()?;
+
+    if channels != 1 && channels != 3 {
+        return Err(IoError::UnsupportedFormat(format!(
+            "Cineon with {channels} channels not supported"
+        )));
+    }
+    if interleave != 0 {
+        return Err(IoError::UnsupportedFormat(
+            "Only pixel-interleaved Cineon is supported".to_string(),
+        ));
+    }
+    // Cineon packing 5 = longword left-justified, identical to DPX method A.
+    let packing = match (bit_depth, packing) {
+        (10, 5) => 1,
+        (8, _) | (16, _) => 1,
+        (bits, p) => {
+            return Err(IoError::UnsupportedFormat(format!(
+                "Cineon {bits}-bit packing {p} not supported"
+            )));
+        }
+    };
+
+    Ok(Header {
+        container: Container::Cineon,
+        big_endian,
+        width,
+        height,
+        channels,
+        bit_depth,
+        packing,
+        data_offset,
+        eol_padding: if eol_padding == UNDEFINED_U32 {
+            0
+        } else {
+            eol_padding as usize
+        },
+        transfer: TRANSFER_PRINTING_DENSITY,
+        is_log: true,
+        pixel_aspect: None,
+    })
+}
+
+/// Kodak Cineon log→lin for a 10-bit code value (0..1023).
+///
+/// Reference black maps to 0.0, reference white to 1.0; highlights above 685
+/// keep their headroom (> 1.0).
+pub fn cineon_log_to_lin(cv: f32) -> f32 {
+    let scale = DENSITY_PER_CV / NEG_GAMMA;
+    let offset = 10f32.powf((REF_BLACK - REF_WHITE) * scale);
+    let lin = 10f32.powf((cv - REF_WHITE) * scale);
+    (lin - offset) / (1.0 - offset)
+}
+
+fn transfer_name(code: u8) -> &'static str {
+    match code {
+        0 => "user-defined",
+        1 => "printing density",
+        2 => "linear",
+        3 => "logarithmic",
+        4 => "unspecified video",
+        5 => "SMPTE 274M",
+        6 => "ITU-R 709-4",
+        7 => "ITU-R 601-5 (625)",
+        8 => "ITU-R 601-5 (525)",
+        9 => "NTSC",
+        10 => "PAL",
+        11 => "Z linear",
+        12 => "Z homogeneous",
+        _ => "unknown",
+    }
+}
+
+/// Unpack one scanline into normalized code values (0..max_cv) per component.
+fn unpack_line(
+    h: &Header,
+    r: &ByteReader,
+    line_off: usize,
+    out: &mut Vec<u32>,
+) -> Result<(), IoError> {
+    let n = h.width * h.channels;
+    let mut r = r.at(line_off);
+    out.clear();
+    match (h.bit_depth, h.packing) {
+        (8, _) => out.extend(r.take(n)?.iter().map(|&b| b as u32)),
+        (16, _) => {
+            for _ in 0..n {
+                out.push(r.u16()? as u32);
+            }
+        }
+        (10, 1 | 2) => {
+            // Three 10-bit components per 32-bit word; method A pads the two
+            // LSBs, method B pads the two MSBs.
+            let shift = if h.packing == 1 { 2 } else { 0 };
+            for _ in 0..n.div_ceil(3) {
+                let word = r.u32()? >> shift;
+                for k in 0..3 {
+                    if out.len() < n {
+                        out.push((word >> (20 - 10 * k)) & 0x3FF);
+                    }
+                }
+            }
+        }
+        (12, 1 | 2) => {
+            // Each component in its own 16-bit slot; method A pads the 4 LSBs.
+            let shift = if h.packing == 1 { 4 } else { 0 };
+            for _ in 0..n {
+                out.push((r.u16()? >> shift) as u32 & 0xFFF);
+            }
+        }
+        (bits, packing) => {
+            return Err(IoError::UnsupportedFormat(format!(
+                "DPX {bits}-bit packing {packing} not supported"
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Bytes occupied by one scanline, excluding end-of-line padding; `None`
+/// when the header dimensions overflow.
+fn line_bytes(h: &Header) -> Option<usize> {
+    let n = h.width.checked_mul(h.channels)?;
+    match h.bit_depth {
+        8 => n.div_ceil(4).checked_mul(4),
+        10 => n.div_ceil(3).checked_mul(4),
+        _ => n.checked_mul(2)?.div_ceil(4).checked_mul(4),
+    }
+}
+
+/// Line stride in bytes, after checking that every scanline the header
+/// describes lies inside `data_len` (so allocations below are bounded by the
+/// file size rather than by raw header fields).
+fn checked_stride(h: &Header, data_len: usize) -> Result<usize, IoError> {
+    let too_big = || {
+        IoError::LoadError(format!(
+            "{:?} header describes {}x{}x{} {}-bit pixels beyond the {} byte file",
+            h.container, h.width, h.height, h.channels, h.bit_depth, data_len
+        ))
+    };
+    let line = line_bytes(h).ok_or_else(too_big)?;
+    let stride = line.checked_add(h.eol_padding).ok_or_else(too_big)?;
+    let end = stride
+        .checked_mul(h.height - 1)
+        .and_then(|v| v.checked_add(line))
+        .and_then(|v| v.checked_add(h.data_offset))
+        .ok_or_else(too_big)?;
+    if end > data_len {
+        return Err(too_big());
+    }
+    Ok(stride)
+}
+
+/// Header probe for `.dpx` / `.cin` (no pixel unpacking).
+pub fn header(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
+    trace!("Reading DPX/Cineon header: {}", path.display());
+    let data = std::fs::read(path)
+        .map_err(|e| IoError::LoadError(format!("Failed to read {}: {}", path.display(), e)))?;
+    let h = parse_header(&data)?;
+
+    let (label, ns) = match h.container {
+        Container::Dpx => ("DPX", "dpx"),
+        Container::Cineon => ("Cineon", "cineon"),
+    };
+    let transfer = transfer_name(h.transfer);
+
+    let mut v = vec![
+        ("width".into(), AttrKv::UInt(h.width as u32)),
+        ("height".into(), AttrKv::UInt(h.height as u32)),
+        (
+            "format".into(),
+            AttrKv::Str(format!("{label} ({}-bit, {transfer})", h.bit_depth)),
+        ),
+        ("channels".into(), AttrKv::UInt(h.channels as u32)),
+        ("bit_depth".into(), AttrKv::UInt(h.bit_depth as u32)),
+        // Decoded pixels are always linear: log data is converted on load.
+        ("colorspace".into(), AttrKv::Str("linear".to_string())),
+        (format!("{ns}:transfer"), AttrKv::Str(transfer.to_string())),
+        (
+            format!("{ns}:endian"),
+            AttrKv::Str(if h.big_endian { "big" } else { "little" }.to_string()),
+        ),
+        (format!("{ns}:packing"), AttrKv::UInt(h.packing as u32)),
+    ];
+    if let Some(par) = h.pixel_aspect {
+        v.push(("pixel_aspect".into(), AttrKv::Float(par)));
+    }
+    Ok(v)
+}
+
+/// Decode a DPX / Cineon frame to linear RGBA F16.
+pub fn decode(path: &Path) -> Result<DecodedRaster, IoError> {
+    trace!("Loading DPX/Cineon: {}", path.display());
+    let data = std::fs::read(path)
+        .map_err(|e| IoError::LoadError(format!("Failed to read {}: {}", path.display(), e)))?;
+    decode_bytes(&data)
+}
+
+fn decode_bytes(data: &[u8]) -> Result<DecodedRaster, IoError> {
+    let h = parse_header(data)?;
+    let r = reader(data, h.big_endian);
+
+    let stride = checked_stride(&h, data.len())?;
+
+    // parse_header only admits 8/10/12/16-bit data
+    let max_cv = ((1u32 << h.bit_depth) - 1) as f32;
+    // Log curve is defined on 10-bit code values; rescale other depths onto it.
+    let to_10bit = 1023.0 / max_cv;
+    let convert = |cv: u32| -> f32 {
+        if h.is_log {
+            cineon_log_to_lin(cv as f32 * to_10bit)
+        } else {
+            cv as f32 / max_cv
+        }
+    };
+
+    let mut buffer = Vec::with_capacity(h.width * h.height * 4);
+    let mut line = Vec::with_capacity(h.width * h.channels);
+
+    for y in 0..h.height {
+        unpack_line(&h, &r, h.data_offset + y * stride, &mut line)?;
+        for px in line.chunks_exact(h.channels) {
+            let rgba = match h.channels {
+                1 => {
+                    let v = convert(px[0]);
+                    [v, v, v, 1.0]
+                }
+                3 => [convert(px[0]), convert(px[1]), convert(px[2]), 1.0],
+                // Alpha is never log-encoded.
+                _ => [
+                    convert(px[0]),
+                    convert(px[1]),
+                    convert(px[2]),
+                    px[3] as f32 / max_cv,
+                ],
+            };
+            buffer.extend(rgba.iter().map(|&v| f16::from_f32(v)));
+        }
+    }
+
+    trace!(
+        "Loaded {:?}: {}x{} {}-bit (log={})",
+        h.container, h.width, h.height, h.bit_depth, h.is_log
+    );
+    Ok(DecodedRaster {
+        buffer: RawPixelBuffer::F16(buffer),
+        format: RawPixelFormat::RgbaF16,
+        width: h.width,
+        height: h.height,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Build a minimal big-endian 10-bit RGB DPX (method A) from code values.
+    fn make_dpx_10bit(width: u32, height: u32, transfer: u8, pixels: &[[u32; 3]]) -> Vec<u8> {
+        let offset = 2048usize;
+        let mut data = vec![0u8; offset];
+        data[0..4].copy_from_slice(&DPX_MAGIC.to_be_bytes());
+        data[4..8].copy_from_slice(&(offset as u32).to_be_bytes());
+        data[772..776].copy_from_slice(&width.to_be_bytes());
+        data[776..780].copy_from_slice(&height.to_be_bytes());
+        data[800] = 50; // RGB
+        data[801] = transfer;
+        data[803] = 10;
+        data[804..806].copy_from_slice(&1u16.to_be_bytes());
+        data[808..812].copy_from_slice(&(offset as u32).to_be_bytes());
+        for p in pixels {
+            let word = (p[0] << 22) | (p[1] << 12) | (p[2] << 2);
+            data.extend_from_slice(&word.to_be_bytes());
+        }
+        data
+    }
+
+    #[test]
+    fn test_log_to_lin_reference_points() {
+        assert!(cineon_log_to_lin(REF_BLACK).abs() < 1e-5);
+        assert!((cineon_log_to_lin(REF_WHITE) - 1.0).abs() < 1e-5);
+        assert!(cineon_log_to_lin(1023.0) > 1.0);
+    }
+
+    #[test]
+    fn test_decode_10bit_log_dpx() {
+        let data = make_dpx_10bit(
+            2,
+            1,
+            TRANSFER_PRINTING_DENSITY,
+            &[[685, 95, 685], [95, 685, 95]],
+        );
+        let raster = decode_bytes(&data).expect("decode DPX");
+        assert_eq!((raster.width, raster.height), (2, 1));
+        assert_eq!(raster.format, RawPixelFormat::RgbaF16);
+        let RawPixelBuffer::F16(px) = raster.buffer else {
+            panic!("expected F16 buffer");
+        };
+        let v: Vec<f32> = px.iter().map(|v| v.to_f32()).collect();
+        let close = |a: f32, b: f32| (a - b).abs() < 1e-2;
+        assert!(close(v[0], 1.0) && close(v[1], 0.0) && close(v[2], 1.0) && v[3] == 1.0);
+        assert!(close(v[4], 0.0) && close(v[5], 1.0) && close(v[6], 0.0));
+    }
+
+    #[test]
+    fn test_decode_10bit_linear_dpx_little_endian() {
+        let mut data = make_dpx_10bit(1, 1, 2, &[[1023, 0, 512]]);
+        // Re-encode every header field we touched as little-endian.
+        data[0..4].copy_from_slice(&DPX_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&2048u32.to_le_bytes());
+        data[772..776].copy_from_slice(&1u32.to_le_bytes());
+        data[776..780].copy_from_slice(&1u32.to_le_bytes());
+        data[804..806].copy_from_slice(&1u16.to_le_bytes());
+        data[808..812].copy_from_slice(&2048u32.to_le_bytes());
+        let word = (1023u32 << 22) | (512 << 2);
+        data[2048..2052].copy_from_slice(&word.to_le_bytes());
+
+        let raster = decode_bytes(&data).expect("decode LE DPX");
+        let RawPixelBuffer::F16(px) = raster.buffer else {
+            panic!("expected F16 buffer");
+        };
+        assert!((px[0].to_f32() - 1.0).abs() < 1e-3);
+        assert_eq!(px[1].to_f32(), 0.0);
+        assert!((px[2].to_f32() - 0.5).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_dpx_pixel_aspect() {
+        let mut data = make_dpx_10bit(1, 1, 2, &[[0, 0, 0]]);
+        assert_eq!(parse_header(&data).unwrap().pixel_aspect, None);
+
+        data[1628..1632].copy_from_slice(&2u32.to_be_bytes());
+        data[1632..1636].copy_from_slice(&1u32.to_be_bytes());
+        assert_eq!(parse_header(&data).unwrap().pixel_aspect, Some(2.0));
+    }
+
+    #[test]
+    fn test_malformed_headers_are_errors() {
+        let good = make_dpx_10bit(1, 1, 2, &[[0, 0, 0]]);
+
+        for bits in [0u8, 1, 32, 255] {
+            let mut data = good.clone();
+            data[803] = bits;
+            assert!(matches!(
+                decode_bytes(&data),
+                Err(IoError::UnsupportedFormat(_))
+            ));
+        }
+
+        // Dimensions far beyond the pixel data: rejected before allocating
+        let mut data = good.clone();
+        data[772..776].copy_from_slice(&u32::MAX.to_be_bytes());
+        data[776..780].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(decode_bytes(&data).is_err());
+
+        // Offsets near usize::MAX must not wrap
+        let mut data = good.clone();
+        data[808..812].copy_from_slice(&0xFFFF_FFF0u32.to_be_bytes());
+        data[812..816].copy_from_slice(&0xFFFF_FFF0u32.to_be_bytes());
+        assert!(decode_bytes(&data).is_err());
+
+        let mut data = good;
+        data[772..776].copy_from_slice(&0u32.to_be_bytes());
+        assert!(decode_bytes(&data).is_err());
+    }
+}
@@ -1,29 +1,37 @@
 //! Media decoding — **FFmpeg video** (`feature = "ffmpeg"`), **EXR** (`feature = "exr"`),
-//! generic images (`image`), and a **WebCodecs** scaffolding module for future Wasm/Web targets.
+//! DPX/Cineon film scans, layered PSD, SVG (`resvg`), animated GIF/APNG, generic images (`image`), and a **WebCodecs** scaffolding module for future Wasm/Web targets.
 //!
 //! Call [`init_ffmpeg`] once from the desktop binary before decoding video (`feature = "ffmpeg"`).
 //! On Wasm/minimal builds it is a no-op.
 
 #![allow(clippy::module_inception)]
 
+pub mod anim;
+mod byte_reader;
 pub mod dispatch;
+pub mod dpx;
 pub mod error;
+pub mod exr_header;
 #[cfg(feature = "exr")]
 pub mod exr_layered;
 pub mod media;
 pub mod pixel;
+pub mod psd;
 pub mod source_image;
+pub mod svg;
 pub mod video;
 pub mod webcodecs;
 
 #[cfg(feature = "ffmpeg")]
 pub use ::playa_ffmpeg as ffmpeg;
 
-pub use dispatch::{AttrKv, decode_raster, header_attrs};
+pub use dispatch::{AttrKv, MetaGroup, decode_raster, header_attrs, inspect};
 pub use error::IoError;
 pub use pixel::{DecodedRaster, RawPixelBuffer, RawPixelFormat};
 pub use source_image::{SourceImage, pick_display_layer};
-pub use video::{VideoMetadata, decode_frame, get_video_dimensions};
+pub use video::{
+    AudioPeaks, VideoMetadata, decode_audio_peaks, decode_frame, get_video_dimensions,
+};
 
 /// Initialise FFmpeg runtime (`feature = "ffmpeg"`).
 #[cfg(feature = "ffmpeg")]
@@ -35,3 +43,25 @@ pub fn init_ffmpeg() -> Result<(), Box<dyn std::error::Error>> {
 pub fn init_ffmpeg() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
+
+/// EXR backend compiled in (`feature = "exr"`); `None` when EXR support is off.
+pub fn exr_backend() -> Option<&'static str> {
+    cfg!(feature = "exr").then_some("vfx-io")
+}
+
+/// Linked FFmpeg library versions, e.g. `"libavcodec 62.11.100, ..."`.
+#[cfg(feature = "ffmpeg")]
+pub fn ffmpeg_version() -> Option<String> {
+    let v = |n: u32| format!("{}.{}.{}", n >> 16, (n >> 8) & 0xff, n & 0xff);
+    Some(format!(
+        "libavcodec {}, libavformat {}, libavutil {}",
+        v(playa_ffmpeg::codec::version()),
+        v(playa_ffmpeg::format::version()),
+        v(playa_ffmpeg::util::version())
+    ))
+}
+
+#[cfg(not(feature = "ffmpeg"))]
+pub fn ffmpeg_version() -> Option<String> {
+    None
+}
@@ -1,13 +1,18 @@
-//! Media extensions and path helpers (sequences, `@frame` suffix for video URLs).
+//! Media extensions and path helpers (sequence detection, `@frame` suffix for video URLs).
 
-use std::path::Path;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
 /// Supported video file extensions (lowercase, no dot).
 pub const VIDEO_EXTS: &[&str] = &["mp4", "mov", "avi", "mkv"];
 
+/// Extensions that may hold an animation (GIF, APNG); single-frame files are stills.
+pub const ANIM_EXTS: &[&str] = &["gif", "png"];
+
 /// All supported extensions (video + raster).
 pub const ALL_EXTS: &[&str] = &[
-    "exr", "png", "jpg", "jpeg", "tif", "tiff", "tga", "hdr", "mp4", "mov", "avi", "mkv",
+    "exr", "png", "jpg", "jpeg", "tif", "tiff", "tga", "hdr", "dpx", "cin", "psd", "svg", "gif",
+    "mp4", "mov", "avi", "mkv",
 ];
 
 /// True if path points at a video container (handles `clip.mp4@135` notation).
@@ -30,6 +35,27 @@ pub fn is_video(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
+/// True if path may be an animated image (GIF / APNG; handles `ref.gif@12`).
+///
+/// Extension check only: whether the file really has several frames is up to
+/// [`crate::anim::probe`].
+pub fn is_anim_image(path: &Path) -> bool {
+    parse_video_path(path)
+        .0
+        .extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| ANIM_EXTS.contains(&s.to_lowercase().as_str()))
+}
+
+/// True if path points at an SVG (handles `logo.svg#1920x1080` notation).
+pub fn is_svg(path: &Path) -> bool {
+    crate::svg::split_sized_path(path)
+        .0
+        .extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| s.eq_ignore_ascii_case("svg"))
+}
+
 /// Strip optional `@frame` suffix.
 /// `"video.mp4@17"` → `(video.mp4, Some(17))`
 pub fn parse_video_path(path: &Path) -> (std::path::PathBuf, Option<usize>) {
@@ -46,3 +72,425 @@ pub fn parse_video_path(path: &Path) -> (std::path::PathBuf, Option<usize>) {
 
     (path.to_path_buf(), None)
 }
+
+/// File on disk behind a virtual frame path: strips the `doc.psd#3` layer,
+/// `logo.svg#1920x1080` raster-size and animated-image `ref.gif@12` suffixes
+/// (video `@frame` is left alone).
+pub fn disk_path(path: &Path) -> PathBuf {
+    if is_anim_image(path) {
+        return parse_video_path(path).0;
+    }
+    let (path, _) = crate::psd::split_layer_path(path);
+    crate::svg::split_sized_path(&path).0
+}
+
+/// Split a file name at its frame field: the LAST run of digits in the stem.
+///
+/// `"shot010_v003.0042.exr"` → `("shot010_v003.", "0042", ".exr")`. Earlier
+/// numeric fields (shot / version numbers) stay in the prefix. Returns `None`
+/// when the stem does not end in digits.
+///
+/// Prefix and suffix identify the sequence and are compared exactly, case
+/// included: a `*` mask has to resolve to the real file names on
+/// case-sensitive file systems, so `a.1.JPG` and `a.2.jpg` are two sequences.
+pub fn split_frame_field(file_name: &str) -> Option<(&str, &str, &str)> {
+    let stem_end = file_name.rfind('.').unwrap_or(file_name.len());
+    let stem = &file_name[..stem_end];
+    let digits_start = stem
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| c.is_ascii_digit())
+        .last()
+        .map(|(i, _)| i)?;
+    Some((
+        &file_name[..digits_start],
+        &file_name[digits_start..stem_end],
+        &file_name[stem_end..],
+    ))
+}
+
+/// Frame-number field of a sequence file name
+#[derive(Clone, Debug, PartialEq)]
+pub enum PaddingPattern {
+    /// Printf-style: %04d -> 4 digits
+    Printf { width: usize },
+    /// Hash-style: #### -> 4 digits
+    Hashes { count: usize },
+    /// Printf hex: %04x -> 4 lowercase hex digits
+    Hex { width: usize },
+    /// At-sign: @ -> no padding
+    At,
+    /// No pattern found
+    None,
+}
+
+impl PaddingPattern {
+    /// Format frame number according to pattern (negative frames keep
+    /// their sign inside the width, like printf)
+    pub fn format(&self, frame: i32) -> String {
+        match self {
+            PaddingPattern::Printf { width } | PaddingPattern::Hashes { count: width } => {
+                format!("{:0width$}", frame, width = *width)
+            }
+            PaddingPattern::Hex { width } => format!("{:0width$x}", frame, width = *width),
+            PaddingPattern::At | PaddingPattern::None => {
+                format!("{}", frame)
+            }
+        }
+    }
+
+    /// Pattern as spelled in a file name (inverse of [`parse_padding_pattern`]):
+    /// `%04d`, `####`, `%04x`, `@`; empty for `None`
+    pub fn token(&self) -> String {
+        match self {
+            PaddingPattern::Printf { width } => format!("%0{}d", width),
+            PaddingPattern::Hashes { count } => "#".repeat(*count),
+            PaddingPattern::Hex { width } => format!("%0{}x", width),
+            PaddingPattern::At => "@".to_string(),
+            PaddingPattern::None => String::new(),
+        }
+    }
+
+    /// Zero-padded digit count; 0 for unpadded patterns
+    pub fn width(&self) -> usize {
+        match self {
+            PaddingPattern::Printf { width }
+            | PaddingPattern::Hashes { count: width }
+            | PaddingPattern::Hex { width } => *width,
+            PaddingPattern::At | PaddingPattern::None => 0,
+        }
+    }
+}
+
+/// Parse filename pattern and extract padding info
+/// Returns (prefix, pattern, suffix)
+/// Example: "render.####.exr" -> ("render.", Hashes{4}, ".exr")
+pub fn parse_padding_pattern(filename: &str) -> (String, PaddingPattern, String) {
+    // Try printf-style first: %0Nd or %Nd (%0Nx for hex)
+    if let Some(pos) = filename.find('%') {
+        let rest = &filename[pos + 1..];
+        let mut chars = rest.chars().peekable();
+
+        // Skip leading zero
+        let has_zero = chars.peek() == Some(&'0');
+        if has_zero {
+            chars.next();
+        }
+
+        // Parse width
+        let mut width_str = String::new();
+        while let Some(&c) = chars.peek() {
+            if c.is_ascii_digit() {
+                width_str.push(c);
+                chars.next();
+            } else {
+                break;
+            }
+        }
+
+        // Check for 'd' / 'x'
+        let conversion = chars.next();
+        if matches!(conversion, Some('d') | Some('x')) {
+            let width = width_str.parse::<usize>().unwrap_or(1);
+            let prefix = filename[..pos].to_string();
+            let consumed = 1 + if has_zero { 1 } else { 0 } + width_str.len() + 1; // % + 0? + digits + d
+            let suffix = filename[pos + consumed..].to_string();
+            let pattern = if conversion == Some('x') {
+                PaddingPattern::Hex { width }
+            } else {
+                PaddingPattern::Printf { width }
+            };
+            return (prefix, pattern, suffix);
+        }
+    }
+
+    // Try hash-style: ####
+    if let Some(start) = filename.find('#') {
+        let mut count = 0;
+        for c in filename[start..].chars() {
+            if c == '#' {
+                count += 1;
+            } else {
+                break;
+            }
+        }
+        if count > 0 {
+            let prefix = filename[..start].to_string();
+            let suffix = filename[start + count..].to_string();
+            return (prefix, PaddingPattern::Hashes { count }, suffix);
+        }
+    }
+
+    // Try @-style
+    if let Some(pos) = filename.find('@') {
+        let prefix = filename[..pos].to_string();
+        let suffix = filename[pos + 1..].to_string();
+        return (prefix, PaddingPattern::At, suffix);
+    }
+
+    // No pattern - insert before extension
+    if let Some(dot_pos) = filename.rfind('.') {
+        let prefix = format!("{}.", &filename[..dot_pos]);
+        let suffix = filename[dot_pos..].to_string();
+        (prefix, PaddingPattern::None, suffix)
+    } else {
+        (
+            format!("{}.", filename),
+            PaddingPattern::None,
+            String::new(),
+        )
+    }
+}
+
+/// Split a sequence file mask at its frame field.
+///
+/// Accepts the `*` masks built by [`detect_sequence`] (digits padded to
+/// `padding`, 0 = unpadded) and the exporter's patterns in the file name:
+/// `#` runs, `%0Nd` and `%0Nx`. `@` is left alone (video / animation frame
+/// suffix), as is a `#` with no extension after it (`doc.psd#3` layer,
+/// `logo.svg#WxH` size). Returns `None` for a plain file.
+pub fn split_frame_mask(mask: &str, padding: usize) -> Option<(String, PaddingPattern, String)> {
+    if let Some((prefix, suffix)) = mask.split_once('*') {
+        let pattern = PaddingPattern::Printf { width: padding };
+        return Some((prefix.to_string(), pattern, suffix.to_string()));
+    }
+    // Only the file name may carry a pattern (`%` / `#` are legal in dirs)
+    let name_start = mask.rfind(['/', '\\']).map_or(0, |i| i + 1);
+    let (prefix, pattern, suffix) = parse_padding_pattern(&mask[name_start..]);
+    match pattern {
+        PaddingPattern::At | PaddingPattern::None => None,
+        PaddingPattern::Hashes { .. } if !suffix.contains('.') => None,
+        pattern => Some((
+            format!("{}{}", &mask[..name_start], prefix),
+            pattern,
+            suffix,
+        )),
+    }
+}
+
+/// Detect the image sequence a single frame belongs to.
+///
+/// Scans the frame's directory for siblings sharing the same prefix and
+/// extension (see [`split_frame_field`]) and returns `(mask, start, end,
+/// padding)`, where `mask` has `*` in place of the frame field
+/// (`render.*.exr`).
+///
+/// - A lone numbered file (`ref.0001.jpg`) is a still, not a sequence: at
+///   least two frames are needed.
+/// - Gaps are allowed: `start..=end` spans the lowest/highest frame found.
+/// - Padding is the shortest frame field seen, so unpadded sequences
+///   (`f.9.png`, `f.10.png`) resolve as padding 1 and padded ones keep theirs.
+/// - Video containers and names without a frame field return `None`.
+pub fn detect_sequence(path: &Path) -> Option<(String, i32, i32, usize)> {
+    if is_video(path) {
+        return None;
+    }
+    let file_name = path.file_name()?.to_str()?;
+    let (prefix, digits, suffix) = split_frame_field(file_name)?;
+    let own_frame: i32 = digits.parse().ok()?;
+
+    let parent = path.parent().filter(|p| !p.as_os_str().is_empty());
+    let dir = parent.unwrap_or(Path::new("."));
+
+    let (mut start, mut end, mut padding) = (own_frame, own_frame, digits.len());
+    let mut frames = 0;
+    if let Ok(entries) = std::fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let Some(name) = name.to_str() else { continue };
+            let Some((p, d, s)) = split_frame_field(name) else {
+                continue;
+            };
+            if p != prefix || s != suffix {
+                continue;
+            }
+            let Ok(frame) = d.parse::<i32>() else {
+                continue;
+            };
+            frames += 1;
+            start = start.min(frame);
+            end = end.max(frame);
+            padding = padding.min(d.len());
+        }
+    }
+    if frames < 2 {
+        return None;
+    }
+
+    let mask = match parent {
+        Some(p) => format!("{}/{}*{}", p.to_string_lossy(), prefix, suffix),
+        None => format!("{}*{}", prefix, suffix),
+    };
+    Some((mask, start, end, padding))
+}
+
+/// Collapse loose files to one path per sequence group.
+///
+/// Files sharing a directory, the prefix before their frame field and the
+/// extension (`ref_01.jpg`, `ref_02.jpg`, see [`split_frame_field`]) form one group, represented by its
+/// lowest frame so [`detect_sequence`] can resolve the whole range. Videos
+/// and names without a frame field stand alone. Groups keep the input
+/// order of their first member.
+pub fn group_by_prefix(paths: &[PathBuf]) -> Vec<PathBuf> {
+    let mut out: Vec<(PathBuf, i64)> = Vec::new();
+    let mut groups: HashMap<(PathBuf, String, String), usize> = HashMap::new();
+    for path in paths {
+        let field = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .filter(|_| !is_video(path))
+            .and_then(split_frame_field);
+        let Some((prefix, digits, suffix)) = field else {
+            out.push((path.clone(), 0));
+            continue;
+        };
+        let frame: i64 = digits.parse().unwrap_or(i64::MAX);
+        let key = (
+            path.parent().map(Path::to_path_buf).unwrap_or_default(),
+            prefix.to_string(),
+            suffix.to_string(),
+        );
+        match groups.get(&key) {
+            Some(&i) if frame < out[i].1 => out[i] = (path.clone(), frame),
+            Some(_) => {}
+            None => {
+                groups.insert(key, out.len());
+                out.push((path.clone(), frame));
+            }
+        }
+    }
+    out.into_iter().map(|(path, _)| path).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_frame_field() {
+        assert_eq!(
+            split_frame_field("shot010_v003.0042.exr"),
+            Some(("shot010_v003.", "0042", ".exr"))
+        );
+        assert_eq!(
+            split_frame_field("frame7.png"),
+            Some(("frame", "7", ".png"))
+        );
+        assert_eq!(split_frame_field("plate.exr"), None);
+        assert_eq!(split_frame_field("v2_final.exr"), None);
+    }
+
+    #[test]
+    fn test_detect_sequence_gaps_and_fields() {
+        let dir = std::env::temp_dir().join(format!("playa_seq_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in [
+            "render.0001.exr",
+            "render.0002.exr",
+            "render.0005.EXR",
+            "render.v2.0001.exr",
+            "render.v2.0099.exr",
+            "render.0003.png",
+            "take.9.tga",
+            "take.10.tga",
+            "ref.0001.jpg",
+        ] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        // `.EXR` is another sequence: the mask couldn't resolve its name
+        let (mask, start, end, padding) = detect_sequence(&dir.join("render.0002.exr")).unwrap();
+        assert!(mask.ends_with("/render.*.exr"));
+        assert_eq!((start, end, padding), (1, 2, 4));
+
+        // Lone numbered files stay stills
+        assert!(detect_sequence(&dir.join("render.0005.EXR")).is_none());
+        assert!(detect_sequence(&dir.join("ref.0001.jpg")).is_none());
+
+        let (_, start, end, _) = detect_sequence(&dir.join("render.v2.0001.exr")).unwrap();
+        assert_eq!((start, end), (1, 99));
+
+        let (mask, start, end, padding) = detect_sequence(&dir.join("take.10.tga")).unwrap();
+        assert!(mask.ends_with("/take.*.tga"));
+        assert_eq!((start, end, padding), (9, 10, 1));
+
+        assert!(detect_sequence(&dir.join("clip.mp4")).is_none());
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_split_frame_mask() {
+        let resolve = |mask: &str, padding: usize, frame: i32| {
+            let (prefix, pattern, suffix) = split_frame_mask(mask, padding)?;
+            Some(format!("{}{}{}", prefix, pattern.format(frame), suffix))
+        };
+        assert_eq!(resolve("shot_#.exr", 4, 7).as_deref(), Some("shot_7.exr"));
+        assert_eq!(
+            resolve("shot_#.exr", 4, 1234).as_deref(),
+            Some("shot_1234.exr")
+        );
+        assert_eq!(
+            resolve("shot_%05d.exr", 4, 7).as_deref(),
+            Some("shot_00007.exr")
+        );
+        assert_eq!(
+            resolve("shot_%04x.exr", 4, 255).as_deref(),
+            Some("shot_00ff.exr")
+        );
+        assert_eq!(resolve("shot.*.exr", 1, 1).as_deref(), Some("shot.1.exr"));
+        assert_eq!(resolve("shot.*.exr", 0, 12).as_deref(), Some("shot.12.exr"));
+        assert_eq!(
+            resolve("shot.*.exr", 4, -5).as_deref(),
+            Some("shot.-005.exr")
+        );
+        assert_eq!(
+            resolve("/jobs/100%_#1/plate.####.exr", 4, 1001).as_deref(),
+            Some("/jobs/100%_#1/plate.1001.exr")
+        );
+        assert_eq!(resolve("shot.1.exr", 4, 2), None);
+        assert_eq!(resolve("clip.mov@12", 4, 2), None);
+        assert_eq!(resolve("/mattes/city.psd#3", 4, 2), None);
+        assert_eq!(resolve("logo.svg#512x512", 4, 2), None);
+
+        // Tokens parse back to the same pattern
+        for name in ["a.%05d.exr", "a.###.exr", "a.%04x.exr", "a.@.exr"] {
+            let (prefix, pattern, suffix) = parse_padding_pattern(name);
+            assert_eq!(format!("{}{}{}", prefix, pattern.token(), suffix), name);
+        }
+    }
+
+    #[test]
+    fn test_group_by_prefix() {
+        let paths: Vec<PathBuf> = [
+            "plates/ref_03.jpg",
+            "plates/clip_001.mov",
+            "plates/ref_01.JPG",
+            "plates/hero.png",
+            "other/ref_02.jpg",
+            "plates/clip_002.mov",
+            "plates/ref_02.png",
+            "plates/ref_01.jpg",
+        ]
+        .iter()
+        .map(PathBuf::from)
+        .collect();
+        let grouped: Vec<String> = group_by_prefix(&paths)
+            .iter()
+            .map(|p| p.to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(
+            grouped,
+            [
+                "plates/ref_01.jpg",
+                "plates/clip_001.mov",
+                // Case counts (see split_frame_field)
+                "plates/ref_01.JPG",
+                "plates/hero.png",
+                "other/ref_02.jpg",
+                "plates/clip_002.mov",
+                "plates/ref_02.png",
+            ]
+        );
+    }
+}
@@ -23,3 +23,72 @@ pub struct DecodedRaster {
     pub width: usize,
     pub height: usize,
 }
+
+impl DecodedRaster {
+    /// Paste this raster with its top-left at `(x, y)` onto a transparent
+    /// `width` x `height` canvas, clipping whatever falls outside.
+    ///
+    /// Places an EXR data window inside its display window.
+    pub fn placed(self, x: i64, y: i64, width: usize, height: usize) -> Self {
+        fn paste<T: Copy + Default>(
+            src: &[T],
+            (sw, sh): (usize, usize),
+            (x, y): (i64, i64),
+            (w, h): (usize, usize),
+        ) -> Vec<T> {
+            let mut out = vec![T::default(); w * h * 4];
+            let x0 = x.max(0);
+            let x1 = (x + sw as i64).min(w as i64);
+            if x1 <= x0 {
+                return out;
+            }
+            let span = (x1 - x0) as usize * 4;
+            for row in 0..sh as i64 {
+                let ty = y + row;
+                if ty < 0 || ty >= h as i64 {
+                    continue;
+                }
+                let s = (row as usize * sw + (x0 - x) as usize) * 4;
+                let d = (ty as usize * w + x0 as usize) * 4;
+                out[d..d + span].copy_from_slice(&src[s..s + span]);
+            }
+            out
+        }
+
+        let src = (self.width, self.height);
+        let buffer = match &self.buffer {
+            RawPixelBuffer::U8(v) => RawPixelBuffer::U8(paste(v, src, (x, y), (width, height))),
+            RawPixelBuffer::F16(v) => RawPixelBuffer::F16(paste(v, src, (x, y), (width, height))),
+            RawPixelBuffer::F32(v) => RawPixelBuffer::F32(paste(v, src, (x, y), (width, height))),
+        };
+        Self {
+            buffer,
+            format: self.format,
+            width,
+            height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placed_offsets_and_clips() {
+        // 2x2 opaque raster, half of it past the canvas's left edge
+        let raster = DecodedRaster {
+            buffer: RawPixelBuffer::U8(vec![255; 2 * 2 * 4]),
+            format: RawPixelFormat::Rgba8,
+            width: 2,
+            height: 2,
+        };
+        let placed = raster.placed(-1, 1, 3, 3);
+        assert_eq!((placed.width, placed.height), (3, 3));
+        let RawPixelBuffer::U8(px) = placed.buffer else {
+            panic!("format kept");
+        };
+        let alpha: Vec<u8> = px.chunks(4).map(|p| p[3]).collect();
+        assert_eq!(alpha, vec![0, 0, 0, 255, 0, 0, 255, 0, 0]);
+    }
+}
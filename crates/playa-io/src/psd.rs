@@ -0,0 +1,1080 @@
+//! Layered Photoshop (PSD) reader for matte-painting deliveries.
+//!
+//! Parsed straight from the file (no external deps): document size, the merged
+//! composite, and every layer record with its bounds, opacity, visibility,
+//! blend key, name, enclosing group and clipping base. RGB documents at 8 or
+//! 16 bits with raw or RLE (PackBits) channel data are supported; pixels are
+//! returned as straight-alpha RGBA8 (16-bit is reduced to 8). PSB (large
+//! document) files, non-RGB color modes and ZIP-compressed channels are
+//! rejected with `UnsupportedFormat`.
+//!
+//! Single layers are addressed as `doc.psd#<index>` (index in file order,
+//! bottom layer first), so a layer decodes through the regular dispatcher like
+//! any other still. Layer records are parsed once per file (see [`parsed`]);
+//! decoding a layer then reads only that layer's channel data. A clipped
+//! layer decodes with its base layer's transparency already applied.
+
+use log::trace;
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::byte_reader::{ByteReader, Endian};
+use crate::dispatch::AttrKv;
+use crate::error::IoError;
+use crate::pixel::{DecodedRaster, RawPixelBuffer, RawPixelFormat};
+
+const SIGNATURE: &[u8; 4] = b"8BPS";
+const COLOR_MODE_RGB: u16 = 3;
+
+const COMPRESSION_RAW: u16 = 0;
+const COMPRESSION_RLE: u16 = 1;
+
+/// Largest PSD (version 1) canvas side; bigger documents are PSB.
+const MAX_DIMENSION: u32 = 30_000;
+/// Most output bytes a PackBits stream can produce per input byte (a 2-byte
+/// repeat run expands to 128).
+const PACKBITS_MAX_EXPANSION: usize = 64;
+
+/// Channel ids of a layer record (user masks are -2 / -3 and ignored).
+const CHANNEL_ALPHA: i16 = -1;
+
+/// Section divider types of the `lsct` block.
+const SECTION_OPEN_FOLDER: u32 = 1;
+const SECTION_CLOSED_FOLDER: u32 = 2;
+const SECTION_DIVIDER: u32 = 3;
+
+/// Additional layer info keys of adjustment and fill layers (no pixels of their own).
+const ADJUSTMENT_KEYS: &[&[u8; 4]] = &[
+    b"levl", b"curv", b"brit", b"blnc", b"hue ", b"hue2", b"selc", b"mixr", b"grdm", b"phfl",
+    b"expA", b"vibA", b"blwh", b"clrL", b"thrs", b"post", b"nvrt", b"SoCo", b"GdFl", b"PtFl",
+];
+
+/// Additional layer info keys of embedded / linked smart objects.
+const SMART_OBJECT_KEYS: &[&[u8; 4]] = &[b"SoLd", b"PlLd", b"SoLE"];
+
+/// Blend key of pass-through groups (children blend straight into the layers below)
+pub const PASS_THROUGH: &str = "pass";
+
+/// Parsed documents kept by [`parsed`]; records are small, pixels aren't kept.
+const MAX_PARSED: usize = 16;
+
+/// What a layer record holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PsdLayerKind {
+    /// Pixel layer (text and shape layers also carry a rasterized copy)
+    Raster,
+    /// Group (folder) record, stored above its children
+    Group,
+    /// Bounding section divider closing a group, stored below its children
+    GroupEnd,
+    /// Adjustment or fill layer
+    Adjustment,
+    /// Embedded or linked smart object
+    SmartObject,
+}
+
+/// One layer record.
+#[derive(Debug, Clone)]
+pub struct PsdLayer {
+    /// Position in file order (0 = bottom); the `#index` of [`layer_path`]
+    pub index: usize,
+    pub name: String,
+    pub kind: PsdLayerKind,
+    /// Bounds in document pixels (may extend past the canvas)
+    pub left: i32,
+    pub top: i32,
+    pub width: u32,
+    pub height: u32,
+    /// Layer opacity, 0-255
+    pub opacity: u8,
+    pub visible: bool,
+    /// Photoshop blend mode key, e.g. `norm`, `mul `, `scrn`; `pass` for
+    /// pass-through groups
+    pub blend_key: String,
+    /// Index of the enclosing [`PsdLayerKind::Group`] record (for a
+    /// [`PsdLayerKind::GroupEnd`], the group it closes)
+    pub group: Option<usize>,
+    /// Clipped to the layer below (clipping mask)
+    pub clipped: bool,
+    /// Raster layer whose transparency clips this one. `None` when not
+    /// clipped, or when the base is a group or adjustment layer.
+    pub clip_base: Option<usize>,
+}
+
+/// Document structure: canvas size and layers in file order (bottom first).
+#[derive(Debug, Clone)]
+pub struct PsdDocument {
+    pub width: u32,
+    pub height: u32,
+    pub depth: u16,
+    pub channels: u16,
+    pub layers: Vec<PsdLayer>,
+}
+
+impl PsdDocument {
+    /// Nearest group enclosing `layer` that isn't pass-through (`None` = the
+    /// document root), with the layer's visibility and 0-1 opacity after
+    /// folding in the pass-through groups in between.
+    pub fn owner(&self, layer: &PsdLayer) -> (Option<usize>, bool, f32) {
+        let (mut visible, mut opacity) = (layer.visible, layer.opacity as f32 / 255.0);
+        let mut group = layer.group;
+        while let Some(g) = group.and_then(|i| self.layers.get(i)) {
+            if g.blend_key != PASS_THROUGH {
+                break;
+            }
+            visible &= g.visible;
+            opacity *= g.opacity as f32 / 255.0;
+            group = g.group;
+        }
+        (group, visible, opacity)
+    }
+}
+
+/// Big-endian reader over the raw file bytes.
+fn cursor(data: &[u8]) -> ByteReader<'_> {
+    ByteReader::new(data, Endian::Big, "PSD")
+}
+
+/// Length-prefixed (u32) block as its own cursor.
+fn section<'a>(c: &mut ByteReader<'a>) -> Result<ByteReader<'a>, IoError> {
+    let len = c.u32()? as usize;
+    Ok(c.sub(len)?)
+}
+
+/// File header fields needed for decoding.
+struct Header {
+    width: u32,
+    height: u32,
+    depth: u16,
+    channels: u16,
+}
+
+/// The three sections after the header that matter here.
+struct Sections<'a> {
+    header: Header,
+    layer_mask: ByteReader<'a>,
+    image_data: ByteReader<'a>,
+}
+
+fn split_sections(data: &[u8]) -> Result<Sections<'_>, IoError> {
+    let mut c = cursor(data);
+    if &c.bytes::<4>()? != SIGNATURE {
+        return Err(IoError::UnsupportedFormat(
+            "Not a Photoshop file (bad signature)".to_string(),
+        ));
+    }
+    let version = c.u16()?;
+    if version != 1 {
+        return Err(IoError::UnsupportedFormat(format!(
+            "PSD version {version} (PSB large documents) not supported"
+        )));
+    }
+    c.take(6)?; // reserved
+    let channels = c.u16()?;
+    let height = c.u32()?;
+    let width = c.u32()?;
+    let depth = c.u16()?;
+    let mode = c.u16()?;
+    if mode != COLOR_MODE_RGB {
+        return Err(IoError::UnsupportedFormat(format!(
+            "PSD color mode {mode} not supported (RGB only)"
+        )));
+    }
+    if depth != 8 && depth != 16 {
+        return Err(IoError::UnsupportedFormat(format!(
+            "PSD bit depth {depth} not supported (8/16 only)"
+        )));
+    }
+    if !(1..=MAX_DIMENSION).contains(&width) || !(1..=MAX_DIMENSION).contains(&height) {
+        return Err(IoError::LoadError(format!(
+            "PSD canvas {width}x{height} out of range (1..={MAX_DIMENSION})"
+        )));
+    }
+
+    section(&mut c)?; // color mode data
+    section(&mut c)?; // image resources
+    let layer_mask = section(&mut c)?;
+    Ok(Sections {
+        header: Header {
+            width,
+            height,
+            depth,
+            channels,
+        },
+        layer_mask,
+        image_data: c,
+    })
+}
+
+/// Per-channel record of a layer: id and where its image data sits in the file.
+#[derive(Debug, Clone, Copy)]
+struct ChannelInfo {
+    id: i16,
+    offset: usize,
+    len: usize,
+}
+
+/// Layer record plus the channel table needed to read its pixels.
+struct LayerRecord {
+    layer: PsdLayer,
+    channels: Vec<ChannelInfo>,
+}
+
+/// Parsed document: layer records plus each layer's channel table.
+#[derive(Debug)]
+struct ParsedPsd {
+    doc: PsdDocument,
+    channels: Vec<Vec<ChannelInfo>>,
+}
+
+fn parse_layer_record(c: &mut ByteReader, index: usize) -> Result<LayerRecord, IoError> {
+    let top = c.i32()?;
+    let left = c.i32()?;
+    let bottom = c.i32()?;
+    let right = c.i32()?;
+    let channel_count = c.u16()?;
+    let mut channels = Vec::with_capacity(channel_count as usize);
+    for _ in 0..channel_count {
+        let id = c.i16()?;
+        let len = c.u32()? as usize;
+        channels.push(ChannelInfo { id, offset: 0, len });
+    }
+    c.take(4)?; // "8BIM"
+    let mut blend_key = String::from_utf8_lossy(c.take(4)?).into_owned();
+    let opacity = c.u8()?;
+    let clipping = c.u8()?;
+    let flags = c.u8()?;
+    c.u8()?; // filler
+
+    let mut extra = section(c)?;
+    section(&mut extra)?; // layer mask / adjustment layer data
+    section(&mut extra)?; // blending ranges
+    let name_len = extra.u8()? as usize;
+    let mut name = String::from_utf8_lossy(extra.take(name_len)?).into_owned();
+    // Pascal string padded to a multiple of 4 (length byte included)
+    let padded = (name_len + 1).div_ceil(4) * 4;
+    extra.take(padded - name_len - 1)?;
+
+    let mut kind = PsdLayerKind::Raster;
+    while extra.remaining() >= 12 {
+        let sig = extra.bytes::<4>()?;
+        if &sig != b"8BIM" && &sig != b"8B64" {
+            break;
+        }
+        let key = extra.bytes::<4>()?;
+        let mut block = section(&mut extra)?;
+        match &key {
+            b"luni" => {
+                // Unicode name (UTF-16BE) supersedes the legacy Pascal name
+                let chars = block.u32()? as usize;
+                if chars.saturating_mul(2) > block.remaining() {
+                    return Err(IoError::LoadError(format!(
+                        "PSD layer {index}: unicode name longer than its block"
+                    )));
+                }
+                let units: Vec<u16> = (0..chars).map(|_| block.u16()).collect::<Result<_, _>>()?;
+                name = String::from_utf16_lossy(&units)
+                    .trim_end_matches('\0')
+                    .to_string();
+            }
+            b"lsct" | b"lsdk" => {
+                match block.u32()? {
+                    SECTION_OPEN_FOLDER | SECTION_CLOSED_FOLDER => kind = PsdLayerKind::Group,
+                    SECTION_DIVIDER => kind = PsdLayerKind::GroupEnd,
+                    _ => {}
+                }
+                // Optional blend key; pass-through groups keep `pass` here
+                if block.remaining() >= 8 && &block.bytes::<4>()? == b"8BIM" {
+                    blend_key = String::from_utf8_lossy(&block.bytes::<4>()?).into_owned();
+                }
+            }
+            k if SMART_OBJECT_KEYS.contains(&k) => kind = PsdLayerKind::SmartObject,
+            k if ADJUSTMENT_KEYS.contains(&k) && kind == PsdLayerKind::Raster => {
+                kind = PsdLayerKind::Adjustment
+            }
+            _ => {}
+        }
+    }
+
+    // Extents in i64: raw bounds near i32::MIN/MAX would overflow
+    let extent = |lo: i32, hi: i32| (hi as i64 - lo as i64).clamp(0, u32::MAX as i64) as u32;
+    Ok(LayerRecord {
+        layer: PsdLayer {
+            index,
+            name,
+            kind,
+            left,
+            top,
+            width: extent(left, right),
+            height: extent(top, bottom),
+            opacity,
+            visible: flags & 0x02 == 0,
+            blend_key,
+            group: None,
+            clipped: clipping != 0,
+            clip_base: None,
+        },
+        channels,
+    })
+}
+
+/// Parse the layer info block: records, then where each channel's data sits.
+fn parse_layer_info(c: &mut ByteReader) -> Result<Vec<LayerRecord>, IoError> {
+    if c.remaining() < 2 {
+        return Ok(Vec::new());
+    }
+    // Negative count: first alpha channel holds the merged transparency
+    let count = c.i16()?.unsigned_abs() as usize;
+    let mut records = (0..count)
+        .map(|i| parse_layer_record(c, i))
+        .collect::<Result<Vec<_>, _>>()?;
+    for ch in records.iter_mut().flat_map(|r| &mut r.channels) {
+        ch.offset = c.abs_pos();
+        c.take(ch.len)?;
+    }
+    Ok(records)
+}
+
+/// Fill in each layer's enclosing group and clipping base.
+///
+/// Records run bottom to top: a group's divider comes first, then its
+/// children, then the group record. A clipping group is a base layer and the
+/// clipped layers directly above it.
+fn resolve_structure(layers: &mut [PsdLayer]) {
+    let mut open = Vec::new();
+    for i in (0..layers.len()).rev() {
+        layers[i].group = match layers[i].kind {
+            PsdLayerKind::GroupEnd => open.pop(),
+            _ => open.last().copied(),
+        };
+        if layers[i].kind == PsdLayerKind::Group {
+            open.push(i);
+        }
+    }
+
+    let mut base = None;
+    for layer in layers.iter_mut() {
+        if layer.clipped {
+            layer.clip_base = base;
+        } else {
+            base = (layer.kind == PsdLayerKind::Raster).then_some(layer.index);
+        }
+    }
+}
+
+/// Decode `planes` consecutive channel planes of `w x h` to 8-bit samples.
+///
+/// RLE data starts with the byte counts of every row of every plane. Sizes
+/// are checked against the bytes left in `c` before anything is allocated.
+fn decode_plane(
+    c: &mut ByteReader,
+    compression: u16,
+    w: usize,
+    h: usize,
+    depth: u16,
+    planes: usize,
+) -> Result<Vec<u8>, IoError> {
+    let bytes_per = (depth / 8) as usize;
+    let too_short = || {
+        IoError::LoadError(format!(
+            "PSD channel data too short for {w}x{h} ({planes} plane(s), {} bytes)",
+            c.remaining()
+        ))
+    };
+    let row_len = w.checked_mul(bytes_per).ok_or_else(too_short)?;
+    let rows = h.checked_mul(planes).ok_or_else(too_short)?;
+    let total = row_len.checked_mul(rows).ok_or_else(too_short)?;
+    let raw = match compression {
+        COMPRESSION_RAW => c.take(total)?.to_vec(),
+        COMPRESSION_RLE => {
+            if rows.saturating_mul(2) > c.remaining()
+                || total > c.remaining().saturating_mul(PACKBITS_MAX_EXPANSION)
+            {
+                return Err(too_short());
+            }
+            let counts = (0..rows)
+                .map(|_| c.u16().map(|n| n as usize))
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut out = Vec::with_capacity(total);
+            for n in counts {
+                let start = out.len();
+                unpack_bits(c.take(n)?, &mut out);
+                out.resize(start + row_len, 0);
+            }
+            out
+        }
+        other => {
+            return Err(IoError::UnsupportedFormat(format!(
+                "PSD channel compression {other} (ZIP) not supported"
+            )));
+        }
+    };
+    // 16-bit big-endian: keep the high byte
+    Ok(raw.into_iter().step_by(bytes_per).collect())
+}
+
+/// PackBits decompression (one row), appended to `out`.
+fn unpack_bits(mut src: &[u8], out: &mut Vec<u8>) {
+    while let Some((&n, rest)) = src.split_first() {
+        let n = n as i8;
+        if n >= 0 {
+            let len = (n as usize + 1).min(rest.len());
+            out.extend_from_slice(&rest[..len]);
+            src = &rest[len..];
+        } else if n != -128 {
+            let Some((&v, rest)) = rest.split_first() else {
+                break;
+            };
+            out.extend(std::iter::repeat_n(v, 1 - n as isize as usize));
+            src = rest;
+        } else {
+            src = rest;
+        }
+    }
+}
+
+fn parse_document(data: &[u8]) -> Result<ParsedPsd, IoError> {
+    let Sections {
+        header,
+        mut layer_mask,
+        ..
+    } = split_sections(data)?;
+    let mut records = if layer_mask.remaining() >= 4 {
+        parse_layer_info(&mut section(&mut layer_mask)?)?
+    } else {
+        Vec::new()
+    };
+
+    // 16-bit documents keep their layers in the global "Lr16" block instead
+    if records.is_empty() && layer_mask.remaining() >= 4 {
+        section(&mut layer_mask)?; // global layer mask info
+        while layer_mask.remaining() >= 12 {
+            let sig = layer_mask.bytes::<4>()?;
+            let key = layer_mask.bytes::<4>()?;
+            let mut block = section(&mut layer_mask)?;
+            if &sig == b"8BIM" && &key == b"Lr16" {
+                records = parse_layer_info(&mut block)?;
+                break;
+            }
+        }
+    }
+
+    let (mut layers, channels): (Vec<_>, Vec<_>) =
+        records.into_iter().map(|r| (r.layer, r.channels)).unzip();
+    resolve_structure(&mut layers);
+    Ok(ParsedPsd {
+        doc: PsdDocument {
+            width: header.width,
+            height: header.height,
+            depth: header.depth,
+            channels: header.channels,
+            layers,
+        },
+        channels,
+    })
+}
+
+/// Decode one channel plane of `layer`, reading its data through `read`.
+fn decode_channel(
+    layer: &PsdLayer,
+    ch: &ChannelInfo,
+    depth: u16,
+    read: &mut impl FnMut(usize, usize) -> Result<Vec<u8>, IoError>,
+) -> Result<Vec<u8>, IoError> {
+    let bytes = read(ch.offset, ch.len)?;
+    let mut plane = cursor(&bytes);
+    let compression = plane.u16()?;
+    let (w, h) = (layer.width as usize, layer.height as usize);
+    decode_plane(&mut plane, compression, w, h, depth, 1).map_err(|e| match e {
+        IoError::UnsupportedFormat(msg) => {
+            IoError::UnsupportedFormat(format!("layer '{}': {}", layer.name, msg))
+        }
+        other => other,
+    })
+}
+
+/// Straight-alpha RGBA8 pixels of layer `index`, channel data read through
+/// `read(offset, len)`. Empty for layers without pixels.
+///
+/// A clipped layer's alpha is multiplied by its base layer's alpha, so it
+/// shows only where the base is opaque.
+fn decode_layer(
+    parsed: &ParsedPsd,
+    index: usize,
+    mut read: impl FnMut(usize, usize) -> Result<Vec<u8>, IoError>,
+) -> Result<Vec<u8>, IoError> {
+    let depth = parsed.doc.depth;
+    let layer = &parsed.doc.layers[index];
+    let mut rgba = Vec::new();
+    if layer.width == 0 || layer.height == 0 {
+        return Ok(rgba);
+    }
+    for ch in &parsed.channels[index] {
+        let slot = match ch.id {
+            0..=2 => ch.id as usize,
+            CHANNEL_ALPHA => 3,
+            _ => continue, // user / vector masks
+        };
+        let samples = decode_channel(layer, ch, depth, &mut read)?;
+        // Allocated once a channel proved the bounds are backed by data
+        if rgba.is_empty() {
+            rgba = [0, 0, 0, 255].repeat(samples.len());
+        }
+        for (px, v) in rgba.chunks_exact_mut(4).zip(samples) {
+            px[slot] = v;
+        }
+    }
+
+    let Some(base_index) = layer.clip_base else {
+        return Ok(rgba);
+    };
+    let base = &parsed.doc.layers[base_index];
+    let base_alpha = match parsed.channels[base_index]
+        .iter()
+        .find(|ch| ch.id == CHANNEL_ALPHA)
+    {
+        Some(ch) if base.width > 0 && base.height > 0 => {
+            Some(decode_channel(base, ch, depth, &mut read)?)
+        }
+        _ => None,
+    };
+    let (w, bw, bh) = (layer.width as i64, base.width as i64, base.height as i64);
+    for (i, px) in rgba.chunks_exact_mut(4).enumerate() {
+        // Document position -> base layer pixel
+        let bx = layer.left as i64 + i as i64 % w - base.left as i64;
+        let by = layer.top as i64 + i as i64 / w - base.top as i64;
+        let a = if (0..bw).contains(&bx) && (0..bh).contains(&by) {
+            base_alpha
+                .as_ref()
+                .map_or(255, |alpha| alpha[(by * bw + bx) as usize])
+        } else {
+            0
+        };
+        px[3] = (px[3] as u32 * a as u32 / 255) as u8;
+    }
+    Ok(rgba)
+}
+
+/// Merged (flattened) image as RGBA8.
+fn decode_merged(data: &[u8]) -> Result<DecodedRaster, IoError> {
+    let Sections {
+        header,
+        mut image_data,
+        ..
+    } = split_sections(data)?;
+    let (w, h) = (header.width as usize, header.height as usize);
+    let planes = header.channels as usize;
+    let compression = image_data.u16()?;
+    let samples = decode_plane(&mut image_data, compression, w, h, header.depth, planes)?;
+
+    let plane = |i: usize| &samples[i * w * h..(i + 1) * w * h];
+    let mut rgba = [0, 0, 0, 255].repeat(w * h);
+    for slot in 0..planes.min(4) {
+        for (px, &v) in rgba.chunks_exact_mut(4).zip(plane(slot)) {
+            px[slot] = v;
+        }
+    }
+    Ok(DecodedRaster {
+        buffer: RawPixelBuffer::U8(rgba),
+        format: RawPixelFormat::Rgba8,
+        width: w,
+        height: h,
+    })
+}
+
+/// Split a `doc.psd#3` layer path into the file and layer index.
+///
+/// Paths without a numeric `#` suffix, or not ending in `.psd`, are returned
+/// unchanged with `None`.
+pub fn split_layer_path(path: &Path) -> (PathBuf, Option<usize>) {
+    let path_str = path.to_string_lossy();
+    if let Some(hash) = path_str.rfind('#') {
+        let (base, suffix) = (&path_str[..hash], &path_str[hash + 1..]);
+        if is_psd(Path::new(base))
+            && let Ok(index) = suffix.parse()
+        {
+            return (PathBuf::from(base), Some(index));
+        }
+    }
+    (path.to_path_buf(), None)
+}
+
+/// Path addressing layer `index` of the PSD at `path`.
+pub fn layer_path(path: &Path, index: usize) -> PathBuf {
+    PathBuf::from(format!("{}#{}", path.display(), index))
+}
+
+/// True for `.psd` files (case-insensitive), including `#layer` paths.
+pub fn is_psd(path: &Path) -> bool {
+    let path_str = path.to_string_lossy();
+    let base = match path_str.rfind('#') {
+        Some(hash) if path_str[hash + 1..].chars().all(|c| c.is_ascii_digit()) => &path_str[..hash],
+        _ => path_str.as_ref(),
+    };
+    Path::new(base)
+        .extension()
+        .and_then(|s| s.to_str())
+        .is_some_and(|s| s.eq_ignore_ascii_case("psd"))
+}
+
+fn read_file(path: &Path) -> Result<Vec<u8>, IoError> {
+    std::fs::read(path)
+        .map_err(|e| IoError::LoadError(format!("Failed to read {}: {}", path.display(), e)))
+}
+
+/// File size and modification time, to notice a PSD saved over since parsing.
+type FileStamp = (u64, Option<SystemTime>);
+
+/// Recently parsed documents, most recently used last.
+static PARSED: Mutex<Vec<(PathBuf, FileStamp, Arc<ParsedPsd>)>> = Mutex::new(Vec::new());
+
+/// Layer records of `file`, parsed on first use and reused while the file is
+/// unchanged, so importing a PSD and decoding each of its layers reads the
+/// records once.
+fn parsed(file: &Path) -> Result<Arc<ParsedPsd>, IoError> {
+    let meta = std::fs::metadata(file)
+        .map_err(|e| IoError::LoadError(format!("Failed to read {}: {}", file.display(), e)))?;
+    let stamp = (meta.len(), meta.modified().ok());
+    {
+        let mut cache = PARSED.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(pos) = cache.iter().position(|(p, s, _)| p == file && *s == stamp) {
+            let entry = cache.remove(pos);
+            let parsed = Arc::clone(&entry.2);
+            cache.push(entry);
+            return Ok(parsed);
+        }
+    }
+
+    let parsed = Arc::new(parse_document(&read_file(file)?)?);
+    let mut cache = PARSED.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|(p, ..)| p != file);
+    if cache.len() >= MAX_PARSED {
+        cache.remove(0);
+    }
+    cache.push((file.to_path_buf(), stamp, Arc::clone(&parsed)));
+    Ok(parsed)
+}
+
+/// Document structure (layer records without pixels).
+pub fn read_layers(path: &Path) -> Result<PsdDocument, IoError> {
+    let (file, _) = split_layer_path(path);
+    Ok(parsed(&file)?.doc.clone())
+}
+
+/// Header probe for a PSD or one of its layers (`doc.psd#3`).
+pub fn header(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
+    trace!("Reading PSD header: {}", path.display());
+    let (file, layer) = split_layer_path(path);
+    let parsed = parsed(&file)?;
+    let doc = &parsed.doc;
+    let (width, height) = match layer {
+        Some(index) => {
+            let l = find_layer(doc, index, &file)?;
+            (l.width, l.height)
+        }
+        None => (doc.width, doc.height),
+    };
+
+    let mut v = vec![
+        ("width".into(), AttrKv::UInt(width)),
+        ("height".into(), AttrKv::UInt(height)),
+        (
+            "format".into(),
+            AttrKv::Str(format!("PSD ({}-bit RGB)", doc.depth)),
+        ),
+        ("channels".into(), AttrKv::UInt(4)),
+        ("bit_depth".into(), AttrKv::UInt(doc.depth as u32)),
+        ("psd:layers".into(), AttrKv::UInt(doc.layers.len() as u32)),
+    ];
+    if let Some(index) = layer {
+        let l = find_layer(doc, index, &file)?;
+        v.push(("psd:layer".into(), AttrKv::Str(l.name.clone())));
+        v.push(("psd:blend".into(), AttrKv::Str(l.blend_key.clone())));
+    }
+    Ok(v)
+}
+
+/// Decode a PSD's merged image, or one layer's pixels for `doc.psd#3`.
+pub fn decode(path: &Path) -> Result<DecodedRaster, IoError> {
+    trace!("Loading PSD: {}", path.display());
+    let (file, layer) = split_layer_path(path);
+    let Some(index) = layer else {
+        return decode_merged(&read_file(&file)?);
+    };
+    let parsed = parsed(&file)?;
+    let l = find_layer(&parsed.doc, index, &file)?;
+
+    let read_err =
+        |e: std::io::Error| IoError::LoadError(format!("Failed to read {}: {}", file.display(), e));
+    let mut f = File::open(&file).map_err(read_err)?;
+    let rgba = decode_layer(&parsed, index, |offset, len| {
+        let mut bytes = Vec::new();
+        f.seek(SeekFrom::Start(offset as u64)).map_err(read_err)?;
+        (&mut f)
+            .take(len as u64)
+            .read_to_end(&mut bytes)
+            .map_err(read_err)?;
+        Ok(bytes)
+    })?;
+    if rgba.is_empty() {
+        return Err(IoError::LoadError(format!(
+            "PSD layer '{}' has no pixels",
+            l.name
+        )));
+    }
+    Ok(DecodedRaster {
+        buffer: RawPixelBuffer::U8(rgba),
+        format: RawPixelFormat::Rgba8,
+        width: l.width as usize,
+        height: l.height as usize,
+    })
+}
+
+fn find_layer<'a>(
+    doc: &'a PsdDocument,
+    index: usize,
+    file: &Path,
+) -> Result<&'a PsdLayer, IoError> {
+    doc.layers.get(index).ok_or_else(|| {
+        IoError::LoadError(format!(
+            "{} has no layer {} ({} layers)",
+            file.display(),
+            index,
+            doc.layers.len()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Layer for [`make_psd`]: name, (left, top, w, h), RGBA pixels, opacity,
+    /// blend key, visibility, clipping and `lsct` section type
+    struct TestLayer<'a> {
+        name: &'a str,
+        rect: (i32, i32, u32, u32),
+        rgba: Vec<u8>,
+        opacity: u8,
+        blend: &'a [u8; 4],
+        rle: bool,
+        hidden: bool,
+        clipped: bool,
+        section: Option<u32>,
+    }
+
+    /// Opaque-or-not raster layer with default settings
+    fn raster<'a>(name: &'a str, rect: (i32, i32, u32, u32), rgba: Vec<u8>) -> TestLayer<'a> {
+        TestLayer {
+            name,
+            rect,
+            rgba,
+            opacity: 255,
+            blend: b"norm",
+            rle: false,
+            hidden: false,
+            clipped: false,
+            section: None,
+        }
+    }
+
+    /// Group record (`section` 1) or its closing divider (3)
+    fn group_record<'a>(name: &'a str, section: u32, blend: &'a [u8; 4]) -> TestLayer<'a> {
+        TestLayer {
+            blend,
+            section: Some(section),
+            ..raster(name, (0, 0, 0, 0), Vec::new())
+        }
+    }
+
+    /// Channel data read from an in-memory file.
+    fn slice_reader(data: &[u8]) -> impl FnMut(usize, usize) -> Result<Vec<u8>, IoError> + '_ {
+        |offset, len| {
+            data.get(offset..offset + len)
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| IoError::LoadError("PSD truncated".to_string()))
+        }
+    }
+
+    /// PackBits-encode one row as literal runs.
+    fn pack_bits(row: &[u8]) -> Vec<u8> {
+        let mut out = Vec::new();
+        for chunk in row.chunks(128) {
+            out.push((chunk.len() - 1) as u8);
+            out.extend_from_slice(chunk);
+        }
+        out
+    }
+
+    fn channel_data(plane: &[u8], w: usize, h: usize, rle: bool) -> Vec<u8> {
+        let mut out = Vec::new();
+        if rle {
+            out.extend_from_slice(&COMPRESSION_RLE.to_be_bytes());
+            let rows: Vec<Vec<u8>> = plane.chunks(w.max(1)).take(h).map(pack_bits).collect();
+            for r in &rows {
+                out.extend_from_slice(&(r.len() as u16).to_be_bytes());
+            }
+            rows.iter().for_each(|r| out.extend_from_slice(r));
+        } else {
+            out.extend_from_slice(&COMPRESSION_RAW.to_be_bytes());
+            out.extend_from_slice(plane);
+        }
+        out
+    }
+
+    /// Minimal 8-bit RGB PSD with the given layers and a flat grey composite.
+    fn make_psd(width: u32, height: u32, layers: &[TestLayer]) -> Vec<u8> {
+        let mut records = Vec::new();
+        let mut pixels = Vec::new();
+        for l in layers {
+            let (left, top, w, h) = l.rect;
+            records.extend_from_slice(&top.to_be_bytes());
+            records.extend_from_slice(&left.to_be_bytes());
+            records.extend_from_slice(&(top + h as i32).to_be_bytes());
+            records.extend_from_slice(&(left + w as i32).to_be_bytes());
+            records.extend_from_slice(&4u16.to_be_bytes());
+            for (id, slot) in [(-1i16, 3usize), (0, 0), (1, 1), (2, 2)] {
+                let plane: Vec<u8> = l.rgba.chunks_exact(4).map(|p| p[slot]).collect();
+                let data = channel_data(&plane, w as usize, h as usize, l.rle);
+                records.extend_from_slice(&id.to_be_bytes());
+                records.extend_from_slice(&(data.len() as u32).to_be_bytes());
+                pixels.extend(data);
+            }
+            records.extend_from_slice(b"8BIM");
+            records.extend_from_slice(l.blend);
+            records.extend_from_slice(&[l.opacity, l.clipped as u8, (l.hidden as u8) << 1, 0]);
+            let mut extra = vec![0u8; 8]; // empty mask + blending ranges
+            extra.push(l.name.len() as u8);
+            extra.extend_from_slice(l.name.as_bytes());
+            while !extra.len().is_multiple_of(4) {
+                extra.push(0);
+            }
+            if let Some(section) = l.section {
+                extra.extend_from_slice(b"8BIMlsct");
+                extra.extend_from_slice(&12u32.to_be_bytes());
+                extra.extend_from_slice(&section.to_be_bytes());
+                extra.extend_from_slice(b"8BIM");
+                extra.extend_from_slice(l.blend);
+            }
+            records.extend_from_slice(&(extra.len() as u32).to_be_bytes());
+            records.extend(extra);
+        }
+
+        let mut layer_info = (layers.len() as i16).to_be_bytes().to_vec();
+        layer_info.extend(records);
+        layer_info.extend(pixels);
+        let mut layer_mask = (layer_info.len() as u32).to_be_bytes().to_vec();
+        layer_mask.extend(layer_info);
+        layer_mask.extend_from_slice(&0u32.to_be_bytes()); // global mask info
+
+        let mut data = SIGNATURE.to_vec();
+        data.extend_from_slice(&1u16.to_be_bytes());
+        data.extend_from_slice(&[0; 6]);
+        data.extend_from_slice(&3u16.to_be_bytes());
+        data.extend_from_slice(&height.to_be_bytes());
+        data.extend_from_slice(&width.to_be_bytes());
+        data.extend_from_slice(&8u16.to_be_bytes());
+        data.extend_from_slice(&COLOR_MODE_RGB.to_be_bytes());
+        data.extend_from_slice(&0u32.to_be_bytes()); // color mode data
+        data.extend_from_slice(&0u32.to_be_bytes()); // image resources
+        data.extend_from_slice(&(layer_mask.len() as u32).to_be_bytes());
+        data.extend(layer_mask);
+        data.extend_from_slice(&COMPRESSION_RAW.to_be_bytes());
+        data.extend(std::iter::repeat_n(128u8, (width * height * 3) as usize));
+        data
+    }
+
+    /// Two layers: opaque red background, half-transparent blue RLE patch
+    fn two_layer_psd() -> Vec<u8> {
+        let bg = raster("Background", (0, 0, 4, 4), [255, 0, 0, 255].repeat(16));
+        let patch = TestLayer {
+            opacity: 128,
+            blend: b"scrn",
+            rle: true,
+            ..raster("Sky patch", (2, 1, 2, 2), [0, 0, 255, 128].repeat(4))
+        };
+        make_psd(4, 4, &[bg, patch])
+    }
+
+    #[test]
+    fn test_parse_two_layer_psd() {
+        let doc = parse_document(&two_layer_psd()).expect("parse PSD").doc;
+        assert_eq!((doc.width, doc.height, doc.depth), (4, 4, 8));
+        assert_eq!(doc.layers.len(), 2);
+
+        let patch = &doc.layers[1];
+        assert_eq!(patch.name, "Sky patch");
+        assert_eq!(patch.kind, PsdLayerKind::Raster);
+        assert_eq!(
+            (patch.left, patch.top, patch.width, patch.height),
+            (2, 1, 2, 2)
+        );
+        assert_eq!((patch.opacity, patch.blend_key.as_str()), (128, "scrn"));
+        assert!(patch.visible);
+        assert_eq!(
+            (patch.group, patch.clipped, patch.clip_base),
+            (None, false, None)
+        );
+    }
+
+    #[test]
+    fn test_decode_layer_pixels() {
+        let data = two_layer_psd();
+        let parsed = parse_document(&data).unwrap();
+        let patch = decode_layer(&parsed, 1, slice_reader(&data)).unwrap();
+        assert_eq!(&patch[..4], &[0, 0, 255, 128]); // RLE channel
+        let bg = decode_layer(&parsed, 0, slice_reader(&data)).unwrap();
+        assert_eq!(&bg[..4], &[255, 0, 0, 255]); // raw channel
+
+        let merged = decode_merged(&data).unwrap();
+        assert_eq!((merged.width, merged.height), (4, 4));
+        let RawPixelBuffer::U8(px) = merged.buffer else {
+            panic!("expected U8 buffer");
+        };
+        assert_eq!(&px[..4], &[128, 128, 128, 255]);
+    }
+
+    #[test]
+    fn test_bogus_bounds_are_errors() {
+        // Huge layer bounds backed by a few bytes of RLE data
+        let mut layer = TestLayer {
+            rle: true,
+            ..raster("Huge", (0, 0, 1, 1), vec![0, 0, 0, 255])
+        };
+        let mut data = make_psd(4, 4, std::slice::from_ref(&layer));
+        // Layer record starts after header (26), two empty sections (8),
+        // layer/mask + layer info lengths (8) and the layer count (2)
+        let rec = 26 + 8 + 8 + 2;
+        data[rec + 8..rec + 12].copy_from_slice(&i32::MAX.to_be_bytes()); // bottom
+        data[rec + 12..rec + 16].copy_from_slice(&i32::MAX.to_be_bytes()); // right
+        data[rec..rec + 4].copy_from_slice(&i32::MIN.to_be_bytes()); // top
+        let parsed = parse_document(&data).unwrap();
+        assert_eq!(parsed.doc.layers[0].height, u32::MAX);
+        assert!(decode_layer(&parsed, 0, slice_reader(&data)).is_err());
+
+        // Raw data shorter than its bounds
+        layer.rle = false;
+        let mut data = make_psd(4, 4, std::slice::from_ref(&layer));
+        data[rec + 12..rec + 16].copy_from_slice(&100_000i32.to_be_bytes());
+        let parsed = parse_document(&data).unwrap();
+        assert!(decode_layer(&parsed, 0, slice_reader(&data)).is_err());
+
+        // Canvas past the PSD limit
+        let mut data = make_psd(4, 4, &[]);
+        data[14..18].copy_from_slice(&u32::MAX.to_be_bytes());
+        assert!(decode_merged(&data).is_err());
+    }
+
+    /// Bottom first: clip base, clipped layer, then a hidden multiply group
+    /// (divider, child, group record) inside a half-opacity pass-through group.
+    fn grouped_psd() -> Vec<u8> {
+        // Base alpha: left column opaque, top-right pixel transparent
+        let base = raster(
+            "Base",
+            (0, 0, 2, 2),
+            [[9, 9, 9, 255], [9, 9, 9, 0], [9, 9, 9, 255], [9, 9, 9, 255]].concat(),
+        );
+        // One pixel to the right of the base
+        let shade = TestLayer {
+            clipped: true,
+            ..raster("Shade", (1, 0, 2, 2), [0, 255, 0, 255].repeat(4))
+        };
+        let hidden = TestLayer {
+            hidden: true,
+            opacity: 128,
+            ..group_record("Trees", 1, b"mul ")
+        };
+        let outer = TestLayer {
+            opacity: 128,
+            ..group_record("Set", 1, b"pass")
+        };
+        make_psd(
+            4,
+            4,
+            &[
+                base,
+                shade,
+                group_record("</Set>", 3, b"pass"),
+                group_record("</Trees>", 3, b"mul "),
+                raster("Tree", (0, 0, 1, 1), vec![0, 0, 0, 255]),
+                hidden,
+                outer,
+            ],
+        )
+    }
+
+    #[test]
+    fn test_groups_and_clipping() {
+        let data = grouped_psd();
+        let parsed = parse_document(&data).unwrap();
+        let doc = &parsed.doc;
+        let kinds: Vec<_> = doc.layers.iter().map(|l| l.kind).collect();
+        use PsdLayerKind::*;
+        assert_eq!(
+            kinds,
+            [Raster, Raster, GroupEnd, GroupEnd, Raster, Group, Group]
+        );
+        let groups: Vec<_> = doc.layers.iter().map(|l| l.group).collect();
+        assert_eq!(
+            groups,
+            [None, None, Some(6), Some(5), Some(5), Some(6), None]
+        );
+
+        // Hidden group: its child's owner is the group comp, the group
+        // itself is hidden inside the pass-through parent, which folds in
+        let trees = &doc.layers[5];
+        assert_eq!(trees.blend_key, "mul ");
+        assert_eq!(doc.owner(&doc.layers[4]), (Some(5), true, 1.0));
+        let (owner, visible, opacity) = doc.owner(trees);
+        assert_eq!((owner, visible), (None, false));
+        assert!((opacity - (128.0 / 255.0) * (128.0 / 255.0)).abs() < 1e-6);
+
+        // Clipped layer: transparent where the base is, and outside it
+        assert!(doc.layers[1].clipped);
+        assert_eq!(doc.layers[1].clip_base, Some(0));
+        let shade = decode_layer(&parsed, 1, slice_reader(&data)).unwrap();
+        let alpha: Vec<u8> = shade.chunks_exact(4).map(|px| px[3]).collect();
+        assert_eq!(alpha, [0, 0, 255, 0]);
+        assert_eq!(&shade[8..11], &[0, 255, 0]);
+    }
+
+    #[test]
+    fn test_parsed_once_per_file() {
+        let dir = std::env::temp_dir().join(format!("playa_psd_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("matte.psd");
+        std::fs::write(&path, grouped_psd()).unwrap();
+
+        let first = parsed(&path).unwrap();
+        assert!(Arc::ptr_eq(&first, &parsed(&path).unwrap()));
+        let shade = decode(&layer_path(&path, 1)).unwrap();
+        let RawPixelBuffer::U8(px) = shade.buffer else {
+            panic!("expected U8 buffer");
+        };
+        assert_eq!(&px[8..12], &[0, 255, 0, 255]);
+
+        // Saved over: parsed again
+        std::fs::write(&path, two_layer_psd()).unwrap();
+        assert_eq!(read_layers(&path).unwrap().layers.len(), 2);
+        assert!(!Arc::ptr_eq(&first, &parsed(&path).unwrap()));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_layer_paths() {
+        let path = layer_path(Path::new("/mattes/city.psd"), 3);
+        assert_eq!(path, PathBuf::from("/mattes/city.psd#3"));
+        assert_eq!(
+            split_layer_path(&path),
+            (PathBuf::from("/mattes/city.psd"), Some(3))
+        );
+        assert!(is_psd(&path) && is_psd(Path::new("A.PSD")));
+        // Not a PSD: '#' stays part of the name
+        let png = Path::new("shot#2.png");
+        assert_eq!(split_layer_path(png), (png.to_path_buf(), None));
+        assert!(!is_psd(png));
+    }
+}
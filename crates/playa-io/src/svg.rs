@@ -0,0 +1,175 @@
+//! SVG rasterization (`resvg`) for logos and UI mockups.
+//!
+//! Vector files have no pixel size of their own, so the raster size travels in
+//! the path: `logo.svg#1920x1080` renders the document fitted into that box
+//! (aspect kept, centered, transparent margins). A bare `logo.svg` renders at
+//! the document's intrinsic size. Pixels are straight-alpha RGBA8.
+
+use log::trace;
+use resvg::{tiny_skia, usvg};
+use std::path::{Path, PathBuf};
+
+use crate::dispatch::AttrKv;
+use crate::error::IoError;
+use crate::pixel::{DecodedRaster, RawPixelBuffer, RawPixelFormat};
+
+/// Largest raster edge accepted from a path suffix (guards typos like `#19200x1080`).
+const MAX_EDGE: u32 = 16384;
+
+/// Split `logo.svg#1920x1080` into the file and the requested raster size.
+///
+/// Paths without a valid `#WxH` suffix are returned unchanged with `None`.
+pub fn split_sized_path(path: &Path) -> (PathBuf, Option<(u32, u32)>) {
+    let path_str = path.to_string_lossy();
+    if let Some(hash) = path_str.rfind('#')
+        && let Some((w, h)) = path_str[hash + 1..].split_once('x')
+        && let (Ok(w), Ok(h)) = (w.parse::<u32>(), h.parse::<u32>())
+        && (1..=MAX_EDGE).contains(&w)
+        && (1..=MAX_EDGE).contains(&h)
+    {
+        return (PathBuf::from(&path_str[..hash]), Some((w, h)));
+    }
+    (path.to_path_buf(), None)
+}
+
+/// Path rasterizing the SVG at `path` to `width x height`.
+pub fn sized_path(path: &Path, width: u32, height: u32) -> PathBuf {
+    PathBuf::from(format!("{}#{}x{}", path.display(), width, height))
+}
+
+/// Fit an intrinsic `size` into `bounds`, keeping the aspect ratio.
+pub fn fit_size(size: (u32, u32), bounds: (u32, u32)) -> (u32, u32) {
+    let scale =
+        (bounds.0 as f32 / size.0.max(1) as f32).min(bounds.1 as f32 / size.1.max(1) as f32);
+    (
+        ((size.0 as f32 * scale).round() as u32).max(1),
+        ((size.1 as f32 * scale).round() as u32).max(1),
+    )
+}
+
+fn parse_tree(file: &Path) -> Result<usvg::Tree, IoError> {
+    let data = std::fs::read(file)
+        .map_err(|e| IoError::LoadError(format!("Failed to read {}: {}", file.display(), e)))?;
+    let mut opt = usvg::Options {
+        resources_dir: file.parent().map(Path::to_path_buf),
+        ..Default::default()
+    };
+    opt.fontdb_mut().load_system_fonts();
+    usvg::Tree::from_data(&data, &opt)
+        .map_err(|e| IoError::Image(format!("SVG parse failed for {}: {}", file.display(), e)))
+}
+
+fn intrinsic_size(tree: &usvg::Tree) -> (u32, u32) {
+    let size = tree.size().to_int_size();
+    (size.width(), size.height())
+}
+
+/// Render `tree` fitted into `width x height`.
+fn render(tree: &usvg::Tree, width: u32, height: u32) -> Result<DecodedRaster, IoError> {
+    let mut pixmap = tiny_skia::Pixmap::new(width, height)
+        .ok_or_else(|| IoError::LoadError(format!("Invalid SVG raster size {width}x{height}")))?;
+
+    let size = tree.size();
+    let scale = (width as f32 / size.width()).min(height as f32 / size.height());
+    let tx = (width as f32 - size.width() * scale) / 2.0;
+    let ty = (height as f32 - size.height() * scale) / 2.0;
+    let transform = tiny_skia::Transform::from_row(scale, 0.0, 0.0, scale, tx, ty);
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+
+    // tiny-skia works premultiplied; frames carry straight alpha
+    let rgba = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|p| {
+            let c = p.demultiply();
+            [c.red(), c.green(), c.blue(), c.alpha()]
+        })
+        .collect();
+    Ok(DecodedRaster {
+        buffer: RawPixelBuffer::U8(rgba),
+        format: RawPixelFormat::Rgba8,
+        width: width as usize,
+        height: height as usize,
+    })
+}
+
+/// Header probe: raster size (requested or intrinsic) plus the document size.
+pub fn header(path: &Path) -> Result<Vec<(String, AttrKv)>, IoError> {
+    trace!("Reading SVG header: {}", path.display());
+    let (file, size) = split_sized_path(path);
+    let tree = parse_tree(&file)?;
+    let intrinsic = intrinsic_size(&tree);
+    let (width, height) = size.unwrap_or(intrinsic);
+    Ok(vec![
+        ("width".into(), AttrKv::UInt(width)),
+        ("height".into(), AttrKv::UInt(height)),
+        ("format".into(), AttrKv::Str("SVG".to_string())),
+        ("channels".into(), AttrKv::UInt(4)),
+        ("svg:width".into(), AttrKv::UInt(intrinsic.0)),
+        ("svg:height".into(), AttrKv::UInt(intrinsic.1)),
+    ])
+}
+
+/// Rasterize an SVG at its `#WxH` size, or its intrinsic size without one.
+pub fn decode(path: &Path) -> Result<DecodedRaster, IoError> {
+    trace!("Rasterizing SVG: {}", path.display());
+    let (file, size) = split_sized_path(path);
+    let tree = parse_tree(&file)?;
+    let (width, height) = size.unwrap_or_else(|| intrinsic_size(&tree));
+    render(&tree, width, height)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::media;
+
+    /// 100x50 document: opaque red left half, empty right half
+    const HALF_RED: &str = r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="50">
+        <rect x="0" y="0" width="50" height="50" fill="red"/>
+    </svg>"#;
+
+    fn pixel(r: &DecodedRaster, x: usize, y: usize) -> [u8; 4] {
+        let RawPixelBuffer::U8(px) = &r.buffer else {
+            panic!("expected U8 buffer");
+        };
+        let i = (y * r.width + x) * 4;
+        px[i..i + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn test_render_at_two_resolutions() {
+        let tree = usvg::Tree::from_str(HALF_RED, &usvg::Options::default()).unwrap();
+        assert_eq!(intrinsic_size(&tree), (100, 50));
+
+        for (w, h) in [(100, 50), (1000, 500)] {
+            let r = render(&tree, w, h).unwrap();
+            assert_eq!((r.width, r.height), (w as usize, h as usize));
+            // Edges stay crisp at any scale: solid inside, transparent outside
+            assert_eq!(pixel(&r, w as usize / 4, h as usize / 2), [255, 0, 0, 255]);
+            assert_eq!(pixel(&r, w as usize * 3 / 4, h as usize / 2)[3], 0);
+            assert_eq!(pixel(&r, w as usize / 2 - 1, 0), [255, 0, 0, 255]);
+            assert_eq!(pixel(&r, w as usize / 2, 0)[3], 0);
+        }
+
+        // Taller box: fitted and centered, margins transparent
+        let r = render(&tree, 100, 100).unwrap();
+        assert_eq!(pixel(&r, 10, 10)[3], 0);
+        assert_eq!(pixel(&r, 10, 50), [255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn test_sized_paths() {
+        let path = sized_path(Path::new("/gfx/logo.svg"), 1920, 1080);
+        assert_eq!(path, PathBuf::from("/gfx/logo.svg#1920x1080"));
+        assert_eq!(
+            split_sized_path(&path),
+            (PathBuf::from("/gfx/logo.svg"), Some((1920, 1080)))
+        );
+        assert!(media::is_svg(&path) && media::is_svg(Path::new("LOGO.SVG")));
+        let bad = Path::new("/gfx/logo.svg#0x10");
+        assert_eq!(split_sized_path(bad), (bad.to_path_buf(), None));
+        assert_eq!(fit_size((100, 50), (1920, 1080)), (1920, 960));
+        assert_eq!(fit_size((50, 100), (1920, 1080)), (540, 1080));
+    }
+}
@@ -5,6 +5,7 @@ use playa_ffmpeg as ffmpeg;
 use std::path::Path;
 use std::sync::Once;
 
+use super::peaks::{AudioPeaks, PeakAccumulator};
 use crate::error::IoError;
 use crate::pixel::{RawPixelBuffer, RawPixelFormat};
 
@@ -259,3 +260,105 @@ pub fn decode_frame(
         frame_num
     )))
 }
+
+/// Decode the best audio stream of `path` into a min/max peak envelope.
+///
+/// Returns `Ok(None)` when the file has no audio stream. Decodes the whole
+/// stream once; callers are expected to cache the result per source.
+pub fn decode_audio_peaks(path: &Path, bins_per_sec: f32) -> Result<Option<AudioPeaks>, IoError> {
+    init_ffmpeg_logging();
+
+    let mut ictx = ffmpeg::format::input(path)
+        .map_err(|e| IoError::LoadError(format!("Failed to open media: {}", e)))?;
+
+    let Some(stream) = ictx.streams().best(ffmpeg::media::Type::Audio) else {
+        return Ok(None);
+    };
+    let stream_idx = stream.index();
+
+    let decoder_ctx = ffmpeg::codec::context::Context::from_parameters(stream.parameters())
+        .map_err(|e| IoError::LoadError(format!("Failed to create decoder context: {}", e)))?;
+    let mut decoder = decoder_ctx
+        .decoder()
+        .audio()
+        .map_err(|e| IoError::LoadError(format!("Failed to create audio decoder: {}", e)))?;
+
+    let mut acc = PeakAccumulator::new(decoder.rate(), bins_per_sec);
+    let mut decoded = ffmpeg::util::frame::audio::Audio::empty();
+
+    for (stream, packet) in ictx.packets() {
+        if stream.index() != stream_idx {
+            continue;
+        }
+        if let Err(e) = decoder.send_packet(&packet) {
+            // Corrupt packets only leave a gap in the envelope
+            warn!("Audio packet skipped: {}", e);
+            continue;
+        }
+        while decoder.receive_frame(&mut decoded).is_ok() {
+            accumulate_frame(&decoded, &mut acc);
+        }
+    }
+    if decoder.send_eof().is_ok() {
+        while decoder.receive_frame(&mut decoded).is_ok() {
+            accumulate_frame(&decoded, &mut acc);
+        }
+    }
+
+    Ok(Some(acc.finish()))
+}
+
+/// Reduce one decoded audio frame (any packed/planar sample format) into `acc`.
+fn accumulate_frame(frame: &ffmpeg::util::frame::audio::Audio, acc: &mut PeakAccumulator) {
+    use ffmpeg::format::Sample;
+    match frame.format() {
+        Sample::U8(_) => accumulate_samples::<u8>(frame, acc, |s| (s as f32 - 128.0) / 128.0),
+        Sample::I16(_) => accumulate_samples::<i16>(frame, acc, |s| s as f32 / 32768.0),
+        Sample::I32(_) => accumulate_samples::<i32>(frame, acc, |s| s as f32 / 2_147_483_648.0),
+        Sample::F32(_) => accumulate_samples::<f32>(frame, acc, |s| s),
+        Sample::F64(_) => accumulate_samples::<f64>(frame, acc, |s| s as f32),
+        other => warn!("Unsupported audio sample format for peaks: {:?}", other),
+    }
+}
+
+fn accumulate_samples<T>(
+    frame: &ffmpeg::util::frame::audio::Audio,
+    acc: &mut PeakAccumulator,
+    to_f32: impl Fn(T) -> f32,
+) where
+    T: ffmpeg::util::frame::audio::Sample + Copy,
+{
+    let channels = frame.channels().max(1) as usize;
+    let samples = frame.samples();
+    if samples == 0 || frame.planes() == 0 {
+        return;
+    }
+
+    if frame.is_planar() {
+        let planes: Vec<&[T]> = (0..channels).map(|c| frame.plane::<T>(c)).collect();
+        for i in 0..samples {
+            let (mut lo, mut hi) = (f32::MAX, f32::MIN);
+            for plane in &planes {
+                let v = to_f32(plane[i]);
+                lo = lo.min(v);
+                hi = hi.max(v);
+            }
+            acc.push(lo, hi);
+        }
+    } else {
+        // Packed: all channels interleaved in plane 0. `plane::<T>` only covers
+        // `samples` elements, so view the raw bytes instead.
+        let bytes = frame.data(0);
+        let count = (samples * channels).min(bytes.len() / std::mem::size_of::<T>());
+        let interleaved = unsafe { std::slice::from_raw_parts(bytes.as_ptr() as *const T, count) };
+        for chunk in interleaved.chunks_exact(channels) {
+            let (mut lo, mut hi) = (f32::MAX, f32::MIN);
+            for &s in chunk {
+                let v = to_f32(s);
+                lo = lo.min(v);
+                hi = hi.max(v);
+            }
+            acc.push(lo, hi);
+        }
+    }
+}
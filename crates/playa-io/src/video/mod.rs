@@ -2,9 +2,12 @@
 
 #[cfg(feature = "ffmpeg")]
 mod ffmpeg_imp;
+mod peaks;
+
+pub use peaks::AudioPeaks;
 
 #[cfg(feature = "ffmpeg")]
-pub use ffmpeg_imp::{VideoMetadata, decode_frame, get_video_dimensions};
+pub use ffmpeg_imp::{VideoMetadata, decode_audio_peaks, decode_frame, get_video_dimensions};
 
 #[cfg(not(feature = "ffmpeg"))]
 mod stub;
@@ -13,4 +16,4 @@ mod stub;
 pub(crate) fn init_ffmpeg_logging() {}
 
 #[cfg(not(feature = "ffmpeg"))]
-pub use stub::{VideoMetadata, decode_frame, get_video_dimensions};
+pub use stub::{VideoMetadata, decode_audio_peaks, decode_frame, get_video_dimensions};
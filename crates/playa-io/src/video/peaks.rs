@@ -0,0 +1,173 @@
+//! Downsampled audio peak envelope for timeline waveform display.
+//!
+//! Decoding lives in the FFmpeg backend ([`decode_audio_peaks`](super::decode_audio_peaks));
+//! this module only holds the backend-independent envelope so the UI can
+//! resample it at any zoom without touching the file again.
+
+/// Min/max envelope of an audio stream, one bin per `1 / bins_per_sec` seconds.
+///
+/// Channels are mixed into a single envelope (min/max across all channels).
+/// Values are normalized samples in `-1.0..=1.0`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AudioPeaks {
+    /// Envelope resolution
+    pub bins_per_sec: f32,
+    /// Per-bin minimum sample
+    pub min: Vec<f32>,
+    /// Per-bin maximum sample
+    pub max: Vec<f32>,
+}
+
+impl AudioPeaks {
+    /// Stream length covered by the envelope
+    pub fn duration_secs(&self) -> f32 {
+        if self.bins_per_sec <= 0.0 {
+            return 0.0;
+        }
+        self.min.len() as f32 / self.bins_per_sec
+    }
+
+    /// Min/max over `[start_secs, end_secs)`; `None` if the span is outside the stream.
+    pub fn range(&self, start_secs: f32, end_secs: f32) -> Option<(f32, f32)> {
+        let len = self.min.len();
+        if len == 0 || self.bins_per_sec <= 0.0 {
+            return None;
+        }
+        let lo = (start_secs * self.bins_per_sec).floor().max(0.0) as usize;
+        // Always cover at least one bin so zoomed-in columns still show a sample
+        let hi = ((end_secs * self.bins_per_sec).ceil() as usize)
+            .max(lo + 1)
+            .min(len);
+        if lo >= hi {
+            return None;
+        }
+        let mn = self.min[lo..hi].iter().copied().fold(f32::MAX, f32::min);
+        let mx = self.max[lo..hi].iter().copied().fold(f32::MIN, f32::max);
+        Some((mn, mx))
+    }
+
+    /// Resample `[start_secs, end_secs)` into `columns` evenly spaced min/max pairs.
+    ///
+    /// Columns outside the stream are `None` (drawn as silence / skipped).
+    pub fn resample(
+        &self,
+        start_secs: f32,
+        end_secs: f32,
+        columns: usize,
+    ) -> Vec<Option<(f32, f32)>> {
+        if columns == 0 {
+            return Vec::new();
+        }
+        let step = (end_secs - start_secs) / columns as f32;
+        (0..columns)
+            .map(|i| {
+                let t0 = start_secs + step * i as f32;
+                self.range(t0, t0 + step)
+            })
+            .collect()
+    }
+}
+
+/// Streaming min/max reducer used by the decoder: feed one sample frame at a
+/// time (already reduced across channels), collect bins at the end.
+#[cfg_attr(not(feature = "ffmpeg"), allow(dead_code))]
+pub(crate) struct PeakAccumulator {
+    bins_per_sec: f32,
+    samples_per_bin: usize,
+    count: usize,
+    cur_min: f32,
+    cur_max: f32,
+    min: Vec<f32>,
+    max: Vec<f32>,
+}
+
+#[cfg_attr(not(feature = "ffmpeg"), allow(dead_code))]
+impl PeakAccumulator {
+    pub(crate) fn new(sample_rate: u32, bins_per_sec: f32) -> Self {
+        let samples_per_bin = (sample_rate as f32 / bins_per_sec.max(1.0))
+            .round()
+            .max(1.0) as usize;
+        Self {
+            bins_per_sec: sample_rate as f32 / samples_per_bin as f32,
+            samples_per_bin,
+            count: 0,
+            cur_min: f32::MAX,
+            cur_max: f32::MIN,
+            min: Vec::new(),
+            max: Vec::new(),
+        }
+    }
+
+    /// Add one sample frame given as its min/max across channels
+    pub(crate) fn push(&mut self, lo: f32, hi: f32) {
+        self.cur_min = self.cur_min.min(lo);
+        self.cur_max = self.cur_max.max(hi);
+        self.count += 1;
+        if self.count == self.samples_per_bin {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if self.count > 0 {
+            self.min.push(self.cur_min.clamp(-1.0, 1.0));
+            self.max.push(self.cur_max.clamp(-1.0, 1.0));
+        }
+        self.count = 0;
+        self.cur_min = f32::MAX;
+        self.cur_max = f32::MIN;
+    }
+
+    pub(crate) fn finish(mut self) -> AudioPeaks {
+        self.flush();
+        AudioPeaks {
+            bins_per_sec: self.bins_per_sec,
+            min: self.min,
+            max: self.max,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accumulator_bins() {
+        // 10 samples/sec at 5 bins/sec -> 2 samples per bin, trailing partial bin kept
+        let mut acc = PeakAccumulator::new(10, 5.0);
+        for (lo, hi) in [
+            (-0.5, 0.2),
+            (-0.1, 0.9),
+            (0.0, 0.3),
+            (-2.0, 0.1),
+            (0.4, 0.4),
+        ] {
+            acc.push(lo, hi);
+        }
+        let peaks = acc.finish();
+        assert_eq!(peaks.bins_per_sec, 5.0);
+        assert_eq!(peaks.min, vec![-0.5, -1.0, 0.4]);
+        assert_eq!(peaks.max, vec![0.9, 0.3, 0.4]);
+        assert!((peaks.duration_secs() - 0.6).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_resample_zoom() {
+        let peaks = AudioPeaks {
+            bins_per_sec: 4.0,
+            min: vec![-0.1, -0.2, -0.3, -0.4],
+            max: vec![0.1, 0.2, 0.3, 0.4],
+        };
+        // Zoomed out: two bins per column
+        assert_eq!(
+            peaks.resample(0.0, 1.0, 2),
+            vec![Some((-0.2, 0.2)), Some((-0.4, 0.4))]
+        );
+        // Zoomed in: several columns share one bin
+        let cols = peaks.resample(0.0, 0.25, 4);
+        assert!(cols.iter().all(|c| *c == Some((-0.1, 0.1))));
+        // Past the end of the stream
+        assert_eq!(peaks.resample(2.0, 3.0, 1), vec![None]);
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::path::Path;
 
+use super::AudioPeaks;
 use crate::error::IoError;
 use crate::pixel::{RawPixelBuffer, RawPixelFormat};
 
@@ -47,3 +48,9 @@ pub fn decode_frame(
         "Video decode not available (compiled without FFmpeg; use WebCodecs on Wasm)".to_string(),
     ))
 }
+
+pub fn decode_audio_peaks(_path: &Path, _bins_per_sec: f32) -> Result<Option<AudioPeaks>, IoError> {
+    Err(IoError::UnsupportedFormat(
+        "Audio decode not available (compiled without FFmpeg)".to_string(),
+    ))
+}
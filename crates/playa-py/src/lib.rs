@@ -19,8 +19,10 @@
 
 use clap::Parser;
 use pyo3::prelude::*;
+use pyo3::types::PyDict;
 
 use ::playa::cli::Args;
+use ::playa::dialogs::encode::encode_capabilities;
 use ::playa::run_app;
 
 /// Run playa player with the given options.
@@ -125,10 +127,44 @@ fn version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
+/// Get build capabilities (same as `GET /api/info`).
+///
+/// Returns:
+///     dict with keys:
+///         exr_backend: EXR writer backend name, or None without EXR support
+///         exr_compressions: EXR compressions the backend writes (e.g. "Dwaa")
+///         video_encoders: codec name -> installed FFmpeg encoders
+///             (e.g. {"h264": ["h264_nvenc", "libx264"], ...})
+///         sequence_formats: image sequence extensions
+///         ffmpeg_version: linked FFmpeg library versions, or None
+///
+/// FFmpeg is probed on the first call; later calls return the cached result.
+#[pyfunction]
+fn info(py: Python<'_>) -> PyResult<Bound<'_, PyDict>> {
+    let caps = encode_capabilities();
+    let dict = PyDict::new(py);
+    dict.set_item("exr_backend", caps.exr_backend)?;
+    let compressions: Vec<String> = caps
+        .exr_compressions
+        .iter()
+        .map(|c| format!("{:?}", c))
+        .collect();
+    dict.set_item("exr_compressions", compressions)?;
+    let encoders = PyDict::new(py);
+    for (codec, names) in &caps.video_encoders {
+        encoders.set_item(*codec, names)?;
+    }
+    dict.set_item("video_encoders", encoders)?;
+    dict.set_item("sequence_formats", &caps.sequence_formats)?;
+    dict.set_item("ffmpeg_version", caps.ffmpeg_version.as_deref())?;
+    Ok(dict)
+}
+
 /// Python module definition.
 #[pymodule]
 fn playa(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(run, m)?)?;
     m.add_function(wrap_pyfunction!(version, m)?)?;
+    m.add_function(wrap_pyfunction!(info, m)?)?;
     Ok(())
 }
@@ -11,21 +11,26 @@
 
 use log::info;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
-use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::mpsc::Sender;
+use std::sync::{Arc, OnceLock};
 
-use playa_engine::entities::Comp;
-use playa_engine::entities::frame::{CropAlign, FrameConversion, PixelFormat, TonemapMode};
+use playa_engine::entities::frame::{
+    CropAlign, FitMode, FrameConversion, PixelFormat, ResizeFilter, TonemapMode,
+};
+use playa_engine::entities::{Comp, Node};
 use playa_io::ffmpeg;
+pub use playa_io::media::{PaddingPattern, parse_padding_pattern};
 
-/// Export mode - video or image sequence
+/// Export mode - video, image sequence or a single contact sheet image
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub enum ExportMode {
     #[default]
     Video,
     Sequence,
+    ContactSheet,
 }
 
 /// Encode dialog settings (persistent via AppSettings)
@@ -53,6 +58,40 @@ pub struct EncodeDialogSettings {
     // Image sequence settings
     #[serde(default)]
     pub sequence_settings: SequenceSettings,
+
+    // Contact sheet layout (writer options shared with the sequence settings)
+    #[serde(default)]
+    pub contact_sheet: ContactSheetSettings,
+
+    // Anamorphic: stretch pixels to square (true) or signal SAR in the stream (false)
+    #[serde(default)]
+    pub bake_pixel_aspect: bool,
+
+    // Draw review annotations into the encoded video frames
+    #[serde(default)]
+    pub burn_annotations: bool,
+
+    // Output resolution (None = source) and how mismatched frames fit it
+    #[serde(default)]
+    pub output_size: Option<(u32, u32)>,
+    #[serde(default)]
+    pub fit_mode: FitMode,
+    #[serde(default)]
+    pub crop_align: CropAlign,
+
+    // Color tags written to video streams
+    #[serde(default)]
+    pub color_preset: ColorPreset,
+
+    // Keyframe interval for long-GOP codecs (H.264/H.265/AV1/VP9)
+    #[serde(default = "default_gop_seconds")]
+    pub gop_seconds: f32,
+    #[serde(default)]
+    pub all_intra: bool,
+
+    // Container tags and start timecode for video encodes
+    #[serde(default)]
+    pub metadata: EncodeMetadata,
 }
 
 impl Default for EncodeDialogSettings {
@@ -66,6 +105,16 @@ impl Default for EncodeDialogSettings {
             codec_settings: CodecSettings::default(),
             export_mode: ExportMode::Video,
             sequence_settings: SequenceSettings::default(),
+            contact_sheet: ContactSheetSettings::default(),
+            bake_pixel_aspect: false,
+            burn_annotations: false,
+            output_size: None,
+            fit_mode: FitMode::default(),
+            crop_align: CropAlign::default(),
+            color_preset: ColorPreset::default(),
+            gop_seconds: DEFAULT_GOP_SECONDS,
+            all_intra: false,
+            metadata: EncodeMetadata::default(),
         }
     }
 }
@@ -76,7 +125,11 @@ impl Default for EncodeDialogSettings {
 /// The UI uses EncodeDialogSettings which stores settings for ALL codecs (H.264/H.265/ProRes/AV1).
 /// When starting encoding, build_encoder_settings() converts EncodeDialogSettings → EncoderSettings
 /// by extracting only the settings for the currently selected codec.
+///
+/// Missing fields deserialize from [`Default`], so remote callers (`POST /api/encode`)
+/// can send only what they override.
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct EncoderSettings {
     pub output_path: PathBuf,
     pub container: Container,
@@ -93,10 +146,65 @@ pub struct EncoderSettings {
     pub profile: Option<String>, // H.264/H.265 profile (e.g. "high", "main", "main10")
     #[serde(default)]
     pub prores_profile: Option<ProResProfile>, // ProRes profile
+    #[serde(default)]
+    pub dnxhr_profile: Option<DNxHRProfile>, // DNxHR profile
+
+    // Keyframe interval. Shorter GOPs scrub faster but cost size/quality;
+    // all-intra (GOP 1, no B-frames) is frame-accurate and largest.
+    #[serde(default = "default_gop_seconds")]
+    pub gop_seconds: f32,
+    #[serde(default)]
+    pub all_intra: bool,
 
     // HDR → LDR conversion settings
     #[serde(default)]
     pub tonemap_mode: TonemapMode, // Tonemapping mode for HDR sources (when encoding 8-bit)
+
+    // Non-square source pixels: bake into square pixels instead of signaling SAR
+    #[serde(default)]
+    pub bake_pixel_aspect: bool,
+
+    // Rasterize the comp's review annotations into each frame
+    #[serde(default)]
+    pub burn_annotations: bool,
+
+    // Output resolution: None = first frame's (after PAR bake). Frames of any
+    // other size are fitted with `fit_mode`, placed by `crop_align`.
+    #[serde(default)]
+    pub output_size: Option<(u32, u32)>,
+    #[serde(default)]
+    pub fit_mode: FitMode,
+    #[serde(default)]
+    pub crop_align: CropAlign,
+
+    // Frames to encode (inclusive, comp frames). None = the comp's work area;
+    // clamped to the comp bounds by `encode_range`.
+    #[serde(default)]
+    pub range: Option<(i32, i32)>,
+
+    // Color description written to the stream (see `ColorPreset`). PQ also
+    // changes the pixels: frames are converted to BT.2020 and PQ-encoded.
+    #[serde(default)]
+    pub color_primaries: ColorPrimaries,
+    #[serde(default)]
+    pub color_trc: ColorTransfer,
+    #[serde(default)]
+    pub colorspace: ColorMatrix,
+    #[serde(default)]
+    pub color_range: ColorRange,
+    #[serde(default)]
+    pub mastering_display: Option<MasteringDisplay>,
+
+    // Title / artist / comment / copyright / start timecode container tags
+    #[serde(default)]
+    pub metadata: EncodeMetadata,
+}
+
+/// Default keyframe interval: a keyframe every 10 seconds
+pub const DEFAULT_GOP_SECONDS: f32 = 10.0;
+
+fn default_gop_seconds() -> f32 {
+    DEFAULT_GOP_SECONDS
 }
 
 impl Default for EncoderSettings {
@@ -112,7 +220,22 @@ impl Default for EncoderSettings {
             preset: Some("medium".to_string()),
             profile: Some("high".to_string()), // H.264: "high", H.265: "main" or "main10"
             prores_profile: Some(ProResProfile::Standard),
+            dnxhr_profile: Some(DNxHRProfile::HQ),
+            gop_seconds: DEFAULT_GOP_SECONDS,
+            all_intra: false,
             tonemap_mode: TonemapMode::default(), // ACES by default
+            bake_pixel_aspect: false,
+            burn_annotations: false,
+            output_size: None,
+            fit_mode: FitMode::default(),
+            crop_align: CropAlign::default(),
+            range: None,
+            color_primaries: ColorPrimaries::default(),
+            color_trc: ColorTransfer::default(),
+            colorspace: ColorMatrix::default(),
+            color_range: ColorRange::default(),
+            mastering_display: None,
+            metadata: EncodeMetadata::default(),
         }
     }
 }
@@ -225,6 +348,98 @@ impl Default for ProResSettings {
     }
 }
 
+/// DNxHR profile (Avid, resolution independent)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum DNxHRProfile {
+    LB,   // Low Bandwidth, 8-bit 4:2:2 (offline)
+    SQ,   // Standard Quality, 8-bit 4:2:2
+    HQ,   // High Quality, 8-bit 4:2:2
+    HQX,  // High Quality, 10-bit 4:2:2
+    R444, // Finishing, 10-bit 4:4:4
+}
+
+impl DNxHRProfile {
+    /// Smallest frame the `dnxhd` encoder accepts for DNxHR profiles
+    pub const MIN_SIZE: (u32, u32) = (256, 120);
+
+    pub fn all() -> &'static [DNxHRProfile] {
+        &[
+            DNxHRProfile::LB,
+            DNxHRProfile::SQ,
+            DNxHRProfile::HQ,
+            DNxHRProfile::HQX,
+            DNxHRProfile::R444,
+        ]
+    }
+
+    pub fn to_ffmpeg_value(self) -> &'static str {
+        match self {
+            DNxHRProfile::LB => "dnxhr_lb",
+            DNxHRProfile::SQ => "dnxhr_sq",
+            DNxHRProfile::HQ => "dnxhr_hq",
+            DNxHRProfile::HQX => "dnxhr_hqx",
+            DNxHRProfile::R444 => "dnxhr_444",
+        }
+    }
+
+    /// Pixel format the `dnxhd` encoder requires for this profile
+    pub fn pixel_format(self) -> ffmpeg::format::Pixel {
+        match self {
+            DNxHRProfile::LB | DNxHRProfile::SQ | DNxHRProfile::HQ => {
+                ffmpeg::format::Pixel::YUV422P
+            }
+            DNxHRProfile::HQX => ffmpeg::format::Pixel::YUV422P10LE,
+            DNxHRProfile::R444 => ffmpeg::format::Pixel::YUV444P10LE,
+        }
+    }
+
+    /// Check that `width` x `height` can be encoded with this profile
+    pub fn validate(self, width: u32, height: u32) -> Result<(), String> {
+        let (min_w, min_h) = Self::MIN_SIZE;
+        if width < min_w || height < min_h {
+            return Err(format!(
+                "DNxHR {} needs at least {}x{}, got {}x{}",
+                self, min_w, min_h, width, height
+            ));
+        }
+        // 4:2:2 halves chroma horizontally
+        if self != DNxHRProfile::R444 && width % 2 != 0 {
+            return Err(format!(
+                "DNxHR {} is 4:2:2 and needs an even width, got {}x{}",
+                self, width, height
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for DNxHRProfile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DNxHRProfile::LB => write!(f, "LB"),
+            DNxHRProfile::SQ => write!(f, "SQ"),
+            DNxHRProfile::HQ => write!(f, "HQ"),
+            DNxHRProfile::HQX => write!(f, "HQX (10-bit)"),
+            DNxHRProfile::R444 => write!(f, "444 (10-bit)"),
+        }
+    }
+}
+
+/// DNxHR specific settings
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DNxHRSettings {
+    pub profile: DNxHRProfile,
+}
+
+impl Default for DNxHRSettings {
+    fn default() -> Self {
+        Self {
+            profile: DNxHRProfile::HQ,
+        }
+    }
+}
+
 /// AV1 specific settings
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct AV1Settings {
@@ -245,6 +460,28 @@ impl Default for AV1Settings {
     }
 }
 
+/// VP9 specific settings
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VP9Settings {
+    pub encoder_impl: EncoderImpl,
+    pub quality_mode: QualityMode,
+    pub quality_value: u32, // CRF 0-63 or bitrate kbps
+    pub preset: String,     // libvpx cpu-used 0-8 (0=slowest/best)
+    pub profile: String,    // "0" (8-bit 4:2:0) or "2" (10-bit 4:2:0)
+}
+
+impl Default for VP9Settings {
+    fn default() -> Self {
+        Self {
+            encoder_impl: EncoderImpl::Auto,
+            quality_mode: QualityMode::CRF,
+            quality_value: 31, // libvpx recommended CRF for 1080p
+            preset: "4".to_string(),
+            profile: "0".to_string(),
+        }
+    }
+}
+
 /// All codec-specific settings
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 pub struct CodecSettings {
@@ -252,6 +489,10 @@ pub struct CodecSettings {
     pub h265: H265Settings,
     pub prores: ProResSettings,
     pub av1: AV1Settings,
+    #[serde(default)]
+    pub vp9: VP9Settings,
+    #[serde(default)]
+    pub dnxhr: DNxHRSettings,
 }
 
 /// Container format
@@ -260,6 +501,7 @@ pub struct CodecSettings {
 pub enum Container {
     MP4,
     MOV,
+    WebM,
 }
 
 impl Container {
@@ -267,6 +509,7 @@ impl Container {
         match self {
             Container::MP4 => "mp4",
             Container::MOV => "mov",
+            Container::WebM => "webm",
         }
     }
 }
@@ -276,6 +519,7 @@ impl std::fmt::Display for Container {
         match self {
             Container::MP4 => write!(f, "MP4"),
             Container::MOV => write!(f, "MOV"),
+            Container::WebM => write!(f, "WebM"),
         }
     }
 }
@@ -288,15 +532,32 @@ pub enum VideoCodec {
     H265,
     ProRes,
     AV1,
+    VP9,
+    DNxHR,
 }
 
 impl VideoCodec {
+    /// Codec from a lowercase CLI / script name (`h264`, `hevc`, `prores`, ...)
+    pub fn parse(name: &str) -> Option<VideoCodec> {
+        match name.to_ascii_lowercase().as_str() {
+            "h264" | "avc" => Some(VideoCodec::H264),
+            "h265" | "hevc" => Some(VideoCodec::H265),
+            "av1" => Some(VideoCodec::AV1),
+            "prores" => Some(VideoCodec::ProRes),
+            "vp9" => Some(VideoCodec::VP9),
+            "dnxhr" => Some(VideoCodec::DNxHR),
+            _ => None,
+        }
+    }
+
     pub fn all() -> &'static [VideoCodec] {
         &[
             VideoCodec::H264,
             VideoCodec::H265,
             VideoCodec::AV1,
             VideoCodec::ProRes,
+            VideoCodec::VP9,
+            VideoCodec::DNxHR,
         ]
     }
 
@@ -307,47 +568,64 @@ impl VideoCodec {
             VideoCodec::H265 => Container::MP4,
             VideoCodec::AV1 => Container::MP4,
             VideoCodec::ProRes => Container::MOV, // ProRes typically uses MOV
+            VideoCodec::VP9 => Container::WebM,
+            VideoCodec::DNxHR => Container::MOV, // MXF is not supported yet
         }
     }
 
-    /// Check if any encoder is available for this codec
-    pub fn is_available(&self) -> bool {
+    /// Lowercase script / API name, the inverse of [`VideoCodec::parse`]
+    pub fn name(&self) -> &'static str {
         match self {
-            VideoCodec::H264 => {
-                // Check all H.264 encoders
-                #[cfg(target_os = "macos")]
-                if ffmpeg::encoder::find_by_name("h264_videotoolbox").is_some() {
-                    return true;
-                }
+            VideoCodec::H264 => "h264",
+            VideoCodec::H265 => "h265",
+            VideoCodec::AV1 => "av1",
+            VideoCodec::ProRes => "prores",
+            VideoCodec::VP9 => "vp9",
+            VideoCodec::DNxHR => "dnxhr",
+        }
+    }
 
-                ffmpeg::encoder::find_by_name("h264_nvenc").is_some()
-                    || ffmpeg::encoder::find_by_name("h264_qsv").is_some()
-                    || ffmpeg::encoder::find_by_name("h264_amf").is_some()
-                    || ffmpeg::encoder::find_by_name("libx264").is_some()
-            }
-            VideoCodec::H265 => {
-                // Check all H.265 encoders
+    /// FFmpeg encoders that produce this codec, hardware first
+    fn encoder_candidates(&self) -> &'static [&'static str] {
+        match self {
+            VideoCodec::H264 => &[
                 #[cfg(target_os = "macos")]
-                if ffmpeg::encoder::find_by_name("hevc_videotoolbox").is_some() {
-                    return true;
-                }
-
-                ffmpeg::encoder::find_by_name("hevc_nvenc").is_some()
-                    || ffmpeg::encoder::find_by_name("hevc_qsv").is_some()
-                    || ffmpeg::encoder::find_by_name("hevc_amf").is_some()
-                    || ffmpeg::encoder::find_by_name("libx265").is_some()
-            }
-            VideoCodec::AV1 => {
-                // Check all AV1 encoders (hardware first, then software)
-                ffmpeg::encoder::find_by_name("av1_nvenc").is_some()
-                    || ffmpeg::encoder::find_by_name("av1_qsv").is_some()
-                    || ffmpeg::encoder::find_by_name("av1_amf").is_some()
-                    || ffmpeg::encoder::find_by_name("libsvtav1").is_some()
-                    || ffmpeg::encoder::find_by_name("libaom-av1").is_some()
-            }
-            VideoCodec::ProRes => ffmpeg::encoder::find_by_name("prores_ks").is_some(),
+                "h264_videotoolbox",
+                "h264_nvenc",
+                "h264_qsv",
+                "h264_amf",
+                "libx264",
+            ],
+            VideoCodec::H265 => &[
+                #[cfg(target_os = "macos")]
+                "hevc_videotoolbox",
+                "hevc_nvenc",
+                "hevc_qsv",
+                "hevc_amf",
+                "libx265",
+            ],
+            VideoCodec::AV1 => &["av1_nvenc", "av1_qsv", "av1_amf", "libsvtav1", "libaom-av1"],
+            VideoCodec::ProRes => &["prores_ks"],
+            VideoCodec::DNxHR => &["dnxhd"],
+            VideoCodec::VP9 => &["vp9_nvenc", "vp9_qsv", "libvpx-vp9"],
         }
     }
+
+    /// Encoders for this codec present in the linked FFmpeg (e.g. `h264_nvenc`)
+    pub fn available_encoders(&self) -> Vec<&'static str> {
+        self.encoder_candidates()
+            .iter()
+            .copied()
+            .filter(|name| ffmpeg::encoder::find_by_name(name).is_some())
+            .collect()
+    }
+
+    /// Check if any encoder is available for this codec
+    pub fn is_available(&self) -> bool {
+        self.encoder_candidates()
+            .iter()
+            .any(|name| ffmpeg::encoder::find_by_name(name).is_some())
+    }
 }
 
 impl std::fmt::Display for VideoCodec {
@@ -357,10 +635,52 @@ impl std::fmt::Display for VideoCodec {
             VideoCodec::H265 => write!(f, "H.265 (HEVC)"),
             VideoCodec::AV1 => write!(f, "AV1"),
             VideoCodec::ProRes => write!(f, "ProRes"),
+            VideoCodec::VP9 => write!(f, "VP9"),
+            VideoCodec::DNxHR => write!(f, "DNxHR"),
         }
     }
 }
 
+/// What this build can export, for `playa.info()` and `GET /api/info`
+#[derive(Clone, Debug, Serialize)]
+pub struct EncodeCapabilities {
+    /// EXR writer backend (`None` if built without EXR)
+    pub exr_backend: Option<&'static str>,
+    /// EXR compressions the backend writes (empty without EXR)
+    pub exr_compressions: Vec<ExrCompression>,
+    /// Codec name ([`VideoCodec::name`]) to its installed FFmpeg encoders
+    pub video_encoders: BTreeMap<&'static str, Vec<&'static str>>,
+    /// Image sequence formats by extension
+    pub sequence_formats: Vec<&'static str>,
+    /// Linked FFmpeg library versions
+    pub ffmpeg_version: Option<String>,
+}
+
+/// Probe FFmpeg encoders once and cache the result for the process
+pub fn encode_capabilities() -> &'static EncodeCapabilities {
+    static CAPS: OnceLock<EncodeCapabilities> = OnceLock::new();
+    CAPS.get_or_init(|| {
+        let exr_backend = playa_io::exr_backend();
+        EncodeCapabilities {
+            exr_backend,
+            exr_compressions: if exr_backend.is_some() {
+                ExrCompression::all().to_vec()
+            } else {
+                Vec::new()
+            },
+            video_encoders: VideoCodec::all()
+                .iter()
+                .map(|codec| (codec.name(), codec.available_encoders()))
+                .collect(),
+            sequence_formats: SequenceFormat::all()
+                .iter()
+                .map(|f| f.extension())
+                .collect(),
+            ffmpeg_version: playa_io::ffmpeg_version(),
+        }
+    })
+}
+
 /// Encoder implementation type
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EncoderImpl {
@@ -412,6 +732,273 @@ impl std::fmt::Display for QualityMode {
     }
 }
 
+// ============================================================================
+// COLOR METADATA
+// ============================================================================
+
+/// Color primaries tagged on the video stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorPrimaries {
+    Unspecified,
+    #[default]
+    Bt709,
+    Bt2020,
+}
+
+impl ColorPrimaries {
+    fn to_ffmpeg(self) -> ffmpeg::color::Primaries {
+        match self {
+            ColorPrimaries::Unspecified => ffmpeg::color::Primaries::Unspecified,
+            ColorPrimaries::Bt709 => ffmpeg::color::Primaries::BT709,
+            ColorPrimaries::Bt2020 => ffmpeg::color::Primaries::BT2020,
+        }
+    }
+}
+
+/// Transfer characteristic tagged on the video stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorTransfer {
+    Unspecified,
+    #[default]
+    Bt709,
+    /// SMPTE ST 2084 (HDR10). Frames are PQ-encoded, not just tagged.
+    Pq,
+}
+
+impl ColorTransfer {
+    fn to_ffmpeg(self) -> ffmpeg::color::TransferCharacteristic {
+        match self {
+            ColorTransfer::Unspecified => ffmpeg::color::TransferCharacteristic::Unspecified,
+            ColorTransfer::Bt709 => ffmpeg::color::TransferCharacteristic::BT709,
+            ColorTransfer::Pq => ffmpeg::color::TransferCharacteristic::SMPTE2084,
+        }
+    }
+}
+
+/// RGB → YUV matrix, used for the conversion and tagged on the stream
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorMatrix {
+    Unspecified,
+    #[default]
+    Bt709,
+    Bt2020Ncl,
+}
+
+impl ColorMatrix {
+    fn to_ffmpeg(self) -> ffmpeg::color::Space {
+        match self {
+            ColorMatrix::Unspecified => ffmpeg::color::Space::Unspecified,
+            ColorMatrix::Bt709 => ffmpeg::color::Space::BT709,
+            ColorMatrix::Bt2020Ncl => ffmpeg::color::Space::BT2020NCL,
+        }
+    }
+
+    /// swscale coefficient table (`SWS_CS_*`); unspecified keeps swscale's BT.601
+    fn sws_colorspace(self) -> i32 {
+        match self {
+            ColorMatrix::Unspecified => 5, // SWS_CS_DEFAULT
+            ColorMatrix::Bt709 => 1,       // SWS_CS_ITU709
+            ColorMatrix::Bt2020Ncl => 9,   // SWS_CS_BT2020
+        }
+    }
+}
+
+/// YUV quantization range
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorRange {
+    /// Broadcast / "TV" range (16-235 at 8 bit)
+    #[default]
+    Limited,
+    /// "PC" range (0-255 at 8 bit)
+    Full,
+}
+
+impl ColorRange {
+    fn to_ffmpeg(self) -> ffmpeg::color::Range {
+        match self {
+            ColorRange::Limited => ffmpeg::color::Range::MPEG,
+            ColorRange::Full => ffmpeg::color::Range::JPEG,
+        }
+    }
+}
+
+/// HDR10 static metadata: SMPTE ST 2086 mastering display + CTA-861.3 light levels
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MasteringDisplay {
+    /// CIE 1931 xy of the display's red, green, blue primaries
+    pub primaries: [[f32; 2]; 3],
+    pub white_point: [f32; 2],
+    /// Display luminance range in cd/m²
+    pub min_luminance: f32,
+    pub max_luminance: f32,
+    /// Maximum content / frame-average light level in cd/m² (0 = unknown)
+    pub max_cll: u32,
+    pub max_fall: u32,
+}
+
+impl MasteringDisplay {
+    /// The common HDR10 grade: P3-D65 monitor, 0.0001-1000 nits
+    pub fn p3_d65_1000() -> Self {
+        Self {
+            primaries: [[0.680, 0.320], [0.265, 0.690], [0.150, 0.060]],
+            white_point: [0.3127, 0.3290],
+            min_luminance: 0.0001,
+            max_luminance: 1000.0,
+            max_cll: 1000,
+            max_fall: 400,
+        }
+    }
+
+    /// x265 `master-display` / `max-cll` parameters (G, B, R order, 0.00002 and
+    /// 0.0001 cd/m² units)
+    fn x265_params(&self) -> String {
+        let xy = |p: [f32; 2]| {
+            format!(
+                "({},{})",
+                (p[0] * 50000.0).round(),
+                (p[1] * 50000.0).round()
+            )
+        };
+        let [r, g, b] = self.primaries;
+        format!(
+            "master-display=G{}B{}R{}WP{}L({},{}):max-cll={},{}",
+            xy(g),
+            xy(b),
+            xy(r),
+            xy(self.white_point),
+            (self.max_luminance * 10000.0).round(),
+            (self.min_luminance * 10000.0).round(),
+            self.max_cll,
+            self.max_fall
+        )
+    }
+}
+
+/// One-click color tagging for the encode dialog
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum ColorPreset {
+    /// No color description; players guess (previous behavior)
+    Untagged,
+    /// BT.709 primaries / transfer / matrix, limited range
+    #[default]
+    Bt709,
+    /// BT.709, full range
+    Bt709Full,
+    /// BT.2020 + PQ, limited range, with mastering display metadata.
+    /// Needs a 10-bit encode (H.265 main10 or ProRes).
+    Hdr10,
+}
+
+impl ColorPreset {
+    pub fn all() -> &'static [ColorPreset] {
+        &[
+            ColorPreset::Untagged,
+            ColorPreset::Bt709,
+            ColorPreset::Bt709Full,
+            ColorPreset::Hdr10,
+        ]
+    }
+
+    /// Write this preset's tags into `settings`
+    pub fn apply(self, settings: &mut EncoderSettings) {
+        let (primaries, trc, matrix, range, mastering) = match self {
+            ColorPreset::Untagged => (
+                ColorPrimaries::Unspecified,
+                ColorTransfer::Unspecified,
+                ColorMatrix::Unspecified,
+                ColorRange::Limited,
+                None,
+            ),
+            ColorPreset::Bt709 => (
+                ColorPrimaries::Bt709,
+                ColorTransfer::Bt709,
+                ColorMatrix::Bt709,
+                ColorRange::Limited,
+                None,
+            ),
+            ColorPreset::Bt709Full => (
+                ColorPrimaries::Bt709,
+                ColorTransfer::Bt709,
+                ColorMatrix::Bt709,
+                ColorRange::Full,
+                None,
+            ),
+            ColorPreset::Hdr10 => (
+                ColorPrimaries::Bt2020,
+                ColorTransfer::Pq,
+                ColorMatrix::Bt2020Ncl,
+                ColorRange::Limited,
+                Some(MasteringDisplay::p3_d65_1000()),
+            ),
+        };
+        settings.color_primaries = primaries;
+        settings.color_trc = trc;
+        settings.colorspace = matrix;
+        settings.color_range = range;
+        settings.mastering_display = mastering;
+    }
+}
+
+impl std::fmt::Display for ColorPreset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ColorPreset::Untagged => write!(f, "Untagged"),
+            ColorPreset::Bt709 => write!(f, "BT.709"),
+            ColorPreset::Bt709Full => write!(f, "BT.709 (full range)"),
+            ColorPreset::Hdr10 => write!(f, "HDR10 (PQ)"),
+        }
+    }
+}
+
+/// Slate info written into the output container. Empty fields are skipped.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EncodeMetadata {
+    pub title: String,
+    pub artist: String,
+    pub comment: String,
+    pub copyright: String,
+    /// Start timecode `hh:mm:ss:ff` (`;` before frames = drop-frame). Empty =
+    /// the first encoded comp frame, i.e. the in-point.
+    pub timecode: String,
+}
+
+impl EncodeMetadata {
+    /// Container tags (`title`, `artist`, ...) that have a value
+    pub fn tags(&self) -> impl Iterator<Item = (&'static str, &str)> {
+        [
+            ("title", &self.title),
+            ("artist", &self.artist),
+            ("comment", &self.comment),
+            ("copyright", &self.copyright),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key, value.trim()))
+        .filter(|(_, value)| !value.is_empty())
+    }
+
+    /// Start timecode written as the `timecode` tag: the user value, else
+    /// `first_frame` counted from 00:00:00:00 at `fps`.
+    pub fn start_timecode(&self, first_frame: i32, fps: f32) -> Result<String, String> {
+        use playa_time::{Fps, TimeDisplay, format_time, parse_time};
+
+        let fps = Fps::from_f32_lossy(fps);
+        let user = self.timecode.trim();
+        let frame = if user.is_empty() {
+            first_frame
+        } else {
+            parse_time(user, fps, TimeDisplay::Timecode { drop_frame: false })
+                .ok_or_else(|| format!("Invalid start timecode '{}'", user))?
+        };
+        let drop_frame = user.contains(';');
+        Ok(format_time(
+            frame.max(0),
+            fps,
+            TimeDisplay::Timecode { drop_frame },
+        ))
+    }
+}
+
 // ============================================================================
 // IMAGE SEQUENCE EXPORT
 // ============================================================================
@@ -438,6 +1025,18 @@ impl SequenceFormat {
         ]
     }
 
+    /// Format written for a file extension (case-insensitive, `jpeg`/`tif` too)
+    pub fn from_extension(ext: &str) -> Option<SequenceFormat> {
+        match ext.to_ascii_lowercase().as_str() {
+            "exr" => Some(SequenceFormat::Exr),
+            "png" => Some(SequenceFormat::Png),
+            "jpg" | "jpeg" => Some(SequenceFormat::Jpeg),
+            "tif" | "tiff" => Some(SequenceFormat::Tiff),
+            "tga" => Some(SequenceFormat::Tga),
+            _ => None,
+        }
+    }
+
     pub fn extension(&self) -> &'static str {
         match self {
             SequenceFormat::Exr => "exr",
@@ -663,6 +1262,32 @@ impl ExrCompression {
         matches!(self, ExrCompression::Dwaa | ExrCompression::Dwab)
     }
 
+    /// Value of the header's `compression` attribute (OpenEXR `Compression` enum).
+    pub fn header_code(self) -> u8 {
+        match self {
+            ExrCompression::None => 0,
+            ExrCompression::Rle => 1,
+            ExrCompression::Zips => 2,
+            ExrCompression::Zip => 3,
+            ExrCompression::Piz => 4,
+            ExrCompression::Pxr24 => 5,
+            ExrCompression::B44 => 6,
+            ExrCompression::B44a => 7,
+            ExrCompression::Dwaa => 8,
+            ExrCompression::Dwab => 9,
+            ExrCompression::HtJ2k256 => 10,
+            ExrCompression::HtJ2k32 => 11,
+        }
+    }
+
+    /// Inverse of [`Self::header_code`].
+    pub fn from_header_code(code: u8) -> Option<Self> {
+        Self::all()
+            .iter()
+            .copied()
+            .find(|c| c.header_code() == code)
+    }
+
     /// OIIO-style compression string ready to drop into
     /// `ImageSpec.attributes["compression"]`. DWA variants embed the quality
     /// level after a colon: `"dwaa:45"`. HTJ2K variants always carry an
@@ -827,6 +1452,17 @@ impl TiffCompression {
             TiffCompression::PackBits,
         ]
     }
+
+    /// `tiff` crate compression (ZIP = Deflate at the balanced level)
+    pub fn to_tiff(self) -> tiff::encoder::Compression {
+        use tiff::encoder::{Compression, DeflateLevel};
+        match self {
+            TiffCompression::None => Compression::Uncompressed,
+            TiffCompression::Lzw => Compression::Lzw,
+            TiffCompression::Zip => Compression::Deflate(DeflateLevel::Balanced),
+            TiffCompression::PackBits => Compression::Packbits,
+        }
+    }
 }
 
 impl std::fmt::Display for TiffCompression {
@@ -903,6 +1539,80 @@ pub struct SequenceFormatSettings {
     pub tga: TgaSequenceSettings,
 }
 
+/// How exported sequence files are numbered
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FrameNumbering {
+    /// Absolute comp frame index
+    #[default]
+    CompFrame,
+    /// First exported frame gets `start_number`, counting up
+    StartAt,
+    /// Source sequence's own numbers (first file layer's `file_start`)
+    Source,
+}
+
+impl FrameNumbering {
+    pub fn all() -> &'static [FrameNumbering] {
+        &[
+            FrameNumbering::CompFrame,
+            FrameNumbering::StartAt,
+            FrameNumbering::Source,
+        ]
+    }
+}
+
+impl std::fmt::Display for FrameNumbering {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FrameNumbering::CompFrame => write!(f, "Comp frame"),
+            FrameNumbering::StartAt => write!(f, "Start at"),
+            FrameNumbering::Source => write!(f, "Source numbers"),
+        }
+    }
+}
+
+/// Studio convention for the first frame of a deliverable
+pub const DEFAULT_START_NUMBER: i32 = 1001;
+
+/// Digits used when neither the settings nor the file name give a width
+pub const DEFAULT_PADDING: usize = 4;
+
+/// How the frame number is spelled in an exported sequence's file name
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaddingStyle {
+    /// Whatever the typed file name uses (`####`, `%04d`, ...)
+    #[default]
+    FromFilename,
+    /// `render.%04d.exr`
+    Printf,
+    /// `render.####.exr`
+    Hashes,
+}
+
+impl PaddingStyle {
+    pub fn all() -> &'static [PaddingStyle] {
+        &[
+            PaddingStyle::FromFilename,
+            PaddingStyle::Printf,
+            PaddingStyle::Hashes,
+        ]
+    }
+}
+
+impl std::fmt::Display for PaddingStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PaddingStyle::FromFilename => write!(f, "From file name"),
+            PaddingStyle::Printf => write!(f, "printf (%04d)"),
+            PaddingStyle::Hashes => write!(f, "Hashes (####)"),
+        }
+    }
+}
+
+fn default_start_number() -> i32 {
+    DEFAULT_START_NUMBER
+}
+
 /// Sequence export settings
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct SequenceSettings {
@@ -912,6 +1622,18 @@ pub struct SequenceSettings {
     pub apply_tonemap: bool,
     pub tonemap_mode: TonemapMode,
     pub format_settings: SequenceFormatSettings,
+    #[serde(default)]
+    pub numbering: FrameNumbering,
+    #[serde(default = "default_start_number")]
+    pub start_number: i32, // First file number with `FrameNumbering::StartAt`
+    #[serde(default)]
+    pub burn_annotations: bool, // Draw review annotations into the written frames
+    #[serde(default)]
+    pub write_hashes: bool, // Write a per-frame content hash manifest (QC, `--verify-hashes`)
+    #[serde(default)]
+    pub padding_style: PaddingStyle,
+    #[serde(default)]
+    pub padding_width: usize, // Frame number digits; 0 = from the file name
 }
 
 impl Default for SequenceSettings {
@@ -923,6 +1645,12 @@ impl Default for SequenceSettings {
             apply_tonemap: false,
             tonemap_mode: TonemapMode::default(),
             format_settings: SequenceFormatSettings::default(),
+            numbering: FrameNumbering::default(),
+            start_number: DEFAULT_START_NUMBER,
+            burn_annotations: false,
+            write_hashes: false,
+            padding_style: PaddingStyle::default(),
+            padding_width: 0,
         }
     }
 }
@@ -933,121 +1661,160 @@ impl SequenceSettings {
         self.format
             .validate_settings(&mut self.channels, &mut self.bit_depth);
     }
-}
-
-/// Padding pattern for frame numbering
-#[derive(Clone, Debug, PartialEq)]
-pub enum PaddingPattern {
-    /// Printf-style: %04d -> 4 digits
-    Printf { width: usize },
-    /// Hash-style: #### -> 4 digits
-    Hashes { count: usize },
-    /// At-sign: @ -> no padding
-    At,
-    /// No pattern found
-    None,
-}
 
-impl PaddingPattern {
-    /// Format frame number according to pattern
-    pub fn format(&self, frame: i32) -> String {
-        match self {
-            PaddingPattern::Printf { width } | PaddingPattern::Hashes { count: width } => {
-                format!("{:0width$}", frame, width = *width)
+    /// Split an output file name at its frame field, with the explicit
+    /// padding style / width overriding what the name implies.
+    /// Returns (prefix, pattern, suffix) like [`parse_padding_pattern`].
+    pub fn filename_pattern(&self, filename: &str) -> (String, PaddingPattern, String) {
+        let (prefix, parsed, suffix) = parse_padding_pattern(filename);
+        let width = match (self.padding_width, parsed.width()) {
+            (0, 0) => DEFAULT_PADDING,
+            (0, w) => w,
+            (w, _) => w,
+        };
+        let pattern = match (self.padding_style, parsed) {
+            (PaddingStyle::Printf, _) => PaddingPattern::Printf { width },
+            (PaddingStyle::Hashes, _) => PaddingPattern::Hashes { count: width },
+            (PaddingStyle::FromFilename, PaddingPattern::Printf { .. }) => {
+                PaddingPattern::Printf { width }
             }
-            PaddingPattern::At | PaddingPattern::None => {
-                format!("{}", frame)
+            (PaddingStyle::FromFilename, PaddingPattern::Hashes { .. }) => {
+                PaddingPattern::Hashes { count: width }
             }
-        }
+            (PaddingStyle::FromFilename, PaddingPattern::Hex { .. }) => {
+                PaddingPattern::Hex { width }
+            }
+            // Unpadded name: an explicit width still pads it
+            (PaddingStyle::FromFilename, parsed) if self.padding_width == 0 => parsed,
+            (PaddingStyle::FromFilename, _) => PaddingPattern::Printf { width },
+        };
+        (prefix, pattern, suffix)
     }
 }
 
-/// Parse filename pattern and extract padding info
-/// Returns (prefix, pattern, suffix)
-/// Example: "render.####.exr" -> ("render.", Hashes{4}, ".exr")
-pub fn parse_padding_pattern(filename: &str) -> (String, PaddingPattern, String) {
-    // Try printf-style first: %0Nd or %Nd
-    if let Some(pos) = filename.find('%') {
-        let rest = &filename[pos + 1..];
-        let mut chars = rest.chars().peekable();
-
-        // Skip leading zero
-        let has_zero = chars.peek() == Some(&'0');
-        if has_zero {
-            chars.next();
-        }
-
-        // Parse width
-        let mut width_str = String::new();
-        while let Some(&c) = chars.peek() {
-            if c.is_ascii_digit() {
-                width_str.push(c);
-                chars.next();
-            } else {
-                break;
-            }
-        }
-
-        // Check for 'd'
-        if chars.next() == Some('d') {
-            let width = width_str.parse::<usize>().unwrap_or(1);
-            let prefix = filename[..pos].to_string();
-            let consumed = 1 + if has_zero { 1 } else { 0 } + width_str.len() + 1; // % + 0? + digits + d
-            let suffix = filename[pos + consumed..].to_string();
-            return (prefix, PaddingPattern::Printf { width }, suffix);
+/// First and last file name of a sequence written with numbers `files`
+/// (inclusive), or why the pattern can't name it: every name must be unique,
+/// and padded numbers must fit the width (a wider number changes the name
+/// length, so the files no longer read back as one sequence).
+pub fn sequence_name_range(
+    prefix: &str,
+    pattern: &PaddingPattern,
+    suffix: &str,
+    files: (i32, i32),
+) -> Result<(String, String), String> {
+    let (first, last) = (files.0.min(files.1), files.0.max(files.1));
+    let name = |n: i32| format!("{}{}{}", prefix, pattern.format(n), suffix);
+    let width = pattern.width();
+    if width > 0 {
+        // The widest numbers sit at the ends of the range
+        if let Some(n) = [first, last]
+            .into_iter()
+            .find(|&n| pattern.format(n).len() > width)
+        {
+            return Err(format!(
+                "Frame number {} needs more than {} digits: raise the padding",
+                n, width
+            ));
         }
     }
-
-    // Try hash-style: ####
-    if let Some(start) = filename.find('#') {
-        let mut count = 0;
-        for c in filename[start..].chars() {
-            if c == '#' {
-                count += 1;
-            } else {
-                break;
-            }
-        }
-        if count > 0 {
-            let prefix = filename[..start].to_string();
-            let suffix = filename[start + count..].to_string();
-            return (prefix, PaddingPattern::Hashes { count }, suffix);
+    let mut seen = std::collections::HashSet::new();
+    for n in first..=last {
+        let file = name(n);
+        if !seen.insert(file.clone()) {
+            return Err(format!("Frames would overwrite each other: {}", file));
         }
     }
+    Ok((name(first), name(last)))
+}
 
-    // Try @-style
-    if let Some(pos) = filename.find('@') {
-        let prefix = filename[..pos].to_string();
-        let suffix = filename[pos + 1..].to_string();
-        return (prefix, PaddingPattern::At, suffix);
-    }
+/// Comp frame -> `Frame::content_hash` of the composed frame, as written
+/// next to an exported sequence and checked by `--verify-hashes`.
+pub type HashManifest = BTreeMap<i32, String>;
 
-    // No pattern - insert before extension
-    if let Some(dot_pos) = filename.rfind('.') {
-        let prefix = format!("{}.", &filename[..dot_pos]);
-        let suffix = filename[dot_pos..].to_string();
-        (prefix, PaddingPattern::None, suffix)
+/// Manifest path for a sequence pattern: `shot.####.exr` -> `shot.hashes.json`
+/// in the same directory.
+pub fn hash_manifest_path(output_path: &std::path::Path) -> PathBuf {
+    let filename = output_path
+        .file_name()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let (prefix, _, _) = parse_padding_pattern(filename);
+    let stem = prefix.trim_end_matches(['.', '_', '-']);
+    let name = if stem.is_empty() {
+        "hashes.json".to_string()
     } else {
-        (
-            format!("{}.", filename),
-            PaddingPattern::None,
-            String::new(),
-        )
-    }
+        format!("{}.hashes.json", stem)
+    };
+    output_path.with_file_name(name)
 }
 
-/// Build frame path from pattern
+/// Write a hash manifest as a JSON object keyed by frame number
+pub fn write_hash_manifest(
+    path: &std::path::Path,
+    manifest: &HashManifest,
+) -> Result<(), EncodeError> {
+    let json = serde_json::to_string_pretty(manifest)
+        .map_err(|e| EncodeError::OutputCreateFailed(format!("Hash manifest: {}", e)))?;
+    std::fs::write(path, json).map_err(|e| {
+        EncodeError::OutputCreateFailed(format!("Hash manifest {}: {}", path.display(), e))
+    })
+}
+
+/// Read a manifest written by [`write_hash_manifest`]
+pub fn read_hash_manifest(path: &std::path::Path) -> Result<HashManifest, String> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read {}: {}", path.display(), e))?;
+    serde_json::from_str(&json)
+        .map_err(|e| format!("Invalid hash manifest {}: {}", path.display(), e))
+}
+
+/// Build frame path from pattern; the file number is `frame + offset`
+/// (see [`frame_number_offset`]).
 pub fn build_frame_path(
     base_dir: &std::path::Path,
     prefix: &str,
     pattern: &PaddingPattern,
     suffix: &str,
     frame: i32,
+    offset: i32,
 ) -> PathBuf {
-    let filename = format!("{}{}{}", prefix, pattern.format(frame), suffix);
+    let filename = format!("{}{}{}", prefix, pattern.format(frame + offset), suffix);
     base_dir.join(filename)
 }
 
+/// Offset from comp frame to output file number for `settings.numbering`.
+///
+/// Source numbering follows the first layer backed by a file sequence at
+/// `first_frame` and counts up from there, so retimed sources still produce
+/// contiguous files. Comps without one keep comp frame numbers.
+pub fn frame_number_offset(
+    settings: &SequenceSettings,
+    comp: &Comp,
+    project: &playa_engine::entities::Project,
+    first_frame: i32,
+) -> i32 {
+    use playa_engine::entities::NodeKind;
+
+    match settings.numbering {
+        FrameNumbering::CompFrame => 0,
+        FrameNumbering::StartAt => settings.start_number - first_frame,
+        FrameNumbering::Source => {
+            let media = project.media.read().expect("media lock poisoned");
+            let offset = comp.layers.iter().find_map(|layer| {
+                let NodeKind::File(fnode) = media.get(&layer.source_uuid())?.as_ref() else {
+                    return None;
+                };
+                let seq_start = fnode.file_start().unwrap_or(fnode._in());
+                Some(seq_start + layer.parent_to_local(first_frame) - first_frame)
+            });
+            offset.unwrap_or_else(|| {
+                log::warn!("No file sequence layer in comp: keeping comp frame numbers");
+                0
+            })
+        }
+    }
+}
+
 /// Update filename extension based on format
 pub fn update_extension(path: &std::path::Path, format: SequenceFormat) -> PathBuf {
     let mut new_path = path.to_path_buf();
@@ -1055,6 +1822,65 @@ pub fn update_extension(path: &std::path::Path, format: SequenceFormat) -> PathB
     new_path
 }
 
+// ============================================================================
+// CONTACT SHEET EXPORT
+// ============================================================================
+
+/// Contact sheet (montage) layout: evenly spaced work-area frames in one grid image
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ContactSheetSettings {
+    /// Image format of the sheet; writer options come from the sequence settings
+    pub format: SequenceFormat,
+    /// Frames sampled across the work area (first and last included)
+    pub frames: u32,
+    /// Tiles per row
+    pub columns: u32,
+    /// Tile width in pixels; the height follows the frame's display aspect
+    pub thumb_width: u32,
+    /// Gap between tiles and around the sheet, in pixels
+    pub spacing: u32,
+    /// Print each tile's frame number under it
+    pub label_frames: bool,
+    /// Draw review annotations into the tiles
+    pub burn_annotations: bool,
+}
+
+impl Default for ContactSheetSettings {
+    fn default() -> Self {
+        Self {
+            format: SequenceFormat::Jpeg,
+            frames: 12,
+            columns: 4,
+            thumb_width: 320,
+            spacing: 8,
+            label_frames: true,
+            burn_annotations: false,
+        }
+    }
+}
+
+impl ContactSheetSettings {
+    /// Formats offered for sheets (display-referred, 8-bit)
+    pub fn formats() -> &'static [SequenceFormat] {
+        &[SequenceFormat::Jpeg, SequenceFormat::Png]
+    }
+}
+
+/// `count` frames spread evenly over `range` (inclusive), ends included.
+/// Never more frames than the range holds, never fewer than one.
+pub fn contact_sheet_frames(range: (i32, i32), count: u32) -> Vec<i32> {
+    let (start, end) = (range.0.min(range.1), range.0.max(range.1));
+    let len = (end - start) as i64 + 1;
+    let n = (count.max(1) as i64).min(len);
+    if n == 1 {
+        return vec![start];
+    }
+    (0..n)
+        .map(|i| start + (i as f64 * (len - 1) as f64 / (n - 1) as f64).round() as i32)
+        .collect()
+}
+
 // ============================================================================
 // VIDEO ENCODING (existing code)
 // ============================================================================
@@ -1079,6 +1905,20 @@ pub enum EncodeStage {
     Error(String), // Failed with error
 }
 
+impl EncodeStage {
+    /// Variant name without payload (for JSON status)
+    pub fn name(&self) -> &'static str {
+        match self {
+            EncodeStage::Validating => "Validating",
+            EncodeStage::Opening => "Opening",
+            EncodeStage::Encoding => "Encoding",
+            EncodeStage::Flushing => "Flushing",
+            EncodeStage::Complete => "Complete",
+            EncodeStage::Error(_) => "Error",
+        }
+    }
+}
+
 /// Encoding errors
 #[derive(Debug)]
 pub enum EncodeError {
@@ -1089,6 +1929,19 @@ pub enum EncodeError {
     Cancelled,
 }
 
+impl EncodeError {
+    /// Variant name without payload (for JSON error payloads)
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EncodeError::EncoderNotFound => "EncoderNotFound",
+            EncodeError::HardwareEncoderUnavailable => "HardwareEncoderUnavailable",
+            EncodeError::OutputCreateFailed(_) => "OutputCreateFailed",
+            EncodeError::EncodeFrameFailed(_) => "EncodeFrameFailed",
+            EncodeError::Cancelled => "Cancelled",
+        }
+    }
+}
+
 impl std::fmt::Display for EncodeError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -1211,11 +2064,58 @@ fn get_encoder_name(
             }
         }
 
+        // VP9 encoders
+        (VideoCodec::VP9, EncoderImpl::Hardware) | (VideoCodec::VP9, EncoderImpl::Auto) => {
+            // Priority: NVENC (NVIDIA) > QSV (Intel) > libvpx (software)
+            if ffmpeg::encoder::find_by_name("vp9_nvenc").is_some() {
+                info!("VP9: Selected vp9_nvenc (NVIDIA NVENC)");
+                Ok("vp9_nvenc")
+            } else if ffmpeg::encoder::find_by_name("vp9_qsv").is_some() {
+                info!("VP9: Selected vp9_qsv (Intel QuickSync)");
+                Ok("vp9_qsv")
+            } else if encoder_impl == EncoderImpl::Auto {
+                info!("VP9: Selected libvpx-vp9 (Software, fallback)");
+                Ok("libvpx-vp9")
+            } else {
+                Err(EncodeError::HardwareEncoderUnavailable)
+            }
+        }
+        (VideoCodec::VP9, EncoderImpl::Software) => {
+            info!("VP9: Selected libvpx-vp9 (Software)");
+            Ok("libvpx-vp9")
+        }
+
         // ProRes (software only)
         (VideoCodec::ProRes, _) => {
             info!("ProRes: Selected prores_ks (Software, Apple ProRes)");
             Ok("prores_ks")
         }
+
+        // DNxHR (software only, FFmpeg's DNxHD encoder)
+        (VideoCodec::DNxHR, _) => {
+            info!("DNxHR: Selected dnxhd (Software, Avid DNxHR)");
+            Ok("dnxhd")
+        }
+    }
+}
+
+/// Keyframe interval in frames: 1 for all-intra, else `gop_seconds` at `fps`.
+fn gop_frames(settings: &EncoderSettings) -> i32 {
+    if settings.all_intra {
+        return 1;
+    }
+    (settings.fps * settings.gop_seconds).round().max(1.0) as i32
+}
+
+/// Largest GOP a hardware encoder accepts, if it has a limit
+/// (QSV stores it in 16 bits, AMF caps the IDR period at 1000).
+fn max_gop(encoder_name: &str) -> Option<i32> {
+    if encoder_name.ends_with("_qsv") {
+        Some(u16::MAX as i32)
+    } else if encoder_name.ends_with("_amf") {
+        Some(1000)
+    } else {
+        None
     }
 }
 
@@ -1242,8 +2142,105 @@ fn fps_to_rational(fps: f32) -> (i32, i32) {
     ((fps * 1000.0).round() as i32, 1000)
 }
 
+/// Attach HDR10 static metadata to the stream's codec parameters as coded
+/// side data (ST 2086 mastering display + CTA-861.3 content light level).
+fn attach_mastering_display(
+    ost: &mut ffmpeg::format::stream::StreamMut,
+    md: &MasteringDisplay,
+) -> Result<(), String> {
+    use ffmpeg::ffi;
+
+    let q = |v: f32, den: i32| ffi::AVRational {
+        num: (v * den as f32).round() as i32,
+        den,
+    };
+    unsafe {
+        let par = ost.parameters().as_mut_ptr();
+
+        let sd = ffi::av_packet_side_data_new(
+            &mut (*par).coded_side_data,
+            &mut (*par).nb_coded_side_data,
+            ffi::AVPacketSideDataType::AV_PKT_DATA_MASTERING_DISPLAY_METADATA,
+            std::mem::size_of::<ffi::AVMasteringDisplayMetadata>(),
+            0,
+        );
+        if sd.is_null() {
+            return Err("Failed to allocate mastering display side data".into());
+        }
+        let mdm = (*sd).data as *mut ffi::AVMasteringDisplayMetadata;
+        std::ptr::write_bytes(mdm, 0, 1);
+        for (dst, src) in (*mdm).display_primaries.iter_mut().zip(md.primaries) {
+            *dst = [q(src[0], 50000), q(src[1], 50000)];
+        }
+        (*mdm).white_point = [q(md.white_point[0], 50000), q(md.white_point[1], 50000)];
+        (*mdm).min_luminance = q(md.min_luminance, 10000);
+        (*mdm).max_luminance = q(md.max_luminance, 10000);
+        (*mdm).has_primaries = 1;
+        (*mdm).has_luminance = 1;
+
+        if md.max_cll > 0 || md.max_fall > 0 {
+            let sd = ffi::av_packet_side_data_new(
+                &mut (*par).coded_side_data,
+                &mut (*par).nb_coded_side_data,
+                ffi::AVPacketSideDataType::AV_PKT_DATA_CONTENT_LIGHT_LEVEL,
+                std::mem::size_of::<ffi::AVContentLightMetadata>(),
+                0,
+            );
+            if sd.is_null() {
+                return Err("Failed to allocate content light level side data".into());
+            }
+            let cll = (*sd).data as *mut ffi::AVContentLightMetadata;
+            (*cll).MaxCLL = md.max_cll;
+            (*cll).MaxFALL = md.max_fall;
+        }
+    }
+    Ok(())
+}
+
+/// Draw the comp's review annotations for `frame_idx` into `frame` when
+/// `enabled` (annotations are comp pixels, so this runs before any resize).
+fn burn_annotations(
+    comp: &Comp,
+    frame: playa_engine::entities::Frame,
+    frame_idx: i32,
+    enabled: bool,
+) -> playa_engine::entities::Frame {
+    if !enabled {
+        return frame;
+    }
+    let annotations = comp.annotations_at(frame_idx);
+    playa_engine::entities::annotation::burn_in(&frame, &annotations)
+}
+
 /// Main encoding function (legacy cache-based)
 ///
+/// Frames to encode: `requested` clamped to the comp bounds, or the work area.
+///
+/// An inverted range is swapped; one entirely outside the comp collapses to
+/// the nearest edge frame.
+pub fn encode_range(comp: &Comp, requested: Option<(i32, i32)>) -> (i32, i32) {
+    let Some((a, b)) = requested else {
+        return comp.play_range(true);
+    };
+    let (comp_start, comp_end) = (comp._in(), comp._out());
+    let range = (
+        a.min(b).clamp(comp_start, comp_end),
+        a.max(b).clamp(comp_start, comp_end),
+    );
+    if range != (a, b) {
+        log::warn!(
+            "Encode range {}..{} clamped to {}..{} (comp {}..{})",
+            a,
+            b,
+            range.0,
+            range.1,
+            comp_start,
+            comp_end
+        );
+    }
+    range
+}
+
 /// Encodes sequence from cache play_range to output file.
 /// Runs in separate thread, sends progress updates via channel.
 pub fn encode_sequence_from_comp(
@@ -1259,8 +2256,8 @@ pub fn encode_sequence_from_comp(
         start_time
     );
 
-    // Get play range from Comp
-    let play_range = comp.play_range(true);
+    // Explicit range if requested, else the comp's play range
+    let play_range = encode_range(comp, settings.range);
     let total_frames = play_range.1.saturating_sub(play_range.0) + 1;
 
     info!(
@@ -1290,11 +2287,35 @@ pub fn encode_sequence_from_comp(
     })?;
 
     let (width, height) = first_frame.resolution();
-    let (width, height) = (width as u32, height as u32);
+    // Anamorphic source: either bake (stretch to square pixels, even width for
+    // 4:2:0 chroma) or keep the squeezed raster and signal SAR below.
+    let pixel_aspect = first_frame.pixel_aspect();
+    let anamorphic = (pixel_aspect - 1.0).abs() > 1e-4;
+    let bake_width = (settings.bake_pixel_aspect && anamorphic)
+        .then(|| (((width as f32 * pixel_aspect) / 2.0).round() as usize * 2).max(2));
+    let width = bake_width.unwrap_or(width);
+    let (width, height) = settings
+        .output_size
+        .unwrap_or((width as u32, height as u32));
     info!(
-        "Using first frame dimensions as target: {}x{}",
-        width, height
+        "Target dimensions: {}x{} ({}, PAR {:.3}{}, fit {:?})",
+        width,
+        height,
+        if settings.output_size.is_some() {
+            "explicit"
+        } else {
+            "first frame"
+        },
+        pixel_aspect,
+        if bake_width.is_some() { ", baked" } else { "" },
+        settings.fit_mode
     );
+    if width == 0 || height == 0 {
+        return Err(EncodeError::OutputCreateFailed(format!(
+            "Invalid output resolution {}x{}",
+            width, height
+        )));
+    }
 
     // Check for cancellation
     if cancel_flag.load(Ordering::Relaxed) {
@@ -1352,6 +2373,13 @@ pub fn encode_sequence_from_comp(
 
     encoder.set_width(width);
     encoder.set_height(height);
+    if anamorphic && bake_width.is_none() {
+        // Signal sample aspect ratio; players unsqueeze on display
+        encoder.set_aspect_ratio(ffmpeg::util::rational::Rational::new(
+            (pixel_aspect * 1000.0).round() as i32,
+            1000,
+        ));
+    }
 
     // Determine pixel format based on encoder
     // Hardware encoders (NVENC, QSV, AMF), AV1, and ProRes need YUV
@@ -1372,12 +2400,20 @@ pub fn encode_sequence_from_comp(
             | "libsvtav1"
             | "libaom-av1"
             | "prores_ks"
+            | "dnxhd"
+            | "vp9_nvenc"
+            | "vp9_qsv"
+            | "libvpx-vp9"
     );
 
     // Determine pixel format based on encoder and profile
+    let dnxhr_profile = settings.dnxhr_profile.unwrap_or(DNxHRProfile::HQ);
     let pixel_format = if encoder_name == "prores_ks" {
         // ProRes always uses YUV422P10 (10-bit 4:2:2)
         ffmpeg::format::Pixel::YUV422P10LE
+    } else if encoder_name == "dnxhd" {
+        // DNxHR: 8-bit or 10-bit 4:2:2, or 10-bit 4:4:4, fixed by profile
+        dnxhr_profile.pixel_format()
     } else if encoder_name == "libx265"
         || encoder_name == "hevc_nvenc"
         || encoder_name == "hevc_qsv"
@@ -1396,12 +2432,39 @@ pub fn encode_sequence_from_comp(
         } else {
             ffmpeg::format::Pixel::YUV420P // 8-bit 4:2:0
         }
+    } else if matches!(encoder_name, "libvpx-vp9" | "vp9_qsv" | "vp9_nvenc") {
+        // VP9: profile 2 is 10-bit; libvpx carries alpha (8-bit only) when
+        // the comp is transparent
+        if settings.profile.as_deref() == Some("2") {
+            ffmpeg::format::Pixel::YUV420P10LE
+        } else if encoder_name == "libvpx-vp9" && frame_has_alpha(&first_frame) {
+            ffmpeg::format::Pixel::YUVA420P
+        } else {
+            ffmpeg::format::Pixel::YUV420P
+        }
     } else if needs_yuv {
         ffmpeg::format::Pixel::YUV420P // 8-bit 4:2:0 for other YUV encoders
     } else {
         ffmpeg::format::Pixel::RGB24 // libx264 can use RGB24 directly
     };
 
+    // YUV chroma is subsampled 2x: a requested odd size can't be encoded as-is
+    if settings.output_size.is_some()
+        && pixel_format != ffmpeg::format::Pixel::RGB24
+        && (width % 2 != 0 || height % 2 != 0)
+    {
+        return Err(EncodeError::OutputCreateFailed(format!(
+            "Output resolution {}x{} must be even for {} ({:?})",
+            width, height, encoder_name, pixel_format
+        )));
+    }
+
+    if encoder_name == "dnxhd" {
+        dnxhr_profile
+            .validate(width, height)
+            .map_err(EncodeError::OutputCreateFailed)?;
+    }
+
     encoder.set_format(pixel_format);
     let (fps_num, fps_den) = fps_to_rational(settings.fps);
     encoder.set_frame_rate(Some(ffmpeg::util::rational::Rational::new(
@@ -1409,10 +2472,53 @@ pub fn encode_sequence_from_comp(
     )));
     encoder.set_time_base(ffmpeg::util::rational::Rational::new(fps_den, fps_num));
 
+    // Color description, written to the bitstream VUI and the container.
+    // PQ frames are encoded by us below, which only fits a 10-bit format.
+    let needs_10bit = matches!(
+        pixel_format,
+        ffmpeg::format::Pixel::YUV422P10LE
+            | ffmpeg::format::Pixel::YUV420P10LE
+            | ffmpeg::format::Pixel::YUV444P10LE
+    );
+    let pq = settings.color_trc == ColorTransfer::Pq;
+    if pq && !needs_10bit {
+        return Err(EncodeError::OutputCreateFailed(format!(
+            "PQ / HDR10 needs a 10-bit encode (H.265 main10 or ProRes), not {} ({:?})",
+            encoder_name, pixel_format
+        )));
+    }
+    encoder.set_colorspace(if pixel_format == ffmpeg::format::Pixel::RGB24 {
+        ffmpeg::color::Space::RGB // libx264 RGB input: no YUV matrix
+    } else {
+        settings.colorspace.to_ffmpeg()
+    });
+    encoder.set_color_range(settings.color_range.to_ffmpeg());
+    unsafe {
+        let ctx = encoder.as_mut_ptr();
+        (*ctx).color_primaries = settings.color_primaries.to_ffmpeg().into();
+        (*ctx).color_trc = settings.color_trc.to_ffmpeg().into();
+    }
+
     // Set GOP size (keyframe interval) for seekability
-    // GOP = 10 seconds (fps * 10) ensures keyframes for timeline scrubbing
-    let gop_size = (settings.fps.round() as i32 * 10).max(1);
+    let gop_size = gop_frames(settings);
+    let gop_size = match max_gop(encoder_name) {
+        Some(max) if gop_size > max => {
+            log::warn!(
+                "{} accepts a GOP of at most {} frames, clamping {} ({:.1}s)",
+                encoder_name,
+                max,
+                gop_size,
+                settings.gop_seconds
+            );
+            max
+        }
+        _ => gop_size,
+    };
     encoder.set_gop(gop_size as u32);
+    if settings.all_intra {
+        // Every frame a keyframe: no frame references, no reordering
+        encoder.set_max_b_frames(0);
+    }
 
     // Set quality parameters
     let mut opts = ffmpeg::Dictionary::new();
@@ -1463,6 +2569,11 @@ pub fn encode_sequence_from_comp(
                 {
                     opts.set("profile", profile); // "main" (8-bit) or "main10" (10-bit)
                 }
+
+                // HDR10 SEI (x265 doesn't pick it up from stream side data)
+                if let Some(md) = &settings.mastering_display {
+                    opts.set("x265-params", &md.x265_params());
+                }
             } else if encoder_name == "h264_qsv" || encoder_name == "hevc_qsv" {
                 // QSV uses global_quality
                 opts.set("global_quality", &settings.quality_value.to_string());
@@ -1513,6 +2624,30 @@ pub fn encode_sequence_from_comp(
                 {
                     opts.set("cpu-used", preset); // Map preset to cpu-used
                 }
+            } else if encoder_name == "libvpx-vp9" {
+                // libvpx-vp9: constant quality needs CRF with bitrate 0
+                opts.set("crf", &settings.quality_value.to_string());
+                opts.set("b", "0");
+                if let Some(ref preset) = settings.preset
+                    && !preset.is_empty()
+                {
+                    opts.set("cpu-used", preset); // 0-8 (0=slowest/best)
+                }
+                if let Some(ref profile) = settings.profile
+                    && !profile.is_empty()
+                {
+                    opts.set("profile", profile); // "0" (8-bit) or "2" (10-bit)
+                }
+                opts.set("row-mt", "1"); // Row-based multithreading
+                if pixel_format == ffmpeg::format::Pixel::YUVA420P {
+                    opts.set("auto-alt-ref", "0"); // Alt-ref frames don't support alpha
+                }
+            } else if encoder_name == "vp9_qsv" {
+                // QSV uses global_quality
+                opts.set("global_quality", &settings.quality_value.to_string());
+            } else if encoder_name == "vp9_nvenc" {
+                opts.set("rc", "constqp");
+                opts.set("qp", &settings.quality_value.to_string());
             } else if encoder_name == "prores_ks" {
                 // ProRes profile from settings or default to Standard
                 let profile = settings
@@ -1527,6 +2662,10 @@ pub fn encode_sequence_from_comp(
                 );
                 opts.set("profile", profile);
                 opts.set("vendor", "apl0"); // Apple vendor ID for compatibility
+            } else if encoder_name == "dnxhd" {
+                // DNxHR bitrate follows from profile + resolution + fps
+                info!("DNxHR encoding with profile {}", dnxhr_profile);
+                opts.set("profile", dnxhr_profile.to_ffmpeg_value());
             }
         }
         QualityMode::Bitrate => {
@@ -1545,6 +2684,9 @@ pub fn encode_sequence_from_comp(
         height
     );
 
+    // Bit-exact output: no encoder version strings, same bytes every run
+    opts.set("flags", "+bitexact");
+
     // Log all encoder options for debugging
     info!("Encoder options:");
     for (key, value) in opts.iter() {
@@ -1577,14 +2719,40 @@ pub fn encode_sequence_from_comp(
         info!("Set HEVC codec tag to 'hvc1' for Apple compatibility");
     }
 
+    // WebM alpha: Matroska only writes the alpha BlockAdditional with this tag
+    if pixel_format == ffmpeg::format::Pixel::YUVA420P {
+        let mut metadata = ffmpeg::Dictionary::new();
+        metadata.set("alpha_mode", "1");
+        ost.set_metadata(metadata);
+    }
+
+    // HDR10 static metadata on the stream (mov/mp4 write mdcv/clli boxes)
+    if let Some(md) = &settings.mastering_display {
+        attach_mastering_display(&mut ost, md).map_err(EncodeError::OutputCreateFailed)?;
+    }
+
     // Set container options (MP4: move moov atom to start for seekability)
     let mut container_opts = ffmpeg::Dictionary::new();
     if matches!(settings.container, Container::MP4) {
         container_opts.set("movflags", "faststart");
     }
+    // No muxer version / creation time: reproducible files
+    container_opts.set("fflags", "+bitexact");
+
+    // Slate tags + start timecode (mov/mp4 also get a tmcd track from it)
+    let timecode = settings
+        .metadata
+        .start_timecode(play_range.0, settings.fps)
+        .map_err(EncodeError::OutputCreateFailed)?;
+    let mut format_metadata = octx.metadata().to_owned();
+    for (key, value) in settings.metadata.tags() {
+        format_metadata.set(key, value);
+    }
+    format_metadata.set("timecode", &timecode);
+    info!("Container timecode: {}", timecode);
 
     // Write container header
-    octx.set_metadata(octx.metadata().to_owned());
+    octx.set_metadata(format_metadata);
     octx.write_header_with(container_opts)
         .map_err(|e| EncodeError::OutputCreateFailed(format!("Failed to write header: {}", e)))?;
 
@@ -1617,12 +2785,11 @@ pub fn encode_sequence_from_comp(
     info!("Starting encoding loop for {} frames", total_frames);
 
     // Create reusable swscale context for RGB→YUV conversion
-    let needs_10bit = pixel_format == ffmpeg::format::Pixel::YUV422P10LE
-        || pixel_format == ffmpeg::format::Pixel::YUV420P10LE;
-
     let mut sws_ctx = if needs_yuv {
         let src_format = if needs_10bit {
             ffmpeg::format::Pixel::RGB48LE // 10-bit: RGB48LE → YUV10
+        } else if pixel_format == ffmpeg::format::Pixel::YUVA420P {
+            ffmpeg::format::Pixel::RGBA // 8-bit with alpha: RGBA → YUVA420P
         } else {
             ffmpeg::format::Pixel::RGB24 // 8-bit: RGB24 → YUV420P
         };
@@ -1631,9 +2798,14 @@ pub fn encode_sequence_from_comp(
             src_format, pixel_format
         );
         Some(
-            SwsContext::new(src_format, pixel_format, width, height).map_err(|e| {
-                EncodeError::OutputCreateFailed(format!("Failed to create swscale context: {}", e))
-            })?,
+            SwsContext::new(src_format, pixel_format, width, height)
+                .and_then(|ctx| ctx.with_colorspace(settings.colorspace, settings.color_range))
+                .map_err(|e| {
+                    EncodeError::OutputCreateFailed(format!(
+                        "Failed to create swscale context: {}",
+                        e
+                    ))
+                })?,
         )
     } else {
         info!("Using RGB24 directly (no YUV conversion)");
@@ -1659,18 +2831,30 @@ pub fn encode_sequence_from_comp(
         }
 
         // Get composed frame from Comp
-        let frame = comp.get_frame(frame_idx, project, true).ok_or_else(|| {
+        let mut frame = comp.get_frame(frame_idx, project, true).ok_or_else(|| {
             EncodeError::EncodeFrameFailed(format!("Frame {} not available in comp", frame_idx))
         })?;
+        frame = burn_annotations(comp, frame, frame_idx, settings.burn_annotations);
+
+        // Bake anamorphic squeeze into square pixels
+        if let Some(w) = bake_width {
+            frame = frame.stretch_width(w);
+        }
 
-        // STEP 1: Crop to target dimensions if needed (handles mixed resolutions)
+        // STEP 1: Fit to target dimensions if needed (mixed resolutions / spec size)
         let (frame_width, frame_height) = frame.resolution();
         let frame_cropped = if frame_width != width as usize || frame_height != height as usize {
             info!(
-                "Cropping frame {} from {}x{} to {}x{}",
-                frame_idx, frame_width, frame_height, width, height
+                "Fitting frame {} from {}x{} to {}x{} ({:?})",
+                frame_idx, frame_width, frame_height, width, height, settings.fit_mode
             );
-            frame.crop_copy(width as usize, height as usize, CropAlign::Center)
+            frame.fit(
+                width as usize,
+                height as usize,
+                settings.fit_mode,
+                settings.crop_align,
+                ResizeFilter::default(),
+            )
         } else {
             frame.clone()
         };
@@ -1717,7 +2901,12 @@ pub fn encode_sequence_from_comp(
             if frame_idx % 10 == 0 {
                 info!("Frame {}: Converting RGBA → RGB48 (10-bit path)", frame_idx);
             }
-            let rgb48_data = frame_for_encode.to_rgb48().map_err(|e| {
+            let rgb48_data = if pq {
+                frame_for_encode.to_rgb48_pq(settings.color_primaries == ColorPrimaries::Bt2020)
+            } else {
+                frame_for_encode.to_rgb48()
+            }
+            .map_err(|e| {
                 EncodeError::EncodeFrameFailed(format!(
                     "Frame {} RGBA→RGB48 conversion failed: {}",
                     frame_idx, e
@@ -1737,6 +2926,16 @@ pub fn encode_sequence_from_comp(
                 .map_err(|e| {
                     EncodeError::EncodeFrameFailed(format!("RGB48→YUV10 conversion failed: {}", e))
                 })?
+        } else if pixel_format == ffmpeg::format::Pixel::YUVA420P {
+            // 8-bit alpha path: RGBA8 → YUVA420P
+            let rgba_data = pixel_buf_to_rgba8(&frame_for_encode.buffer());
+            sws_ctx
+                .as_mut()
+                .unwrap()
+                .convert(&rgba_data, width, height)
+                .map_err(|e| {
+                    EncodeError::EncodeFrameFailed(format!("RGBA→YUVA conversion failed: {}", e))
+                })?
         } else if needs_yuv {
             // 8-bit YUV path: RGBA8 → RGB24 → YUV420P
             let rgb24_data = frame_for_encode.to_rgb24().map_err(|e| {
@@ -1805,6 +3004,10 @@ pub fn encode_sequence_from_comp(
             }
             encoded.set_stream(0);
 
+            // Packet duration is 1 frame in encoder time_base units; set it
+            // before rescaling so it converts along with pts/dts
+            encoded.set_duration(1);
+
             // Rescale packet timestamps from encoder time_base to stream time_base
             // This is CRITICAL for proper MP4 timeline and seeking
             encoded.rescale_ts(encoder_tb, stream_tb);
@@ -1812,9 +3015,6 @@ pub fn encode_sequence_from_comp(
             // Set packet stream index
             encoded.set_stream(0);
 
-            // Set packet duration (1 frame in time_base units)
-            encoded.set_duration(1);
-
             // Ensure DTS is set (NVENC sometimes doesn't set it)
             let pts_val = encoded.pts();
             let dts_val = encoded.dts();
@@ -1889,15 +3089,15 @@ pub fn encode_sequence_from_comp(
             return Err(EncodeError::Cancelled);
         }
 
+        // Packet duration (1 frame in encoder time_base), rescaled with pts/dts
+        encoded.set_duration(1);
+
         // Rescale packet timestamps from encoder time_base to stream time_base
         encoded.rescale_ts(encoder_tb, stream_tb);
 
         // Set packet stream index
         encoded.set_stream(0);
 
-        // Set packet duration (1 frame in time_base units)
-        encoded.set_duration(1);
-
         // Ensure DTS is set
         if encoded.dts().is_none()
             && let Some(pts) = encoded.pts()
@@ -2042,7 +3242,22 @@ mod tests {
             preset: None,
             profile: None,
             prores_profile: None,
+            dnxhr_profile: None,
+            gop_seconds: DEFAULT_GOP_SECONDS,
+            all_intra: false,
             tonemap_mode: TonemapMode::default(),
+            bake_pixel_aspect: false,
+            burn_annotations: false,
+            output_size: None,
+            fit_mode: FitMode::default(),
+            crop_align: CropAlign::default(),
+            range: None,
+            color_primaries: ColorPrimaries::default(),
+            color_trc: ColorTransfer::default(),
+            colorspace: ColorMatrix::default(),
+            color_range: ColorRange::default(),
+            mastering_display: None,
+            metadata: EncodeMetadata::default(),
         };
 
         // Create progress channel
@@ -2141,56 +3356,619 @@ mod tests {
             Err(e) => Err(e),
         };
 
-        if let Err(EncodeError::EncoderNotFound) = &result {
-            println!("⚠ Encoder not available after fallbacks. Skipping encode test.");
-            return;
+        if let Err(EncodeError::EncoderNotFound) = &result {
+            println!("⚠ Encoder not available after fallbacks. Skipping encode test.");
+            return;
+        }
+
+        // Check progress updates
+        let mut last_progress: Option<EncodeProgress> = None;
+        while let Ok(progress) = rx.try_recv() {
+            last_progress = Some(progress);
+        }
+
+        // Verify encoding succeeded
+        assert!(result.is_ok(), "Encoding failed: {:?}", result);
+
+        // Verify output file exists and is not empty
+        assert!(final_output.exists(), "Output file was not created");
+        let metadata =
+            std::fs::metadata(&final_output).expect("Failed to read output file metadata");
+        assert!(metadata.len() > 0, "Output file is empty");
+
+        println!("✓ Encoding test passed!");
+        println!("  Encoder: {}", encoder_used);
+        let abs_path = std::fs::canonicalize(&final_output)
+            .unwrap_or_else(|_| std::env::current_dir().unwrap().join(&final_output));
+        println!("  Output: {}", abs_path.display());
+        println!(
+            "  Size: {} bytes ({:.2} KB)",
+            metadata.len(),
+            metadata.len() as f64 / 1024.0
+        );
+
+        // Verify progress reached completion
+        if let Some(progress) = last_progress {
+            assert_eq!(
+                progress.stage,
+                EncodeStage::Complete,
+                "Encoding did not complete"
+            );
+            println!(
+                "  Frames: {}/{} (play range: {}..{})",
+                progress.current_frame, progress.total_frames, play_start, play_end
+            );
+            assert_eq!(
+                progress.total_frames, 10,
+                "Should encode exactly 10 frames from play range 0..9"
+            );
+        }
+
+        // Cleanup
+        let _ = std::fs::remove_file(&output_path);
+    }
+
+    /// Test: cached capabilities agree with per-codec availability
+    #[test]
+    fn test_encode_capabilities() {
+        playa_io::init_ffmpeg().expect("Failed to init FFmpeg");
+        let caps = encode_capabilities();
+        assert!(std::ptr::eq(caps, encode_capabilities()));
+        for codec in VideoCodec::all() {
+            assert_eq!(VideoCodec::parse(codec.name()), Some(*codec));
+            assert_eq!(
+                !caps.video_encoders[codec.name()].is_empty(),
+                codec.is_available()
+            );
+        }
+        assert!(caps.sequence_formats.contains(&"exr"));
+        assert_eq!(
+            caps.exr_backend.is_some(),
+            !caps.exr_compressions.is_empty()
+        );
+    }
+
+    /// Test: VP9 is discovered when an encoder is installed and targets WebM
+    #[test]
+    fn test_vp9_encoder_discovery() {
+        playa_io::init_ffmpeg().expect("Failed to init FFmpeg");
+        assert_eq!(VideoCodec::VP9.preferred_container(), Container::WebM);
+        assert_eq!(Container::WebM.extension(), "webm");
+
+        let installed = ["vp9_nvenc", "vp9_qsv", "libvpx-vp9"]
+            .iter()
+            .any(|name| ffmpeg::encoder::find_by_name(name).is_some());
+        assert_eq!(VideoCodec::VP9.is_available(), installed);
+
+        if ffmpeg::encoder::find_by_name("libvpx-vp9").is_some() {
+            assert_eq!(
+                get_encoder_name(VideoCodec::VP9, EncoderImpl::Software).unwrap(),
+                "libvpx-vp9"
+            );
+            let auto = get_encoder_name(VideoCodec::VP9, EncoderImpl::Auto).unwrap();
+            assert!(ffmpeg::encoder::find_by_name(auto).is_some());
+        } else {
+            println!("⚠ libvpx-vp9 not available, skipping selection check");
+        }
+    }
+
+    /// Test: GOP follows fps * seconds, all-intra forces 1, HW limits known
+    #[test]
+    fn test_gop_frames_and_limits() {
+        let mut settings = EncoderSettings {
+            fps: 23.976,
+            gop_seconds: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(gop_frames(&settings), 48);
+        settings.gop_seconds = 0.0;
+        assert_eq!(gop_frames(&settings), 1);
+        settings.gop_seconds = 10.0;
+        settings.all_intra = true;
+        assert_eq!(gop_frames(&settings), 1);
+
+        assert_eq!(max_gop("libx264"), None);
+        assert_eq!(max_gop("h264_amf"), Some(1000));
+        assert_eq!(max_gop("hevc_qsv"), Some(65535));
+    }
+
+    /// Test: explicit range overrides the work area and is clamped to the comp
+    #[test]
+    fn test_encode_range_clamps_to_comp() {
+        let mut comp = playa_engine::entities::CompNode::new("range", 0, 99, 24.0);
+        comp.mark_play_start(10);
+        comp.mark_play_end(20);
+        assert_eq!(encode_range(&comp, None), (10, 20));
+
+        assert_eq!(encode_range(&comp, Some((40, 89))), (40, 89));
+        assert_eq!(encode_range(&comp, Some((89, 40))), (40, 89));
+        assert_eq!(encode_range(&comp, Some((-10, 150))), (0, 99));
+        assert_eq!(encode_range(&comp, Some((200, 300))), (99, 99));
+    }
+
+    /// Test: codec and sequence format names parse case-insensitively
+    #[test]
+    fn test_codec_and_format_names() {
+        assert_eq!(VideoCodec::parse("HEVC"), Some(VideoCodec::H265));
+        assert_eq!(VideoCodec::parse("prores"), Some(VideoCodec::ProRes));
+        assert_eq!(VideoCodec::parse("mpeg2"), None);
+        assert_eq!(
+            SequenceFormat::from_extension("JPEG"),
+            Some(SequenceFormat::Jpeg)
+        );
+        assert_eq!(
+            SequenceFormat::from_extension("tif"),
+            Some(SequenceFormat::Tiff)
+        );
+        assert_eq!(SequenceFormat::from_extension("mov"), None);
+    }
+
+    /// Test: sequence files numbered by comp frame, a fixed start or the source
+    #[test]
+    fn test_sequence_frame_numbering() {
+        use playa_engine::entities::{FileNode, NodeKind, NodeLayer};
+
+        let mut project =
+            playa_engine::entities::project::Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let mut comp = playa_engine::entities::CompNode::new("numbering", 0, 99, 24.0);
+        let mut settings = SequenceSettings::default();
+        assert_eq!(frame_number_offset(&settings, &comp, &project, 10), 0);
+
+        settings.numbering = FrameNumbering::StartAt;
+        assert_eq!(frame_number_offset(&settings, &comp, &project, 10), 991);
+        let (prefix, pattern, suffix) = parse_padding_pattern("render.####.exr");
+        let path = build_frame_path(
+            std::path::Path::new("out"),
+            &prefix,
+            &pattern,
+            &suffix,
+            10,
+            991,
+        );
+        assert_eq!(path, std::path::Path::new("out").join("render.1001.exr"));
+
+        // No file layer yet: source numbering falls back to comp frames
+        settings.numbering = FrameNumbering::Source;
+        assert_eq!(frame_number_offset(&settings, &comp, &project, 10), 0);
+
+        // plate.0101-0200 placed at comp frame 5: comp 10 is file 106
+        let plate = FileNode::new("plate.*.exr".to_string(), 101, 200, 24.0);
+        let plate_uuid = plate.uuid();
+        project.add_node(NodeKind::File(plate));
+        comp.add_layer(NodeLayer::new(plate_uuid, "plate", 5, 100, (64, 64)), None);
+        assert_eq!(frame_number_offset(&settings, &comp, &project, 10), 96);
+    }
+
+    /// Test: explicit padding overrides the file name, bad ranges are refused
+    #[test]
+    fn test_sequence_padding_overrides() {
+        let mut settings = SequenceSettings::default();
+        let name = |settings: &SequenceSettings, filename: &str, n: i32| {
+            let (prefix, pattern, suffix) = settings.filename_pattern(filename);
+            format!("{}{}{}", prefix, pattern.format(n), suffix)
+        };
+        assert_eq!(name(&settings, "render.###.exr", 7), "render.007.exr");
+        assert_eq!(name(&settings, "render.exr", 7), "render.7.exr");
+
+        settings.padding_width = 5;
+        assert_eq!(name(&settings, "render.###.exr", 7), "render.00007.exr");
+        assert_eq!(name(&settings, "render.exr", 7), "render.00007.exr");
+
+        settings.padding_style = PaddingStyle::Hashes;
+        let (prefix, pattern, suffix) = settings.filename_pattern("render.%03d.exr");
+        assert_eq!(
+            format!("{}{}{}", prefix, pattern.token(), suffix),
+            "render.#####.exr"
+        );
+
+        assert_eq!(
+            sequence_name_range(&prefix, &pattern, &suffix, (1001, 1100)),
+            Ok(("render.01001.exr".into(), "render.01100.exr".into()))
+        );
+        let narrow = PaddingPattern::Printf { width: 1 };
+        assert!(sequence_name_range("f.", &narrow, ".png", (1, 12)).is_err());
+        // Unpadded numbers grow freely
+        assert!(sequence_name_range("f.", &PaddingPattern::At, ".png", (1, 12)).is_ok());
+    }
+
+    /// 64x64 RGBA8 frame of horizontal bands (compresses well)
+    fn banded_frame() -> (playa_engine::entities::Frame, Vec<u8>) {
+        use playa_engine::entities::frame::PixelBuffer;
+        let mut data = Vec::with_capacity(64 * 64 * 4);
+        for y in 0..64u8 {
+            for _ in 0..64 {
+                data.extend_from_slice(&[y * 4, 128, 255 - y, 255]);
+            }
+        }
+        let frame = playa_engine::entities::Frame::from_buffer(
+            PixelBuffer::U8(data.clone()),
+            PixelFormat::Rgba8,
+            64,
+            64,
+        );
+        (frame, data)
+    }
+
+    /// Test: hash manifest sits next to the sequence and round-trips
+    #[test]
+    fn test_hash_manifest() {
+        let dir = std::env::temp_dir().join(format!("playa_hashes_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = hash_manifest_path(&dir.join("shot.####.exr"));
+        assert_eq!(path, dir.join("shot.hashes.json"));
+        assert_eq!(
+            hash_manifest_path(&dir.join("%04d.png")),
+            dir.join("hashes.json")
+        );
+
+        let (frame, _) = banded_frame();
+        let manifest: HashManifest = [(-1, frame.content_hash()), (1001, "00ff".to_string())]
+            .into_iter()
+            .collect();
+        write_hash_manifest(&path, &manifest).unwrap();
+        assert_eq!(read_hash_manifest(&path).unwrap(), manifest);
+        assert!(read_hash_manifest(&dir.join("missing.json")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Test: TIFF compression is applied and lossless
+    #[test]
+    fn test_tiff_compression_applied() {
+        let (frame, pixels) = banded_frame();
+        let dir = std::env::temp_dir().join(format!("playa_tiff_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut sizes = Vec::new();
+        for compression in [TiffCompression::None, TiffCompression::Lzw] {
+            let path = dir.join(format!("{}.tif", compression));
+            let settings = TiffSequenceSettings {
+                bit_depth: TiffBitDepth::Eight,
+                compression,
+            };
+            write_tiff_frame(
+                &frame,
+                &path,
+                &settings,
+                ChannelMode::Rgba,
+                OutputBitDepth::U8,
+            )
+            .unwrap();
+            let decoded = image::open(&path).unwrap().to_rgba8();
+            assert_eq!(decoded.as_raw(), &pixels, "{} round trip", compression);
+            sizes.push(std::fs::metadata(&path).unwrap().len());
+        }
+        assert!(sizes[1] < sizes[0], "LZW {} >= raw {}", sizes[1], sizes[0]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Test: TGA RLE follows the setting and is lossless
+    #[test]
+    fn test_tga_rle_applied() {
+        let (frame, pixels) = banded_frame();
+        let dir = std::env::temp_dir().join(format!("playa_tga_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut sizes = Vec::new();
+        for rle_compression in [false, true] {
+            let path = dir.join(format!("rle_{}.tga", rle_compression));
+            let settings = TgaSequenceSettings { rle_compression };
+            write_tga_frame(&frame, &path, &settings, ChannelMode::Rgba).unwrap();
+            let decoded = image::open(&path).unwrap().to_rgba8();
+            assert_eq!(decoded.as_raw(), &pixels);
+            sizes.push(std::fs::metadata(&path).unwrap().len());
+        }
+        assert!(sizes[1] < sizes[0], "RLE {} >= raw {}", sizes[1], sizes[0]);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Test: EXR header compression is parsed and DWAA is actually written
+    #[test]
+    fn test_exr_compression_verified() {
+        // Minimal header: magic, version, one channel attr, compression = PIZ
+        let mut header = vec![0x76, 0x2f, 0x31, 0x01, 2, 0, 0, 0];
+        for (name, kind, value) in [
+            ("channels", "chlist", &[0u8][..]),
+            ("compression", "compression", &[4u8][..]),
+        ] {
+            header.extend_from_slice(name.as_bytes());
+            header.push(0);
+            header.extend_from_slice(kind.as_bytes());
+            header.push(0);
+            header.extend_from_slice(&(value.len() as u32).to_le_bytes());
+            header.extend_from_slice(value);
+        }
+        header.push(0);
+        assert_eq!(exr_header_compression(&header[..]), Some(4));
+        assert_eq!(
+            ExrCompression::from_header_code(4),
+            Some(ExrCompression::Piz)
+        );
+        assert_eq!(exr_header_compression(&b"not an exr"[..]), None);
+
+        let (frame, _) = banded_frame();
+        let dir = std::env::temp_dir().join(format!("playa_exr_dwa_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("dwaa.exr");
+        let settings = ExrSequenceSettings {
+            compression: ExrCompression::Dwaa,
+            ..Default::default()
+        };
+        write_exr_frame(
+            &frame,
+            &path,
+            &settings,
+            ChannelMode::Rgba,
+            OutputBitDepth::F16,
+            &[],
+        )
+        .unwrap();
+        let file = std::fs::File::open(&path).unwrap();
+        assert_eq!(exr_header_compression(file), Some(8));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    /// Test: DNxHR profiles map to dnxhd and reject unsupported sizes
+    #[test]
+    fn test_dnxhr_profile_validation() {
+        assert_eq!(DNxHRProfile::HQX.to_ffmpeg_value(), "dnxhr_hqx");
+        assert_eq!(
+            DNxHRProfile::R444.pixel_format(),
+            ffmpeg::format::Pixel::YUV444P10LE
+        );
+        assert_eq!(VideoCodec::DNxHR.preferred_container(), Container::MOV);
+
+        for profile in DNxHRProfile::all() {
+            assert!(profile.validate(1920, 1080).is_ok());
+            assert!(profile.validate(4096, 2160).is_ok());
+            assert!(profile.validate(128, 72).is_err(), "{profile} too small");
+        }
+        // Odd width only fits 4:4:4
+        assert!(DNxHRProfile::HQ.validate(1921, 1080).is_err());
+        assert!(DNxHRProfile::R444.validate(1921, 1080).is_ok());
+    }
+
+    /// Test: HDR10 encode is tagged BT.2020/PQ with mastering metadata, and
+    /// encoding the same comp twice gives identical bytes
+    #[test]
+    fn test_hdr10_tags_round_trip() {
+        playa_io::init_ffmpeg().expect("Failed to init FFmpeg");
+        if ffmpeg::encoder::find_by_name("libx265").is_none() {
+            println!("⚠ libx265 not available, skipping HDR10 probe");
+            return;
+        }
+
+        let dir = std::env::temp_dir().join(format!("playa_hdr10_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let mut settings = EncoderSettings {
+            container: Container::MOV,
+            codec: VideoCodec::H265,
+            encoder_impl: EncoderImpl::Software,
+            quality_mode: QualityMode::CRF,
+            quality_value: 28,
+            preset: Some("ultrafast".to_string()),
+            profile: Some("main10".to_string()),
+            ..Default::default()
+        };
+        ColorPreset::Hdr10.apply(&mut settings);
+
+        let mut comp = playa_engine::entities::CompNode::new("hdr", 0, 4, settings.fps);
+        comp.attrs.set(
+            playa_engine::entities::keys::A_WIDTH,
+            playa_engine::entities::AttrValue::UInt(64),
+        );
+        comp.attrs.set(
+            playa_engine::entities::keys::A_HEIGHT,
+            playa_engine::entities::AttrValue::UInt(64),
+        );
+        let project =
+            playa_engine::entities::project::Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+
+        let mut outputs = Vec::new();
+        for name in ["a.mov", "b.mov"] {
+            settings.output_path = dir.join(name);
+            let (tx, _rx) = std::sync::mpsc::channel();
+            encode_comp(
+                &comp,
+                &project,
+                &settings,
+                tx,
+                Arc::new(AtomicBool::new(false)),
+            )
+            .expect("HDR10 encode failed");
+            outputs.push(std::fs::read(&settings.output_path).unwrap());
         }
+        assert_eq!(outputs[0], outputs[1], "encode is not reproducible");
+
+        // Probe the stream back
+        let ictx = ffmpeg::format::input(&settings.output_path).unwrap();
+        let stream = ictx.streams().best(ffmpeg::media::Type::Video).unwrap();
+        let has_mastering = unsafe {
+            let par = stream.parameters().as_ptr();
+            !ffmpeg::ffi::av_packet_side_data_get(
+                (*par).coded_side_data,
+                (*par).nb_coded_side_data,
+                ffmpeg::ffi::AVPacketSideDataType::AV_PKT_DATA_MASTERING_DISPLAY_METADATA,
+            )
+            .is_null()
+        };
+        let decoder = ffmpeg::codec::context::Context::from_parameters(stream.parameters())
+            .unwrap()
+            .decoder()
+            .video()
+            .unwrap();
+        assert_eq!(decoder.color_primaries(), ffmpeg::color::Primaries::BT2020);
+        assert_eq!(
+            decoder.color_transfer_characteristic(),
+            ffmpeg::color::TransferCharacteristic::SMPTE2084
+        );
+        assert_eq!(decoder.color_space(), ffmpeg::color::Space::BT2020NCL);
+        assert_eq!(decoder.color_range(), ffmpeg::color::Range::MPEG);
+        assert!(has_mastering, "mastering display metadata missing");
+
+        // 8-bit PQ is refused up front
+        settings.profile = Some("main".to_string());
+        let (tx, _rx) = std::sync::mpsc::channel();
+        let result = encode_comp(
+            &comp,
+            &project,
+            &settings,
+            tx,
+            Arc::new(AtomicBool::new(false)),
+        );
+        assert!(matches!(result, Err(EncodeError::OutputCreateFailed(_))));
 
-        // Check progress updates
-        let mut last_progress: Option<EncodeProgress> = None;
-        while let Ok(progress) = rx.try_recv() {
-            last_progress = Some(progress);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Test: start timecode defaults to the in-point and parses user values
+    #[test]
+    fn test_metadata_start_timecode() {
+        let mut metadata = EncodeMetadata::default();
+        assert_eq!(metadata.start_timecode(1001, 24.0).unwrap(), "00:00:41:17");
+        assert_eq!(metadata.start_timecode(-5, 24.0).unwrap(), "00:00:00:00");
+        assert_eq!(metadata.tags().count(), 0);
+
+        metadata.timecode = "01:00:00:00".to_string();
+        assert_eq!(metadata.start_timecode(1001, 25.0).unwrap(), "01:00:00:00");
+        metadata.timecode = "00:59:59;29".to_string();
+        assert_eq!(metadata.start_timecode(0, 29.97).unwrap(), "00:59:59;29");
+        metadata.timecode = "not a timecode".to_string();
+        assert!(metadata.start_timecode(0, 24.0).is_err());
+
+        metadata.artist = "  Comp Dept ".to_string();
+        assert_eq!(
+            metadata.tags().collect::<Vec<_>>(),
+            vec![("artist", "Comp Dept")]
+        );
+    }
+
+    /// Test: slate tags and the start timecode survive muxing
+    #[test]
+    fn test_metadata_survives_muxing() {
+        playa_io::init_ffmpeg().expect("Failed to init FFmpeg");
+        if ffmpeg::encoder::find_by_name("libx264").is_none() {
+            println!("⚠ libx264 not available, skipping metadata probe");
+            return;
         }
 
-        // Verify encoding succeeded
-        assert!(result.is_ok(), "Encoding failed: {:?}", result);
+        let dir = std::env::temp_dir().join(format!("playa_metadata_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let settings = EncoderSettings {
+            output_path: dir.join("slate.mov"),
+            container: Container::MOV,
+            encoder_impl: EncoderImpl::Software,
+            preset: Some("ultrafast".to_string()),
+            metadata: EncodeMetadata {
+                title: "sh010 comp v003".to_string(),
+                artist: "Comp Dept".to_string(),
+                comment: "Fix edge flicker".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
 
-        // Verify output file exists and is not empty
-        assert!(final_output.exists(), "Output file was not created");
-        let metadata =
-            std::fs::metadata(&final_output).expect("Failed to read output file metadata");
-        assert!(metadata.len() > 0, "Output file is empty");
+        let mut comp = playa_engine::entities::CompNode::new("slate", 1001, 1005, settings.fps);
+        comp.attrs.set(
+            playa_engine::entities::keys::A_WIDTH,
+            playa_engine::entities::AttrValue::UInt(64),
+        );
+        comp.attrs.set(
+            playa_engine::entities::keys::A_HEIGHT,
+            playa_engine::entities::AttrValue::UInt(64),
+        );
+        let project =
+            playa_engine::entities::project::Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let (tx, _rx) = std::sync::mpsc::channel();
+        encode_comp(
+            &comp,
+            &project,
+            &settings,
+            tx,
+            Arc::new(AtomicBool::new(false)),
+        )
+        .expect("metadata encode failed");
+
+        let ictx = ffmpeg::format::input(&settings.output_path).unwrap();
+        let format_metadata = ictx.metadata();
+        assert_eq!(format_metadata.get("title"), Some("sh010 comp v003"));
+        assert_eq!(format_metadata.get("artist"), Some("Comp Dept"));
+        assert_eq!(format_metadata.get("comment"), Some("Fix edge flicker"));
+
+        // mov exposes the tmcd track's start on the format and/or its stream
+        let mut timecodes: Vec<String> = format_metadata
+            .get("timecode")
+            .map(str::to_string)
+            .into_iter()
+            .collect();
+        for stream in ictx.streams() {
+            if let Some(tc) = stream.metadata().get("timecode") {
+                timecodes.push(tc.to_string());
+            }
+        }
+        assert!(
+            timecodes.iter().any(|tc| tc == "00:00:41:17"),
+            "timecode tag missing: {:?}",
+            timecodes
+        );
 
-        println!("✓ Encoding test passed!");
-        println!("  Encoder: {}", encoder_used);
-        let abs_path = std::fs::canonicalize(&final_output)
-            .unwrap_or_else(|_| std::env::current_dir().unwrap().join(&final_output));
-        println!("  Output: {}", abs_path.display());
-        println!(
-            "  Size: {} bytes ({:.2} KB)",
-            metadata.len(),
-            metadata.len() as f64 / 1024.0
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    /// Test: contact sheet frames are evenly spaced and include both ends
+    #[test]
+    fn test_contact_sheet_frames() {
+        assert_eq!(
+            contact_sheet_frames((1001, 1100), 4),
+            vec![1001, 1034, 1067, 1100]
         );
+        assert_eq!(contact_sheet_frames((0, 2), 12), vec![0, 1, 2]);
+        assert_eq!(contact_sheet_frames((10, 20), 1), vec![10]);
+        assert_eq!(contact_sheet_frames((5, 5), 0), vec![5]);
+    }
 
-        // Verify progress reached completion
-        if let Some(progress) = last_progress {
-            assert_eq!(
-                progress.stage,
-                EncodeStage::Complete,
-                "Encoding did not complete"
-            );
-            println!(
-                "  Frames: {}/{} (play range: {}..{})",
-                progress.current_frame, progress.total_frames, play_start, play_end
-            );
-            assert_eq!(
-                progress.total_frames, 10,
-                "Should encode exactly 10 frames from play range 0..9"
-            );
-        }
+    /// Test: tiles land in a grid with spacing; empty cells show the background
+    #[test]
+    fn test_contact_sheet_layout() {
+        use playa_engine::entities::frame::PixelBuffer;
+
+        let tiles: Vec<(i32, playa_engine::entities::Frame)> = (0..5u8)
+            .map(|i| {
+                let color = [i * 50, 200 - i * 40, 100, 255];
+                let frame = playa_engine::entities::Frame::from_buffer(
+                    PixelBuffer::U8(color.repeat(32 * 16)),
+                    PixelFormat::Rgba8,
+                    32,
+                    16,
+                );
+                (i as i32, frame)
+            })
+            .collect();
+        let sheet = ContactSheetSettings {
+            columns: 2,
+            thumb_width: 16,
+            spacing: 4,
+            label_frames: false,
+            ..Default::default()
+        };
 
-        // Cleanup
-        let _ = std::fs::remove_file(&output_path);
+        // 2x3 grid of 16x8 tiles with 4px gaps
+        let out = build_contact_sheet(&tiles, &sheet);
+        assert_eq!(out.resolution(), (44, 40));
+        let px = pixel_buf_to_rgba8(out.buffer().as_ref());
+        let at = |x: usize, y: usize| &px[(y * 44 + x) * 4..][..4];
+
+        // Tile 3 = row 1, column 1
+        let tile = at(4 + 20 + 8, 4 + 12 + 4);
+        for (got, want) in tile.iter().zip([150u8, 80, 100, 255]) {
+            assert!(got.abs_diff(want) <= 1, "{:?}", tile);
+        }
+        assert_eq!(at(0, 0), SHEET_BACKGROUND); // border
+        assert_eq!(at(30, 30), SHEET_BACKGROUND); // empty last cell
     }
 }
 
@@ -2222,6 +4000,16 @@ fn f16_to_f32_buf(data: &[half::f16]) -> Vec<f32> {
     data.iter().map(|v| v.to_f32()).collect()
 }
 
+/// True if any pixel is not fully opaque.
+fn frame_has_alpha(frame: &playa_engine::entities::Frame) -> bool {
+    use playa_engine::entities::frame::PixelBuffer;
+    match frame.buffer().as_ref() {
+        PixelBuffer::U8(data) => data.chunks_exact(4).any(|p| p[3] < 255),
+        PixelBuffer::F16(data) => data.chunks_exact(4).any(|p| p[3].to_f32() < 1.0),
+        PixelBuffer::F32(data) => data.chunks_exact(4).any(|p| p[3] < 1.0),
+    }
+}
+
 /// Convert any PixelBuffer variant to packed RGBA u8 (clamped, LDR).
 fn pixel_buf_to_rgba8(buffer: &playa_engine::entities::frame::PixelBuffer) -> Vec<u8> {
     use playa_engine::entities::frame::PixelBuffer;
@@ -2341,6 +4129,8 @@ fn source_exr_attrs_from_project(
 
 /// Write frame to EXR file using vfx-io (pure Rust, all compressions).
 ///
+/// The written header is checked against the requested compression, so a
+/// backend lacking a codec errors out instead of writing something else.
 /// `extra_attrs` carries the source EXR's absorbed (and possibly user-edited)
 /// header attributes for round-trip; empty for non-EXR sources.
 fn write_exr_frame(
@@ -2452,7 +4242,72 @@ fn write_exr_frame(
             playa_io::IoError::LoadError(s) => format!("EXR write failed (load): {s}"),
             playa_io::IoError::UnsupportedFormat(s) => format!("EXR write failed (format): {s}"),
         })
-    })
+    })?;
+
+    // A backend built without a codec (e.g. vfx-io without `htj2k`) may fall
+    // back to another compression; fail the frame instead of shipping it.
+    let written = File::open(path)
+        .ok()
+        .and_then(|f| exr_header_compression(std::io::BufReader::new(f)));
+    if written != Some(settings.compression.header_code()) {
+        let _ = std::fs::remove_file(path);
+        let written = written
+            .map(|code| match ExrCompression::from_header_code(code) {
+                Some(c) => c.to_string(),
+                None => format!("unknown code {}", code),
+            })
+            .unwrap_or_else(|| "no compression attribute".to_string());
+        return Err(EncodeError::EncodeFrameFailed(format!(
+            "EXR backend does not support {} compression (file had {})",
+            settings.compression, written
+        )));
+    }
+    Ok(())
+}
+
+/// `compression` attribute of an EXR's (first part) header, read without
+/// decoding pixels. `None` if the stream is not EXR or the attribute is missing.
+fn exr_header_compression(mut reader: impl std::io::Read) -> Option<u8> {
+    // Header strings are NUL-terminated, at most 255 bytes with long names
+    fn read_cstr(reader: &mut impl std::io::Read) -> Option<String> {
+        let mut bytes = Vec::new();
+        let mut byte = [0u8; 1];
+        loop {
+            reader.read_exact(&mut byte).ok()?;
+            if byte[0] == 0 {
+                return String::from_utf8(bytes).ok();
+            }
+            if bytes.len() >= 255 {
+                return None;
+            }
+            bytes.push(byte[0]);
+        }
+    }
+
+    // Magic number + version/flags
+    let mut magic = [0u8; 8];
+    reader.read_exact(&mut magic).ok()?;
+    if magic[..4] != [0x76, 0x2f, 0x31, 0x01] {
+        return None;
+    }
+    loop {
+        let name = read_cstr(&mut reader)?;
+        if name.is_empty() {
+            return None; // end of header
+        }
+        let kind = read_cstr(&mut reader)?;
+        let mut size = [0u8; 4];
+        reader.read_exact(&mut size).ok()?;
+        let size = u32::from_le_bytes(size) as usize;
+        if size > 1 << 24 {
+            return None;
+        }
+        let mut value = vec![0u8; size];
+        reader.read_exact(&mut value).ok()?;
+        if name == "compression" && kind == "compression" {
+            return value.first().copied();
+        }
+    }
 }
 
 /// Pass-through EXR transcode: read the source EXR for `frame_idx` via vfx-io
@@ -2681,6 +4536,9 @@ fn write_jpeg_frame(
 }
 
 /// Write frame to TIFF file
+///
+/// Goes through the `tiff` crate directly: `image`'s TIFF encoder always
+/// writes uncompressed strips.
 fn write_tiff_frame(
     frame: &playa_engine::entities::Frame,
     path: &std::path::Path,
@@ -2688,41 +4546,30 @@ fn write_tiff_frame(
     channels: ChannelMode,
     bit_depth: OutputBitDepth,
 ) -> Result<(), EncodeError> {
-    use image::{ImageBuffer, Rgb, Rgba};
     use playa_engine::entities::frame::PixelBuffer;
+    use tiff::encoder::{TiffEncoder, colortype};
 
     let buffer = frame.buffer();
     let (width, height) = frame.resolution();
+    let (width, height) = (width as u32, height as u32);
+
+    let file = File::create(path).map_err(|e| {
+        EncodeError::OutputCreateFailed(format!("Failed to create TIFF file: {}", e))
+    })?;
+    let mut encoder = TiffEncoder::new(BufWriter::new(file))
+        .map_err(|e| EncodeError::OutputCreateFailed(format!("TIFF header failed: {}", e)))?
+        .with_compression(settings.compression.to_tiff());
 
     // TIFF supports U8 and U16
-    match bit_depth {
+    let written = match bit_depth {
         OutputBitDepth::U8 => {
             let rgba_data = pixel_buf_to_rgba8(buffer.as_ref());
             match channels {
                 ChannelMode::Rgba => {
-                    let img: ImageBuffer<Rgba<u8>, Vec<u8>> = ImageBuffer::from_raw(
-                        width as u32,
-                        height as u32,
-                        rgba_data,
-                    )
-                    .ok_or_else(|| {
-                        EncodeError::EncodeFrameFailed("Failed to create TIFF buffer".into())
-                    })?;
-                    img.save(path).map_err(|e| {
-                        EncodeError::EncodeFrameFailed(format!("TIFF save failed: {}", e))
-                    })?;
+                    encoder.write_image::<colortype::RGBA8>(width, height, &rgba_data)
                 }
                 ChannelMode::Rgb => {
-                    let img: ImageBuffer<Rgb<u8>, Vec<u8>> =
-                        ImageBuffer::from_raw(width as u32, height as u32, strip_alpha(&rgba_data))
-                            .ok_or_else(|| {
-                                EncodeError::EncodeFrameFailed(
-                                    "Failed to create TIFF buffer".into(),
-                                )
-                            })?;
-                    img.save(path).map_err(|e| {
-                        EncodeError::EncodeFrameFailed(format!("TIFF save failed: {}", e))
-                    })?;
+                    encoder.write_image::<colortype::RGB8>(width, height, &strip_alpha(&rgba_data))
                 }
             }
         }
@@ -2742,46 +4589,34 @@ fn write_tiff_frame(
 
             match channels {
                 ChannelMode::Rgba => {
-                    let img: ImageBuffer<Rgba<u16>, Vec<u16>> =
-                        ImageBuffer::from_raw(width as u32, height as u32, rgba16_data)
-                            .ok_or_else(|| {
-                                EncodeError::EncodeFrameFailed(
-                                    "Failed to create TIFF16 buffer".into(),
-                                )
-                            })?;
-                    img.save(path).map_err(|e| {
-                        EncodeError::EncodeFrameFailed(format!("TIFF16 save failed: {}", e))
-                    })?;
-                }
-                ChannelMode::Rgb => {
-                    let img: ImageBuffer<Rgb<u16>, Vec<u16>> = ImageBuffer::from_raw(
-                        width as u32,
-                        height as u32,
-                        strip_alpha(&rgba16_data),
-                    )
-                    .ok_or_else(|| {
-                        EncodeError::EncodeFrameFailed("Failed to create TIFF16 buffer".into())
-                    })?;
-                    img.save(path).map_err(|e| {
-                        EncodeError::EncodeFrameFailed(format!("TIFF16 save failed: {}", e))
-                    })?;
+                    encoder.write_image::<colortype::RGBA16>(width, height, &rgba16_data)
                 }
+                ChannelMode::Rgb => encoder.write_image::<colortype::RGB16>(
+                    width,
+                    height,
+                    &strip_alpha(&rgba16_data),
+                ),
             }
         }
-    }
+    };
 
-    let _ = settings.compression; // TODO: image crate doesn't expose TIFF compression settings easily
-    Ok(())
+    written.map_err(|e| {
+        EncodeError::EncodeFrameFailed(format!(
+            "TIFF encode failed ({}): {}",
+            settings.compression, e
+        ))
+    })
 }
 
-/// Write frame to TGA file
+/// Write frame to TGA file (RLE packets unless `rle_compression` is off)
 fn write_tga_frame(
     frame: &playa_engine::entities::Frame,
     path: &std::path::Path,
-    _settings: &TgaSequenceSettings,
+    settings: &TgaSequenceSettings,
     channels: ChannelMode,
 ) -> Result<(), EncodeError> {
-    use image::{ImageBuffer, Rgb, Rgba};
+    use image::ImageEncoder;
+    use image::codecs::tga::TgaEncoder;
     use playa_engine::entities::frame::PixelBuffer;
 
     let buffer = frame.buffer();
@@ -2796,27 +4631,22 @@ fn write_tga_frame(
         }
     };
 
-    match channels {
-        ChannelMode::Rgba => {
-            let img: ImageBuffer<Rgba<u8>, Vec<u8>> =
-                ImageBuffer::from_raw(width as u32, height as u32, rgba_data).ok_or_else(|| {
-                    EncodeError::EncodeFrameFailed("Failed to create TGA buffer".into())
-                })?;
-            img.save(path)
-                .map_err(|e| EncodeError::EncodeFrameFailed(format!("TGA save failed: {}", e)))?;
-        }
-        ChannelMode::Rgb => {
-            let img: ImageBuffer<Rgb<u8>, Vec<u8>> =
-                ImageBuffer::from_raw(width as u32, height as u32, strip_alpha(&rgba_data))
-                    .ok_or_else(|| {
-                        EncodeError::EncodeFrameFailed("Failed to create TGA buffer".into())
-                    })?;
-            img.save(path)
-                .map_err(|e| EncodeError::EncodeFrameFailed(format!("TGA save failed: {}", e)))?;
-        }
+    let file = File::create(path).map_err(|e| {
+        EncodeError::OutputCreateFailed(format!("Failed to create TGA file: {}", e))
+    })?;
+    let mut encoder = TgaEncoder::new(BufWriter::new(file));
+    if !settings.rle_compression {
+        encoder = encoder.disable_rle();
     }
 
-    // TODO: RLE compression when image crate supports it
+    let (data, color) = match channels {
+        ChannelMode::Rgba => (rgba_data, image::ExtendedColorType::Rgba8),
+        ChannelMode::Rgb => (strip_alpha(&rgba_data), image::ExtendedColorType::Rgb8),
+    };
+    encoder
+        .write_image(&data, width as u32, height as u32, color)
+        .map_err(|e| EncodeError::EncodeFrameFailed(format!("TGA encode failed: {}", e)))?;
+
     Ok(())
 }
 
@@ -2861,10 +4691,14 @@ pub fn encode_image_sequence(
         })?;
     }
 
-    let (prefix, pattern, suffix) = parse_padding_pattern(filename);
+    let (prefix, pattern, suffix) = settings.filename_pattern(filename);
+    let number_offset = frame_number_offset(settings, comp, project, play_range.0);
+    let files = (play_range.0 + number_offset, play_range.1 + number_offset);
+    sequence_name_range(&prefix, &pattern, &suffix, files)
+        .map_err(EncodeError::OutputCreateFailed)?;
     info!(
-        "Pattern parsed: prefix='{}', pattern={:?}, suffix='{}'",
-        prefix, pattern, suffix
+        "Pattern parsed: prefix='{}', pattern={:?}, suffix='{}', numbering={:?} ({:+})",
+        prefix, pattern, suffix, settings.numbering, number_offset
     );
 
     // Stage 1: Validating
@@ -2900,6 +4734,7 @@ pub fn encode_image_sequence(
     // encoded EXR frame. Constant across frames — computed once. Empty for non-EXR
     // sources or DisplayOnly with no EXR source present.
     let source_exr_attrs = source_exr_attrs_from_project(project);
+    let mut hashes = HashManifest::new();
 
     for frame_idx in play_range.0..=play_range.1 {
         // Check for cancellation
@@ -2913,6 +4748,13 @@ pub fn encode_image_sequence(
         let frame = comp.get_frame(frame_idx, project, true).ok_or_else(|| {
             EncodeError::EncodeFrameFailed(format!("Frame {} not available", frame_idx))
         })?;
+        // Hash the comp output (before burn-in / tonemap), as `--verify-hashes` renders it
+        if settings.write_hashes {
+            let hash = frame.content_hash();
+            info!("Frame {} hash {}", frame_idx, hash);
+            hashes.insert(frame_idx, hash);
+        }
+        let frame = burn_annotations(comp, frame, frame_idx, settings.burn_annotations);
 
         // Apply tonemapping if needed (HDR -> LDR for non-EXR formats)
         let frame_to_write = if settings.apply_tonemap
@@ -2926,64 +4768,26 @@ pub fn encode_image_sequence(
         };
 
         // Build output path for this frame
-        let frame_path = build_frame_path(base_dir, &prefix, &pattern, &suffix, frame_idx);
+        let frame_path = build_frame_path(
+            base_dir,
+            &prefix,
+            &pattern,
+            &suffix,
+            frame_idx,
+            number_offset,
+        );
 
         if frame_idx % 10 == 0 {
             info!("Writing frame {} -> {}", frame_idx, frame_path.display());
         }
 
-        // Write frame based on format
-        match settings.format {
-            SequenceFormat::Exr => {
-                let exr_settings = &settings.format_settings.exr;
-                let did_pass_through = match exr_settings.mode {
-                    ExrEncodeMode::PassThrough => {
-                        write_exr_pass_through(project, frame_idx, &frame_path)?
-                    }
-                    ExrEncodeMode::DisplayOnly => false,
-                };
-                if !did_pass_through {
-                    // Either DisplayOnly mode or pass-through couldn't find an
-                    // EXR source — fall back to compositor-output single-layer write.
-                    write_exr_frame(
-                        &frame_to_write,
-                        &frame_path,
-                        exr_settings,
-                        settings.channels,
-                        settings.bit_depth,
-                        &source_exr_attrs,
-                    )?;
-                }
-            }
-            SequenceFormat::Png => {
-                write_png_frame(
-                    &frame_to_write,
-                    &frame_path,
-                    &settings.format_settings.png,
-                    settings.channels,
-                    settings.bit_depth,
-                )?;
-            }
-            SequenceFormat::Jpeg => {
-                write_jpeg_frame(&frame_to_write, &frame_path, &settings.format_settings.jpeg)?;
-            }
-            SequenceFormat::Tiff => {
-                write_tiff_frame(
-                    &frame_to_write,
-                    &frame_path,
-                    &settings.format_settings.tiff,
-                    settings.channels,
-                    settings.bit_depth,
-                )?;
-            }
-            SequenceFormat::Tga => {
-                write_tga_frame(
-                    &frame_to_write,
-                    &frame_path,
-                    &settings.format_settings.tga,
-                    settings.channels,
-                )?;
-            }
+        // EXR pass-through copies source layers; everything else (and pass-through
+        // without an EXR source) writes the compositor output.
+        let did_pass_through = settings.format == SequenceFormat::Exr
+            && settings.format_settings.exr.mode == ExrEncodeMode::PassThrough
+            && write_exr_pass_through(project, frame_idx, &frame_path)?;
+        if !did_pass_through {
+            write_sequence_frame(&frame_to_write, &frame_path, settings, &source_exr_attrs)?;
         }
 
         // Update progress
@@ -2999,6 +4803,12 @@ pub fn encode_image_sequence(
         }
     }
 
+    if settings.write_hashes {
+        let manifest_path = hash_manifest_path(output_path);
+        write_hash_manifest(&manifest_path, &hashes)?;
+        info!("Hash manifest written: {}", manifest_path.display());
+    }
+
     // Stage 3: Complete
     let _ = progress_tx.send(EncodeProgress {
         current_frame: total_frames,
@@ -3017,6 +4827,248 @@ pub fn encode_image_sequence(
     Ok(())
 }
 
+/// Renders evenly spaced work-area frames into one contact sheet image.
+/// Runs in separate thread, sends progress updates via channel.
+///
+/// Writer options (JPEG quality, PNG compression) come from `settings`; the
+/// sheet itself is opaque 8-bit sRGB.
+pub fn encode_contact_sheet(
+    comp: &Comp,
+    project: &playa_engine::entities::Project,
+    output_path: &std::path::Path,
+    settings: &SequenceSettings,
+    sheet: &ContactSheetSettings,
+    progress_tx: Sender<EncodeProgress>,
+    cancel_flag: Arc<AtomicBool>,
+) -> Result<(), EncodeError> {
+    let play_range = comp.play_range(true);
+    let frames = contact_sheet_frames(play_range, sheet.frames);
+    let total_frames = frames.len() as i32;
+    let path = update_extension(output_path, sheet.format);
+    info!(
+        "Contact sheet: {} frames of {:?}, {} columns, {}px tiles -> {}",
+        total_frames,
+        play_range,
+        sheet.columns,
+        sheet.thumb_width,
+        path.display()
+    );
+
+    if progress_tx
+        .send(EncodeProgress {
+            current_frame: 0,
+            total_frames,
+            stage: EncodeStage::Validating,
+        })
+        .is_err()
+    {
+        return Err(EncodeError::Cancelled);
+    }
+
+    if let Some(dir) = path.parent()
+        && !dir.as_os_str().is_empty()
+        && !dir.exists()
+    {
+        std::fs::create_dir_all(dir).map_err(|e| {
+            EncodeError::OutputCreateFailed(format!("Failed to create output directory: {}", e))
+        })?;
+    }
+
+    let mut tiles = Vec::with_capacity(frames.len());
+    for (i, &frame_idx) in frames.iter().enumerate() {
+        if cancel_flag.load(Ordering::Relaxed) {
+            return Err(EncodeError::Cancelled);
+        }
+        let frame = comp.get_frame(frame_idx, project, true).ok_or_else(|| {
+            EncodeError::EncodeFrameFailed(format!("Frame {} not available", frame_idx))
+        })?;
+        let frame = burn_annotations(comp, frame, frame_idx, sheet.burn_annotations);
+        tiles.push((frame_idx, frame));
+
+        if progress_tx
+            .send(EncodeProgress {
+                current_frame: i as i32 + 1,
+                total_frames,
+                stage: EncodeStage::Encoding,
+            })
+            .is_err()
+        {
+            return Err(EncodeError::Cancelled);
+        }
+    }
+
+    let sheet_frame = build_contact_sheet(&tiles, sheet);
+    let mut write_settings = settings.clone();
+    write_settings.format = sheet.format;
+    write_settings.channels = ChannelMode::Rgb;
+    write_settings.bit_depth = OutputBitDepth::U8;
+    write_settings.validate();
+    write_sequence_frame(&sheet_frame, &path, &write_settings, &[])?;
+
+    let _ = progress_tx.send(EncodeProgress {
+        current_frame: total_frames,
+        total_frames,
+        stage: EncodeStage::Complete,
+    });
+    info!(
+        "Contact sheet written: {}x{} -> {}",
+        sheet_frame.width(),
+        sheet_frame.height(),
+        path.display()
+    );
+    Ok(())
+}
+
+/// Sheet background (8-bit sRGB)
+const SHEET_BACKGROUND: [u8; 4] = [24, 24, 24, 255];
+
+/// Frame number label color
+const SHEET_LABEL_COLOR: [f32; 4] = [0.85, 0.85, 0.85, 1.0];
+
+/// Lay `tiles` (frame number, frame) out in a grid on an opaque 8-bit sheet.
+///
+/// Tiles share one size, taken from the first frame's display aspect; each is
+/// resized like a Project panel thumbnail (unpremultiplied, sRGB, clamped).
+fn build_contact_sheet(
+    tiles: &[(i32, playa_engine::entities::Frame)],
+    sheet: &ContactSheetSettings,
+) -> playa_engine::entities::Frame {
+    use playa_engine::entities::Frame;
+    use playa_engine::entities::frame::PixelBuffer;
+    use playa_engine::entities::text_node::TextNode;
+
+    let (src_w, src_h, aspect) = tiles
+        .first()
+        .map(|(_, f)| (f.width().max(1), f.height().max(1), f.pixel_aspect()))
+        .unwrap_or((1, 1, 1.0));
+    let tile_w = sheet.thumb_width.max(16) as usize;
+    let tile_h = ((tile_w as f32 * src_h as f32 / (src_w as f32 * aspect)).round() as usize).max(1);
+    let font_size = (tile_w as f32 / 16.0).clamp(10.0, 48.0);
+    let label_h = if sheet.label_frames {
+        (font_size * 1.5).ceil() as usize
+    } else {
+        0
+    };
+    let gap = sheet.spacing as usize;
+    let cols = (sheet.columns.max(1) as usize).min(tiles.len().max(1));
+    let rows = tiles.len().div_ceil(cols).max(1);
+    let sheet_w = cols * tile_w + (cols + 1) * gap;
+    let sheet_h = rows * (tile_h + label_h) + (rows + 1) * gap;
+
+    let mut pixels = SHEET_BACKGROUND.repeat(sheet_w * sheet_h);
+    for (i, (frame_idx, frame)) in tiles.iter().enumerate() {
+        let x = gap + (i % cols) * (tile_w + gap);
+        let y = gap + (i / cols) * (tile_h + label_h + gap);
+
+        let thumb = frame
+            .unpremultiply()
+            .resize(tile_w, tile_h, ResizeFilter::Bilinear)
+            .to_srgb();
+        let thumb = if thumb.pixel_format() == PixelFormat::Rgba8 {
+            thumb
+        } else {
+            thumb.tonemap(TonemapMode::Clamp).unwrap_or(thumb)
+        };
+        blend_onto_sheet(&mut pixels, sheet_w, &thumb, x, y);
+
+        if sheet.label_frames {
+            let mut text = TextNode::new("label", &frame_idx.to_string());
+            text.set_font_size(font_size);
+            text.set_color(SHEET_LABEL_COLOR);
+            let label = text.render_text();
+            let lx = x + tile_w.saturating_sub(label.width()) / 2;
+            let ly = y + tile_h + label_h.saturating_sub(label.height()) / 2;
+            blend_onto_sheet(&mut pixels, sheet_w, &label, lx, ly);
+        }
+    }
+
+    let out = Frame::from_buffer(
+        PixelBuffer::U8(pixels),
+        PixelFormat::Rgba8,
+        sheet_w,
+        sheet_h,
+    );
+    out.set_color_space(playa_engine::entities::frame::ColorSpace::Srgb);
+    out
+}
+
+/// Alpha-blend a straight-alpha frame over the opaque RGBA8 `sheet` at (x, y),
+/// clipped to the sheet.
+fn blend_onto_sheet(
+    sheet: &mut [u8],
+    sheet_w: usize,
+    src: &playa_engine::entities::Frame,
+    x: usize,
+    y: usize,
+) {
+    let src_px = pixel_buf_to_rgba8(src.buffer().as_ref());
+    let (w, h) = src.resolution();
+    let sheet_h = sheet.len() / 4 / sheet_w.max(1);
+    for row in 0..h.min(sheet_h.saturating_sub(y)) {
+        for col in 0..w.min(sheet_w.saturating_sub(x)) {
+            let s = &src_px[(row * w + col) * 4..][..4];
+            let d = &mut sheet[((y + row) * sheet_w + x + col) * 4..][..3];
+            let a = s[3] as u32;
+            for (dc, &sc) in d.iter_mut().zip(s) {
+                *dc = ((sc as u32 * a + *dc as u32 * (255 - a) + 127) / 255) as u8;
+            }
+        }
+    }
+}
+
+/// Write one image with the writer for `settings.format`.
+///
+/// `extra_attrs` are EXR header attributes to round-trip (ignored by other formats).
+fn write_sequence_frame(
+    frame: &playa_engine::entities::Frame,
+    path: &std::path::Path,
+    settings: &SequenceSettings,
+    extra_attrs: &[(String, playa_io::exr_layered::AttrValue)],
+) -> Result<(), EncodeError> {
+    match settings.format {
+        SequenceFormat::Exr => {
+            write_exr_frame(
+                frame,
+                path,
+                &settings.format_settings.exr,
+                settings.channels,
+                settings.bit_depth,
+                extra_attrs,
+            )?;
+        }
+        SequenceFormat::Png => {
+            write_png_frame(
+                frame,
+                path,
+                &settings.format_settings.png,
+                settings.channels,
+                settings.bit_depth,
+            )?;
+        }
+        SequenceFormat::Jpeg => {
+            write_jpeg_frame(frame, path, &settings.format_settings.jpeg)?;
+        }
+        SequenceFormat::Tiff => {
+            write_tiff_frame(
+                frame,
+                path,
+                &settings.format_settings.tiff,
+                settings.channels,
+                settings.bit_depth,
+            )?;
+        }
+        SequenceFormat::Tga => {
+            write_tga_frame(
+                frame,
+                path,
+                &settings.format_settings.tga,
+                settings.channels,
+            )?;
+        }
+    }
+    Ok(())
+}
+
 // ============================================================================
 // Frame format conversion utilities (SwsContext)
 // ============================================================================
@@ -3031,6 +5083,8 @@ pub struct SwsContext {
     dst_format: ffmpeg::format::Pixel,
     width: u32,
     height: u32,
+    /// RGB→YUV matrix (`SWS_CS_*`) and full-range output, if set
+    colorspace: Option<(i32, bool)>,
 }
 
 impl SwsContext {
@@ -3058,16 +5112,54 @@ impl SwsContext {
             dst_format,
             width,
             height,
+            colorspace: None,
         })
     }
 
-    /// Convert RGB24 data to destination format (YUV420P, YUV422P10, etc.)
+    /// Use `matrix` and `range` for the YUV output instead of swscale's
+    /// default (BT.601, limited), so pixels match the stream's color tags.
+    pub fn with_colorspace(
+        mut self,
+        matrix: ColorMatrix,
+        range: ColorRange,
+    ) -> Result<Self, String> {
+        self.colorspace = Some((matrix.sws_colorspace(), range == ColorRange::Full));
+        self.apply_colorspace()?;
+        Ok(self)
+    }
+
+    fn apply_colorspace(&mut self) -> Result<(), String> {
+        let (Some(ctx), Some((colorspace, full_range))) = (self.ctx.as_mut(), self.colorspace)
+        else {
+            return Ok(());
+        };
+        let ret = unsafe {
+            let coeffs = ffmpeg::ffi::sws_getCoefficients(colorspace);
+            ffmpeg::ffi::sws_setColorspaceDetails(
+                ctx.as_mut_ptr(),
+                coeffs.cast(),
+                1, // source is full-range RGB
+                coeffs.cast(),
+                full_range as i32,
+                0,
+                1 << 16,
+                1 << 16,
+            )
+        };
+        if ret < 0 {
+            return Err(format!("swscale colorspace {} not supported", colorspace));
+        }
+        Ok(())
+    }
+
+    /// Convert packed 8-bit RGB24 (or RGBA, if that is the source format) data
+    /// to destination format (YUV420P, YUVA420P, YUV422P10, etc.)
     ///
     /// Uses the destination format specified during SwsContext creation.
     /// Reuses internal swscale context. Recreates if dimensions change.
     ///
     /// # Arguments
-    /// * `rgb24_data` - RGB24 pixel data (width * height * 3 bytes)
+    /// * `rgb24_data` - RGB24 pixel data (width * height * 3 bytes, 4 for RGBA)
     /// * `width` - Frame width
     /// * `height` - Frame height
     ///
@@ -3080,10 +5172,16 @@ impl SwsContext {
         height: u32,
     ) -> Result<ffmpeg::util::frame::video::Video, String> {
         // Validate input size
-        let expected_size = (width * height * 3) as usize;
+        let channels = if self.src_format == ffmpeg::format::Pixel::RGBA {
+            4
+        } else {
+            3
+        };
+        let expected_size = (width * height * channels) as usize;
         if rgb24_data.len() != expected_size {
             return Err(format!(
-                "Invalid RGB24 data size: expected {} bytes, got {}",
+                "Invalid {:?} data size: expected {} bytes, got {}",
+                self.src_format,
                 expected_size,
                 rgb24_data.len()
             ));
@@ -3099,7 +5197,7 @@ impl SwsContext {
 
         // Copy RGB24 data to source frame
         let src_stride = src_frame.stride(0);
-        let row_bytes = (width * 3) as usize;
+        let row_bytes = (width * channels) as usize;
 
         {
             let dst_data = src_frame.data_mut(0);
@@ -3209,6 +5307,6 @@ impl SwsContext {
         );
         self.width = width;
         self.height = height;
-        Ok(())
+        self.apply_colorspace()
     }
 }
@@ -23,17 +23,19 @@ use eframe::egui;
 use log::info;
 
 use crate::dialogs::encode::{
-    ChannelMode, CodecSettings, Container, EncodeError, EncodeProgress, EncodeStage, EncoderImpl,
-    EncoderSettings, ExportMode, ExrCompression, ExrEncodeMode, OutputBitDepth, ProResProfile,
-    QualityMode, SequenceFormat, SequenceSettings, TiffBitDepth, TiffCompression, VideoCodec,
+    ChannelMode, CodecSettings, ColorPreset, ContactSheetSettings, Container, DEFAULT_GOP_SECONDS,
+    DEFAULT_START_NUMBER, DNxHRProfile, EncodeError, EncodeMetadata, EncodeProgress, EncodeStage,
+    EncoderImpl, EncoderSettings, ExportMode, ExrCompression, ExrEncodeMode, FrameNumbering,
+    OutputBitDepth, PaddingStyle, ProResProfile, QualityMode, SequenceFormat, SequenceSettings,
+    TiffBitDepth, TiffCompression, VideoCodec, frame_number_offset, sequence_name_range,
 };
 use egui_encode_dialog::{
     Codec, EncodeDialog as EncodeWidget, EncodeDialogResult, EncodeOption, EncodeSchema,
     EncodeSettings as WidgetSettings, Format, ShowConfig,
 };
 use egui_progressbar::ProgressBar;
-use playa_engine::entities::frame::TonemapMode;
-use playa_engine::entities::{Comp, Project};
+use playa_engine::entities::frame::{CropAlign, FitMode, TonemapMode};
+use playa_engine::entities::{Comp, Node, Project};
 
 /// Encoding dialog state.
 ///
@@ -83,6 +85,45 @@ pub struct EncodeDialog {
 
     /// Image sequence settings
     pub sequence_settings: SequenceSettings,
+
+    /// Contact sheet layout
+    pub contact_sheet: ContactSheetSettings,
+
+    /// Bake non-square pixels to square on video encode (else signal SAR)
+    pub bake_pixel_aspect: bool,
+
+    /// Burn review annotations into video frames
+    pub burn_annotations: bool,
+
+    /// Video output resolution (None = source)
+    pub output_size: Option<(u32, u32)>,
+
+    /// How frames of another size fit the output resolution
+    pub fit_mode: FitMode,
+
+    /// Placement for crop / letterbox fits
+    pub crop_align: CropAlign,
+
+    /// Color tags (primaries / transfer / matrix / range) for video encodes
+    pub color_preset: ColorPreset,
+
+    /// Keyframe interval in seconds (long-GOP codecs)
+    pub gop_seconds: f32,
+
+    /// Every frame a keyframe (overrides `gop_seconds`)
+    pub all_intra: bool,
+
+    /// Title / artist / comment / copyright tags and start timecode (video)
+    pub metadata: EncodeMetadata,
+
+    /// Explicit frames to encode (None = comp work area); per session, not persisted
+    pub encode_range: Option<(i32, i32)>,
+
+    /// Active comp's in/out marks, refreshed each render (range defaults)
+    comp_marks: (i32, i32),
+
+    /// Active comp's full frame bounds, refreshed each render (range limits)
+    comp_bounds: (i32, i32),
 }
 
 impl EncodeDialog {
@@ -116,6 +157,10 @@ impl EncodeDialog {
             "  ProRes: profile={:?}",
             settings.codec_settings.prores.profile
         );
+        log::trace!(
+            "  DNxHR: profile={:?}",
+            settings.codec_settings.dnxhr.profile
+        );
         log::trace!(
             "  AV1: impl={:?}, mode={:?}, value={}, preset={}",
             settings.codec_settings.av1.encoder_impl,
@@ -123,7 +168,21 @@ impl EncodeDialog {
             settings.codec_settings.av1.quality_value,
             settings.codec_settings.av1.preset
         );
+        log::trace!(
+            "  VP9: impl={:?}, mode={:?}, value={}, speed={}, profile={}",
+            settings.codec_settings.vp9.encoder_impl,
+            settings.codec_settings.vp9.quality_mode,
+            settings.codec_settings.vp9.quality_value,
+            settings.codec_settings.vp9.preset,
+            settings.codec_settings.vp9.profile
+        );
         log::trace!("  Tonemap: {:?}", settings.tonemap_mode);
+        log::trace!(
+            "  Output: {:?}, fit={:?}, align={:?}",
+            settings.output_size,
+            settings.fit_mode,
+            settings.crop_align
+        );
         log::trace!("  ExportMode: {:?}", settings.export_mode);
         log::trace!(
             "  Sequence: format={:?}, channels={:?}, depth={:?}",
@@ -148,6 +207,19 @@ impl EncodeDialog {
             tonemap_mode: settings.tonemap_mode,
             export_mode: settings.export_mode,
             sequence_settings: settings.sequence_settings.clone(),
+            contact_sheet: settings.contact_sheet.clone(),
+            bake_pixel_aspect: settings.bake_pixel_aspect,
+            burn_annotations: settings.burn_annotations,
+            output_size: settings.output_size,
+            fit_mode: settings.fit_mode,
+            crop_align: settings.crop_align,
+            color_preset: settings.color_preset,
+            gop_seconds: settings.gop_seconds,
+            all_intra: settings.all_intra,
+            metadata: settings.metadata.clone(),
+            encode_range: None,
+            comp_marks: (0, 0),
+            comp_bounds: (0, 0),
         }
     }
 
@@ -178,6 +250,7 @@ impl EncodeDialog {
             self.codec_settings.h265.profile
         );
         log::trace!("  ProRes: profile={:?}", self.codec_settings.prores.profile);
+        log::trace!("  DNxHR: profile={:?}", self.codec_settings.dnxhr.profile);
         log::trace!(
             "  AV1: impl={:?}, mode={:?}, value={}, preset={}",
             self.codec_settings.av1.encoder_impl,
@@ -185,7 +258,21 @@ impl EncodeDialog {
             self.codec_settings.av1.quality_value,
             self.codec_settings.av1.preset
         );
+        log::trace!(
+            "  VP9: impl={:?}, mode={:?}, value={}, speed={}, profile={}",
+            self.codec_settings.vp9.encoder_impl,
+            self.codec_settings.vp9.quality_mode,
+            self.codec_settings.vp9.quality_value,
+            self.codec_settings.vp9.preset,
+            self.codec_settings.vp9.profile
+        );
         log::trace!("  Tonemap: {:?}", self.tonemap_mode);
+        log::trace!(
+            "  Output: {:?}, fit={:?}, align={:?}",
+            self.output_size,
+            self.fit_mode,
+            self.crop_align
+        );
         log::trace!("  ExportMode: {:?}", self.export_mode);
         log::trace!(
             "  Sequence: format={:?}, channels={:?}, depth={:?}",
@@ -203,6 +290,16 @@ impl EncodeDialog {
             codec_settings: self.codec_settings.clone(),
             export_mode: self.export_mode,
             sequence_settings: self.sequence_settings.clone(),
+            contact_sheet: self.contact_sheet.clone(),
+            bake_pixel_aspect: self.bake_pixel_aspect,
+            burn_annotations: self.burn_annotations,
+            output_size: self.output_size,
+            fit_mode: self.fit_mode,
+            crop_align: self.crop_align,
+            color_preset: self.color_preset,
+            gop_seconds: self.gop_seconds,
+            all_intra: self.all_intra,
+            metadata: self.metadata.clone(),
         }
     }
 
@@ -235,6 +332,14 @@ impl EncodeDialog {
                     None,
                     None,
                 ),
+                VideoCodec::VP9 => (
+                    self.codec_settings.vp9.encoder_impl,
+                    self.codec_settings.vp9.quality_mode,
+                    self.codec_settings.vp9.quality_value,
+                    Some(self.codec_settings.vp9.preset.clone()),
+                    Some(self.codec_settings.vp9.profile.clone()),
+                    None,
+                ),
                 VideoCodec::ProRes => (
                     crate::dialogs::encode::EncoderImpl::Software,
                     crate::dialogs::encode::QualityMode::CRF,
@@ -243,9 +348,17 @@ impl EncodeDialog {
                     None,
                     Some(self.codec_settings.prores.profile),
                 ),
+                VideoCodec::DNxHR => (
+                    crate::dialogs::encode::EncoderImpl::Software,
+                    crate::dialogs::encode::QualityMode::CRF,
+                    0, // DNxHR bitrate is fixed by profile
+                    None,
+                    None,
+                    None,
+                ),
             };
 
-        EncoderSettings {
+        let mut settings = EncoderSettings {
             output_path: self.output_path.clone(),
             container: self.container,
             codec: self.selected_codec,
@@ -256,8 +369,22 @@ impl EncodeDialog {
             preset,
             profile,
             prores_profile,
+            dnxhr_profile: (self.selected_codec == VideoCodec::DNxHR)
+                .then_some(self.codec_settings.dnxhr.profile),
+            gop_seconds: self.gop_seconds,
+            all_intra: self.all_intra,
             tonemap_mode: self.tonemap_mode,
-        }
+            bake_pixel_aspect: self.bake_pixel_aspect,
+            burn_annotations: self.burn_annotations,
+            output_size: self.output_size,
+            fit_mode: self.fit_mode,
+            crop_align: self.crop_align,
+            range: self.encode_range,
+            metadata: self.metadata.clone(),
+            ..Default::default()
+        };
+        self.color_preset.apply(&mut settings);
+        settings
     }
 
     /// Check if encoding is currently in progress
@@ -318,6 +445,12 @@ impl EncodeDialog {
             // settings widget owns its modal and does not render progress).
             self.render_progress_window(ctx, &mut should_close);
         } else {
+            // Range options default to / are limited by the active comp
+            if let Some(comp) = active_comp {
+                self.comp_marks = comp.play_range(true);
+                self.comp_bounds = (comp._in(), comp._out());
+            }
+
             // Settings UI: build the schema + working settings from the model, show
             // the generic widget, then mirror its result back into the model.
             let schema = self.build_schema();
@@ -326,13 +459,25 @@ impl EncodeDialog {
                 title: "Export".to_string(),
                 width: 600.0,
                 show_browse: true,
-                // Frame range stays "use active Comp" (the worker derives it from the
-                // comp's play_range), so we hide the widget's range row.
+                // Frame range is our own "Encode marked range" video option (the
+                // worker falls back to the comp's play_range), so we hide the
+                // widget's range row.
                 show_frame_range: false,
                 start_label: "Encode".to_string(),
             };
             let result = widget.show(ctx, &cfg);
 
+            // The widget has no free-text options; tags get a small companion window
+            if self.export_mode == ExportMode::Video {
+                self.render_metadata_window(ctx);
+            }
+            // Sequences: live preview of the names the padding settings produce
+            if self.export_mode == ExportMode::Sequence
+                && let Some(comp) = active_comp
+            {
+                self.render_filenames_window(ctx, comp, project);
+            }
+
             // Host-owned Browse: the widget only signals intent; we run rfd ourselves.
             if widget.take_browse_request() {
                 let mut fd = rfd::FileDialog::new();
@@ -366,11 +511,107 @@ impl EncodeDialog {
         !should_close
     }
 
+    /// Metadata fields shown next to the settings widget for video exports.
+    fn render_metadata_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new("Export Metadata")
+            .id(egui::Id::new("encode_metadata"))
+            .order(egui::Order::Foreground)
+            .resizable(false)
+            .collapsible(true)
+            .default_open(false)
+            .show(ctx, |ui| {
+                let meta = &mut self.metadata;
+                egui::Grid::new("encode_metadata_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for (label, value) in [
+                            ("Title", &mut meta.title),
+                            ("Artist", &mut meta.artist),
+                            ("Comment", &mut meta.comment),
+                            ("Copyright", &mut meta.copyright),
+                        ] {
+                            ui.label(label);
+                            ui.text_edit_singleline(value);
+                            ui.end_row();
+                        }
+                        ui.label("Start timecode");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut meta.timecode)
+                                .hint_text("comp in-point"),
+                        );
+                        ui.end_row();
+                    });
+
+                // Same check the encoder runs, so bad input shows before encoding
+                if let Err(e) = meta.start_timecode(self.comp_marks.0, self.fps) {
+                    ui.colored_label(egui::Color32::LIGHT_RED, e);
+                }
+            });
+    }
+
+    /// First / last exported file name for the current output path, padding
+    /// and numbering, with the same validation the exporter runs.
+    fn render_filenames_window(&self, ctx: &egui::Context, comp: &Comp, project: &Project) {
+        let filename = self
+            .output_path
+            .file_name()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default();
+        let seq = &self.sequence_settings;
+        let (prefix, pattern, suffix) = seq.filename_pattern(filename);
+        let range = self.comp_marks;
+        let offset = frame_number_offset(seq, comp, project, range.0);
+        let names = sequence_name_range(
+            &prefix,
+            &pattern,
+            &suffix,
+            (range.0 + offset, range.1 + offset),
+        );
+
+        egui::Window::new("Sequence Files")
+            .id(egui::Id::new("encode_filenames"))
+            .order(egui::Order::Foreground)
+            .resizable(false)
+            .collapsible(true)
+            .show(ctx, |ui| match names {
+                Ok((first, last)) => {
+                    egui::Grid::new("encode_filenames_grid")
+                        .num_columns(2)
+                        .show(ui, |ui| {
+                            ui.label("First");
+                            ui.monospace(first);
+                            ui.end_row();
+                            ui.label("Last");
+                            ui.monospace(last);
+                            ui.end_row();
+                        });
+                }
+                Err(e) => {
+                    ui.colored_label(egui::Color32::LIGHT_RED, e);
+                }
+            });
+    }
+
+    /// Output path with the frame field spelled as exported (explicit padding
+    /// style / width applied), so the stored path matches the files on disk.
+    fn sequence_output_path(&self) -> PathBuf {
+        let Some(filename) = self.output_path.file_name().and_then(|s| s.to_str()) else {
+            return self.output_path.clone();
+        };
+        let (prefix, pattern, suffix) = self.sequence_settings.filename_pattern(filename);
+        if pattern.width() == 0 {
+            return self.output_path.clone();
+        }
+        self.output_path
+            .with_file_name(format!("{}{}{}", prefix, pattern.token(), suffix))
+    }
+
     /// Progress window shown while encoding (the widget renders no progress).
     fn render_progress_window(&mut self, ctx: &egui::Context, should_close: &mut bool) {
         let window_title = match self.export_mode {
             ExportMode::Video => "Video Encoder",
             ExportMode::Sequence => "Image Sequence Export",
+            ExportMode::ContactSheet => "Contact Sheet Export",
         };
         egui::Window::new(window_title)
             .id(egui::Id::new("encode_progress"))
@@ -429,6 +670,8 @@ impl EncodeDialog {
                 VideoCodec::H265 => ("mp4", "h265"),
                 VideoCodec::AV1 => ("mp4", "av1"),
                 VideoCodec::ProRes => ("mov", "prores"),
+                VideoCodec::DNxHR => ("mov", "dnxhr"),
+                VideoCodec::VP9 => ("webm", "vp9"),
             },
             ExportMode::Sequence => match self.sequence_settings.format {
                 SequenceFormat::Exr => ("exr", "exr"),
@@ -437,6 +680,10 @@ impl EncodeDialog {
                 SequenceFormat::Tiff => ("tiff", "tiff"),
                 SequenceFormat::Tga => ("tga", "tga"),
             },
+            ExportMode::ContactSheet => match self.contact_sheet.format {
+                SequenceFormat::Png => ("sheet_png", "sheet_png"),
+                _ => ("sheet_jpg", "sheet_jpeg"),
+            },
         }
     }
 
@@ -453,9 +700,124 @@ impl EncodeDialog {
         }
     }
 
-    /// Common image-sequence options (channels / bit depth / tonemapping), shared
-    /// by every sequence format. Choice lists are format-specific (alpha + depth
-    /// support), mirroring the source dialog's per-format validation.
+    /// Common video options (framerate / pixel aspect / output resolution / frame
+    /// range / annotation burn-in), shared by every video codec. Width/height only
+    /// apply with "Custom" resolution, From/To only with "Encode marked range".
+    fn video_common_options(&self) -> Vec<EncodeOption> {
+        let (out_w, out_h) = self.output_size.unwrap_or((1920, 1080));
+        let (range_start, range_end) = self.encode_range.unwrap_or(self.comp_marks);
+        let (min, max) = (self.comp_bounds.0 as i64, self.comp_bounds.1 as i64);
+        vec![
+            EncodeOption::float("fps", "Framerate", self.fps as f64, 1.0, 960.0),
+            EncodeOption::boolean("bake_par", "Bake pixel aspect", self.bake_pixel_aspect),
+            EncodeOption::choice(
+                "resolution",
+                "Resolution",
+                RESOLUTION_LABELS,
+                self.output_size.is_some() as usize,
+            ),
+            EncodeOption::int("out_w", "Width", out_w as i64, 2, 16384),
+            EncodeOption::int("out_h", "Height", out_h as i64, 2, 16384),
+            EncodeOption::choice(
+                "fit",
+                "Fit",
+                fit_labels(),
+                FitMode::ALL
+                    .iter()
+                    .position(|&m| m == self.fit_mode)
+                    .unwrap_or(0),
+            ),
+            EncodeOption::choice(
+                "align",
+                "Align",
+                align_labels(),
+                CropAlign::ALL
+                    .iter()
+                    .position(|&a| a == self.crop_align)
+                    .unwrap_or(0),
+            ),
+            EncodeOption::choice(
+                "color",
+                "Color",
+                color_preset_labels(),
+                ColorPreset::all()
+                    .iter()
+                    .position(|&p| p == self.color_preset)
+                    .unwrap_or(0),
+            ),
+            EncodeOption::boolean(
+                "marked_range",
+                "Encode marked range",
+                self.encode_range.is_some(),
+            ),
+            EncodeOption::int("range_start", "From frame", range_start as i64, min, max),
+            EncodeOption::int("range_end", "To frame", range_end as i64, min, max),
+            EncodeOption::boolean(
+                "burn_annotations",
+                "Burn in annotations",
+                self.burn_annotations,
+            ),
+        ]
+    }
+
+    /// Keyframe options for long-GOP codecs (ProRes / DNxHR are intra-only).
+    fn gop_options(&self) -> [EncodeOption; 2] {
+        [
+            EncodeOption::float(
+                "gop_seconds",
+                "Keyframe every (s)",
+                self.gop_seconds as f64,
+                0.04,
+                60.0,
+            ),
+            EncodeOption::boolean("all_intra", "All-intra", self.all_intra),
+        ]
+    }
+
+    /// Inverse of [`Self::gop_options`].
+    fn apply_gop(&mut self, s: &WidgetSettings) {
+        self.gop_seconds = s
+            .get_float("gop_seconds")
+            .unwrap_or(DEFAULT_GOP_SECONDS as f64) as f32;
+        self.all_intra = s.get_bool("all_intra").unwrap_or(false);
+    }
+
+    /// Inverse of [`Self::video_common_options`].
+    fn apply_video_common(&mut self, s: &WidgetSettings) {
+        self.fps = s.get_float("fps").unwrap_or(24.0) as f32;
+        self.bake_pixel_aspect = s.get_bool("bake_par").unwrap_or(false);
+        self.output_size = (s.get_choice("resolution").unwrap_or(0) == 1).then(|| {
+            (
+                s.get_int("out_w").unwrap_or(1920).clamp(2, 16384) as u32,
+                s.get_int("out_h").unwrap_or(1080).clamp(2, 16384) as u32,
+            )
+        });
+        self.fit_mode = FitMode::ALL
+            .get(s.get_choice("fit").unwrap_or(0))
+            .copied()
+            .unwrap_or_default();
+        self.crop_align = CropAlign::ALL
+            .get(s.get_choice("align").unwrap_or(0))
+            .copied()
+            .unwrap_or_default();
+        self.color_preset = ColorPreset::all()
+            .get(s.get_choice("color").unwrap_or(0))
+            .copied()
+            .unwrap_or_default();
+        self.encode_range = s.get_bool("marked_range").unwrap_or(false).then(|| {
+            (
+                s.get_int("range_start").unwrap_or(self.comp_marks.0 as i64) as i32,
+                s.get_int("range_end").unwrap_or(self.comp_marks.1 as i64) as i32,
+            )
+        });
+        self.burn_annotations = s.get_bool("burn_annotations").unwrap_or(false);
+    }
+
+    /// Common image-sequence options (channels / bit depth / tonemapping / frame
+    /// numbering / padding / annotation burn-in / hash manifest), shared by every
+    /// sequence format. Choice lists are format-specific (alpha + depth support),
+    /// mirroring the source dialog's per-format validation. Start number only
+    /// applies with "Start at".
     fn seq_common_options(&self, fmt: SequenceFormat) -> Vec<EncodeOption> {
         let seq = &self.sequence_settings;
         vec![
@@ -475,12 +837,98 @@ impl EncodeDialog {
             EncodeOption::choice(
                 "tonemap_mode",
                 "Tonemap mode",
-                TONEMAP_LABELS,
+                tonemap_labels(),
                 tonemap_to_idx(seq.tonemap_mode),
             ),
+            EncodeOption::choice(
+                "numbering",
+                "Frame numbers",
+                numbering_labels(),
+                FrameNumbering::all()
+                    .iter()
+                    .position(|&n| n == seq.numbering)
+                    .unwrap_or(0),
+            ),
+            EncodeOption::int(
+                "start_number",
+                "Start number",
+                seq.start_number as i64,
+                0,
+                9_999_999,
+            ),
+            EncodeOption::choice(
+                "padding_style",
+                "Frame padding",
+                padding_style_labels(),
+                PaddingStyle::all()
+                    .iter()
+                    .position(|&p| p == seq.padding_style)
+                    .unwrap_or(0),
+            ),
+            EncodeOption::int(
+                "padding_width",
+                "Padding digits (0 = from name)",
+                seq.padding_width as i64,
+                0,
+                10,
+            ),
+            EncodeOption::boolean(
+                "burn_annotations",
+                "Burn in annotations",
+                seq.burn_annotations,
+            ),
+            EncodeOption::boolean("write_hashes", "Write hash manifest", seq.write_hashes),
         ]
     }
 
+    /// Contact sheet layout options, shared by both sheet formats.
+    fn sheet_options(&self) -> Vec<EncodeOption> {
+        let sheet = &self.contact_sheet;
+        vec![
+            EncodeOption::int("sheet_frames", "Frames", sheet.frames as i64, 1, 400),
+            EncodeOption::int("sheet_columns", "Columns", sheet.columns as i64, 1, 32),
+            EncodeOption::int(
+                "sheet_thumb",
+                "Thumbnail width",
+                sheet.thumb_width as i64,
+                16,
+                4096,
+            ),
+            EncodeOption::int("sheet_spacing", "Spacing", sheet.spacing as i64, 0, 256),
+            EncodeOption::boolean("sheet_labels", "Frame numbers", sheet.label_frames),
+            EncodeOption::boolean(
+                "burn_annotations",
+                "Burn in annotations",
+                sheet.burn_annotations,
+            ),
+        ]
+    }
+
+    /// Inverse of [`Self::sheet_options`].
+    fn apply_sheet(&mut self, s: &WidgetSettings, format: SequenceFormat) {
+        let defaults = ContactSheetSettings::default();
+        let sheet = &mut self.contact_sheet;
+        sheet.format = format;
+        sheet.frames = s
+            .get_int("sheet_frames")
+            .unwrap_or(defaults.frames as i64)
+            .clamp(1, 400) as u32;
+        sheet.columns = s
+            .get_int("sheet_columns")
+            .unwrap_or(defaults.columns as i64)
+            .clamp(1, 32) as u32;
+        sheet.thumb_width = s
+            .get_int("sheet_thumb")
+            .unwrap_or(defaults.thumb_width as i64)
+            .clamp(16, 4096) as u32;
+        sheet.spacing = s
+            .get_int("sheet_spacing")
+            .unwrap_or(defaults.spacing as i64)
+            .clamp(0, 256) as u32;
+        sheet.label_frames = s.get_bool("sheet_labels").unwrap_or(true);
+        sheet.burn_annotations = s.get_bool("burn_annotations").unwrap_or(false);
+    }
+
     /// Build the encode schema mirroring playa's codec/format tables 1:1, seeding
     /// every option default from the current model and codec availability from the
     /// ffmpeg encoder probe.
@@ -493,11 +941,16 @@ impl EncodeDialog {
             "MP4",
             "mp4",
             [
-                Codec::new(
-                    "h264",
-                    "H.264",
-                    [
-                        EncodeOption::float("fps", "Framerate", self.fps as f64, 1.0, 960.0),
+                Codec::new("h264", "H.264", {
+                    let mut o = self.video_common_options();
+                    o.extend(self.gop_options());
+                    o.extend([
+                        EncodeOption::choice(
+                            "tonemap_mode",
+                            "Tonemap mode",
+                            tonemap_labels(),
+                            tonemap_to_idx(self.tonemap_mode),
+                        ),
                         EncodeOption::choice(
                             "impl",
                             "Encoder",
@@ -523,15 +976,24 @@ impl EncodeDialog {
                             H264_PROFILES,
                             list_idx(&H264_PROFILES, &cs.h264.profile, 2),
                         ),
-                    ],
-                )
+                    ]);
+                    o
+                })
                 .available(VideoCodec::H264.is_available())
-                .hint("18=best, 23=default, 28=fast"),
-                Codec::new(
-                    "h265",
-                    "H.265 (HEVC)",
-                    [
-                        EncodeOption::float("fps", "Framerate", self.fps as f64, 1.0, 960.0),
+                .hint(
+                    "18=best, 23=default, 28=fast. \
+                    Short GOP / all-intra: frame-accurate scrubbing, bigger files",
+                ),
+                Codec::new("h265", "H.265 (HEVC)", {
+                    let mut o = self.video_common_options();
+                    o.extend(self.gop_options());
+                    o.extend([
+                        EncodeOption::choice(
+                            "tonemap_mode",
+                            "Tonemap mode",
+                            tonemap_labels(),
+                            tonemap_to_idx(self.tonemap_mode),
+                        ),
                         EncodeOption::choice(
                             "impl",
                             "Encoder",
@@ -557,15 +1019,24 @@ impl EncodeDialog {
                             H265_PROFILES,
                             list_idx(&H265_PROFILES, &cs.h265.profile, 0),
                         ),
-                    ],
-                )
+                    ]);
+                    o
+                })
                 .available(VideoCodec::H265.is_available())
-                .hint("28=default (higher than H.264)"),
-                Codec::new(
-                    "av1",
-                    "AV1",
-                    [
-                        EncodeOption::float("fps", "Framerate", self.fps as f64, 1.0, 960.0),
+                .hint(
+                    "28=default (higher than H.264). \
+                    Short GOP / all-intra: frame-accurate scrubbing, bigger files",
+                ),
+                Codec::new("av1", "AV1", {
+                    let mut o = self.video_common_options();
+                    o.extend(self.gop_options());
+                    o.extend([
+                        EncodeOption::choice(
+                            "tonemap_mode",
+                            "Tonemap mode",
+                            tonemap_labels(),
+                            tonemap_to_idx(self.tonemap_mode),
+                        ),
                         EncodeOption::choice(
                             "impl",
                             "Encoder",
@@ -585,33 +1056,98 @@ impl EncodeDialog {
                             AV1_PRESETS,
                             list_idx(&AV1_PRESETS, &cs.av1.preset, 17),
                         ),
-                    ],
-                )
+                    ]);
+                    o
+                })
                 .available(VideoCodec::AV1.is_available())
-                .hint("AV1: Best compression, slower encoding. HW: RTX 40xx/Arc/RDNA 3"),
+                .hint(
+                    "AV1: Best compression, slower encoding. HW: RTX 40xx/Arc/RDNA 3. \
+                    Short GOP / all-intra: frame-accurate scrubbing, bigger files",
+                ),
             ],
         );
 
-        // --- Video: MOV (ProRes) ---
+        // --- Video: MOV (ProRes / DNxHR) ---
         let mov = Format::new(
             "mov",
             "MOV",
             "mov",
-            [Codec::new(
-                "prores",
-                "ProRes",
-                [
-                    EncodeOption::float("fps", "Framerate", self.fps as f64, 1.0, 960.0),
-                    EncodeOption::choice(
+            [
+                Codec::new("prores", "ProRes", {
+                    let mut o = self.video_common_options();
+                    o.push(EncodeOption::choice(
                         "profile",
                         "Profile",
                         prores_labels(),
                         prores_idx(cs.prores.profile),
+                    ));
+                    o
+                })
+                .available(VideoCodec::ProRes.is_available())
+                .hint("ProRes is always software-encoded (prores_ks)"),
+                Codec::new("dnxhr", "DNxHR", {
+                    let mut o = self.video_common_options();
+                    o.push(EncodeOption::choice(
+                        "profile",
+                        "Profile",
+                        dnxhr_labels(),
+                        dnxhr_idx(cs.dnxhr.profile),
+                    ));
+                    o
+                })
+                .available(VideoCodec::DNxHR.is_available())
+                .hint("DNxHR: min 256x120; HQX/444 are 10-bit"),
+            ],
+        );
+
+        // --- Video: WebM (VP9) ---
+        let webm = Format::new(
+            "webm",
+            "WebM",
+            "webm",
+            [Codec::new("vp9", "VP9", {
+                let mut o = self.video_common_options();
+                o.extend(self.gop_options());
+                o.extend([
+                    EncodeOption::choice(
+                        "tonemap_mode",
+                        "Tonemap mode",
+                        tonemap_labels(),
+                        tonemap_to_idx(self.tonemap_mode),
                     ),
-                ],
-            )
-            .available(VideoCodec::ProRes.is_available())
-            .hint("ProRes is always software-encoded (prores_ks)")],
+                    EncodeOption::choice(
+                        "impl",
+                        "Encoder",
+                        ENC_IMPL_LABELS,
+                        enc_impl_to_idx(cs.vp9.encoder_impl),
+                    ),
+                    EncodeOption::choice(
+                        "qmode",
+                        "Quality Mode",
+                        QMODE_LABELS,
+                        qmode_to_idx(cs.vp9.quality_mode),
+                    ),
+                    EncodeOption::int("value", "Value", cs.vp9.quality_value as i64, 0, 10000),
+                    EncodeOption::choice(
+                        "preset",
+                        "Speed",
+                        VP9_SPEEDS,
+                        list_idx(&VP9_SPEEDS, &cs.vp9.preset, 4),
+                    ),
+                    EncodeOption::choice(
+                        "profile",
+                        "Profile",
+                        VP9_PROFILE_LABELS,
+                        list_idx(&VP9_PROFILES, &cs.vp9.profile, 0),
+                    ),
+                ]);
+                o
+            })
+            .available(VideoCodec::VP9.is_available())
+            .hint(
+                "31=default. Transparent comps keep alpha (8-bit, libvpx). \
+                Short GOP / all-intra: frame-accurate scrubbing, bigger files",
+            )],
         );
 
         // --- Image sequence formats (each its own widget Format/extension) ---
@@ -718,7 +1254,46 @@ impl EncodeDialog {
             .hint("TGA: Legacy format, game industry")],
         );
 
-        EncodeSchema::new([mp4, mov, exr, png, jpeg, tiff, tga])
+        // --- Contact sheet: one grid image of the work area ---
+        let sheet_jpg = Format::new(
+            "sheet_jpg",
+            "Contact Sheet (JPEG)",
+            "jpg",
+            [Codec::new("sheet_jpeg", "JPEG", {
+                let mut o = self.sheet_options();
+                o.push(EncodeOption::int(
+                    "quality",
+                    "Quality",
+                    seq.format_settings.jpeg.quality as i64,
+                    1,
+                    100,
+                ));
+                o
+            })
+            .hint("One image of evenly spaced work-area frames, for review")],
+        );
+
+        let sheet_png = Format::new(
+            "sheet_png",
+            "Contact Sheet (PNG)",
+            "png",
+            [Codec::new("sheet_png", "PNG", {
+                let mut o = self.sheet_options();
+                o.push(EncodeOption::int(
+                    "compression",
+                    "Compression",
+                    seq.format_settings.png.compression as i64,
+                    0,
+                    9,
+                ));
+                o
+            })
+            .hint("One image of evenly spaced work-area frames, lossless")],
+        );
+
+        EncodeSchema::new([
+            mp4, mov, webm, exr, png, jpeg, tiff, tga, sheet_jpg, sheet_png,
+        ])
     }
 
     /// Map the widget's chosen settings back onto the model. Each option id is the
@@ -733,7 +1308,9 @@ impl EncodeDialog {
                 self.export_mode = ExportMode::Video;
                 self.selected_codec = VideoCodec::H264;
                 self.container = Container::MP4;
-                self.fps = s.get_float("fps").unwrap_or(24.0) as f32;
+                self.apply_video_common(s);
+                self.apply_gop(s);
+                self.tonemap_mode = idx_to_tonemap(ci("tonemap_mode"));
                 let c = &mut self.codec_settings.h264;
                 c.encoder_impl = idx_to_enc_impl(ci("impl"));
                 c.quality_mode = idx_to_qmode(ci("qmode"));
@@ -745,7 +1322,9 @@ impl EncodeDialog {
                 self.export_mode = ExportMode::Video;
                 self.selected_codec = VideoCodec::H265;
                 self.container = Container::MP4;
-                self.fps = s.get_float("fps").unwrap_or(24.0) as f32;
+                self.apply_video_common(s);
+                self.apply_gop(s);
+                self.tonemap_mode = idx_to_tonemap(ci("tonemap_mode"));
                 let c = &mut self.codec_settings.h265;
                 c.encoder_impl = idx_to_enc_impl(ci("impl"));
                 c.quality_mode = idx_to_qmode(ci("qmode"));
@@ -757,23 +1336,57 @@ impl EncodeDialog {
                 self.export_mode = ExportMode::Video;
                 self.selected_codec = VideoCodec::AV1;
                 self.container = Container::MP4;
-                self.fps = s.get_float("fps").unwrap_or(24.0) as f32;
+                self.apply_video_common(s);
+                self.apply_gop(s);
+                self.tonemap_mode = idx_to_tonemap(ci("tonemap_mode"));
                 let c = &mut self.codec_settings.av1;
                 c.encoder_impl = idx_to_enc_impl(ci("impl"));
                 c.quality_mode = idx_to_qmode(ci("qmode"));
                 c.quality_value = s.get_int("value").unwrap_or(30).max(0) as u32;
                 c.preset = AV1_PRESETS.get(ci("preset")).copied().unwrap_or("p4").to_string();
             }
+            "vp9" => {
+                self.export_mode = ExportMode::Video;
+                self.selected_codec = VideoCodec::VP9;
+                self.container = Container::WebM;
+                self.apply_video_common(s);
+                self.apply_gop(s);
+                self.tonemap_mode = idx_to_tonemap(ci("tonemap_mode"));
+                let c = &mut self.codec_settings.vp9;
+                c.encoder_impl = idx_to_enc_impl(ci("impl"));
+                c.quality_mode = idx_to_qmode(ci("qmode"));
+                c.quality_value = s.get_int("value").unwrap_or(31).max(0) as u32;
+                c.preset = VP9_SPEEDS
+                    .get(ci("preset"))
+                    .copied()
+                    .unwrap_or("4")
+                    .to_string();
+                c.profile = VP9_PROFILES
+                    .get(ci("profile"))
+                    .copied()
+                    .unwrap_or("0")
+                    .to_string();
+            }
             "prores" => {
                 self.export_mode = ExportMode::Video;
                 self.selected_codec = VideoCodec::ProRes;
                 self.container = Container::MOV;
-                self.fps = s.get_float("fps").unwrap_or(24.0) as f32;
+                self.apply_video_common(s);
                 self.codec_settings.prores.profile = ProResProfile::all()
                     .get(ci("profile"))
                     .copied()
                     .unwrap_or(ProResProfile::Standard);
             }
+            "dnxhr" => {
+                self.export_mode = ExportMode::Video;
+                self.selected_codec = VideoCodec::DNxHR;
+                self.container = Container::MOV;
+                self.apply_video_common(s);
+                self.codec_settings.dnxhr.profile = DNxHRProfile::all()
+                    .get(ci("profile"))
+                    .copied()
+                    .unwrap_or(DNxHRProfile::HQ);
+            }
             "exr" => {
                 self.export_mode = ExportMode::Sequence;
                 self.apply_seq_common(s, SequenceFormat::Exr);
@@ -828,17 +1441,44 @@ impl EncodeDialog {
                 self.sequence_settings.format = SequenceFormat::Tga;
                 self.sequence_settings.validate();
             }
+            "sheet_jpeg" => {
+                self.export_mode = ExportMode::ContactSheet;
+                self.apply_sheet(s, SequenceFormat::Jpeg);
+                self.sequence_settings.format_settings.jpeg.quality =
+                    s.get_int("quality").unwrap_or(90).clamp(1, 100) as u8;
+            }
+            "sheet_png" => {
+                self.export_mode = ExportMode::ContactSheet;
+                self.apply_sheet(s, SequenceFormat::Png);
+                self.sequence_settings.format_settings.png.compression =
+                    s.get_int("compression").unwrap_or(6).clamp(0, 9) as u8;
+            }
             _ => {}
         }
     }
 
-    /// Apply the shared sequence options (channels / depth / tonemapping) for a format.
+    /// Apply the shared sequence options (channels / depth / tonemapping /
+    /// numbering) for a format.
     fn apply_seq_common(&mut self, s: &WidgetSettings, fmt: SequenceFormat) {
         let seq = &mut self.sequence_settings;
         seq.channels = idx_to_channel(fmt, s.get_choice("channels").unwrap_or(0));
         seq.bit_depth = idx_to_seq_depth(fmt, s.get_choice("bitdepth").unwrap_or(0));
         seq.apply_tonemap = s.get_bool("tonemap").unwrap_or(false);
         seq.tonemap_mode = idx_to_tonemap(s.get_choice("tonemap_mode").unwrap_or(0));
+        seq.numbering = FrameNumbering::all()
+            .get(s.get_choice("numbering").unwrap_or(0))
+            .copied()
+            .unwrap_or_default();
+        seq.start_number = s
+            .get_int("start_number")
+            .unwrap_or(DEFAULT_START_NUMBER as i64) as i32;
+        seq.padding_style = PaddingStyle::all()
+            .get(s.get_choice("padding_style").unwrap_or(0))
+            .copied()
+            .unwrap_or_default();
+        seq.padding_width = s.get_int("padding_width").unwrap_or(0).clamp(0, 10) as usize;
+        seq.burn_annotations = s.get_bool("burn_annotations").unwrap_or(false);
+        seq.write_hashes = s.get_bool("write_hashes").unwrap_or(false);
     }
 
     // ===================================================================
@@ -891,6 +1531,7 @@ impl EncodeDialog {
             ExportMode::Sequence => {
                 // Image sequence export
                 let settings = self.sequence_settings.clone();
+                self.output_path = self.sequence_output_path();
                 let output_path = self.output_path.clone();
                 info!(
                     "Format: {:?}, Channels: {:?}",
@@ -912,6 +1553,29 @@ impl EncodeDialog {
                     )
                 })
             }
+            ExportMode::ContactSheet => {
+                // Single grid image through the sequence writers
+                let settings = self.sequence_settings.clone();
+                let sheet = self.contact_sheet.clone();
+                let output_path = self.output_path.clone();
+                info!("Contact sheet: {:?}", sheet);
+                info!("Output: {}", output_path.display());
+
+                use crate::dialogs::encode::encode_contact_sheet;
+
+                thread::spawn(move || {
+                    info!("Contact sheet export thread started");
+                    encode_contact_sheet(
+                        &comp_clone,
+                        &project_clone,
+                        &output_path,
+                        &settings,
+                        &sheet,
+                        tx,
+                        cancel_flag_clone,
+                    )
+                })
+            }
         };
 
         self.encode_thread = Some(handle);
@@ -1010,10 +1674,10 @@ impl Drop for EncodeDialog {
 
 /// Encoder-impl labels, indexed to match [`EncoderImpl`] order.
 const ENC_IMPL_LABELS: [&str; 3] = ["Auto (HW → CPU)", "Hardware only", "Software (CPU)"];
+/// Output resolution labels: index 0 = source, 1 = custom width/height.
+const RESOLUTION_LABELS: [&str; 2] = ["Source", "Custom"];
 /// Quality-mode labels, indexed to match [`QualityMode`] order.
 const QMODE_LABELS: [&str; 2] = ["CRF (Quality)", "Bitrate (kbps)"];
-/// Tonemap labels (order is fixed here, mapped explicitly — not enum order).
-const TONEMAP_LABELS: [&str; 3] = ["ACES", "Reinhard", "Clamp"];
 /// H.264/H.265 preset union (libx26x ladder + NVENC/QSV/AMF presets). Single list
 /// because the widget can't vary a choice list by another option; the chosen
 /// string is what the encoder consumes.
@@ -1028,6 +1692,10 @@ const AV1_PRESETS: [&str; 25] = [
     "0", "1", "2", "3", "4", "5", "6", "7", "8", "9", "10", "11", "12", "13", "p1", "p2", "p3",
     "p4", "p5", "p6", "p7", "default", "slow", "medium", "fast",
 ];
+/// libvpx-vp9 `cpu-used` (0=slowest/best). "4" at idx 4.
+const VP9_SPEEDS: [&str; 9] = ["0", "1", "2", "3", "4", "5", "6", "7", "8"];
+const VP9_PROFILES: [&str; 2] = ["0", "2"];
+const VP9_PROFILE_LABELS: [&str; 2] = ["0 (8-bit)", "2 (10-bit)"];
 const EXR_MODE_LABELS: [&str; 2] = [
     "Display only (single RGBA)",
     "Pass-through (preserve all layers)",
@@ -1061,19 +1729,36 @@ fn idx_to_qmode(i: usize) -> QualityMode {
     }
 }
 
+fn fit_labels() -> Vec<String> {
+    FitMode::ALL.iter().map(|m| m.label().to_string()).collect()
+}
+fn align_labels() -> Vec<String> {
+    CropAlign::ALL.iter().map(|a| a.label().to_string()).collect()
+}
+fn color_preset_labels() -> Vec<String> {
+    ColorPreset::all().iter().map(|p| p.to_string()).collect()
+}
+fn numbering_labels() -> Vec<String> {
+    FrameNumbering::all()
+        .iter()
+        .map(|n| n.to_string())
+        .collect()
+}
+fn padding_style_labels() -> Vec<String> {
+    PaddingStyle::all().iter().map(|p| p.to_string()).collect()
+}
+
+fn tonemap_labels() -> Vec<String> {
+    TonemapMode::ALL
+        .iter()
+        .map(|m| m.label().to_string())
+        .collect()
+}
 fn tonemap_to_idx(v: TonemapMode) -> usize {
-    match v {
-        TonemapMode::ACES => 0,
-        TonemapMode::Reinhard => 1,
-        TonemapMode::Clamp => 2,
-    }
+    TonemapMode::ALL.iter().position(|&m| m == v).unwrap_or(0)
 }
 fn idx_to_tonemap(i: usize) -> TonemapMode {
-    match i {
-        1 => TonemapMode::Reinhard,
-        2 => TonemapMode::Clamp,
-        _ => TonemapMode::ACES,
-    }
+    TonemapMode::ALL.get(i).copied().unwrap_or_default()
 }
 
 fn exr_mode_idx(m: ExrEncodeMode) -> usize {
@@ -1104,6 +1789,16 @@ fn prores_idx(p: ProResProfile) -> usize {
         .unwrap_or(2)
 }
 
+fn dnxhr_labels() -> Vec<String> {
+    DNxHRProfile::all().iter().map(|p| p.to_string()).collect()
+}
+fn dnxhr_idx(p: DNxHRProfile) -> usize {
+    DNxHRProfile::all()
+        .iter()
+        .position(|&x| x == p)
+        .unwrap_or(2)
+}
+
 fn exr_comp_labels() -> Vec<String> {
     ExrCompression::all().iter().map(|c| c.to_string()).collect()
 }
@@ -0,0 +1,120 @@
+//! Go-to-frame prompt (Ctrl+G).
+//!
+//! A small window with one text field, prefilled with the current frame in
+//! the timeline's display mode and selected so typing replaces it. Accepts a
+//! frame number (or seconds, in Seconds mode) or SMPTE timecode
+//! (`HH:MM:SS:FF`, `;` = drop-frame); the result is clamped to the comp's
+//! in/out. Enter jumps, Escape closes.
+
+use eframe::egui;
+use playa_engine::utils::timecode::{TimeDisplay, display_frame, frame_to_tc, parse_frame_entry};
+
+/// Outcome of one [`GotoFrameDialog::show`] call
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GotoFrameResult {
+    /// Still waiting for input
+    Open,
+    Cancelled,
+    /// Jump to this frame (already clamped)
+    Go(i32),
+}
+
+/// State of an open go-to-frame prompt
+pub struct GotoFrameDialog {
+    text: String,
+    current: i32,
+    fps: f32,
+    /// Comp (in, out), inclusive
+    bounds: (i32, i32),
+    mode: TimeDisplay,
+    /// Focus the field and select the prefilled text on the first frame
+    select_all: bool,
+}
+
+impl GotoFrameDialog {
+    pub fn new(current: i32, fps: f32, bounds: (i32, i32), mode: TimeDisplay) -> Self {
+        Self {
+            text: display_frame(current, fps, mode),
+            current,
+            fps,
+            bounds: (bounds.0, bounds.1.max(bounds.0)),
+            mode,
+            select_all: true,
+        }
+    }
+
+    /// Parsed entry clamped to the comp bounds; `None` when it doesn't parse.
+    pub fn target(&self) -> Option<i32> {
+        parse_frame_entry(self.text.trim(), self.fps, self.mode)
+            .map(|f| f.clamp(self.bounds.0, self.bounds.1))
+    }
+
+    pub fn show(&mut self, ctx: &egui::Context) -> GotoFrameResult {
+        let mut result = GotoFrameResult::Open;
+        let mut open = true;
+        egui::Window::new("Go to Frame")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 80.0])
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Current: {} ({})",
+                    self.current,
+                    frame_to_tc(self.current, self.fps, false)
+                ));
+                let output = egui::TextEdit::singleline(&mut self.text)
+                    .hint_text("frame or HH:MM:SS:FF")
+                    .desired_width(180.0)
+                    .font(egui::TextStyle::Monospace)
+                    .show(ui);
+                if self.select_all {
+                    self.select_all = false;
+                    let mut state = output.state;
+                    state
+                        .cursor
+                        .set_char_range(Some(egui::text::CCursorRange::two(
+                            egui::text::CCursor::new(0),
+                            egui::text::CCursor::new(self.text.chars().count()),
+                        )));
+                    state.store(ui.ctx(), output.response.id);
+                    output.response.request_focus();
+                }
+
+                let target = self.target();
+                match target {
+                    Some(frame) => ui.weak(format!(
+                        "-> {} ({})   range {}..{}",
+                        frame,
+                        frame_to_tc(frame, self.fps, false),
+                        self.bounds.0,
+                        self.bounds.1
+                    )),
+                    None => ui.colored_label(
+                        egui::Color32::from_rgb(230, 90, 90),
+                        "Not a frame number or timecode",
+                    ),
+                };
+
+                ui.horizontal(|ui| {
+                    let enter = output.response.lost_focus()
+                        && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    let go = ui
+                        .add_enabled(target.is_some(), egui::Button::new("Go"))
+                        .clicked();
+                    if (enter || go)
+                        && let Some(frame) = target
+                    {
+                        result = GotoFrameResult::Go(frame);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        result = GotoFrameResult::Cancelled;
+                    }
+                });
+            });
+        if !open || ctx.input(|i| i.key_pressed(egui::Key::Escape)) {
+            result = GotoFrameResult::Cancelled;
+        }
+        result
+    }
+}
@@ -1,10 +1,11 @@
 //! Dialogs - modal and non-modal dialog windows
 //!
-//! Preferences, encoder settings
+//! Preferences, encoder settings, go-to-frame prompt
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod encode;
 #[cfg(target_arch = "wasm32")]
 #[path = "encode_stub_wasm.rs"]
 pub mod encode;
+pub mod goto_frame;
 pub mod prefs;
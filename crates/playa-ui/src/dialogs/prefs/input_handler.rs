@@ -12,14 +12,40 @@ use playa_engine::core::player_events::*;
 use playa_engine::entities::comp_events::*;
 use playa_events::viewport_tool::{SetToolEvent, ToolMode};
 use std::collections::HashMap;
+use std::sync::Arc;
 use uuid::Uuid;
 
 /// Factory function type for creating events
-type EventFactory = Box<dyn Fn() -> BoxedEvent + Send + Sync>;
+type EventFactory = Arc<dyn Fn() -> BoxedEvent + Send + Sync>;
+
+/// Version tag written by [`HotkeyHandler::export_bindings`]
+const BINDINGS_FILE_VERSION: u32 = 1;
+
+/// Bound action: stable id (written to exported files) + event factory
+#[derive(Clone)]
+struct Action {
+    id: String,
+    factory: EventFactory,
+}
+
+/// One `chord -> action` line of an exported bindings file
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BindingEntry {
+    window: HotkeyWindow,
+    key: String,
+    action: String,
+}
+
+/// Exported bindings file (`export_bindings` / `import_bindings`)
+#[derive(serde::Serialize, serde::Deserialize)]
+struct BindingsFile {
+    version: u32,
+    bindings: Vec<BindingEntry>,
+}
 
 /// Hotkey handler for managing keyboard shortcuts
 pub struct HotkeyHandler {
-    bindings: HashMap<(HotkeyWindow, String), EventFactory>,
+    bindings: HashMap<(HotkeyWindow, String), Action>,
     focused_window: HotkeyWindow,
 }
 
@@ -41,16 +67,16 @@ impl HotkeyHandler {
     /// Handle key press, returns cloned event
     pub fn handle_key(&self, key: &str) -> Option<BoxedEvent> {
         // Try current focused window first
-        if let Some(factory) = self.bindings.get(&(self.focused_window, key.to_string())) {
+        if let Some(action) = self.bindings.get(&(self.focused_window, key.to_string())) {
             log::trace!("Hotkey: ({:?}, {}) -> matched", self.focused_window, key);
-            return Some(factory());
+            return Some((action.factory)());
         }
         // Fallback: try Global
         if self.focused_window != HotkeyWindow::Global
-            && let Some(factory) = self.bindings.get(&(HotkeyWindow::Global, key.to_string()))
+            && let Some(action) = self.bindings.get(&(HotkeyWindow::Global, key.to_string()))
         {
             log::trace!("Hotkey: (Global, {}) -> matched (fallback)", key);
-            return Some(factory());
+            return Some((action.factory)());
         }
         log::trace!("Hotkey: ({:?}, {}) -> NO MATCH", self.focused_window, key);
         None
@@ -83,15 +109,117 @@ impl HotkeyHandler {
         self.focused_window = window;
     }
 
-    /// Add hotkey binding with factory
+    /// Add hotkey binding with factory. The action id is the event type name.
     fn bind<E: Clone + Send + Sync + 'static>(
         &mut self,
         window: HotkeyWindow,
         key: &str,
         event: E,
     ) {
-        let factory: EventFactory = Box::new(move || Box::new(event.clone()));
-        self.bindings.insert((window, key.to_string()), factory);
+        let type_name = std::any::type_name::<E>();
+        let id = type_name.rsplit("::").next().unwrap_or(type_name);
+        self.bind_as(window, key, id, event);
+    }
+
+    /// Add hotkey binding under an explicit action id, for event types bound
+    /// with several payloads (e.g. one `SetToolEvent` per tool).
+    fn bind_as<E: Clone + Send + Sync + 'static>(
+        &mut self,
+        window: HotkeyWindow,
+        key: &str,
+        id: &str,
+        event: E,
+    ) {
+        let factory: EventFactory = Arc::new(move || Box::new(event.clone()));
+        self.bindings.insert(
+            (window, key.to_string()),
+            Action {
+                id: id.to_string(),
+                factory,
+            },
+        );
+    }
+
+    /// Drop all bindings and restore [`Self::setup_default_bindings`].
+    pub fn reset_to_defaults(&mut self) {
+        self.bindings.clear();
+        self.setup_default_bindings();
+    }
+
+    /// Current bindings as JSON (`{version, bindings: [{window, key, action}]}`),
+    /// sorted by window then chord so exported files diff cleanly.
+    pub fn export_bindings(&self) -> String {
+        let mut bindings: Vec<BindingEntry> = self
+            .bindings
+            .iter()
+            .map(|((window, key), action)| BindingEntry {
+                window: *window,
+                key: key.clone(),
+                action: action.id.clone(),
+            })
+            .collect();
+        bindings.sort_by(|a, b| (a.window, &a.key).cmp(&(b.window, &b.key)));
+        let file = BindingsFile {
+            version: BINDINGS_FILE_VERSION,
+            bindings,
+        };
+        serde_json::to_string_pretty(&file).unwrap_or_default()
+    }
+
+    /// Replace all bindings with a set written by [`Self::export_bindings`].
+    ///
+    /// Actions must be ones the default set knows about; unknown ones are
+    /// skipped. Returns warnings for skipped entries and for conflicts (two
+    /// actions on one chord in the same window - the later entry wins).
+    /// On error (malformed JSON, nothing usable) the current bindings are kept.
+    pub fn import_bindings(&mut self, json: &str) -> Result<Vec<String>, String> {
+        let file: BindingsFile =
+            serde_json::from_str(json).map_err(|e| format!("Invalid hotkey file: {}", e))?;
+        if file.version > BINDINGS_FILE_VERSION {
+            return Err(format!(
+                "Hotkey file version {} is newer than supported ({})",
+                file.version, BINDINGS_FILE_VERSION
+            ));
+        }
+
+        let mut defaults = Self::new();
+        defaults.setup_default_bindings();
+        let known: HashMap<String, EventFactory> = defaults
+            .bindings
+            .into_values()
+            .map(|action| (action.id, action.factory))
+            .collect();
+
+        let mut warnings = Vec::new();
+        let mut bindings = HashMap::new();
+        for entry in file.bindings {
+            let Some(factory) = known.get(&entry.action) else {
+                warnings.push(format!(
+                    "Unknown action '{}' on {:?} {} - skipped",
+                    entry.action, entry.window, entry.key
+                ));
+                continue;
+            };
+            let action = Action {
+                id: entry.action,
+                factory: Arc::clone(factory),
+            };
+            let new_id = action.id.clone();
+            if let Some(prev) = bindings.insert((entry.window, entry.key.clone()), action)
+                && prev.id != new_id
+            {
+                warnings.push(format!(
+                    "Conflict: {} in {:?} bound to both {} and {} - using {}",
+                    entry.key, entry.window, prev.id, new_id, new_id
+                ));
+            }
+        }
+        if bindings.is_empty() {
+            return Err("Hotkey file contains no usable bindings".to_string());
+        }
+
+        self.bindings = bindings;
+        Ok(warnings)
     }
 
     /// Setup default hotkey bindings
@@ -134,30 +262,85 @@ impl HotkeyHandler {
         self.bind(Global, "Period", IncreaseFPSBaseEvent); // . = increase base FPS
         self.bind(Global, "Semicolon", JumpToPrevEdgeEvent);
         self.bind(Global, "Quote", JumpToNextEdgeEvent);
+        self.bind(Global, "M", AddMarkerEvent);
+        self.bind(Global, "Alt+Semicolon", JumpToPrevMarkerEvent);
+        self.bind(Global, "Alt+Quote", JumpToNextMarkerEvent);
+        self.bind(Global, "Ctrl+Semicolon", JumpToPrevKeyframeEvent);
+        self.bind(Global, "Ctrl+Quote", JumpToNextKeyframeEvent);
+        self.bind(Global, "Ctrl+G", GoToFrameEvent);
         self.bind(Global, "Backtick", ToggleLoopEvent);
+        self.bind(Global, "Shift+Backtick", ToggleSequencePlaybackEvent);
+        self.bind(Global, "Shift+H", ToggleFrameHoldEvent);
+        self.bind(Global, "P", CycleProxyLevelEvent);
         self.bind(Global, "Backspace", ToggleFrameNumbersEvent);
         self.bind(Global, "B", SetPlayRangeStartEvent);
         self.bind(Global, "N", SetPlayRangeEndEvent);
         self.bind(Global, "Ctrl+B", ResetPlayRangeEvent);
+        // NLE-style mark in/out (works live during playback)
+        self.bind(Global, "I", SetPlayRangeStartEvent);
+        self.bind(Global, "O", SetPlayRangeEndEvent);
+        self.bind(Global, "Alt+X", ResetPlayRangeEvent);
         self.bind(Global, "Ctrl+ArrowLeft", JumpToStartEvent);
         self.bind(Global, "Ctrl+ArrowRight", JumpToEndEvent);
         // Ctrl+R is now ResetTrimsEvent in Timeline context (see below)
         self.bind(Global, "Ctrl+S", QuickSaveEvent);
         self.bind(Global, "Ctrl+O", OpenProjectDialogEvent);
+        self.bind(Global, "Ctrl+Shift+O", OpenMostRecentEvent);
+        self.bind(Global, "Ctrl+Z", UndoEvent);
+        self.bind(Global, "Ctrl+Shift+Z", RedoEvent);
         self.bind(Global, "Z", ToggleFullscreenEvent);
         self.bind(Global, "U", ProjectPreviousCompEvent);
         self.bind(Global, "Ctrl+Alt+Slash", ClearCacheEvent); // Clear all cached frames
         self.bind(Global, "F", FitViewportEvent);
         self.bind(Global, "A", Viewport100Event);
         self.bind(Global, "H", Viewport100Event);
-        // Tool hotkeys (Q/W/E/R like Maya)
-        self.bind(Global, "Q", SetToolEvent(ToolMode::Select));
-        self.bind(Global, "W", SetToolEvent(ToolMode::Move));
-        self.bind(Global, "E", SetToolEvent(ToolMode::Rotate));
-        self.bind(Global, "R", SetToolEvent(ToolMode::Scale));
+        // Tool hotkeys (Q/W/E/R like Maya, D = region of interest, S = annotate)
+        self.bind_as(
+            Global,
+            "Q",
+            "SetToolEvent(Select)",
+            SetToolEvent(ToolMode::Select),
+        );
+        self.bind_as(
+            Global,
+            "W",
+            "SetToolEvent(Move)",
+            SetToolEvent(ToolMode::Move),
+        );
+        self.bind_as(
+            Global,
+            "E",
+            "SetToolEvent(Rotate)",
+            SetToolEvent(ToolMode::Rotate),
+        );
+        self.bind_as(
+            Global,
+            "R",
+            "SetToolEvent(Scale)",
+            SetToolEvent(ToolMode::Scale),
+        );
+        self.bind_as(
+            Global,
+            "D",
+            "SetToolEvent(Roi)",
+            SetToolEvent(ToolMode::Roi),
+        );
+        self.bind_as(
+            Global,
+            "S",
+            "SetToolEvent(Annotate)",
+            SetToolEvent(ToolMode::Annotate),
+        );
+        self.bind_as(
+            Global,
+            "V",
+            "SetToolEvent(Mask)",
+            SetToolEvent(ToolMode::Mask),
+        );
 
         // Timeline-specific
         self.bind(Timeline, "Delete", RemoveSelectedLayerEvent);
+        self.bind(Timeline, "Shift+Delete", RippleDeleteSelectedLayerEvent);
         self.bind(Timeline, "F", TimelineFitEvent::selected()); // Fit to selected (or all if none)
         self.bind(Timeline, "A", TimelineFitWorkAreaEvent); // Fit to work area (B/N range)
         self.bind(Timeline, "OpenBracket", AlignLayersStartEvent(Uuid::nil()));
@@ -172,6 +355,11 @@ impl HotkeyHandler {
             "Alt+CloseBracket",
             TrimLayersEndEvent(Uuid::nil()),
         );
+        self.bind(
+            Timeline,
+            "Shift+Alt+CloseBracket",
+            RippleTrimLayersEndEvent(Uuid::nil()),
+        );
         // Layer clipboard operations
         self.bind(
             Timeline,
@@ -180,6 +368,13 @@ impl HotkeyHandler {
                 comp_uuid: Uuid::nil(),
             },
         );
+        self.bind(
+            Timeline,
+            "Ctrl+Shift+D",
+            SplitLayersEvent {
+                comp_uuid: Uuid::nil(),
+            },
+        );
         self.bind(
             Timeline,
             "Ctrl+C",
@@ -221,11 +416,35 @@ impl HotkeyHandler {
 
         // Project-specific
         self.bind(Project, "Delete", RemoveSelectedMediaEvent);
+        self.bind(Project, "Ctrl+D", DuplicateSelectedMediaEvent);
 
         // Viewport-specific
         self.bind(Viewport, "F", FitViewportEvent);
         self.bind(Viewport, "A", Viewport100Event);
         self.bind(Viewport, "H", Viewport100Event);
+        self.bind(Viewport, "G", CycleGuidesEvent);
+        self.bind(Viewport, "C", ToggleCheckerEvent);
+        self.bind(Viewport, "Shift+O", ToggleOnionSkinEvent); // O = mark out
+        self.bind(Viewport, "T", RotateViewportEvent);
+        self.bind(Viewport, "X", FlipViewportEvent);
+        self.bind(Viewport, "Y", FlopViewportEvent);
+        self.bind(Viewport, "Shift+T", ResetOrientationEvent);
+        // View bookmarks: 1-9 recall, Ctrl+1-9 save (timeline digits only act
+        // while the timeline is hovered)
+        for slot in 1..=9u8 {
+            self.bind_as(
+                Viewport,
+                &format!("Num{slot}"),
+                &format!("RecallViewBookmarkEvent({slot})"),
+                RecallViewBookmarkEvent(slot),
+            );
+            self.bind_as(
+                Viewport,
+                &format!("Ctrl+Num{slot}"),
+                &format!("StoreViewBookmarkEvent({slot})"),
+                StoreViewBookmarkEvent(slot),
+            );
+        }
 
         // Node editor-specific
         self.bind(NodeEditor, "A", NodeEditorFitAllEvent);
@@ -315,3 +534,60 @@ impl HotkeyHandler {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use playa_engine::core::event_bus::downcast_event;
+
+    fn defaults() -> HotkeyHandler {
+        let mut handler = HotkeyHandler::new();
+        handler.setup_default_bindings();
+        handler
+    }
+
+    #[test]
+    fn test_bindings_round_trip() {
+        let exported = defaults().export_bindings();
+
+        let mut handler = HotkeyHandler::new();
+        assert_eq!(handler.import_bindings(&exported), Ok(Vec::new()));
+        assert_eq!(handler.export_bindings(), exported);
+        // Imported actions fire the same events, payloads included
+        let tool = handler.handle_key("W").expect("W is bound");
+        assert!(matches!(
+            downcast_event::<SetToolEvent>(&tool),
+            Some(SetToolEvent(ToolMode::Move))
+        ));
+
+        // A remapped chord comes back remapped, with the conflict reported
+        let remapped = exported.replacen(r#""key": "F1","#, r#""key": "F2","#, 1);
+        let warnings = handler.import_bindings(&remapped).unwrap();
+        assert_eq!(warnings.len(), 1, "{warnings:?}");
+        assert!(warnings[0].starts_with("Conflict: F2"));
+        assert!(handler.handle_key("F1").is_none());
+    }
+
+    #[test]
+    fn test_import_rejects_malformed_input() {
+        let mut handler = defaults();
+        let before = handler.export_bindings();
+
+        for bad in [
+            "",
+            "not json",
+            r#"{"bindings": []}"#,
+            r#"{"version": 99, "bindings": []}"#,
+            r#"{"version": 1, "bindings": [{"window": "Nowhere", "key": "F1", "action": "ToggleHelpEvent"}]}"#,
+            r#"{"version": 1, "bindings": []}"#,
+        ] {
+            assert!(handler.import_bindings(bad).is_err(), "accepted {bad:?}");
+        }
+        // Nothing usable left after skipping unknown actions
+        let unknown = r#"{"version": 1, "bindings": [{"window": "Global", "key": "F1", "action": "NoSuchEvent"}]}"#;
+        assert!(handler.import_bindings(unknown).is_err());
+
+        // Failed imports keep the current bindings
+        assert_eq!(handler.export_bindings(), before);
+    }
+}
@@ -1,8 +1,17 @@
 use eframe::egui;
 use egui_ltreeview::TreeView;
 use std::collections::HashMap;
-
-use super::prefs_events::{CompositorBackend, CompositorBackendChangedEvent, SetGizmoPrefsEvent};
+use std::path::PathBuf;
+
+use super::prefs_events::{
+    CompositorBackend, CompositorBackendChangedEvent, ExportHotkeysEvent, ImportHotkeysEvent,
+    ResetHotkeysEvent, SetGizmoPrefsEvent, SetViewportDefaultsEvent,
+};
+use crate::widgets::timeline::TIME_DISPLAY_MODES;
+use crate::widgets::viewport::{
+    DEFAULT_VIEWPORT_BACKGROUND, GridStyle, NEUTRAL_GRAY_BACKGROUND, ZebraThresholds,
+};
+use playa_engine::utils::timecode::TimeDisplay;
 
 /// Settings categories
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -11,7 +20,9 @@ enum SettingsCategory {
     UI,
     Cache,
     Gizmo,
+    ProjectViewport,
     Compositing,
+    Hotkeys,
     WebServer,
 }
 
@@ -22,7 +33,9 @@ impl SettingsCategory {
             SettingsCategory::UI => "UI",
             SettingsCategory::Cache => "Cache",
             SettingsCategory::Gizmo => "Gizmo",
+            SettingsCategory::ProjectViewport => "Project Viewport",
             SettingsCategory::Compositing => "Compositing",
+            SettingsCategory::Hotkeys => "Hotkeys",
             SettingsCategory::WebServer => "Web Server",
         }
     }
@@ -33,13 +46,84 @@ impl SettingsCategory {
             "UI" => Some(SettingsCategory::UI),
             "Cache" => Some(SettingsCategory::Cache),
             "Gizmo" => Some(SettingsCategory::Gizmo),
+            "Project Viewport" => Some(SettingsCategory::ProjectViewport),
             "Compositing" => Some(SettingsCategory::Compositing),
+            "Hotkeys" => Some(SettingsCategory::Hotkeys),
             "Web Server" => Some(SettingsCategory::WebServer),
             _ => None,
         }
     }
 }
 
+/// Built-in egui color themes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum UiTheme {
+    #[default]
+    Dark,
+    Light,
+    /// Near-black panels for dim grading suites
+    Midnight,
+    /// Mid-gray panels that don't bias image judgement
+    Neutral,
+}
+
+impl UiTheme {
+    pub const ALL: [UiTheme; 4] = [
+        UiTheme::Dark,
+        UiTheme::Light,
+        UiTheme::Midnight,
+        UiTheme::Neutral,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            UiTheme::Dark => "Dark",
+            UiTheme::Light => "Light",
+            UiTheme::Midnight => "Midnight",
+            UiTheme::Neutral => "Neutral Gray",
+        }
+    }
+
+    /// egui visuals for this theme
+    pub fn visuals(self) -> egui::Visuals {
+        match self {
+            UiTheme::Dark => egui::Visuals::dark(),
+            UiTheme::Light => egui::Visuals::light(),
+            UiTheme::Midnight => {
+                let mut v = egui::Visuals::dark();
+                v.panel_fill = egui::Color32::from_gray(14);
+                v.window_fill = egui::Color32::from_gray(18);
+                v.faint_bg_color = egui::Color32::from_gray(20);
+                v.extreme_bg_color = egui::Color32::BLACK;
+                v
+            }
+            UiTheme::Neutral => {
+                let mut v = egui::Visuals::dark();
+                v.panel_fill = egui::Color32::from_gray(64);
+                v.window_fill = egui::Color32::from_gray(68);
+                v.faint_bg_color = egui::Color32::from_gray(72);
+                v.extreme_bg_color = egui::Color32::from_gray(48);
+                v
+            }
+        }
+    }
+
+    /// Accepts a theme name or the legacy `dark_mode` bool from older saves.
+    fn deserialize_compat<'de, D: serde::Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(untagged)]
+        enum Compat {
+            Theme(UiTheme),
+            DarkMode(bool),
+        }
+        Ok(match <Compat as serde::Deserialize>::deserialize(d)? {
+            Compat::Theme(theme) => theme,
+            Compat::DarkMode(true) => UiTheme::Dark,
+            Compat::DarkMode(false) => UiTheme::Light,
+        })
+    }
+}
+
 /// Viewport hover/selection highlight settings. Slice of [`AppSettings`]
 /// flattened via `#[serde(flatten)]` for back-compat with legacy
 /// playa.json saves.
@@ -52,6 +136,18 @@ pub struct ViewportSettings {
     pub hover_stroke_width: f32,
     pub hover_corner_length: f32,
     pub hover_opacity: f32,
+    /// Display-only backdrop around/behind the image (sRGB)
+    pub viewport_background: [u8; 3],
+    /// Pixel grid: image pixels between lines
+    pub grid_spacing: u32,
+    /// Pixel grid line color (sRGB, unmultiplied alpha)
+    pub grid_color: [u8; 4],
+    /// Zoom at which the 1-px grid appears (8.0 = 800%)
+    pub grid_min_zoom: f32,
+    /// Zebra: stripe pixels whose brightest channel is at or below this
+    pub zebra_shadow: f32,
+    /// Zebra: stripe pixels with any channel at or above this (HDR may exceed 1.0)
+    pub zebra_highlight: f32,
 }
 
 impl Default for ViewportSettings {
@@ -62,6 +158,31 @@ impl Default for ViewportSettings {
             hover_stroke_width: 2.0,
             hover_corner_length: 20.0,
             hover_opacity: 0.5,
+            viewport_background: DEFAULT_VIEWPORT_BACKGROUND,
+            grid_spacing: GridStyle::default().spacing,
+            grid_color: GridStyle::default().color,
+            grid_min_zoom: GridStyle::default().min_zoom,
+            zebra_shadow: ZebraThresholds::default().shadow,
+            zebra_highlight: ZebraThresholds::default().highlight,
+        }
+    }
+}
+
+impl ViewportSettings {
+    /// Pixel grid style for the viewport overlay
+    pub fn grid_style(&self) -> GridStyle {
+        GridStyle {
+            spacing: self.grid_spacing,
+            color: self.grid_color,
+            min_zoom: self.grid_min_zoom,
+        }
+    }
+
+    /// Exposure warning levels for the viewport zebra overlay
+    pub fn zebra_thresholds(&self) -> ZebraThresholds {
+        ZebraThresholds {
+            shadow: self.zebra_shadow,
+            highlight: self.zebra_highlight,
         }
     }
 }
@@ -80,6 +201,8 @@ pub struct TimelineSettings {
     pub timeline_snap_enabled: bool,
     pub timeline_lock_work_area: bool,
     pub timeline_hover_highlight: bool,
+    /// Frame numbers vs SMPTE timecode in the timeline and status bar.
+    pub timeline_time_display: TimeDisplay,
 }
 
 impl Default for TimelineSettings {
@@ -91,6 +214,7 @@ impl Default for TimelineSettings {
             timeline_snap_enabled: true,
             timeline_lock_work_area: false,
             timeline_hover_highlight: false,
+            timeline_time_display: TimeDisplay::Frames,
         }
     }
 }
@@ -109,6 +233,9 @@ pub struct CacheSettings {
     pub reserve_system_memory_gb: f32,
     /// Caching strategy (LastOnly or All).
     pub cache_strategy: playa_engine::entities::CacheStrategy,
+    /// Hard cap on frames preloaded either side of the playhead
+    /// (`None` = fill the work area, the default).
+    pub max_preload_radius: Option<i32>,
 }
 
 impl Default for CacheSettings {
@@ -117,6 +244,7 @@ impl Default for CacheSettings {
             cache_memory_percent: 75.0,
             reserve_system_memory_gb: 2.0,
             cache_strategy: playa_engine::entities::CacheStrategy::All,
+            max_preload_radius: None,
         }
     }
 }
@@ -132,10 +260,25 @@ pub struct PlaybackSettings {
     /// Base FPS (persistent across restarts).
     pub fps_base: f32,
     pub loop_enabled: bool,
+    /// Roll into the next comp at the work-area end (when loop is off).
+    pub sequence_playback: bool,
     /// Frames to preload around playhead (-1 = all, default 100).
     pub preload_radius: i32,
     /// Delay before full preload after attr change (default 500ms).
     pub preload_delay_ms: u64,
+    /// Decode/display resolution while playing or scrubbing (full res when paused).
+    pub proxy_level: playa_engine::entities::ProxyLevel,
+    /// Frames a loaded single still is held for (default 1).
+    pub still_frames: i32,
+    /// Playback fps given to image sequences and stills at load (default 24).
+    pub sequence_fps: f32,
+    /// Frames moved by Shift+Arrow / Shift+PageUp/PageDown (default 25).
+    pub step_frames: i32,
+    /// Pace playback by the wall clock at the comp fps, dropping frames the
+    /// display can't keep up with (off = show every frame, however slow).
+    pub lock_to_fps: bool,
+    /// Present on vertical blank (applies on next start; no tearing).
+    pub vsync: bool,
 }
 
 impl Default for PlaybackSettings {
@@ -143,8 +286,15 @@ impl Default for PlaybackSettings {
         Self {
             fps_base: 24.0,
             loop_enabled: true,
+            sequence_playback: false,
             preload_radius: -1,
             preload_delay_ms: 500,
+            proxy_level: playa_engine::entities::ProxyLevel::Full,
+            still_frames: 1,
+            sequence_fps: 24.0,
+            step_frames: playa_engine::core::player::FRAME_JUMP_STEP,
+            lock_to_fps: true,
+            vsync: true,
         }
     }
 }
@@ -177,6 +327,53 @@ impl Default for Layout {
     }
 }
 
+/// Cap for [`AppSettings::recent_items`]
+pub const MAX_RECENT_ITEMS: usize = 10;
+
+/// What a recent entry reopens as
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum RecentKind {
+    /// Image sequence or video (first frame / file path)
+    Media,
+    /// Saved project JSON
+    Project,
+}
+
+/// Entry in the recent files / projects list
+#[derive(Clone, Debug, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct RecentItem {
+    pub path: PathBuf,
+    pub kind: RecentKind,
+}
+
+impl RecentItem {
+    /// Menu label: file name plus parent folder
+    pub fn label(&self) -> String {
+        let name = self
+            .path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.path.display().to_string());
+        match self.path.parent().and_then(|p| p.file_name()) {
+            Some(dir) => format!("{}  ({})", name, dir.to_string_lossy()),
+            None => name,
+        }
+    }
+}
+
+/// Record `path` as most recent: moves an existing entry to the front
+/// instead of duplicating it, and drops the oldest past [`MAX_RECENT_ITEMS`].
+pub fn push_recent(items: &mut Vec<RecentItem>, path: PathBuf, kind: RecentKind) {
+    items.retain(|item| item.path != path);
+    items.insert(0, RecentItem { path, kind });
+    items.truncate(MAX_RECENT_ITEMS);
+}
+
+/// Drop entries whose paths no longer exist on disk
+pub fn prune_recent(items: &mut Vec<RecentItem>) {
+    items.retain(|item| item.path.exists());
+}
+
 /// Application settings
 #[derive(Clone, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(default)]
@@ -196,8 +393,12 @@ pub struct AppSettings {
     pub show_attributes_editor: bool,
     pub show_frame_numbers: bool, // Show frame numbers on timeslider
     pub show_tooltips: bool,      // Show tooltips on toolbar controls (2s delay)
-    pub dark_mode: bool,
+    /// UI color theme (replaces the legacy `dark_mode` flag, still read from old saves)
+    #[serde(alias = "dark_mode", deserialize_with = "UiTheme::deserialize_compat")]
+    pub theme: UiTheme,
     pub font_size: f32,
+    /// Project panel thumbnail edge in px; 0 shows the plain list
+    pub project_thumbnail_size: u32,
 
     /// Timeline slice (layer height, name column, snap/lock flags etc.) —
     /// flattens to legacy top-level `timeline_*` keys.
@@ -210,7 +411,17 @@ pub struct AppSettings {
     pub viewport: ViewportSettings,
 
     // Workers (applied to App::workers / playback/encoding threads)
-    pub workers_override: u32, // 0 = auto, N = override (applies on restart)
+    pub workers_override: u32, // 0 = auto, N = override (applied live)
+
+    /// Crash-recovery auto-save period in seconds; 0 disables it
+    pub autosave_interval_secs: u32,
+
+    /// Recently opened media and saved/loaded projects, most recent first
+    pub recent_items: Vec<RecentItem>,
+
+    /// Imported hotkey bindings (`HotkeyHandler::export_bindings` JSON);
+    /// empty = built-in defaults
+    pub custom_hotkeys: String,
 
     /// Cache slice — flattens to the legacy top-level keys
     /// `cache_memory_percent`, `reserve_system_memory_gb`,
@@ -230,6 +441,8 @@ pub struct AppSettings {
     // REST API Server
     pub api_server_enabled: bool,
     pub api_server_port: Option<u16>,
+    /// Bearer token required by the REST API; empty = no authentication
+    pub api_server_token: String,
 
     // Layouts (named UI configurations)
     pub layouts: HashMap<String, Layout>,
@@ -254,17 +467,22 @@ impl Default for AppSettings {
             show_attributes_editor: true,
             show_frame_numbers: true,
             show_tooltips: true,
-            dark_mode: true,
+            theme: UiTheme::Dark,
             font_size: 11.0,
+            project_thumbnail_size: playa_engine::entities::thumbnail::DEFAULT_THUMBNAIL_SIZE,
             timeline: TimelineSettings::default(),
             viewport: ViewportSettings::default(),
             workers_override: 0,
+            autosave_interval_secs: 60,
+            recent_items: Vec::new(),
+            custom_hotkeys: String::new(),
             cache: CacheSettings::default(),
             compositor_backend: CompositorBackend::default(),
             encode_dialog: crate::dialogs::encode::EncodeDialogSettings::default(),
             selected_settings_category: Some("UI".to_string()),
             api_server_enabled: false,
             api_server_port: Some(9876),
+            api_server_token: String::new(),
             layouts: HashMap::new(),
             current_layout: String::new(),
             jobs: playa_jobs_core::JobsSettings::default(),
@@ -273,8 +491,67 @@ impl Default for AppSettings {
 }
 
 /// Render General settings category
-fn render_general_settings(ui: &mut egui::Ui, _settings: &mut AppSettings) {
-    ui.label("General settings will be added here.");
+fn render_general_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
+    ui.heading("Crash Recovery");
+    ui.add_space(8.0);
+
+    ui.label("Auto-save Interval:");
+    ui.add(
+        egui::Slider::new(&mut settings.autosave_interval_secs, 0..=600)
+            .suffix(" s")
+            .step_by(10.0),
+    )
+    .on_hover_text("Periodically snapshot project, settings and layout; 0 = off");
+    ui.label(
+        egui::RichText::new("Restored on next start if Playa exits unexpectedly.")
+            .small()
+            .weak(),
+    );
+
+    ui.add_space(16.0);
+    ui.heading("Import");
+    ui.add_space(8.0);
+
+    ui.label("Still Image Duration:");
+    ui.add(
+        egui::DragValue::new(&mut settings.playback.still_frames)
+            .range(1..=10000)
+            .suffix(" frames"),
+    )
+    .on_hover_text("Frames a single loaded image is held for; editable per clip afterwards");
+
+    ui.label("Sequence FPS:");
+    ui.add(
+        egui::DragValue::new(&mut settings.playback.sequence_fps)
+            .range(1.0..=240.0)
+            .speed(0.1)
+            .suffix(" fps"),
+    )
+    .on_hover_text("Playback rate for image sequences at load; videos keep their own rate");
+
+    ui.add_space(16.0);
+    ui.heading("Playback");
+    ui.add_space(8.0);
+
+    ui.label("Step Size:");
+    ui.add(
+        egui::DragValue::new(&mut settings.playback.step_frames)
+            .range(1..=10000)
+            .suffix(" frames"),
+    )
+    .on_hover_text("Frames moved by Shift+Arrow and Shift+PageUp/PageDown");
+
+    ui.add_space(8.0);
+    ui.checkbox(
+        &mut settings.playback.lock_to_fps,
+        "Lock playback to comp fps",
+    )
+    .on_hover_text(
+        "Keep the authored frame rate by dropping frames the display can't keep up with; \
+         off shows every frame, even if playback slows down",
+    );
+    ui.checkbox(&mut settings.playback.vsync, "Vsync")
+        .on_hover_text("Sync presentation to the monitor refresh (no tearing); applies on restart");
 }
 
 /// Render Web Server settings category
@@ -295,6 +572,39 @@ fn render_webserver_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
             settings.api_server_port = Some(port as u16);
         }
     });
+
+    ui.horizontal(|ui| {
+        ui.label("Token:");
+        // Edit a draft and apply it once the field loses focus, so the running
+        // server doesn't switch tokens on every keystroke
+        let draft_id = ui.id().with("api_token_draft");
+        let mut draft = ui
+            .data(|d| d.get_temp::<String>(draft_id))
+            .unwrap_or_else(|| settings.api_server_token.clone());
+        let response = ui.add(
+            egui::TextEdit::singleline(&mut draft)
+                .password(true)
+                .hint_text("none (open)")
+                .desired_width(220.0),
+        );
+        if response.has_focus() {
+            ui.data_mut(|d| d.insert_temp(draft_id, draft));
+        } else {
+            ui.data_mut(|d| d.remove::<String>(draft_id));
+            settings.api_server_token = draft;
+        }
+        if ui.button("Generate").clicked() {
+            settings.api_server_token = uuid::Uuid::new_v4().simple().to_string();
+        }
+        if !settings.api_server_token.is_empty() && ui.button("Clear").clicked() {
+            settings.api_server_token.clear();
+        }
+    })
+    .response
+    .on_hover_text(
+        "Clients must send 'Authorization: Bearer <token>' (except /api/health); \
+         /api/events also accepts ?token=<token> for browser EventSource",
+    );
     ui.add_space(12.0);
 
     if settings.api_server_enabled {
@@ -310,7 +620,9 @@ fn render_webserver_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
         ui.monospace("GET  /api/player              - player state");
         ui.monospace("GET  /api/comp                - active comp info");
         ui.monospace("GET  /api/cache               - cache stats");
+        ui.monospace("GET  /api/graph               - node graph JSON (?format=dot)");
         ui.monospace("GET  /api/health              - health check");
+        ui.monospace("GET  /api/events              - live state stream (SSE)");
 
         ui.add_space(8.0);
         ui.label("Player control:");
@@ -326,11 +638,18 @@ fn render_webserver_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
         ui.add_space(8.0);
         ui.label("Project:");
         ui.monospace("POST /api/project/load        - load sequence (JSON)");
+        ui.monospace("POST /api/comp/{id}/layer     - add layer (source, frame, index)");
+        ui.monospace("PATCH /api/comp/{id}/layer/{l} - set layer attrs (JSON)");
+        ui.monospace("DEL  /api/comp/{id}/layer/{l} - remove layer");
+        ui.monospace("POST /api/encode              - start encode (JSON settings)");
+        ui.monospace("GET  /api/encode/{id}         - encode job status");
+        ui.monospace("DEL  /api/encode/{id}         - cancel encode job");
 
         ui.add_space(8.0);
         ui.label("Screenshots:");
         ui.monospace("GET  /api/screenshot          - full window (with UI)");
         ui.monospace("GET  /api/screenshot/frame    - raw frame only");
+        ui.monospace("GET  /api/frame/current.png   - frame as PNG (?frame=N)");
 
         ui.add_space(8.0);
         ui.label("Other:");
@@ -377,9 +696,90 @@ fn render_ui_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
             .suffix(" px")
             .step_by(1.0),
     );
+    ui.add_space(8.0);
+
+    ui.label("Project Thumbnail Size (0 = Off):");
+    ui.add(
+        egui::Slider::new(&mut settings.project_thumbnail_size, 0..=256)
+            .suffix(" px")
+            .step_by(16.0),
+    );
+    ui.add_space(8.0);
+
+    ui.label("Time Display (timeline / status bar):");
+    let current = TIME_DISPLAY_MODES
+        .iter()
+        .find(|(_, m)| *m == settings.timeline.timeline_time_display)
+        .map_or("Frames", |(label, _)| *label);
+    egui::ComboBox::from_id_salt("prefs_time_display")
+        .selected_text(current)
+        .show_ui(ui, |ui| {
+            for (label, mode) in TIME_DISPLAY_MODES {
+                ui.selectable_value(&mut settings.timeline.timeline_time_display, *mode, *label);
+            }
+        });
     ui.add_space(16.0);
 
-    ui.checkbox(&mut settings.dark_mode, "Dark Mode");
+    ui.horizontal(|ui| {
+        ui.label("Theme:");
+        egui::ComboBox::from_id_salt("prefs_ui_theme")
+            .selected_text(settings.theme.as_str())
+            .show_ui(ui, |ui| {
+                for theme in UiTheme::ALL {
+                    ui.selectable_value(&mut settings.theme, theme, theme.as_str());
+                }
+            });
+    });
+    ui.horizontal(|ui| {
+        ui.label("Viewport Background:");
+        ui.color_edit_button_srgb(&mut settings.viewport.viewport_background);
+        if ui.button("Black").clicked() {
+            settings.viewport.viewport_background = DEFAULT_VIEWPORT_BACKGROUND;
+        }
+        if ui
+            .button("18% Gray")
+            .on_hover_text("Neutral surround for judging images")
+            .clicked()
+        {
+            settings.viewport.viewport_background = NEUTRAL_GRAY_BACKGROUND;
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label("Pixel Grid:");
+        ui.add(
+            egui::DragValue::new(&mut settings.viewport.grid_spacing)
+                .range(1..=256)
+                .suffix(" px"),
+        )
+        .on_hover_text("Image pixels between grid lines");
+        ui.color_edit_button_srgba_unmultiplied(&mut settings.viewport.grid_color);
+        ui.add(
+            egui::DragValue::new(&mut settings.viewport.grid_min_zoom)
+                .range(2.0..=64.0)
+                .speed(0.5)
+                .prefix("from ")
+                .suffix("x"),
+        )
+        .on_hover_text("Zoom at which the grid appears; hidden below to avoid moire");
+    });
+    ui.horizontal(|ui| {
+        ui.label("Zebra:");
+        ui.add(
+            egui::DragValue::new(&mut settings.viewport.zebra_highlight)
+                .range(0.01..=100.0)
+                .speed(0.01)
+                .prefix("highlights ≥ "),
+        )
+        .on_hover_text("Any channel at or above this is striped; above 1.0 for HDR frames");
+        ui.add(
+            egui::DragValue::new(&mut settings.viewport.zebra_shadow)
+                .range(0.0..=1.0)
+                .speed(0.001)
+                .prefix("shadows ≤ "),
+        )
+        .on_hover_text("Pixels with every channel at or below this are striped");
+    });
+    ui.add_space(8.0);
     ui.checkbox(
         &mut settings.show_tooltips,
         "Show Tooltips (2s delay on toolbar controls)",
@@ -427,7 +827,7 @@ fn render_ui_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
             .speed(1.0)
             .range(0..=256),
     );
-    ui.label("Takes effect on next launch. Defaults to ~75% of CPU cores.");
+    ui.label("Applies immediately; running jobs finish first. Defaults to ~75% of CPU cores.");
 
     ui.add_space(8.0);
     ui.label("Preload/cache settings moved to Settings → Cache.");
@@ -463,6 +863,15 @@ fn render_cache_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
     );
     ui.label("Delay before full preload after attribute change. 0 = immediate.");
 
+    ui.add_space(8.0);
+    ui.label("Proxy Playback:");
+    ui.horizontal(|ui| {
+        for level in playa_engine::entities::ProxyLevel::ALL {
+            ui.radio_value(&mut settings.playback.proxy_level, level, level.label());
+        }
+    });
+    ui.label("Decode at reduced resolution while playing/scrubbing; full res when paused.");
+
     ui.add_space(16.0);
     ui.heading("Cache & Memory");
     ui.add_space(8.0);
@@ -501,6 +910,19 @@ fn render_cache_settings(ui: &mut egui::Ui, settings: &mut AppSettings) {
     });
     ui.label("All Frames: Maximum performance, more memory usage.");
     ui.label("Last Only: Minimal memory, only last accessed frame per comp.");
+
+    ui.add_space(8.0);
+    ui.label("Max Preload Radius (frames):");
+    ui.horizontal(|ui| {
+        let mut limited = settings.cache.max_preload_radius.is_some();
+        if ui.checkbox(&mut limited, "Limit").changed() {
+            settings.cache.max_preload_radius = limited.then_some(250);
+        }
+        if let Some(radius) = settings.cache.max_preload_radius.as_mut() {
+            ui.add(egui::DragValue::new(radius).range(0..=10000).speed(10.0));
+        }
+    });
+    ui.label("Caps every preload, even \"All\" above. Off = fill the work area.");
 }
 
 /// Render Gizmo settings category (stored in the current Project)
@@ -569,6 +991,97 @@ fn render_gizmo_settings(
     }
 }
 
+/// Render Project Viewport category: viewport defaults stored in the current
+/// Project and applied when it loads
+fn render_project_viewport_settings(
+    ui: &mut egui::Ui,
+    settings: &AppSettings,
+    project: Option<&playa_engine::entities::Project>,
+    event_bus: Option<&playa_engine::core::event_bus::EventBus>,
+) {
+    ui.heading("Project Viewport");
+    ui.add_space(8.0);
+
+    let Some(project) = project else {
+        ui.label("No active project - viewport defaults are stored per project.");
+        return;
+    };
+
+    let Some(bus) = event_bus else {
+        ui.label("Event bus unavailable - cannot apply changes.");
+        return;
+    };
+
+    let current = project.viewport_defaults();
+    let mut next = current.clone();
+
+    ui.label("Applied when the project loads. Unchecked items keep the app settings;");
+    ui.label("changes made in the viewport afterwards last for the session.");
+    ui.add_space(8.0);
+
+    egui::Grid::new("project_viewport_defaults")
+        .num_columns(2)
+        .spacing([12.0, 6.0])
+        .show(ui, |ui| {
+            let mut set = next.view_transform.is_some();
+            if ui.checkbox(&mut set, "View transform").changed() {
+                next.view_transform = set.then(|| settings.current_shader.clone());
+            }
+            match &next.view_transform {
+                Some(view) => {
+                    ui.horizontal(|ui| {
+                        ui.monospace(view);
+                        if *view != settings.current_shader
+                            && ui
+                                .small_button("Use current")
+                                .on_hover_text(&settings.current_shader)
+                                .clicked()
+                        {
+                            next.view_transform = Some(settings.current_shader.clone());
+                        }
+                    });
+                }
+                None => {
+                    ui.weak("app setting");
+                }
+            }
+            ui.end_row();
+
+            let mut set = next.background.is_some();
+            if ui.checkbox(&mut set, "Background").changed() {
+                next.background = set.then_some(settings.viewport.viewport_background);
+            }
+            match next.background.as_mut() {
+                Some(rgb) => {
+                    ui.color_edit_button_srgb(rgb);
+                }
+                None => {
+                    ui.weak("app setting");
+                }
+            }
+            ui.end_row();
+
+            let mut set = next.checker.is_some();
+            if ui.checkbox(&mut set, "Alpha checkerboard").changed() {
+                next.checker = set.then_some(false);
+            }
+            match next.checker.as_mut() {
+                Some(on) => {
+                    ui.checkbox(on, "On");
+                }
+                None => {
+                    ui.weak("session");
+                }
+            }
+            ui.end_row();
+        });
+
+    if next != current {
+        bus.emit(SetViewportDefaultsEvent(next));
+        ui.ctx().request_repaint();
+    }
+}
+
 /// Render Compositing settings category
 fn render_compositing_settings(
     ui: &mut egui::Ui,
@@ -610,6 +1123,54 @@ fn render_compositing_settings(
     ui.label("Prevents infinite loops when compositions reference each other.");
 }
 
+/// Render Hotkeys settings category
+fn render_hotkey_settings(
+    ui: &mut egui::Ui,
+    settings: &AppSettings,
+    event_bus: Option<&playa_engine::core::event_bus::EventBus>,
+) {
+    ui.heading("Hotkeys");
+    ui.add_space(8.0);
+
+    if settings.custom_hotkeys.is_empty() {
+        ui.label("Using the default bindings.");
+    } else {
+        ui.label("Using imported custom bindings.");
+    }
+    ui.label("Export the set to a file to carry it to another workstation.");
+    ui.add_space(8.0);
+
+    let Some(bus) = event_bus else {
+        return;
+    };
+    ui.horizontal(|ui| {
+        if ui.button("Export...").clicked()
+            && let Some(path) = rfd::FileDialog::new()
+                .add_filter("Hotkeys", &["json"])
+                .set_file_name("playa_hotkeys.json")
+                .save_file()
+        {
+            bus.emit(ExportHotkeysEvent(path));
+        }
+        if ui.button("Import...").clicked()
+            && let Some(path) = rfd::FileDialog::new()
+                .add_filter("Hotkeys", &["json"])
+                .pick_file()
+        {
+            bus.emit(ImportHotkeysEvent(path));
+        }
+        if ui
+            .add_enabled(
+                !settings.custom_hotkeys.is_empty(),
+                egui::Button::new("Reset to Defaults"),
+            )
+            .clicked()
+        {
+            bus.emit(ResetHotkeysEvent);
+        }
+    });
+}
+
 /// Render settings window
 pub fn render_settings_window(
     ctx: &egui::Context,
@@ -648,8 +1209,10 @@ pub fn render_settings_window(
                                 builder.leaf(1, SettingsCategory::UI.as_str());
                                 builder.leaf(2, SettingsCategory::Cache.as_str());
                                 builder.leaf(3, SettingsCategory::Gizmo.as_str());
+                                builder.leaf(7, SettingsCategory::ProjectViewport.as_str());
                                 builder.leaf(4, SettingsCategory::Compositing.as_str());
                                 builder.leaf(5, SettingsCategory::WebServer.as_str());
+                                builder.leaf(6, SettingsCategory::Hotkeys.as_str());
                             });
 
                             // Handle selection from actions
@@ -664,6 +1227,8 @@ pub fn render_settings_window(
                                         3 => SettingsCategory::Gizmo,
                                         4 => SettingsCategory::Compositing,
                                         5 => SettingsCategory::WebServer,
+                                        6 => SettingsCategory::Hotkeys,
+                                        7 => SettingsCategory::ProjectViewport,
                                         _ => selected,
                                     };
                                 }
@@ -683,9 +1248,17 @@ pub fn render_settings_window(
                                 SettingsCategory::Gizmo => {
                                     render_gizmo_settings(ui, project, event_bus)
                                 }
+                                SettingsCategory::ProjectViewport => {
+                                    render_project_viewport_settings(
+                                        ui, settings, project, event_bus,
+                                    )
+                                }
                                 SettingsCategory::Compositing => {
                                     render_compositing_settings(ui, settings, event_bus)
                                 }
+                                SettingsCategory::Hotkeys => {
+                                    render_hotkey_settings(ui, settings, event_bus)
+                                }
                                 SettingsCategory::WebServer => {
                                     render_webserver_settings(ui, settings)
                                 }
@@ -744,8 +1317,15 @@ mod tests {
         let p = PlaybackSettings::default();
         assert_eq!(p.fps_base, 24.0);
         assert!(p.loop_enabled);
+        assert!(!p.sequence_playback);
         assert_eq!(p.preload_radius, -1);
         assert_eq!(p.preload_delay_ms, 500);
+        assert_eq!(p.proxy_level, playa_engine::entities::ProxyLevel::Full);
+        assert_eq!(p.still_frames, 1);
+        assert_eq!(p.sequence_fps, 24.0);
+        assert_eq!(p.step_frames, 25);
+        assert!(p.lock_to_fps);
+        assert!(p.vsync);
     }
 
     #[test]
@@ -791,6 +1371,8 @@ mod tests {
         assert_eq!(s.viewport.hover_stroke_width, 4.0);
         assert_eq!(s.viewport.hover_corner_length, 30.0);
         assert_eq!(s.viewport.hover_opacity, 0.75);
+        assert_eq!(s.viewport.grid_style(), GridStyle::default());
+        assert_eq!(s.viewport.zebra_thresholds(), ZebraThresholds::default());
     }
 
     #[test]
@@ -824,10 +1406,12 @@ mod tests {
             "preload_radius",
             "cache_memory_percent",
             "cache_strategy",
+            "max_preload_radius",
             "viewport_hover_highlight",
             "hover_opacity",
             "timeline_layer_height",
             "timeline_snap_enabled",
+            "timeline_time_display",
         ] {
             assert!(json.get(key).is_some(), "missing legacy top-level key {key}");
         }
@@ -838,4 +1422,59 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn recent_items_dedup_and_cap() {
+        let mut items = Vec::new();
+        push_recent(&mut items, PathBuf::from("/a.exr"), RecentKind::Media);
+        push_recent(&mut items, PathBuf::from("/p.json"), RecentKind::Project);
+        push_recent(&mut items, PathBuf::from("/a.exr"), RecentKind::Media);
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].path, PathBuf::from("/a.exr"));
+        assert_eq!(items[1].kind, RecentKind::Project);
+
+        for i in 0..MAX_RECENT_ITEMS + 5 {
+            push_recent(
+                &mut items,
+                PathBuf::from(format!("/{i}.exr")),
+                RecentKind::Media,
+            );
+        }
+        assert_eq!(items.len(), MAX_RECENT_ITEMS);
+        assert_eq!(
+            items[0].path,
+            PathBuf::from(format!("/{}.exr", MAX_RECENT_ITEMS + 4))
+        );
+    }
+
+    #[test]
+    fn prune_recent_drops_missing_paths() {
+        let existing = std::env::temp_dir();
+        let mut items = vec![
+            RecentItem {
+                path: existing.clone(),
+                kind: RecentKind::Media,
+            },
+            RecentItem {
+                path: existing.join("playa-no-such-file.exr"),
+                kind: RecentKind::Media,
+            },
+        ];
+        prune_recent(&mut items);
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].path, existing);
+    }
+
+    #[test]
+    fn legacy_dark_mode_maps_to_theme() {
+        let s: AppSettings = serde_json::from_str(r#"{"dark_mode": false}"#).unwrap();
+        assert_eq!(s.theme, UiTheme::Light);
+        let s: AppSettings = serde_json::from_str(r#"{"dark_mode": true}"#).unwrap();
+        assert_eq!(s.theme, UiTheme::Dark);
+        let s: AppSettings = serde_json::from_str(r#"{"theme": "Midnight"}"#).unwrap();
+        assert_eq!(s.theme, UiTheme::Midnight);
+        // Round-trips under the new key
+        let json = serde_json::to_value(&s).unwrap();
+        assert_eq!(json["theme"], "Midnight");
+    }
 }
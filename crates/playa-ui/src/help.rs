@@ -24,11 +24,22 @@ pub const VIEWPORT_HELP: &[HelpEntry] = &[
     HelpEntry::new("W", "Move Tool"),
     HelpEntry::new("E", "Rotate Tool"),
     HelpEntry::new("R", "Scale Tool"),
+    HelpEntry::new("D", "ROI Tool (drag region, click clears)"),
+    HelpEntry::new("S", "Annotate Tool (draw review notes)"),
     HelpEntry::new("A / H", "100% Zoom"),
     HelpEntry::new("F", "Fit to View"),
+    HelpEntry::new("G", "Cycle Guides"),
+    HelpEntry::new("C", "Alpha Checkerboard"),
+    HelpEntry::new("Shift+O", "Onion Skin (paused)"),
+    HelpEntry::new("T", "Rotate 90° CW"),
+    HelpEntry::new("X / Y", "Flip / Flop"),
+    HelpEntry::new("Shift+T", "Reset Orientation"),
+    HelpEntry::new("1-9", "Recall View Bookmark"),
+    HelpEntry::new("Ctrl+1-9", "Save View Bookmark"),
     HelpEntry::new("Wheel", "Zoom"),
     HelpEntry::new("MMB Drag", "Pan"),
     HelpEntry::new("LMB", "Scrub / Pick"),
+    HelpEntry::new("Alt+Release", "Key Gizmo Drag"),
     HelpEntry::new("Backspace", "Frame Numbers"),
 ];
 
@@ -39,19 +50,27 @@ pub const PLAYBACK_HELP: &[HelpEntry] = &[
     HelpEntry::new("J / ,", "Jog Back"),
     HelpEntry::new("L / .", "Jog Forward"),
     HelpEntry::new("`", "Toggle Loop"),
+    HelpEntry::new("Shift+`", "Toggle Auto-advance (next comp at end)"),
+    HelpEntry::new("Shift+H", "Hold Frame (freeze until released)"),
+    HelpEntry::new("P", "Proxy Full/Half/Quarter"),
     HelpEntry::new("- / +", "FPS Down/Up"),
 ];
 
 /// Frame navigation
 pub const NAVIGATION_HELP: &[HelpEntry] = &[
     HelpEntry::new("Left/Right", "Step 1 frame"),
-    HelpEntry::new("Shift+Arrows", "Step 25 frames"),
+    HelpEntry::new("Shift+Arrows", "Step N frames (prefs)"),
     HelpEntry::new("Ctrl+Arrows", "Start/End"),
     HelpEntry::new("1 / Home", "Jump Start"),
     HelpEntry::new("2 / End", "Jump End"),
     HelpEntry::new("; / '", "Prev/Next Edge"),
+    HelpEntry::new("M", "Add Marker"),
+    HelpEntry::new("Alt+; / Alt+'", "Prev/Next Marker"),
+    HelpEntry::new("Ctrl+; / Ctrl+'", "Prev/Next Keyframe"),
+    HelpEntry::new("Ctrl+G", "Go to Frame (number or timecode)"),
+    HelpEntry::new("I / O", "Mark In / Out"),
     HelpEntry::new("B / N", "Set Range"),
-    HelpEntry::new("Ctrl+B", "Reset Range"),
+    HelpEntry::new("Alt+X / Ctrl+B", "Clear In/Out"),
 ];
 
 // =============================================================================
@@ -63,11 +82,15 @@ pub const TIMELINE_HELP: &[HelpEntry] = &[
     HelpEntry::new("[ / ]", "Align to Cursor"),
     HelpEntry::new("Alt+[ / ]", "Trim to Cursor"),
     HelpEntry::new("Delete", "Remove Layer"),
+    HelpEntry::new("Shift+Del", "Ripple Delete"),
+    HelpEntry::new("Shift+Alt+]", "Ripple Trim End"),
     HelpEntry::new("Ctrl+D", "Duplicate"),
+    HelpEntry::new("Ctrl+Shift+D", "Split at Cursor"),
     HelpEntry::new("Ctrl+C/V", "Copy/Paste"),
     HelpEntry::new("Ctrl+A", "Select All"),
     HelpEntry::new("Ctrl+R", "Reset Trims"),
     HelpEntry::new("F / A", "Fit / Work Area"),
+    HelpEntry::new("Shift+Drag", "Invert Snap"),
     HelpEntry::new("Wheel", "Zoom"),
     HelpEntry::new("MMB Drag", "Pan"),
 ];
@@ -77,6 +100,7 @@ pub const PROJECT_HELP: &[HelpEntry] = &[
     HelpEntry::new("Dbl-click", "Open Comp"),
     HelpEntry::new("Drag", "Reorder / Add"),
     HelpEntry::new("Delete", "Remove"),
+    HelpEntry::new("Ctrl+D", "Duplicate Comp"),
     HelpEntry::new("Enter", "Rename"),
 ];
 
@@ -91,6 +115,9 @@ pub const GLOBAL_HELP: &[HelpEntry] = &[
     HelpEntry::new("ESC", "Exit / Quit"),
     HelpEntry::new("Ctrl+S", "Save"),
     HelpEntry::new("Ctrl+O", "Open"),
+    HelpEntry::new("Ctrl+Shift+O", "Reopen recent"),
+    HelpEntry::new("Ctrl+Z", "Undo"),
+    HelpEntry::new("Ctrl+Shift+Z", "Redo"),
     HelpEntry::new("Ctrl+Alt+/", "Clear Cache"),
 ];
 
@@ -107,13 +107,15 @@ use crate::widgets::timeline::{
 use crate::widgets::viewport::shaders::Shaders;
 use playa_engine::core::event_bus::EventBus;
 use playa_engine::core::player::Player;
-use playa_engine::entities::Project;
+use playa_engine::core::workers::Workers;
+use playa_engine::entities::{Node, Project};
 
 /// Render timeline panel inside a dock tab. Returns true if shader changed.
 pub fn render_timeline_panel(
     ui: &mut egui::Ui,
     player: &mut Player,
     project: &Project,
+    workers: &Workers,
     shader_manager: &mut Shaders,
     timeline_state: &mut TimelineState,
     event_bus: &EventBus,
@@ -158,7 +160,10 @@ pub fn render_timeline_panel(
                 render_toolbar(
                     ui,
                     timeline_state,
+                    comp.frame(),
+                    comp.fps(),
                     player.loop_enabled(),
+                    player.sequence_playback(),
                     show_tooltips,
                     layout_names,
                     current_layout,
@@ -227,6 +232,7 @@ pub fn render_timeline_panel(
                                     comp_uuid,
                                     comp,
                                     project,
+                                    workers,
                                     &config,
                                     timeline_state,
                                     timeline_state.view_mode,
@@ -246,6 +252,7 @@ pub fn render_timeline_panel(
                                     comp_uuid,
                                     comp,
                                     project,
+                                    workers,
                                     &config,
                                     timeline_state,
                                     timeline_state.view_mode,
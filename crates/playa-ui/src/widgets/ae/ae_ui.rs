@@ -426,6 +426,40 @@ fn render_effect_attrs(
                                         ));
                                     }
                                 }
+                                AttrValue::Bool(v) => {
+                                    let mut temp = *v;
+                                    if ui.checkbox(&mut temp, "").changed() {
+                                        actions.push(EffectAction::AttrChanged(
+                                            effect.uuid,
+                                            key.clone(),
+                                            AttrValue::Bool(temp),
+                                        ));
+                                    }
+                                }
+                                AttrValue::Vec3(v) => {
+                                    // One DragValue per channel, same range for all
+                                    let mut temp = *v;
+                                    let mut changed = false;
+                                    ui.horizontal(|ui| {
+                                        for c in &mut temp {
+                                            changed |= ui
+                                                .add(
+                                                    egui::DragValue::new(c)
+                                                        .speed(speed)
+                                                        .range(min..=max)
+                                                        .max_decimals(3),
+                                                )
+                                                .changed();
+                                        }
+                                    });
+                                    if changed {
+                                        actions.push(EffectAction::AttrChanged(
+                                            effect.uuid,
+                                            key.clone(),
+                                            AttrValue::Vec3(temp),
+                                        ));
+                                    }
+                                }
                                 _ => {
                                     ui.label(format!("{:?}", value));
                                 }
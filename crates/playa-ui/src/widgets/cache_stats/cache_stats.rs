@@ -0,0 +1,250 @@
+//! Cache statistics panel: memory vs budget, hit rate, evictions, per-comp
+//! frame status counts, a rolling memory sparkline and the active comp's
+//! load errors (path plus loader message per failed frame).
+//!
+//! Meant for debugging stutter: a climbing eviction rate with memory pinned
+//! at the budget means the cache is thrashing. Counters are sampled a few
+//! times per second rather than every frame, so an open panel costs one
+//! cache read lock per sample.
+
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use playa_engine::core::cache_man::CacheManager;
+use playa_engine::core::global_cache::{GlobalFrameCache, StatusCounts};
+use playa_engine::entities::CacheStatsSnapshot;
+use uuid::Uuid;
+
+/// How often counters are re-read (4 Hz)
+pub const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Sparkline length in samples (one minute at [`SAMPLE_INTERVAL`])
+pub const HISTORY_LEN: usize = 240;
+
+/// Counters read at one sample tick
+#[derive(Debug, Clone, Copy, Default)]
+struct Sample {
+    usage: usize,
+    limit: usize,
+    stats: CacheStatsSnapshot,
+    counts: Option<StatusCounts>,
+    /// Evictions per second since the previous sample
+    eviction_rate: f32,
+}
+
+/// Persistent panel state: last sample plus the memory history.
+#[derive(Debug, Default)]
+pub struct CacheStatsState {
+    /// Memory usage as a fraction of the budget, oldest first
+    history: VecDeque<f32>,
+    last_sample: Option<Instant>,
+    sample: Sample,
+    /// Failed frames of the active comp: (frame, message)
+    load_errors: Vec<(i32, String)>,
+}
+
+impl CacheStatsState {
+    /// Re-read counters if [`SAMPLE_INTERVAL`] elapsed since the last sample
+    fn sample(
+        &mut self,
+        manager: Option<&Arc<CacheManager>>,
+        cache: Option<&Arc<GlobalFrameCache>>,
+        comp: Option<Uuid>,
+    ) {
+        let now = Instant::now();
+        let elapsed = match self.last_sample {
+            Some(t) if now - t < SAMPLE_INTERVAL => return,
+            Some(t) => (now - t).as_secs_f32(),
+            None => 0.0,
+        };
+
+        let (usage, limit) = manager.map(|m| m.mem()).unwrap_or((0, 0));
+        let stats = cache.map(|c| c.stats_snapshot()).unwrap_or_default();
+        let evicted = stats.evictions.saturating_sub(self.sample.stats.evictions);
+        self.sample = Sample {
+            usage,
+            limit,
+            stats,
+            counts: cache.zip(comp).map(|(c, uuid)| c.status_counts(uuid)),
+            eviction_rate: if elapsed > 0.0 {
+                evicted as f32 / elapsed
+            } else {
+                0.0
+            },
+        };
+        self.load_errors = cache
+            .zip(comp)
+            .map(|(c, uuid)| c.load_errors(uuid))
+            .unwrap_or_default();
+        self.last_sample = Some(now);
+
+        let fraction = if limit > 0 {
+            usage as f32 / limit as f32
+        } else {
+            0.0
+        };
+        if self.history.len() == HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(fraction);
+    }
+}
+
+/// Render the cache stats panel.
+///
+/// `comp` is the active comp whose cached frames are counted; `workers` the
+/// active worker thread count.
+pub fn render(
+    ui: &mut egui::Ui,
+    state: &mut CacheStatsState,
+    manager: Option<&Arc<CacheManager>>,
+    cache: Option<&Arc<GlobalFrameCache>>,
+    comp: Option<Uuid>,
+    workers: usize,
+) {
+    state.sample(manager, cache, comp);
+    // Keep sampling while the panel is visible, without repainting every frame
+    ui.ctx().request_repaint_after(SAMPLE_INTERVAL);
+
+    let s = state.sample;
+    let mb = |bytes: usize| bytes as f64 / 1024.0 / 1024.0;
+    let over_budget = s.limit > 0 && s.usage >= s.limit;
+
+    egui::Grid::new("cache_stats_grid")
+        .num_columns(2)
+        .spacing([16.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("Memory:");
+            let mem = format!(
+                "{:.0} / {:.0} MB ({:.0}%)",
+                mb(s.usage),
+                mb(s.limit),
+                if s.limit > 0 {
+                    s.usage as f64 / s.limit as f64 * 100.0
+                } else {
+                    0.0
+                }
+            );
+            if over_budget {
+                ui.colored_label(egui::Color32::from_rgb(255, 120, 90), mem);
+            } else {
+                ui.monospace(mem);
+            }
+            ui.end_row();
+
+            ui.label("Frames cached:");
+            ui.monospace(s.stats.size.to_string());
+            ui.end_row();
+
+            ui.label("Hit rate:");
+            ui.monospace(format!(
+                "{:.1}% ({} hits / {} misses)",
+                s.stats.hit_rate() * 100.0,
+                s.stats.hits,
+                s.stats.misses
+            ));
+            ui.end_row();
+
+            ui.label("Evictions:");
+            let evictions = format!("{} ({:.1}/s)", s.stats.evictions, s.eviction_rate);
+            if s.eviction_rate > 0.0 {
+                ui.colored_label(egui::Color32::from_rgb(255, 200, 90), evictions)
+                    .on_hover_text("Frames are being dropped to stay within the memory budget");
+            } else {
+                ui.monospace(evictions);
+            }
+            ui.end_row();
+
+            ui.label("Active comp:");
+            match s.counts {
+                Some(c) => ui.monospace(format!(
+                    "{} loaded, {} loading, {} error",
+                    c.loaded, c.loading, c.error
+                )),
+                None => ui.weak("none"),
+            };
+            ui.end_row();
+
+            ui.label("Workers:");
+            ui.monospace(format!("{} threads", workers));
+            ui.end_row();
+        });
+
+    ui.add_space(8.0);
+    ui.label("Memory (last minute):");
+    render_sparkline(ui, &state.history);
+
+    ui.add_space(4.0);
+    if let Some(cache) = cache
+        && ui
+            .small_button("Reset counters")
+            .on_hover_text("Zero hits, misses and evictions")
+            .clicked()
+    {
+        cache.stats().reset();
+        state.sample.stats = CacheStatsSnapshot::default();
+    }
+
+    ui.add_space(8.0);
+    render_load_errors(ui, state);
+}
+
+/// Collapsible log of the active comp's failed frames
+fn render_load_errors(ui: &mut egui::Ui, state: &CacheStatsState) {
+    egui::CollapsingHeader::new(format!("Load errors ({})", state.load_errors.len()))
+        .id_salt("cache_stats_load_errors")
+        .show(ui, |ui| {
+            if state.load_errors.is_empty() {
+                ui.weak("No failed frames");
+                return;
+            }
+            egui::ScrollArea::vertical()
+                .max_height(160.0)
+                .show(ui, |ui| {
+                    for (frame, message) in &state.load_errors {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.monospace(format!("{:>6}", frame));
+                            ui.colored_label(egui::Color32::from_rgb(255, 120, 90), message);
+                        });
+                    }
+                });
+        });
+}
+
+/// Memory usage history as a line against the budget (top edge = 100%)
+fn render_sparkline(ui: &mut egui::Ui, history: &VecDeque<f32>) {
+    let size = egui::vec2(ui.available_width().max(120.0), 60.0);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    let visuals = ui.visuals();
+
+    painter.rect_filled(rect, 2.0, visuals.extreme_bg_color);
+    painter.hline(
+        rect.x_range(),
+        rect.top(),
+        egui::Stroke::new(1.0, egui::Color32::from_rgb(255, 120, 90)),
+    );
+
+    if history.len() < 2 {
+        return;
+    }
+    let step = rect.width() / (HISTORY_LEN - 1) as f32;
+    // Right-aligned so the newest sample always sits at the right edge
+    let x0 = rect.right() - step * (history.len() - 1) as f32;
+    let points: Vec<egui::Pos2> = history
+        .iter()
+        .enumerate()
+        .map(|(i, f)| {
+            egui::pos2(
+                x0 + step * i as f32,
+                rect.bottom() - f.clamp(0.0, 1.0) * rect.height(),
+            )
+        })
+        .collect();
+    painter.add(egui::Shape::line(
+        points,
+        egui::Stroke::new(1.5, visuals.selection.bg_fill),
+    ));
+}
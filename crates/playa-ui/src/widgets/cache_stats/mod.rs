@@ -0,0 +1,3 @@
+pub mod cache_stats;
+
+pub use cache_stats::CacheStatsState;
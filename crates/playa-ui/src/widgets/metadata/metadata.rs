@@ -0,0 +1,182 @@
+//! Metadata panel: the file header behind the displayed frame as a
+//! read-only tree.
+//!
+//! EXR shows every part's attributes in file order (channels, compression,
+//! data/display windows, pixel aspect, chromaticities, custom attrs); other
+//! formats show what their header carries (PNG text chunks, EXIF, video
+//! stream info). The header is re-read only when the file on disk changes,
+//! so scrubbing a video or holding a still costs nothing, and the read runs
+//! on a background thread so slow or remote files don't stall the UI.
+//!
+//! The displayed frame's content hash (QC) is computed on request: hashing a
+//! 4K float frame is too slow to redo on every scrub step.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::mpsc::{Receiver, TryRecvError, channel};
+use std::thread;
+
+use eframe::egui;
+use playa_engine::entities::frame::{Frame, PixelBuffer};
+use playa_io::MetaGroup;
+use playa_io::media;
+
+/// Last inspected file and its header groups (or the read error).
+#[derive(Debug, Default)]
+pub struct MetadataState {
+    /// Disk file the groups were read from (frame / layer suffixes stripped)
+    file: Option<PathBuf>,
+    groups: Vec<MetaGroup>,
+    error: Option<String>,
+    /// Header read in flight for `file`
+    pending: Option<Receiver<Result<Vec<MetaGroup>, String>>>,
+    /// Pixels of the last hashed frame and their `Frame::content_hash`
+    hash: Option<(Arc<PixelBuffer>, String)>,
+}
+
+impl MetadataState {
+    /// Start re-reading the header if `path` points at a different file on
+    /// disk, and pick up the result of a read in flight
+    fn update(&mut self, ctx: &egui::Context, path: Option<&Path>) {
+        let file = path.map(|p| media::parse_video_path(&media::disk_path(p)).0);
+        if file != self.file {
+            self.file = file;
+            self.groups.clear();
+            self.error = None;
+            // Dropping the old receiver discards a stale read
+            self.pending = path.map(|path| {
+                let (tx, rx) = channel();
+                let path = path.to_path_buf();
+                let ctx = ctx.clone();
+                thread::spawn(move || {
+                    let _ = tx.send(playa_io::inspect(&path).map_err(|e| e.to_string()));
+                    ctx.request_repaint();
+                });
+                rx
+            });
+        }
+        if let Some(rx) = &self.pending {
+            match rx.try_recv() {
+                Ok(Ok(groups)) => self.groups = groups,
+                Ok(Err(e)) => self.error = Some(e),
+                Err(TryRecvError::Empty) => return,
+                Err(TryRecvError::Disconnected) => {
+                    self.error = Some("Header read failed".to_string())
+                }
+            }
+            self.pending = None;
+        }
+    }
+}
+
+/// Render the metadata panel for `path`, the source file of the displayed
+/// frame (`None` when no file layer covers it). `frame` is the displayed
+/// (composed) frame, offered for hashing.
+pub fn render(
+    ui: &mut egui::Ui,
+    state: &mut MetadataState,
+    path: Option<&Path>,
+    frame: Option<&Frame>,
+) {
+    state.update(ui.ctx(), path);
+    if let Some(frame) = frame {
+        render_hash(ui, state, frame);
+        ui.separator();
+    }
+
+    let Some(file) = &state.file else {
+        ui.weak("No source file at the current frame");
+        return;
+    };
+    ui.label(
+        file.file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    )
+    .on_hover_text(file.display().to_string());
+    ui.separator();
+
+    if state.pending.is_some() {
+        ui.horizontal(|ui| {
+            ui.spinner();
+            ui.weak("Reading header...");
+        });
+        return;
+    }
+    if let Some(error) = &state.error {
+        ui.colored_label(egui::Color32::from_rgb(255, 120, 90), error);
+        return;
+    }
+    if state
+        .groups
+        .iter()
+        .all(|g| g.rows.is_empty() && g.children.is_empty())
+    {
+        ui.weak("No metadata in this file");
+        return;
+    }
+    egui::ScrollArea::both()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for (idx, group) in state.groups.iter().enumerate() {
+                render_group(ui, group, egui::Id::new("metadata_group").with(idx));
+            }
+        });
+}
+
+/// Content hash row: the cached hash while the same pixels are shown,
+/// otherwise a button computing it
+fn render_hash(ui: &mut egui::Ui, state: &mut MetadataState, frame: &Frame) {
+    let buffer = frame.buffer();
+    let current = state
+        .hash
+        .as_ref()
+        .filter(|(pixels, _)| Arc::ptr_eq(pixels, &buffer))
+        .map(|(_, hash)| hash.clone());
+    ui.horizontal(|ui| {
+        ui.label("Hash:");
+        match current {
+            Some(hash) => {
+                ui.monospace(&hash[..16])
+                    .on_hover_text(format!("SHA-256 of the frame's pixels\n{}", hash));
+                if ui.small_button("Copy").clicked() {
+                    ui.ctx().copy_text(hash);
+                }
+            }
+            None => {
+                if ui
+                    .small_button("Compute")
+                    .on_hover_text("SHA-256 of the displayed frame's pixels (QC)")
+                    .clicked()
+                {
+                    state.hash = Some((buffer, frame.content_hash()));
+                }
+            }
+        }
+    });
+}
+
+/// One collapsible group: a key/value grid, then nested groups
+fn render_group(ui: &mut egui::Ui, group: &MetaGroup, id: egui::Id) {
+    egui::CollapsingHeader::new(&group.title)
+        .id_salt(id)
+        .default_open(true)
+        .show(ui, |ui| {
+            if !group.rows.is_empty() {
+                egui::Grid::new(id.with("rows"))
+                    .num_columns(2)
+                    .spacing([16.0, 2.0])
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, value) in &group.rows {
+                            ui.label(key);
+                            ui.monospace(value);
+                            ui.end_row();
+                        }
+                    });
+            }
+            for (idx, child) in group.children.iter().enumerate() {
+                render_group(ui, child, id.with(idx));
+            }
+        });
+}
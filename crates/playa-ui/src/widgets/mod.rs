@@ -5,8 +5,10 @@
 
 pub mod actions;
 pub mod ae;
+pub mod cache_stats;
 pub mod dnd;
 pub mod file_dialogs;
+pub mod metadata;
 pub mod node_editor;
 pub mod project;
 pub mod status;
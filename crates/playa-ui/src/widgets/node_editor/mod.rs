@@ -7,4 +7,4 @@ pub mod node_events;
 mod node_graph;
 
 pub use node_events::*;
-pub use node_graph::{NodeEditorState, render_node_editor};
+pub use node_graph::{GraphExport, NodeEditorState, render_node_editor};
@@ -19,19 +19,33 @@
 //! KNOWN LIMITATION: a `PlayaNode` has a single `in` pin, so a parent with
 //! multiple children shows one wire (the model's input holds one source). Full
 //! multi-input wiring is a follow-up.
+//!
+//! Merge nodes are the exception: a merge is a two-layer comp (see
+//! `Project::create_merge`), drawn as a `Merge` node whose `A`/`B` pins are
+//! wired to its top/background inputs. It composites through the regular comp
+//! pipeline, so activating it shows the result in the viewport. The wires are
+//! drawn from the comp, so inputs are rewired from the Merge menu (which edits
+//! the merge's layers) rather than by dragging.
+//!
+//! The same comp DAG can be exported for tooling: [`NodeEditorState::export_json`]
+//! (topology + attrs, deterministic order for diffing) and
+//! [`NodeEditorState::export_dot`] (Graphviz, for diagrams).
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::RwLockReadGuard;
 
-use eframe::egui::{Pos2, Ui};
+use eframe::egui::{self, Pos2, Ui};
 use nodes_core::{NodeId, NodeTypeRegistry, Subnet, ValueTypes};
 use nodes_egui::{GraphViewportConfig, GraphViewportExtras, GraphViewportHost, GraphViewportState};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 use playa_engine::core::event_bus::BoxedEvent;
+use playa_engine::entities::compositor::BlendMode;
 use playa_engine::entities::node::Node;
-use playa_engine::entities::{AttrValue, Project};
+use playa_engine::entities::{AttrValue, Attrs, NodeKind, Project};
+
+use super::node_events::{CreateMergeNodeEvent, ExportNodeGraphEvent, SetMergeInputEvent};
 
 /// Fallback grid spacing when a node has no stored `node_pos`.
 const HORIZONTAL_SPACING: f32 = 220.0;
@@ -41,6 +55,8 @@ const VERTICAL_SPACING: f32 = 120.0;
 /// generic input + output; the cook is a no-op (this graph never evaluates —
 /// playa's comp engine does the real work).
 const NODE_TYPE: &str = "PlayaNode";
+/// Two-input merge (comp flagged `merge`): `A` over `B`.
+const MERGE_NODE_TYPE: &str = "Merge";
 
 fn default_true() -> bool {
     true
@@ -68,6 +84,14 @@ impl Default for PlayaGraphHost {
             [nodes_core::prelude::any("in")] => [nodes_core::prelude::any("out")],
             |_node, _graph, _registry, _types| Ok(())
         );
+        nodes_core::node!(
+            registry,
+            "Merge",
+            "Playa",
+            [nodes_core::prelude::any("A"), nodes_core::prelude::any("B")]
+                => [nodes_core::prelude::any("out")],
+            |_node, _graph, _registry, _types| Ok(())
+        );
 
         Self {
             subnet: Subnet::new(),
@@ -122,6 +146,15 @@ pub struct NodeEditorState {
     /// Auto-layout the subnet on the next frame.
     #[serde(skip)]
     pub layout_requested: bool,
+    /// Merge picker: foreground input
+    #[serde(skip)]
+    merge_a: Option<Uuid>,
+    /// Merge picker: background input
+    #[serde(skip)]
+    merge_b: Option<Uuid>,
+    /// Merge picker: blend mode for A over B
+    #[serde(skip)]
+    merge_blend: Option<BlendMode>,
 }
 
 impl Default for NodeEditorState {
@@ -135,6 +168,9 @@ impl Default for NodeEditorState {
             fit_all_requested: false,
             fit_selected_requested: false,
             layout_requested: false,
+            merge_a: None,
+            merge_b: None,
+            merge_blend: None,
         }
     }
 }
@@ -163,6 +199,173 @@ impl NodeEditorState {
             self.needs_rebuild = true;
         }
     }
+
+    /// Comp the editor currently shows
+    pub fn comp_uuid(&self) -> Option<Uuid> {
+        self.comp_uuid
+    }
+
+    /// Structured graph of the shown comp (`None` until a comp is set).
+    pub fn export(&self, project: &Project) -> Option<GraphExport> {
+        self.comp_uuid.map(|uuid| GraphExport::build(project, uuid))
+    }
+
+    /// Pretty JSON of [`GraphExport`]: every node's UUID, kind, attrs and its
+    /// input connections — enough to rebuild the topology.
+    pub fn export_json(&self, project: &Project) -> Option<String> {
+        self.export(project).and_then(|g| g.to_json())
+    }
+
+    /// Graphviz DOT of the shown comp (documentation only, not re-importable).
+    pub fn export_dot(&self, project: &Project) -> Option<String> {
+        self.export(project).map(|g| g.to_dot())
+    }
+}
+
+/// Serializable snapshot of a comp's node graph.
+///
+/// Nodes are instances: a source used by two layers appears twice, once per
+/// layer UUID. Order is depth-first from the root, layers top to bottom, so
+/// exports of an unchanged graph are byte-identical.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphExport {
+    /// Comp the graph was exported from
+    pub root: Uuid,
+    pub nodes: Vec<GraphExportNode>,
+    pub edges: Vec<GraphExportEdge>,
+}
+
+/// One node instance in a [`GraphExport`].
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphExportNode {
+    /// Instance UUID (layer UUID; the comp's own UUID for the root)
+    pub uuid: Uuid,
+    /// Source node in the project media pool
+    pub source: Uuid,
+    /// `NodeKind` type ("File", "Comp", "Camera", ...; "Missing" if unresolved)
+    pub kind: String,
+    pub name: String,
+    /// Distance from the root
+    pub depth: usize,
+    /// Source node attrs
+    pub attrs: BTreeMap<String, AttrValue>,
+    /// Per-instance layer attrs in the parent comp (absent for the root)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub layer_attrs: Option<BTreeMap<String, AttrValue>>,
+}
+
+/// Connection from a child instance into a parent comp instance.
+#[derive(Debug, Clone, Serialize)]
+pub struct GraphExportEdge {
+    pub from: Uuid,
+    pub to: Uuid,
+    /// Input pin on `to`: "in", or "A"/"B" on merges
+    pub input: String,
+    /// Layer index in the parent comp (0 = top)
+    pub index: usize,
+}
+
+impl GraphExport {
+    /// Walk the comp DAG under `comp_uuid`.
+    pub fn build(project: &Project, comp_uuid: Uuid) -> Self {
+        let media = project.media.read().expect("media lock");
+        let mut graph = Self {
+            root: comp_uuid,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        };
+        let mut ancestors = Vec::new();
+        graph.collect(comp_uuid, comp_uuid, 0, None, &media, &mut ancestors);
+        graph
+    }
+
+    fn collect(
+        &mut self,
+        instance_uuid: Uuid,
+        source_uuid: Uuid,
+        depth: usize,
+        layer_attrs: Option<&Attrs>,
+        media: &HashMap<Uuid, std::sync::Arc<NodeKind>>,
+        ancestors: &mut Vec<Uuid>,
+    ) {
+        let source = media.get(&source_uuid);
+        self.nodes.push(GraphExportNode {
+            uuid: instance_uuid,
+            source: source_uuid,
+            kind: source.map_or("Missing", |n| n.node_type()).to_string(),
+            name: source.map(|n| n.name().to_string()).unwrap_or_default(),
+            depth,
+            attrs: source.map(|n| sorted_attrs(n.attrs())).unwrap_or_default(),
+            layer_attrs: layer_attrs.map(sorted_attrs),
+        });
+
+        // Cycles: record the instance but don't descend (same rule as the view)
+        if ancestors.contains(&source_uuid) {
+            return;
+        }
+        let Some(comp) = source.and_then(|n| n.as_comp()) else {
+            return;
+        };
+        ancestors.push(source_uuid);
+        for (idx, (child_instance, child_source)) in
+            comp.get_children_sources().into_iter().enumerate()
+        {
+            let input = match (comp.is_merge(), idx) {
+                (true, 0) => "A",
+                (true, 1) => "B",
+                _ => "in",
+            };
+            self.edges.push(GraphExportEdge {
+                from: child_instance,
+                to: instance_uuid,
+                input: input.to_string(),
+                index: idx,
+            });
+            self.collect(
+                child_instance,
+                child_source,
+                depth + 1,
+                comp.layers_attrs_get(&child_instance),
+                media,
+                ancestors,
+            );
+        }
+        ancestors.pop();
+    }
+
+    /// Pretty JSON (see [`NodeEditorState::export_json`]).
+    pub fn to_json(&self) -> Option<String> {
+        serde_json::to_string_pretty(self).ok()
+    }
+
+    /// Graphviz DOT: left-to-right, children feed into their parent comp.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph playa {\n    rankdir=LR;\n    node [shape=box];\n");
+        for node in &self.nodes {
+            out.push_str(&format!(
+                "    \"{}\" [label=\"{}\\n{}\"];\n",
+                node.uuid,
+                dot_escape(&node.name),
+                node.kind
+            ));
+        }
+        for edge in &self.edges {
+            out.push_str(&format!(
+                "    \"{}\" -> \"{}\" [label=\"{}\"];\n",
+                edge.from, edge.to, edge.input
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+fn sorted_attrs(attrs: &Attrs) -> BTreeMap<String, AttrValue> {
+    attrs.iter().map(|(k, v)| (k.clone(), v.clone())).collect()
+}
+
+fn dot_escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
 /// Rebuild the host's subnet from the comp DAG: one `PlayaNode` per comp/layer,
@@ -199,24 +402,36 @@ fn rebuild_subnet(host: &mut PlayaGraphHost, project: &Project, comp_uuid: Uuid)
             info.instance_uuid,
             Pos2::new(default_x, default_y),
         );
+        let node_type = if info.is_merge {
+            MERGE_NODE_TYPE
+        } else {
+            NODE_TYPE
+        };
         if let Ok(id) =
             host.subnet
-                .create_node(NODE_TYPE, [pos.x, pos.y], &host.registry, &host.value_types)
+                .create_node(node_type, [pos.x, pos.y], &host.registry, &host.value_types)
         {
             uuid_to_node.insert(info.instance_uuid, id);
         }
     }
 
     // Wire child output → parent input (single input per node — see module note).
+    // Merge nodes wire layer 0 (top) to `A` and layer 1 (background) to `B`.
     for info in node_info.values() {
         let Some(&parent) = uuid_to_node.get(&info.instance_uuid) else {
             continue;
         };
-        for (child_instance, _) in &info.children {
+        for (idx, (child_instance, _)) in info.children.iter().enumerate() {
+            let pin = match (info.is_merge, idx) {
+                (true, 0) => "A",
+                (true, 1) => "B",
+                (true, _) => continue,
+                (false, _) => "in",
+            };
             if let Some(&child) = uuid_to_node.get(child_instance) {
                 let _ = host
                     .subnet
-                    .connect(child, "out", parent, "in", &host.registry);
+                    .connect(child, "out", parent, pin, &host.registry);
             }
         }
     }
@@ -229,7 +444,7 @@ pub fn render_node_editor(
     state: &mut NodeEditorState,
     project: &Project,
     comp_uuid: Uuid,
-    _dispatch: impl FnMut(BoxedEvent),
+    mut dispatch: impl FnMut(BoxedEvent),
 ) -> bool {
     state.set_comp(comp_uuid);
 
@@ -262,6 +477,30 @@ pub fn render_node_editor(
         ui.weak("A: fit all · F: fit selected · L: layout · MMB/wheel: pan/zoom");
         ui.separator();
         ui.label(format!("{} nodes", state.host.subnet.nodes.len()));
+        ui.separator();
+        ui.menu_button("Merge", |ui| {
+            merge_menu(ui, state, project, comp_uuid, &mut dispatch)
+        })
+        .response
+        .on_hover_text("Composite two project items (A over B) into a new Merge node");
+        ui.menu_button("Export", |ui| {
+            for (label, ext, dot) in [
+                ("Graph as DOT...", "dot", true),
+                ("Graph as JSON...", "json", false),
+            ] {
+                if ui.button(label).clicked() {
+                    ui.close();
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter(ext.to_uppercase(), &[ext])
+                        .set_title("Export Node Graph")
+                        .set_file_name(format!("graph.{}", ext))
+                        .save_file()
+                    {
+                        dispatch(Box::new(ExportNodeGraphEvent { path, dot }));
+                    }
+                }
+            }
+        });
     });
     ui.separator();
 
@@ -276,6 +515,117 @@ pub fn render_node_editor(
     ui.rect_contains_pointer(ui.max_rect())
 }
 
+/// Merge picker: choose A (over) and B (under) from the project's renderable
+/// items plus a blend mode, then create the Merge node. Pre-fills from the
+/// first two selected project items. When the shown comp is itself a merge,
+/// its A/B inputs can be rewired here too.
+fn merge_menu(
+    ui: &mut Ui,
+    state: &mut NodeEditorState,
+    project: &Project,
+    comp_uuid: Uuid,
+    dispatch: &mut impl FnMut(BoxedEvent),
+) {
+    let items: Vec<(Uuid, String)> = project
+        .order()
+        .into_iter()
+        .filter_map(|uuid| {
+            project
+                .with_node(uuid, |n| n.is_renderable().then(|| n.name().to_string()))
+                .flatten()
+                .map(|name| (uuid, name))
+        })
+        .collect();
+    let selection = project.selection();
+    if state.merge_a.is_none() && state.merge_b.is_none() && selection.len() >= 2 {
+        state.merge_a = Some(selection[0]);
+        state.merge_b = Some(selection[1]);
+    }
+    let name_of = |uuid: Option<Uuid>| {
+        uuid.and_then(|u| items.iter().find(|(i, _)| *i == u))
+            .map(|(_, name)| name.clone())
+            .unwrap_or_else(|| "—".to_string())
+    };
+
+    if let Some((a, b)) = project.with_comp(comp_uuid, |c| c.merge_inputs()).flatten() {
+        ui.strong("Inputs");
+        egui::Grid::new("merge_inputs")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (input, (label, current)) in [("A", a), ("B", b)].into_iter().enumerate() {
+                    let mut picked = current;
+                    ui.label(label);
+                    egui::ComboBox::from_id_salt(("merge_input", input))
+                        .selected_text(name_of(Some(current)))
+                        .show_ui(ui, |ui| {
+                            for (uuid, name) in items.iter().filter(|(u, _)| *u != comp_uuid) {
+                                ui.selectable_value(&mut picked, *uuid, name);
+                            }
+                        });
+                    if picked != current {
+                        dispatch(Box::new(SetMergeInputEvent {
+                            merge: comp_uuid,
+                            input,
+                            source: picked,
+                        }));
+                    }
+                    ui.end_row();
+                }
+            });
+        ui.separator();
+        ui.strong("New merge");
+    }
+
+    egui::Grid::new("merge_picker")
+        .num_columns(2)
+        .show(ui, |ui| {
+            for (label, slot) in [
+                ("A (over)", &mut state.merge_a),
+                ("B (under)", &mut state.merge_b),
+            ] {
+                ui.label(label);
+                egui::ComboBox::from_id_salt(label)
+                    .selected_text(name_of(*slot))
+                    .show_ui(ui, |ui| {
+                        for (uuid, name) in &items {
+                            ui.selectable_value(slot, Some(*uuid), name);
+                        }
+                    });
+                ui.end_row();
+            }
+            let blend = state.merge_blend.get_or_insert(BlendMode::Normal);
+            ui.label("Blend");
+            egui::ComboBox::from_id_salt("merge_blend")
+                .selected_text(blend.as_str())
+                .show_ui(ui, |ui| {
+                    for mode in BlendMode::ALL {
+                        ui.selectable_value(blend, mode, mode.as_str());
+                    }
+                });
+            ui.end_row();
+        });
+
+    let ready = matches!((state.merge_a, state.merge_b), (Some(a), Some(b)) if a != b);
+    if ui
+        .add_enabled(ready, egui::Button::new("Create Merge"))
+        .clicked()
+        && let (Some(a), Some(b)) = (state.merge_a, state.merge_b)
+    {
+        dispatch(Box::new(CreateMergeNodeEvent {
+            a,
+            b,
+            blend_mode: state
+                .merge_blend
+                .unwrap_or(BlendMode::Normal)
+                .as_str()
+                .to_string(),
+        }));
+        state.merge_a = None;
+        state.merge_b = None;
+        ui.close();
+    }
+}
+
 // =============================================================================
 // Comp-DAG traversal helpers (independent of the rendering backend)
 // =============================================================================
@@ -284,6 +634,8 @@ pub fn render_node_editor(
 struct NodeInfo {
     depth: usize,
     instance_uuid: Uuid,
+    /// Source is a Merge comp (A/B pins instead of a single `in`)
+    is_merge: bool,
     #[allow(dead_code)]
     source_uuid: Uuid,
     children: Vec<(Uuid, Uuid)>, // (instance_uuid, source_uuid)
@@ -326,6 +678,7 @@ fn collect_tree_recursive(
             NodeInfo {
                 depth,
                 instance_uuid,
+                is_merge: false,
                 source_uuid,
                 children: vec![],
             },
@@ -341,6 +694,7 @@ fn collect_tree_recursive(
             NodeInfo {
                 depth,
                 instance_uuid,
+                is_merge: false,
                 source_uuid,
                 children: vec![],
             },
@@ -355,6 +709,7 @@ fn collect_tree_recursive(
         NodeInfo {
             depth,
             instance_uuid,
+            is_merge: comp.is_merge(),
             source_uuid,
             children: children.clone(),
         },
@@ -373,3 +728,48 @@ fn collect_tree_recursive(
     }
     ancestors.pop();
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use playa_engine::core::CacheManager;
+    use playa_engine::entities::FileNode;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_graph_export_of_merge() {
+        let mut project = Project::new(Arc::new(CacheManager::new(0.75, 2.0)));
+        let fg = FileNode::new("/shots/fg.####.exr".to_string(), 1, 10, 24.0);
+        let bg = FileNode::new("/shots/bg.####.exr".to_string(), 1, 10, 24.0);
+        let (fg_uuid, bg_uuid) = (fg.uuid(), bg.uuid());
+        project.add_node(NodeKind::File(fg));
+        project.add_node(NodeKind::File(bg));
+        let merge = project
+            .create_merge(fg_uuid, bg_uuid, BlendMode::Screen)
+            .unwrap();
+
+        let graph = GraphExport::build(&project, merge);
+        let sources: Vec<Uuid> = graph.nodes.iter().map(|n| n.source).collect();
+        assert_eq!(sources, [merge, fg_uuid, bg_uuid]);
+        let inputs: Vec<&str> = graph.edges.iter().map(|e| e.input.as_str()).collect();
+        assert_eq!(inputs, ["A", "B"]);
+        assert!(graph.edges.iter().all(|e| e.to == merge));
+
+        let json: serde_json::Value = serde_json::from_str(&graph.to_json().unwrap()).unwrap();
+        assert_eq!(json["root"], merge.to_string());
+        assert_eq!(json["nodes"][1]["kind"], "File");
+        assert_eq!(
+            json["nodes"][1]["layer_attrs"]["blend_mode"]["Str"],
+            "screen"
+        );
+        assert!(json["nodes"][0].get("layer_attrs").is_none());
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph playa {"));
+        let b_edge = format!(
+            "\"{}\" -> \"{}\" [label=\"B\"];",
+            graph.edges[1].from, merge
+        );
+        assert!(dot.contains(&b_edge));
+    }
+}
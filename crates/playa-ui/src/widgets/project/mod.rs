@@ -5,7 +5,8 @@
 mod project;
 pub mod project_events;
 pub mod project_ui;
+pub(crate) mod thumbnails;
 
 pub use project::ProjectActions;
 pub use project_events::*;
-pub use project_ui::render;
+pub use project_ui::{render, render_recent_items};
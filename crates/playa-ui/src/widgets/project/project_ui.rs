@@ -14,6 +14,8 @@
 //!   default, clear-all — none expressible through the generic widget).
 //! - Translating each [`AssetAction`] back into the existing playa events.
 //! - The `Uuid <-> u64` id bridge (the widget is `Uuid`-free).
+//! - The thumbnail list that replaces the widget's rows when a thumbnail size
+//!   is set (see [`super::thumbnails`]).
 
 use eframe::egui;
 use std::collections::HashMap;
@@ -24,10 +26,12 @@ use egui_asset_browser::{
     show as asset_browser_show,
 };
 
+use crate::dialogs::prefs::{RecentItem, RecentKind, prune_recent};
 use crate::widgets::dnd::{GlobalDragState, global_drag_state_id};
 use crate::widgets::file_dialogs::create_media_dialog;
 use crate::widgets::project::project::ProjectActions;
 use crate::widgets::project::project_events::*;
+use crate::widgets::project::thumbnails::{RowItem, render_rows};
 use playa_engine::core::player::Player;
 use playa_engine::entities::Project;
 use playa_engine::entities::node::Node;
@@ -54,45 +58,88 @@ fn uuid_to_u64(uuid: &Uuid) -> u64 {
     u64::from_le_bytes(head)
 }
 
+/// Entries of the right-click "Create" menu.
+pub(super) const CREATE_KINDS: [&str; 5] = ["Comp", "Camera", "Text", "Solid", "Matte"];
+
+/// Per-kind icon tag + colour, mirroring the old hard-coded styling.
+const KIND_STYLES: [(&str, &str, egui::Color32); 7] = [
+    ("Clip", "[F]", egui::Color32::from_rgb(100, 180, 100)),
+    ("Comp", "[C]", egui::Color32::from_rgb(100, 150, 255)),
+    ("Camera", "[K]", egui::Color32::from_rgb(255, 200, 100)),
+    ("Text", "[T]", egui::Color32::from_rgb(200, 150, 255)),
+    ("Solid", "[S]", egui::Color32::from_rgb(150, 200, 200)),
+    ("AI", "[AI]", egui::Color32::from_rgb(255, 150, 150)),
+    ("Ref", "[R]", egui::Color32::from_rgb(180, 180, 180)),
+];
+
+/// Icon tag + colour of a kind (grey fallback for unknown kinds).
+pub(super) fn kind_style(kind: &str) -> (&'static str, egui::Color32) {
+    KIND_STYLES
+        .iter()
+        .find(|(k, _, _)| *k == kind)
+        .map(|(_, tag, color)| (*tag, *color))
+        .unwrap_or(("[?]", egui::Color32::GRAY))
+}
+
 /// Build the asset-browser config: create-menu entries + the per-kind
-/// icon/colour table mirroring the old hard-coded styling. Grouping is off to
-/// preserve playa's flat, user-ordered media list; rename is off because there
-/// is no project rename event.
+/// icon/colour table. Grouping is off to preserve playa's flat, user-ordered
+/// media list; rename is off because there is no project rename event.
 fn build_config() -> AssetBrowserConfig {
-    AssetBrowserConfig::default()
-        .with_create_kinds(["Comp", "Camera", "Text"])
-        .with_add_media(true)
-        .with_rename(false)
-        .with_grouping(false)
-        .with_kind_style(
-            "Clip",
-            KindStyle::new("[F]", egui::Color32::from_rgb(100, 180, 100)),
-        )
-        .with_kind_style(
-            "Comp",
-            KindStyle::new("[C]", egui::Color32::from_rgb(100, 150, 255)),
-        )
-        .with_kind_style(
-            "Camera",
-            KindStyle::new("[K]", egui::Color32::from_rgb(255, 200, 100)),
-        )
-        .with_kind_style(
-            "Text",
-            KindStyle::new("[T]", egui::Color32::from_rgb(200, 150, 255)),
-        )
-        .with_kind_style(
-            "AI",
-            KindStyle::new("[AI]", egui::Color32::from_rgb(255, 150, 150)),
-        )
-        .with_kind_style(
-            "Ref",
-            KindStyle::new("[R]", egui::Color32::from_rgb(180, 180, 180)),
-        )
+    KIND_STYLES.iter().fold(
+        AssetBrowserConfig::default()
+            .with_create_kinds(CREATE_KINDS)
+            .with_add_media(true)
+            .with_rename(false)
+            .with_grouping(false),
+        |config, (kind, tag, color)| config.with_kind_style(*kind, KindStyle::new(*tag, *color)),
+    )
+}
+
+/// File dialog for review playlists (text `.m3u`-style or JSON)
+fn playlist_dialog(title: &str) -> rfd::FileDialog {
+    rfd::FileDialog::new()
+        .add_filter("Playlist", &["m3u", "m3u8", "playlist", "json"])
+        .set_title(title)
+}
+
+/// One button per recent item (most recent first); clicking reopens it via
+/// the same events as the Load button / media dialog. Returns true on click.
+pub fn render_recent_items(
+    ui: &mut egui::Ui,
+    items: &[RecentItem],
+    actions: &mut ProjectActions,
+) -> bool {
+    let mut clicked = false;
+    for item in items {
+        let prefix = match item.kind {
+            RecentKind::Media => "[F]",
+            RecentKind::Project => "[P]",
+        };
+        if ui
+            .button(format!("{} {}", prefix, item.label()))
+            .on_hover_text(item.path.display().to_string())
+            .clicked()
+        {
+            match item.kind {
+                RecentKind::Media => actions.send(AddClipEvent(item.path.clone())),
+                RecentKind::Project => actions.send(LoadProjectEvent(item.path.clone())),
+            }
+            clicked = true;
+        }
+    }
+    clicked
 }
 
 /// Render project window (dock tab): unified list of Clips & Compositions,
-/// driven by the `egui-asset-browser` widget.
-pub fn render(ui: &mut egui::Ui, _player: &mut Player, project: &Project) -> ProjectActions {
+/// driven by the `egui-asset-browser` widget, or drawn as rows with
+/// `thumbnail_size` px thumbnails as icons (0 uses the plain list).
+pub fn render(
+    ui: &mut egui::Ui,
+    _player: &mut Player,
+    project: &Project,
+    recent: &mut Vec<RecentItem>,
+    thumbnail_size: u32,
+) -> ProjectActions {
     let mut actions = ProjectActions::new();
 
     // Capture the full panel rect up-front for hover detection (input routing).
@@ -117,6 +164,47 @@ pub fn render(ui: &mut egui::Ui, _player: &mut Player, project: &Project) -> Pro
         {
             actions.send(LoadProjectEvent(path));
         }
+        ui.menu_button("Recent", |ui| {
+            // Files may have moved or been deleted since they were recorded
+            prune_recent(recent);
+            if recent.is_empty() {
+                ui.weak("No recent items");
+                return;
+            }
+            if render_recent_items(ui, recent.as_slice(), &mut actions) {
+                ui.close();
+            }
+            ui.separator();
+            if ui.button("Clear Recent").clicked() {
+                recent.clear();
+                ui.close();
+            }
+        });
+        ui.menu_button("Playlist", |ui| {
+            if ui
+                .button("Load...")
+                .on_hover_text("Append a playlist's items as clips, in order")
+                .clicked()
+            {
+                ui.close();
+                if let Some(path) = playlist_dialog("Load Playlist").pick_file() {
+                    actions.send(LoadPlaylistEvent(path));
+                }
+            }
+            if ui
+                .button("Save...")
+                .on_hover_text("Write the clips (with trimmed work areas) as a playlist")
+                .clicked()
+            {
+                ui.close();
+                if let Some(path) = playlist_dialog("Save Playlist")
+                    .set_file_name("review.m3u")
+                    .save_file()
+                {
+                    actions.send(SavePlaylistEvent(path));
+                }
+            }
+        });
         ui.separator();
         if ui.button("+Folder").clicked()
             && let Some(folder) = rfd::FileDialog::new()
@@ -150,6 +238,7 @@ pub fn render(ui: &mut egui::Ui, _player: &mut Player, project: &Project) -> Pro
     let config = build_config();
     let mut model = AssetBrowserModel::new();
     let mut id_map: HashMap<u64, ItemMeta> = HashMap::new();
+    let mut rows: Vec<RowItem> = Vec::new();
 
     let order = project.order();
     let active = project.active();
@@ -185,6 +274,8 @@ pub fn render(ui: &mut egui::Ui, _player: &mut Player, project: &Project) -> Pro
                 ("Camera", node.name().to_string())
             } else if node.is_text() {
                 ("Text", node.name().to_string())
+            } else if node.is_solid() {
+                ("Solid", node.name().to_string())
             } else if node.is_ai() {
                 ("AI", node.name().to_string())
             } else if node.is_ref() {
@@ -203,9 +294,20 @@ pub fn render(ui: &mut egui::Ui, _player: &mut Player, project: &Project) -> Pro
                 name
             };
 
-            model.items.push(
-                AssetItem::new(id, name, kind).with_subtitle(format!("{frame_count}f  {fps}fps")),
-            );
+            let subtitle = format!("{frame_count}f  {fps}fps");
+            if thumbnail_size > 0 {
+                rows.push(RowItem {
+                    id,
+                    uuid: *uuid,
+                    kind,
+                    name,
+                    subtitle,
+                });
+            } else {
+                model
+                    .items
+                    .push(AssetItem::new(id, name, kind).with_subtitle(subtitle));
+            }
             id_map.insert(
                 id,
                 ItemMeta {
@@ -220,7 +322,11 @@ pub fn render(ui: &mut egui::Ui, _player: &mut Player, project: &Project) -> Pro
     model.selection = project.selection().iter().map(uuid_to_u64).collect();
 
     // --- Render the widget and translate its actions back to playa events ----
-    let raw_actions = asset_browser_show(ui, &model, &config);
+    let raw_actions = if thumbnail_size > 0 {
+        render_rows(ui, project, &rows, &project.selection(), thumbnail_size)
+    } else {
+        asset_browser_show(ui, &model, &config)
+    };
     let ctx = ui.ctx().clone();
     for action in raw_actions {
         translate_action(action, project, &order, &id_map, &ctx, &mut actions);
@@ -294,6 +400,14 @@ fn translate_action(
                 name: "New Text".to_string(),
                 text: "Hello World".to_string(),
             }),
+            "Solid" => actions.send(AddSolidEvent {
+                name: "Solid".to_string(),
+                matte: false,
+            }),
+            "Matte" => actions.send(AddSolidEvent {
+                name: "Matte".to_string(),
+                matte: true,
+            }),
             other => log::warn!("project panel: unhandled create kind '{other}'"),
         },
         AssetAction::Delete { id } => {
@@ -0,0 +1,249 @@
+//! Thumbnail list view for the Project panel.
+//!
+//! Shown instead of the plain asset-browser list when a thumbnail size is set:
+//! one row per item with its thumbnail as the icon (kind tag for items without
+//! pixels), the name and the frame count / fps line. Interaction mirrors the
+//! list: click selects (ctrl/shift as usual), double click activates, drag
+//! starts a drop, the context menu deletes / creates. Everything is reported as
+//! [`AssetAction`]s so it goes through the same translation as the widget.
+//!
+//! Pixels come from the engine's [`ThumbnailCache`] (rendered lazily on a
+//! background thread and keyed by node content). This module only uploads them
+//! as egui textures, cached by node uuid + thumbnail key, so repaints never
+//! re-upload.
+
+use eframe::egui;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+use egui_asset_browser::AssetAction;
+use playa_engine::entities::frame::PixelBuffer;
+use playa_engine::entities::{Project, Thumbnail, ThumbnailCache};
+
+use super::project_ui::{CREATE_KINDS, kind_style};
+
+/// One item of the list.
+pub(super) struct RowItem {
+    /// Widget-space id (see `uuid_to_u64`), used for the emitted actions
+    pub id: u64,
+    pub uuid: Uuid,
+    /// Kind label ("Clip", "Comp", ...), see [`kind_style`]
+    pub kind: &'static str,
+    pub name: String,
+    pub subtitle: String,
+}
+
+impl RowItem {
+    /// Only clips and comps have pixels worth a thumbnail
+    fn has_thumbnail(&self) -> bool {
+        matches!(self.kind, "Clip" | "Comp")
+    }
+}
+
+/// Uploaded textures by node, with the thumbnail key each was built from.
+#[derive(Clone, Default)]
+struct ThumbnailTextures(HashMap<Uuid, (u64, egui::TextureHandle)>);
+
+fn textures_id() -> egui::Id {
+    egui::Id::new("project_thumbnail_textures")
+}
+
+/// Upload an engine thumbnail as an egui texture (also used by the timeline
+/// ruler's scrub preview).
+pub(crate) fn upload(
+    ctx: &egui::Context,
+    name: String,
+    thumb: &Thumbnail,
+) -> Option<egui::TextureHandle> {
+    let (w, h) = thumb.frame.resolution();
+    let PixelBuffer::U8(rgba) = thumb.frame.buffer().as_ref().clone() else {
+        return None;
+    };
+    let image = egui::ColorImage::from_rgba_unmultiplied([w, h], &rgba);
+    Some(ctx.load_texture(name, image, egui::TextureOptions::LINEAR))
+}
+
+/// Texture for `uuid`'s thumbnail; requests a (re)render when missing or stale.
+fn texture(
+    ctx: &egui::Context,
+    project: &Project,
+    uuid: Uuid,
+    size: u32,
+) -> Option<egui::TextureHandle> {
+    let repaint = ctx.clone();
+    let thumb = ThumbnailCache::global()
+        .get_or_request(project, uuid, size, move || repaint.request_repaint())?;
+
+    let cached = ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<ThumbnailTextures>(textures_id())
+            .0
+            .get(&uuid)
+            .cloned()
+    });
+    if let Some((key, tex)) = cached
+        && key == thumb.key
+    {
+        return Some(tex);
+    }
+
+    let tex = upload(ctx, format!("project_thumb_{uuid}"), &thumb)?;
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<ThumbnailTextures>(textures_id())
+            .0
+            .insert(uuid, (thumb.key, tex.clone()));
+    });
+    Some(tex)
+}
+
+/// Right-click menu entries shared by rows and the empty area below them.
+fn create_menu(ui: &mut egui::Ui, out: &mut Vec<AssetAction>) {
+    if ui.button("Add media...").clicked() {
+        out.push(AssetAction::AddMedia);
+        ui.close();
+    }
+    ui.separator();
+    for kind in CREATE_KINDS {
+        if ui.button(format!("New {kind}")).clicked() {
+            out.push(AssetAction::Create {
+                kind: kind.to_string(),
+            });
+            ui.close();
+        }
+    }
+}
+
+/// Draw the list with `size`-px thumbnails as row icons.
+pub(super) fn render_rows(
+    ui: &mut egui::Ui,
+    project: &Project,
+    rows: &[RowItem],
+    selection: &[Uuid],
+    size: u32,
+) -> Vec<AssetAction> {
+    // Forget nodes that left the project (textures + engine-side pixels)
+    let listed = |uuid: &Uuid| rows.iter().any(|r| r.uuid == *uuid && r.has_thumbnail());
+    ui.ctx().data_mut(|d| {
+        d.get_temp_mut_or_default::<ThumbnailTextures>(textures_id())
+            .0
+            .retain(|uuid, _| listed(uuid));
+    });
+    ThumbnailCache::global().retain(listed);
+
+    let mut out = Vec::new();
+    let edge = size as f32;
+    egui::ScrollArea::vertical()
+        .auto_shrink([false, false])
+        .show(ui, |ui| {
+            for row in rows {
+                let (rect, response) = ui.allocate_exact_size(
+                    egui::vec2(ui.available_width(), edge + 4.0),
+                    egui::Sense::click_and_drag(),
+                );
+                // Pointer test rather than `hovered()`, which the delete button
+                // on top would steal
+                let hovered = ui.rect_contains_pointer(rect);
+                let selected = selection.contains(&row.uuid);
+                let painter = ui.painter_at(rect);
+                if selected {
+                    painter.rect_filled(rect, 2.0, ui.visuals().selection.bg_fill);
+                } else if hovered {
+                    painter.rect_filled(rect, 2.0, ui.visuals().widgets.hovered.weak_bg_fill);
+                }
+
+                let (tag, color) = kind_style(row.kind);
+                let icon = egui::Rect::from_min_size(
+                    rect.min + egui::vec2(2.0, 2.0),
+                    egui::vec2(edge, edge),
+                );
+                painter.rect_filled(icon, 2.0, ui.visuals().extreme_bg_color);
+                let tex = row
+                    .has_thumbnail()
+                    .then(|| texture(ui.ctx(), project, row.uuid, size))
+                    .flatten();
+                if let Some(tex) = tex {
+                    painter.image(
+                        tex.id(),
+                        egui::Rect::from_center_size(icon.center(), tex.size_vec2()),
+                        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                        egui::Color32::WHITE,
+                    );
+                } else {
+                    painter.text(
+                        icon.center(),
+                        egui::Align2::CENTER_CENTER,
+                        if row.has_thumbnail() { "…" } else { tag },
+                        egui::FontId::proportional(12.0),
+                        color,
+                    );
+                }
+
+                let text_x = icon.right() + 6.0;
+                let text_color = if selected {
+                    ui.visuals().selection.stroke.color
+                } else {
+                    ui.visuals().text_color()
+                };
+                painter.text(
+                    egui::pos2(text_x, rect.center().y - 1.0),
+                    egui::Align2::LEFT_BOTTOM,
+                    &row.name,
+                    egui::FontId::proportional(13.0),
+                    text_color,
+                );
+                painter.text(
+                    egui::pos2(text_x, rect.center().y + 1.0),
+                    egui::Align2::LEFT_TOP,
+                    format!("{tag}  {}", row.subtitle),
+                    egui::FontId::proportional(11.0),
+                    color,
+                );
+
+                if hovered {
+                    let del_rect = egui::Rect::from_center_size(
+                        egui::pos2(rect.right() - 12.0, rect.center().y),
+                        egui::vec2(18.0, 18.0),
+                    );
+                    if ui
+                        .put(del_rect, egui::Button::new("✕").small())
+                        .on_hover_text("Remove from project")
+                        .clicked()
+                    {
+                        out.push(AssetAction::Delete { id: row.id });
+                    }
+                }
+
+                if response.drag_started() {
+                    out.push(AssetAction::BeginDrag { id: row.id });
+                }
+                if response.double_clicked() {
+                    out.push(AssetAction::Open { id: row.id });
+                } else if response.clicked() {
+                    let modifiers = ui.input(|i| i.modifiers);
+                    out.push(AssetAction::Select {
+                        id: row.id,
+                        additive: modifiers.command,
+                        range: modifiers.shift,
+                    });
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Delete").clicked() {
+                        out.push(AssetAction::Delete { id: row.id });
+                        ui.close();
+                    }
+                    ui.separator();
+                    create_menu(ui, &mut out);
+                });
+            }
+
+            // Empty space below the rows: click clears, right-click creates
+            let (_, empty) = ui.allocate_exact_size(
+                ui.available_size().max(egui::vec2(0.0, edge)),
+                egui::Sense::click(),
+            );
+            if empty.clicked() {
+                out.push(AssetAction::ClearSelection);
+            }
+            empty.context_menu(|ui| create_menu(ui, &mut out));
+        });
+    out
+}
@@ -4,10 +4,29 @@ use egui_statusbar::{Section, StatusBar as Bar, StatusBarLayout};
 use playa_engine::core::cache_man::CacheManager;
 use playa_engine::core::event_bus::BoxedEvent;
 use playa_engine::core::player::Player;
+use playa_engine::core::workers::Workers;
 use playa_engine::entities::Project;
 use playa_engine::entities::frame::{Frame, PixelFormat};
 use playa_engine::entities::node::Node;
+use playa_engine::utils::timecode::{TimeDisplay, display_frame};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How often the cached % / decode rate readout is re-sampled
+const LOAD_SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Work-area cache readout for the active comp (sampled a few times a second)
+#[derive(Debug, Default)]
+struct LoadSample {
+    last_sample: Option<Instant>,
+    /// `Workers::jobs_finished` at the last sample
+    finished: u64,
+    /// Smoothed frames/sec loaded by the workers
+    rate: f32,
+    /// Loaded share of the work area, 0..=100 (`None` = no active comp)
+    percent: Option<u32>,
+    busy: bool,
+}
 
 /// Bottom status bar built on the reusable `egui-statusbar` widget: fixed,
 /// drag-resizable sections (double-click a splitter to reset) with a flexing
@@ -16,6 +35,7 @@ use std::sync::Arc;
 pub struct StatusBar {
     pub current_message: String,
     layout: StatusBarLayout,
+    load: LoadSample,
 }
 
 impl StatusBar {
@@ -25,6 +45,34 @@ impl StatusBar {
 
     pub fn update(&mut self, _ctx: &egui::Context) {}
 
+    /// Re-read the work-area cached % and decode rate if
+    /// [`LOAD_SAMPLE_INTERVAL`] elapsed since the last sample
+    fn sample_load(&mut self, player: &Player, project: &Project, workers: &Workers) {
+        let now = Instant::now();
+        let elapsed = match self.load.last_sample {
+            Some(t) if now - t < LOAD_SAMPLE_INTERVAL => return,
+            Some(t) => (now - t).as_secs_f32(),
+            None => 0.0,
+        };
+
+        let finished = workers.jobs_finished();
+        if elapsed > 0.0 {
+            let rate = finished.saturating_sub(self.load.finished) as f32 / elapsed;
+            // Rolling average so one burst doesn't make the readout flicker
+            self.load.rate = self.load.rate * 0.5 + rate * 0.5;
+        }
+        self.load.finished = finished;
+        self.load.busy = workers.is_busy();
+        self.load.percent = player.active_comp().and_then(|comp_uuid| {
+            let cache = project.global_cache.as_ref()?;
+            let (start, end) = project.with_node(comp_uuid, |comp| comp.play_range(true))?;
+            let total = (end - start + 1).max(1) as usize;
+            let loaded = cache.loaded_in_range(comp_uuid, start, end);
+            Some((loaded * 100 / total) as u32)
+        });
+        self.load.last_sample = Some(now);
+    }
+
     /// Render the status bar at the bottom of `ui`. Section content is computed
     /// up front into owned strings so the per-section draw closures stay free of
     /// engine borrows; `egui_statusbar` lays them out with resizable splitters.
@@ -38,6 +86,8 @@ impl StatusBar {
         viewport_state: &ViewportState,
         render_time_ms: f32,
         cache_manager: Option<&Arc<CacheManager>>,
+        workers: &Workers,
+        time_display: TimeDisplay,
         mut dispatch: impl FnMut(BoxedEvent),
     ) {
         // Precompute display strings (decouples the section closures from the
@@ -47,10 +97,30 @@ impl StatusBar {
             .and_then(|p| p.file_name().and_then(|n| n.to_str()).map(str::to_string))
             .unwrap_or_else(|| "No file".to_string());
 
-        let res_text = match frame {
+        let mut res_text = match frame {
+            Some(img) if img.proxy_factor() > 1 => format!(
+                "{:>4}x{:<4} 1/{}",
+                img.width(),
+                img.height(),
+                img.proxy_factor()
+            ),
             Some(img) => format!("{:>4}x{:<4}", img.width(), img.height()),
             None => "   0x0   ".to_string(),
         };
+        // Anamorphic: append PAR and the effective (unsqueezed) display resolution
+        if let Some(img) = frame {
+            let par = img.pixel_aspect();
+            if (par - 1.0).abs() > 1e-4 {
+                let full_w = img.width() * img.proxy_factor();
+                let full_h = img.height() * img.proxy_factor();
+                res_text.push_str(&format!(
+                    " PAR {:.2} -> {}x{}",
+                    par,
+                    (full_w as f32 * par).round() as u32,
+                    full_h
+                ));
+            }
+        }
 
         let fmt_text = match frame {
             Some(img) => Self::format_pixel_format(img.pixel_format()),
@@ -58,6 +128,8 @@ impl StatusBar {
         };
 
         let zoom_text = format!("{:>6.1}%", viewport_state.zoom * 100.0);
+        // Non-default display orientation (rotate / flip / flop)
+        let orient_text = viewport_state.orientation.label();
         let time_text = format!("{:.1}ms", render_time_ms);
 
         let mem_text = cache_manager.map(|manager| {
@@ -71,25 +143,94 @@ impl StatusBar {
             };
             format!("Mem: {}/{}MB ({}%)", usage_mb, limit_mb, percent)
         });
+        let workers_text = format!("{}T", workers.num_threads());
+
+        // Work-area cached % and decode rate; keep sampling while preloading
+        self.sample_load(player, project, workers);
+        if self.load.busy {
+            ui.ctx().request_repaint_after(LOAD_SAMPLE_INTERVAL);
+        }
+        let load_text = self.load.percent.map(|p| format!("Cached {:>3}%", p));
+        let load_complete = self.load.percent == Some(100);
+        let rate_text = if self.load.busy {
+            format!("Loading {:.0} f/s", self.load.rate)
+        } else {
+            "Idle".to_string()
+        };
 
         let mut loop_enabled = player.loop_enabled();
-        let fps_text = format!("{:.0}/{:.0} fps", player.fps_base(), player.fps_play());
+        // Active rate follows the comp; keep fractional rates like 23.976 readable
+        let fps = |f: f32| (f * 1000.0).round() / 1000.0;
+        let fps_text = format!("{}/{} fps", fps(player.fps_base()), fps(player.fps_play()));
+        // Measured display rate while playing; flagged when it falls behind
+        let actual_fps = player.actual_fps().map(|actual| {
+            let behind = actual < player.fps_play() * 0.95;
+            (format!("{:.1} fps actual", actual), behind)
+        });
 
-        // Comp/clip range: <start | play_start <current> play_end | end>
+        // Comp/clip range: <start | play_start <current> play_end | end>,
+        // in frames or timecode per the timeline display mode
         let range_text = player.active_comp().and_then(|comp_uuid| {
             let media = project.media.read().unwrap_or_else(|e| e.into_inner());
             media.get(&comp_uuid).map(|comp| {
                 let (play_start, play_end) = comp.play_range(true);
+                let t = |frame: i32| display_frame(frame, comp.fps(), time_display);
                 format!(
                     "<{} | {} <{}> {} | {}>",
-                    comp._in(),
-                    play_start,
-                    comp.frame(),
-                    play_end,
-                    comp._out()
+                    t(comp._in()),
+                    t(play_start),
+                    t(comp.frame()),
+                    t(play_end),
+                    t(comp._out())
                 )
             })
         });
+        // Marked in/out (I/O): shown only while narrower than the full comp
+        let marked_text = player.active_comp().and_then(|comp_uuid| {
+            let media = project.media.read().unwrap_or_else(|e| e.into_inner());
+            let comp = media.get(&comp_uuid)?;
+            let (play_start, play_end) = comp.play_range(true);
+            if (play_start, play_end) == (comp._in(), comp._out()) {
+                return None;
+            }
+            let t = |frame: i32| display_frame(frame, comp.fps(), time_display);
+            Some(format!(
+                "In/Out {}-{} ({}f)",
+                t(play_start),
+                t(play_end),
+                (play_end - play_start + 1).max(0)
+            ))
+        });
+
+        // Frame parked by the hold toggle (Shift+H)
+        let held_text = player.held_frame().map(|frame| {
+            let fps = player
+                .active_comp()
+                .and_then(|uuid| project.with_node(uuid, |comp| comp.fps()))
+                .unwrap_or_else(|| player.fps_base());
+            format!("HOLD {}", display_frame(frame, fps, time_display))
+        });
+
+        // Region of interest bbox in image coordinates (ROI tool)
+        let roi_text = player.active_comp().and_then(|comp_uuid| {
+            let media = project.media.read().unwrap_or_else(|e| e.into_inner());
+            let roi = media.get(&comp_uuid)?.as_comp()?.roi()?;
+            Some(format!(
+                "ROI {},{} - {},{} ({}x{})",
+                roi.x,
+                roi.y,
+                roi.right(),
+                roi.bottom(),
+                roi.width,
+                roi.height
+            ))
+        });
+
+        // Clipped-pixel shares while zebra warnings are on
+        let zebra_text = viewport_state
+            .zebra
+            .clipped
+            .map(|stats| stats.status_text());
 
         let msg = self.current_message.clone();
 
@@ -110,10 +251,25 @@ impl StatusBar {
                 Section::new(70.0, |ui| {
                     ui.monospace(&time_text);
                 }),
-                Section::new(150.0, |ui| {
+                Section::new(190.0, |ui| {
                     if let Some(t) = &mem_text {
                         ui.monospace(t);
                     }
+                    ui.monospace(&workers_text)
+                        .on_hover_text("Active worker threads");
+                }),
+                Section::new(190.0, |ui| {
+                    if let Some(t) = &load_text {
+                        let color = if load_complete {
+                            egui::Color32::from_rgb(110, 200, 110)
+                        } else {
+                            ui.visuals().text_color()
+                        };
+                        ui.label(egui::RichText::new(t).monospace().color(color))
+                            .on_hover_text("Work-area frames loaded in the cache");
+                    }
+                    ui.monospace(&rate_text)
+                        .on_hover_text("Background preloading: frames decoded per second");
                 }),
                 // Flexing tail: loop toggle + fps + range + status message.
                 Section::new(0.0, |ui| {
@@ -124,10 +280,54 @@ impl StatusBar {
                     }
                     ui.separator();
                     ui.monospace(&fps_text);
+                    if let Some((a, behind)) = &actual_fps {
+                        let color = if *behind {
+                            egui::Color32::from_rgb(255, 160, 60)
+                        } else {
+                            ui.visuals().text_color()
+                        };
+                        ui.label(egui::RichText::new(a).monospace().color(color))
+                            .on_hover_text("Frames displayed per second vs the target play fps");
+                    }
                     if let Some(r) = &range_text {
                         ui.separator();
                         ui.monospace(r);
                     }
+                    if let Some(h) = &held_text {
+                        ui.separator();
+                        ui.label(
+                            egui::RichText::new(h)
+                                .monospace()
+                                .strong()
+                                .color(egui::Color32::from_rgb(255, 110, 90)),
+                        )
+                        .on_hover_text("Playback and scrubbing paused; Shift+H releases");
+                    }
+                    if let Some(m) = &marked_text {
+                        ui.separator();
+                        ui.label(
+                            egui::RichText::new(m)
+                                .monospace()
+                                .color(egui::Color32::from_rgb(255, 200, 90)),
+                        );
+                    }
+                    if let Some(r) = &roi_text {
+                        ui.separator();
+                        ui.label(
+                            egui::RichText::new(r)
+                                .monospace()
+                                .color(egui::Color32::from_rgb(90, 200, 255)),
+                        );
+                    }
+                    if let Some(z) = &zebra_text {
+                        ui.separator();
+                        ui.monospace(z)
+                            .on_hover_text("Pixels under zebra stripes: ▲ highlights, ▼ shadows");
+                    }
+                    if let Some(o) = &orient_text {
+                        ui.separator();
+                        ui.monospace(o);
+                    }
                     if !msg.is_empty() {
                         ui.separator();
                         ui.monospace(&msg);
@@ -14,6 +14,7 @@ mod timeline_ui;
 
 pub use crate::widgets::dnd::GlobalDragState;
 pub use timeline::{
-    ClipboardLayer, TimelineActions, TimelineConfig, TimelineState, TimelineViewMode,
+    ClipboardLayer, TIME_DISPLAY_MODES, TimelineActions, TimelineConfig, TimelineState,
+    TimelineViewMode,
 };
 pub use timeline_ui::{render_canvas, render_outline, render_toolbar};
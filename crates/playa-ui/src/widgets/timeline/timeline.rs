@@ -7,6 +7,7 @@
 use crate::widgets::dnd::GlobalDragState;
 use eframe::egui;
 use playa_engine::entities::Attrs;
+use playa_engine::utils::timecode::TimeDisplay;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -20,6 +21,14 @@ pub struct ClipboardLayer {
     pub original_start: i32,
 }
 
+/// Time display modes offered in the toolbar and preferences
+pub const TIME_DISPLAY_MODES: &[(&str, TimeDisplay)] = &[
+    ("Frames", TimeDisplay::Frames),
+    ("Timecode", TimeDisplay::Timecode { drop_frame: false }),
+    ("Timecode DF", TimeDisplay::Timecode { drop_frame: true }),
+    ("Seconds", TimeDisplay::Seconds),
+];
+
 /// Timeline actions result - returned from render functions
 #[derive(Default)]
 pub struct TimelineActions {
@@ -71,6 +80,12 @@ pub struct TimelineState {
     pub drag_state: Option<GlobalDragState>, // Active drag operation (centralized for all drag types)
     pub snap_enabled: bool,
     pub lock_work_area: bool,
+    /// Frame numbers vs timecode (synced with `TimelineSettings`)
+    #[serde(default)]
+    pub time_display: TimeDisplay,
+    /// Frame-entry field buffer while it has focus
+    #[serde(skip)]
+    pub frame_entry: Option<String>,
     pub last_comp_uuid: Option<Uuid>, // Track last active comp to recenter on change
     pub view_mode: TimelineViewMode,
     #[serde(skip)]
@@ -92,6 +107,15 @@ pub struct TimelineState {
     /// The original layout name before rename (needed for LayoutRenamedEvent).
     #[serde(skip)]
     pub rename_dialog_old_name: String,
+
+    /// Marker being edited in the ruler context menu: (frame, label buffer).
+    #[serde(skip)]
+    pub marker_edit: Option<(i32, String)>,
+
+    /// Frame the last layer drag/trim snapped to (draws the snap guide line);
+    /// cleared when the pointer is released.
+    #[serde(skip)]
+    pub snap_guide: Option<i32>,
 }
 
 impl std::fmt::Debug for TimelineState {
@@ -102,6 +126,7 @@ impl std::fmt::Debug for TimelineState {
             .field("drag_state", &self.drag_state)
             .field("snap_enabled", &self.snap_enabled)
             .field("lock_work_area", &self.lock_work_area)
+            .field("time_display", &self.time_display)
             .field("last_comp_uuid", &self.last_comp_uuid)
             .field("view_mode", &self.view_mode)
             .field("last_canvas_width", &self.last_canvas_width)
@@ -125,6 +150,8 @@ impl Default for TimelineState {
             drag_state: None,
             snap_enabled: true,
             lock_work_area: false,
+            time_display: TimeDisplay::Frames,
+            frame_entry: None,
             last_comp_uuid: None,
             view_mode: TimelineViewMode::Split,
             last_canvas_width: 800.0, // Default estimate
@@ -135,6 +162,8 @@ impl Default for TimelineState {
             rename_dialog_open: false,
             rename_dialog_name: String::new(),
             rename_dialog_old_name: String::new(),
+            marker_edit: None,
+            snap_guide: None,
         }
     }
 }
@@ -4,7 +4,8 @@
 //! timeline canvas migrated to the `egui-track-timeline` widget (it owns those
 //! gestures + ruler internally). What remains is shared by the host overlays
 //! playa still paints itself: the project-drop ghost and the frame-cache status
-//! strip (via [`frame_to_screen_x`]), plus the stable per-clip bar colour.
+//! strip (via [`frame_to_screen_x`]), layer-drag snapping ([`nearest_snap`]),
+//! plus the stable per-clip bar colour.
 use eframe::egui::{Color32, Pos2, Rect};
 
 use super::{TimelineConfig, TimelineState};
@@ -22,6 +23,20 @@ pub(super) fn frame_to_screen_x(
     timeline_rect_min_x + (frame - state.pan_offset) * config.pixels_per_frame * state.zoom
 }
 
+/// Snap radius for dragged layer edges, in screen pixels.
+pub(super) const SNAP_PX: f32 = 8.0;
+
+/// Pick the snap for a dragged span: the `(offset, target)` pair with the
+/// smallest `|target - edge|` over all dragged `edges` (frames) and `targets`,
+/// limited to `threshold` frames. Adding `offset` to the drag aligns the edge.
+pub(super) fn nearest_snap(edges: &[i32], targets: &[i32], threshold: f32) -> Option<(i32, i32)> {
+    edges
+        .iter()
+        .flat_map(|&e| targets.iter().map(move |&t| (t - e, t)))
+        .filter(|(off, _)| (*off as f32).abs() <= threshold)
+        .min_by_key(|(off, _)| off.abs())
+}
+
 /// Screen-space rect for the project→timeline drop ghost (a thumbnail-style bar
 /// at `frame` on the row starting at `row_y`).
 pub(super) fn drop_preview_thumb_rect(
@@ -31,8 +31,8 @@ use super::timeline_events::{
     TimelineFitAllEvent, TimelineLockWorkAreaChangedEvent, TimelinePanChangedEvent,
     TimelineSnapChangedEvent, TimelineZoomChangedEvent,
 };
-use super::timeline_helpers::{drop_preview_thumb_rect, hash_color_str};
-use super::{TimelineConfig, TimelineState};
+use super::timeline_helpers::{SNAP_PX, drop_preview_thumb_rect, hash_color_str, nearest_snap};
+use super::{TIME_DISPLAY_MODES, TimelineConfig, TimelineState};
 use crate::widgets::dnd::{
     GlobalDragState, ProjectDragSnapOverlay, global_drag_state_id, project_drag_snap_overlay_id,
 };
@@ -44,15 +44,21 @@ use egui_track_timeline::{
 };
 use playa_engine::core::event_bus::BoxedEvent;
 use playa_engine::core::player_events::{
-    JumpToEndEvent, JumpToStartEvent, SetFrameEvent, SetLoopEvent, StopEvent, TogglePlayPauseEvent,
+    JumpToEndEvent, JumpToStartEvent, SetFrameEvent, SetLoopEvent, SetSequencePlaybackEvent,
+    StopEvent, TogglePlayPauseEvent,
 };
+use playa_engine::core::workers::Workers;
 use playa_engine::entities::comp_events::{
     AddLayerEvent, CompSelectionChangedEvent, HoverLayerEvent, LayerAttributesChangedEvent,
-    MoveAndReorderLayerEvent, ReorderLayerEvent, SetLayerPlayEndEvent, SetLayerPlayStartEvent,
-    SlideLayerEvent,
+    MoveAndReorderLayerEvent, RemoveMarkerEvent, ReorderLayerEvent, SetLayerPlayEndEvent,
+    SetLayerPlayStartEvent, SetMarkerEvent, SlideLayerEvent,
 };
 use playa_engine::entities::keys::{A_IN, A_SPEED, A_TRIM_IN, A_TRIM_OUT};
-use playa_engine::entities::{AttrValue, Comp, Node, frame::FrameStatus};
+use playa_engine::entities::marker::MARKER_COLORS;
+use playa_engine::entities::{
+    AttrValue, Comp, Marker, Node, NodeKind, ThumbnailCache, WaveformCache, frame::FrameStatus,
+};
+use playa_engine::utils::timecode::{TimeDisplay, display_frame, parse_frame_entry};
 use playa_events::project_media::{ProjectActiveChangedEvent, SelectionFocusEvent};
 use playa_time::{Round, Speed};
 use uuid::Uuid;
@@ -67,6 +73,27 @@ fn uuid_to_u64(uuid: &Uuid) -> u64 {
     u64::from_le_bytes(head)
 }
 
+/// Small icon toggle for per-layer flags: filled with `color` when on, dim
+/// when off. Flips `value` on click.
+fn flag_toggle(ui: &mut egui::Ui, value: &mut bool, icon: &str, color: Color32) -> egui::Response {
+    let text = egui::RichText::new(icon).strong().color(if *value {
+        Color32::BLACK
+    } else {
+        Color32::from_gray(110)
+    });
+    let button = egui::Button::new(text).min_size(egui::vec2(16.0, 16.0));
+    let button = if *value {
+        button.fill(color)
+    } else {
+        button.frame(false)
+    };
+    let response = ui.add(button);
+    if response.clicked() {
+        *value = !*value;
+    }
+    response
+}
+
 #[inline]
 fn frame_status_paint_rgba(status: FrameStatus) -> Color32 {
     let [r, g, b, a] = status.indicator_rgba_unmul();
@@ -107,11 +134,15 @@ fn compute_layer_selection(
     }
 }
 
-/// Render timeline toolbar (transport controls, zoom, snap, loop, view mode, layouts)
+/// Render timeline toolbar (transport controls, current time, zoom, snap, loop,
+/// view mode, layouts). `current_frame`/`fps` feed the current-time field.
 pub fn render_toolbar(
     ui: &mut Ui,
     state: &mut TimelineState,
+    current_frame: i32,
+    fps: f32,
     loop_enabled: bool,
+    sequence_playback: bool,
     show_tooltips: bool,
     layout_names: &[String],
     current_layout: &str,
@@ -137,6 +168,44 @@ pub fn render_toolbar(
 
         ui.separator();
 
+        // Current time: shown in the display mode, accepts frames or timecode
+        // (HH:MM:SS:FF, `;` = drop-frame); Enter jumps there.
+        let mut entry = state
+            .frame_entry
+            .take()
+            .unwrap_or_else(|| display_frame(current_frame, fps, state.time_display));
+        let entry_response = ui.add(
+            egui::TextEdit::singleline(&mut entry)
+                .desired_width(90.0)
+                .font(egui::TextStyle::Monospace),
+        );
+        if entry_response.lost_focus() {
+            if ui.input(|i| i.key_pressed(egui::Key::Enter))
+                && let Some(frame) = parse_frame_entry(&entry, fps, state.time_display)
+            {
+                dispatch(Box::new(SetFrameEvent(frame)));
+            }
+        } else if entry_response.has_focus() {
+            state.frame_entry = Some(entry);
+        }
+        if show_tooltips {
+            entry_response.on_hover_text_at_pointer("Current time (frames or HH:MM:SS:FF)");
+        }
+        let mode_label = TIME_DISPLAY_MODES
+            .iter()
+            .find(|(_, m)| *m == state.time_display)
+            .map_or("Frames", |(label, _)| *label);
+        egui::ComboBox::from_id_salt("timeline_time_display")
+            .selected_text(mode_label)
+            .width(90.0)
+            .show_ui(ui, |ui| {
+                for (label, mode) in TIME_DISPLAY_MODES {
+                    ui.selectable_value(&mut state.time_display, *mode, *label);
+                }
+            });
+
+        ui.separator();
+
         // Zoom controls - fixed max width to leave room for buttons/checkboxes
         ui.label("Zoom:");
         ui.spacing_mut().slider_width = 500.0;
@@ -167,7 +236,9 @@ pub fn render_toolbar(
             dispatch(Box::new(TimelineSnapChangedEvent(state.snap_enabled)));
         }
         if show_tooltips {
-            snap_response.on_hover_text_at_pointer("Snap to frame edges when dragging layers");
+            snap_response.on_hover_text_at_pointer(
+                "Snap dragged layer edges to the playhead, work area and other layers (hold Shift to invert)",
+            );
         }
 
         // Lock checkbox with optional tooltip (2s delay)
@@ -191,6 +262,18 @@ pub fn render_toolbar(
             loop_response.on_hover_text_at_pointer("Loop playback within work area (` key)");
         }
 
+        // Auto-advance checkbox: with loop off, roll into the next comp at the end
+        let mut sequence_state = sequence_playback;
+        let sequence_response = ui.checkbox(&mut sequence_state, "Auto-advance");
+        if sequence_response.changed() {
+            dispatch(Box::new(SetSequencePlaybackEvent(sequence_state)));
+        }
+        if show_tooltips {
+            sequence_response.on_hover_text_at_pointer(
+                "With loop off, continue into the next comp in project order at the work-area end (Shift+` key)",
+            );
+        }
+
         ui.separator();
 
         // View mode selector (moved from ui.rs)
@@ -387,6 +470,8 @@ pub fn render_outline(
 
                     let mut visible = attrs.get_bool("visible").unwrap_or(true);
                     let mut solo = attrs.get_bool("solo").unwrap_or(false);
+                    let mut mute = attrs.get_bool("mute").unwrap_or(false);
+                    let mut locked = attrs.get_bool("locked").unwrap_or(false);
                     let mut opacity = attrs.get_float("opacity").unwrap_or(1.0);
                     let prev_blend = attrs.get_str("blend_mode").unwrap_or("normal").to_string();
                     let mut blend = prev_blend.clone();
@@ -403,24 +488,22 @@ pub fn render_outline(
                             }
                         },
                     );
-                    // Solo checkbox (20px) - yellow when active
-                    row_ui.allocate_ui_with_layout(
-                        egui::Vec2::new(20.0, config.layer_height),
-                        egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
-                        |ui| {
-                            let resp = ui.checkbox(&mut solo, "");
-                            if solo {
-                                ui.painter().rect_filled(
-                                    resp.rect.shrink(2.0),
-                                    2.0,
-                                    egui::Color32::from_rgb(200, 180, 50),
-                                );
-                            }
-                            if resp.changed() {
-                                dirty = true;
-                            }
-                        },
-                    );
+                    // Solo / mute / lock toggles (20px each), lit when active
+                    for (flag, icon, color, hint) in [
+                        (&mut solo, "S", Color32::from_rgb(200, 180, 50), "Solo"),
+                        (&mut mute, "M", Color32::from_rgb(220, 90, 70), "Mute"),
+                        (&mut locked, "🔒", Color32::from_rgb(110, 160, 230), "Lock"),
+                    ] {
+                        row_ui.allocate_ui_with_layout(
+                            egui::Vec2::new(20.0, config.layer_height),
+                            egui::Layout::centered_and_justified(egui::Direction::LeftToRight),
+                            |ui| {
+                                if flag_toggle(ui, flag, icon, color).on_hover_text(hint).clicked() {
+                                    dirty = true;
+                                }
+                            },
+                        );
+                    }
 
                     let child_name = attrs
                         .get_str("name")
@@ -432,7 +515,17 @@ pub fn render_outline(
                         egui::Layout::left_to_right(egui::Align::Center),
                         |ui| {
                             ui.set_min_width(config.name_column_width);
-                            ui.add(egui::Label::new(child_name).truncate());
+                            // Advisory fps / resolution mismatch: tint + hover details
+                            let warnings: Vec<String> =
+                                comp.layer_warnings(child_uuid).map(|w| w.to_string()).collect();
+                            if warnings.is_empty() {
+                                ui.add(egui::Label::new(child_name).truncate());
+                            } else {
+                                let text = egui::RichText::new(format!("⚠ {}", child_name))
+                                    .color(Color32::from_rgb(255, 200, 90));
+                                ui.add(egui::Label::new(text).truncate())
+                                    .on_hover_text(warnings.join("\n"));
+                            }
                         },
                     );
 
@@ -568,6 +661,8 @@ pub fn render_outline(
                             layer_uuids: targets,
                             visible,
                             solo,
+                            mute,
+                            locked,
                             opacity,
                             blend_mode: blend,
                             speed,
@@ -667,6 +762,7 @@ pub fn render_canvas(
     comp_uuid: Uuid,
     comp: &Comp,
     project: &playa_engine::entities::Project,
+    workers: &Workers,
     config: &TimelineConfig,
     state: &mut TimelineState,
     view_mode: super::TimelineViewMode,
@@ -704,8 +800,8 @@ pub fn render_canvas(
         let trim_out = (start + duration) - (play_end as i64 + 1);
 
         let name = attrs.get_str("name").unwrap_or("?").to_string();
-        let visible = attrs.get_bool("visible").unwrap_or(true);
-        // Preserve the exact old bar colour (hash of name, grey when hidden).
+        let visible = attrs.get_bool("visible").unwrap_or(true) && !layer.is_muted();
+        // Preserve the exact old bar colour (hash of name, grey when hidden or muted).
         let color = if visible {
             hash_color_str(&name)
         } else {
@@ -762,6 +858,10 @@ pub fn render_canvas(
     // just below the widget ruler (the widget draws no status strip).
     let status_strip = comp.cache_frame_statuses(project.global_cache.as_ref());
     let comp_start = comp._in();
+    // Named markers are painted over the ruler by playa (the widget only knows
+    // bare bookmark glyphs).
+    let comp_markers = comp.markers();
+    let mut marker_clicked = false;
 
     // Mirror playa's canonical zoom/pan into the widget view before show.
     state.track_view.zoom = state.zoom;
@@ -785,8 +885,29 @@ pub fn render_canvas(
                     Pos2::new(ruler.min.x, ruler.max.y),
                     Pos2::new(ruler.max.x, ruler.max.y + strip_height),
                 );
-                draw_status_strip(ui, strip_rect, statuses, comp_start, 0, ruler, config, state);
+                draw_status_strip(
+                    ui, strip_rect, statuses, comp_start, 0, ruler, config, state,
+                );
+                status_strip_error_tooltip(
+                    ui,
+                    strip_rect,
+                    statuses,
+                    comp_start,
+                    config,
+                    state,
+                    |f| comp.cache_load_error(project.global_cache.as_ref(), f),
+                );
             }
+            draw_waveforms(ui, resp.track_rect, comp, &media, state, &ett_cfg);
+            marker_clicked = draw_markers(
+                ui,
+                resp.ruler_rect,
+                &comp_markers,
+                comp_uuid,
+                state,
+                &ett_cfg,
+                &mut dispatch,
+            );
             resp
         })
         .inner;
@@ -797,7 +918,8 @@ pub fn render_canvas(
     let modifiers = ui.input(|i| i.modifiers);
     let pointer = ui.input(|i| i.pointer.interact_pos());
     let primary_clicked = ui.input(|i| i.pointer.primary_clicked());
-    let mut suppress_seek = false;
+    // A click on a marker flag already seeked to the marker.
+    let mut suppress_seek = marker_clicked;
     if modifiers.ctrl
         && primary_clicked
         && let Some(pos) = pointer
@@ -834,6 +956,47 @@ pub fn render_canvas(
         }
     }
 
+    // --- Ruler scrub preview: thumbnail of the hovered frame beside the cursor
+    // (hidden while a button is down so it never covers a scrub or drag) ---
+    if let Some(pos) = ui.ctx().pointer_hover_pos()
+        && response.ruler_rect.contains(pos)
+        && !ui.input(|i| i.pointer.any_down())
+    {
+        let frame = state
+            .track_view
+            .x_to_frame(pos.x, response.ruler_rect.min.x, &ett_cfg)
+            .round() as i32;
+        if (comp._in()..=comp._out()).contains(&frame) {
+            draw_ruler_preview(ui.ctx(), project, workers, comp_uuid, frame, pos);
+        }
+    }
+
+    // --- Magnetic snapping for layer drags/trims ---
+    // The toolbar Snap toggle, inverted while Shift is held. Dragged edges snap
+    // to the playhead, the work-area bounds and other layers' edges within
+    // SNAP_PX, converted to frames at the current zoom.
+    let snapping = state.snap_enabled != modifiers.shift;
+    let snap_threshold = SNAP_PX / (ett_cfg.pixels_per_frame * state.zoom).max(f32::EPSILON);
+    let snap_radius = snap_threshold.ceil() as i32;
+    let snap_targets = |edges: &[i32], exclude: &[Uuid]| -> Vec<i32> {
+        let mut targets = vec![comp.frame(), wa_start, wa_end + 1];
+        for &edge in edges {
+            targets.extend(comp.get_child_edges_near(edge, snap_radius, exclude));
+        }
+        targets
+    };
+    // Layers moving together with `uuid` never snap to themselves.
+    let drag_group = |uuid: Uuid| -> Vec<Uuid> {
+        if comp.layer_selection.contains(&uuid) {
+            comp.layer_selection.clone()
+        } else {
+            vec![uuid]
+        }
+    };
+    if !ui.input(|i| i.pointer.any_down()) {
+        state.snap_guide = None;
+    }
+
     // --- Translate widget actions into playa events ---
     for action in &response.actions {
         match *action {
@@ -893,10 +1056,24 @@ pub fn render_canvas(
                 let Some(idx) = comp.uuid_to_idx(uuid) else {
                     continue;
                 };
+                let mut new_start = new_start as i32;
+                state.snap_guide = None;
+                if snapping {
+                    // Visible (trimmed) bar edges, relative to A_IN
+                    let layer = &comp.layers[idx];
+                    let head = layer.attrs.layer_start() - layer.start();
+                    let tail = layer.attrs.layer_end() + 1 - layer.start();
+                    let edges = [new_start + head, new_start + tail];
+                    let targets = snap_targets(&edges, &drag_group(uuid));
+                    if let Some((offset, target)) = nearest_snap(&edges, &targets, snap_threshold) {
+                        new_start += offset;
+                        state.snap_guide = Some(target);
+                    }
+                }
                 dispatch(Box::new(MoveAndReorderLayerEvent {
                     comp_uuid,
                     layer_idx: idx,
-                    new_start: new_start as i32,
+                    new_start,
                     new_idx: new_track,
                 }));
             }
@@ -908,7 +1085,17 @@ pub fn render_canvas(
                     continue;
                 };
                 // play_start is in timeline frames; widget TrimStart delta is too.
-                let new_play_start = comp.layers[idx].attrs.layer_start() + delta as i32;
+                let mut new_play_start = comp.layers[idx].attrs.layer_start() + delta as i32;
+                state.snap_guide = None;
+                if snapping {
+                    let targets = snap_targets(&[new_play_start], &drag_group(uuid));
+                    if let Some((offset, target)) =
+                        nearest_snap(&[new_play_start], &targets, snap_threshold)
+                    {
+                        new_play_start += offset;
+                        state.snap_guide = Some(target);
+                    }
+                }
                 dispatch(Box::new(SetLayerPlayStartEvent {
                     comp_uuid,
                     layer_idx: idx,
@@ -926,7 +1113,18 @@ pub fn render_canvas(
                 // more trimmed off the tail => play_end decreases). playa's event
                 // wants `layer_end() + px_delta` with `px_delta = -delta`, hence
                 // `- delta`.
-                let new_play_end = comp.layers[idx].attrs.layer_end() - delta as i32;
+                let mut new_play_end = comp.layers[idx].attrs.layer_end() - delta as i32;
+                state.snap_guide = None;
+                if snapping {
+                    // Snap the exclusive tail boundary (last frame + 1)
+                    let tail = new_play_end + 1;
+                    let targets = snap_targets(&[tail], &drag_group(uuid));
+                    if let Some((offset, target)) = nearest_snap(&[tail], &targets, snap_threshold)
+                    {
+                        new_play_end += offset;
+                        state.snap_guide = Some(target);
+                    }
+                }
                 dispatch(Box::new(SetLayerPlayEndEvent {
                     comp_uuid,
                     layer_idx: idx,
@@ -959,6 +1157,50 @@ pub fn render_canvas(
         }
     }
 
+    // --- Playhead time badge: the widget ruler labels raw frames, so show the
+    // current time in the chosen display mode next to the playhead ---
+    if state.time_display != TimeDisplay::Frames {
+        let ruler = response.ruler_rect;
+        let x = state
+            .track_view
+            .frame_to_x(comp.frame() as f32, ruler.min.x, &ett_cfg);
+        if ruler.x_range().contains(x) {
+            let painter = ui.painter().with_clip_rect(ruler);
+            let text = display_frame(comp.frame(), comp.fps(), state.time_display);
+            let galley = painter.layout_no_wrap(
+                text,
+                egui::FontId::monospace(10.0),
+                Color32::from_gray(230),
+            );
+            let badge = Rect::from_min_size(
+                Pos2::new(x + 3.0, ruler.min.y + 1.0),
+                galley.size() + Vec2::new(6.0, 2.0),
+            );
+            painter.rect_filled(badge, 2.0, Color32::from_rgba_unmultiplied(20, 20, 20, 200));
+            painter.galley(
+                badge.min + Vec2::new(3.0, 1.0),
+                galley,
+                Color32::from_gray(230),
+            );
+        }
+    }
+
+    // --- Snap guide: vertical line through ruler and tracks at the snapped frame ---
+    if let Some(frame) = state.snap_guide {
+        let ruler = response.ruler_rect;
+        let x = state
+            .track_view
+            .frame_to_x(frame as f32, ruler.min.x, &ett_cfg);
+        let clip = ruler.union(response.track_rect).intersect(ui.clip_rect());
+        ui.painter().with_clip_rect(clip).line_segment(
+            [
+                Pos2::new(x, ruler.min.y),
+                Pos2::new(x, response.track_rect.max.y),
+            ],
+            egui::Stroke::new(1.0, Color32::from_rgb(255, 210, 60)),
+        );
+    }
+
     // --- Double-click a bar: dive into the layer's source comp ---
     if let Some(id) = response.double_clicked
         && let Some(uuid) = id_map.get(&id).copied()
@@ -1120,6 +1362,298 @@ pub fn render_canvas(
     }
 }
 
+/// Paint named markers on the ruler (colored flag + label) and handle their
+/// interaction: click seeks to the marker, hover shows its note, right-click
+/// edits label / color or deletes it. Returns true if a flag was clicked.
+fn draw_markers(
+    ui: &mut Ui,
+    ruler: Rect,
+    markers: &[Marker],
+    comp_uuid: Uuid,
+    state: &mut TimelineState,
+    cfg: &TtConfig,
+    dispatch: &mut impl FnMut(BoxedEvent),
+) -> bool {
+    const FLAG: f32 = 6.0;
+    let to_color = |c: [f32; 3]| {
+        Color32::from_rgb(
+            (c[0].clamp(0.0, 1.0) * 255.0) as u8,
+            (c[1].clamp(0.0, 1.0) * 255.0) as u8,
+            (c[2].clamp(0.0, 1.0) * 255.0) as u8,
+        )
+    };
+    let painter = ui.painter().with_clip_rect(ruler);
+    let mut clicked = false;
+
+    for marker in markers {
+        let x = state
+            .track_view
+            .frame_to_x(marker.frame as f32, ruler.min.x, cfg);
+        if x < ruler.min.x - FLAG || x > ruler.max.x + FLAG {
+            continue;
+        }
+        let color = to_color(marker.color);
+        let tip = Pos2::new(x, ruler.max.y);
+        painter.add(egui::Shape::convex_polygon(
+            vec![
+                tip,
+                Pos2::new(x + FLAG, tip.y - FLAG * 1.5),
+                Pos2::new(x - FLAG, tip.y - FLAG * 1.5),
+            ],
+            color,
+            egui::Stroke::NONE,
+        ));
+        if !marker.label.is_empty() {
+            painter.text(
+                Pos2::new(x + FLAG + 2.0, tip.y - 1.0),
+                egui::Align2::LEFT_BOTTOM,
+                &marker.label,
+                egui::FontId::proportional(10.0),
+                color,
+            );
+        }
+
+        let hit = Rect::from_center_size(
+            Pos2::new(x, tip.y - FLAG * 0.75),
+            Vec2::new(FLAG * 2.0 + 2.0, FLAG * 1.5 + 2.0),
+        );
+        let id = ui.id().with(("timeline_marker", marker.frame));
+        let mut response = ui.interact(hit, id, Sense::click());
+        if !marker.label.is_empty() {
+            response = response.on_hover_text(format!("{}: {}", marker.frame, marker.label));
+        }
+        if response.clicked() {
+            dispatch(Box::new(SetFrameEvent(marker.frame)));
+            clicked = true;
+        }
+        response.context_menu(|ui| {
+            let edit = state
+                .marker_edit
+                .get_or_insert_with(|| (marker.frame, marker.label.clone()));
+            if edit.0 != marker.frame {
+                *edit = (marker.frame, marker.label.clone());
+            }
+            ui.label(format!("Marker @ {}", marker.frame));
+            let label_changed = ui.text_edit_singleline(&mut edit.1).changed();
+            let mut color = marker.color;
+            ui.horizontal(|ui| {
+                for &c in MARKER_COLORS {
+                    let swatch = egui::Button::new("")
+                        .fill(to_color(c))
+                        .min_size(Vec2::splat(14.0));
+                    if ui.add(swatch).clicked() {
+                        color = c;
+                    }
+                }
+            });
+            if label_changed || color != marker.color {
+                dispatch(Box::new(SetMarkerEvent {
+                    comp_uuid,
+                    frame: marker.frame,
+                    label: edit.1.clone(),
+                    color,
+                }));
+            }
+            if ui.button("Delete").clicked() {
+                dispatch(Box::new(RemoveMarkerEvent {
+                    comp_uuid,
+                    frame: marker.frame,
+                }));
+                state.marker_edit = None;
+            }
+        });
+    }
+    clicked
+}
+
+/// Edge of the ruler scrub preview box in pixels
+const RULER_PREVIEW_SIZE: u32 = 160;
+
+/// Last uploaded scrub preview: (comp, frame, thumbnail key) and its texture,
+/// so hovering one frame uploads once.
+#[derive(Clone, Default)]
+struct RulerPreviewTexture(Option<((Uuid, i32, u64), egui::TextureHandle)>);
+
+/// Popup near `pos` with the thumbnail of `frame`, or a placeholder while the
+/// engine's [`ThumbnailCache`] renders it in the background.
+fn draw_ruler_preview(
+    ctx: &egui::Context,
+    project: &playa_engine::entities::Project,
+    workers: &Workers,
+    comp_uuid: Uuid,
+    frame: i32,
+    pos: Pos2,
+) {
+    let repaint = ctx.clone();
+    let thumb = ThumbnailCache::global().get_or_request_frame(
+        project,
+        workers,
+        comp_uuid,
+        frame,
+        RULER_PREVIEW_SIZE,
+        move || repaint.request_repaint(),
+    );
+    let id = egui::Id::new("timeline_ruler_preview");
+    let texture = thumb.and_then(|thumb| {
+        let key = (comp_uuid, frame, thumb.key);
+        let cached = ctx.data_mut(|d| {
+            d.get_temp_mut_or_default::<RulerPreviewTexture>(id)
+                .0
+                .clone()
+        });
+        if let Some((cached_key, tex)) = cached
+            && cached_key == key
+        {
+            return Some(tex);
+        }
+        let tex = crate::widgets::project::thumbnails::upload(
+            ctx,
+            "timeline_ruler_preview".to_string(),
+            &thumb,
+        )?;
+        ctx.data_mut(|d| d.insert_temp(id, RulerPreviewTexture(Some((key, tex.clone())))));
+        Some(tex)
+    });
+
+    let edge = RULER_PREVIEW_SIZE as f32;
+    egui::Area::new(id)
+        .order(egui::Order::Tooltip)
+        .fixed_pos(pos + Vec2::new(12.0, 16.0))
+        .interactable(false)
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                let size = texture
+                    .as_ref()
+                    .map(|tex| tex.size_vec2())
+                    .unwrap_or(Vec2::new(edge, edge * 9.0 / 16.0));
+                let (rect, _) = ui.allocate_exact_size(size, Sense::hover());
+                match &texture {
+                    Some(tex) => {
+                        ui.painter().image(
+                            tex.id(),
+                            rect,
+                            Rect::from_min_max(Pos2::ZERO, Pos2::new(1.0, 1.0)),
+                            Color32::WHITE,
+                        );
+                    }
+                    None => {
+                        ui.painter()
+                            .rect_filled(rect, 2.0, ui.visuals().extreme_bg_color);
+                        ui.painter().text(
+                            rect.center(),
+                            egui::Align2::CENTER_CENTER,
+                            "…",
+                            egui::FontId::proportional(14.0),
+                            ui.visuals().weak_text_color(),
+                        );
+                    }
+                }
+                ui.label(egui::RichText::new(frame.to_string()).small());
+            });
+        });
+}
+
+/// Audio waveform for video-file layers, drawn in the lower part of each clip
+/// body. Peaks decode lazily per source (see [`WaveformCache`]); layers without
+/// audio, or still decoding, render as plain bars.
+fn draw_waveforms(
+    ui: &Ui,
+    track_rect: Rect,
+    comp: &Comp,
+    media: &std::collections::HashMap<Uuid, std::sync::Arc<NodeKind>>,
+    state: &TimelineState,
+    cfg: &TtConfig,
+) {
+    let clip = track_rect.intersect(ui.clip_rect());
+    if !clip.is_positive() {
+        return;
+    }
+    let painter = ui.painter().with_clip_rect(clip);
+    let stroke = egui::Stroke::new(1.0, Color32::from_rgba_unmultiplied(15, 15, 15, 160));
+
+    for (row, layer) in comp.layers.iter().enumerate() {
+        let source = layer.source_uuid();
+        let Some(file) = media.get(&source).and_then(|n| n.as_file()) else {
+            continue;
+        };
+        let top = track_rect.min.y + row as f32 * cfg.row_height;
+        let band_y = (top + cfg.row_height * 0.4)..=(top + cfg.row_height - 2.0);
+        if *band_y.end() < clip.min.y || *band_y.start() > clip.max.y {
+            continue;
+        }
+        let ctx = ui.ctx().clone();
+        let Some(peaks) =
+            WaveformCache::global().get_for_file(source, file, move || ctx.request_repaint())
+        else {
+            continue;
+        };
+
+        // Visible (trimmed) bar span, clamped to the view
+        let (play_start, play_end) = comp.get_layer_work_area(layer, media);
+        let fx = state
+            .track_view
+            .frame_to_x(play_start as f32, track_rect.min.x, cfg);
+        let ppf = state
+            .track_view
+            .frame_to_x(play_start as f32 + 1.0, track_rect.min.x, cfg)
+            - fx;
+        let bar_end = fx + (play_end + 1 - play_start) as f32 * ppf;
+        let x0 = fx.max(clip.min.x).floor();
+        let x1 = bar_end.min(clip.max.x);
+        if ppf <= 0.0 || x1 - x0 < 1.0 {
+            continue;
+        }
+
+        // Screen x -> comp frame -> source seconds (speed-scaled, from A_IN)
+        let speed = Speed::new(layer.attrs.get_float(A_SPEED).unwrap_or(1.0)).magnitude();
+        let src_fps = file.fps().max(1.0);
+        let secs_at = |x: f32| {
+            let frame = play_start as f32 + (x - fx) / ppf;
+            (frame - layer.start() as f32) * speed / src_fps
+        };
+        let columns = (x1 - x0).ceil() as usize;
+        let center = (band_y.start() + band_y.end()) * 0.5;
+        let half = (band_y.end() - band_y.start()) * 0.5;
+        let envelope = peaks.resample(secs_at(x0), secs_at(x0 + columns as f32), columns);
+        for (i, column) in envelope.into_iter().enumerate() {
+            let Some((lo, hi)) = column else {
+                continue;
+            };
+            let x = x0 + i as f32 + 0.5;
+            let y_top = center - hi * half;
+            let y_bottom = (center - lo * half).max(y_top + 1.0);
+            painter.line_segment([Pos2::new(x, y_top), Pos2::new(x, y_bottom)], stroke);
+        }
+    }
+}
+
+/// Hover tooltip over failed (red) status strip frames: frame number plus
+/// the file path and loader message recorded on the cached frame.
+fn status_strip_error_tooltip(
+    ui: &mut Ui,
+    strip_rect: Rect,
+    statuses: &[FrameStatus],
+    comp_start: i32,
+    config: &super::TimelineConfig,
+    state: &super::TimelineState,
+    load_error: impl Fn(i32) -> Option<String>,
+) {
+    // The strip is only 2px tall: give the pointer a few px of slack
+    let hit = strip_rect.expand2(Vec2::new(0.0, 3.0));
+    let response = ui.interact(hit, ui.id().with("status_strip_errors"), Sense::hover());
+    let Some(pos) = response.hover_pos() else {
+        return;
+    };
+    let effective_ppf = config.pixels_per_frame * state.zoom;
+    let frame = (state.pan_offset + (pos.x - strip_rect.min.x) / effective_ppf).floor() as i32;
+    let offset = frame - comp_start;
+    if offset < 0 || statuses.get(offset as usize) != Some(&FrameStatus::Error) {
+        return;
+    }
+    let message = load_error(frame).unwrap_or_else(|| "Load failed".to_string());
+    response.on_hover_text(format!("Frame {}: {}", frame, message));
+}
+
 fn draw_status_strip(
     ui: &Ui,
     rect: Rect,
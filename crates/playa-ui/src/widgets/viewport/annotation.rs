@@ -0,0 +1,372 @@
+//! Review annotations - freehand strokes, arrows and text notes over the frame.
+//!
+//! Annotations live on the comp ([`CompNode::annotations`], comp pixels, keyed
+//! by frame) so they save with the project. This module records the LMB
+//! gesture in image space, draws the current frame's annotations through the
+//! viewport transform (they follow zoom / pan / orientation) and hosts the
+//! tool options bar. Pixels are never touched; burning the markup into
+//! exported frames is an encode option.
+//!
+//! [`CompNode::annotations`]: playa_engine::entities::CompNode::annotations
+
+use eframe::egui;
+use playa_engine::core::event_bus::BoxedEvent;
+use playa_engine::core::player::Player;
+use playa_engine::entities::Project;
+use playa_engine::entities::annotation::{
+    ANNOTATION_COLORS, Annotation, AnnotationKind, DEFAULT_STROKE_SIZE, DEFAULT_TEXT_SIZE,
+    arrow_head,
+};
+use playa_engine::entities::comp_events::{
+    AddAnnotationEvent, ClearAnnotationsEvent, PopAnnotationEvent,
+};
+use uuid::Uuid;
+
+use super::ViewportState;
+use super::tool::ToolMode;
+
+/// Minimum screen distance between recorded freehand points
+const MIN_POINT_SPACING: f32 = 2.0;
+
+/// Annotation tool options plus the gesture in progress
+#[derive(Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct AnnotationTool {
+    pub kind: AnnotationKind,
+    /// Index into [`ANNOTATION_COLORS`]
+    pub color: usize,
+    /// Freehand / arrow stroke width in comp pixels
+    pub stroke_size: f32,
+    /// Text height in comp pixels
+    pub text_size: f32,
+    /// Draw annotations over the frame (with any tool active)
+    pub visible: bool,
+    /// Image-space points of the stroke being drawn
+    #[serde(skip)]
+    stroke: Vec<egui::Vec2>,
+    /// Text note being typed: image-space anchor + text
+    #[serde(skip)]
+    pending_text: Option<(egui::Vec2, String)>,
+}
+
+impl Default for AnnotationTool {
+    fn default() -> Self {
+        Self {
+            kind: AnnotationKind::default(),
+            color: 0,
+            stroke_size: DEFAULT_STROKE_SIZE,
+            text_size: DEFAULT_TEXT_SIZE,
+            visible: true,
+            stroke: Vec::new(),
+            pending_text: None,
+        }
+    }
+}
+
+impl AnnotationTool {
+    fn rgba(&self) -> [f32; 4] {
+        ANNOTATION_COLORS[self.color % ANNOTATION_COLORS.len()]
+    }
+
+    /// New annotation on `frame` with the current options
+    fn annotation(&self, frame: i32) -> Annotation {
+        let size = match self.kind {
+            AnnotationKind::Text => self.text_size,
+            _ => self.stroke_size,
+        };
+        Annotation::new(frame, self.kind)
+            .with_color(self.rgba())
+            .with_size(size)
+    }
+
+    fn is_drawing(&self) -> bool {
+        !self.stroke.is_empty()
+    }
+}
+
+/// LMB in Annotate mode: drag draws a freehand stroke or an arrow, a click
+/// with the text kind opens a note entry at the cursor.
+pub(super) fn input_event(
+    response: &egui::Response,
+    panel_rect: egui::Rect,
+    viewport_state: &mut ViewportState,
+    player: &Player,
+    project: &Project,
+) -> Option<BoxedEvent> {
+    if ToolMode::from_str(&project.tool()) != ToolMode::Annotate {
+        viewport_state.annotation.stroke.clear();
+        viewport_state.annotation.pending_text = None;
+        return None;
+    }
+    let comp_uuid = player.active_comp()?;
+    let frame = player.current_frame(project);
+    let pointer = response
+        .interact_pointer_pos()
+        .or_else(|| response.ctx.pointer_latest_pos())
+        .map(|pos| viewport_state.screen_to_image_clamped(pos - panel_rect.min));
+    let spacing = MIN_POINT_SPACING / viewport_state.zoom.max(f32::EPSILON);
+    let tool = &mut viewport_state.annotation;
+
+    if tool.kind == AnnotationKind::Text {
+        if response.clicked_by(egui::PointerButton::Primary) {
+            tool.pending_text = pointer.map(|p| (p, String::new()));
+        }
+        return None;
+    }
+
+    if response.drag_started_by(egui::PointerButton::Primary) {
+        tool.stroke = pointer.into_iter().collect();
+    } else if response.dragged_by(egui::PointerButton::Primary)
+        && tool.is_drawing()
+        && let Some(p) = pointer
+    {
+        match tool.kind {
+            // Arrow: anchor + live tip
+            AnnotationKind::Arrow => {
+                tool.stroke.truncate(1);
+                tool.stroke.push(p);
+            }
+            _ => {
+                if tool
+                    .stroke
+                    .last()
+                    .is_none_or(|&last| (last - p).length() >= spacing)
+                {
+                    tool.stroke.push(p);
+                }
+            }
+        }
+    }
+
+    if response.drag_stopped_by(egui::PointerButton::Primary) && tool.is_drawing() {
+        let points: Vec<[f32; 2]> = std::mem::take(&mut tool.stroke)
+            .into_iter()
+            .map(|p| [p.x, p.y])
+            .collect();
+        if tool.kind == AnnotationKind::Arrow && points.len() < 2 {
+            return None;
+        }
+        return add_event(comp_uuid, tool.annotation(frame).with_points(points));
+    }
+    None
+}
+
+/// Note entry box at the clicked point; Enter commits, Escape cancels.
+pub(super) fn text_entry(
+    ctx: &egui::Context,
+    panel_rect: egui::Rect,
+    viewport_state: &mut ViewportState,
+    player: &Player,
+    project: &Project,
+) -> Option<BoxedEvent> {
+    let anchor = viewport_state.annotation.pending_text.as_ref()?.0;
+    let pos = panel_rect.min + viewport_state.image_to_screen(anchor);
+    let comp_uuid = player.active_comp()?;
+    let frame = player.current_frame(project);
+    let tool = &mut viewport_state.annotation;
+
+    let (mut commit, mut cancel) = (false, false);
+    if let Some((_, text)) = tool.pending_text.as_mut() {
+        egui::Area::new(egui::Id::new("annotation_text_entry"))
+            .fixed_pos(pos)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                let edit = ui.add(
+                    egui::TextEdit::singleline(text)
+                        .hint_text("Note, Enter to add")
+                        .desired_width(240.0),
+                );
+                if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                    cancel = true;
+                } else if edit.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                    commit = true;
+                } else {
+                    edit.request_focus();
+                }
+            });
+    }
+    if !(commit || cancel) {
+        return None;
+    }
+    let (anchor, text) = tool.pending_text.take()?;
+    let text = text.trim();
+    if cancel || text.is_empty() {
+        return None;
+    }
+    add_event(
+        comp_uuid,
+        tool.annotation(frame)
+            .with_points(vec![[anchor.x, anchor.y]])
+            .with_text(text),
+    )
+}
+
+fn add_event(comp_uuid: Uuid, annotation: Annotation) -> Option<BoxedEvent> {
+    match serde_json::to_value(&annotation) {
+        Ok(annotation) => Some(Box::new(AddAnnotationEvent {
+            comp_uuid,
+            annotation,
+        })),
+        Err(err) => {
+            log::error!("Failed to encode annotation: {}", err);
+            None
+        }
+    }
+}
+
+/// Tool options bar (top-left) while Annotate is active: kind, color, size,
+/// visibility, undo and clear.
+pub(super) fn toolbar(
+    ctx: &egui::Context,
+    panel_rect: egui::Rect,
+    viewport_state: &mut ViewportState,
+    player: &Player,
+    project: &Project,
+) -> Vec<BoxedEvent> {
+    let mut events: Vec<BoxedEvent> = Vec::new();
+    if ToolMode::from_str(&project.tool()) != ToolMode::Annotate {
+        return events;
+    }
+    let Some(comp_uuid) = player.active_comp() else {
+        return events;
+    };
+    let frame = player.current_frame(project);
+    let tool = &mut viewport_state.annotation;
+
+    egui::Area::new(egui::Id::new("annotation_toolbar"))
+        .fixed_pos(panel_rect.min + egui::vec2(10.0, 10.0))
+        .show(ctx, |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    for kind in AnnotationKind::ALL {
+                        ui.selectable_value(&mut tool.kind, kind, kind.label());
+                    }
+                    ui.separator();
+                    for (idx, rgba) in ANNOTATION_COLORS.iter().enumerate() {
+                        let selected = tool.color == idx;
+                        let swatch = egui::Button::new("")
+                            .fill(to_color32(*rgba))
+                            .min_size(egui::vec2(16.0, 16.0))
+                            .stroke(if selected {
+                                egui::Stroke::new(2.0, egui::Color32::WHITE)
+                            } else {
+                                egui::Stroke::NONE
+                            });
+                        if ui.add(swatch).clicked() {
+                            tool.color = idx;
+                        }
+                    }
+                    ui.separator();
+                    let size = match tool.kind {
+                        AnnotationKind::Text => &mut tool.text_size,
+                        _ => &mut tool.stroke_size,
+                    };
+                    ui.add(
+                        egui::DragValue::new(size)
+                            .range(1.0..=500.0)
+                            .speed(0.25)
+                            .suffix(" px"),
+                    )
+                    .on_hover_text("Stroke width / text height in comp pixels");
+                    ui.checkbox(&mut tool.visible, "Show");
+                    ui.separator();
+                    if ui
+                        .button("Undo")
+                        .on_hover_text("Remove the last annotation on this frame")
+                        .clicked()
+                    {
+                        events.push(Box::new(PopAnnotationEvent { comp_uuid, frame }));
+                    }
+                    if ui.button("Clear frame").clicked() {
+                        events.push(Box::new(ClearAnnotationsEvent {
+                            comp_uuid,
+                            frame: Some(frame),
+                        }));
+                    }
+                    if ui.button("Clear all").clicked() {
+                        events.push(Box::new(ClearAnnotationsEvent {
+                            comp_uuid,
+                            frame: None,
+                        }));
+                    }
+                });
+            });
+        });
+    events
+}
+
+/// Draw this frame's annotations plus the stroke being drawn.
+pub(super) fn draw(
+    ui: &egui::Ui,
+    panel_rect: egui::Rect,
+    viewport_state: &ViewportState,
+    annotations: &[Annotation],
+) {
+    let tool = &viewport_state.annotation;
+    if !tool.visible && !tool.is_drawing() {
+        return;
+    }
+    let painter = ui.painter().with_clip_rect(panel_rect);
+    let to_screen = |[x, y]: [f32; 2]| {
+        (panel_rect.min.to_vec2() + viewport_state.image_to_screen(egui::vec2(x, y))).to_pos2()
+    };
+    let zoom = viewport_state.zoom;
+
+    let draw_one = |annotation: &Annotation| {
+        let color = to_color32(annotation.color);
+        let stroke = egui::Stroke::new((annotation.size * zoom).max(1.0), color);
+        match annotation.kind {
+            AnnotationKind::Freehand => {
+                let points: Vec<egui::Pos2> =
+                    annotation.points.iter().copied().map(to_screen).collect();
+                match points.len() {
+                    0 => {}
+                    1 => painter.circle_filled(points[0], stroke.width * 0.5, color),
+                    _ => {
+                        painter.add(egui::Shape::line(points, stroke));
+                    }
+                }
+            }
+            AnnotationKind::Arrow => {
+                let (Some(&tail), Some(&tip)) =
+                    (annotation.points.first(), annotation.points.last())
+                else {
+                    return;
+                };
+                painter.line_segment([to_screen(tail), to_screen(tip)], stroke);
+                if let Some(barbs) = arrow_head(tail, tip, annotation.size) {
+                    for barb in barbs {
+                        painter.line_segment([to_screen(tip), to_screen(barb)], stroke);
+                    }
+                }
+            }
+            AnnotationKind::Text => {
+                if let Some(&anchor) = annotation.points.first() {
+                    painter.text(
+                        to_screen(anchor),
+                        egui::Align2::LEFT_TOP,
+                        &annotation.text,
+                        egui::FontId::proportional((annotation.size * zoom).max(4.0)),
+                        color,
+                    );
+                }
+            }
+        }
+    };
+
+    if tool.visible {
+        for annotation in annotations {
+            draw_one(annotation);
+        }
+    }
+    if tool.is_drawing() {
+        let points = tool.stroke.iter().map(|p| [p.x, p.y]).collect();
+        draw_one(&tool.annotation(0).with_points(points));
+    }
+}
+
+/// sRGB 0..1 straight RGBA -> egui color
+fn to_color32([r, g, b, a]: [f32; 4]) -> egui::Color32 {
+    let byte = |v: f32| (v.clamp(0.0, 1.0) * 255.0).round() as u8;
+    egui::Color32::from_rgba_unmultiplied(byte(r), byte(g), byte(b), byte(a))
+}
@@ -0,0 +1,135 @@
+//! Viewport view bookmarks - numbered slots holding zoom / pan mode,
+//! orientation and the alpha checkerboard, recalled with a hotkey to compare
+//! the same crop across frames.
+//!
+//! Slots are saved in the project (`view_bookmarks` attr, JSON) so a review
+//! setup travels with the project file. "Frame all" (`F`) and "100%" (`A`)
+//! stay fixed defaults outside the slots.
+
+use eframe::egui;
+use playa_engine::core::event_bus::BoxedEvent;
+use playa_engine::entities::Project;
+
+use super::viewport::{ViewportMode, ViewportOrientation, ViewportState};
+use super::viewport_events::{RecallViewBookmarkEvent, StoreViewBookmarkEvent};
+
+/// User slots, keyed 1..=9
+pub const SLOT_COUNT: u8 = 9;
+/// Project attr holding the slots (JSON list, index 0 = slot 1)
+const ATTR_KEY: &str = "view_bookmarks";
+
+/// One saved view
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+pub struct ViewBookmark {
+    pub mode: ViewportMode,
+    pub zoom: f32,
+    pub pan: [f32; 2],
+    pub orientation: ViewportOrientation,
+    pub checker: bool,
+}
+
+impl ViewBookmark {
+    pub fn capture(state: &ViewportState) -> Self {
+        Self {
+            mode: state.mode,
+            zoom: state.zoom,
+            pan: state.pan.into(),
+            orientation: state.orientation,
+            checker: state.checker,
+        }
+    }
+
+    /// Restore onto `state`. Fit / 100% bookmarks re-apply their mode, so they
+    /// follow the current image and window size.
+    pub fn apply(&self, state: &mut ViewportState) {
+        state.orientation = self.orientation;
+        match self.mode {
+            ViewportMode::AutoFit => state.set_mode_fit(),
+            ViewportMode::Auto100 => state.set_mode_100(),
+            ViewportMode::Manual => {
+                state.mode = ViewportMode::Manual;
+                state.zoom = self.zoom;
+                state.pan = self.pan.into();
+            }
+        }
+        if state.checker != self.checker {
+            state.toggle_checker();
+        }
+    }
+
+    /// Menu label, e.g. `250% (Rot 90 Flip)`
+    fn label(&self) -> String {
+        let zoom = match self.mode {
+            ViewportMode::AutoFit => "Fit".to_string(),
+            ViewportMode::Auto100 => "100%".to_string(),
+            ViewportMode::Manual => format!("{:.0}%", self.zoom * 100.0),
+        };
+        match self.orientation.label() {
+            Some(o) => format!("{} ({})", zoom, o),
+            None => zoom,
+        }
+    }
+}
+
+/// All slots saved in `project`; missing or unreadable = empty
+pub fn load(project: &Project) -> Vec<Option<ViewBookmark>> {
+    let mut slots: Vec<Option<ViewBookmark>> = project.attrs.get_json(ATTR_KEY).unwrap_or_default();
+    slots.resize(SLOT_COUNT as usize, None);
+    slots
+}
+
+/// Bookmark in `slot` (1..=9)
+pub fn recall(project: &Project, slot: u8) -> Option<ViewBookmark> {
+    let idx = (slot as usize).checked_sub(1)?;
+    load(project).get(idx).copied().flatten()
+}
+
+/// Save `bookmark` to `slot` (1..=9); out-of-range slots are ignored
+pub fn store(project: &mut Project, slot: u8, bookmark: ViewBookmark) {
+    if !(1..=SLOT_COUNT).contains(&slot) {
+        return;
+    }
+    let mut slots = load(project);
+    slots[slot as usize - 1] = Some(bookmark);
+    project.attrs.set_json(ATTR_KEY, &slots);
+}
+
+/// Views menu: fixed Fit / 100% entries, then recall / save per slot.
+/// Slot operations go through the event bus (they touch the project).
+pub fn menu_ui(ui: &mut egui::Ui, state: &mut ViewportState, project: &Project) -> Vec<BoxedEvent> {
+    let mut events: Vec<BoxedEvent> = Vec::new();
+    if ui.button("Frame all (F)").clicked() {
+        state.set_mode_fit();
+        ui.close();
+    }
+    if ui.button("100% (A)").clicked() {
+        state.set_mode_100();
+        ui.close();
+    }
+    ui.separator();
+    for (idx, bookmark) in load(project).iter().enumerate() {
+        let slot = idx as u8 + 1;
+        ui.horizontal(|ui| {
+            let label = match bookmark {
+                Some(b) => format!("{}: {}", slot, b.label()),
+                None => format!("{}: empty", slot),
+            };
+            if ui
+                .add_enabled(bookmark.is_some(), egui::Button::new(label))
+                .on_hover_text(format!("Recall ({})", slot))
+                .clicked()
+            {
+                events.push(Box::new(RecallViewBookmarkEvent(slot)));
+                ui.close();
+            }
+            if ui
+                .small_button("Save")
+                .on_hover_text(format!("Save the current view (Ctrl+{})", slot))
+                .clicked()
+            {
+                events.push(Box::new(StoreViewBookmarkEvent(slot)));
+            }
+        });
+    }
+    events
+}
@@ -33,3 +33,13 @@ pub fn screen_to_viewport_centered(
 pub fn screen_delta_to_viewport(delta: egui::Vec2) -> egui::Vec2 {
     flip_y_vec2(delta)
 }
+
+/// Convert a viewport delta (+Y up) to a frame-space delta, undoing zoom and
+/// the display transform (`ViewportState::display_affine`: pixel aspect,
+/// rotate / flip / flop). Keeps drags following the pointer on a
+/// reoriented view.
+pub fn viewport_delta_to_frame(delta: egui::Vec2, zoom: f32, display: glam::Affine2) -> egui::Vec2 {
+    let g = space::viewport_to_frame(glam::Vec2::new(delta.x, delta.y), zoom, glam::Vec2::ZERO);
+    let g = display.inverse().transform_vector2(g);
+    egui::vec2(g.x, g.y)
+}
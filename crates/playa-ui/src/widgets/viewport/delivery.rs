@@ -0,0 +1,154 @@
+//! Delivery framing preview - the frame as it would sit in an output of a
+//! target aspect: letterboxed (bars above / below) when the target is
+//! narrower than the image, pillarboxed (bars left / right) when wider.
+//!
+//! Unlike the guides' aspect mask, which crops *into* the image, this grows
+//! the frame out to the delivery aspect and fills the difference with black
+//! mattes, dimming the viewport around it. Fit (`F`) frames the whole
+//! delivery rect while it is on. Display-only; persisted with
+//! [`ViewportState`](super::ViewportState).
+
+use eframe::egui;
+
+/// Delivery aspect presets (label, width / height)
+pub const DELIVERY_PRESETS: &[(&str, f32)] = &[
+    ("4:3", 4.0 / 3.0),
+    ("16:9", 16.0 / 9.0),
+    ("1.85", 1.85),
+    ("2.39", 2.39),
+];
+
+/// Matte fill (the output's black bars)
+const MATTE_COLOR: egui::Color32 = egui::Color32::BLACK;
+/// Darkening of the viewport outside the delivery frame
+const DIM_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(0, 0, 0, 170);
+/// Frame outline and label
+const FRAME_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(180, 180, 180, 180);
+
+/// Target output aspect for the framing preview
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct DeliveryFrame {
+    pub enabled: bool,
+    /// Output width / height
+    pub aspect: f32,
+}
+
+impl Default for DeliveryFrame {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            aspect: 2.39,
+        }
+    }
+}
+
+impl DeliveryFrame {
+    /// Active target aspect, `None` while off
+    pub fn active_aspect(&self) -> Option<f32> {
+        (self.enabled && self.aspect > 0.0).then_some(self.aspect)
+    }
+
+    /// Size of the delivery frame around an image of `size` (same units):
+    /// the image grown to the target aspect on one axis. Unchanged while off.
+    pub fn frame_size(&self, size: egui::Vec2) -> egui::Vec2 {
+        match self.active_aspect() {
+            Some(aspect) if size.y > 0.0 && size.x / size.y < aspect => {
+                egui::vec2(size.y * aspect, size.y)
+            }
+            Some(aspect) if size.y > 0.0 => egui::vec2(size.x, size.x / aspect),
+            _ => size,
+        }
+    }
+
+    /// Paint mattes inside the delivery frame and dim the viewport outside it.
+    /// `image_rect` is the displayed image in screen space.
+    pub fn draw(&self, ui: &egui::Ui, image_rect: egui::Rect, clip: egui::Rect) {
+        let Some(aspect) = self.active_aspect() else {
+            return;
+        };
+        if image_rect.width() <= 0.0 || image_rect.height() <= 0.0 {
+            return;
+        }
+        let frame =
+            egui::Rect::from_center_size(image_rect.center(), self.frame_size(image_rect.size()));
+        let painter = ui.painter().with_clip_rect(clip);
+
+        // Viewport outside the frame: top / bottom full width, then the sides
+        for (rect, color) in [
+            (surround(clip, frame), DIM_COLOR),
+            (surround(frame, image_rect), MATTE_COLOR),
+        ]
+        .into_iter()
+        .flat_map(|(bars, color)| bars.map(|bar| (bar, color)))
+        {
+            if rect.width() > 0.0 && rect.height() > 0.0 {
+                painter.rect_filled(rect, 0.0, color);
+            }
+        }
+        painter.rect_stroke(
+            frame,
+            0.0,
+            egui::Stroke::new(1.0, FRAME_COLOR),
+            egui::epaint::StrokeKind::Outside,
+        );
+        let kind = if frame.width() > image_rect.width() + 0.5 {
+            "pillarbox"
+        } else if frame.height() > image_rect.height() + 0.5 {
+            "letterbox"
+        } else {
+            "full frame"
+        };
+        painter.text(
+            frame.left_bottom() + egui::vec2(0.0, 4.0),
+            egui::Align2::LEFT_TOP,
+            format!("Delivery {:.2}:1 ({})", aspect, kind),
+            egui::FontId::proportional(11.0),
+            FRAME_COLOR,
+        );
+    }
+
+    /// Delivery menu: toggle, presets and a free aspect. Returns true when
+    /// the framing changed (the view refits).
+    pub fn menu_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let before = *self;
+        ui.checkbox(&mut self.enabled, "Preview delivery framing");
+        ui.add_enabled_ui(self.enabled, |ui| {
+            ui.horizontal(|ui| {
+                for &(label, aspect) in DELIVERY_PRESETS {
+                    if ui
+                        .selectable_label((self.aspect - aspect).abs() < 1e-3, label)
+                        .clicked()
+                    {
+                        self.aspect = aspect;
+                    }
+                }
+            });
+            ui.add(
+                egui::DragValue::new(&mut self.aspect)
+                    .range(0.5..=4.0)
+                    .speed(0.01)
+                    .suffix(":1"),
+            );
+        });
+        *self != before
+    }
+}
+
+/// The four bars of `outer` around `inner` (top, bottom, left, right); empty
+/// bars come back zero-sized
+fn surround(outer: egui::Rect, inner: egui::Rect) -> [egui::Rect; 4] {
+    let inner = inner.intersect(outer);
+    [
+        egui::Rect::from_min_max(outer.min, egui::pos2(outer.max.x, inner.min.y)),
+        egui::Rect::from_min_max(egui::pos2(outer.min.x, inner.max.y), outer.max),
+        egui::Rect::from_min_max(
+            egui::pos2(outer.min.x, inner.min.y),
+            egui::pos2(inner.min.x, inner.max.y),
+        ),
+        egui::Rect::from_min_max(
+            egui::pos2(inner.max.x, inner.min.y),
+            egui::pos2(outer.max.x, inner.max.y),
+        ),
+    ]
+}
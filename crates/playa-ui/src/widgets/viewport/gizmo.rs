@@ -24,6 +24,13 @@
 //!   `GizmoSpace` curation: 3D-camera comps stay full 3D, flat 2D comps use the
 //!   2D handle set (Z translate/scale and X/Y rotation rings auto-hidden).
 //! - the CW+deg <-> CCW+rad rotation conversion and ZYX euler order are identical.
+//!
+//! ## Keyframing
+//!
+//! The gizmo edits the transform evaluated at the current frame. Channels that
+//! already carry keyframes update their key at that frame while dragging;
+//! holding Alt when the drag is released records a key for the edited channel.
+//! A numeric readout of the evaluated values is drawn next to the gizmo.
 
 use eframe::egui;
 use egui_gizmo::{
@@ -48,12 +55,15 @@ use playa_engine::entities::space;
 /// each frame. We reconfigure it via `update_config` and then `interact`.
 pub struct GizmoState {
     gizmo: Gizmo,
+    /// A handle was dragged last frame (release detection for keying)
+    dragging: bool,
 }
 
 impl Default for GizmoState {
     fn default() -> Self {
         Self {
             gizmo: Gizmo::default(),
+            dragging: false,
         }
     }
 }
@@ -93,14 +103,16 @@ impl GizmoState {
             return (false, Vec::new());
         }
 
-        // Collect layer transforms
-        let (transforms, layer_data) = self.collect_transforms(tool, project, comp_uuid, &selected);
+        // Collect layer transforms (evaluated at the current frame)
+        let frame_idx = player.current_frame(project);
+        let (transforms, layer_data) =
+            self.collect_transforms(tool, project, comp_uuid, &selected, frame_idx);
         if transforms.is_empty() {
+            self.dragging = false;
             return (false, Vec::new());
         }
 
         // Get camera matrices if 3D camera is active
-        let frame_idx = player.current_frame(project);
         let camera_matrices = get_camera_matrices(project, comp_uuid, frame_idx, ui.clip_rect());
 
         // Preserve the pre-migration behavior: always expose the full 3D handle
@@ -142,16 +154,45 @@ impl GizmoState {
             ..Default::default()
         });
 
+        let key_modifier = ui.input(|i| i.modifiers.alt);
+        let hint = self.dragging.then_some(if key_modifier {
+            "◆ key on release"
+        } else {
+            "Alt: key on release"
+        });
+        render_readout(ui, view, proj, &layer_data, hint);
+
         // Interact
         if let Some((_result, new_transforms)) = self.gizmo.interact(ui, &transforms) {
+            self.dragging = true;
             if let Some(event) =
-                self.build_transform_event(tool, comp_uuid, &layer_data, &new_transforms)
+                self.build_transform_event(tool, comp_uuid, frame_idx, &layer_data, &new_transforms)
             {
                 return (true, vec![Box::new(event)]);
             }
             return (true, Vec::new());
         }
 
+        // Drag released: Alt records the edited channel as a keyframe at this frame
+        let released = std::mem::take(&mut self.dragging);
+        if released && key_modifier {
+            let channel = match tool {
+                ToolMode::Move => A_POSITION,
+                ToolMode::Rotate => A_ROTATION,
+                ToolMode::Scale => A_SCALE,
+                ToolMode::Select | ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask => {
+                    return (false, Vec::new());
+                }
+            };
+            let event = SetLayerTransformsEvent {
+                comp_uuid,
+                frame: frame_idx,
+                updates: layer_data,
+                record_keys: vec![channel.to_string()],
+            };
+            return (false, vec![Box::new(event)]);
+        }
+
         (false, Vec::new())
     }
 
@@ -161,12 +202,15 @@ impl GizmoState {
         project: &Project,
         comp_uuid: Uuid,
         selected: &[Uuid],
+        frame_idx: i32,
     ) -> (Vec<Transform>, Vec<(Uuid, [f32; 3], [f32; 3], [f32; 3])>) {
         let mut transforms = Vec::new();
         let mut layer_data = Vec::new();
 
         for &layer_uuid in selected {
-            if let Some((pos, rot, scale)) = get_layer_transform(project, comp_uuid, layer_uuid) {
+            if let Some((pos, rot, scale)) =
+                get_layer_transform(project, comp_uuid, layer_uuid, frame_idx)
+            {
                 transforms.push(layer_to_gizmo_transform(tool, pos, rot, scale));
                 layer_data.push((layer_uuid, pos, rot, scale));
             }
@@ -179,6 +223,7 @@ impl GizmoState {
         &self,
         tool: ToolMode,
         comp_uuid: Uuid,
+        frame_idx: i32,
         layer_data: &[(Uuid, [f32; 3], [f32; 3], [f32; 3])],
         new_transforms: &[Transform],
     ) -> Option<SetLayerTransformsEvent> {
@@ -196,7 +241,9 @@ impl GizmoState {
                 ToolMode::Move => (gizmo_pos, *old_rot, *old_scale),
                 ToolMode::Rotate => (*old_pos, gizmo_rot, *old_scale),
                 ToolMode::Scale => (*old_pos, *old_rot, gizmo_scale),
-                ToolMode::Select => (*old_pos, *old_rot, *old_scale),
+                ToolMode::Select | ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask => {
+                    (*old_pos, *old_rot, *old_scale)
+                }
             };
 
             // Avoid emitting redundant updates when values haven't changed meaningfully.
@@ -214,7 +261,12 @@ impl GizmoState {
             return None;
         }
 
-        Some(SetLayerTransformsEvent { comp_uuid, updates })
+        Some(SetLayerTransformsEvent {
+            comp_uuid,
+            frame: frame_idx,
+            updates,
+            record_keys: Vec::new(),
+        })
     }
 }
 
@@ -224,13 +276,13 @@ impl GizmoState {
 
 /// Map playa's `ToolMode` to the gizmo's exclusive `GizmoTool`.
 ///
-/// Only the transform tools manipulate layers; `Select` shows no gizmo (returns
+/// Only the transform tools manipulate layers; `Select` / `Roi` show no gizmo (returns
 /// `None`, so `render` bails before drawing anything). The narrowing of the
 /// handle set *within* a tool (which axes/planes/rings) is left to the gizmo's
 /// `GizmoSpace` curation (see `render`) and the default `GizmoFeatures::all()`.
 fn tool_to_gizmo_tool(tool: ToolMode) -> Option<GizmoTool> {
     match tool {
-        ToolMode::Select => None,
+        ToolMode::Select | ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask => None,
         ToolMode::Move => Some(GizmoTool::Move),
         ToolMode::Rotate => Some(GizmoTool::Rotate),
         ToolMode::Scale => Some(GizmoTool::Scale),
@@ -326,7 +378,8 @@ fn get_camera_matrices(
 /// # 2D Mode (no camera)
 ///
 /// Without camera, layer positions are already in frame space (centered, Y-up).
-/// We use simple orthographic projection with zoom/pan in the view matrix.
+/// We use simple orthographic projection with zoom/pan (and the display
+/// orientation, see `ViewportState::display_affine`) in the view matrix.
 ///
 /// # Aspect Ratio (IMPORTANT)
 ///
@@ -361,9 +414,9 @@ fn build_gizmo_matrices(
         let view_f64 = DMat4::from_cols_array(&cam_view.to_cols_array().map(|v| v as f64));
         let proj_f64 = DMat4::from_cols_array(&cam_proj.to_cols_array().map(|v| v as f64));
 
-        // Camera NDC → screen NDC chain (zoom + pan, comp/viewport
-        // remap). Single source of truth lives in
-        // `playa_coord::screen_ndc_from_frame_ndc` — gizmo and any
+        // Camera NDC → screen NDC chain (display orientation, zoom + pan,
+        // comp/viewport remap). Single source of truth lives in
+        // `playa_coord::screen_ndc_from_frame_ndc_display` — gizmo and any
         // future viewport overlay share the algebra. f32 helper cast
         // up to DMat4 here for gizmo's f64 precision pipeline.
         let comp_size = (
@@ -372,9 +425,10 @@ fn build_gizmo_matrices(
         );
         let vp_size_f32 = glam::Vec2::new(clip_rect.width(), clip_rect.height());
         let pan_f32 = glam::Vec2::new(viewport_state.pan.x, viewport_state.pan.y);
-        let viewport_transform_f32 = space::screen_ndc_from_frame_ndc(
+        let viewport_transform_f32 = space::screen_ndc_from_frame_ndc_display(
             viewport_state.zoom,
             pan_f32,
+            viewport_state.display_affine(),
             comp_size,
             vp_size_f32,
         );
@@ -386,8 +440,8 @@ fn build_gizmo_matrices(
 
         (view_f64.as_mat4(), final_proj.as_mat4())
     } else {
-        // 2D mode: simple ortho with zoom/pan
-        let view = DMat4::from_scale_rotation_translation(
+        // 2D mode: simple ortho with zoom/pan over the display orientation
+        let zoom_pan = DMat4::from_scale_rotation_translation(
             DVec3::splat(viewport_state.zoom as f64),
             glam::DQuat::IDENTITY,
             DVec3::new(
@@ -396,6 +450,8 @@ fn build_gizmo_matrices(
                 0.0,
             ),
         );
+        let display = space::affine2_to_mat4(viewport_state.display_affine());
+        let view = zoom_pan * DMat4::from_cols_array(&display.to_cols_array().map(f64::from));
 
         // Projection: orthographic
         let w = clip_rect.width() as f64;
@@ -448,7 +504,12 @@ fn layer_to_gizmo_transform(
 
     let scale_vec = match tool {
         ToolMode::Scale => DVec3::new(scale[0] as f64, scale[1] as f64, scale[2] as f64),
-        ToolMode::Move | ToolMode::Rotate | ToolMode::Select => DVec3::splat(1.0),
+        ToolMode::Move
+        | ToolMode::Rotate
+        | ToolMode::Select
+        | ToolMode::Roi
+        | ToolMode::Annotate
+        | ToolMode::Mask => DVec3::splat(1.0),
     };
 
     Transform::from_scale_rotation_translation(
@@ -486,23 +547,75 @@ fn gizmo_to_layer_transform(t: &Transform) -> ([f32; 3], [f32; 3], [f32; 3]) {
     )
 }
 
+/// Layer transform evaluated at `frame_idx` (keyframes win over static attrs)
 fn get_layer_transform(
     project: &Project,
     comp_uuid: Uuid,
     layer_uuid: Uuid,
+    frame_idx: i32,
 ) -> Option<([f32; 3], [f32; 3], [f32; 3])> {
     project
         .with_comp(comp_uuid, |comp| {
-            comp.get_layer(layer_uuid).map(|layer| {
-                let pos = layer.attrs.get_vec3(A_POSITION).unwrap_or([0.0, 0.0, 0.0]);
-                let rot = layer.attrs.get_vec3(A_ROTATION).unwrap_or([0.0, 0.0, 0.0]);
-                let scale = layer.attrs.get_vec3(A_SCALE).unwrap_or([1.0, 1.0, 1.0]);
-                (pos, rot, scale)
-            })
+            comp.get_layer(layer_uuid)
+                .map(|layer| layer.transform_at(frame_idx))
         })
         .flatten()
 }
 
+// ============================================================================
+// Numeric readout
+// ============================================================================
+
+/// Draw position/rotation/scale of the first selected layer next to its
+/// gizmo origin (projected with the same matrices the gizmo uses).
+fn render_readout(
+    ui: &egui::Ui,
+    view: glam::Mat4,
+    proj: glam::Mat4,
+    layer_data: &[(Uuid, [f32; 3], [f32; 3], [f32; 3])],
+    hint: Option<&str>,
+) {
+    let Some((_, pos, rot, scale)) = layer_data.first() else {
+        return;
+    };
+    let clip = proj * view * glam::Vec4::new(pos[0], pos[1], pos[2], 1.0);
+    if clip.w.abs() < f32::EPSILON {
+        return;
+    }
+    let rect = ui.clip_rect();
+    let anchor = egui::pos2(
+        rect.center().x + clip.x / clip.w * rect.width() * 0.5,
+        rect.center().y - clip.y / clip.w * rect.height() * 0.5,
+    );
+    if !rect.contains(anchor) {
+        return;
+    }
+
+    let mut text = format!(
+        "P {:.1}, {:.1}, {:.1}\nR {:.1}°, {:.1}°, {:.1}°\nS {:.2}, {:.2}, {:.2}",
+        pos[0], pos[1], pos[2], rot[0], rot[1], rot[2], scale[0], scale[1], scale[2]
+    );
+    if layer_data.len() > 1 {
+        text.push_str(&format!("\n({} layers)", layer_data.len()));
+    }
+    if let Some(hint) = hint {
+        text.push('\n');
+        text.push_str(hint);
+    }
+
+    let painter = ui.painter();
+    let galley = painter.layout_no_wrap(
+        text,
+        egui::FontId::monospace(11.0),
+        egui::Color32::from_gray(230),
+    );
+    // Offset past the handles so the text never sits under the pointer
+    let origin = anchor + egui::vec2(24.0, 24.0);
+    let bg = egui::Rect::from_min_size(origin, galley.size()).expand(4.0);
+    painter.rect_filled(bg, 3.0, egui::Color32::from_black_alpha(170));
+    painter.galley(origin, galley, egui::Color32::WHITE);
+}
+
 #[inline]
 fn approx_vec3_equal(a: [f32; 3], b: [f32; 3]) -> bool {
     // Keep epsilon conservative: gizmo drags are continuous; this just avoids
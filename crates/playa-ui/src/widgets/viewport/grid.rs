@@ -0,0 +1,120 @@
+//! Pixel grid and cursor pixel highlight for close-up inspection.
+//!
+//! Toggled from the Guides menu ([`ViewportGuides::pixel_grid`] /
+//! [`ViewportGuides::pixel_highlight`]); spacing, color and the zoom threshold
+//! come from prefs as a [`GridStyle`]. Lines are image-pixel boundaries mapped
+//! through [`ViewportState::image_to_screen`] (so pixel aspect and
+//! rotate/flip are respected) and snapped to physical pixels to stay crisp.
+//! Like the guides, this is screen-space display only.
+//!
+//! [`ViewportGuides::pixel_grid`]: super::ViewportGuides::pixel_grid
+//! [`ViewportGuides::pixel_highlight`]: super::ViewportGuides::pixel_highlight
+
+use eframe::egui;
+
+use super::ViewportState;
+
+/// Cursor pixel outline
+const HIGHLIGHT_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 210, 60);
+/// Smallest on-screen pixel (points) worth outlining under the cursor
+const HIGHLIGHT_MIN_PX: f32 = 4.0;
+/// Safety cap on grid lines per axis (a low threshold in prefs on a huge image)
+const MAX_LINES: usize = 4096;
+
+/// Pixel grid appearance (from prefs)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GridStyle {
+    /// Image pixels between grid lines
+    pub spacing: u32,
+    /// sRGB, unmultiplied alpha
+    pub color: [u8; 4],
+    /// Zoom at which a 1-px grid appears (8.0 = 800%); coarser spacing
+    /// appears once one cell spans the same number of screen points
+    pub min_zoom: f32,
+}
+
+impl Default for GridStyle {
+    fn default() -> Self {
+        Self {
+            spacing: 1,
+            color: [128, 128, 128, 160],
+            min_zoom: 8.0,
+        }
+    }
+}
+
+/// Draw the enabled grid / pixel highlight over the image, clipped to the panel.
+pub(super) fn draw(
+    ui: &egui::Ui,
+    panel_rect: egui::Rect,
+    state: &ViewportState,
+    style: &GridStyle,
+) {
+    let guides = &state.guides;
+    if !(guides.pixel_grid || guides.pixel_highlight) || state.image_size.min_elem() < 1.0 {
+        return;
+    }
+    let painter = ui.painter().with_clip_rect(panel_rect);
+    let ppp = ui.ctx().pixels_per_point();
+    let snap = |v: f32| ((v * ppp).floor() + 0.5) / ppp;
+    let to_screen = |image: egui::Vec2| {
+        let p = panel_rect.min + state.image_to_screen(image);
+        egui::pos2(snap(p.x), snap(p.y))
+    };
+    // Screen size of one image pixel along its shorter side
+    let pixel_px = state.zoom * state.pixel_aspect.min(1.0);
+
+    let step = style.spacing.max(1) as f32;
+    if guides.pixel_grid && pixel_px * step >= style.min_zoom {
+        let [r, g, b, a] = style.color;
+        let stroke = egui::Stroke::new(1.0, egui::Color32::from_rgba_unmultiplied(r, g, b, a));
+        let visible = state.visible_image_rect();
+        let lines = |from: f32, to: f32| {
+            let first = (from / step).ceil() as usize;
+            let last = (to / step).floor() as usize;
+            (first..=last).take(MAX_LINES).map(move |i| i as f32 * step)
+        };
+        for x in lines(visible.min.x, visible.max.x) {
+            painter.line_segment(
+                [
+                    to_screen(egui::vec2(x, visible.min.y)),
+                    to_screen(egui::vec2(x, visible.max.y)),
+                ],
+                stroke,
+            );
+        }
+        for y in lines(visible.min.y, visible.max.y) {
+            painter.line_segment(
+                [
+                    to_screen(egui::vec2(visible.min.x, y)),
+                    to_screen(egui::vec2(visible.max.x, y)),
+                ],
+                stroke,
+            );
+        }
+    }
+
+    if guides.pixel_highlight
+        && pixel_px >= HIGHLIGHT_MIN_PX
+        && let Some(hover) = ui.ctx().pointer_hover_pos()
+        && panel_rect.contains(hover)
+        && let Some(image) = state.screen_to_image(hover - panel_rect.min)
+    {
+        let pixel = image.floor().min(state.image_size - egui::Vec2::splat(1.0));
+        let rect =
+            egui::Rect::from_two_pos(to_screen(pixel), to_screen(pixel + egui::Vec2::splat(1.0)));
+        painter.rect_stroke(
+            rect,
+            0.0,
+            egui::Stroke::new(1.5, HIGHLIGHT_COLOR),
+            egui::epaint::StrokeKind::Middle,
+        );
+        painter.text(
+            rect.right_top() + egui::vec2(4.0, 0.0),
+            egui::Align2::LEFT_BOTTOM,
+            format!("{}, {}", pixel.x as u32, pixel.y as u32),
+            egui::FontId::monospace(11.0),
+            HIGHLIGHT_COLOR,
+        );
+    }
+}
@@ -0,0 +1,292 @@
+//! Framing guides overlay - action/title safe areas, rule of thirds, center
+//! cross, aspect mask, EXR data window. Also holds the pixel grid toggles
+//! drawn by `grid.rs`.
+//!
+//! Pure display overlay: drawn in screen space from the displayed image rect
+//! (see [`ViewportState::get_image_screen_bounds`](super::ViewportState::get_image_screen_bounds)),
+//! never baked into frames. Persisted as part of [`ViewportState`](super::ViewportState).
+
+use eframe::egui;
+use std::path::{Path, PathBuf};
+
+use super::ViewportState;
+
+/// Action-safe area as fraction of image size
+const ACTION_SAFE: f32 = 0.9;
+/// Title-safe area as fraction of image size
+const TITLE_SAFE: f32 = 0.8;
+
+/// Guide line color (white, semi-transparent)
+const GUIDE_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(180, 180, 180, 180);
+/// EXR data window outline (orange)
+const DATA_WINDOW_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 150, 40);
+/// Darkening applied outside the aspect mask
+const MASK_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(0, 0, 0, 200);
+
+/// Common delivery aspect ratios offered for the mask
+pub const MASK_ASPECTS: &[f32] = &[1.33, 1.78, 1.85, 2.0, 2.39];
+
+/// Viewport framing guides (toggled per-guide, cycled via hotkey)
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ViewportGuides {
+    /// 90% action-safe rectangle
+    pub action_safe: bool,
+    /// 80% title-safe rectangle
+    pub title_safe: bool,
+    /// Center cross
+    pub center: bool,
+    /// Rule-of-thirds lines
+    pub thirds: bool,
+    /// Darken outside this aspect (width / height), e.g. 2.39 letterbox
+    pub aspect_mask: Option<f32>,
+    /// Aspect used when the mask is toggled on
+    pub mask_aspect: f32,
+    /// Pixel grid at high zoom (spacing, color and threshold from prefs)
+    pub pixel_grid: bool,
+    /// Outline the image pixel under the cursor
+    pub pixel_highlight: bool,
+    /// Outline the EXR data window of the displayed source
+    pub data_window: bool,
+}
+
+impl Default for ViewportGuides {
+    fn default() -> Self {
+        Self {
+            action_safe: false,
+            title_safe: false,
+            center: false,
+            thirds: false,
+            aspect_mask: None,
+            mask_aspect: 2.39,
+            pixel_grid: false,
+            pixel_highlight: false,
+            data_window: false,
+        }
+    }
+}
+
+impl ViewportGuides {
+    /// True if any framing guide is enabled (the pixel grid draws separately)
+    pub fn any(&self) -> bool {
+        self.cycled() || self.thirds
+    }
+
+    /// True if any guide stepped through by [`Self::cycle`] is enabled
+    fn cycled(&self) -> bool {
+        self.action_safe || self.title_safe || self.center || self.aspect_mask.is_some()
+    }
+
+    /// Cycle presets: off -> safe areas -> safe + center -> safe + center + mask -> off
+    pub fn cycle(&mut self) {
+        let safe = self.action_safe && self.title_safe;
+        let step = match (safe, self.center, self.aspect_mask.is_some()) {
+            (true, false, false) => 2,
+            (true, true, false) => 3,
+            _ if self.cycled() => 0,
+            _ => 1,
+        };
+        self.action_safe = step >= 1;
+        self.title_safe = step >= 1;
+        self.center = step >= 2;
+        self.aspect_mask = (step >= 3).then_some(self.mask_aspect);
+    }
+
+    /// Draw enabled guides over `image_rect` (screen space), clipped to `clip`.
+    pub fn draw(&self, ui: &egui::Ui, image_rect: egui::Rect, clip: egui::Rect) {
+        if !self.any() || image_rect.width() <= 0.0 || image_rect.height() <= 0.0 {
+            return;
+        }
+        let painter = ui.painter().with_clip_rect(clip);
+        let stroke = egui::Stroke::new(1.0, GUIDE_COLOR);
+
+        if let Some(aspect) = self.aspect_mask.filter(|a| *a > 0.0) {
+            let visible = fit_aspect(image_rect, aspect);
+            // Darken the bars outside the visible region (top/bottom or left/right)
+            for bar in [
+                egui::Rect::from_min_max(
+                    image_rect.min,
+                    egui::pos2(image_rect.max.x, visible.min.y),
+                ),
+                egui::Rect::from_min_max(
+                    egui::pos2(image_rect.min.x, visible.max.y),
+                    image_rect.max,
+                ),
+                egui::Rect::from_min_max(
+                    egui::pos2(image_rect.min.x, visible.min.y),
+                    egui::pos2(visible.min.x, visible.max.y),
+                ),
+                egui::Rect::from_min_max(
+                    egui::pos2(visible.max.x, visible.min.y),
+                    egui::pos2(image_rect.max.x, visible.max.y),
+                ),
+            ] {
+                if bar.width() > 0.0 && bar.height() > 0.0 {
+                    painter.rect_filled(bar, 0.0, MASK_COLOR);
+                }
+            }
+            painter.text(
+                visible.left_top() + egui::vec2(4.0, 4.0),
+                egui::Align2::LEFT_TOP,
+                format!("{:.2}:1", aspect),
+                egui::FontId::proportional(11.0),
+                GUIDE_COLOR,
+            );
+        }
+
+        if self.action_safe {
+            painter.rect_stroke(
+                scaled(image_rect, ACTION_SAFE),
+                0.0,
+                stroke,
+                egui::epaint::StrokeKind::Middle,
+            );
+        }
+        if self.title_safe {
+            painter.rect_stroke(
+                scaled(image_rect, TITLE_SAFE),
+                0.0,
+                stroke,
+                egui::epaint::StrokeKind::Middle,
+            );
+        }
+        if self.thirds {
+            for t in [1.0 / 3.0, 2.0 / 3.0] {
+                let x = image_rect.min.x + image_rect.width() * t;
+                let y = image_rect.min.y + image_rect.height() * t;
+                painter.line_segment(
+                    [
+                        egui::pos2(x, image_rect.min.y),
+                        egui::pos2(x, image_rect.max.y),
+                    ],
+                    stroke,
+                );
+                painter.line_segment(
+                    [
+                        egui::pos2(image_rect.min.x, y),
+                        egui::pos2(image_rect.max.x, y),
+                    ],
+                    stroke,
+                );
+            }
+        }
+        if self.center {
+            let c = image_rect.center();
+            let arm = image_rect.width().min(image_rect.height()) * 0.03;
+            painter.line_segment([c - egui::vec2(arm, 0.0), c + egui::vec2(arm, 0.0)], stroke);
+            painter.line_segment([c - egui::vec2(0.0, arm), c + egui::vec2(0.0, arm)], stroke);
+        }
+    }
+
+    /// Guides menu: per-guide toggles and mask aspect
+    pub fn menu_ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.action_safe, "Action safe (90%)");
+        ui.checkbox(&mut self.title_safe, "Title safe (80%)");
+        ui.checkbox(&mut self.thirds, "Rule of thirds");
+        ui.checkbox(&mut self.center, "Center cross");
+        ui.separator();
+        let mut masked = self.aspect_mask.is_some();
+        if ui.checkbox(&mut masked, "Aspect mask").changed() {
+            self.aspect_mask = masked.then_some(self.mask_aspect);
+        }
+        ui.add_enabled_ui(masked, |ui| {
+            ui.horizontal(|ui| {
+                for &a in MASK_ASPECTS {
+                    if ui
+                        .selectable_label((self.mask_aspect - a).abs() < 1e-3, format!("{a:.2}"))
+                        .clicked()
+                    {
+                        self.mask_aspect = a;
+                    }
+                }
+            });
+            ui.add(
+                egui::DragValue::new(&mut self.mask_aspect)
+                    .range(0.5..=4.0)
+                    .speed(0.01)
+                    .suffix(":1"),
+            );
+        });
+        if masked {
+            self.aspect_mask = Some(self.mask_aspect);
+        }
+        ui.separator();
+        ui.checkbox(&mut self.pixel_grid, "Pixel grid")
+            .on_hover_text("Shown when zoomed in; spacing and color in Preferences");
+        ui.checkbox(&mut self.pixel_highlight, "Highlight pixel under cursor");
+        ui.separator();
+        ui.checkbox(&mut self.data_window, "EXR data window")
+            .on_hover_text("Pixel bounds of the topmost EXR source (overscan / partial renders)");
+    }
+}
+
+/// Outline the data window of the EXR at `path` (topmost source of the
+/// displayed frame), relative to its display window. Headers are read once
+/// per path and cached in `viewport_state`.
+pub(super) fn draw_data_window(
+    ui: &egui::Ui,
+    panel_rect: egui::Rect,
+    viewport_state: &mut ViewportState,
+    path: Option<&Path>,
+) {
+    let Some(path) = path.filter(|p| {
+        p.extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("exr"))
+    }) else {
+        return;
+    };
+    if viewport_state
+        .data_window
+        .as_ref()
+        .is_none_or(|(cached, _)| cached != path)
+    {
+        let windows = playa_io::exr_header::windows(path).ok();
+        viewport_state.data_window = Some((PathBuf::from(path), windows));
+    }
+    let Some((_, Some(windows))) = viewport_state.data_window else {
+        return;
+    };
+    if windows.data == windows.display {
+        return;
+    }
+
+    let (x, y) = windows.data_offset();
+    let (w, h) = windows.data_size();
+    let to_screen = |x: f32, y: f32| {
+        (panel_rect.min.to_vec2() + viewport_state.image_to_screen(egui::vec2(x, y))).to_pos2()
+    };
+    let rect = egui::Rect::from_two_pos(
+        to_screen(x as f32, y as f32),
+        to_screen((x + w as i64) as f32, (y + h as i64) as f32),
+    );
+    let painter = ui.painter().with_clip_rect(panel_rect);
+    painter.rect_stroke(
+        rect,
+        0.0,
+        egui::Stroke::new(1.0, DATA_WINDOW_COLOR),
+        egui::epaint::StrokeKind::Middle,
+    );
+    painter.text(
+        rect.left_top() + egui::vec2(4.0, 4.0),
+        egui::Align2::LEFT_TOP,
+        format!("data {}x{} @ {},{}", w, h, x, y),
+        egui::FontId::proportional(11.0),
+        DATA_WINDOW_COLOR,
+    );
+}
+
+/// Rect shrunk around its center to `fraction` of its size
+fn scaled(rect: egui::Rect, fraction: f32) -> egui::Rect {
+    egui::Rect::from_center_size(rect.center(), rect.size() * fraction)
+}
+
+/// Largest centered rect of `aspect` (w/h) inside `rect`
+fn fit_aspect(rect: egui::Rect, aspect: f32) -> egui::Rect {
+    let (w, h) = (rect.width(), rect.height());
+    let size = if w / h > aspect {
+        egui::vec2(h * aspect, h)
+    } else {
+        egui::vec2(w, w / aspect)
+    };
+    egui::Rect::from_center_size(rect.center(), size)
+}
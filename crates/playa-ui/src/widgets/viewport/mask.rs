@@ -0,0 +1,282 @@
+//! Mask tool - draw and edit the selected layer's rectangle / ellipse mask.
+//!
+//! The mask lives on the layer as attrs ([`LayerMask`], normalized layer
+//! source coords) so it follows the layer transform; this module maps those
+//! coords through the layer model matrix to the viewport. LMB drag draws a new
+//! rectangle (Shift: ellipse), dragging a corner handle reshapes the current
+//! mask and a plain click removes it. The edit is sent once on release, so a
+//! drag is a single undo step. Feather and invert are set in the Attribute
+//! Editor; the feather falloff is drawn as a dimmer inner outline.
+//!
+//! Tilted layers (X/Y rotation) are skipped: their source plane doesn't map
+//! to the viewport with a flat matrix.
+
+use eframe::egui;
+use glam::{Mat4, Vec2, Vec3};
+use playa_engine::core::event_bus::BoxedEvent;
+use playa_engine::core::player::Player;
+use playa_engine::entities::comp_events::SetLayerAttrsEvent;
+use playa_engine::entities::keys::{A_MASK_RECT, A_MASK_SHAPE, A_PIVOT};
+use playa_engine::entities::transform::build_model_matrix;
+use playa_engine::entities::{AttrValue, LayerMask, MaskShape, Project, space};
+use uuid::Uuid;
+
+use super::ViewportState;
+use super::tool::ToolMode;
+
+/// Mask outline and handles
+const MASK_COLOR: egui::Color32 = egui::Color32::from_rgb(255, 200, 60);
+/// Feather falloff outline
+const FEATHER_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(128, 100, 30, 128);
+/// Screen-space size of the corner handles
+const HANDLE_SIZE: f32 = 7.0;
+/// Segments used to draw an ellipse outline
+const ELLIPSE_SEGMENTS: usize = 64;
+
+/// Mask drag in progress: fixed corner (normalized source coords) and shape
+#[derive(Clone, Copy, Debug)]
+pub struct MaskDrag {
+    layer: Uuid,
+    anchor: Vec2,
+    shape: MaskShape,
+}
+
+/// The edited layer's placement and current mask
+struct MaskTarget {
+    comp: Uuid,
+    layer: Uuid,
+    /// Object -> frame space
+    model: Mat4,
+    /// Source size in pixels (object space spans +-size/2)
+    size: Vec2,
+    mask: Option<LayerMask>,
+}
+
+impl MaskTarget {
+    /// First selected layer of the active comp, when it is flat
+    fn find(project: &Project, player: &Player) -> Option<Self> {
+        let comp = player.active_comp()?;
+        let frame = player.current_frame(project);
+        project
+            .with_comp(comp, |c| {
+                let layer = c.get_layer(*c.layer_selection.first()?)?;
+                let (pos, rot, scl) = layer.transform_at(frame);
+                if rot[0] != 0.0 || rot[1] != 0.0 {
+                    return None;
+                }
+                let pvt = layer.vec3_at(A_PIVOT, frame, [0.0, 0.0, 0.0]);
+                let rot_rad = rot.map(f32::to_radians);
+                let w = layer.attrs.get_u32("width").unwrap_or(100) as f32;
+                let h = layer.attrs.get_u32("height").unwrap_or(100) as f32;
+                Some(Self {
+                    comp,
+                    layer: layer.uuid(),
+                    model: build_model_matrix(pos, rot_rad, scl, pvt),
+                    size: Vec2::new(w, h),
+                    mask: layer.mask_at(frame),
+                })
+            })
+            .flatten()
+    }
+
+    /// Normalized source coords -> image pixels
+    fn to_image(&self, uv: Vec2, state: &ViewportState) -> egui::Vec2 {
+        let obj = Vec3::new((uv.x - 0.5) * self.size.x, (0.5 - uv.y) * self.size.y, 0.0);
+        let frame = self.model.transform_point3(obj);
+        let p = space::frame_to_image(Vec2::new(frame.x, frame.y), image_size(state));
+        egui::vec2(p.x, p.y)
+    }
+
+    /// Image pixels -> normalized source coords
+    fn to_uv(&self, image: egui::Vec2, state: &ViewportState) -> Vec2 {
+        let frame = space::image_to_frame(Vec2::new(image.x, image.y), image_size(state));
+        let obj = self
+            .model
+            .inverse()
+            .transform_point3(Vec3::new(frame.x, frame.y, 0.0));
+        Vec2::new(obj.x / self.size.x + 0.5, 0.5 - obj.y / self.size.y)
+    }
+}
+
+fn image_size(state: &ViewportState) -> (usize, usize) {
+    (state.image_size.x as usize, state.image_size.y as usize)
+}
+
+/// Corners of a normalized rect, clockwise from top-left
+fn corners(rect: [f32; 4]) -> [Vec2; 4] {
+    let [x0, y0, x1, y1] = rect;
+    [
+        Vec2::new(x0, y0),
+        Vec2::new(x1, y0),
+        Vec2::new(x1, y1),
+        Vec2::new(x0, y1),
+    ]
+}
+
+/// Outline of `shape` over `rect` (normalized coords)
+fn outline(shape: MaskShape, rect: [f32; 4]) -> Vec<Vec2> {
+    match shape {
+        MaskShape::Rectangle => corners(rect).to_vec(),
+        MaskShape::Ellipse => {
+            let center = Vec2::new(rect[0] + rect[2], rect[1] + rect[3]) * 0.5;
+            let half = Vec2::new(rect[2] - rect[0], rect[3] - rect[1]) * 0.5;
+            (0..ELLIPSE_SEGMENTS)
+                .map(|i| {
+                    let a = i as f32 / ELLIPSE_SEGMENTS as f32 * std::f32::consts::TAU;
+                    center + half * Vec2::new(a.cos(), a.sin())
+                })
+                .collect()
+        }
+    }
+}
+
+/// LMB in Mask mode: drag draws / reshapes, release commits, click removes.
+pub(super) fn drag_event(
+    response: &egui::Response,
+    panel_rect: egui::Rect,
+    viewport_state: &mut ViewportState,
+    player: &Player,
+    project: &Project,
+) -> Option<BoxedEvent> {
+    if ToolMode::from_str(&project.tool()) != ToolMode::Mask {
+        viewport_state.mask_drag = None;
+        return None;
+    }
+    let target = MaskTarget::find(project, player)?;
+    let pointer = response
+        .interact_pointer_pos()
+        .or_else(|| response.ctx.pointer_latest_pos())?;
+    let image = viewport_state.screen_to_image_unbounded(pointer - panel_rect.min);
+    let uv = target.to_uv(image, viewport_state);
+
+    if response.drag_started_by(egui::PointerButton::Primary) {
+        // Grabbing a handle keeps the opposite corner and the shape
+        let to_screen = |p: Vec2| {
+            panel_rect.min + viewport_state.image_to_screen(target.to_image(p, viewport_state))
+        };
+        let grabbed = target.mask.and_then(|mask| {
+            let pts = corners(mask.rect);
+            (0..4)
+                .find(|&i| to_screen(pts[i]).distance(pointer) <= HANDLE_SIZE)
+                .map(|i| (pts[(i + 2) % 4], mask.shape))
+        });
+        let shift = response.ctx.input(|i| i.modifiers.shift);
+        let (anchor, shape) = grabbed.unwrap_or(if shift {
+            (uv, MaskShape::Ellipse)
+        } else {
+            (uv, MaskShape::Rectangle)
+        });
+        viewport_state.mask_drag = Some(MaskDrag {
+            layer: target.layer,
+            anchor,
+            shape,
+        });
+    }
+    if response.drag_stopped_by(egui::PointerButton::Primary)
+        && let Some(drag) = viewport_state.mask_drag.take()
+    {
+        let (min, max) = (drag.anchor.min(uv), drag.anchor.max(uv));
+        // Ignore slivers under a source pixel
+        let px = (max - min) * target.size;
+        if drag.layer != target.layer || px.x < 1.0 || px.y < 1.0 {
+            return None;
+        }
+        return Some(set_mask(
+            &target,
+            drag.shape.as_str(),
+            Some([min.x, min.y, max.x, max.y]),
+        ));
+    }
+    if response.clicked_by(egui::PointerButton::Primary) && target.mask.is_some() {
+        return Some(set_mask(&target, "none", None));
+    }
+    None
+}
+
+/// Layer attr edit for the mask shape (and rect when given)
+fn set_mask(target: &MaskTarget, shape: &str, rect: Option<[f32; 4]>) -> BoxedEvent {
+    let mut attrs = vec![(A_MASK_SHAPE.to_string(), serde_json::json!(shape))];
+    if let Some(rect) = rect
+        && let Ok(value) = serde_json::to_value(AttrValue::Vec4(rect))
+    {
+        attrs.push((A_MASK_RECT.to_string(), value));
+    }
+    Box::new(SetLayerAttrsEvent {
+        comp_uuid: target.comp,
+        layer_uuids: vec![target.layer],
+        attrs,
+    })
+}
+
+/// Draw the drag preview, or the committed mask with its feather outline and
+/// corner handles.
+pub(super) fn draw(
+    ui: &egui::Ui,
+    panel_rect: egui::Rect,
+    viewport_state: &ViewportState,
+    player: &Player,
+    project: &Project,
+) {
+    if ToolMode::from_str(&project.tool()) != ToolMode::Mask {
+        return;
+    }
+    let Some(target) = MaskTarget::find(project, player) else {
+        return;
+    };
+    let painter = ui.painter().with_clip_rect(panel_rect);
+    let to_screen = |p: Vec2| {
+        panel_rect.min + viewport_state.image_to_screen(target.to_image(p, viewport_state))
+    };
+    let stroke = egui::Stroke::new(1.5, MASK_COLOR);
+    let closed = |points: Vec<Vec2>, stroke: egui::Stroke| {
+        let pts: Vec<egui::Pos2> = points.into_iter().map(to_screen).collect();
+        painter.add(egui::Shape::closed_line(pts, stroke));
+    };
+
+    if let Some(drag) = viewport_state.mask_drag
+        && drag.layer == target.layer
+    {
+        if let Some(pos) = ui.ctx().pointer_latest_pos() {
+            let image = viewport_state.screen_to_image_unbounded(pos - panel_rect.min);
+            let uv = target.to_uv(image, viewport_state);
+            let rect = [drag.anchor.x, drag.anchor.y, uv.x, uv.y];
+            closed(outline(drag.shape, rect), stroke);
+        }
+        return;
+    }
+    let Some(mask) = target.mask else {
+        return;
+    };
+
+    closed(outline(mask.shape, mask.rect), stroke);
+    // Feather: where the falloff reaches full coverage
+    if mask.feather > 0.0 {
+        let inset = Vec2::splat(mask.feather) / target.size;
+        let [x0, y0, x1, y1] = mask.rect;
+        let (min, max) = (
+            Vec2::new(x0.min(x1), y0.min(y1)),
+            Vec2::new(x0.max(x1), y0.max(y1)),
+        );
+        let (min, max) = (min + inset, max - inset);
+        if min.x < max.x && min.y < max.y {
+            closed(
+                outline(mask.shape, [min.x, min.y, max.x, max.y]),
+                egui::Stroke::new(1.0, FEATHER_COLOR),
+            );
+        }
+    }
+    for corner in corners(mask.rect) {
+        let handle =
+            egui::Rect::from_center_size(to_screen(corner), egui::Vec2::splat(HANDLE_SIZE));
+        painter.rect_filled(handle, 0.0, MASK_COLOR);
+    }
+    if mask.invert {
+        painter.text(
+            to_screen(corners(mask.rect)[0]) + egui::vec2(0.0, -4.0),
+            egui::Align2::LEFT_BOTTOM,
+            "inverted",
+            egui::FontId::proportional(11.0),
+            MASK_COLOR,
+        );
+    }
+}
@@ -2,19 +2,33 @@
 //!
 //! Raster is presented through wgpu inside egui’s paint callbacks.
 
+mod annotation;
+pub mod bookmarks;
 mod coords;
+mod delivery;
 pub mod gizmo;
+mod grid;
+mod guides;
+mod mask;
 mod pick;
 mod renderer;
+mod roi;
 pub mod shaders;
 pub mod tool;
 mod viewport;
 pub mod viewport_events;
 mod viewport_ui;
+mod zebra;
 
-pub use renderer::{ViewportPaintCallback, ViewportRenderer};
-pub use renderer::{skip_upload, stage_frame, update_tonemap};
+pub use delivery::DeliveryFrame;
+pub use grid::GridStyle;
+pub use guides::ViewportGuides;
+pub use renderer::TonemapCache;
+pub use renderer::{DEFAULT_VIEWPORT_BACKGROUND, NEUTRAL_GRAY_BACKGROUND};
+pub use renderer::{OnionGhost, StageOptions, ViewportPaintCallback, ViewportRenderer};
+pub use renderer::{cpu_tonemap, paint_background, skip_upload, stage_frame, update_tonemap};
 pub use shaders::Shaders;
-pub use viewport::{ViewportMode, ViewportRenderState, ViewportState};
+pub use viewport::{OnionSkin, ViewportMode, ViewportRenderState, ViewportState};
 pub use viewport_events::ViewportRefreshEvent;
 pub use viewport_ui::render;
+pub use zebra::{Zebra, ZebraPass, ZebraStats, ZebraThresholds};
@@ -93,12 +93,19 @@ pub fn pick_layer_at(
     // Timeline order: layers[0] = top row (foreground), layers[n-1] = bottom row (background).
     // Note: compose_internal uses rev() for bottom-up blending, pick uses forward for top-down hit test.
     debug!("[pick] checking {} layers", comp.layers.len());
+    let has_solo = comp.has_solo();
     for (i, layer) in comp.layers.iter().enumerate() {
         let name = layer.attrs.get_str("name").unwrap_or("?");
 
-        // Skip invisible layers
-        if !layer.is_visible() {
-            debug!("[pick] layer[{}] = {} SKIP (invisible)", i, name);
+        // Skip layers that don't composite (hidden, muted, solo'd out)
+        if !layer.is_active(has_solo) {
+            debug!("[pick] layer[{}] = {} SKIP (inactive)", i, name);
+            continue;
+        }
+
+        // Locked layers can't be grabbed in the viewport either
+        if layer.is_locked() {
+            debug!("[pick] layer[{}] = {} SKIP (locked)", i, name);
             continue;
         }
 
@@ -120,11 +127,9 @@ pub fn pick_layer_at(
 
         debug!("[pick] layer[{}] = {}", i, name);
 
-        // Get layer transform
-        let position = layer.attrs.get_vec3("position").unwrap_or([0.0, 0.0, 0.0]);
-        let rotation_deg = layer.attrs.get_vec3("rotation").unwrap_or([0.0, 0.0, 0.0]);
-        let scale = layer.attrs.get_vec3("scale").unwrap_or([1.0, 1.0, 1.0]);
-        let pivot = layer.attrs.get_vec3("pivot").unwrap_or([0.0, 0.0, 0.0]);
+        // Get layer transform (evaluated at this frame for keyed layers)
+        let (position, rotation_deg, scale) = layer.transform_at(frame_idx);
+        let pivot = layer.vec3_at("pivot", frame_idx, [0.0, 0.0, 0.0]);
 
         // Convert rotation to radians
         let rotation = [
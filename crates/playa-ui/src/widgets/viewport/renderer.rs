@@ -9,11 +9,15 @@
 //! `needs_texture_update`, `destroy`) are called directly on the re-exported
 //! type; only the playa-typed operations below need translation.
 
+use std::sync::Arc;
+
+use eframe::egui;
 use egui_hdr_view::{HdrFormat, HdrView, Mvp, Tonemap};
-use playa_engine::entities::frame::{PixelBuffer, PixelFormat};
+use playa_engine::entities::frame::{PixelBuffer, PixelFormat, PixelRect, TonemapMode};
 
 use super::ViewportRenderState;
 use super::shaders::Shaders;
+use super::zebra::{self, ZebraPass, ZebraStats};
 
 /// GPU image presenter (exposure/gamma/tonemap). Held in `Arc<Mutex<_>>` by the app.
 pub use egui_hdr_view::HdrView as ViewportRenderer;
@@ -38,31 +42,374 @@ pub fn update_tonemap(hdr: &mut HdrView, shaders: &Shaders) {
     };
 }
 
+/// Operator for presets the presenter has no GPU curve for.
+///
+/// These are applied on the CPU while staging (see [`StageOptions::tonemap`])
+/// with the same engine curves the encoder uses, so review matches export.
+/// The view exposure goes in before the curve and display gamma after it;
+/// the presenter gets an 8-bit display-referred frame it shows as-is.
+pub fn cpu_tonemap(shaders: &Shaders) -> Option<TonemapMode> {
+    match shaders.current_shader.as_str() {
+        "tonemap_reinhard_ext" => Some(TonemapMode::ReinhardExtended),
+        "tonemap_hable" => Some(TonemapMode::Hable),
+        "tonemap_clamp" => Some(TonemapMode::Clamp),
+        _ => None,
+    }
+}
+
+/// Default viewport backdrop (sRGB), matching the comp's black base
+pub const DEFAULT_VIEWPORT_BACKGROUND: [u8; 3] = [0, 0, 0];
+/// 18% gray backdrop (sRGB) for judging images against a neutral surround
+pub const NEUTRAL_GRAY_BACKGROUND: [u8; 3] = [118, 118, 118];
+
+/// Fill the viewport backdrop behind the image quad.
+///
+/// Display-only: painted by egui under the raster callback and never
+/// composited into frames, so changing it doesn't touch the frame cache.
+pub fn paint_background(painter: &egui::Painter, rect: egui::Rect, rgb: [u8; 3]) {
+    painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(rgb[0], rgb[1], rgb[2]));
+}
+
+/// Checkerboard square size in image pixels
+const CHECKER_SIZE: usize = 16;
+/// Checkerboard dark / light values (display-referred, 0..1)
+const CHECKER_DARK: f32 = 0.4;
+const CHECKER_LIGHT: f32 = 0.6;
+/// Display gamma assumed by the presenter for HDR frames
+pub(super) const DISPLAY_GAMMA: f32 = 2.2;
+
+/// Neighbor frame overlaid by onion-skinning (display only).
+pub struct OnionGhost {
+    /// Ghost pixels; skipped unless size and format match the staged frame
+    pub buffer: Arc<PixelBuffer>,
+    /// Tint multiplied into the ghost's luminance (display-referred)
+    pub tint: [f32; 3],
+    /// Ghost opacity (0..1)
+    pub opacity: f32,
+}
+
+/// Display-only steps applied to the staged copy of a frame.
+#[derive(Default)]
+pub struct StageOptions<'a> {
+    /// Composite transparent pixels inside this image rect (the visible part
+    /// of the frame) over a checkerboard
+    pub checker: Option<PixelRect>,
+    /// Onion-skin ghosts, drawn over the frame in order
+    pub ghosts: &'a [OnionGhost],
+    /// CPU tonemap for HDR frames (presets without a GPU curve)
+    pub tonemap: Option<TonemapMode>,
+    /// Exposure warning stripes (see [`zebra`])
+    pub zebra: Option<ZebraPass>,
+}
+
+impl StageOptions<'_> {
+    fn is_noop(&self) -> bool {
+        self.checker.is_none()
+            && self.ghosts.is_empty()
+            && self.tonemap.is_none()
+            && self.zebra.is_none()
+    }
+}
+
+/// CPU-tonemapped display copy of the last staged frame, so re-staging the
+/// same frame (zebra crawl, onion or checker toggles) skips the curve pass.
+#[derive(Clone, Default)]
+pub struct TonemapCache {
+    /// Source buffer, operator and exposure bits the copy was made from
+    key: Option<(Arc<PixelBuffer>, TonemapMode, u32)>,
+    display: Vec<u8>,
+}
+
+impl TonemapCache {
+    /// Display copy of `buffer`, recomputed only when the buffer, operator
+    /// or exposure changed
+    fn display(&mut self, buffer: &Arc<PixelBuffer>, mode: TonemapMode, exposure: f32) -> &[u8] {
+        let hit = self.key.as_ref().is_some_and(|(b, m, e)| {
+            Arc::ptr_eq(b, buffer) && *m == mode && *e == exposure.to_bits()
+        });
+        if !hit {
+            self.display = tonemap_to_display(buffer, mode, exposure);
+            self.key = Some((Arc::clone(buffer), mode, exposure.to_bits()));
+        }
+        &self.display
+    }
+}
+
+/// Exposure, then the curve, then display gamma: straight-alpha HDR RGBA to
+/// display-referred 8-bit, as the presenter's HDR path would show it
+fn tonemap_to_display(buffer: &PixelBuffer, mode: TonemapMode, exposure: f32) -> Vec<u8> {
+    let encode = |(i, v): (usize, f32)| {
+        let v = if i % 4 == 3 {
+            v
+        } else {
+            mode.apply(v * exposure).max(0.0).powf(1.0 / DISPLAY_GAMMA)
+        };
+        (v * 255.0).round().clamp(0.0, 255.0) as u8
+    };
+    match buffer {
+        PixelBuffer::U8(data) => data.clone(),
+        PixelBuffer::F16(data) => data
+            .iter()
+            .map(|v| v.to_f32())
+            .enumerate()
+            .map(encode)
+            .collect(),
+        PixelBuffer::F32(data) => data.iter().copied().enumerate().map(encode).collect(),
+    }
+}
+
+/// 8-bit values normalized to 0..1
+fn u8_to_f32(data: &[u8]) -> Vec<f32> {
+    data.iter().map(|&v| v as f32 / 255.0).collect()
+}
+
+/// Pixel buffer as straight RGBA f32 (u8 normalized to 0..1)
+fn to_f32_vec(buffer: &PixelBuffer) -> Vec<f32> {
+    match buffer {
+        PixelBuffer::U8(data) => u8_to_f32(data),
+        PixelBuffer::F16(data) => data.iter().map(|v| v.to_f32()).collect(),
+        PixelBuffer::F32(data) => data.clone(),
+    }
+}
+
+/// Pixel indices of `rect` in a `width`-wide RGBA buffer, row by row
+fn rect_pixels(rect: PixelRect, width: usize) -> impl Iterator<Item = (usize, usize, usize)> {
+    (rect.y..rect.bottom())
+        .flat_map(move |y| (rect.x..rect.right()).map(move |x| (x, y, y * width + x)))
+}
+
+/// Whether any pixel of `rect` has alpha below 1 (the checkerboard would show
+/// through)
+fn has_transparency(buffer: &PixelBuffer, width: usize, rect: PixelRect) -> bool {
+    let alpha = |i: usize| match buffer {
+        PixelBuffer::U8(data) => data[i * 4 + 3] < 255,
+        PixelBuffer::F16(data) => data[i * 4 + 3].to_f32() < 1.0,
+        PixelBuffer::F32(data) => data[i * 4 + 3] < 1.0,
+    };
+    rect_pixels(rect, width).any(|(_, _, i)| alpha(i))
+}
+
+/// Checker value at pixel (x, y), display-referred
+fn checker_value(x: usize, y: usize) -> f32 {
+    if ((x / CHECKER_SIZE) + (y / CHECKER_SIZE)) % 2 == 0 {
+        CHECKER_LIGHT
+    } else {
+        CHECKER_DARK
+    }
+}
+
+/// Composite one straight-alpha RGBA pixel at (x, y) over the checkerboard
+/// (opaque result).
+fn checker_over(px: &mut [f32], x: usize, y: usize, hdr: bool) {
+    let a = px[3].clamp(0.0, 1.0);
+    let mut k = checker_value(x, y);
+    if hdr {
+        k = k.powf(DISPLAY_GAMMA);
+    }
+    for c in &mut px[..3] {
+        *c = *c * a + k * (1.0 - a);
+    }
+    px[3] = 1.0;
+}
+
+/// Composite the pixels of `rect` over the checkerboard.
+fn composite_checker(data: &mut [f32], width: usize, rect: PixelRect, hdr: bool) {
+    for (x, y, i) in rect_pixels(rect, width) {
+        let px = &mut data[i * 4..i * 4 + 4];
+        if px[3] < 1.0 {
+            checker_over(px, x, y, hdr);
+        }
+    }
+}
+
+/// Staged bytes for a checker-only pass: the native buffer with the pixels of
+/// `rect` composited in place, without a float copy of the whole frame.
+fn checker_native(buffer: &PixelBuffer, width: usize, rect: PixelRect) -> Vec<u8> {
+    match buffer {
+        PixelBuffer::U8(data) => {
+            let mut data = data.clone();
+            for (x, y, i) in rect_pixels(rect, width) {
+                let px = &mut data[i * 4..i * 4 + 4];
+                let a = px[3] as u32;
+                if a == 255 {
+                    continue;
+                }
+                let k = (checker_value(x, y) * 255.0).round() as u32;
+                for c in &mut px[..3] {
+                    *c = ((*c as u32 * a + k * (255 - a) + 127) / 255) as u8;
+                }
+                px[3] = 255;
+            }
+            data
+        }
+        PixelBuffer::F16(data) => {
+            let mut bits: Vec<u16> = data.iter().map(|x| x.to_bits()).collect();
+            for (x, y, i) in rect_pixels(rect, width) {
+                let src = &data[i * 4..i * 4 + 4];
+                if src[3].to_f32() >= 1.0 {
+                    continue;
+                }
+                let mut px: [f32; 4] = std::array::from_fn(|c| src[c].to_f32());
+                checker_over(&mut px, x, y, true);
+                for (b, v) in bits[i * 4..i * 4 + 4].iter_mut().zip(px) {
+                    *b = half::f16::from_f32(v).to_bits();
+                }
+            }
+            bytemuck::cast_slice(&bits).to_vec()
+        }
+        PixelBuffer::F32(data) => {
+            let mut data = data.clone();
+            composite_checker(&mut data, width, rect, true);
+            bytemuck::cast_slice(data.as_slice()).to_vec()
+        }
+    }
+}
+
+/// Composite a tinted ghost over straight-alpha RGBA pixels ("over").
+fn composite_ghost(data: &mut [f32], ghost: &[f32], tint: [f32; 3], opacity: f32, hdr: bool) {
+    let tint = if hdr {
+        tint.map(|t| t.powf(DISPLAY_GAMMA))
+    } else {
+        tint
+    };
+    for (px, g) in data.chunks_exact_mut(4).zip(ghost.chunks_exact(4)) {
+        let ga = g[3].clamp(0.0, 1.0) * opacity;
+        if ga <= 0.0 {
+            continue;
+        }
+        let luma = 0.2126 * g[0] + 0.7152 * g[1] + 0.0722 * g[2];
+        let a = px[3].clamp(0.0, 1.0);
+        let out_a = ga + a * (1.0 - ga);
+        for (c, t) in px[..3].iter_mut().zip(tint) {
+            *c = (luma * t * ga + *c * a * (1.0 - ga)) / out_a;
+        }
+        px[3] = out_a;
+    }
+}
+
 /// Stage the current frame: pack the pixel buffer to interleaved RGBA bytes
 /// (u8 as-is, f16 via `to_bits`, f32 via cast) and hand it to the presenter
 /// together with the quad transform.
+///
+/// `options` are applied to the staged copy only (the frame itself is
+/// untouched): CPU tonemap (HDR only, staged as 8-bit display values and
+/// reused from `tonemap_cache` while the frame stays the same), onion-skin
+/// ghosts, the alpha checkerboard, then zebra stripes (classified on the
+/// source values). HDR buffers get tints and checker values in linear so they
+/// display at the same levels after gamma. The checker only covers
+/// `options.checker`'s rect and, on its own, is composited in the native
+/// format instead of through a float copy of the frame.
+///
+/// Returns the clipped-pixel shares when `options.zebra` is set.
 pub fn stage_frame(
     hdr: &mut HdrView,
     rs: &ViewportRenderState,
     width: usize,
     height: usize,
-    pixel_buffer: &PixelBuffer,
+    pixel_buffer: &Arc<PixelBuffer>,
     pixel_format: PixelFormat,
-) {
+    options: &StageOptions,
+    tonemap_cache: &mut TonemapCache,
+) -> Option<ZebraStats> {
+    // Only the visible part gets the checker, and an opaque one looks the same
+    // without it: skip the pass unless something there shows through.
+    // 8-bit frames are display-referred already: no tonemap.
+    let options = &StageOptions {
+        checker: options
+            .checker
+            .and_then(|rect| rect.clamp_to(width, height))
+            .filter(|&rect| has_transparency(pixel_buffer, width, rect)),
+        tonemap: options
+            .tonemap
+            .filter(|_| pixel_format != PixelFormat::Rgba8),
+        ..*options
+    };
+    let tonemap = options.tonemap;
+    // CPU-tonemapped frames are display-referred 8-bit from here on
+    let pixel_format = if tonemap.is_some() {
+        PixelFormat::Rgba8
+    } else {
+        pixel_format
+    };
     let format = match pixel_format {
         PixelFormat::Rgba8 => HdrFormat::Rgba8,
         PixelFormat::RgbaF16 => HdrFormat::Rgba16F,
         PixelFormat::RgbaF32 => HdrFormat::Rgba32F,
     };
-    let bytes = match pixel_buffer {
-        PixelBuffer::U8(data) => data.clone(),
-        PixelBuffer::F16(data) => {
-            let bits: Vec<u16> = data.iter().map(|x| x.to_bits()).collect();
-            bytemuck::cast_slice(&bits).to_vec()
+    let exposure = hdr.exposure;
+    let mut zebra_stats = None;
+    let bytes = if options.is_noop() {
+        match pixel_buffer.as_ref() {
+            PixelBuffer::U8(data) => data.clone(),
+            PixelBuffer::F16(data) => {
+                let bits: Vec<u16> = data.iter().map(|x| x.to_bits()).collect();
+                bytemuck::cast_slice(&bits).to_vec()
+            }
+            PixelBuffer::F32(data) => bytemuck::cast_slice(data.as_slice()).to_vec(),
+        }
+    } else if let Some(mode) = tonemap
+        && options.ghosts.is_empty()
+        && options.checker.is_none()
+        && options.zebra.is_none()
+    {
+        tonemap_cache.display(pixel_buffer, mode, exposure).to_vec()
+    } else if let Some(rect) = options.checker
+        && tonemap.is_none()
+        && options.ghosts.is_empty()
+        && options.zebra.is_none()
+    {
+        checker_native(pixel_buffer, width, rect)
+    } else {
+        let source = to_f32_vec(pixel_buffer);
+        let zebra_marks = options.zebra.map(|pass| {
+            let (marks, stats) = zebra::classify(&source, &pass);
+            zebra_stats = Some(stats);
+            (marks, pass.phase)
+        });
+        let (mut data, is_hdr) = match tonemap {
+            Some(mode) => (
+                u8_to_f32(tonemap_cache.display(pixel_buffer, mode, exposure)),
+                false,
+            ),
+            None => (source, pixel_format != PixelFormat::Rgba8),
+        };
+        for ghost in options.ghosts {
+            if std::mem::discriminant(ghost.buffer.as_ref())
+                != std::mem::discriminant(pixel_buffer.as_ref())
+            {
+                continue;
+            }
+            let g = match tonemap {
+                Some(mode) => u8_to_f32(&tonemap_to_display(&ghost.buffer, mode, exposure)),
+                None => to_f32_vec(&ghost.buffer),
+            };
+            if g.len() == data.len() {
+                composite_ghost(&mut data, &g, ghost.tint, ghost.opacity, is_hdr);
+            }
+        }
+        if let Some(rect) = options.checker {
+            composite_checker(&mut data, width, rect, is_hdr);
+        }
+        if let Some((marks, phase)) = zebra_marks {
+            zebra::paint(&mut data, &marks, width, phase, is_hdr);
+        }
+        match pixel_format {
+            PixelFormat::Rgba8 => data
+                .iter()
+                .map(|v| (v * 255.0).round().clamp(0.0, 255.0) as u8)
+                .collect(),
+            PixelFormat::RgbaF16 => {
+                let bits: Vec<u16> = data
+                    .iter()
+                    .map(|&v| half::f16::from_f32(v).to_bits())
+                    .collect();
+                bytemuck::cast_slice(&bits).to_vec()
+            }
+            PixelFormat::RgbaF32 => bytemuck::cast_slice(data.as_slice()).to_vec(),
         }
-        PixelBuffer::F32(data) => bytemuck::cast_slice(data.as_slice()).to_vec(),
     };
     hdr.stage_frame(format, bytes, width, height, to_mvp(rs));
+    zebra_stats
 }
 
 /// Update the transform without re-uploading pixels (pan/zoom-only frames).
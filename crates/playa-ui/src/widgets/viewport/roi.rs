@@ -0,0 +1,184 @@
+//! Region-of-interest tool - drag a rect to composite and inspect only that area.
+//!
+//! The committed rect lives on the comp ([`CompNode::roi`], comp pixels) so the
+//! compositor clips to it; this module handles the LMB drag, the dimmed surround
+//! and the per-channel stats readout. Like the guides, drawing happens in screen
+//! space over the displayed image and never touches pixels.
+//!
+//! [`CompNode::roi`]: playa_engine::entities::CompNode::roi
+
+use eframe::egui;
+use playa_engine::core::event_bus::BoxedEvent;
+use playa_engine::core::player::Player;
+use playa_engine::entities::Project;
+use playa_engine::entities::comp_events::SetRoiEvent;
+use playa_engine::entities::frame::{Frame, PixelRect, RegionStats};
+
+use super::ViewportState;
+use super::tool::ToolMode;
+
+/// Darkening applied outside the region
+const DIM_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(0, 0, 0, 160);
+/// Region outline / rubber band
+const ROI_COLOR: egui::Color32 = egui::Color32::from_rgb(90, 200, 255);
+/// Stats panel background
+const PANEL_COLOR: egui::Color32 = egui::Color32::from_rgba_premultiplied(0, 0, 0, 190);
+
+/// LMB in ROI mode: drag rubber-bands a rect, release commits it, a plain
+/// click clears the region.
+pub(super) fn drag_event(
+    response: &egui::Response,
+    panel_rect: egui::Rect,
+    viewport_state: &mut ViewportState,
+    player: &Player,
+    project: &Project,
+) -> Option<BoxedEvent> {
+    if ToolMode::from_str(&project.tool()) != ToolMode::Roi {
+        viewport_state.roi_drag = None;
+        return None;
+    }
+    let comp_uuid = player.active_comp()?;
+    let pointer_image = |state: &ViewportState| {
+        let pos = response
+            .interact_pointer_pos()
+            .or_else(|| response.ctx.pointer_latest_pos())?;
+        Some(state.screen_to_image_clamped(pos - panel_rect.min))
+    };
+
+    if response.drag_started_by(egui::PointerButton::Primary) {
+        viewport_state.roi_drag = pointer_image(viewport_state);
+    }
+    if response.drag_stopped_by(egui::PointerButton::Primary)
+        && let Some(anchor) = viewport_state.roi_drag.take()
+    {
+        let end = pointer_image(viewport_state)?;
+        let rect = image_rect(anchor, end)?;
+        return Some(Box::new(SetRoiEvent {
+            comp_uuid,
+            rect: Some([
+                rect.x as u32,
+                rect.y as u32,
+                rect.width as u32,
+                rect.height as u32,
+            ]),
+        }));
+    }
+    if response.clicked_by(egui::PointerButton::Primary) {
+        return Some(Box::new(SetRoiEvent {
+            comp_uuid,
+            rect: None,
+        }));
+    }
+    None
+}
+
+/// Whole-pixel rect spanned by two image-space corners; `None` below 1x1.
+fn image_rect(a: egui::Vec2, b: egui::Vec2) -> Option<PixelRect> {
+    let min = a.min(b).floor();
+    let max = a.max(b).ceil();
+    let (w, h) = ((max.x - min.x) as usize, (max.y - min.y) as usize);
+    (w >= 1 && h >= 1).then(|| PixelRect::new(min.x as usize, min.y as usize, w, h))
+}
+
+/// Refresh the cached region stats when the frame or the region changed.
+pub(super) fn update_stats(
+    viewport_state: &mut ViewportState,
+    frame: &Frame,
+    roi: Option<PixelRect>,
+    frame_changed: bool,
+) {
+    let Some(roi) = roi else {
+        viewport_state.roi_stats = None;
+        return;
+    };
+    let cached = viewport_state.roi_stats.is_some_and(|(r, _)| r == roi);
+    if cached && !frame_changed {
+        return;
+    }
+    // Proxy frames hold 1/N of the comp pixels
+    viewport_state.roi_stats = frame
+        .region_stats(roi.downscale(frame.proxy_factor()))
+        .map(|stats| (roi, stats));
+}
+
+/// Draw the rubber band while dragging, or the committed region: dimmed
+/// surround, outline and the stats panel.
+pub(super) fn draw(
+    ui: &egui::Ui,
+    panel_rect: egui::Rect,
+    viewport_state: &ViewportState,
+    roi: Option<PixelRect>,
+) {
+    let painter = ui.painter().with_clip_rect(panel_rect);
+    let to_screen = |x: f32, y: f32| {
+        (panel_rect.min.to_vec2() + viewport_state.image_to_screen(egui::vec2(x, y))).to_pos2()
+    };
+    let stroke = egui::Stroke::new(1.0, ROI_COLOR);
+
+    if let Some(anchor) = viewport_state.roi_drag {
+        if let Some(pos) = ui.ctx().pointer_latest_pos() {
+            let end = viewport_state.screen_to_image_clamped(pos - panel_rect.min);
+            let band =
+                egui::Rect::from_two_pos(to_screen(anchor.x, anchor.y), to_screen(end.x, end.y));
+            painter.rect_stroke(band, 0.0, stroke, egui::epaint::StrokeKind::Middle);
+        }
+        return;
+    }
+    let Some(roi) = roi else {
+        return;
+    };
+
+    let image = viewport_state
+        .get_image_screen_bounds()
+        .translate(panel_rect.min.to_vec2());
+    // Opposite corners stay opposite under display rotation / flips
+    let region = egui::Rect::from_two_pos(
+        to_screen(roi.x as f32, roi.y as f32),
+        to_screen(roi.right() as f32, roi.bottom() as f32),
+    )
+    .intersect(image);
+    for bar in [
+        egui::Rect::from_min_max(image.min, egui::pos2(image.max.x, region.min.y)),
+        egui::Rect::from_min_max(egui::pos2(image.min.x, region.max.y), image.max),
+        egui::Rect::from_min_max(
+            egui::pos2(image.min.x, region.min.y),
+            egui::pos2(region.min.x, region.max.y),
+        ),
+        egui::Rect::from_min_max(
+            egui::pos2(region.max.x, region.min.y),
+            egui::pos2(image.max.x, region.max.y),
+        ),
+    ] {
+        if bar.width() > 0.0 && bar.height() > 0.0 {
+            painter.rect_filled(bar, 0.0, DIM_COLOR);
+        }
+    }
+    painter.rect_stroke(region, 0.0, stroke, egui::epaint::StrokeKind::Outside);
+
+    if let Some((_, stats)) = &viewport_state.roi_stats {
+        let galley = painter.layout_no_wrap(
+            stats_text(roi, stats),
+            egui::FontId::monospace(11.0),
+            egui::Color32::WHITE,
+        );
+        let pos = region.left_bottom() + egui::vec2(0.0, 4.0);
+        let bg = egui::Rect::from_min_size(pos, galley.size()).expand(3.0);
+        painter.rect_filled(bg, 2.0, PANEL_COLOR);
+        painter.galley(pos, galley, egui::Color32::WHITE);
+    }
+}
+
+/// Stats panel: header plus one `min max mean` row per channel.
+fn stats_text(roi: PixelRect, stats: &RegionStats) -> String {
+    let mut text = format!(
+        "ROI {}x{} @ {},{}  ({} px)\n     min      max      mean",
+        roi.width, roi.height, roi.x, roi.y, stats.pixels
+    );
+    for (c, name) in ["R", "G", "B", "A"].iter().enumerate() {
+        text.push_str(&format!(
+            "\n{}  {:>8.4} {:>8.4} {:>8.4}",
+            name, stats.min[c], stats.max[c], stats.mean[c]
+        ));
+    }
+    text
+}
@@ -194,7 +194,7 @@ impl Shaders {
         Ok(())
     }
 
-    /// Load all embedded shaders (default, reinhard, aces)
+    /// Load all embedded shaders (default, reinhard, aces + CPU-tonemapped presets)
     fn load_embedded_shaders(&mut self) {
         // Default shader (simple exposure + gamma)
         self.shaders.insert(
@@ -217,8 +217,17 @@ impl Shaders {
             (VERTEX_SHADER.to_string(), FRAGMENT_SHADER_ACES.to_string()),
         );
 
+        // Curves without a GPU path: tonemapped on the CPU at upload
+        // (`renderer::cpu_tonemap`), then presented by the passthrough shader
+        for name in ["tonemap_reinhard_ext", "tonemap_hable", "tonemap_clamp"] {
+            self.shaders.insert(
+                name.to_string(),
+                (VERTEX_SHADER.to_string(), FRAGMENT_SHADER.to_string()),
+            );
+        }
+
         self.current_shader = "default".to_string();
-        log::info!("Loaded 3 embedded shaders: default, tonemap_reinhard, tonemap_aces");
+        log::info!("Loaded 6 embedded shaders: default + 5 tonemap presets");
     }
 
     /// Get the current vertex and fragment shaders
@@ -19,9 +19,17 @@
 
 use eframe::egui;
 use log::{info, trace};
+use playa_engine::entities::frame::{PixelRect, RegionStats};
 use playa_engine::entities::space;
+use playa_io::exr_header::ExrWindows;
 
+use super::annotation::AnnotationTool;
 use super::coords;
+use super::delivery::DeliveryFrame;
+use super::guides::ViewportGuides;
+use super::mask::MaskDrag;
+use super::renderer::TonemapCache;
+use super::zebra::Zebra;
 
 /// Scrubber line color when inside image bounds (white, 50% transparent)
 const SCRUB_NORMAL: (f32, f32, f32, f32) = (1.0, 1.0, 1.0, 0.5);
@@ -54,6 +62,95 @@ pub enum ViewportMode {
     Auto100,
 }
 
+/// Onion-skin settings: ghosts of neighbor frames over the current one (paused only)
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct OnionSkin {
+    pub enabled: bool,
+    /// Ghost frames on each side of the current frame
+    pub frames: usize,
+    /// Opacity of the nearest ghost (farther ghosts fade out)
+    pub opacity: f32,
+}
+
+impl Default for OnionSkin {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            frames: 2,
+            opacity: 0.35,
+        }
+    }
+}
+
+impl OnionSkin {
+    /// Previous-frame ghost tint
+    pub const PREV_TINT: [f32; 3] = [1.0, 0.3, 0.3];
+    /// Next-frame ghost tint
+    pub const NEXT_TINT: [f32; 3] = [0.3, 1.0, 0.3];
+
+    /// Onion-skin menu: toggle, ghost count, opacity
+    pub fn menu_ui(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = ui.checkbox(&mut self.enabled, "Onion skin").changed();
+        ui.add_enabled_ui(self.enabled, |ui| {
+            changed |= ui
+                .add(egui::Slider::new(&mut self.frames, 1..=5).text("Frames"))
+                .changed();
+            changed |= ui
+                .add(egui::Slider::new(&mut self.opacity, 0.05..=1.0).text("Opacity"))
+                .changed();
+        });
+        changed
+    }
+}
+
+/// Non-destructive display orientation: flip/flop, then quarter turns clockwise
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct ViewportOrientation {
+    /// Clockwise quarter turns (0..=3)
+    pub rotation: u8,
+    /// Mirror horizontally (flip)
+    pub flip_h: bool,
+    /// Mirror vertically (flop)
+    pub flip_v: bool,
+}
+
+impl ViewportOrientation {
+    /// True if the image is displayed as-is
+    pub fn is_identity(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Rotate a further 90° clockwise
+    pub fn rotate_cw(&mut self) {
+        self.rotation = (self.rotation + 1) % 4;
+    }
+
+    /// True if width and height are swapped on screen
+    pub fn swaps_axes(&self) -> bool {
+        self.rotation % 2 == 1
+    }
+
+    /// Short status-bar label, e.g. "Rot 90 Flip", or None when identity
+    pub fn label(&self) -> Option<String> {
+        if self.is_identity() {
+            return None;
+        }
+        let mut parts = Vec::new();
+        if self.rotation % 4 != 0 {
+            parts.push(format!("Rot {}", u32::from(self.rotation % 4) * 90));
+        }
+        if self.flip_h {
+            parts.push("Flip".to_string());
+        }
+        if self.flip_v {
+            parts.push("Flop".to_string());
+        }
+        Some(parts.join(" "))
+    }
+}
+
 /// Viewport state for pan/zoom
 #[derive(Clone, serde::Deserialize, serde::Serialize)]
 pub struct ViewportState {
@@ -62,6 +159,10 @@ pub struct ViewportState {
     pub mode: ViewportMode,
     #[serde(skip)]
     pub image_size: egui::Vec2,
+    /// Display pixel aspect of the current frame: horizontal stretch applied
+    /// after frame space (anamorphic unsqueeze, pixels untouched)
+    #[serde(skip)]
+    pub pixel_aspect: f32,
     #[serde(skip)]
     pub viewport_size: egui::Vec2,
     #[serde(skip)]
@@ -77,6 +178,48 @@ pub struct ViewportState {
     /// Last rendered frame number (for detecting frame changes)
     #[serde(skip)]
     pub last_rendered_frame: Option<i32>,
+    /// Framing guides overlay (safe areas, center, aspect mask)
+    #[serde(default)]
+    pub guides: ViewportGuides,
+    /// Show a checkerboard behind transparent pixels (display only)
+    #[serde(default)]
+    pub checker: bool,
+    /// Image rect the checkerboard was staged under (`None`: no checker)
+    #[serde(skip)]
+    pub checker_staged: Option<PixelRect>,
+    /// Onion-skin ghosts of neighbor frames
+    #[serde(default)]
+    pub onion: OnionSkin,
+    /// Whether ghosts were included in the last staged upload
+    #[serde(skip)]
+    pub onion_staged: bool,
+    /// CPU-tonemapped copy of the last staged frame
+    #[serde(skip)]
+    pub tonemap_cache: TonemapCache,
+    /// Display rotation / flip / flop (pixels untouched)
+    #[serde(default)]
+    pub orientation: ViewportOrientation,
+    /// ROI tool: image-space anchor of the rect being dragged
+    #[serde(skip)]
+    pub roi_drag: Option<egui::Vec2>,
+    /// ROI tool: stats of the committed region for the displayed frame
+    #[serde(skip)]
+    pub roi_stats: Option<(PixelRect, RegionStats)>,
+    /// Mask tool: drag in progress
+    #[serde(skip)]
+    pub mask_drag: Option<MaskDrag>,
+    /// Annotate tool options and the stroke / note being drawn
+    #[serde(default)]
+    pub annotation: AnnotationTool,
+    /// Over/under exposure zebra toggles and the last clipped-pixel shares
+    #[serde(default)]
+    pub zebra: Zebra,
+    /// Letterbox / pillarbox preview at a delivery aspect
+    #[serde(default)]
+    pub delivery: DeliveryFrame,
+    /// EXR windows of the last source outlined by the data-window guide
+    #[serde(skip)]
+    pub data_window: Option<(std::path::PathBuf, Option<ExrWindows>)>,
 }
 
 /// Render-only viewport state (cheap to copy into GL callbacks).
@@ -94,11 +237,26 @@ impl Default for ViewportState {
             pan: egui::Vec2::ZERO,
             mode: ViewportMode::AutoFit,
             image_size: egui::Vec2::new(1920.0, 1080.0),
+            pixel_aspect: 1.0,
             viewport_size: egui::Vec2::new(1920.0, 1080.0),
             scrubber: ViewportScrubber::new(),
             rmb_tool_drag_active: false,
             last_rendered_epoch: 0,
             last_rendered_frame: None,
+            guides: ViewportGuides::default(),
+            checker: false,
+            checker_staged: None,
+            onion: OnionSkin::default(),
+            onion_staged: false,
+            tonemap_cache: TonemapCache::default(),
+            orientation: ViewportOrientation::default(),
+            roi_drag: None,
+            roi_stats: None,
+            mask_drag: None,
+            annotation: AnnotationTool::default(),
+            zebra: Zebra::default(),
+            delivery: DeliveryFrame::default(),
+            data_window: None,
         }
     }
 }
@@ -121,12 +279,58 @@ impl ViewportState {
         self.last_rendered_frame = None;
     }
 
+    /// Toggle alpha checkerboard; forces a re-upload of the current frame
+    pub fn toggle_checker(&mut self) {
+        self.checker = !self.checker;
+        self.request_refresh();
+    }
+
+    /// Toggle onion-skinning; forces a re-upload of the current frame
+    pub fn toggle_onion(&mut self) {
+        self.onion.enabled = !self.onion.enabled;
+        self.request_refresh();
+    }
+
+    /// Rotate the display 90° clockwise
+    pub fn rotate_cw(&mut self) {
+        self.orientation.rotate_cw();
+        self.refit();
+    }
+
+    /// Mirror the display horizontally
+    pub fn toggle_flip(&mut self) {
+        self.orientation.flip_h = !self.orientation.flip_h;
+    }
+
+    /// Mirror the display vertically
+    pub fn toggle_flop(&mut self) {
+        self.orientation.flip_v = !self.orientation.flip_v;
+    }
+
+    /// Back to upright, unmirrored display
+    pub fn reset_orientation(&mut self) {
+        self.orientation = ViewportOrientation::default();
+        self.refit();
+    }
+
+    /// Re-apply fit if in AutoFit mode (display size changed)
+    pub(super) fn refit(&mut self) {
+        if self.mode == ViewportMode::AutoFit {
+            self.apply_fit();
+        }
+    }
+
     /// Draw all viewport overlays (scrubber, guides, safe zones, etc.)
     pub fn draw(&self, ui: &egui::Ui, panel_rect: egui::Rect) {
         // Draw scrubber line during scrubbing
         self.scrubber.draw(ui, panel_rect);
 
-        // Future: add guides, safe zones, grid, etc.
+        // Framing guides in screen space over the displayed image
+        let image_rect = self
+            .get_image_screen_bounds()
+            .translate(panel_rect.min.to_vec2());
+        self.delivery.draw(ui, image_rect, panel_rect);
+        self.guides.draw(ui, image_rect, panel_rect);
     }
 
     /// Update viewport size (called when window resizes)
@@ -147,6 +351,32 @@ impl ViewportState {
         }
     }
 
+    /// Update display pixel aspect (called when new image loads)
+    pub fn set_pixel_aspect(&mut self, aspect: f32) {
+        self.pixel_aspect = if aspect > 0.0 { aspect } else { 1.0 };
+        if self.mode == ViewportMode::AutoFit {
+            self.apply_fit();
+        }
+    }
+
+    /// Image size as displayed (width stretched by pixel aspect, axes
+    /// swapped by odd quarter turns)
+    pub fn display_size(&self) -> egui::Vec2 {
+        let size = egui::vec2(self.image_size.x * self.pixel_aspect, self.image_size.y);
+        if self.orientation.swaps_axes() {
+            egui::vec2(size.y, size.x)
+        } else {
+            size
+        }
+    }
+
+    /// Frame-space display transform: pixel aspect, then orientation.
+    /// Sits between frame and viewport space in every screen mapping.
+    pub fn display_affine(&self) -> glam::Affine2 {
+        let o = self.orientation;
+        space::display_affine(o.rotation, o.flip_h, o.flip_v, self.pixel_aspect)
+    }
+
     /// Set AutoFit mode and apply fit
     pub fn set_mode_fit(&mut self) {
         info!("Viewport mode: AutoFit");
@@ -161,13 +391,14 @@ impl ViewportState {
         self.apply_100();
     }
 
-    /// Apply fit to window
+    /// Apply fit to window (the whole delivery frame while it is previewed)
     fn apply_fit(&mut self) {
         if self.image_size.x <= 0.0 || self.image_size.y <= 0.0 {
             return;
         }
-        let scale_x = self.viewport_size.x / self.image_size.x;
-        let scale_y = self.viewport_size.y / self.image_size.y;
+        let display = self.delivery.frame_size(self.display_size());
+        let scale_x = self.viewport_size.x / display.x;
+        let scale_y = self.viewport_size.y / display.y;
         self.zoom = scale_x.min(scale_y);
         self.pan = egui::Vec2::ZERO;
     }
@@ -214,9 +445,32 @@ impl ViewportState {
 
     /// Get image bounds in screen space
     pub fn get_image_screen_bounds(&self) -> egui::Rect {
-        let min = self.image_to_screen(egui::vec2(0.0, 0.0));
-        let max = self.image_to_screen(self.image_size);
-        egui::Rect::from_min_max(min.to_pos2(), max.to_pos2())
+        // Opposite corners stay opposite under quarter turns and mirrors
+        let a = self.image_to_screen(egui::vec2(0.0, 0.0));
+        let b = self.image_to_screen(self.image_size);
+        egui::Rect::from_two_pos(a.to_pos2(), b.to_pos2())
+    }
+
+    /// Image-space (buffer) rect currently visible in the viewport, clamped
+    /// to the image.
+    pub fn visible_image_rect(&self) -> egui::Rect {
+        let (w, h) = (self.viewport_size.x, self.viewport_size.y);
+        let corners = [
+            egui::vec2(0.0, 0.0),
+            egui::vec2(w, 0.0),
+            egui::vec2(0.0, h),
+            egui::vec2(w, h),
+        ]
+        .map(|corner| self.screen_to_image_clamped(corner).to_pos2());
+        egui::Rect::from_points(&corners)
+    }
+
+    /// [`Self::visible_image_rect`] in whole pixels (rounded outward)
+    pub fn visible_pixel_rect(&self) -> PixelRect {
+        let r = self.visible_image_rect();
+        let (x0, y0) = (r.min.x.floor() as usize, r.min.y.floor() as usize);
+        let (x1, y1) = (r.max.x.ceil() as usize, r.max.y.ceil() as usize);
+        PixelRect::new(x0, y0, x1.saturating_sub(x0), y1.saturating_sub(y0))
     }
 
     /// Check if screen position is over the image
@@ -228,18 +482,20 @@ impl ViewportState {
     /// Convert buffer-space image coordinates (top-left, Y-down,
     /// 0..image_size) to egui screen space (top-left, Y-down).
     ///
-    /// Chains `image → frame → viewport → screen`, all canonical helpers
+    /// Chains `image → frame → display → viewport → screen`, all canonical helpers
     /// from the `playa-coord` crate (re-exported through
     /// `playa_engine::entities::space`). The Y-flip happens TWICE on purpose
     /// (`image_to_frame` flips into Y-up; `viewport_to_screen` flips back
     /// into Y-down) because pan is stored in viewport Y-up convention.
+    /// The display step applies pixel aspect and rotate/flip/flop.
     pub fn image_to_screen(&self, image_pos: egui::Vec2) -> egui::Vec2 {
         let img_sz = (self.image_size.x as usize, self.image_size.y as usize);
         let pan = glam::Vec2::new(self.pan.x, self.pan.y);
         let vp_sz = glam::Vec2::new(self.viewport_size.x, self.viewport_size.y);
 
         let frame = space::image_to_frame(glam::Vec2::new(image_pos.x, image_pos.y), img_sz);
-        let viewport = space::frame_to_viewport(frame, self.zoom, pan);
+        let display = self.display_affine().transform_point2(frame);
+        let viewport = space::frame_to_viewport(display, self.zoom, pan);
         let screen = space::viewport_to_screen(viewport, vp_sz);
         egui::vec2(screen.x, screen.y)
     }
@@ -249,26 +505,38 @@ impl ViewportState {
     /// Returns `None` if position is outside the image bounds.
     #[allow(dead_code)]
     pub fn screen_to_image(&self, screen_pos: egui::Vec2) -> Option<egui::Vec2> {
-        let img_sz = (self.image_size.x as usize, self.image_size.y as usize);
-        let pan = glam::Vec2::new(self.pan.x, self.pan.y);
-        let vp_sz = glam::Vec2::new(self.viewport_size.x, self.viewport_size.y);
-
-        let viewport =
-            space::screen_to_viewport(glam::Vec2::new(screen_pos.x, screen_pos.y), vp_sz);
-        let frame = space::viewport_to_frame(viewport, self.zoom, pan);
-        let image = space::frame_to_image(frame, img_sz);
-
+        let image = self.screen_to_image_unbounded(screen_pos);
         if image.x >= 0.0
             && image.x <= self.image_size.x
             && image.y >= 0.0
             && image.y <= self.image_size.y
         {
-            Some(egui::vec2(image.x, image.y))
+            Some(image)
         } else {
             None
         }
     }
 
+    /// [`Self::screen_to_image`] clamped onto the image instead of rejecting
+    /// positions outside it (drags that leave the image keep tracking its edge).
+    pub fn screen_to_image_clamped(&self, screen_pos: egui::Vec2) -> egui::Vec2 {
+        self.screen_to_image_unbounded(screen_pos)
+            .clamp(egui::Vec2::ZERO, self.image_size)
+    }
+
+    pub(super) fn screen_to_image_unbounded(&self, screen_pos: egui::Vec2) -> egui::Vec2 {
+        let img_sz = (self.image_size.x as usize, self.image_size.y as usize);
+        let pan = glam::Vec2::new(self.pan.x, self.pan.y);
+        let vp_sz = glam::Vec2::new(self.viewport_size.x, self.viewport_size.y);
+
+        let viewport =
+            space::screen_to_viewport(glam::Vec2::new(screen_pos.x, screen_pos.y), vp_sz);
+        let display = space::viewport_to_frame(viewport, self.zoom, pan);
+        let frame = self.display_affine().inverse().transform_point2(display);
+        let image = space::frame_to_image(frame, img_sz);
+        egui::vec2(image.x, image.y)
+    }
+
     /// High-level scrubbing handler. Returns Some(frame_idx) when scrubbing
     /// requests a new frame, or None if nothing changed.
     /// Maps mouse X from image bounds directly to [play_start, play_end] frame range.
@@ -350,20 +618,18 @@ impl ViewportState {
         }
     }
 
-    /// Get model matrix for shader (scales normalized quad to image pixel size).
+    /// Get model matrix for shader (scales normalized quad to image pixel
+    /// size, then applies pixel aspect and rotate/flip/flop — i.e. the quad's
+    /// texture coordinates are reoriented, pixels are never touched).
     pub fn get_model_matrix(&self) -> [[f32; 4]; 4] {
-        [
-            [self.image_size.x, 0.0, 0.0, 0.0],
-            [0.0, self.image_size.y, 0.0, 0.0],
-            [0.0, 0.0, 1.0, 0.0],
-            [0.0, 0.0, 0.0, 1.0],
-        ]
+        let size = glam::Affine2::from_scale(glam::Vec2::new(self.image_size.x, self.image_size.y));
+        space::affine2_to_mat4(self.display_affine() * size).to_cols_array_2d()
     }
 
     /// Get view matrix for shader (zoom + pan only, matches gizmo).
     pub fn get_view_matrix(&self) -> [[f32; 4]; 4] {
         // View = zoom + pan (same as gizmo uses)
-        // This keeps renderer and gizmo in sync
+        // This keeps renderer and gizmo in sync. Display orientation lives in the model matrix.
         [
             [self.zoom, 0.0, 0.0, 0.0],
             [0.0, self.zoom, 0.0, 0.0],
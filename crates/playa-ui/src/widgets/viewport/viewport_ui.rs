@@ -9,7 +9,10 @@ use super::gizmo::GizmoState;
 use super::pick;
 use super::shaders::Shaders;
 use super::tool::ToolMode;
-use super::{ViewportPaintCallback, ViewportRenderer, ViewportState};
+use super::{
+    GridStyle, OnionGhost, OnionSkin, StageOptions, ViewportPaintCallback, ViewportRenderer,
+    ViewportState, ZebraThresholds,
+};
 use crate::widgets::actions::ActionQueue;
 use crate::widgets::file_dialogs::create_media_dialog;
 use playa_engine::core::event_bus::BoxedEvent;
@@ -34,13 +37,15 @@ pub fn render(
     shader_manager: &mut Shaders,
     gizmo_state: &mut GizmoState,
     show_help: bool,
-    is_fullscreen: bool,
     texture_needs_upload: bool,
     viewport_hover_highlight: bool,
     tools_selection_highlight: bool,
     hover_stroke_width: f32,
     hover_corner_length: f32,
     hover_opacity: f32,
+    background: [u8; 3],
+    grid: GridStyle,
+    zebra: ZebraThresholds,
 ) -> (ViewportActions, f32) {
     let mut actions = ViewportActions::default();
     let mut render_time_ms = 0.0;
@@ -48,10 +53,7 @@ pub fn render(
 
     let ctx = ui.ctx().clone();
     let panel_rect = ui.max_rect();
-    if is_fullscreen {
-        ui.painter()
-            .rect_filled(panel_rect, 0.0, egui::Color32::BLACK);
-    }
+    super::paint_background(ui.painter(), panel_rect, background);
 
     let response = ui.interact(
         panel_rect,
@@ -90,10 +92,17 @@ pub fn render(
         if viewport_state.viewport_size != available_size {
             viewport_state.set_viewport_size(available_size);
         }
-        let image_size = egui::vec2(w as f32, h as f32);
+        // Proxy frames are smaller buffers; keep the logical (full-res) image size
+        // so zoom, gizmo and picking stay in comp pixels.
+        let proxy = img.proxy_factor() as f32;
+        let image_size = egui::vec2(w as f32 * proxy, h as f32 * proxy);
         if viewport_state.image_size != image_size {
             viewport_state.set_image_size(image_size);
         }
+        let pixel_aspect = img.pixel_aspect();
+        if viewport_state.pixel_aspect != pixel_aspect {
+            viewport_state.set_pixel_aspect(pixel_aspect);
+        }
 
         handle_viewport_input(&ctx, ui, panel_rect, viewport_state, response.hovered());
 
@@ -103,6 +112,37 @@ pub fn render(
         let renderer_arc = viewport_renderer.clone();
         let render_state = viewport_state.render_state();
         let mut needs_upload = texture_needs_upload;
+        // Onion-skin only while paused; re-stage when that flips
+        let onion_active = viewport_state.onion.enabled && !player.is_playing();
+        if onion_active != viewport_state.onion_staged {
+            needs_upload = true;
+        }
+        // The checker covers the visible part only: re-stage once the view
+        // shows pixels outside the rect it was staged under
+        let checker = viewport_state
+            .checker
+            .then(|| viewport_state.visible_pixel_rect());
+        let checker_covered = match (checker, viewport_state.checker_staged) {
+            (None, None) => true,
+            (Some(view), Some(staged)) => {
+                staged.x <= view.x
+                    && staged.y <= view.y
+                    && staged.right() >= view.right()
+                    && staged.bottom() >= view.bottom()
+            }
+            _ => false,
+        };
+        if !checker_covered {
+            needs_upload = true;
+        }
+        // Zebra stripes crawl: re-stage on each step while a warning is on
+        let zebra_pass = viewport_state.zebra.pass(zebra, ctx.input(|i| i.time));
+        if zebra_pass != viewport_state.zebra.staged {
+            needs_upload = true;
+        }
+        if zebra_pass.is_some() {
+            ctx.request_repaint_after(super::zebra::CRAWL_INTERVAL);
+        }
         {
             let r = renderer_arc.lock().unwrap();
             if r.needs_texture_update(w, h) {
@@ -114,7 +154,30 @@ pub fn render(
             super::update_tonemap(&mut r, shader_manager);
             if needs_upload {
                 let buf = img.buffer();
-                super::stage_frame(&mut r, &render_state, w, h, buf.as_ref(), img.pixel_format());
+                let ghosts = if onion_active {
+                    onion_ghosts(player, project, &viewport_state.onion)
+                } else {
+                    Vec::new()
+                };
+                let options = StageOptions {
+                    checker,
+                    ghosts: &ghosts,
+                    tonemap: super::cpu_tonemap(shader_manager),
+                    zebra: zebra_pass,
+                };
+                viewport_state.zebra.clipped = super::stage_frame(
+                    &mut r,
+                    &render_state,
+                    w,
+                    h,
+                    &buf,
+                    img.pixel_format(),
+                    &options,
+                    &mut viewport_state.tonemap_cache,
+                );
+                viewport_state.onion_staged = onion_active;
+                viewport_state.checker_staged = checker;
+                viewport_state.zebra.staged = zebra_pass;
             } else {
                 super::skip_upload(&mut r, &render_state);
             }
@@ -130,6 +193,13 @@ pub fn render(
 
         render_time_ms = render_start.elapsed().as_secs_f32() * 1000.0;
 
+        // Committed region of interest (comp pixels) and its stats for this frame
+        let roi = player
+            .active_comp()
+            .and_then(|uuid| project.with_comp(uuid, |comp| comp.roi()))
+            .flatten();
+        super::roi::update_stats(viewport_state, img, roi, needs_upload);
+
         // Render gizmo for transform manipulation (Move/Rotate/Scale tools)
         // (must be after raster callback so it stays visible.)
         let (_gizmo_consumed, gizmo_events) =
@@ -158,6 +228,41 @@ pub fn render(
             actions.events.push(evt);
         }
 
+        // LMB drag in ROI mode: draw / clear the region of interest
+        if let Some(evt) =
+            super::roi::drag_event(&response, panel_rect, viewport_state, player, project)
+        {
+            actions.events.push(evt);
+            ctx.request_repaint();
+        }
+        if viewport_state.roi_drag.is_some() {
+            ctx.request_repaint();
+        }
+
+        // LMB in Mask mode: draw / reshape / remove the selected layer's mask
+        if let Some(evt) =
+            super::mask::drag_event(&response, panel_rect, viewport_state, player, project)
+        {
+            actions.events.push(evt);
+            ctx.request_repaint();
+        }
+        if viewport_state.mask_drag.is_some() {
+            ctx.request_repaint();
+        }
+
+        // LMB in Annotate mode: freehand / arrow strokes, text note placement
+        if let Some(evt) =
+            super::annotation::input_event(&response, panel_rect, viewport_state, player, project)
+        {
+            actions.events.push(evt);
+            ctx.request_repaint();
+        }
+        if let Some(evt) =
+            super::annotation::text_entry(&ctx, panel_rect, viewport_state, player, project)
+        {
+            actions.events.push(evt);
+        }
+
         // Hover/selection highlight: update hovered_layer based on tool mode
         if let Some(evt) = hover_layer_event(
             &ctx,
@@ -205,12 +310,32 @@ pub fn render(
 
         // Draw viewport overlays (scrubber, guides, etc.)
         viewport_state.draw(ui, panel_rect);
+        super::grid::draw(ui, panel_rect, viewport_state, &grid);
+        super::roi::draw(ui, panel_rect, viewport_state, roi);
+        if viewport_state.guides.data_window {
+            let path = player.active_comp().and_then(|uuid| {
+                let frame = player.current_frame(project);
+                let media = project.media.read().expect("media lock poisoned");
+                media.get(&uuid)?.as_comp()?.source_path_at(frame, &media)
+            });
+            super::guides::draw_data_window(ui, panel_rect, viewport_state, path.as_deref());
+        }
+        let annotations = player
+            .active_comp()
+            .and_then(|uuid| {
+                let frame = player.current_frame(project);
+                project.with_comp(uuid, |comp| comp.annotations_at(frame))
+            })
+            .unwrap_or_default();
+        super::annotation::draw(ui, panel_rect, viewport_state, &annotations);
+        super::mask::draw(ui, panel_rect, viewport_state, player, project);
 
         // Draw hover/selection highlight
         let tool = ToolMode::from_str(&project.tool());
         let show_highlight = match tool {
             ToolMode::Select => viewport_hover_highlight,
             ToolMode::Move | ToolMode::Rotate | ToolMode::Scale => tools_selection_highlight,
+            ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask => false,
         };
         if show_highlight {
             draw_hover_highlight(
@@ -231,10 +356,19 @@ pub fn render(
         render_help_overlay(ui, panel_rect);
     }
 
-    // Shader selector overlay (top-right corner)
+    // Annotate tool options (top-left corner)
+    actions.events.extend(super::annotation::toolbar(
+        &ctx,
+        panel_rect,
+        viewport_state,
+        player,
+        project,
+    ));
+
+    // Shader selector + guides menu overlay (top-right corner)
     egui::Area::new(ui.id().with("shader_overlay"))
         .fixed_pos(egui::pos2(
-            panel_rect.max.x - 200.0,
+            panel_rect.max.x - 560.0,
             panel_rect.min.y + 10.0,
         ))
         .show(&ctx, |ui| {
@@ -251,6 +385,34 @@ pub fn render(
                             );
                         }
                     });
+                ui.menu_button("Views", |ui| {
+                    let events = super::bookmarks::menu_ui(ui, viewport_state, project);
+                    actions.events.extend(events);
+                });
+                ui.menu_button("Guides", |ui| viewport_state.guides.menu_ui(ui));
+                ui.menu_button("Delivery", |ui| {
+                    if viewport_state.delivery.menu_ui(ui) {
+                        viewport_state.refit();
+                    }
+                })
+                .response
+                .on_hover_text("Letterbox / pillarbox preview at an output aspect");
+                ui.menu_button("Onion", |ui| {
+                    if viewport_state.onion.menu_ui(ui) {
+                        viewport_state.request_refresh();
+                    }
+                });
+                ui.menu_button("Zebra", |ui| viewport_state.zebra.menu_ui(ui))
+                    .response
+                    .on_hover_text("Over/under exposure warning stripes");
+                let mut checker = viewport_state.checker;
+                if ui
+                    .toggle_value(&mut checker, "Alpha")
+                    .on_hover_text("Checkerboard behind transparent pixels (C)")
+                    .clicked()
+                {
+                    viewport_state.toggle_checker();
+                }
             });
         });
 
@@ -259,6 +421,8 @@ pub fn render(
         && let Ok(mut renderer) = viewport_renderer.lock()
     {
         super::update_tonemap(&mut renderer, shader_manager);
+        // CPU-tonemapped presets bake into the staged texture
+        viewport_state.request_refresh();
     }
 
     // Track hover state for input routing
@@ -267,6 +431,37 @@ pub fn render(
     (actions, render_time_ms)
 }
 
+/// Cached neighbor frames for onion-skinning, farthest first so nearer ghosts
+/// draw on top. Cache-only (`get_frame` non-blocking): never triggers compute.
+fn onion_ghosts(player: &Player, project: &Project, onion: &OnionSkin) -> Vec<OnionGhost> {
+    let Some(comp_uuid) = player.active_comp() else {
+        return Vec::new();
+    };
+    let Some(current) = project.with_comp(comp_uuid, |c| c.frame()) else {
+        return Vec::new();
+    };
+    let count = onion.frames.max(1) as i32;
+    let mut ghosts = Vec::new();
+    for k in (1..=count).rev() {
+        let opacity = onion.opacity * (1.0 - (k - 1) as f32 / count as f32);
+        for (offset, tint) in [(-k, OnionSkin::PREV_TINT), (k, OnionSkin::NEXT_TINT)] {
+            let frame = project
+                .with_comp(comp_uuid, |c| c.get_frame(current + offset, project, false))
+                .flatten();
+            if let Some(frame) = frame
+                && frame.status() == FrameStatus::Loaded
+            {
+                ghosts.push(OnionGhost {
+                    buffer: frame.buffer(),
+                    tint,
+                    opacity,
+                });
+            }
+        }
+    }
+    ghosts
+}
+
 fn handle_viewport_input(
     ctx: &egui::Context,
     _ui: &egui::Ui,
@@ -303,7 +498,7 @@ fn handle_viewport_input(
 }
 
 /// RMB drag handler for all tools:
-/// - Select (Q): timeline scrubbing
+/// - Select (Q) / ROI (D): timeline scrubbing
 /// - Move (W): translate layer
 /// - Rotate (E): rotate layer Z
 /// - Scale (R): uniform scale layer
@@ -330,7 +525,11 @@ fn right_drag_tool_event(
     if pressed {
         viewport_state.rmb_tool_drag_active = latest_pos.is_some_and(|p| panel_rect.contains(p));
         // Initialize scrubber on press for Select tool
-        if matches!(tool, ToolMode::Select) && viewport_state.rmb_tool_drag_active {
+        if matches!(
+            tool,
+            ToolMode::Select | ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask
+        ) && viewport_state.rmb_tool_drag_active
+        {
             let bounds = viewport_state.get_image_screen_bounds();
             viewport_state
                 .scrubber
@@ -339,7 +538,10 @@ fn right_drag_tool_event(
     }
     if released || !down {
         viewport_state.rmb_tool_drag_active = false;
-        if matches!(tool, ToolMode::Select) {
+        if matches!(
+            tool,
+            ToolMode::Select | ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask
+        ) {
             viewport_state.scrubber.stop_scrubbing();
         }
     }
@@ -348,7 +550,10 @@ fn right_drag_tool_event(
     }
 
     // Select tool: timeline scrubbing
-    if matches!(tool, ToolMode::Select) {
+    if matches!(
+        tool,
+        ToolMode::Select | ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask
+    ) {
         let local_x = latest_pos.map(|p| p.x - panel_rect.min.x)?;
         let comp_uuid = player.active_comp()?;
         let (play_start, play_end) = project
@@ -393,24 +598,29 @@ fn right_drag_tool_event(
         return None;
     }
 
-    // Convert screen-space delta to comp-space pixels (Y-up).
+    // Convert screen-space delta to comp-space pixels (Y-up), undoing the
+    // display orientation so drags follow the pointer on a rotated view.
     let zoom = viewport_state.zoom.max(0.0001);
     let delta_viewport = super::coords::screen_delta_to_viewport(delta);
-    let dx_px = delta_viewport.x / zoom;
-    let dy_px = delta_viewport.y / zoom;
+    let delta_frame = super::coords::viewport_delta_to_frame(
+        delta_viewport,
+        zoom,
+        viewport_state.display_affine(),
+    );
+    let (dx_px, dy_px) = (delta_frame.x, delta_frame.y);
 
     // Rotate/scale sensitivity: normalized by viewport size so it feels stable across resolutions.
     let min_dim = panel_rect.width().min(panel_rect.height()).max(1.0);
 
+    // Start from the evaluated transform so keyed layers drag from where they are
+    let frame = player.current_frame(project);
     let mut updates = Vec::new();
     project.with_comp(comp_uuid, |comp| {
         for layer_uuid in &selected {
             let Some(layer) = comp.get_layer(*layer_uuid) else {
                 continue;
             };
-            let mut pos = layer.attrs.get_vec3("position").unwrap_or([0.0, 0.0, 0.0]);
-            let mut rot = layer.attrs.get_vec3("rotation").unwrap_or([0.0, 0.0, 0.0]);
-            let mut scale = layer.attrs.get_vec3("scale").unwrap_or([1.0, 1.0, 1.0]);
+            let (mut pos, mut rot, mut scale) = layer.transform_at(frame);
 
             match tool {
                 ToolMode::Move => {
@@ -431,7 +641,7 @@ fn right_drag_tool_event(
                     scale[0] = (scale[0] * factor).clamp(0.001, 1000.0);
                     scale[1] = (scale[1] * factor).clamp(0.001, 1000.0);
                 }
-                ToolMode::Select => {} // handled above
+                ToolMode::Select | ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask => {} // handled above
             }
 
             updates.push((*layer_uuid, pos, rot, scale));
@@ -443,7 +653,12 @@ fn right_drag_tool_event(
     }
 
     Some(Box::new(
-        playa_engine::entities::comp_events::SetLayerTransformsEvent { comp_uuid, updates },
+        playa_engine::entities::comp_events::SetLayerTransformsEvent {
+            comp_uuid,
+            frame,
+            updates,
+            record_keys: Vec::new(),
+        },
     ))
 }
 
@@ -639,15 +854,14 @@ fn draw_hover_highlight(
             let layer_uuids: Vec<uuid::Uuid> = match tool {
                 ToolMode::Select => comp.hovered_layer.into_iter().collect(),
                 ToolMode::Move | ToolMode::Rotate | ToolMode::Scale => comp.layer_selection.clone(),
+                ToolMode::Roi | ToolMode::Annotate | ToolMode::Mask => Vec::new(),
             };
 
             layer_uuids
                 .into_iter()
                 .filter_map(|uuid| {
                     let layer = comp.get_layer(uuid)?;
-                    let pos = layer.attrs.get_vec3("position").unwrap_or([0.0, 0.0, 0.0]);
-                    let rot = layer.attrs.get_vec3("rotation").unwrap_or([0.0, 0.0, 0.0]);
-                    let scl = layer.attrs.get_vec3("scale").unwrap_or([1.0, 1.0, 1.0]);
+                    let (pos, rot, scl) = layer.transform_at(frame_idx);
                     let w = layer.attrs.get_u32("width").unwrap_or(100) as f32;
                     let h = layer.attrs.get_u32("height").unwrap_or(100) as f32;
                     Some((pos, rot, scl, w, h))
@@ -693,7 +907,7 @@ fn draw_hover_highlight(
             // Project to screen based on whether camera is active
             let screen_pos = if let Some(vp) = camera_vp {
                 // 3D mode: project through camera VP, then chain
-                // ndc → frame → viewport → screen via canonical helpers.
+                // ndc → frame → display → viewport → screen via canonical helpers.
                 let clip = vp * Vec4::new(world_pt.x, world_pt.y, world_pt.z, 1.0);
                 if clip.w.abs() < 1e-6 {
                     continue; // Behind camera
@@ -709,7 +923,8 @@ fn draw_hover_highlight(
                     Vec2::new(viewport_state.viewport_size.x, viewport_state.viewport_size.y);
 
                 let frame = space::ndc_to_frame(Vec2::new(ndc.x, ndc.y), comp_size);
-                let viewport = space::frame_to_viewport(frame, viewport_state.zoom, pan);
+                let display = viewport_state.display_affine().transform_point2(frame);
+                let viewport = space::frame_to_viewport(display, viewport_state.zoom, pan);
                 let screen = space::viewport_to_screen(viewport, vp_sz);
 
                 panel_rect.left_top() + egui::vec2(screen.x, screen.y)
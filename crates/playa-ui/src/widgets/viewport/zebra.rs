@@ -0,0 +1,168 @@
+//! Exposure "zebra" warnings - crawling diagonal stripes over pixels at or
+//! below the shadow threshold or at or above the highlight threshold.
+//!
+//! Display-only like the checkerboard and onion ghosts: stripes are painted
+//! into the staged copy of the frame (see [`StageOptions`](super::StageOptions)),
+//! never into the frame or the cache. Thresholds compare the frame's own
+//! values before view exposure / tonemap, so HDR frames can take a highlight
+//! threshold above 1.0. The crawl re-stages the frame every
+//! [`CRAWL_INTERVAL`] while a warning is on.
+
+use std::time::Duration;
+
+use eframe::egui;
+
+use super::renderer::DISPLAY_GAMMA;
+
+/// Stripe width along the diagonal, in image pixels
+const STRIPE_WIDTH: usize = 6;
+/// Time between one-pixel crawl steps
+pub const CRAWL_INTERVAL: Duration = Duration::from_millis(125);
+/// Stripe colors (display-referred RGB)
+const HIGHLIGHT_STRIPE: [f32; 3] = [1.0, 0.0, 0.0];
+const SHADOW_STRIPE: [f32; 3] = [0.0, 0.35, 1.0];
+
+/// Pixel marks produced by [`classify`]
+const MARK_SHADOW: u8 = 1;
+const MARK_HIGHLIGHT: u8 = 2;
+
+/// Warning levels, set in prefs. Compared against the largest RGB channel:
+/// a highlight is any channel clipped, a shadow all channels crushed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZebraThresholds {
+    pub shadow: f32,
+    pub highlight: f32,
+}
+
+impl Default for ZebraThresholds {
+    fn default() -> Self {
+        Self {
+            shadow: 0.02,
+            highlight: 1.0,
+        }
+    }
+}
+
+/// Share of the last staged frame caught by each warning, in percent
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ZebraStats {
+    pub shadows: Option<f32>,
+    pub highlights: Option<f32>,
+}
+
+impl ZebraStats {
+    /// Status bar readout, e.g. `Zebra ▲ 2.4% ▼ 0.1%`
+    pub fn status_text(&self) -> String {
+        let mut text = "Zebra".to_string();
+        if let Some(p) = self.highlights {
+            text.push_str(&format!(" ▲ {:.1}%", p));
+        }
+        if let Some(p) = self.shadows {
+            text.push_str(&format!(" ▼ {:.1}%", p));
+        }
+        text
+    }
+}
+
+/// One staging pass: active thresholds plus the crawl phase
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZebraPass {
+    pub shadow: Option<f32>,
+    pub highlight: Option<f32>,
+    pub phase: usize,
+}
+
+/// Viewport zebra toggles (persisted with [`ViewportState`](super::ViewportState))
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Zebra {
+    /// Stripe pixels at or below the shadow threshold
+    pub shadows: bool,
+    /// Stripe pixels at or above the highlight threshold
+    pub highlights: bool,
+    /// Pass used for the last staged upload (`None` = staged without stripes)
+    #[serde(skip)]
+    pub(super) staged: Option<ZebraPass>,
+    /// Clipped-pixel shares of the last staged frame
+    #[serde(skip)]
+    pub clipped: Option<ZebraStats>,
+}
+
+impl Zebra {
+    pub fn enabled(&self) -> bool {
+        self.shadows || self.highlights
+    }
+
+    /// Pass for the current time, `None` while both warnings are off
+    pub(super) fn pass(&self, thresholds: ZebraThresholds, time: f64) -> Option<ZebraPass> {
+        self.enabled().then(|| ZebraPass {
+            shadow: self.shadows.then_some(thresholds.shadow),
+            highlight: self.highlights.then_some(thresholds.highlight),
+            phase: (time / CRAWL_INTERVAL.as_secs_f64()) as usize % (STRIPE_WIDTH * 2),
+        })
+    }
+
+    /// Zebra menu: highlight / shadow toggles. No refresh needed: a changed
+    /// [`ZebraPass`] re-stages the frame by itself.
+    pub fn menu_ui(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.highlights, "Highlights");
+        ui.checkbox(&mut self.shadows, "Shadows");
+        ui.weak("Thresholds: Preferences → UI");
+    }
+}
+
+/// Mark warned pixels of straight-alpha RGBA data and count them.
+///
+/// Runs on the source values, before tonemap / ghosts / checker.
+pub(super) fn classify(data: &[f32], pass: &ZebraPass) -> (Vec<u8>, ZebraStats) {
+    let (mut shadows, mut highlights) = (0usize, 0usize);
+    let marks: Vec<u8> = data
+        .chunks_exact(4)
+        .map(|px| {
+            let peak = px[0].max(px[1]).max(px[2]);
+            if pass.highlight.is_some_and(|t| peak >= t) {
+                highlights += 1;
+                MARK_HIGHLIGHT
+            } else if pass.shadow.is_some_and(|t| peak <= t) {
+                shadows += 1;
+                MARK_SHADOW
+            } else {
+                0
+            }
+        })
+        .collect();
+    let percent = |n: usize| n as f32 * 100.0 / marks.len().max(1) as f32;
+    let stats = ZebraStats {
+        shadows: pass.shadow.map(|_| percent(shadows)),
+        highlights: pass.highlight.map(|_| percent(highlights)),
+    };
+    (marks, stats)
+}
+
+/// Paint diagonal stripes over marked pixels (opaque, so they read over the
+/// checkerboard too). HDR data gets linear stripe colors.
+pub(super) fn paint(data: &mut [f32], marks: &[u8], width: usize, phase: usize, hdr: bool) {
+    if width == 0 {
+        return;
+    }
+    let linear = |c: [f32; 3]| {
+        if hdr {
+            c.map(|v| v.powf(DISPLAY_GAMMA))
+        } else {
+            c
+        }
+    };
+    let (highlight, shadow) = (linear(HIGHLIGHT_STRIPE), linear(SHADOW_STRIPE));
+    for (i, (px, mark)) in data.chunks_exact_mut(4).zip(marks).enumerate() {
+        let color = match *mark {
+            MARK_HIGHLIGHT => highlight,
+            MARK_SHADOW => shadow,
+            _ => continue,
+        };
+        let (x, y) = (i % width, i / width);
+        if (x + y + phase) / STRIPE_WIDTH % 2 == 0 {
+            px[..3].copy_from_slice(&color);
+            px[3] = 1.0;
+        }
+    }
+}
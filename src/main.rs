@@ -29,7 +29,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         || args.range.is_some()
         || args.log_file.is_some()
         || args.verbosity > 0
-        || args.config_dir.is_some();
+        || args.config_dir.is_some()
+        || args.headless
+        || args.verify_hashes.is_some();
 
     if !has_any_args {
         // Print help in GUI mode (no CLI arguments provided)